@@ -0,0 +1,607 @@
+//! Admin endpoints for test fixture setup.
+//!
+//! `GET /admin/flags` reads the runtime flags and `PATCH /admin/flags`
+//! changes them, notifying `test://meta/flags` subscribers.
+//!
+//! `GET /admin/sessions` lists live sessions and
+//! `DELETE /admin/sessions/{id}` force-terminates one.
+//! `GET /admin/sessions/events` reports session lifecycle events (see
+//! [`crate::sessions`]).
+//! `GET /admin/conformance/{session}` reports which protocol features a
+//! session's client exercised (see [`crate::conformance`]).
+//! `POST /admin/sessions/{id}/flood` blasts notifications at a session for a
+//! while, to stress the client's notification queue and UI throttling.
+//! `GET /admin/pings` reports how sessions answered server-initiated pings
+//! (see [`crate::pings`]).
+//! `GET /admin/subscriptions` lists every session's resource subscriptions
+//! (see [`crate::resources::subscriptions`]).
+//!
+//! `POST /admin/tools/batch` runs a list of tool calls server-side, in order,
+//! against the same server state the `/mcp` endpoint uses. The calls go
+//! through the full MCP request handler over an in-memory transport, so they
+//! behave exactly like client calls. They never touch the HTTP transport,
+//! though, so setup does not show up in client-side traffic or in the
+//! `/debug/*` reports. A batch naming a `session` runs as that session (its
+//! `Mcp-Session-Id`), so session-scoped state such as the key-value store and
+//! counters is set up for the client that will use it.
+
+use std::time::{Duration, Instant};
+
+use axum::extract::{Path, State};
+use axum::http::request::Parts;
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::Json;
+use rmcp::model::{
+    ErrorCode, GetExtensions, JsonObject, JsonRpcMessage, LoggingLevel,
+    LoggingMessageNotificationParam, ProgressNotificationParam, ResourceUpdatedNotificationParam,
+};
+use rmcp::service::{RxJsonRpcMessage, TxJsonRpcMessage};
+use rmcp::transport::Transport;
+use rmcp::transport::async_rw::AsyncRwTransport;
+use rmcp::{Peer, RoleServer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::conformance::ConformanceReport;
+use crate::pings::PingReport;
+use crate::resources::dynamic_resources::TICKER_URI;
+use crate::resources::meta_resources::FlagsSnapshot;
+use crate::resources::subscriptions::SubscriptionReport;
+use crate::server::McpTestServer;
+use crate::sessions::{SessionEvent, SessionList};
+
+/// Transport buffer between the batch runner and the in-memory server.
+const TRANSPORT_BUFFER_BYTES: usize = 64 * 1024;
+
+/// Highest notification rate a flood accepts, per second.
+pub const MAX_FLOOD_RATE: u32 = 10_000;
+
+/// Longest flood accepted.
+pub const MAX_FLOOD_DURATION: Duration = Duration::from_secs(60);
+
+/// Progress token of flood `notifications/progress`.
+pub const FLOOD_PROGRESS_TOKEN: &str = "flood";
+
+/// Body of `POST /admin/tools/batch`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchRequest {
+    /// Tool calls to run, in order.
+    pub calls: Vec<BatchCall>,
+    /// Skip the remaining calls after the first failure (default: false).
+    #[serde(default)]
+    pub stop_on_error: bool,
+    /// Session to run the calls as, sent to them as `Mcp-Session-Id`.
+    /// Without it the calls run as [`crate::sessions::DEFAULT_SESSION`].
+    #[serde(default)]
+    pub session: Option<String>,
+}
+
+/// One tool call in a batch.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchCall {
+    /// Tool name.
+    pub name: String,
+    /// Tool arguments.
+    #[serde(default)]
+    pub arguments: Option<JsonObject>,
+}
+
+/// Response of `POST /admin/tools/batch`.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchResponse {
+    /// Calls that returned a result without `isError`.
+    pub succeeded: usize,
+    /// Calls that returned a protocol error or an `isError` result.
+    pub failed: usize,
+    /// Calls not run because an earlier one failed with `stop_on_error`.
+    pub skipped: usize,
+    /// Outcome of every call that ran, in order.
+    pub results: Vec<BatchResult>,
+}
+
+/// Outcome of one tool call.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchResult {
+    /// Tool name as requested.
+    pub name: String,
+    /// Whether the call succeeded.
+    pub ok: bool,
+    /// The `CallToolResult`, if the call returned one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    /// The JSON-RPC error, if the call failed at the protocol level.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
+}
+
+/// A kind of notification sent by a flood.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FloodKind {
+    /// `notifications/message` at `info` level, logger `flood`.
+    Log,
+    /// `notifications/resources/updated` for the ticker resource.
+    ResourceUpdated,
+    /// `notifications/progress` with the `flood` progress token.
+    Progress,
+}
+
+impl FloodKind {
+    /// Every kind, in the order a flood cycles through them.
+    pub const ALL: [Self; 3] = [Self::Log, Self::ResourceUpdated, Self::Progress];
+}
+
+/// Body of `POST /admin/sessions/{id}/flood`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FloodRequest {
+    /// Notifications per second (default: 100).
+    #[serde(default = "default_flood_rate")]
+    pub rate: u32,
+    /// How long to keep sending, in milliseconds (default: 1000).
+    #[serde(default = "default_flood_duration_ms")]
+    pub duration_ms: u64,
+    /// Kinds to send, in turn (default: all).
+    #[serde(default = "default_flood_kinds")]
+    pub kinds: Vec<FloodKind>,
+}
+
+const fn default_flood_rate() -> u32 {
+    100
+}
+
+const fn default_flood_duration_ms() -> u64 {
+    1000
+}
+
+fn default_flood_kinds() -> Vec<FloodKind> {
+    FloodKind::ALL.to_vec()
+}
+
+/// Response of `POST /admin/sessions/{id}/flood`.
+#[derive(Debug, Clone, Serialize)]
+pub struct FloodReport {
+    /// The flooded session.
+    pub session: String,
+    /// Notifications handed to the session's transport.
+    pub sent: u64,
+    /// Notifications the transport refused (e.g. the session closed).
+    pub failed: u64,
+    /// Milliseconds the flood took.
+    pub elapsed_ms: u64,
+}
+
+/// `POST /admin/tools/batch`
+///
+/// # Errors
+///
+/// `400 Bad Request` if `session` is not a valid header value.
+pub async fn batch_tools(
+    State(server): State<McpTestServer>,
+    Json(request): Json<BatchRequest>,
+) -> Result<Json<BatchResponse>, (StatusCode, String)> {
+    if let Some(session) = &request.session
+        && HeaderValue::from_str(session).is_err()
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Invalid session ID: {session:?}"),
+        ));
+    }
+    Ok(Json(run_batch(server, request).await))
+}
+
+/// `GET /admin/flags`
+pub async fn get_flags(State(server): State<McpTestServer>) -> Json<FlagsSnapshot> {
+    Json(server.resource_handler().flags().snapshot())
+}
+
+/// `PATCH /admin/flags`: merge the body into the flags.
+///
+/// # Errors
+///
+/// `400 Bad Request` if the body names an unknown flag or has a value of the
+/// wrong type.
+pub async fn update_flags(
+    State(server): State<McpTestServer>,
+    Json(patch): Json<JsonObject>,
+) -> Result<Json<FlagsSnapshot>, (StatusCode, String)> {
+    server
+        .resource_handler()
+        .flags()
+        .update(patch)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))
+}
+
+/// `GET /admin/sessions`
+pub async fn list_sessions(State(server): State<McpTestServer>) -> Json<SessionList> {
+    Json(server.sessions().list().await)
+}
+
+/// Response of `GET /admin/sessions/events`.
+#[derive(Debug, Serialize)]
+pub struct SessionEvents {
+    /// Lifecycle events, oldest first.
+    pub events: Vec<SessionEvent>,
+}
+
+/// `GET /admin/sessions/events`
+pub async fn session_events(State(server): State<McpTestServer>) -> Json<SessionEvents> {
+    Json(SessionEvents {
+        events: server.sessions().events(),
+    })
+}
+
+/// `DELETE /admin/sessions/events`: clear the event log.
+pub async fn clear_session_events(State(server): State<McpTestServer>) -> StatusCode {
+    server.sessions().clear_events();
+    StatusCode::NO_CONTENT
+}
+
+/// `DELETE /admin/sessions/{id}`: `204` if the session was terminated, `404`
+/// if it was not live.
+pub async fn terminate_session(
+    State(server): State<McpTestServer>,
+    Path(id): Path<String>,
+) -> StatusCode {
+    if server.sessions().terminate(&id).await {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+/// `GET /admin/pings`
+pub async fn ping_report(State(server): State<McpTestServer>) -> Json<PingReport> {
+    Json(server.pings().report())
+}
+
+/// `GET /admin/subscriptions`
+pub async fn subscription_report(State(server): State<McpTestServer>) -> Json<SubscriptionReport> {
+    Json(server.resource_handler().subscriptions().report())
+}
+
+/// `GET /admin/conformance/{session}`: the session's protocol coverage.
+///
+/// # Errors
+///
+/// `404 Not Found` if the session sent no messages.
+pub async fn conformance_report(
+    State(server): State<McpTestServer>,
+    Path(session): Path<String>,
+) -> Result<Json<ConformanceReport>, StatusCode> {
+    server
+        .conformance()
+        .report(&session)
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// `POST /admin/sessions/{id}/flood`: send notifications to the session at
+/// `rate` per second for `duration_ms`, answering once the flood is over.
+///
+/// # Errors
+///
+/// `400 Bad Request` if the rate, duration, or kinds are out of range, and
+/// `404 Not Found` if the session is not an initialized stateful session.
+pub async fn flood_session(
+    State(server): State<McpTestServer>,
+    Path(id): Path<String>,
+    Json(request): Json<FloodRequest>,
+) -> Result<Json<FloodReport>, (StatusCode, String)> {
+    if !(1..=MAX_FLOOD_RATE).contains(&request.rate) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("rate must be between 1 and {MAX_FLOOD_RATE}"),
+        ));
+    }
+    let duration = Duration::from_millis(request.duration_ms);
+    if duration > MAX_FLOOD_DURATION {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "duration_ms must be at most {}",
+                MAX_FLOOD_DURATION.as_millis()
+            ),
+        ));
+    }
+    if request.kinds.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "kinds must not be empty".into()));
+    }
+    let peer = server.sessions().peer(&id).ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            format!("No initialized session {id}"),
+        )
+    })?;
+    Ok(Json(run_flood(&peer, id, &request, duration).await))
+}
+
+/// Send the notifications of a flood to `peer`.
+async fn run_flood(
+    peer: &Peer<RoleServer>,
+    session: String,
+    request: &FloodRequest,
+    duration: Duration,
+) -> FloodReport {
+    let total = duration.as_millis() * u128::from(request.rate) / 1000;
+    let total = u64::try_from(total).unwrap_or(u64::MAX);
+    let mut ticks = tokio::time::interval(Duration::from_secs(1) / request.rate);
+    let started = Instant::now();
+    let (mut sent, mut failed) = (0, 0);
+    for (n, kind) in (1..=total).zip(request.kinds.iter().cycle()) {
+        ticks.tick().await;
+        let result = match kind {
+            FloodKind::Log => {
+                peer.notify_logging_message(
+                    LoggingMessageNotificationParam::new(
+                        LoggingLevel::Info,
+                        serde_json::json!({ "seq": n, "total": total }),
+                    )
+                    .with_logger("flood"),
+                )
+                .await
+            }
+            FloodKind::ResourceUpdated => {
+                peer.notify_resource_updated(ResourceUpdatedNotificationParam::new(TICKER_URI))
+                    .await
+            }
+            FloodKind::Progress => {
+                #[allow(clippy::cast_precision_loss)]
+                let param = ProgressNotificationParam::new(
+                    rmcp::model::ProgressToken(rmcp::model::NumberOrString::String(
+                        FLOOD_PROGRESS_TOKEN.into(),
+                    )),
+                    n as f64,
+                )
+                .with_total(total as f64);
+                peer.notify_progress(param).await
+            }
+        };
+        if result.is_ok() {
+            sent += 1;
+        } else {
+            failed += 1;
+        }
+    }
+
+    let elapsed_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    tracing::info!(%session, sent, failed, elapsed_ms, "Flooded session with notifications");
+    FloodReport {
+        session,
+        sent,
+        failed,
+        elapsed_ms,
+    }
+}
+
+/// In-memory server transport that hands every request the HTTP parts of
+/// the batch's session, as the Streamable HTTP transport does.
+struct SessionTransport<T> {
+    inner: T,
+    parts: Option<Parts>,
+}
+
+impl<T: Transport<RoleServer>> Transport<RoleServer> for SessionTransport<T> {
+    type Error = T::Error;
+
+    fn send(
+        &mut self,
+        item: TxJsonRpcMessage<RoleServer>,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send + 'static {
+        self.inner.send(item)
+    }
+
+    async fn receive(&mut self) -> Option<RxJsonRpcMessage<RoleServer>> {
+        let mut message = self.inner.receive().await?;
+        if let (JsonRpcMessage::Request(request), Some(parts)) = (&mut message, &self.parts) {
+            request.request.extensions_mut().insert(parts.clone());
+        }
+        Some(message)
+    }
+
+    fn close(&mut self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        self.inner.close()
+    }
+}
+
+/// HTTP parts of a request sent with `session` as its `Mcp-Session-Id`.
+fn session_parts(session: &str) -> Option<Parts> {
+    let value = HeaderValue::from_str(session).ok()?;
+    let (mut parts, ()) = axum::http::Request::new(()).into_parts();
+    parts.headers.insert("mcp-session-id", value);
+    Some(parts)
+}
+
+/// Run a batch of tool calls against `server`.
+pub async fn run_batch(server: McpTestServer, request: BatchRequest) -> BatchResponse {
+    let (client, transport) = tokio::io::duplex(TRANSPORT_BUFFER_BYTES);
+    let (read, write) = tokio::io::split(transport);
+    let transport = SessionTransport {
+        inner: AsyncRwTransport::new_server(read, write),
+        parts: request.session.as_deref().and_then(session_parts),
+    };
+    let running = rmcp::service::serve_directly(server, transport, None);
+    let (reader, mut writer) = tokio::io::split(client);
+    let mut lines = BufReader::new(reader).lines();
+
+    let mut response = BatchResponse {
+        succeeded: 0,
+        failed: 0,
+        skipped: 0,
+        results: Vec::with_capacity(request.calls.len()),
+    };
+    let total = request.calls.len();
+    for (id, call) in request.calls.into_iter().enumerate() {
+        let message = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": { "name": call.name, "arguments": call.arguments },
+        });
+        let reply = async {
+            writer.write_all(format!("{message}\n").as_bytes()).await?;
+            // Skip notifications (logging, progress) until our response arrives
+            while let Some(line) = lines.next_line().await? {
+                if let Ok(reply) = serde_json::from_str::<Value>(&line)
+                    && reply.get("id") == Some(&Value::from(id))
+                {
+                    return Ok(reply);
+                }
+            }
+            Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof))
+        }
+        .await;
+
+        let result = outcome(call.name, reply);
+        if result.ok {
+            response.succeeded += 1;
+        } else {
+            response.failed += 1;
+        }
+        response.results.push(result);
+        if response.failed > 0 && request.stop_on_error {
+            response.skipped = total - id - 1;
+            break;
+        }
+    }
+
+    tracing::info!(
+        session = request.session.as_deref(),
+        succeeded = response.succeeded,
+        failed = response.failed,
+        skipped = response.skipped,
+        "Ran admin tool batch"
+    );
+    let _ = running.cancel().await;
+    response
+}
+
+/// Turn a JSON-RPC reply into a [`BatchResult`].
+fn outcome(name: String, reply: std::io::Result<Value>) -> BatchResult {
+    match reply {
+        Ok(mut reply) => {
+            if let Some(error) = reply.get_mut("error").map(Value::take) {
+                return BatchResult {
+                    name,
+                    ok: false,
+                    result: None,
+                    error: Some(error),
+                };
+            }
+            let result = reply.get_mut("result").map(Value::take);
+            let is_error = result
+                .as_ref()
+                .and_then(|r| r.get("isError"))
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            BatchResult {
+                name,
+                ok: !is_error,
+                result,
+                error: None,
+            }
+        }
+        Err(e) => BatchResult {
+            name,
+            ok: false,
+            result: None,
+            error: Some(serde_json::json!({
+                "code": ErrorCode::INTERNAL_ERROR.0,
+                "message": format!("In-memory transport failed: {e}"),
+            })),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+
+    fn call(name: &str, arguments: Value) -> BatchCall {
+        BatchCall {
+            name: name.to_string(),
+            arguments: match arguments {
+                Value::Object(map) => Some(map),
+                _ => None,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_runs_calls_in_order() {
+        let server = McpTestServer::new(Config::default());
+        let response = run_batch(
+            server,
+            BatchRequest {
+                calls: vec![
+                    call("echo", serde_json::json!({ "text": "first" })),
+                    call("add", serde_json::json!({ "a": 1, "b": 2 })),
+                ],
+                stop_on_error: false,
+                session: None,
+            },
+        )
+        .await;
+
+        assert_eq!(response.succeeded, 2);
+        assert_eq!(response.failed, 0);
+        assert_eq!(response.results[0].name, "echo");
+        let text = &response.results[0].result.as_ref().unwrap()["content"][0]["text"];
+        assert_eq!(text, "first");
+        let text = &response.results[1].result.as_ref().unwrap()["content"][0]["text"];
+        assert_eq!(text, "3");
+    }
+
+    #[tokio::test]
+    async fn test_batch_reports_failures() {
+        let server = McpTestServer::new(Config::default());
+        let response = run_batch(
+            server,
+            BatchRequest {
+                calls: vec![
+                    call("fail", serde_json::json!({})),
+                    call("no_such_tool", serde_json::json!({})),
+                    call("noop", serde_json::json!({})),
+                ],
+                stop_on_error: false,
+                session: None,
+            },
+        )
+        .await;
+
+        assert_eq!(response.succeeded, 1);
+        assert_eq!(response.failed, 2);
+        // Tool-level failure: isError result
+        assert!(response.results[0].result.is_some());
+        // Protocol-level failure: JSON-RPC error
+        assert!(response.results[1].error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_batch_stop_on_error() {
+        let server = McpTestServer::new(Config::default());
+        let response = run_batch(
+            server,
+            BatchRequest {
+                calls: vec![
+                    call("noop", serde_json::json!({})),
+                    call("fail", serde_json::json!({})),
+                    call("noop", serde_json::json!({})),
+                    call("noop", serde_json::json!({})),
+                ],
+                stop_on_error: true,
+                session: None,
+            },
+        )
+        .await;
+
+        assert_eq!(response.succeeded, 1);
+        assert_eq!(response.failed, 1);
+        assert_eq!(response.skipped, 2);
+        assert_eq!(response.results.len(), 2);
+    }
+}
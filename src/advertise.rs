@@ -0,0 +1,233 @@
+//! Shape of the `initialize` result.
+//!
+//! By default the server advertises every capability it implements. With
+//! `MCP_CAPABILITIES` set, only the listed [`Capability`] entries are
+//! advertised, so clients can be tested against servers that, say, offer
+//! resources but no subscriptions. Only the advertisement changes: the
+//! methods behind an unadvertised capability still answer, which also lets
+//! clients be checked for calling only what was advertised.
+//!
+//! `MCP_INSTRUCTIONS`, `MCP_SERVER_NAME`, `MCP_SERVER_TITLE`, and
+//! `MCP_SERVER_VERSION` override the matching `initialize` fields.
+//!
+//! `MCP_EXPERIMENTAL_CAPABILITIES` names a JSON file whose object becomes
+//! `capabilities.experimental` verbatim (see [`load_experimental`]), so
+//! clients built against experimental extensions can test how they detect
+//! them. `MCP_CAPABILITIES` does not affect it.
+
+use std::path::Path;
+
+use rmcp::model::{ExperimentalCapabilities, ServerCapabilities};
+
+/// A capability, or capability flag, the server can advertise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// `tools`.
+    Tools,
+    /// `tools.listChanged`.
+    ToolsListChanged,
+    /// `prompts`.
+    Prompts,
+    /// `prompts.listChanged`.
+    PromptsListChanged,
+    /// `resources`.
+    Resources,
+    /// `resources.subscribe`.
+    ResourcesSubscribe,
+    /// `resources.listChanged`.
+    ResourcesListChanged,
+    /// `logging`.
+    Logging,
+    /// `completions`.
+    Completions,
+    /// `tasks`.
+    Tasks,
+    /// The `io.modelcontextprotocol/ui` extension (MCP Apps).
+    Ui,
+}
+
+impl Capability {
+    /// Every capability, in a stable order.
+    pub const ALL: [Self; 11] = [
+        Self::Tools,
+        Self::ToolsListChanged,
+        Self::Prompts,
+        Self::PromptsListChanged,
+        Self::Resources,
+        Self::ResourcesSubscribe,
+        Self::ResourcesListChanged,
+        Self::Logging,
+        Self::Completions,
+        Self::Tasks,
+        Self::Ui,
+    ];
+
+    /// The name used for this capability in `MCP_CAPABILITIES`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Tools => "tools",
+            Self::ToolsListChanged => "tools.listChanged",
+            Self::Prompts => "prompts",
+            Self::PromptsListChanged => "prompts.listChanged",
+            Self::Resources => "resources",
+            Self::ResourcesSubscribe => "resources.subscribe",
+            Self::ResourcesListChanged => "resources.listChanged",
+            Self::Logging => "logging",
+            Self::Completions => "completions",
+            Self::Tasks => "tasks",
+            Self::Ui => "ui",
+        }
+    }
+
+    /// Parse a capability from its name (case-insensitive).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim();
+        Self::ALL
+            .into_iter()
+            .find(|c| c.as_str().eq_ignore_ascii_case(name))
+    }
+
+    /// Parse a comma-separated list of capability names, skipping unknown
+    /// entries.
+    #[must_use]
+    pub fn parse_list(list: &str) -> Vec<Self> {
+        list.split(',').filter_map(Self::parse).collect()
+    }
+}
+
+/// Remove from `capabilities` everything not in `advertised`.
+///
+/// A flag such as `resources.subscribe` keeps its capability even if the
+/// capability itself is not listed.
+pub fn restrict(capabilities: &mut ServerCapabilities, advertised: &[Capability]) {
+    let has = |c: Capability| advertised.contains(&c);
+
+    if has(Capability::Tools) || has(Capability::ToolsListChanged) {
+        if let Some(tools) = &mut capabilities.tools {
+            tools.list_changed = tools
+                .list_changed
+                .filter(|_| has(Capability::ToolsListChanged));
+        }
+    } else {
+        capabilities.tools = None;
+    }
+    if has(Capability::Prompts) || has(Capability::PromptsListChanged) {
+        if let Some(prompts) = &mut capabilities.prompts {
+            prompts.list_changed = prompts
+                .list_changed
+                .filter(|_| has(Capability::PromptsListChanged));
+        }
+    } else {
+        capabilities.prompts = None;
+    }
+    if has(Capability::Resources)
+        || has(Capability::ResourcesSubscribe)
+        || has(Capability::ResourcesListChanged)
+    {
+        if let Some(resources) = &mut capabilities.resources {
+            resources.subscribe = resources
+                .subscribe
+                .filter(|_| has(Capability::ResourcesSubscribe));
+            resources.list_changed = resources
+                .list_changed
+                .filter(|_| has(Capability::ResourcesListChanged));
+        }
+    } else {
+        capabilities.resources = None;
+    }
+    if !has(Capability::Logging) {
+        capabilities.logging = None;
+    }
+    if !has(Capability::Completions) {
+        capabilities.completions = None;
+    }
+    if !has(Capability::Tasks) {
+        capabilities.tasks = None;
+    }
+    if !has(Capability::Ui) {
+        capabilities.extensions = None;
+    }
+}
+
+/// Read experimental capabilities from a JSON file: an object mapping each
+/// capability name to an object of its settings.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or does not hold such an
+/// object.
+pub fn load_experimental(path: &Path) -> std::io::Result<ExperimentalCapabilities> {
+    let text = std::fs::read_to_string(path)?;
+    serde_json::from_str(&text).map_err(|e| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{}: {e}", path.display()),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn everything() -> ServerCapabilities {
+        ServerCapabilities::builder()
+            .enable_tools()
+            .enable_tool_list_changed()
+            .enable_prompts()
+            .enable_resources()
+            .enable_resources_list_changed()
+            .enable_resources_subscribe()
+            .enable_logging()
+            .build()
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(
+            Capability::parse_list("tools, Resources.Subscribe,bogus"),
+            vec![Capability::Tools, Capability::ResourcesSubscribe]
+        );
+    }
+
+    #[test]
+    fn test_restrict_keeps_listed_flags_only() {
+        let mut capabilities = everything();
+        restrict(
+            &mut capabilities,
+            &[Capability::Resources, Capability::Tools],
+        );
+
+        let resources = capabilities.resources.unwrap();
+        assert_eq!(resources.subscribe, None);
+        assert_eq!(resources.list_changed, None);
+        assert_eq!(capabilities.tools.unwrap().list_changed, None);
+        assert!(capabilities.prompts.is_none());
+        assert!(capabilities.logging.is_none());
+    }
+
+    #[test]
+    fn test_load_experimental() {
+        let path = std::env::temp_dir().join(format!("experimental-{}.json", std::process::id()));
+        std::fs::write(&path, r#"{"acme/streaming": {"version": 2}, "beta": {}}"#).unwrap();
+        let experimental = load_experimental(&path).unwrap();
+        assert_eq!(experimental["acme/streaming"]["version"], 2);
+        assert!(experimental["beta"].is_empty());
+
+        // Each capability's settings must be an object
+        std::fs::write(&path, r#"{"beta": true}"#).unwrap();
+        let err = load_experimental(&path).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_flag_implies_its_capability() {
+        let mut capabilities = everything();
+        restrict(&mut capabilities, &[Capability::ResourcesSubscribe]);
+        assert_eq!(capabilities.resources.unwrap().subscribe, Some(true));
+        assert!(capabilities.tools.is_none());
+    }
+}
@@ -1,5 +1,8 @@
 //! Authentication middleware for API key validation and origin checking.
 //!
+//! Authenticated requests carry their [`Principal`] to the MCP handler, which
+//! reports it through the `whoami` tool and `test://dynamic/auth-context`.
+//!
 //! # Example Usage
 //!
 //! ```rust,no_run
@@ -34,29 +37,184 @@
 use axum::{
     body::Body,
     extract::State,
-    http::{Request, StatusCode},
+    http::{HeaderValue, Request, StatusCode, header::WWW_AUTHENTICATE},
     middleware::Next,
     response::{IntoResponse, Response},
 };
 use serde::Serialize;
+use sha2::{Digest, Sha256};
 use subtle::ConstantTimeEq;
 
 use crate::config::Config;
+use crate::oauth::IssuedToken;
+
+/// Realm advertised in `WWW-Authenticate` challenges.
+const REALM: &str = "mcp-test-server";
+
+/// The API key a request authenticated with, added to the request extensions
+/// by [`auth_middleware`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKeyId(pub String);
+
+impl ApiKeyId {
+    /// Identify `key` without revealing it: `sha256:` and the first 12 hex
+    /// digits of its SHA-256.
+    #[must_use]
+    pub fn for_key(key: &str) -> Self {
+        let digest = format!("{:x}", Sha256::digest(key.as_bytes()));
+        Self(format!("sha256:{}", &digest[..12]))
+    }
+}
+
+/// How a request authenticated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthMethod {
+    /// No credentials were required or presented.
+    #[default]
+    None,
+    /// The static `MCP_API_KEY` bearer token.
+    ApiKey,
+    /// An access token issued by the mock OAuth server.
+    Oauth,
+}
+
+/// The authenticated principal behind a request.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Principal {
+    /// How the request authenticated.
+    pub method: AuthMethod,
+    /// Fingerprint of the API key, in API key mode.
+    pub api_key_id: Option<String>,
+    /// Client the OAuth access token was issued to.
+    pub client_id: Option<String>,
+    /// Scopes granted to the OAuth access token.
+    pub scopes: Vec<String>,
+    /// Grant type the OAuth access token was obtained with.
+    pub grant_type: Option<String>,
+}
+
+impl Principal {
+    /// Read the principal that the auth middleware attached to a request.
+    /// Requests without one are unauthenticated.
+    #[must_use]
+    pub fn from_extensions(extensions: &axum::http::Extensions) -> Self {
+        if let Some(token) = extensions.get::<IssuedToken>() {
+            return Self {
+                method: AuthMethod::Oauth,
+                api_key_id: None,
+                client_id: token.client_id.clone(),
+                scopes: token.scope.split_whitespace().map(str::to_string).collect(),
+                grant_type: Some(token.grant_type.clone()),
+            };
+        }
+        if let Some(ApiKeyId(id)) = extensions.get::<ApiKeyId>() {
+            return Self {
+                method: AuthMethod::ApiKey,
+                api_key_id: Some(id.clone()),
+                ..Self::default()
+            };
+        }
+        Self::default()
+    }
+}
+
+/// Describe who sent a request: its [`Principal`] plus the MCP session it
+/// belongs to. `parts` is `None` for requests that did not arrive over HTTP.
+#[must_use]
+pub fn auth_context(parts: Option<&axum::http::request::Parts>) -> serde_json::Value {
+    let principal = parts.map_or_else(Principal::default, |parts| {
+        Principal::from_extensions(&parts.extensions)
+    });
+    let session = parts
+        .and_then(|parts| parts.headers.get("mcp-session-id"))
+        .and_then(|v| v.to_str().ok());
+    let mut context = serde_json::to_value(principal).unwrap_or_default();
+    context["session"] = serde_json::json!(session);
+    context
+}
+
+/// How missing or invalid API keys are reported.
+///
+/// Disallowed origins are always `403 Forbidden`: the client is
+/// authenticated (or doesn't need to be) but the request is refused.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AuthFailureMode {
+    /// `401 Unauthorized` with a `WWW-Authenticate: Bearer` challenge (RFC 6750 §3).
+    #[default]
+    Spec,
+    /// `403 Forbidden` without a challenge, as returned by earlier versions.
+    Forbidden,
+    /// `401 Unauthorized` without `WWW-Authenticate` (non-compliant, for
+    /// testing clients that must cope with a missing challenge).
+    BareUnauthorized,
+}
+
+impl AuthFailureMode {
+    /// Parse a mode name (`spec`, `forbidden`, or `bare_401`, case-insensitive).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "spec" => Some(Self::Spec),
+            "forbidden" => Some(Self::Forbidden),
+            "bare_401" => Some(Self::BareUnauthorized),
+            _ => None,
+        }
+    }
+}
 
 /// Error response for authentication failures.
 #[derive(Debug, Serialize)]
 pub struct AuthError {
+    #[serde(skip)]
+    status: StatusCode,
+    #[serde(skip)]
+    challenge: Option<String>,
     error: String,
     message: String,
 }
 
 impl AuthError {
-    fn new(message: impl Into<String>) -> Self {
+    /// The request is refused regardless of credentials.
+    fn forbidden(message: impl Into<String>) -> Self {
         Self {
+            status: StatusCode::FORBIDDEN,
+            challenge: None,
             error: "forbidden".to_string(),
             message: message.into(),
         }
     }
+
+    /// No usable credentials were presented, or the presented token is wrong
+    /// (`invalid_token`). Reported according to `mode`.
+    fn credentials(mode: AuthFailureMode, message: impl Into<String>, invalid_token: bool) -> Self {
+        let message = message.into();
+        match mode {
+            AuthFailureMode::Forbidden => Self::forbidden(message),
+            AuthFailureMode::BareUnauthorized => Self {
+                status: StatusCode::UNAUTHORIZED,
+                challenge: None,
+                error: "unauthorized".to_string(),
+                message,
+            },
+            AuthFailureMode::Spec => {
+                // RFC 6750 §3.1: no error code when credentials are absent
+                let challenge = if invalid_token {
+                    format!(
+                        r#"Bearer realm="{REALM}", error="invalid_token", error_description="{message}""#
+                    )
+                } else {
+                    format!(r#"Bearer realm="{REALM}""#)
+                };
+                Self {
+                    status: StatusCode::UNAUTHORIZED,
+                    challenge: Some(challenge),
+                    error: "unauthorized".to_string(),
+                    message,
+                }
+            }
+        }
+    }
 }
 
 impl IntoResponse for AuthError {
@@ -64,7 +222,11 @@ impl IntoResponse for AuthError {
         let body = serde_json::to_string(&self).unwrap_or_else(|_| {
             r#"{"error":"forbidden","message":"Authentication failed"}"#.to_string()
         });
-        (StatusCode::FORBIDDEN, body).into_response()
+        let mut response = (self.status, body).into_response();
+        if let Some(value) = self.challenge.and_then(|c| HeaderValue::from_str(&c).ok()) {
+            response.headers_mut().insert(WWW_AUTHENTICATE, value);
+        }
+        response
     }
 }
 
@@ -87,17 +249,19 @@ impl IntoResponse for AuthError {
 /// - `http://localhost:*`
 /// - `http://127.0.0.1:*`
 /// - `https://localhost:*`
+/// - Origins listed in `MCP_CORS_ORIGINS` (see [`crate::cors`])
+/// - The origin of `MCP_PUBLIC_URL` (see [`crate::proxy`])
 ///
 /// # Errors
 ///
-/// Returns `403 Forbidden` with a JSON error body if:
-/// - API key is required but missing
-/// - API key is invalid
-/// - Origin is present but not allowed
+/// Returns a JSON error body if:
+/// - API key is required but missing, or invalid: `401 Unauthorized` with a
+///   `WWW-Authenticate` challenge by default (see [`AuthFailureMode`])
+/// - Origin is present but not allowed: `403 Forbidden`
 #[allow(clippy::cognitive_complexity)]
 pub async fn auth_middleware(
     State(config): State<Config>,
-    request: Request<Body>,
+    mut request: Request<Body>,
     next: Next,
 ) -> Result<Response, AuthError> {
     // Validate API key if configured
@@ -114,18 +278,30 @@ pub async fn auth_middleware(
                 // Constant-time comparison to prevent timing attacks
                 if !constant_time_compare(token.as_bytes(), expected_key.as_bytes()) {
                     tracing::debug!("API key validation failed");
-                    return Err(AuthError::new("Invalid API key"));
+                    return Err(AuthError::credentials(
+                        config.auth_failure_mode,
+                        "Invalid API key",
+                        true,
+                    ));
                 }
+                let key_id = ApiKeyId::for_key(token);
+                request.extensions_mut().insert(key_id);
             }
             Some(_) => {
                 tracing::debug!("Invalid Authorization header format");
-                return Err(AuthError::new(
+                return Err(AuthError::credentials(
+                    config.auth_failure_mode,
                     "Invalid Authorization header format. Expected: Bearer <token>",
+                    false,
                 ));
             }
             None => {
                 tracing::debug!("Missing Authorization header");
-                return Err(AuthError::new("Missing Authorization header"));
+                return Err(AuthError::credentials(
+                    config.auth_failure_mode,
+                    "Missing Authorization header",
+                    false,
+                ));
             }
         }
     }
@@ -136,9 +312,14 @@ pub async fn auth_middleware(
         .get("origin")
         .and_then(|v| v.to_str().ok())
         && !is_allowed_origin(origin)
+        && !config.cors.lists(origin)
+        && config
+            .public_url
+            .as_deref()
+            .is_none_or(|url| crate::proxy::origin(url) != origin)
     {
         tracing::debug!(origin = %origin, "Origin not allowed");
-        return Err(AuthError::new("Origin not allowed"));
+        return Err(AuthError::forbidden("Origin not allowed"));
     }
 
     Ok(next.run(request).await)
@@ -146,8 +327,8 @@ pub async fn auth_middleware(
 
 /// Compare two byte slices in constant time.
 ///
-/// This prevents timing attacks when comparing API keys.
-fn constant_time_compare(a: &[u8], b: &[u8]) -> bool {
+/// This prevents timing attacks when comparing API keys and client secrets.
+pub(crate) fn constant_time_compare(a: &[u8], b: &[u8]) -> bool {
     if a.len() != b.len() {
         return false;
     }
@@ -192,9 +373,83 @@ mod tests {
 
     #[test]
     fn test_auth_error_serialization() {
-        let error = AuthError::new("Test message");
+        let error = AuthError::forbidden("Test message");
         let json = serde_json::to_string(&error).unwrap();
         assert!(json.contains("forbidden"));
         assert!(json.contains("Test message"));
+        assert!(!json.contains("status"));
+    }
+
+    #[test]
+    fn test_credentials_error_modes() {
+        let spec = AuthError::credentials(AuthFailureMode::Spec, "Invalid API key", true);
+        assert_eq!(spec.status, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            spec.challenge.as_deref(),
+            Some(
+                r#"Bearer realm="mcp-test-server", error="invalid_token", error_description="Invalid API key""#
+            )
+        );
+
+        let missing = AuthError::credentials(AuthFailureMode::Spec, "Missing", false);
+        assert_eq!(
+            missing.challenge.as_deref(),
+            Some(r#"Bearer realm="mcp-test-server""#)
+        );
+
+        let legacy = AuthError::credentials(AuthFailureMode::Forbidden, "Missing", false);
+        assert_eq!(legacy.status, StatusCode::FORBIDDEN);
+        assert_eq!(legacy.challenge, None);
+
+        let bare = AuthError::credentials(AuthFailureMode::BareUnauthorized, "Missing", false);
+        assert_eq!(bare.status, StatusCode::UNAUTHORIZED);
+        assert_eq!(bare.challenge, None);
+    }
+
+    #[test]
+    fn test_auth_failure_mode_parse() {
+        assert_eq!(AuthFailureMode::parse("SPEC"), Some(AuthFailureMode::Spec));
+        assert_eq!(
+            AuthFailureMode::parse("forbidden"),
+            Some(AuthFailureMode::Forbidden)
+        );
+        assert_eq!(
+            AuthFailureMode::parse("bare_401"),
+            Some(AuthFailureMode::BareUnauthorized)
+        );
+        assert_eq!(AuthFailureMode::parse("418"), None);
+    }
+
+    #[test]
+    fn test_api_key_id_hides_key() {
+        let id = ApiKeyId::for_key("secret-key");
+        assert!(id.0.starts_with("sha256:"));
+        assert_eq!(id.0.len(), "sha256:".len() + 12);
+        assert!(!id.0.contains("secret"));
+        assert_eq!(id, ApiKeyId::for_key("secret-key"));
+    }
+
+    #[test]
+    fn test_principal_from_extensions() {
+        let mut extensions = axum::http::Extensions::new();
+        assert_eq!(
+            Principal::from_extensions(&extensions),
+            Principal::default()
+        );
+
+        extensions.insert(ApiKeyId::for_key("k"));
+        let principal = Principal::from_extensions(&extensions);
+        assert_eq!(principal.method, AuthMethod::ApiKey);
+        assert_eq!(principal.api_key_id, Some(ApiKeyId::for_key("k").0));
+
+        extensions.insert(IssuedToken {
+            client_id: Some("client-1".to_string()),
+            scope: "mcp:read mcp:write".to_string(),
+            grant_type: "client_credentials".to_string(),
+        });
+        let principal = Principal::from_extensions(&extensions);
+        assert_eq!(principal.method, AuthMethod::Oauth);
+        assert_eq!(principal.client_id.as_deref(), Some("client-1"));
+        assert_eq!(principal.scopes, ["mcp:read", "mcp:write"]);
     }
 }
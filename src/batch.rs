@@ -0,0 +1,276 @@
+//! JSON-RPC batches on `/mcp` (`MCP_BATCH`).
+//!
+//! The `2025-03-26` revision lets clients POST an array of messages; the
+//! `2025-06-18` revision dropped batching again. With [`BatchPolicy::Spec`]
+//! the server follows the revision in the request's `MCP-Protocol-Version`
+//! header, so the same client can be checked against both. The header must
+//! name a revision the server knows; any other value is rejected. A missing
+//! header means `2025-03-26`, as the spec tells servers to assume, so
+//! batches without the header are served. [`BatchPolicy::Accept`] and
+//! [`BatchPolicy::Reject`] pin one behavior whatever the revision.
+//!
+//! An accepted batch is split up and each message is served as if it had
+//! been posted alone, concurrently and with the batch's headers. The
+//! responses to its requests come back as one `application/json` array, in
+//! the order of the batch; a batch of only notifications and responses gets
+//! `202 Accepted`. Notifications a request sends while it runs are dropped,
+//! as a JSON array has no place for them. `initialize` must not be batched
+//! and is answered with a `-32600` error, as are batches that are empty.
+//!
+//! A rejected batch gets `400 Bad Request` with a single `-32600` error
+//! whose `id` is `null`.
+
+use axum::Router;
+use axum::body::{Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::request::Parts;
+use axum::http::{HeaderValue, Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Json, Response};
+use futures_util::future::join_all;
+use rmcp::model::{ErrorCode, ProtocolVersion};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+/// First revision without batching.
+const BATCHES_REMOVED_IN: &str = "2025-06-18";
+
+/// Revision assumed when a request has no `MCP-Protocol-Version` header.
+const DEFAULT_REVISION: &str = "2025-03-26";
+
+/// Whether JSON-RPC batches are served.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BatchPolicy {
+    /// Accept batches up to `2025-03-26`, reject them from `2025-06-18`
+    /// (default).
+    #[default]
+    Spec,
+    /// Accept batches whatever the revision.
+    Accept,
+    /// Reject batches whatever the revision.
+    Reject,
+}
+
+impl BatchPolicy {
+    /// Parse `spec`, `accept`, or `reject` (case-insensitive).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "spec" => Some(Self::Spec),
+            "accept" => Some(Self::Accept),
+            "reject" => Some(Self::Reject),
+            _ => None,
+        }
+    }
+
+    /// Whether a batch sent with the `MCP-Protocol-Version` `revision` is
+    /// served.
+    #[must_use]
+    pub fn accepts(self, revision: Option<&str>) -> bool {
+        match self {
+            Self::Spec => {
+                let revision = revision.unwrap_or(DEFAULT_REVISION);
+                is_known(revision) && revision < BATCHES_REMOVED_IN
+            }
+            Self::Accept => true,
+            Self::Reject => false,
+        }
+    }
+}
+
+/// Whether `revision` is a protocol revision the server knows.
+fn is_known(revision: &str) -> bool {
+    ProtocolVersion::KNOWN_VERSIONS
+        .iter()
+        .any(|known| known.as_str() == revision)
+}
+
+/// State of [`batch_middleware`].
+#[derive(Clone)]
+pub struct Batches {
+    /// Whether batches are served.
+    pub policy: BatchPolicy,
+    /// The `/mcp` routes each message of a batch is sent through.
+    pub inner: Router,
+}
+
+/// Middleware serving or rejecting JSON-RPC batches per [`BatchPolicy`].
+pub async fn batch_middleware(
+    State(batches): State<Batches>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+    let (parts, body) = request.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    let Ok(Value::Array(messages)) = serde_json::from_slice::<Value>(&bytes) else {
+        return next
+            .run(Request::from_parts(parts, Body::from(bytes)))
+            .await;
+    };
+
+    let revision = parts
+        .headers
+        .get("mcp-protocol-version")
+        .map(|v| String::from_utf8_lossy(v.as_bytes()).into_owned());
+    let revision = revision.as_deref();
+    if !batches.policy.accepts(revision) {
+        tracing::info!(len = messages.len(), ?revision, "Rejecting batch");
+        return invalid_request(&match revision {
+            Some(revision) if !is_known(revision) => {
+                format!("Batch requests are not supported: unknown MCP-Protocol-Version {revision}")
+            }
+            _ => format!(
+                "Batch requests are not supported in protocol version {}",
+                revision.unwrap_or(DEFAULT_REVISION)
+            ),
+        });
+    }
+    if messages.is_empty() {
+        return invalid_request("Invalid Request: empty batch");
+    }
+
+    tracing::debug!(len = messages.len(), "Serving batch");
+    let responses = join_all(
+        messages
+            .into_iter()
+            .map(|message| serve_one(&batches.inner, &parts, message)),
+    )
+    .await;
+    let responses: Vec<Value> = responses.into_iter().flatten().collect();
+    if responses.is_empty() {
+        return StatusCode::ACCEPTED.into_response();
+    }
+    Json(Value::Array(responses)).into_response()
+}
+
+/// Serve one message of a batch; `None` for notifications and responses.
+async fn serve_one(inner: &Router, parts: &Parts, message: Value) -> Option<Value> {
+    let id = message.get("id").cloned();
+    let is_request = message.get("method").is_some() && id.is_some();
+    if is_request && message["method"] == "initialize" {
+        return Some(error_message(
+            id.as_ref().unwrap_or(&Value::Null),
+            "Invalid Request: initialize must not be part of a batch",
+            None,
+        ));
+    }
+
+    let mut request = Request::new(Body::from(message.to_string()));
+    *request.method_mut() = parts.method.clone();
+    *request.uri_mut() = parts.uri.clone();
+    *request.version_mut() = parts.version;
+    *request.headers_mut() = parts.headers.clone();
+    *request.extensions_mut() = parts.extensions.clone();
+    request.headers_mut().remove(header::CONTENT_LENGTH);
+    request.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+
+    let response = match inner.clone().oneshot(request).await {
+        Ok(response) => response,
+        Err(never) => match never {},
+    };
+    let id = id.filter(|_| is_request)?;
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap_or_default();
+    Some(response_for(&id, &body).unwrap_or_else(|| {
+        error_message(
+            &id,
+            &format!("HTTP {status}: {}", String::from_utf8_lossy(&body).trim()),
+            Some(json!({ "status": status.as_u16() })),
+        )
+    }))
+}
+
+/// The response to the request `id` in a plain JSON or SSE body.
+fn response_for(id: &Value, body: &Bytes) -> Option<Value> {
+    let is_response = |message: &Value| {
+        message.get("id") == Some(id)
+            && (message.get("result").is_some() || message.get("error").is_some())
+    };
+    if let Ok(message) = serde_json::from_slice::<Value>(body) {
+        return is_response(&message).then_some(message);
+    }
+    String::from_utf8_lossy(body)
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .filter_map(|data| serde_json::from_str::<Value>(data.trim()).ok())
+        .find(is_response)
+}
+
+/// A JSON-RPC `-32600` error for `id`.
+fn error_message(id: &Value, message: &str, data: Option<Value>) -> Value {
+    let mut error = json!({
+        "code": ErrorCode::INVALID_REQUEST.0,
+        "message": message,
+    });
+    if let Some(data) = data {
+        error["data"] = data;
+    }
+    json!({ "jsonrpc": "2.0", "id": id, "error": error })
+}
+
+/// `400 Bad Request` with a single `-32600` error without an `id`.
+fn invalid_request(message: &str) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(error_message(&Value::Null, message, None)),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(BatchPolicy::parse(" Accept "), Some(BatchPolicy::Accept));
+        assert_eq!(BatchPolicy::parse("reject"), Some(BatchPolicy::Reject));
+        assert_eq!(BatchPolicy::parse("spec"), Some(BatchPolicy::Spec));
+        assert_eq!(BatchPolicy::parse("sometimes"), None);
+    }
+
+    #[test]
+    fn test_spec_follows_revision() {
+        let spec = BatchPolicy::Spec;
+        assert!(spec.accepts(None));
+        assert!(spec.accepts(Some("2024-11-05")));
+        assert!(spec.accepts(Some("2025-03-26")));
+        assert!(!spec.accepts(Some("2025-06-18")));
+        assert!(BatchPolicy::Accept.accepts(Some("2025-06-18")));
+        assert!(!BatchPolicy::Reject.accepts(None));
+    }
+
+    #[test]
+    fn test_spec_rejects_unknown_revisions() {
+        for revision in ["", "1.0", "2025-99", "2025-03-25", "2025-03-26 "] {
+            assert!(!BatchPolicy::Spec.accepts(Some(revision)), "{revision:?}");
+        }
+    }
+
+    #[test]
+    fn test_response_for() {
+        let id = json!(3);
+        let sse = Bytes::from(
+            "data: \n\n\
+             data: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/progress\"}\n\n\
+             data: {\"jsonrpc\":\"2.0\",\"id\":3,\"result\":{}}\n\n",
+        );
+        assert_eq!(
+            response_for(&id, &sse),
+            Some(json!({"jsonrpc": "2.0", "id": 3, "result": {}}))
+        );
+        let other = Bytes::from(r#"{"jsonrpc":"2.0","id":4,"result":{}}"#);
+        assert_eq!(response_for(&id, &other), None);
+    }
+}
@@ -0,0 +1,39 @@
+//! Broken MCP server entry point.
+//!
+//! Serves deliberately spec-violating responses (see
+//! [`mcp_test_server::broken`]). `MCP_BROKEN_MODE` selects the mode for
+//! `POST /mcp` (default: `invalid_jsonrpc`); `POST /{mode}/mcp` overrides it.
+
+use std::process::ExitCode;
+
+use mcp_test_server::Config;
+use mcp_test_server::broken::BrokenMode;
+use tracing_subscriber::{EnvFilter, fmt, prelude::*};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<ExitCode> {
+    let mode = match std::env::var("MCP_BROKEN_MODE") {
+        Err(_) => BrokenMode::InvalidJsonRpc,
+        Ok(name) => {
+            let Some(mode) = BrokenMode::parse(&name) else {
+                let names: Vec<_> = BrokenMode::ALL.iter().map(|m| m.name()).collect();
+                eprintln!(
+                    "MCP_BROKEN_MODE: unknown mode {name:?} (expected one of {})",
+                    names.join(", ")
+                );
+                return Ok(ExitCode::FAILURE);
+            };
+            mode
+        }
+    };
+
+    let filter =
+        EnvFilter::try_from_env("MCP_LOG_LEVEL").unwrap_or_else(|_| EnvFilter::new("info"));
+    tracing_subscriber::registry()
+        .with(fmt::layer())
+        .with(filter)
+        .init();
+
+    mcp_test_server::broken::run(Config::from_env(), mode).await?;
+    Ok(ExitCode::SUCCESS)
+}
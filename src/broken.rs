@@ -0,0 +1,309 @@
+//! A deliberately broken MCP server for client robustness testing.
+//!
+//! The `mcp-broken-server` binary (feature `broken-server`) answers the
+//! basic MCP requests (`initialize`, `ping`, `tools/list`, and `tools/call`
+//! for `echo`) and then breaks each response in one [`BrokenMode`]. `POST
+//! /mcp` uses the mode from `MCP_BROKEN_MODE`; `POST /{mode}/mcp` picks one
+//! per request, so a single instance serves every mode.
+//!
+//! It shares the main server's [`Config`] for `MCP_HOST`, `MCP_PORT`, and
+//! `MCP_API_KEY`, and its authentication middleware, so auth works the same
+//! way on both.
+
+use std::fmt;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::{IntoResponse, Json, Response};
+use axum::routing::{get, post};
+use axum::{Router, middleware};
+use serde_json::{Value, json};
+
+use crate::auth::auth_middleware;
+use crate::config::Config;
+
+/// How the broken server violates the protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrokenMode {
+    /// `initialize` returns capabilities of the wrong types, an invalid
+    /// protocol version, and a non-string server name.
+    BadCapabilities,
+    /// Responses claim `"jsonrpc": "1.0"`, carry both `result` and `error`,
+    /// and echo numeric IDs back as strings.
+    InvalidJsonRpc,
+    /// Correct JSON served as `text/html`.
+    WrongContentType,
+    /// JSON cut off halfway through, served as `application/json`.
+    MalformedJson,
+    /// `200 OK` with an empty body.
+    EmptyBody,
+    /// A `text/event-stream` whose `data:` lines are not JSON.
+    SseGarbage,
+}
+
+impl BrokenMode {
+    /// Every mode.
+    pub const ALL: [Self; 6] = [
+        Self::BadCapabilities,
+        Self::InvalidJsonRpc,
+        Self::WrongContentType,
+        Self::MalformedJson,
+        Self::EmptyBody,
+        Self::SseGarbage,
+    ];
+
+    /// Parse a mode name such as `bad_capabilities` (case-insensitive,
+    /// `-` accepted for `_`).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim().to_ascii_lowercase().replace('-', "_");
+        Self::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+
+    /// Mode name as used in `MCP_BROKEN_MODE` and `/{mode}/mcp`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::BadCapabilities => "bad_capabilities",
+            Self::InvalidJsonRpc => "invalid_jsonrpc",
+            Self::WrongContentType => "wrong_content_type",
+            Self::MalformedJson => "malformed_json",
+            Self::EmptyBody => "empty_body",
+            Self::SseGarbage => "sse_garbage",
+        }
+    }
+}
+
+impl fmt::Display for BrokenMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Build the broken server's routes: `/health`, `/mcp`, and `/{mode}/mcp`.
+pub fn router(config: Config, default_mode: BrokenMode) -> Router {
+    let protected = Router::new()
+        .route("/mcp", post(mcp_default))
+        .route("/{mode}/mcp", post(mcp_with_mode))
+        .with_state(default_mode)
+        .layer(middleware::from_fn_with_state(config, auth_middleware));
+    Router::new()
+        .route("/health", get(|| async { Json(json!({ "status": "ok" })) }))
+        .merge(protected)
+}
+
+/// Run the broken server on the configured host and port until Ctrl+C.
+///
+/// # Errors
+///
+/// Returns an error if the server fails to bind or encounters a runtime error.
+pub async fn run(config: Config, mode: BrokenMode) -> anyhow::Result<()> {
+    let addr = std::net::SocketAddr::new(config.host, config.port);
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!(%addr, %mode, "Broken MCP server listening");
+    axum::serve(listener, router(config, mode))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
+
+async fn mcp_default(State(mode): State<BrokenMode>, Json(message): Json<Value>) -> Response {
+    respond(mode, &message)
+}
+
+async fn mcp_with_mode(Path(name): Path<String>, Json(message): Json<Value>) -> Response {
+    BrokenMode::parse(&name).map_or_else(
+        || {
+            (
+                StatusCode::NOT_FOUND,
+                format!("unknown broken mode {name:?}"),
+            )
+                .into_response()
+        },
+        |mode| respond(mode, &message),
+    )
+}
+
+/// Answer one JSON-RPC message, broken according to `mode`.
+fn respond(mode: BrokenMode, message: &Value) -> Response {
+    let Some(id) = message.get("id").cloned() else {
+        // Notifications are accepted normally so clients get far enough to break
+        return StatusCode::ACCEPTED.into_response();
+    };
+    let method = message["method"].as_str().unwrap_or_default();
+    tracing::debug!(%mode, method, "Sending broken response");
+    let outcome = if mode == BrokenMode::BadCapabilities && method == "initialize" {
+        Ok(bad_initialize_result())
+    } else {
+        answer(method, &message["params"])
+    };
+    let response = match outcome {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, text)) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": code, "message": text },
+        }),
+    };
+    render(mode, response)
+}
+
+/// The correct result for `method`, or a JSON-RPC error code and message.
+fn answer(method: &str, params: &Value) -> Result<Value, (i64, String)> {
+    match method {
+        "initialize" => Ok(json!({
+            "protocolVersion": "2025-06-18",
+            "capabilities": { "tools": {} },
+            "serverInfo": { "name": "mcp-broken-server", "version": env!("CARGO_PKG_VERSION") },
+        })),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({
+            "tools": [{
+                "name": "echo",
+                "description": "Echo back the input text",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": { "text": { "type": "string" } },
+                    "required": ["text"],
+                },
+            }],
+        })),
+        "tools/call" if params["name"] == "echo" => Ok(json!({
+            "content": [{
+                "type": "text",
+                "text": params["arguments"]["text"].as_str().unwrap_or_default(),
+            }],
+            "isError": false,
+        })),
+        "tools/call" => Err((-32602, format!("Unknown tool: {}", params["name"]))),
+        _ => Err((-32601, format!("Method not found: {method}"))),
+    }
+}
+
+fn bad_initialize_result() -> Value {
+    json!({
+        "protocolVersion": "not-a-version",
+        "capabilities": { "tools": true, "resources": "yes", "prompts": [1, 2] },
+        "serverInfo": { "name": 42 },
+    })
+}
+
+/// Serialize `response`, applying the transport-level breakage of `mode`.
+fn render(mode: BrokenMode, mut response: Value) -> Response {
+    match mode {
+        // Broken in the result itself
+        BrokenMode::BadCapabilities => Json(response).into_response(),
+        BrokenMode::InvalidJsonRpc => {
+            response["jsonrpc"] = json!("1.0");
+            if let Some(id) = response["id"].as_i64() {
+                response["id"] = json!(id.to_string());
+            }
+            if response.get("error").is_none() {
+                response["error"] = json!({ "code": -32603, "message": "also an error" });
+            }
+            if response.get("result").is_none() {
+                response["result"] = json!({});
+            }
+            Json(response).into_response()
+        }
+        BrokenMode::WrongContentType => (
+            [(CONTENT_TYPE, "text/html; charset=utf-8")],
+            response.to_string(),
+        )
+            .into_response(),
+        BrokenMode::MalformedJson => {
+            let mut text = response.to_string();
+            let mut cut = text.len() / 2;
+            while !text.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            text.truncate(cut);
+            ([(CONTENT_TYPE, "application/json")], text).into_response()
+        }
+        BrokenMode::EmptyBody => ([(CONTENT_TYPE, "application/json")], "").into_response(),
+        BrokenMode::SseGarbage => (
+            [(CONTENT_TYPE, "text/event-stream")],
+            "event: message\ndata: this is not json\n\ndata: {\"jsonrpc\":\n\n",
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn body(response: Response) -> (Option<String>, String) {
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (content_type, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    fn request(method: &str) -> Value {
+        json!({ "jsonrpc": "2.0", "id": 7, "method": method, "params": {} })
+    }
+
+    #[test]
+    fn test_mode_names_round_trip() {
+        for mode in BrokenMode::ALL {
+            assert_eq!(BrokenMode::parse(mode.name()), Some(mode));
+        }
+        assert_eq!(
+            BrokenMode::parse("Wrong-Content-Type"),
+            Some(BrokenMode::WrongContentType)
+        );
+        assert_eq!(BrokenMode::parse("fine"), None);
+    }
+
+    #[tokio::test]
+    async fn test_bad_capabilities_only_breaks_initialize() {
+        let (_, init) = body(respond(BrokenMode::BadCapabilities, &request("initialize"))).await;
+        let init: Value = serde_json::from_str(&init).unwrap();
+        assert_eq!(init["result"]["capabilities"]["tools"], true);
+        let (_, ping) = body(respond(BrokenMode::BadCapabilities, &request("ping"))).await;
+        let ping: Value = serde_json::from_str(&ping).unwrap();
+        assert_eq!(ping["result"], json!({}));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_jsonrpc_envelope() {
+        let (_, text) = body(respond(BrokenMode::InvalidJsonRpc, &request("ping"))).await;
+        let message: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(message["jsonrpc"], "1.0");
+        assert_eq!(message["id"], "7");
+        assert!(message.get("result").is_some() && message.get("error").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_transport_breakage() {
+        let (content_type, _) = body(respond(BrokenMode::WrongContentType, &request("ping"))).await;
+        assert_eq!(content_type.as_deref(), Some("text/html; charset=utf-8"));
+
+        let (_, text) = body(respond(BrokenMode::MalformedJson, &request("tools/list"))).await;
+        assert!(!text.is_empty());
+        assert!(serde_json::from_str::<Value>(&text).is_err());
+
+        let (_, text) = body(respond(BrokenMode::EmptyBody, &request("ping"))).await;
+        assert!(text.is_empty());
+
+        let (content_type, text) = body(respond(BrokenMode::SseGarbage, &request("ping"))).await;
+        assert_eq!(content_type.as_deref(), Some("text/event-stream"));
+        assert!(text.contains("data: this is not json"));
+    }
+
+    #[tokio::test]
+    async fn test_notifications_accepted() {
+        let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        let response = respond(BrokenMode::EmptyBody, &notification);
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+    }
+}
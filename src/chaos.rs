@@ -0,0 +1,398 @@
+//! Response chaos on `POST /mcp` event streams, and intermittent tool
+//! failures.
+//!
+//! With response chaos configured (`MCP_CHAOS_RESPONSES`),
+//! [`response_chaos_middleware`] picks JSON-RPC responses at random
+//! (`MCP_CHAOS_RATE`, seeded by `MCP_SEED`) and disturbs them, so clients can
+//! check that they correlate responses by `id` rather than by arrival:
+//!
+//! - [`ResponseChaos::Duplicate`] sends the response event twice, so the
+//!   second copy carries an id the client has already seen answered.
+//! - [`ResponseChaos::Reorder`] holds the response back until the next
+//!   response of the same session has gone out (or [`REORDER_MAX_DELAY`]
+//!   passes), so concurrent requests are answered out of request order.
+//!
+//! Notifications and server-to-client requests are never touched.
+//!
+//! With per-tool error rates configured (`MCP_TOOL_ERROR_RATE`, e.g.
+//! `add:0.25`), [`ToolFaults`] makes calls to those tools fail at random
+//! with an `isError` result instead of running, so retry policies can be
+//! tested per tool.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::body::{Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::header::CONTENT_TYPE;
+use axum::middleware::Next;
+use axum::response::Response;
+use futures_util::StreamExt;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use tokio::sync::Notify;
+
+/// Longest time a reordered response waits for a later one to overtake it.
+pub const REORDER_MAX_DELAY: Duration = Duration::from_millis(500);
+
+/// A way of disturbing JSON-RPC responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResponseChaos {
+    /// Send the response twice.
+    Duplicate,
+    /// Deliver the response after a later one.
+    Reorder,
+}
+
+impl ResponseChaos {
+    /// Every chaos mode, in a stable order.
+    pub const ALL: [Self; 2] = [Self::Duplicate, Self::Reorder];
+
+    /// The name used for this mode in `MCP_CHAOS_RESPONSES`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Duplicate => "duplicate",
+            Self::Reorder => "reorder",
+        }
+    }
+
+    /// Parse a mode from its name (case-insensitive).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim();
+        Self::ALL
+            .into_iter()
+            .find(|m| m.as_str().eq_ignore_ascii_case(name))
+    }
+
+    /// Parse a comma-separated list of mode names, skipping unknown entries.
+    #[must_use]
+    pub fn parse_list(list: &str) -> Vec<Self> {
+        list.split(',').filter_map(Self::parse).collect()
+    }
+}
+
+/// How often calls to one tool fail.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolErrorRate {
+    /// Tool name.
+    pub tool: String,
+    /// Probability that a call fails, from 0.0 to 1.0.
+    pub rate: f64,
+}
+
+impl ToolErrorRate {
+    /// Parse a `tool:rate` entry; the rate must be between 0 and 1.
+    #[must_use]
+    pub fn parse(entry: &str) -> Option<Self> {
+        let (tool, rate) = entry.trim().rsplit_once(':')?;
+        let tool = tool.trim();
+        let rate: f64 = rate.trim().parse().ok()?;
+        (!tool.is_empty() && (0.0..=1.0).contains(&rate)).then(|| Self {
+            tool: tool.to_string(),
+            rate,
+        })
+    }
+
+    /// Parse a comma-separated list of `tool:rate` entries, skipping invalid
+    /// ones.
+    #[must_use]
+    pub fn parse_list(list: &str) -> Vec<Self> {
+        list.split(',').filter_map(Self::parse).collect()
+    }
+}
+
+/// Random failures of tool calls, by tool.
+#[derive(Debug)]
+pub struct ToolFaults {
+    rates: HashMap<String, f64>,
+    rng: Mutex<StdRng>,
+}
+
+impl ToolFaults {
+    /// Fail calls at the given rates; a later entry for a tool wins. A
+    /// `seed` makes the failures repeatable.
+    #[must_use]
+    pub fn new(rates: &[ToolErrorRate], seed: Option<u64>) -> Self {
+        let rng = seed.map_or_else(StdRng::from_os_rng, StdRng::seed_from_u64);
+        Self {
+            rates: rates
+                .iter()
+                .map(|r| (r.tool.clone(), r.rate.clamp(0.0, 1.0)))
+                .collect(),
+            rng: Mutex::new(rng),
+        }
+    }
+
+    /// Whether the current call to `tool` should fail.
+    #[must_use]
+    pub fn should_fail(&self, tool: &str) -> bool {
+        self.rates.get(tool).is_some_and(|&rate| {
+            self.rng
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .random_bool(rate)
+        })
+    }
+}
+
+/// Shared state of [`response_chaos_middleware`].
+#[derive(Debug)]
+pub struct ChaosState {
+    modes: Vec<ResponseChaos>,
+    rate: f64,
+    rng: Mutex<StdRng>,
+    /// Sessions with a response held back, and the signal that releases it.
+    held: Mutex<HashMap<String, Arc<Notify>>>,
+}
+
+impl ChaosState {
+    /// Disturb responses with `modes`, each picking a response with
+    /// probability `rate` (clamped to 0.0–1.0). A `seed` makes the picks
+    /// repeatable.
+    #[must_use]
+    pub fn new(modes: Vec<ResponseChaos>, rate: f64, seed: Option<u64>) -> Self {
+        let rng = seed.map_or_else(StdRng::from_os_rng, StdRng::seed_from_u64);
+        Self {
+            modes,
+            rate: rate.clamp(0.0, 1.0),
+            rng: Mutex::new(rng),
+            held: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether `mode` is configured and picked for the next response.
+    fn roll(&self, mode: ResponseChaos) -> bool {
+        self.modes.contains(&mode)
+            && self
+                .rng
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .random_bool(self.rate)
+    }
+
+    /// Hold a response of `session` back, unless another one already is.
+    fn hold(&self, session: &str) -> Option<Arc<Notify>> {
+        let mut held = self
+            .held
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if held.contains_key(session) {
+            return None;
+        }
+        let release = Arc::new(Notify::new());
+        held.insert(session.to_string(), Arc::clone(&release));
+        drop(held);
+        Some(release)
+    }
+
+    /// Stop holding the response of `session` back.
+    fn unhold(&self, session: &str) {
+        self.held
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(session);
+    }
+
+    /// Release the held response of `session`, if any.
+    fn release(&self, session: &str) {
+        let held = self
+            .held
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(release) = held.get(session) {
+            release.notify_one();
+        }
+    }
+}
+
+/// Middleware applying [`ChaosState`] to event-stream responses.
+pub async fn response_chaos_middleware(
+    State(chaos): State<Arc<ChaosState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let (parts, body) = request.into_parts();
+    let session = crate::sessions::session_key(Some(&parts)).to_string();
+    let response = next.run(Request::from_parts(parts, body)).await;
+    let is_event_stream = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/event-stream"));
+    if !is_event_stream {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let state = StreamState {
+        body: body.into_data_stream(),
+        buffer: String::new(),
+        ready: VecDeque::new(),
+        done: false,
+        released: false,
+    };
+    let events = futures_util::stream::unfold(state, move |mut state| {
+        let chaos = Arc::clone(&chaos);
+        let session = session.clone();
+        async move {
+            // The previous response has been handed to the connection
+            if std::mem::take(&mut state.released) {
+                chaos.release(&session);
+            }
+            loop {
+                if let Some((event, is_response)) = state.ready.pop_front() {
+                    state.released = is_response;
+                    return Some((Ok::<_, axum::Error>(Bytes::from(event)), state));
+                }
+                if state.done {
+                    return None;
+                }
+                match state.body.next().await {
+                    Some(Ok(chunk)) => {
+                        state.buffer.push_str(&String::from_utf8_lossy(&chunk));
+                        while let Some(end) = state.buffer.find("\n\n") {
+                            let event: String = state.buffer.drain(..end + 2).collect();
+                            disturb(&chaos, &session, event, &mut state.ready).await;
+                        }
+                    }
+                    Some(Err(e)) => {
+                        state.done = true;
+                        return Some((Err(e), state));
+                    }
+                    None => {
+                        state.done = true;
+                        if !state.buffer.is_empty() {
+                            state
+                                .ready
+                                .push_back((std::mem::take(&mut state.buffer), false));
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    Response::from_parts(parts, Body::from_stream(events))
+}
+
+/// Progress of one event stream through [`response_chaos_middleware`].
+struct StreamState {
+    body: axum::body::BodyDataStream,
+    buffer: String,
+    /// Events to send, each flagged if it is a response.
+    ready: VecDeque<(String, bool)>,
+    done: bool,
+    /// A response was just sent; release any response waiting on it.
+    released: bool,
+}
+
+/// Queue one complete event, disturbed if it is a response and is picked.
+async fn disturb(
+    chaos: &ChaosState,
+    session: &str,
+    event: String,
+    ready: &mut VecDeque<(String, bool)>,
+) {
+    let Some(id) = response_id(&event) else {
+        ready.push_back((event, false));
+        return;
+    };
+    if chaos.roll(ResponseChaos::Reorder)
+        && let Some(release) = chaos.hold(session)
+    {
+        tracing::info!(%id, "Chaos: holding response back for a later one");
+        let overtaken = tokio::time::timeout(REORDER_MAX_DELAY, release.notified())
+            .await
+            .is_ok();
+        chaos.unhold(session);
+        tracing::debug!(%id, overtaken, "Chaos: releasing held response");
+    }
+    if chaos.roll(ResponseChaos::Duplicate) {
+        tracing::info!(%id, "Chaos: duplicating response");
+        ready.push_back((event.clone(), true));
+    }
+    ready.push_back((event, true));
+}
+
+/// The id of the JSON-RPC response carried by an SSE event, if it is one.
+fn response_id(event: &str) -> Option<serde_json::Value> {
+    let data: Vec<&str> = event
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| data.strip_prefix(' ').unwrap_or(data))
+        .collect();
+    let message: serde_json::Value = serde_json::from_str(&data.join("\n")).ok()?;
+    let is_response = message.get("method").is_none()
+        && (message.get("result").is_some() || message.get("error").is_some());
+    is_response.then(|| message.get("id").cloned()).flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(
+            ResponseChaos::parse_list("duplicate, REORDER,bogus"),
+            vec![ResponseChaos::Duplicate, ResponseChaos::Reorder]
+        );
+    }
+
+    #[test]
+    fn test_tool_error_rate_parse_list() {
+        assert_eq!(
+            ToolErrorRate::parse_list("add:0.25, echo : 1,bad:2,:0.5,nope"),
+            vec![
+                ToolErrorRate {
+                    tool: "add".into(),
+                    rate: 0.25
+                },
+                ToolErrorRate {
+                    tool: "echo".into(),
+                    rate: 1.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tool_faults() {
+        let faults = ToolFaults::new(&ToolErrorRate::parse_list("add:1,echo:0"), Some(3));
+        assert!(faults.should_fail("add"));
+        assert!(!faults.should_fail("echo"));
+        assert!(!faults.should_fail("subtract"));
+    }
+
+    #[test]
+    fn test_response_id() {
+        let response = "id: 3\ndata: {\"jsonrpc\":\"2.0\",\"id\":7,\"result\":{}}\n\n";
+        assert_eq!(response_id(response), Some(serde_json::json!(7)));
+        let notification = "data: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/message\"}\n\n";
+        assert_eq!(response_id(notification), None);
+        let request = "data: {\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"ping\"}\n\n";
+        assert_eq!(response_id(request), None);
+    }
+
+    #[test]
+    fn test_roll_respects_modes_and_rate() {
+        let chaos = ChaosState::new(vec![ResponseChaos::Duplicate], 1.0, Some(1));
+        assert!(chaos.roll(ResponseChaos::Duplicate));
+        assert!(!chaos.roll(ResponseChaos::Reorder));
+        let chaos = ChaosState::new(vec![ResponseChaos::Duplicate], 0.0, Some(1));
+        assert!(!chaos.roll(ResponseChaos::Duplicate));
+    }
+
+    #[test]
+    fn test_only_one_response_held_per_session() {
+        let chaos = ChaosState::new(vec![ResponseChaos::Reorder], 1.0, None);
+        assert!(chaos.hold("a").is_some());
+        assert!(chaos.hold("a").is_none());
+        assert!(chaos.hold("b").is_some());
+        chaos.unhold("a");
+        assert!(chaos.hold("a").is_some());
+    }
+}
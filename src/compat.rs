@@ -0,0 +1,309 @@
+//! Compatibility shims for clients built against older protocol revisions.
+//!
+//! With `MCP_COMPAT` set to an older revision, the server advertises that
+//! revision in its `initialize` result and upgrades older request layouts
+//! to the current schema before the SDK parses them:
+//!
+//! - `initialize` without `capabilities`, `clientInfo`, `clientInfo.version`,
+//!   or `protocolVersion` gets defaults (`{}`, `unknown`, `0.0.0`, and the
+//!   `MCP_COMPAT` revision).
+//! - Snake-case parameter names (`protocol_version`, `client_info`,
+//!   `progress_token`, `request_id`) are renamed to camelCase.
+//! - A `progressToken` sent directly in `params`, as some clients did before
+//!   `_meta`, moves to `params._meta.progressToken`.
+//! - `tools/call` `arguments` sent as a JSON-encoded string are decoded.
+//! - The legacy `initialized` notification becomes `notifications/initialized`.
+//!
+//! Bodies that are not JSON, or that need no shim, pass through unchanged.
+
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use rmcp::model::ProtocolVersion;
+use serde_json::{Map, Value, json};
+
+/// Older protocol revision targeted by `MCP_COMPAT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompatTarget {
+    /// The first published revision, `2024-11-05`.
+    V2024_11_05,
+    /// The Streamable HTTP revision, `2025-03-26`.
+    V2025_03_26,
+}
+
+impl CompatTarget {
+    /// Parse a revision date: `2024-11-05` or `2025-03-26`.
+    #[must_use]
+    pub fn parse(revision: &str) -> Option<Self> {
+        match revision.trim() {
+            "2024-11-05" => Some(Self::V2024_11_05),
+            "2025-03-26" => Some(Self::V2025_03_26),
+            _ => None,
+        }
+    }
+
+    /// The protocol version advertised for this revision.
+    #[must_use]
+    pub const fn protocol_version(self) -> ProtocolVersion {
+        match self {
+            Self::V2024_11_05 => ProtocolVersion::V_2024_11_05,
+            Self::V2025_03_26 => ProtocolVersion::V_2025_03_26,
+        }
+    }
+}
+
+/// Snake-case parameter names and their current camel-case spelling.
+const RENAMED_PARAMS: &[(&str, &str)] = &[
+    ("protocol_version", "protocolVersion"),
+    ("client_info", "clientInfo"),
+    ("progress_token", "progressToken"),
+    ("request_id", "requestId"),
+];
+
+/// Upgrade one JSON-RPC message (or a batch of them) in place.
+///
+/// Returns the names of the shims applied, empty if the message already
+/// matched the current schema.
+pub fn upgrade(target: CompatTarget, message: &mut Value) -> Vec<&'static str> {
+    let mut applied = Vec::new();
+    match message {
+        Value::Array(batch) => {
+            for item in batch {
+                upgrade_message(target, item, &mut applied);
+            }
+        }
+        item => upgrade_message(target, item, &mut applied),
+    }
+    applied
+}
+
+fn upgrade_message(target: CompatTarget, message: &mut Value, applied: &mut Vec<&'static str>) {
+    let Some(message) = message.as_object_mut() else {
+        return;
+    };
+    if message.get("method").and_then(Value::as_str) == Some("initialized") {
+        message.insert("method".to_string(), json!("notifications/initialized"));
+        applied.push("initialized_method");
+    }
+    let method = message
+        .get("method")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let Some(Value::Object(params)) = message.get_mut("params") else {
+        if method == "initialize" {
+            let mut params = Map::new();
+            upgrade_initialize(target, &mut params, applied);
+            message.insert("params".to_string(), Value::Object(params));
+        }
+        return;
+    };
+
+    for (old, new) in RENAMED_PARAMS {
+        if !params.contains_key(*new)
+            && let Some(value) = params.remove(*old)
+        {
+            params.insert((*new).to_string(), value);
+            applied.push("snake_case_params");
+        }
+    }
+    // `notifications/progress` carries its token in params by design
+    if method != "notifications/progress"
+        && let Some(token) = params.remove("progressToken")
+    {
+        let meta = params
+            .entry("_meta")
+            .or_insert_with(|| Value::Object(Map::new()));
+        if let Some(meta) = meta.as_object_mut() {
+            meta.entry("progressToken").or_insert(token);
+        }
+        applied.push("progress_token_outside_meta");
+    }
+    match method.as_str() {
+        "initialize" => upgrade_initialize(target, params, applied),
+        "tools/call" => upgrade_tool_call(params, applied),
+        _ => {}
+    }
+}
+
+fn upgrade_initialize(
+    target: CompatTarget,
+    params: &mut Map<String, Value>,
+    applied: &mut Vec<&'static str>,
+) {
+    if !params.contains_key("protocolVersion") {
+        params.insert(
+            "protocolVersion".to_string(),
+            json!(target.protocol_version().as_str()),
+        );
+        applied.push("default_protocol_version");
+    }
+    if !params.contains_key("capabilities") {
+        params.insert("capabilities".to_string(), json!({}));
+        applied.push("default_capabilities");
+    }
+    let info = params
+        .entry("clientInfo")
+        .or_insert_with(|| json!({ "name": "unknown" }));
+    if let Some(info) = info.as_object_mut()
+        && !info.contains_key("version")
+    {
+        info.insert("version".to_string(), json!("0.0.0"));
+        applied.push("default_client_info");
+    }
+}
+
+fn upgrade_tool_call(params: &mut Map<String, Value>, applied: &mut Vec<&'static str>) {
+    let Some(Value::String(encoded)) = params.get("arguments") else {
+        return;
+    };
+    if let Ok(decoded @ Value::Object(_)) = serde_json::from_str(encoded) {
+        params.insert("arguments".to_string(), decoded);
+        applied.push("string_arguments");
+    }
+}
+
+/// Axum middleware applying [`upgrade`] to `POST /mcp` bodies.
+pub async fn compat_middleware(
+    State(target): State<CompatTarget>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+
+    let (mut parts, body) = request.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    let Ok(mut message) = serde_json::from_slice::<Value>(&bytes) else {
+        return next
+            .run(Request::from_parts(parts, Body::from(bytes)))
+            .await;
+    };
+    let applied = upgrade(target, &mut message);
+    if applied.is_empty() {
+        return next
+            .run(Request::from_parts(parts, Body::from(bytes)))
+            .await;
+    }
+
+    tracing::debug!(?applied, "Upgraded legacy request layout");
+    let upgraded = serde_json::to_vec(&message).unwrap_or_else(|_| bytes.to_vec());
+    parts.headers.remove(axum::http::header::CONTENT_LENGTH);
+    next.run(Request::from_parts(parts, Body::from(upgraded)))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_targets() {
+        assert_eq!(
+            CompatTarget::parse("2024-11-05"),
+            Some(CompatTarget::V2024_11_05)
+        );
+        assert_eq!(
+            CompatTarget::parse(" 2025-03-26 "),
+            Some(CompatTarget::V2025_03_26)
+        );
+        assert_eq!(CompatTarget::parse("2025-06-18"), None);
+        assert_eq!(CompatTarget::parse("latest"), None);
+    }
+
+    #[test]
+    fn test_minimal_initialize_gets_defaults() {
+        let mut message = json!({ "jsonrpc": "2.0", "id": 0, "method": "initialize" });
+        let applied = upgrade(CompatTarget::V2024_11_05, &mut message);
+        assert_eq!(
+            applied,
+            [
+                "default_protocol_version",
+                "default_capabilities",
+                "default_client_info"
+            ]
+        );
+        assert_eq!(
+            message["params"],
+            json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": { "name": "unknown", "version": "0.0.0" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_snake_case_and_progress_token_upgraded() {
+        let mut message = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "echo",
+                "arguments": "{\"text\":\"hi\"}",
+                "progress_token": "p1"
+            }
+        });
+        let applied = upgrade(CompatTarget::V2025_03_26, &mut message);
+        assert_eq!(
+            applied,
+            [
+                "snake_case_params",
+                "progress_token_outside_meta",
+                "string_arguments"
+            ]
+        );
+        assert_eq!(
+            message["params"],
+            json!({
+                "name": "echo",
+                "arguments": { "text": "hi" },
+                "_meta": { "progressToken": "p1" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_current_layout_unchanged() {
+        let original = json!([
+            { "jsonrpc": "2.0", "method": "notifications/initialized" },
+            {
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "tools/call",
+                "params": { "name": "echo", "arguments": { "text": "hi" }, "_meta": { "progressToken": 1 } }
+            }
+        ]);
+        let mut message = original.clone();
+        assert!(upgrade(CompatTarget::V2024_11_05, &mut message).is_empty());
+        assert_eq!(message, original);
+    }
+
+    #[test]
+    fn test_progress_notification_keeps_token() {
+        let mut message = json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/progress",
+            "params": { "progressToken": "p1", "progress": 1 }
+        });
+        assert!(upgrade(CompatTarget::V2024_11_05, &mut message).is_empty());
+        assert_eq!(message["params"]["progressToken"], "p1");
+    }
+
+    #[test]
+    fn test_legacy_initialized_notification_renamed() {
+        let mut message = json!({ "jsonrpc": "2.0", "method": "initialized" });
+        assert_eq!(
+            upgrade(CompatTarget::V2024_11_05, &mut message),
+            ["initialized_method"]
+        );
+        assert_eq!(message["method"], "notifications/initialized");
+    }
+}
@@ -0,0 +1,107 @@
+//! Artificial latency and rate limiting for `completion/complete`.
+//!
+//! Clients that complete as the user types should debounce keystrokes and
+//! cancel stale requests. `MCP_COMPLETION_DELAY` holds every completion
+//! back (cancelled requests stop waiting), and `MCP_COMPLETION_RATE_LIMIT`
+//! rejects a session's requests beyond N in any one-second window with
+//! [`COMPLETION_RATE_LIMITED`], so clients can be tuned against realistic
+//! latency and checked to stay under a limit.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use rmcp::ErrorData;
+use rmcp::model::ErrorCode;
+
+/// JSON-RPC error code of a completion rejected by the rate limit.
+pub const COMPLETION_RATE_LIMITED: ErrorCode = ErrorCode(-32029);
+
+/// Window the rate limit counts requests in.
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// Per-session sliding-window limit on completion requests.
+#[derive(Debug, Default)]
+pub struct CompletionLimiter {
+    /// Requests allowed per window; `None` allows any number.
+    limit: Option<u32>,
+    /// Times of the accepted requests in the current window, by session.
+    accepted: Mutex<HashMap<String, VecDeque<Instant>>>,
+}
+
+impl CompletionLimiter {
+    /// Create a limiter allowing `limit` requests per second and session.
+    #[must_use]
+    pub fn new(limit: Option<u32>) -> Self {
+        Self {
+            limit,
+            accepted: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Count a request of `session` against the limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`COMPLETION_RATE_LIMITED`] error with `{"limit",
+    /// "retry_after_ms"}` as data if the session already made `limit`
+    /// requests in the last second. Rejected requests are not counted.
+    pub fn check(&self, session: &str) -> Result<(), ErrorData> {
+        let Some(limit) = self.limit else {
+            return Ok(());
+        };
+        let now = Instant::now();
+        let mut accepted = self.accepted.lock().unwrap_or_else(PoisonError::into_inner);
+        let times = accepted.entry(session.to_string()).or_default();
+        while times
+            .front()
+            .is_some_and(|&t| now.duration_since(t) >= WINDOW)
+        {
+            times.pop_front();
+        }
+        if times.len() < limit as usize {
+            times.push_back(now);
+            return Ok(());
+        }
+        let retry_after = times.front().map_or(Duration::ZERO, |&t| {
+            WINDOW.saturating_sub(now.duration_since(t))
+        });
+        drop(accepted);
+        Err(ErrorData::new(
+            COMPLETION_RATE_LIMITED,
+            format!("Completion rate limit exceeded: {limit} requests per second"),
+            Some(serde_json::json!({
+                "limit": limit,
+                "retry_after_ms": u64::try_from(retry_after.as_millis()).unwrap_or(u64::MAX),
+            })),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlimited_by_default() {
+        let limiter = CompletionLimiter::default();
+        for _ in 0..1000 {
+            assert!(limiter.check("a").is_ok());
+        }
+    }
+
+    #[test]
+    fn test_limit_is_per_session() {
+        let limiter = CompletionLimiter::new(Some(2));
+        assert!(limiter.check("a").is_ok());
+        assert!(limiter.check("a").is_ok());
+
+        let err = limiter.check("a").unwrap_err();
+        assert_eq!(err.code, COMPLETION_RATE_LIMITED);
+        let data = err.data.unwrap();
+        assert_eq!(data["limit"], 2);
+        assert!(data["retry_after_ms"].as_u64().unwrap() <= 1000);
+
+        assert!(limiter.check("b").is_ok());
+    }
+}
@@ -1,10 +1,43 @@
 //! Configuration management for the MCP Test Server.
+//!
+//! [`Config::from_env`] is forgiving: values that don't parse fall back to
+//! their defaults. [`Config::validate_vars`] is the strict counterpart run at
+//! startup (and by `--check-config`). It reports every invalid value, unknown
+//! `MCP_*` variable, and conflicting setting at once.
 
+use std::collections::HashMap;
 use std::env;
+use std::fmt;
 use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use rmcp::model::ExperimentalCapabilities;
+
+use crate::advertise::{self, Capability};
+use crate::auth::AuthFailureMode;
+use crate::batch::BatchPolicy;
+use crate::chaos::{ResponseChaos, ToolErrorRate};
+use crate::compat::CompatTarget;
+use crate::content_encoding::ResponseCompression;
+use crate::cors::{self, CorsOrigins, CorsPolicy};
+use crate::i18n::{ErrorLang, Locale};
+use crate::icons::ServerIcons;
+use crate::oauth::OAuthFailure;
+use crate::proxy;
+use crate::resources::static_resources::UiAssets;
+use crate::resources::synthetic_resources::MAX_SYNTHETIC_RESOURCES;
+use crate::resources::ui_csp::UiCsp;
+use crate::sse::SseOversizePolicy;
+use crate::supervision::PanicPolicy;
+use crate::tool_errors::ToolErrorMode;
+use crate::tools::fetch::MockRoute;
+use crate::unknown::{UnknownNotificationPolicy, UnknownToolPolicy};
+use crate::validation::SchemaErrorDetail;
 
 /// Server configuration loaded from environment variables.
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)] // independent env toggles
 pub struct Config {
     /// Server bind address (default: 0.0.0.0)
     pub host: IpAddr,
@@ -14,6 +47,125 @@ pub struct Config {
     pub api_key: Option<String>,
     /// Log level (default: info)
     pub log_level: String,
+    /// Reject tool arguments not declared in the tool's input schema (default: false)
+    pub strict_args: bool,
+    /// Reject `/mcp` requests after `initialize` without the negotiated
+    /// `MCP-Protocol-Version` header (default: false)
+    pub strict_protocol_version: bool,
+    /// Whether JSON-RPC batches on `/mcp` are served (default: spec)
+    pub batch: BatchPolicy,
+    /// Match tool names and resource URIs ignoring case and surrounding whitespace (default: false)
+    pub lenient_names: bool,
+    /// Track sessions via `Mcp-Session-Id` and keep a server-to-client stream open (default: false)
+    pub stateful: bool,
+    /// Interval between `test://dynamic/ticker` updates in milliseconds (default: 1000)
+    pub ticker_interval_ms: u64,
+    /// Failure modes injected into the OAuth mock (default: none)
+    pub oauth_failures: Vec<OAuthFailure>,
+    /// Maximum size of a single SSE event's data payload in bytes (default: unlimited)
+    pub max_sse_event_bytes: Option<usize>,
+    /// What to do with SSE events over `max_sse_event_bytes` (default: reject)
+    pub sse_oversize_policy: SseOversizePolicy,
+    /// Require access tokens issued by the built-in OAuth mock on `/mcp` (default: false)
+    pub oauth: bool,
+    /// Language for error messages (default: English)
+    pub error_lang: ErrorLang,
+    /// How missing or invalid API keys are reported (default: 401 + `WWW-Authenticate`)
+    pub auth_failure_mode: AuthFailureMode,
+    /// Namespace for deterministic UUID v5 `random_uuid` values (default: none, random v4)
+    pub uuid_namespace: Option<uuid::Uuid>,
+    /// Seed for generated fixtures and the seeded random tools (default: none,
+    /// a random seed per call)
+    pub seed: Option<u64>,
+    /// Maximum number of concurrent stateful sessions (default: unlimited)
+    pub max_sessions: Option<usize>,
+    /// Idle time after which a stateful session expires (default: never)
+    pub session_ttl: Option<Duration>,
+    /// Refuse client `DELETE /mcp` session termination with `405` (default: false)
+    pub deny_session_delete: bool,
+    /// Older protocol revision to advertise and accept request layouts from (default: none)
+    pub compat: Option<CompatTarget>,
+    /// How long to keep serving existing sessions after a shutdown signal (default: none, stop at once)
+    pub drain_period: Option<Duration>,
+    /// File to append every JSON-RPC exchange on `POST /mcp` to (default: none)
+    pub record: Option<PathBuf>,
+    /// Recording to answer `POST /mcp` from instead of running tools (default: none)
+    pub replay: Option<PathBuf>,
+    /// Canned routes of the `fetch_url` mock upstream, matched before the
+    /// built-in ones (default: none)
+    pub mock_routes: Vec<MockRoute>,
+    /// Time a `regex_*` tool call may run before it fails, in milliseconds (default: 1000)
+    pub regex_timeout_ms: u64,
+    /// Largest serialized tool result the server returns, in bytes; larger
+    /// results become an error (default: unlimited)
+    pub max_response_bytes: Option<usize>,
+    /// How a panicking tool handler is reported (default: internal error)
+    pub panic_policy: PanicPolicy,
+    /// Time a tool call may run before it is answered with a timeout error
+    /// (default: unlimited)
+    pub request_timeout: Option<Duration>,
+    /// Ways of disturbing `POST /mcp` responses (default: none)
+    pub chaos_responses: Vec<ResponseChaos>,
+    /// Probability that a response is picked by each chaos mode (default: 0.1)
+    pub chaos_rate: f64,
+    /// Flush SSE responses in chunks of at most this many bytes (default: off)
+    pub sse_drip_bytes: Option<usize>,
+    /// Pause between dripped SSE chunks (default: 10ms)
+    pub sse_drip_delay: Duration,
+    /// Interval of SSE keep-alive comments in seconds; 0 disables them (default: 15)
+    pub sse_keepalive_secs: u64,
+    /// Interval of server-initiated `ping` requests to each session (default: none)
+    pub ping_interval: Option<Duration>,
+    /// Tools that fail intermittently, with their failure rates (default: none)
+    pub tool_error_rates: Vec<ToolErrorRate>,
+    /// How failed tool calls are reported (default: native)
+    pub tool_error_mode: ToolErrorMode,
+    /// How much invalid params errors say about schema mismatches (default: opaque)
+    pub schema_errors: SchemaErrorDetail,
+    /// How calls to unknown tools are answered (default: error)
+    pub unknown_tool: UnknownToolPolicy,
+    /// How notifications with unknown methods are answered (default: ignore)
+    pub unknown_notification: UnknownNotificationPolicy,
+    /// Number of generated `test://synthetic/{n}` resources (default: 0)
+    pub synthetic_resources: usize,
+    /// Locale of prompt titles, descriptions, and `greeting_localized` (default: English)
+    pub locale: Locale,
+    /// Delay before answering `completion/complete` (default: none)
+    pub completion_delay: Option<Duration>,
+    /// Most `completion/complete` requests per second and session (default: unlimited)
+    pub completion_rate_limit: Option<u32>,
+    /// Server icons advertised in `initialize` (default: SVG and PNGs)
+    pub server_icons: ServerIcons,
+    /// `instructions` in `initialize`; empty omits the field (default: built-in text)
+    pub instructions: Option<String>,
+    /// `serverInfo.name` in `initialize` (default: `mcp-test-server`)
+    pub server_name: Option<String>,
+    /// `serverInfo.title` in `initialize`; empty omits the field (default: `MCP Test Server`)
+    pub server_title: Option<String>,
+    /// `serverInfo.version` in `initialize` (default: the crate version)
+    pub server_version: Option<String>,
+    /// Capabilities advertised in `initialize` (default: all of them)
+    pub capabilities: Option<Vec<Capability>>,
+    /// `capabilities.experimental` in `initialize` (default: none)
+    pub experimental_capabilities: Option<ExperimentalCapabilities>,
+    /// How the `ui://` apps get their libraries (default: CDN tags)
+    pub ui_assets: UiAssets,
+    /// CSP declared as `_meta.ui.csp` of the `ui://` apps (default: none)
+    pub ui_csp: UiCsp,
+    /// Serve the apps at `/apps/{app}` with a `Content-Security-Policy` header (default: false)
+    pub hosted_apps: bool,
+    /// Compression of HTTP responses (default: off)
+    pub compression: ResponseCompression,
+    /// CORS answers of the HTTP endpoints (default: any origin, method, and header)
+    pub cors: CorsPolicy,
+    /// PEM certificate chain to serve HTTPS with (default: none, plain HTTP)
+    pub tls_cert: Option<PathBuf>,
+    /// PEM private key of [`Config::tls_cert`] (default: none)
+    pub tls_key: Option<PathBuf>,
+    /// Base URL clients reach the server at through a reverse proxy, used
+    /// for the OAuth issuer and metadata URLs (default: none, derived from
+    /// forwarded headers or the bind address)
+    pub public_url: Option<String>,
 }
 
 impl Config {
@@ -44,6 +196,7 @@ impl Config {
     /// This function will panic if the default host address "0.0.0.0" fails to parse,
     /// which should never happen under normal circumstances.
     #[must_use]
+    #[allow(clippy::too_many_lines)] // one field per variable
     pub fn from_env() -> Self {
         Self {
             host: env::var("MCP_HOST")
@@ -56,65 +209,1322 @@ impl Config {
                 .unwrap_or(3000),
             api_key: env::var("MCP_API_KEY").ok().filter(|s| !s.is_empty()),
             log_level: env::var("MCP_LOG_LEVEL").unwrap_or_else(|_| "info".to_string()),
+            strict_args: env_flag("MCP_STRICT_ARGS"),
+            strict_protocol_version: env_flag("MCP_STRICT_PROTOCOL_VERSION"),
+            batch: env::var("MCP_BATCH")
+                .ok()
+                .and_then(|s| BatchPolicy::parse(&s))
+                .unwrap_or_default(),
+            lenient_names: env_flag("MCP_LENIENT_NAMES"),
+            stateful: env_flag("MCP_STATEFUL"),
+            ticker_interval_ms: env::var("MCP_TICKER_INTERVAL_MS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .filter(|&ms| ms > 0)
+                .unwrap_or(1000),
+            oauth_failures: env::var("MCP_OAUTH_FAILURES")
+                .map(|s| OAuthFailure::parse_list(&s))
+                .unwrap_or_default(),
+            max_sse_event_bytes: env::var("MCP_MAX_SSE_EVENT_BYTES")
+                .ok()
+                .and_then(|s| s.parse().ok()),
+            sse_oversize_policy: env::var("MCP_SSE_OVERSIZE_POLICY")
+                .ok()
+                .and_then(|s| SseOversizePolicy::parse(&s))
+                .unwrap_or_default(),
+            oauth: env_flag("MCP_OAUTH"),
+            error_lang: env::var("MCP_ERROR_LANG")
+                .ok()
+                .and_then(|s| ErrorLang::parse(&s))
+                .unwrap_or_default(),
+            auth_failure_mode: env::var("MCP_AUTH_FAILURE_MODE")
+                .ok()
+                .and_then(|s| AuthFailureMode::parse(&s))
+                .unwrap_or_default(),
+            uuid_namespace: env::var("MCP_UUID_NAMESPACE")
+                .ok()
+                .map(|s| uuid_namespace(&s)),
+            seed: env::var("MCP_SEED")
+                .ok()
+                .and_then(|s| s.trim().parse().ok()),
+            max_sessions: env::var("MCP_MAX_SESSIONS")
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+                .filter(|&max| max > 0),
+            session_ttl: env::var("MCP_SESSION_TTL")
+                .ok()
+                .and_then(|s| parse_duration(&s)),
+            deny_session_delete: env_flag("MCP_DENY_SESSION_DELETE"),
+            compat: env::var("MCP_COMPAT")
+                .ok()
+                .and_then(|s| CompatTarget::parse(&s)),
+            drain_period: env::var("MCP_DRAIN_SECS")
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+                .filter(|&secs| secs > 0)
+                .map(Duration::from_secs),
+            record: env_path("MCP_RECORD"),
+            replay: env_path("MCP_REPLAY"),
+            mock_routes: env_path("MCP_MOCK_ROUTES")
+                .and_then(|path| MockRoute::load(&path).ok())
+                .unwrap_or_default(),
+            regex_timeout_ms: env::var("MCP_REGEX_TIMEOUT_MS")
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+                .filter(|&ms| ms > 0)
+                .unwrap_or(1000),
+            max_response_bytes: env::var("MCP_MAX_RESPONSE_BYTES")
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+                .filter(|&bytes| bytes > 0),
+            panic_policy: env::var("MCP_PANIC_POLICY")
+                .ok()
+                .and_then(|s| PanicPolicy::parse(&s))
+                .unwrap_or_default(),
+            request_timeout: env::var("MCP_REQUEST_TIMEOUT")
+                .ok()
+                .and_then(|s| parse_duration(&s)),
+            chaos_responses: env::var("MCP_CHAOS_RESPONSES")
+                .map(|s| ResponseChaos::parse_list(&s))
+                .unwrap_or_default(),
+            chaos_rate: env::var("MCP_CHAOS_RATE")
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+                .filter(|rate| (0.0..=1.0).contains(rate))
+                .unwrap_or(DEFAULT_CHAOS_RATE),
+            sse_drip_bytes: env::var("MCP_SSE_DRIP_BYTES")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .filter(|&bytes| bytes > 0),
+            sse_drip_delay: env::var("MCP_SSE_DRIP_DELAY")
+                .ok()
+                .and_then(|s| parse_duration(&s))
+                .unwrap_or(DEFAULT_SSE_DRIP_DELAY),
+            sse_keepalive_secs: env::var("MCP_SSE_KEEPALIVE_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(DEFAULT_SSE_KEEPALIVE_SECS),
+            ping_interval: env::var("MCP_PING_INTERVAL")
+                .ok()
+                .and_then(|s| parse_duration(&s)),
+            tool_error_rates: env::var("MCP_TOOL_ERROR_RATE")
+                .map(|s| ToolErrorRate::parse_list(&s))
+                .unwrap_or_default(),
+            tool_error_mode: env::var("MCP_TOOL_ERROR_MODE")
+                .ok()
+                .and_then(|s| ToolErrorMode::parse(&s))
+                .unwrap_or_default(),
+            schema_errors: env::var("MCP_SCHEMA_ERRORS")
+                .ok()
+                .and_then(|s| SchemaErrorDetail::parse(&s))
+                .unwrap_or_default(),
+            unknown_tool: env::var("MCP_UNKNOWN_TOOL")
+                .ok()
+                .and_then(|s| UnknownToolPolicy::parse(&s))
+                .unwrap_or_default(),
+            unknown_notification: env::var("MCP_UNKNOWN_NOTIFICATION")
+                .ok()
+                .and_then(|s| UnknownNotificationPolicy::parse(&s))
+                .unwrap_or_default(),
+            synthetic_resources: env::var("MCP_SYNTHETIC_RESOURCES")
+                .ok()
+                .and_then(|s| s.parse::<usize>().ok())
+                .unwrap_or_default()
+                .min(MAX_SYNTHETIC_RESOURCES),
+            locale: env::var("MCP_LOCALE")
+                .ok()
+                .and_then(|s| Locale::parse(&s))
+                .unwrap_or_default(),
+            completion_delay: env::var("MCP_COMPLETION_DELAY")
+                .ok()
+                .and_then(|s| parse_duration(&s)),
+            completion_rate_limit: env::var("MCP_COMPLETION_RATE_LIMIT")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .filter(|&limit| limit > 0),
+            server_icons: env::var("MCP_SERVER_ICONS")
+                .ok()
+                .and_then(|s| ServerIcons::parse(&s))
+                .unwrap_or_default(),
+            instructions: env::var("MCP_INSTRUCTIONS").ok(),
+            server_name: env::var("MCP_SERVER_NAME").ok().filter(|s| !s.is_empty()),
+            server_title: env::var("MCP_SERVER_TITLE").ok(),
+            server_version: env::var("MCP_SERVER_VERSION")
+                .ok()
+                .filter(|s| !s.is_empty()),
+            capabilities: env::var("MCP_CAPABILITIES")
+                .ok()
+                .filter(|s| !s.trim().is_empty())
+                .map(|s| Capability::parse_list(&s)),
+            experimental_capabilities: env_path("MCP_EXPERIMENTAL_CAPABILITIES")
+                .and_then(|path| advertise::load_experimental(&path).ok()),
+            ui_assets: env::var("MCP_UI_ASSETS")
+                .ok()
+                .and_then(|s| UiAssets::parse(&s))
+                .unwrap_or_default(),
+            ui_csp: env::var("MCP_UI_CSP")
+                .ok()
+                .and_then(|s| UiCsp::parse(&s))
+                .unwrap_or_default(),
+            hosted_apps: env_flag("MCP_HOSTED_APPS"),
+            compression: env::var("MCP_COMPRESSION")
+                .ok()
+                .and_then(|s| ResponseCompression::parse(&s))
+                .unwrap_or_default(),
+            cors: CorsPolicy {
+                origins: env::var("MCP_CORS_ORIGINS")
+                    .ok()
+                    .and_then(|s| CorsOrigins::parse(&s))
+                    .unwrap_or_default(),
+                methods: env::var("MCP_CORS_METHODS")
+                    .ok()
+                    .and_then(|s| cors::parse_methods(&s))
+                    .flatten(),
+                headers: env::var("MCP_CORS_HEADERS")
+                    .ok()
+                    .and_then(|s| cors::parse_headers(&s))
+                    .flatten(),
+                expose_headers: env::var("MCP_CORS_EXPOSE_HEADERS")
+                    .ok()
+                    .and_then(|s| cors::parse_headers(&s))
+                    .flatten(),
+            },
+            tls_cert: env_path("MCP_TLS_CERT"),
+            tls_key: env_path("MCP_TLS_KEY"),
+            public_url: env::var("MCP_PUBLIC_URL")
+                .ok()
+                .and_then(|s| proxy::parse_public_url(&s)),
+        }
+    }
+
+    /// Check if authentication is required.
+    #[must_use]
+    pub const fn requires_auth(&self) -> bool {
+        self.api_key.is_some()
+    }
+
+    /// Check if the server speaks HTTPS.
+    #[must_use]
+    pub const fn uses_tls(&self) -> bool {
+        self.tls_cert.is_some() && self.tls_key.is_some()
+    }
+
+    /// URL scheme of the server: `https` with TLS, `http` without.
+    #[must_use]
+    pub const fn scheme(&self) -> &'static str {
+        if self.uses_tls() { "https" } else { "http" }
+    }
+
+    /// Validate the `MCP_*` variables of the process environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns every problem found; see [`Config::validate_vars`].
+    pub fn validate_env() -> Result<(), ConfigErrors> {
+        Self::validate_vars(env::vars())
+    }
+
+    /// Validate `MCP_*` variables, as `(name, value)` pairs.
+    ///
+    /// Variables without the `MCP_` prefix are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns all problems at once: values that don't parse, unknown `MCP_*`
+    /// names (with the closest known name as a hint), and settings that
+    /// conflict or have no effect.
+    pub fn validate_vars<I, K, V>(vars: I) -> Result<(), ConfigErrors>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let vars: HashMap<String, String> = vars
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .filter(|(k, _)| k.starts_with("MCP_"))
+            .collect();
+        let mut issues = unknown_vars(&vars);
+        issues.extend(invalid_values(&vars));
+        issues.extend(conflicts(&vars));
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigErrors(issues))
+        }
+    }
+}
+
+/// Every environment variable the server reads.
+pub const ENV_VARS: &[&str] = &[
+    "MCP_HOST",
+    "MCP_PORT",
+    "MCP_API_KEY",
+    "MCP_LOG_LEVEL",
+    "MCP_STRICT_ARGS",
+    "MCP_STRICT_PROTOCOL_VERSION",
+    "MCP_BATCH",
+    "MCP_LENIENT_NAMES",
+    "MCP_STATEFUL",
+    "MCP_TICKER_INTERVAL_MS",
+    "MCP_OAUTH_FAILURES",
+    "MCP_MAX_SSE_EVENT_BYTES",
+    "MCP_SSE_OVERSIZE_POLICY",
+    "MCP_OAUTH",
+    "MCP_ERROR_LANG",
+    "MCP_AUTH_FAILURE_MODE",
+    "MCP_UUID_NAMESPACE",
+    "MCP_SEED",
+    "MCP_MAX_SESSIONS",
+    "MCP_SESSION_TTL",
+    "MCP_DENY_SESSION_DELETE",
+    "MCP_COMPAT",
+    "MCP_DRAIN_SECS",
+    "MCP_RECORD",
+    "MCP_REPLAY",
+    "MCP_MOCK_ROUTES",
+    "MCP_REGEX_TIMEOUT_MS",
+    "MCP_MAX_RESPONSE_BYTES",
+    "MCP_PANIC_POLICY",
+    "MCP_REQUEST_TIMEOUT",
+    "MCP_CHAOS_RESPONSES",
+    "MCP_CHAOS_RATE",
+    "MCP_SSE_DRIP_BYTES",
+    "MCP_SSE_DRIP_DELAY",
+    "MCP_SSE_KEEPALIVE_SECS",
+    "MCP_PING_INTERVAL",
+    "MCP_TOOL_ERROR_RATE",
+    "MCP_TOOL_ERROR_MODE",
+    "MCP_SCHEMA_ERRORS",
+    "MCP_UNKNOWN_TOOL",
+    "MCP_UNKNOWN_NOTIFICATION",
+    "MCP_SYNTHETIC_RESOURCES",
+    "MCP_LOCALE",
+    "MCP_COMPLETION_DELAY",
+    "MCP_COMPLETION_RATE_LIMIT",
+    "MCP_SERVER_ICONS",
+    "MCP_INSTRUCTIONS",
+    "MCP_SERVER_NAME",
+    "MCP_SERVER_TITLE",
+    "MCP_SERVER_VERSION",
+    "MCP_CAPABILITIES",
+    "MCP_EXPERIMENTAL_CAPABILITIES",
+    "MCP_UI_ASSETS",
+    "MCP_UI_CSP",
+    "MCP_HOSTED_APPS",
+    "MCP_COMPRESSION",
+    "MCP_CORS_ORIGINS",
+    "MCP_CORS_METHODS",
+    "MCP_CORS_HEADERS",
+    "MCP_CORS_EXPOSE_HEADERS",
+    "MCP_TLS_CERT",
+    "MCP_TLS_KEY",
+    "MCP_PUBLIC_URL",
+];
+
+/// Default interval of SSE keep-alive comments, in seconds.
+const DEFAULT_SSE_KEEPALIVE_SECS: u64 = 15;
+
+/// Default pause between dripped SSE chunks.
+const DEFAULT_SSE_DRIP_DELAY: Duration = Duration::from_millis(10);
+
+/// Default probability of a response being disturbed by response chaos.
+const DEFAULT_CHAOS_RATE: f64 = 0.1;
+
+/// One problem found by [`Config::validate_vars`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    /// The environment variable at fault.
+    pub name: String,
+    /// What is wrong with it.
+    pub message: String,
+}
+
+impl ConfigIssue {
+    fn new(name: &str, message: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Every problem found in a configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigErrors(pub Vec<ConfigIssue>);
+
+impl fmt::Display for ConfigErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = self.0.len();
+        let plural = if count == 1 { "" } else { "s" };
+        write!(f, "invalid configuration ({count} problem{plural}):")?;
+        for issue in &self.0 {
+            write!(f, "\n  {}: {}", issue.name, issue.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigErrors {}
+
+/// Report `MCP_*` variables the server does not read.
+fn unknown_vars(vars: &HashMap<String, String>) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    let mut names: Vec<&String> = vars.keys().collect();
+    names.sort();
+    for name in names {
+        if !ENV_VARS.contains(&name.as_str()) {
+            let hint = closest_env_var(name)
+                .map(|known| format!("; did you mean {known}?"))
+                .unwrap_or_default();
+            issues.push(ConfigIssue::new(name, format!("unknown variable{hint}")));
+        }
+    }
+    issues
+}
+
+/// Report values that don't parse.
+#[allow(clippy::too_many_lines)] // one check per variable
+fn invalid_values(vars: &HashMap<String, String>) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    let mut check = |name: &str, valid: fn(&str) -> bool, expected: &str| {
+        if let Some(value) = vars.get(name)
+            && !valid(value.trim())
+        {
+            issues.push(ConfigIssue::new(
+                name,
+                format!("invalid value {value:?}, expected {expected}"),
+            ));
+        }
+    };
+    check("MCP_HOST", |v| v.parse::<IpAddr>().is_ok(), "an IP address");
+    check(
+        "MCP_PORT",
+        |v| v.parse::<u16>().is_ok(),
+        "a port number (0-65535)",
+    );
+    check(
+        "MCP_LOG_LEVEL",
+        |v| tracing_subscriber::EnvFilter::try_new(v).is_ok(),
+        "a log filter such as info or mcp_test_server=debug",
+    );
+    for flag in [
+        "MCP_STRICT_ARGS",
+        "MCP_STRICT_PROTOCOL_VERSION",
+        "MCP_LENIENT_NAMES",
+        "MCP_STATEFUL",
+        "MCP_OAUTH",
+        "MCP_DENY_SESSION_DELETE",
+        "MCP_HOSTED_APPS",
+    ] {
+        check(flag, is_flag_value, "true or false");
+    }
+    check(
+        "MCP_TICKER_INTERVAL_MS",
+        |v| v.parse::<u64>().is_ok_and(|ms| ms > 0),
+        "a positive number of milliseconds",
+    );
+    check(
+        "MCP_OAUTH_FAILURES",
+        |v| {
+            v.split(',')
+                .filter(|f| !f.trim().is_empty())
+                .all(|f| OAuthFailure::parse(f).is_some())
+        },
+        "a comma-separated list of OAuth failure modes",
+    );
+    check(
+        "MCP_MAX_SSE_EVENT_BYTES",
+        |v| v.parse::<usize>().is_ok_and(|bytes| bytes > 0),
+        "a positive number of bytes",
+    );
+    check(
+        "MCP_SSE_OVERSIZE_POLICY",
+        |v| SseOversizePolicy::parse(v).is_some(),
+        "reject or split",
+    );
+    check(
+        "MCP_ERROR_LANG",
+        |v| ErrorLang::parse(v).is_some(),
+        "en, de, ja, ar, pseudo, or lorem",
+    );
+    check(
+        "MCP_AUTH_FAILURE_MODE",
+        |v| AuthFailureMode::parse(v).is_some(),
+        "spec, forbidden, or bare_401",
+    );
+    check(
+        "MCP_SEED",
+        |v| v.parse::<u64>().is_ok(),
+        "an unsigned integer",
+    );
+    check(
+        "MCP_MAX_SESSIONS",
+        |v| v.parse::<usize>().is_ok_and(|max| max > 0),
+        "a positive number of sessions",
+    );
+    check(
+        "MCP_SESSION_TTL",
+        |v| parse_duration(v).is_some(),
+        "a positive duration such as 30s, 500ms, or 5m",
+    );
+    check(
+        "MCP_COMPAT",
+        |v| CompatTarget::parse(v).is_some(),
+        "2024-11-05 or 2025-03-26",
+    );
+    check(
+        "MCP_DRAIN_SECS",
+        |v| v.parse::<u64>().is_ok_and(|secs| secs > 0),
+        "a positive number of seconds",
+    );
+    check(
+        "MCP_REPLAY",
+        |v| Path::new(v).is_file(),
+        "an existing recording file",
+    );
+    check(
+        "MCP_MOCK_ROUTES",
+        |v| MockRoute::load(Path::new(v)).is_ok(),
+        "a JSON file holding an array of routes",
+    );
+    check(
+        "MCP_REGEX_TIMEOUT_MS",
+        |v| v.parse::<u64>().is_ok_and(|ms| ms > 0),
+        "a positive number of milliseconds",
+    );
+    check(
+        "MCP_MAX_RESPONSE_BYTES",
+        |v| v.parse::<usize>().is_ok_and(|bytes| bytes > 0),
+        "a positive number of bytes",
+    );
+    check(
+        "MCP_PANIC_POLICY",
+        |v| PanicPolicy::parse(v).is_some(),
+        "error or abort",
+    );
+    check(
+        "MCP_REQUEST_TIMEOUT",
+        |v| parse_duration(v).is_some(),
+        "a positive duration such as 30s, 500ms, or 5m",
+    );
+    check(
+        "MCP_CHAOS_RESPONSES",
+        |v| {
+            v.split(',')
+                .filter(|m| !m.trim().is_empty())
+                .all(|m| ResponseChaos::parse(m).is_some())
+        },
+        "a comma-separated list of duplicate and reorder",
+    );
+    check(
+        "MCP_CHAOS_RATE",
+        |v| {
+            v.parse::<f64>()
+                .is_ok_and(|rate| (0.0..=1.0).contains(&rate))
+        },
+        "a probability between 0 and 1",
+    );
+    check(
+        "MCP_SSE_DRIP_BYTES",
+        |v| v.parse::<usize>().is_ok_and(|bytes| bytes > 0),
+        "a positive number of bytes",
+    );
+    check(
+        "MCP_SSE_DRIP_DELAY",
+        |v| parse_duration(v).is_some(),
+        "a positive duration such as 10ms or 1s",
+    );
+    check(
+        "MCP_SSE_KEEPALIVE_SECS",
+        |v| v.parse::<u64>().is_ok(),
+        "a number of seconds (0 disables keep-alives)",
+    );
+    check(
+        "MCP_PING_INTERVAL",
+        |v| parse_duration(v).is_some(),
+        "a positive duration such as 30s or 500ms",
+    );
+    check(
+        "MCP_TOOL_ERROR_RATE",
+        |v| {
+            v.split(',')
+                .filter(|entry| !entry.trim().is_empty())
+                .all(|entry| ToolErrorRate::parse(entry).is_some())
+        },
+        "a comma-separated list of tool:rate entries with rates between 0 and 1",
+    );
+    check(
+        "MCP_TOOL_ERROR_MODE",
+        |v| ToolErrorMode::parse(v).is_some(),
+        "native, result, or protocol",
+    );
+    check(
+        "MCP_SCHEMA_ERRORS",
+        |v| SchemaErrorDetail::parse(v).is_some(),
+        "opaque, summary, or detailed",
+    );
+    check(
+        "MCP_UNKNOWN_TOOL",
+        |v| UnknownToolPolicy::parse(v).is_some(),
+        "error or result",
+    );
+    check(
+        "MCP_BATCH",
+        |v| BatchPolicy::parse(v).is_some(),
+        "spec, accept, or reject",
+    );
+    check(
+        "MCP_UNKNOWN_NOTIFICATION",
+        |v| UnknownNotificationPolicy::parse(v).is_some(),
+        "ignore or error",
+    );
+    check(
+        "MCP_SYNTHETIC_RESOURCES",
+        |v| {
+            v.parse::<usize>()
+                .is_ok_and(|count| count <= MAX_SYNTHETIC_RESOURCES)
+        },
+        "a number of resources up to 100000",
+    );
+    check(
+        "MCP_LOCALE",
+        |v| Locale::parse(v).is_some(),
+        "en, de, ja, or ar (optionally with a region, e.g. de-AT)",
+    );
+    check(
+        "MCP_COMPLETION_DELAY",
+        |v| parse_duration(v).is_some(),
+        "a positive duration such as 300ms or 1s",
+    );
+    check(
+        "MCP_COMPLETION_RATE_LIMIT",
+        |v| v.parse::<u32>().is_ok_and(|limit| limit > 0),
+        "a positive number of requests per second",
+    );
+    check(
+        "MCP_SERVER_ICONS",
+        |v| ServerIcons::parse(v).is_some(),
+        "all, svg, png, or none",
+    );
+    check(
+        "MCP_CAPABILITIES",
+        |v| {
+            v.trim().eq_ignore_ascii_case("none")
+                || v.split(',')
+                    .filter(|c| !c.trim().is_empty())
+                    .all(|c| Capability::parse(c).is_some())
+        },
+        "none, or a comma-separated list of tools, tools.listChanged, prompts, \
+         prompts.listChanged, resources, resources.subscribe, resources.listChanged, \
+         logging, completions, tasks, and ui",
+    );
+    check(
+        "MCP_EXPERIMENTAL_CAPABILITIES",
+        |v| advertise::load_experimental(Path::new(v)).is_ok(),
+        "a JSON file holding an object of objects",
+    );
+    check(
+        "MCP_UI_ASSETS",
+        |v| UiAssets::parse(v).is_some(),
+        "cdn or inline",
+    );
+    check(
+        "MCP_UI_CSP",
+        |v| UiCsp::parse(v).is_some(),
+        "none, strict, cdn, or lax",
+    );
+    check(
+        "MCP_COMPRESSION",
+        |v| ResponseCompression::parse(v).is_some(),
+        "off, on, or forced",
+    );
+    check(
+        "MCP_CORS_ORIGINS",
+        |v| CorsOrigins::parse(v).is_some(),
+        "*, none, or a comma-separated list of origins such as https://app.example.com",
+    );
+    check(
+        "MCP_CORS_METHODS",
+        |v| cors::parse_methods(v).is_some(),
+        "* or a comma-separated list of methods such as GET, POST",
+    );
+    check(
+        "MCP_TLS_CERT",
+        |v| crate::transport::load_certs(Path::new(v)).is_ok(),
+        "a PEM file holding a certificate chain",
+    );
+    check(
+        "MCP_TLS_KEY",
+        |v| crate::transport::load_key(Path::new(v)).is_ok(),
+        "a PEM file holding a private key",
+    );
+    check(
+        "MCP_PUBLIC_URL",
+        |v| proxy::parse_public_url(v).is_some(),
+        "an http:// or https:// URL such as https://mcp.example.com",
+    );
+    for name in ["MCP_CORS_HEADERS", "MCP_CORS_EXPOSE_HEADERS"] {
+        check(
+            name,
+            |v| cors::parse_headers(v).is_some(),
+            "* or a comma-separated list of header names",
+        );
+    }
+    issues
+}
+
+/// Report settings that conflict with, or depend on, other settings.
+fn conflicts(vars: &HashMap<String, String>) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    let set = |name: &str| vars.get(name).is_some_and(|v| !v.trim().is_empty());
+    let enabled = |name: &str| vars.get(name).is_some_and(|v| is_truthy(v));
+    if enabled("MCP_OAUTH") && set("MCP_API_KEY") {
+        issues.push(ConfigIssue::new(
+            "MCP_API_KEY",
+            "ignored when MCP_OAUTH is enabled; /mcp accepts OAuth access tokens only",
+        ));
+    }
+    for (name, other) in [
+        ("MCP_TLS_CERT", "MCP_TLS_KEY"),
+        ("MCP_TLS_KEY", "MCP_TLS_CERT"),
+    ] {
+        if set(name) && !set(other) {
+            issues.push(ConfigIssue::new(
+                name,
+                format!("has no effect without {other}; TLS needs both"),
+            ));
+        }
+    }
+    if set("MCP_RECORD") && set("MCP_REPLAY") {
+        issues.push(ConfigIssue::new(
+            "MCP_RECORD",
+            "cannot be combined with MCP_REPLAY; replayed exchanges are not recorded again",
+        ));
+    }
+    if set("MCP_CHAOS_RATE") && !set("MCP_CHAOS_RESPONSES") {
+        issues.push(ConfigIssue::new(
+            "MCP_CHAOS_RATE",
+            "has no effect without MCP_CHAOS_RESPONSES",
+        ));
+    }
+    if set("MCP_SSE_DRIP_DELAY") && !set("MCP_SSE_DRIP_BYTES") {
+        issues.push(ConfigIssue::new(
+            "MCP_SSE_DRIP_DELAY",
+            "has no effect without MCP_SSE_DRIP_BYTES",
+        ));
+    }
+    if set("MCP_SSE_OVERSIZE_POLICY") && !set("MCP_MAX_SSE_EVENT_BYTES") {
+        issues.push(ConfigIssue::new(
+            "MCP_SSE_OVERSIZE_POLICY",
+            "has no effect without MCP_MAX_SSE_EVENT_BYTES",
+        ));
+    }
+    if vars
+        .get("MCP_CORS_ORIGINS")
+        .is_some_and(|v| CorsOrigins::parse(v) == Some(CorsOrigins::None))
+    {
+        for name in [
+            "MCP_CORS_METHODS",
+            "MCP_CORS_HEADERS",
+            "MCP_CORS_EXPOSE_HEADERS",
+        ] {
+            if set(name) {
+                issues.push(ConfigIssue::new(
+                    name,
+                    "has no effect with MCP_CORS_ORIGINS=none (CORS is disabled)",
+                ));
+            }
+        }
+    }
+    if !enabled("MCP_STATEFUL") {
+        for name in [
+            "MCP_TICKER_INTERVAL_MS",
+            "MCP_MAX_SESSIONS",
+            "MCP_SESSION_TTL",
+            "MCP_DENY_SESSION_DELETE",
+            "MCP_PING_INTERVAL",
+            "MCP_DRAIN_SECS",
+        ] {
+            if set(name) {
+                issues.push(ConfigIssue::new(
+                    name,
+                    "has no effect without MCP_STATEFUL=true (sessions are disabled)",
+                ));
+            }
+        }
+    }
+    issues
+}
+
+/// Whether `value` is a flag value [`env_flag`] understands.
+fn is_flag_value(value: &str) -> bool {
+    is_truthy(value)
+        || matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "" | "0" | "false" | "no" | "off"
+        )
+}
+
+/// Interpret a flag value: `1`, `true`, `yes`, and `on` (case-insensitive)
+/// are true.
+fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "1" | "true" | "yes" | "on"
+    )
+}
+
+/// The known variable closest to `name`, if it looks like a typo of one.
+fn closest_env_var(name: &str) -> Option<&'static str> {
+    ENV_VARS
+        .iter()
+        .map(|known| (edit_distance(name, known), *known))
+        .filter(|(distance, _)| *distance <= 3)
+        .min()
+        .map(|(_, known)| known)
+}
+
+/// Levenshtein distance between two ASCII strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.as_bytes();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.bytes().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitute = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitute.min(row[j] + 1).min(diagonal + 1);
         }
     }
+    row[b.len()]
+}
+
+/// Read a boolean flag from the environment.
+///
+/// Accepts `1`, `true`, `yes`, and `on` (case-insensitive) as true; anything
+/// else, including an unset variable, is false.
+fn env_flag(name: &str) -> bool {
+    env::var(name).is_ok_and(|v| is_truthy(&v))
+}
+
+/// Read a path variable; empty values count as unset.
+fn env_path(name: &str) -> Option<PathBuf> {
+    env::var_os(name)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Parse a duration: `500ms`, `30s`, `5m`, or a bare number of seconds.
+/// Zero is rejected.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (number, unit_ms) = [("ms", 1), ("s", 1000), ("m", 60_000)]
+        .into_iter()
+        .find_map(|(suffix, unit_ms)| value.strip_suffix(suffix).map(|n| (n, unit_ms)))
+        .unwrap_or((value, 1000));
+    number
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(unit_ms))
+        .filter(|&ms| ms > 0)
+        .map(Duration::from_millis)
+}
+
+/// Interpret a UUID namespace setting.
+///
+/// A UUID is used as is; any other string is hashed into a namespace
+/// (UUID v5 in the OID namespace), so `MCP_UUID_NAMESPACE=my-suite` works too.
+fn uuid_namespace(value: &str) -> uuid::Uuid {
+    let value = value.trim();
+    uuid::Uuid::parse_str(value)
+        .unwrap_or_else(|_| uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_OID, value.as_bytes()))
+}
+
+/// Builder for creating [`Config`] instances with a fluent API.
+///
+/// # Example
+///
+/// ```
+/// use mcp_test_server::Config;
+/// use std::net::IpAddr;
+///
+/// let config = Config::builder()
+///     .host("127.0.0.1".parse::<IpAddr>().unwrap())
+///     .port(9000)
+///     .api_key("my-secret-key")
+///     .log_level("debug")
+///     .build();
+/// ```
+#[derive(Debug, Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ConfigBuilder {
+    host: Option<IpAddr>,
+    port: Option<u16>,
+    api_key: Option<String>,
+    log_level: Option<String>,
+    strict_args: bool,
+    strict_protocol_version: bool,
+    batch: BatchPolicy,
+    lenient_names: bool,
+    stateful: bool,
+    ticker_interval_ms: Option<u64>,
+    oauth_failures: Vec<OAuthFailure>,
+    max_sse_event_bytes: Option<usize>,
+    sse_oversize_policy: SseOversizePolicy,
+    oauth: bool,
+    error_lang: ErrorLang,
+    auth_failure_mode: AuthFailureMode,
+    uuid_namespace: Option<uuid::Uuid>,
+    seed: Option<u64>,
+    max_sessions: Option<usize>,
+    session_ttl: Option<Duration>,
+    deny_session_delete: bool,
+    compat: Option<CompatTarget>,
+    drain_period: Option<Duration>,
+    record: Option<PathBuf>,
+    replay: Option<PathBuf>,
+    mock_routes: Vec<MockRoute>,
+    regex_timeout_ms: Option<u64>,
+    max_response_bytes: Option<usize>,
+    panic_policy: PanicPolicy,
+    request_timeout: Option<Duration>,
+    chaos_responses: Vec<ResponseChaos>,
+    chaos_rate: Option<f64>,
+    sse_drip_bytes: Option<usize>,
+    sse_drip_delay: Option<Duration>,
+    sse_keepalive_secs: Option<u64>,
+    ping_interval: Option<Duration>,
+    tool_error_rates: Vec<ToolErrorRate>,
+    tool_error_mode: ToolErrorMode,
+    schema_errors: SchemaErrorDetail,
+    unknown_tool: UnknownToolPolicy,
+    unknown_notification: UnknownNotificationPolicy,
+    synthetic_resources: usize,
+    locale: Locale,
+    completion_delay: Option<Duration>,
+    completion_rate_limit: Option<u32>,
+    server_icons: ServerIcons,
+    instructions: Option<String>,
+    server_name: Option<String>,
+    server_title: Option<String>,
+    server_version: Option<String>,
+    capabilities: Option<Vec<Capability>>,
+    experimental_capabilities: Option<ExperimentalCapabilities>,
+    ui_assets: UiAssets,
+    ui_csp: UiCsp,
+    hosted_apps: bool,
+    compression: ResponseCompression,
+    cors: CorsPolicy,
+    tls_cert: Option<PathBuf>,
+    tls_key: Option<PathBuf>,
+    public_url: Option<String>,
+}
+
+impl ConfigBuilder {
+    /// Set the server bind address.
+    #[must_use]
+    pub const fn host(mut self, host: IpAddr) -> Self {
+        self.host = Some(host);
+        self
+    }
+
+    /// Set the server listen port.
+    #[must_use]
+    pub const fn port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// Set the API key for authentication.
+    #[must_use]
+    pub fn api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+
+    /// Set the log level.
+    #[must_use]
+    pub fn log_level(mut self, level: impl Into<String>) -> Self {
+        self.log_level = Some(level.into());
+        self
+    }
+
+    /// Reject tool arguments not declared in the tool's input schema.
+    #[must_use]
+    pub const fn strict_args(mut self, strict: bool) -> Self {
+        self.strict_args = strict;
+        self
+    }
+
+    /// Reject `/mcp` requests after `initialize` without the negotiated
+    /// `MCP-Protocol-Version` header.
+    #[must_use]
+    pub const fn strict_protocol_version(mut self, strict: bool) -> Self {
+        self.strict_protocol_version = strict;
+        self
+    }
+
+    /// Set whether JSON-RPC batches on `/mcp` are served.
+    #[must_use]
+    pub const fn batch(mut self, policy: BatchPolicy) -> Self {
+        self.batch = policy;
+        self
+    }
+
+    /// Match tool names and resource URIs ignoring case and surrounding whitespace.
+    #[must_use]
+    pub const fn lenient_names(mut self, lenient: bool) -> Self {
+        self.lenient_names = lenient;
+        self
+    }
+
+    /// Enable stateful sessions (`Mcp-Session-Id`) with a standalone SSE stream.
+    #[must_use]
+    pub const fn stateful(mut self, stateful: bool) -> Self {
+        self.stateful = stateful;
+        self
+    }
+
+    /// Set the interval between ticker resource updates, in milliseconds.
+    #[must_use]
+    pub const fn ticker_interval_ms(mut self, ms: u64) -> Self {
+        self.ticker_interval_ms = Some(ms);
+        self
+    }
+
+    /// Set the failure modes injected into the OAuth mock.
+    #[must_use]
+    pub fn oauth_failures(mut self, failures: impl IntoIterator<Item = OAuthFailure>) -> Self {
+        self.oauth_failures = failures.into_iter().collect();
+        self
+    }
+
+    /// Set the maximum SSE event payload size and the policy for oversized events.
+    #[must_use]
+    pub const fn max_sse_event_bytes(mut self, max: usize, policy: SseOversizePolicy) -> Self {
+        self.max_sse_event_bytes = Some(max);
+        self.sse_oversize_policy = policy;
+        self
+    }
+
+    /// Require access tokens issued by the built-in OAuth mock on `/mcp`.
+    #[must_use]
+    pub const fn oauth(mut self, oauth: bool) -> Self {
+        self.oauth = oauth;
+        self
+    }
+
+    /// Set the language for error messages.
+    #[must_use]
+    pub const fn error_lang(mut self, lang: ErrorLang) -> Self {
+        self.error_lang = lang;
+        self
+    }
+
+    /// Set how missing or invalid API keys are reported.
+    #[must_use]
+    pub const fn auth_failure_mode(mut self, mode: AuthFailureMode) -> Self {
+        self.auth_failure_mode = mode;
+        self
+    }
+
+    /// Make `random_uuid` return deterministic UUID v5 values in `namespace`.
+    #[must_use]
+    pub const fn uuid_namespace(mut self, namespace: uuid::Uuid) -> Self {
+        self.uuid_namespace = Some(namespace);
+        self
+    }
+
+    /// Set the seed for generated fixtures and the seeded random tools.
+    #[must_use]
+    pub const fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Limit the number of concurrent stateful sessions.
+    #[must_use]
+    pub const fn max_sessions(mut self, max: usize) -> Self {
+        self.max_sessions = Some(max);
+        self
+    }
+
+    /// Expire stateful sessions after this much idle time.
+    #[must_use]
+    pub const fn session_ttl(mut self, ttl: Duration) -> Self {
+        self.session_ttl = Some(ttl);
+        self
+    }
+
+    /// Answer client `DELETE /mcp` with `405 Method Not Allowed`.
+    #[must_use]
+    pub const fn deny_session_delete(mut self, deny: bool) -> Self {
+        self.deny_session_delete = deny;
+        self
+    }
+
+    /// Speak an older protocol revision and accept its request layouts.
+    #[must_use]
+    pub const fn compat(mut self, target: CompatTarget) -> Self {
+        self.compat = Some(target);
+        self
+    }
+
+    /// Keep serving existing sessions for this long after a shutdown signal.
+    #[must_use]
+    pub const fn drain_period(mut self, drain: Duration) -> Self {
+        self.drain_period = Some(drain);
+        self
+    }
+
+    /// Append every JSON-RPC exchange on `POST /mcp` to the file at `path`.
+    #[must_use]
+    pub fn record(mut self, path: impl Into<PathBuf>) -> Self {
+        self.record = Some(path.into());
+        self
+    }
+
+    /// Answer `POST /mcp` from a recording made with [`ConfigBuilder::record`].
+    #[must_use]
+    pub fn replay(mut self, path: impl Into<PathBuf>) -> Self {
+        self.replay = Some(path.into());
+        self
+    }
+
+    /// Set the canned routes of the `fetch_url` mock upstream.
+    #[must_use]
+    pub fn mock_routes(mut self, routes: impl IntoIterator<Item = MockRoute>) -> Self {
+        self.mock_routes = routes.into_iter().collect();
+        self
+    }
+
+    /// Set how long a `regex_*` tool call may run, in milliseconds.
+    #[must_use]
+    pub const fn regex_timeout_ms(mut self, ms: u64) -> Self {
+        self.regex_timeout_ms = Some(ms);
+        self
+    }
+
+    /// Turn tool results larger than `max` serialized bytes into an error.
+    #[must_use]
+    pub const fn max_response_bytes(mut self, max: usize) -> Self {
+        self.max_response_bytes = Some(max);
+        self
+    }
+
+    /// Set how a panicking tool handler is reported.
+    #[must_use]
+    pub const fn panic_policy(mut self, policy: PanicPolicy) -> Self {
+        self.panic_policy = policy;
+        self
+    }
+
+    /// Answer tool calls still running after `timeout` with a timeout error.
+    #[must_use]
+    pub const fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Disturb `POST /mcp` responses with `modes`, each picking a response
+    /// with probability `rate`.
+    #[must_use]
+    pub fn chaos_responses(
+        mut self,
+        modes: impl IntoIterator<Item = ResponseChaos>,
+        rate: f64,
+    ) -> Self {
+        self.chaos_responses = modes.into_iter().collect();
+        self.chaos_rate = Some(rate);
+        self
+    }
+
+    /// Flush SSE responses in chunks of at most `chunk_bytes`, pausing
+    /// `delay` between chunks.
+    #[must_use]
+    pub const fn sse_drip(mut self, chunk_bytes: usize, delay: Duration) -> Self {
+        self.sse_drip_bytes = Some(chunk_bytes);
+        self.sse_drip_delay = Some(delay);
+        self
+    }
+
+    /// Send SSE keep-alive comments every `secs` seconds; 0 disables them.
+    #[must_use]
+    pub const fn sse_keepalive_secs(mut self, secs: u64) -> Self {
+        self.sse_keepalive_secs = Some(secs);
+        self
+    }
+
+    /// Send each session a `ping` request every `interval` (stateful mode).
+    #[must_use]
+    pub const fn ping_interval(mut self, interval: Duration) -> Self {
+        self.ping_interval = Some(interval);
+        self
+    }
+
+    /// Make calls to `tool` fail with probability `rate`.
+    #[must_use]
+    pub fn tool_error_rate(mut self, tool: impl Into<String>, rate: f64) -> Self {
+        self.tool_error_rates.push(ToolErrorRate {
+            tool: tool.into(),
+            rate,
+        });
+        self
+    }
+
+    /// Set how failed tool calls are reported.
+    #[must_use]
+    pub const fn tool_error_mode(mut self, mode: ToolErrorMode) -> Self {
+        self.tool_error_mode = mode;
+        self
+    }
+
+    /// Set how much invalid params errors say about schema mismatches.
+    #[must_use]
+    pub const fn schema_errors(mut self, detail: SchemaErrorDetail) -> Self {
+        self.schema_errors = detail;
+        self
+    }
+
+    /// Set how calls to unknown tools are answered.
+    #[must_use]
+    pub const fn unknown_tool(mut self, policy: UnknownToolPolicy) -> Self {
+        self.unknown_tool = policy;
+        self
+    }
+
+    /// Set how notifications with unknown methods are answered.
+    #[must_use]
+    pub const fn unknown_notification(mut self, policy: UnknownNotificationPolicy) -> Self {
+        self.unknown_notification = policy;
+        self
+    }
+
+    /// Generate `count` synthetic resources (at most 100 000).
+    #[must_use]
+    pub const fn synthetic_resources(mut self, count: usize) -> Self {
+        self.synthetic_resources = count;
+        self
+    }
+
+    /// Set the locale of prompt titles and descriptions.
+    #[must_use]
+    pub const fn locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Hold every `completion/complete` answer back by `delay`.
+    #[must_use]
+    pub const fn completion_delay(mut self, delay: Duration) -> Self {
+        self.completion_delay = Some(delay);
+        self
+    }
+
+    /// Reject `completion/complete` requests beyond `limit` per second and
+    /// session.
+    #[must_use]
+    pub const fn completion_rate_limit(mut self, limit: u32) -> Self {
+        self.completion_rate_limit = Some(limit);
+        self
+    }
+
+    /// Pick the server icons advertised in `initialize`.
+    #[must_use]
+    pub const fn server_icons(mut self, icons: ServerIcons) -> Self {
+        self.server_icons = icons;
+        self
+    }
+
+    /// Set the `initialize` instructions; an empty string omits them.
+    #[must_use]
+    pub fn instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.instructions = Some(instructions.into());
+        self
+    }
+
+    /// Set the `serverInfo` name, title, and version of `initialize`; an
+    /// empty title omits it.
+    #[must_use]
+    pub fn server_info(
+        mut self,
+        name: impl Into<String>,
+        title: impl Into<String>,
+        version: impl Into<String>,
+    ) -> Self {
+        self.server_name = Some(name.into());
+        self.server_title = Some(title.into());
+        self.server_version = Some(version.into());
+        self
+    }
+
+    /// Advertise only `capabilities` in `initialize`.
+    #[must_use]
+    pub fn capabilities(mut self, capabilities: impl IntoIterator<Item = Capability>) -> Self {
+        self.capabilities = Some(capabilities.into_iter().collect());
+        self
+    }
+
+    /// Advertise `experimental` as `capabilities.experimental` in
+    /// `initialize`.
+    #[must_use]
+    pub fn experimental_capabilities(mut self, experimental: ExperimentalCapabilities) -> Self {
+        self.experimental_capabilities = Some(experimental);
+        self
+    }
 
-    /// Check if authentication is required.
+    /// Serve the `ui://` apps with their libraries delivered as `assets`.
     #[must_use]
-    pub const fn requires_auth(&self) -> bool {
-        self.api_key.is_some()
+    pub const fn ui_assets(mut self, assets: UiAssets) -> Self {
+        self.ui_assets = assets;
+        self
     }
-}
 
-/// Builder for creating [`Config`] instances with a fluent API.
-///
-/// # Example
-///
-/// ```
-/// use mcp_test_server::Config;
-/// use std::net::IpAddr;
-///
-/// let config = Config::builder()
-///     .host("127.0.0.1".parse::<IpAddr>().unwrap())
-///     .port(9000)
-///     .api_key("my-secret-key")
-///     .log_level("debug")
-///     .build();
-/// ```
-#[derive(Debug, Default)]
-pub struct ConfigBuilder {
-    host: Option<IpAddr>,
-    port: Option<u16>,
-    api_key: Option<String>,
-    log_level: Option<String>,
-}
+    /// Declare `csp` as `_meta.ui.csp` of the `ui://` apps.
+    #[must_use]
+    pub const fn ui_csp(mut self, csp: UiCsp) -> Self {
+        self.ui_csp = csp;
+        self
+    }
 
-impl ConfigBuilder {
-    /// Set the server bind address.
+    /// Serve the apps at `/apps/{app}` with a `Content-Security-Policy`
+    /// header.
     #[must_use]
-    pub const fn host(mut self, host: IpAddr) -> Self {
-        self.host = Some(host);
+    pub const fn hosted_apps(mut self, enabled: bool) -> Self {
+        self.hosted_apps = enabled;
         self
     }
 
-    /// Set the server listen port.
+    /// Compress HTTP responses as `compression` says.
     #[must_use]
-    pub const fn port(mut self, port: u16) -> Self {
-        self.port = Some(port);
+    pub const fn compression(mut self, compression: ResponseCompression) -> Self {
+        self.compression = compression;
         self
     }
 
-    /// Set the API key for authentication.
+    /// Answer CORS requests as `cors` says.
     #[must_use]
-    pub fn api_key(mut self, key: impl Into<String>) -> Self {
-        self.api_key = Some(key.into());
+    pub fn cors(mut self, cors: CorsPolicy) -> Self {
+        self.cors = cors;
         self
     }
 
-    /// Set the log level.
+    /// Serve HTTPS with the PEM certificate chain at `cert` and private key
+    /// at `key`.
     #[must_use]
-    pub fn log_level(mut self, level: impl Into<String>) -> Self {
-        self.log_level = Some(level.into());
+    pub fn tls(mut self, cert: impl Into<PathBuf>, key: impl Into<PathBuf>) -> Self {
+        self.tls_cert = Some(cert.into());
+        self.tls_key = Some(key.into());
+        self
+    }
+
+    /// Advertise `url` as the server's base URL, as behind a reverse proxy.
+    #[must_use]
+    pub fn public_url(mut self, url: impl Into<String>) -> Self {
+        self.public_url = Some(url.into());
         self
     }
 
@@ -131,6 +1541,67 @@ impl ConfigBuilder {
             port: self.port.unwrap_or(3000),
             api_key: self.api_key,
             log_level: self.log_level.unwrap_or_else(|| "info".to_string()),
+            strict_args: self.strict_args,
+            strict_protocol_version: self.strict_protocol_version,
+            batch: self.batch,
+            lenient_names: self.lenient_names,
+            stateful: self.stateful,
+            ticker_interval_ms: self.ticker_interval_ms.unwrap_or(1000).max(1),
+            oauth_failures: self.oauth_failures,
+            max_sse_event_bytes: self.max_sse_event_bytes,
+            sse_oversize_policy: self.sse_oversize_policy,
+            oauth: self.oauth,
+            error_lang: self.error_lang,
+            auth_failure_mode: self.auth_failure_mode,
+            uuid_namespace: self.uuid_namespace,
+            seed: self.seed,
+            max_sessions: self.max_sessions,
+            session_ttl: self.session_ttl,
+            deny_session_delete: self.deny_session_delete,
+            compat: self.compat,
+            drain_period: self.drain_period,
+            record: self.record,
+            replay: self.replay,
+            mock_routes: self.mock_routes,
+            regex_timeout_ms: self.regex_timeout_ms.unwrap_or(1000).max(1),
+            max_response_bytes: self.max_response_bytes,
+            panic_policy: self.panic_policy,
+            request_timeout: self.request_timeout,
+            chaos_responses: self.chaos_responses,
+            chaos_rate: self
+                .chaos_rate
+                .unwrap_or(DEFAULT_CHAOS_RATE)
+                .clamp(0.0, 1.0),
+            sse_drip_bytes: self.sse_drip_bytes.filter(|&bytes| bytes > 0),
+            sse_drip_delay: self.sse_drip_delay.unwrap_or(DEFAULT_SSE_DRIP_DELAY),
+            sse_keepalive_secs: self
+                .sse_keepalive_secs
+                .unwrap_or(DEFAULT_SSE_KEEPALIVE_SECS),
+            ping_interval: self.ping_interval,
+            tool_error_rates: self.tool_error_rates,
+            tool_error_mode: self.tool_error_mode,
+            schema_errors: self.schema_errors,
+            unknown_tool: self.unknown_tool,
+            unknown_notification: self.unknown_notification,
+            synthetic_resources: self.synthetic_resources.min(MAX_SYNTHETIC_RESOURCES),
+            locale: self.locale,
+            completion_delay: self.completion_delay,
+            completion_rate_limit: self.completion_rate_limit.filter(|&limit| limit > 0),
+            server_icons: self.server_icons,
+            instructions: self.instructions,
+            server_name: self.server_name.filter(|s| !s.is_empty()),
+            server_title: self.server_title,
+            server_version: self.server_version.filter(|s| !s.is_empty()),
+            capabilities: self.capabilities,
+            experimental_capabilities: self.experimental_capabilities,
+            ui_assets: self.ui_assets,
+            ui_csp: self.ui_csp,
+            hosted_apps: self.hosted_apps,
+            compression: self.compression,
+            cors: self.cors,
+            tls_cert: self.tls_cert,
+            tls_key: self.tls_key,
+            public_url: self.public_url,
         }
     }
 }
@@ -142,6 +1613,62 @@ impl Default for Config {
             port: 3000,
             api_key: None,
             log_level: "info".to_string(),
+            strict_args: false,
+            strict_protocol_version: false,
+            batch: BatchPolicy::Spec,
+            lenient_names: false,
+            stateful: false,
+            ticker_interval_ms: 1000,
+            oauth_failures: Vec::new(),
+            max_sse_event_bytes: None,
+            sse_oversize_policy: SseOversizePolicy::Reject,
+            oauth: false,
+            error_lang: ErrorLang::English,
+            auth_failure_mode: AuthFailureMode::Spec,
+            uuid_namespace: None,
+            seed: None,
+            max_sessions: None,
+            session_ttl: None,
+            deny_session_delete: false,
+            compat: None,
+            drain_period: None,
+            record: None,
+            replay: None,
+            mock_routes: Vec::new(),
+            regex_timeout_ms: 1000,
+            max_response_bytes: None,
+            panic_policy: PanicPolicy::Error,
+            request_timeout: None,
+            chaos_responses: Vec::new(),
+            chaos_rate: DEFAULT_CHAOS_RATE,
+            sse_drip_bytes: None,
+            sse_drip_delay: DEFAULT_SSE_DRIP_DELAY,
+            sse_keepalive_secs: DEFAULT_SSE_KEEPALIVE_SECS,
+            ping_interval: None,
+            tool_error_rates: Vec::new(),
+            tool_error_mode: ToolErrorMode::Native,
+            schema_errors: SchemaErrorDetail::Opaque,
+            unknown_tool: UnknownToolPolicy::Error,
+            unknown_notification: UnknownNotificationPolicy::Ignore,
+            synthetic_resources: 0,
+            locale: Locale::English,
+            completion_delay: None,
+            completion_rate_limit: None,
+            server_icons: ServerIcons::All,
+            instructions: None,
+            server_name: None,
+            server_title: None,
+            server_version: None,
+            capabilities: None,
+            experimental_capabilities: None,
+            ui_assets: UiAssets::Cdn,
+            ui_csp: UiCsp::None,
+            hosted_apps: false,
+            compression: ResponseCompression::Off,
+            cors: CorsPolicy::default(),
+            tls_cert: None,
+            tls_key: None,
+            public_url: None,
         }
     }
 }
@@ -181,6 +1708,7 @@ mod tests {
             port: 8080,
             api_key: Some("secret".to_string()),
             log_level: "debug".to_string(),
+            ..Default::default()
         };
         assert_eq!(config.host.to_string(), "127.0.0.1");
         assert_eq!(config.port, 8080);
@@ -269,6 +1797,531 @@ mod tests {
         assert_eq!(config.log_level, "trace");
     }
 
+    #[test]
+    fn test_builder_with_strict_args() {
+        assert!(!Config::builder().build().strict_args);
+        assert!(Config::builder().strict_args(true).build().strict_args);
+    }
+
+    #[test]
+    fn test_builder_with_lenient_names() {
+        assert!(!Config::builder().build().lenient_names);
+        assert!(Config::builder().lenient_names(true).build().lenient_names);
+    }
+
+    #[test]
+    fn test_builder_with_stateful() {
+        assert!(!Config::builder().build().stateful);
+        assert!(Config::builder().stateful(true).build().stateful);
+    }
+
+    #[test]
+    fn test_builder_with_ticker_interval() {
+        assert_eq!(Config::builder().build().ticker_interval_ms, 1000);
+        assert_eq!(
+            Config::builder()
+                .ticker_interval_ms(50)
+                .build()
+                .ticker_interval_ms,
+            50
+        );
+        // Zero would spin; it is clamped to 1ms
+        assert_eq!(
+            Config::builder()
+                .ticker_interval_ms(0)
+                .build()
+                .ticker_interval_ms,
+            1
+        );
+    }
+
+    #[test]
+    fn test_builder_with_oauth_failures() {
+        assert!(Config::builder().build().oauth_failures.is_empty());
+        let config = Config::builder()
+            .oauth_failures([OAuthFailure::ExpiredCode])
+            .build();
+        assert_eq!(config.oauth_failures, vec![OAuthFailure::ExpiredCode]);
+    }
+
+    #[test]
+    fn test_builder_with_max_sse_event_bytes() {
+        let config = Config::builder().build();
+        assert_eq!(config.max_sse_event_bytes, None);
+        assert_eq!(config.sse_oversize_policy, SseOversizePolicy::Reject);
+
+        let config = Config::builder()
+            .max_sse_event_bytes(512, SseOversizePolicy::Split)
+            .build();
+        assert_eq!(config.max_sse_event_bytes, Some(512));
+        assert_eq!(config.sse_oversize_policy, SseOversizePolicy::Split);
+    }
+
+    #[test]
+    fn test_builder_with_oauth() {
+        assert!(!Config::builder().build().oauth);
+        assert!(Config::builder().oauth(true).build().oauth);
+    }
+
+    #[test]
+    fn test_builder_with_error_lang() {
+        assert_eq!(Config::builder().build().error_lang, ErrorLang::English);
+        let config = Config::builder().error_lang(ErrorLang::Pseudo).build();
+        assert_eq!(config.error_lang, ErrorLang::Pseudo);
+    }
+
+    #[test]
+    fn test_builder_with_auth_failure_mode() {
+        assert_eq!(
+            Config::builder().build().auth_failure_mode,
+            AuthFailureMode::Spec
+        );
+        let config = Config::builder()
+            .auth_failure_mode(AuthFailureMode::Forbidden)
+            .build();
+        assert_eq!(config.auth_failure_mode, AuthFailureMode::Forbidden);
+    }
+
+    #[test]
+    fn test_builder_with_uuid_namespace() {
+        assert_eq!(Config::builder().build().uuid_namespace, None);
+        let config = Config::builder()
+            .uuid_namespace(uuid::Uuid::NAMESPACE_DNS)
+            .build();
+        assert_eq!(config.uuid_namespace, Some(uuid::Uuid::NAMESPACE_DNS));
+    }
+
+    #[test]
+    fn test_uuid_namespace_parsing() {
+        let dns = uuid::Uuid::NAMESPACE_DNS.to_string();
+        assert_eq!(uuid_namespace(&dns), uuid::Uuid::NAMESPACE_DNS);
+        // Arbitrary names hash to a stable namespace
+        assert_eq!(uuid_namespace("my-suite"), uuid_namespace(" my-suite "));
+        assert_ne!(uuid_namespace("my-suite"), uuid_namespace("other-suite"));
+    }
+
+    #[test]
+    fn test_builder_with_seed() {
+        assert_eq!(Config::builder().build().seed, None);
+        assert_eq!(Config::builder().seed(42).build().seed, Some(42));
+    }
+
+    #[test]
+    fn test_builder_with_max_sessions() {
+        assert_eq!(Config::builder().build().max_sessions, None);
+        assert_eq!(
+            Config::builder().max_sessions(2).build().max_sessions,
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_builder_with_session_ttl() {
+        assert_eq!(Config::builder().build().session_ttl, None);
+        let config = Config::builder()
+            .session_ttl(Duration::from_secs(30))
+            .build();
+        assert_eq!(config.session_ttl, Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_builder_with_drain_period() {
+        assert_eq!(Config::builder().build().drain_period, None);
+        assert_eq!(
+            Config::builder()
+                .drain_period(Duration::from_secs(5))
+                .build()
+                .drain_period,
+            Some(Duration::from_secs(5))
+        );
+    }
+
+    #[test]
+    fn test_builder_with_record_and_replay() {
+        let config = Config::builder().build();
+        assert_eq!((config.record, config.replay), (None, None));
+        let config = Config::builder()
+            .record("out.jsonl")
+            .replay("in.jsonl")
+            .build();
+        assert_eq!(config.record, Some(PathBuf::from("out.jsonl")));
+        assert_eq!(config.replay, Some(PathBuf::from("in.jsonl")));
+    }
+
+    #[test]
+    fn test_builder_with_mock_routes() {
+        assert!(Config::builder().build().mock_routes.is_empty());
+        let route = MockRoute::new("/users", 200, "[]");
+        let config = Config::builder().mock_routes([route.clone()]).build();
+        assert_eq!(config.mock_routes, vec![route]);
+    }
+
+    #[test]
+    fn test_builder_with_regex_timeout() {
+        assert_eq!(Config::builder().build().regex_timeout_ms, 1000);
+        assert_eq!(
+            Config::builder()
+                .regex_timeout_ms(50)
+                .build()
+                .regex_timeout_ms,
+            50
+        );
+        // Zero would fail every call; it is clamped to 1ms
+        assert_eq!(
+            Config::builder()
+                .regex_timeout_ms(0)
+                .build()
+                .regex_timeout_ms,
+            1
+        );
+    }
+
+    #[test]
+    fn test_builder_with_max_response_bytes() {
+        assert_eq!(Config::builder().build().max_response_bytes, None);
+        assert_eq!(
+            Config::builder()
+                .max_response_bytes(1024)
+                .build()
+                .max_response_bytes,
+            Some(1024)
+        );
+    }
+
+    #[test]
+    fn test_builder_with_panic_policy() {
+        assert_eq!(Config::builder().build().panic_policy, PanicPolicy::Error);
+        assert_eq!(
+            Config::builder()
+                .panic_policy(PanicPolicy::Abort)
+                .build()
+                .panic_policy,
+            PanicPolicy::Abort
+        );
+    }
+
+    #[test]
+    fn test_builder_with_request_timeout() {
+        assert_eq!(Config::builder().build().request_timeout, None);
+        assert_eq!(
+            Config::builder()
+                .request_timeout(Duration::from_secs(2))
+                .build()
+                .request_timeout,
+            Some(Duration::from_secs(2))
+        );
+    }
+
+    #[test]
+    fn test_builder_with_chaos_responses() {
+        let config = Config::builder().build();
+        assert!(config.chaos_responses.is_empty());
+        assert!((config.chaos_rate - 0.1).abs() < f64::EPSILON);
+
+        let config = Config::builder()
+            .chaos_responses([ResponseChaos::Reorder], 2.0)
+            .build();
+        assert_eq!(config.chaos_responses, vec![ResponseChaos::Reorder]);
+        // Probabilities above 1 are clamped
+        assert!((config.chaos_rate - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_builder_with_sse_drip() {
+        let config = Config::builder().build();
+        assert_eq!(config.sse_drip_bytes, None);
+        assert_eq!(config.sse_drip_delay, Duration::from_millis(10));
+
+        let config = Config::builder()
+            .sse_drip(8, Duration::from_millis(50))
+            .build();
+        assert_eq!(config.sse_drip_bytes, Some(8));
+        assert_eq!(config.sse_drip_delay, Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_builder_with_keepalive_and_pings() {
+        let config = Config::builder().build();
+        assert_eq!(config.sse_keepalive_secs, 15);
+        assert_eq!(config.ping_interval, None);
+
+        let config = Config::builder()
+            .sse_keepalive_secs(0)
+            .ping_interval(Duration::from_secs(5))
+            .build();
+        assert_eq!(config.sse_keepalive_secs, 0);
+        assert_eq!(config.ping_interval, Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_builder_with_tool_error_rate() {
+        assert!(Config::builder().build().tool_error_rates.is_empty());
+        let config = Config::builder()
+            .tool_error_rate("add", 0.25)
+            .tool_error_rate("echo", 1.0)
+            .build();
+        let tools: Vec<&str> = config
+            .tool_error_rates
+            .iter()
+            .map(|r| r.tool.as_str())
+            .collect();
+        assert_eq!(tools, ["add", "echo"]);
+        assert!((config.tool_error_rates[0].rate - 0.25).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_builder_with_tool_error_mode() {
+        assert_eq!(
+            Config::builder().build().tool_error_mode,
+            ToolErrorMode::Native
+        );
+        assert_eq!(
+            Config::builder()
+                .tool_error_mode(ToolErrorMode::Protocol)
+                .build()
+                .tool_error_mode,
+            ToolErrorMode::Protocol
+        );
+    }
+
+    #[test]
+    fn test_builder_with_schema_errors() {
+        assert_eq!(
+            Config::builder().build().schema_errors,
+            SchemaErrorDetail::Opaque
+        );
+        assert_eq!(
+            Config::builder()
+                .schema_errors(SchemaErrorDetail::Detailed)
+                .build()
+                .schema_errors,
+            SchemaErrorDetail::Detailed
+        );
+    }
+
+    #[test]
+    fn test_builder_with_unknown_policies() {
+        let config = Config::builder().build();
+        assert_eq!(config.unknown_tool, UnknownToolPolicy::Error);
+        assert_eq!(
+            config.unknown_notification,
+            UnknownNotificationPolicy::Ignore
+        );
+
+        let config = Config::builder()
+            .unknown_tool(UnknownToolPolicy::Result)
+            .unknown_notification(UnknownNotificationPolicy::Error)
+            .build();
+        assert_eq!(config.unknown_tool, UnknownToolPolicy::Result);
+        assert_eq!(
+            config.unknown_notification,
+            UnknownNotificationPolicy::Error
+        );
+    }
+
+    #[test]
+    fn test_builder_with_synthetic_resources() {
+        assert_eq!(Config::builder().build().synthetic_resources, 0);
+        assert_eq!(
+            Config::builder()
+                .synthetic_resources(10_000)
+                .build()
+                .synthetic_resources,
+            10_000
+        );
+        assert_eq!(
+            Config::builder()
+                .synthetic_resources(usize::MAX)
+                .build()
+                .synthetic_resources,
+            MAX_SYNTHETIC_RESOURCES
+        );
+    }
+
+    #[test]
+    fn test_builder_with_locale() {
+        assert_eq!(Config::builder().build().locale, Locale::English);
+        let config = Config::builder().locale(Locale::Japanese).build();
+        assert_eq!(config.locale, Locale::Japanese);
+    }
+
+    #[test]
+    fn test_builder_with_completion_throttling() {
+        let config = Config::builder().build();
+        assert_eq!(config.completion_delay, None);
+        assert_eq!(config.completion_rate_limit, None);
+
+        let config = Config::builder()
+            .completion_delay(Duration::from_millis(300))
+            .completion_rate_limit(5)
+            .build();
+        assert_eq!(config.completion_delay, Some(Duration::from_millis(300)));
+        assert_eq!(config.completion_rate_limit, Some(5));
+        assert_eq!(
+            Config::builder()
+                .completion_rate_limit(0)
+                .build()
+                .completion_rate_limit,
+            None
+        );
+    }
+
+    #[test]
+    fn test_builder_with_server_icons() {
+        assert_eq!(Config::builder().build().server_icons, ServerIcons::All);
+        let config = Config::builder().server_icons(ServerIcons::None).build();
+        assert_eq!(config.server_icons, ServerIcons::None);
+    }
+
+    #[test]
+    fn test_builder_with_ui_assets() {
+        assert_eq!(Config::builder().build().ui_assets, UiAssets::Cdn);
+        let config = Config::builder().ui_assets(UiAssets::Inline).build();
+        assert_eq!(config.ui_assets, UiAssets::Inline);
+    }
+
+    #[test]
+    fn test_builder_with_ui_csp() {
+        let config = Config::builder().build();
+        assert_eq!(config.ui_csp, UiCsp::None);
+        assert!(!config.hosted_apps);
+        let config = Config::builder()
+            .ui_csp(UiCsp::Strict)
+            .hosted_apps(true)
+            .build();
+        assert_eq!(config.ui_csp, UiCsp::Strict);
+        assert!(config.hosted_apps);
+    }
+
+    #[test]
+    fn test_builder_with_compression() {
+        assert_eq!(
+            Config::builder().build().compression,
+            ResponseCompression::Off
+        );
+        let config = Config::builder()
+            .compression(ResponseCompression::Forced)
+            .build();
+        assert_eq!(config.compression, ResponseCompression::Forced);
+    }
+
+    #[test]
+    fn test_builder_with_cors() {
+        assert_eq!(Config::builder().build().cors, CorsPolicy::default());
+        let cors = CorsPolicy {
+            origins: CorsOrigins::None,
+            ..CorsPolicy::default()
+        };
+        assert_eq!(Config::builder().cors(cors.clone()).build().cors, cors);
+    }
+
+    #[test]
+    fn test_builder_with_tls() {
+        let config = Config::builder().build();
+        assert!(!config.uses_tls());
+        assert_eq!(config.scheme(), "http");
+        let config = Config::builder().tls("cert.pem", "key.pem").build();
+        assert_eq!(config.tls_cert, Some(PathBuf::from("cert.pem")));
+        assert_eq!(config.scheme(), "https");
+    }
+
+    #[test]
+    fn test_builder_with_public_url() {
+        let config = Config::builder()
+            .public_url("https://mcp.example.com")
+            .build();
+        assert_eq!(
+            config.public_url.as_deref(),
+            Some("https://mcp.example.com")
+        );
+        assert_eq!(Config::default().public_url, None);
+    }
+
+    #[test]
+    fn test_builder_with_strict_protocol_version() {
+        assert!(!Config::builder().build().strict_protocol_version);
+        assert!(
+            Config::builder()
+                .strict_protocol_version(true)
+                .build()
+                .strict_protocol_version
+        );
+    }
+
+    #[test]
+    fn test_builder_with_batch() {
+        assert_eq!(Config::builder().build().batch, BatchPolicy::Spec);
+        assert_eq!(
+            Config::builder().batch(BatchPolicy::Reject).build().batch,
+            BatchPolicy::Reject
+        );
+    }
+
+    #[test]
+    fn test_builder_with_initialize_overrides() {
+        let config = Config::builder().build();
+        assert_eq!(config.instructions, None);
+        assert_eq!(config.server_name, None);
+        assert_eq!(config.capabilities, None);
+
+        let config = Config::builder()
+            .instructions("")
+            .server_info("other-server", "", "0.0.1")
+            .capabilities([Capability::Resources])
+            .build();
+        assert_eq!(config.instructions.as_deref(), Some(""));
+        assert_eq!(config.server_name.as_deref(), Some("other-server"));
+        assert_eq!(config.server_title.as_deref(), Some(""));
+        assert_eq!(config.server_version.as_deref(), Some("0.0.1"));
+        assert_eq!(config.capabilities, Some(vec![Capability::Resources]));
+    }
+
+    #[test]
+    fn test_builder_with_experimental_capabilities() {
+        assert_eq!(Config::builder().build().experimental_capabilities, None);
+        let experimental = ExperimentalCapabilities::from([(
+            "acme/streaming".to_string(),
+            serde_json::Map::new(),
+        )]);
+        let config = Config::builder()
+            .experimental_capabilities(experimental.clone())
+            .build();
+        assert_eq!(config.experimental_capabilities, Some(experimental));
+    }
+
+    #[test]
+    fn test_builder_with_compat() {
+        assert_eq!(Config::builder().build().compat, None);
+        assert_eq!(
+            Config::builder()
+                .compat(CompatTarget::V2024_11_05)
+                .build()
+                .compat,
+            Some(CompatTarget::V2024_11_05)
+        );
+    }
+
+    #[test]
+    fn test_builder_with_deny_session_delete() {
+        assert!(!Config::builder().build().deny_session_delete);
+        assert!(
+            Config::builder()
+                .deny_session_delete(true)
+                .build()
+                .deny_session_delete
+        );
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("500ms"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_duration("30s"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_duration(" 30 "), Some(Duration::from_secs(30)));
+        assert_eq!(parse_duration("5m"), Some(Duration::from_secs(300)));
+        assert_eq!(parse_duration("0"), None);
+        assert_eq!(parse_duration("soon"), None);
+    }
+
     #[test]
     fn test_builder_debug() {
         let builder = Config::builder().port(8080);
@@ -276,4 +2329,91 @@ mod tests {
         assert!(debug_str.contains("ConfigBuilder"));
         assert!(debug_str.contains("8080"));
     }
+
+    fn issues(vars: &[(&str, &str)]) -> Vec<ConfigIssue> {
+        Config::validate_vars(vars.iter().copied())
+            .err()
+            .map(|e| e.0)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_config() {
+        assert_eq!(
+            Config::validate_vars([
+                ("MCP_PORT", "8080"),
+                ("MCP_STATEFUL", "true"),
+                ("MCP_TICKER_INTERVAL_MS", "250"),
+                ("MCP_ERROR_LANG", "de"),
+                ("MCP_OAUTH_FAILURES", "expired_code,token_500"),
+                ("PATH", "/usr/bin"),
+            ]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_validate_aggregates_invalid_values() {
+        let issues = issues(&[
+            ("MCP_PORT", "99999"),
+            ("MCP_STRICT_ARGS", "maybe"),
+            ("MCP_OAUTH_FAILURES", "expired_code,bogus"),
+        ]);
+        let names: Vec<&str> = issues.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, ["MCP_PORT", "MCP_STRICT_ARGS", "MCP_OAUTH_FAILURES"]);
+        assert!(issues[0].message.contains("\"99999\""));
+    }
+
+    #[test]
+    fn test_validate_unknown_variable_suggests_closest() {
+        let issues = issues(&[("MCP_PROT", "8080"), ("MCP_ZZZZZZZZZZZZ", "1")]);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].name, "MCP_PROT");
+        assert!(issues[0].message.contains("did you mean MCP_PORT?"));
+        assert_eq!(issues[1].message, "unknown variable");
+    }
+
+    #[test]
+    fn test_validate_conflicts() {
+        let issues = issues(&[
+            ("MCP_OAUTH", "1"),
+            ("MCP_API_KEY", "secret"),
+            ("MCP_SSE_OVERSIZE_POLICY", "split"),
+            ("MCP_TICKER_INTERVAL_MS", "100"),
+            ("MCP_DRAIN_SECS", "5"),
+        ]);
+        let names: Vec<&str> = issues.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "MCP_API_KEY",
+                "MCP_SSE_OVERSIZE_POLICY",
+                "MCP_TICKER_INTERVAL_MS",
+                "MCP_DRAIN_SECS"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_config_errors_display() {
+        let errors = ConfigErrors(vec![
+            ConfigIssue::new("MCP_PORT", "bad"),
+            ConfigIssue::new("MCP_HOST", "worse"),
+        ]);
+        assert_eq!(
+            errors.to_string(),
+            "invalid configuration (2 problems):\n  MCP_PORT: bad\n  MCP_HOST: worse"
+        );
+    }
+
+    #[test]
+    fn test_env_vars_are_documented() {
+        let readme = include_str!("../README.md");
+        for name in ENV_VARS {
+            assert!(
+                readme.contains(&format!("`{name}`")),
+                "{name} not in README"
+            );
+        }
+    }
 }
@@ -0,0 +1,327 @@
+//! Protocol coverage of client sessions.
+//!
+//! Every JSON-RPC message a client POSTs to `/mcp` is classified per session
+//! (the `mcp-session-id` header, or `default` without sessions): the
+//! `initialize` options it sent, how often it called each method, which
+//! notifications it sent, and how it answered server-initiated requests.
+//!
+//! `GET /admin/conformance/{session}` returns the report. It has no
+//! timestamps and its maps are sorted, so a test run can compare it against
+//! a checked-in golden file; `coverage` lists which of the methods and
+//! notifications the server handles the client exercised.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::sessions::DEFAULT_SESSION;
+
+/// Sessions kept; the oldest is dropped when a new one would exceed this.
+const MAX_SESSIONS: usize = 1000;
+
+/// Client-to-server methods and notifications the server handles.
+pub const FEATURES: &[&str] = &[
+    "initialize",
+    "ping",
+    "tools/list",
+    "tools/call",
+    "resources/list",
+    "resources/templates/list",
+    "resources/read",
+    "resources/subscribe",
+    "resources/unsubscribe",
+    "prompts/list",
+    "prompts/get",
+    "completion/complete",
+    "logging/setLevel",
+    "tasks/get",
+    "tasks/list",
+    "tasks/result",
+    "tasks/cancel",
+    "notifications/initialized",
+    "notifications/cancelled",
+    "notifications/progress",
+    "notifications/roots/list_changed",
+];
+
+#[derive(Debug, Default)]
+struct SessionCoverage {
+    initialize: Option<InitializeOptions>,
+    requests: BTreeMap<String, u64>,
+    notifications: BTreeMap<String, u64>,
+    responses: ResponseCounts,
+}
+
+#[derive(Debug, Default)]
+struct Sessions {
+    coverage: HashMap<String, SessionCoverage>,
+    /// Session keys, oldest first.
+    order: VecDeque<String>,
+}
+
+/// Per-session record of the protocol features clients used.
+#[derive(Debug, Default)]
+pub struct ConformanceLog {
+    sessions: Mutex<Sessions>,
+}
+
+/// What the client declared in `initialize`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InitializeOptions {
+    /// The requested `protocolVersion`.
+    pub protocol_version: Option<String>,
+    /// The `clientInfo` object as sent.
+    pub client_info: Option<Value>,
+    /// Declared capabilities as dotted paths, e.g. `roots` and
+    /// `roots.listChanged`, sorted.
+    pub capabilities: Vec<String>,
+}
+
+/// Client answers to server-initiated requests (sampling, elicitation,
+/// roots, ping).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ResponseCounts {
+    /// Responses carrying a `result`.
+    pub results: u64,
+    /// Responses carrying an `error`.
+    pub errors: u64,
+}
+
+/// Which of [`FEATURES`] a session exercised.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Coverage {
+    /// Features used at least once, in [`FEATURES`] order.
+    pub exercised: Vec<&'static str>,
+    /// Features never used, in [`FEATURES`] order.
+    pub missing: Vec<&'static str>,
+    /// Share of [`FEATURES`] exercised, rounded down to a whole percent.
+    pub percent: usize,
+}
+
+/// Response of `GET /admin/conformance/{session}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConformanceReport {
+    /// The `mcp-session-id`, or `default` for messages without one.
+    pub session: String,
+    /// The latest `initialize` of the session, if one was seen.
+    pub initialize: Option<InitializeOptions>,
+    /// Requests sent, by method, including methods the server doesn't know.
+    pub requests: BTreeMap<String, u64>,
+    /// Notifications sent, by method.
+    pub notifications: BTreeMap<String, u64>,
+    /// Answers to server-initiated requests.
+    pub responses: ResponseCounts,
+    /// Coverage of the server's features.
+    pub coverage: Coverage,
+}
+
+impl ConformanceLog {
+    /// Create an empty log.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one client message (or batch) for `session`.
+    #[allow(clippy::significant_drop_tightening)]
+    pub fn record(&self, session: &str, message: &Value) {
+        let messages = match message {
+            Value::Array(batch) => batch.iter().collect(),
+            message => vec![message],
+        };
+        let mut sessions = self.sessions.lock().unwrap_or_else(PoisonError::into_inner);
+        if !sessions.coverage.contains_key(session) {
+            if sessions.order.len() == MAX_SESSIONS
+                && let Some(oldest) = sessions.order.pop_front()
+            {
+                sessions.coverage.remove(&oldest);
+            }
+            sessions.order.push_back(session.to_string());
+        }
+        let coverage = sessions.coverage.entry(session.to_string()).or_default();
+        for message in messages {
+            classify(coverage, message);
+        }
+    }
+
+    /// The report for `session`, or `None` if it sent nothing.
+    #[must_use]
+    #[allow(clippy::significant_drop_tightening)]
+    pub fn report(&self, session: &str) -> Option<ConformanceReport> {
+        let sessions = self.sessions.lock().unwrap_or_else(PoisonError::into_inner);
+        let coverage = sessions.coverage.get(session)?;
+        let (exercised, missing): (Vec<&'static str>, Vec<&'static str>) =
+            FEATURES.iter().partition(|feature| {
+                coverage.requests.contains_key(**feature)
+                    || coverage.notifications.contains_key(**feature)
+            });
+        Some(ConformanceReport {
+            session: session.to_string(),
+            initialize: coverage.initialize.clone(),
+            requests: coverage.requests.clone(),
+            notifications: coverage.notifications.clone(),
+            responses: coverage.responses,
+            coverage: Coverage {
+                percent: exercised.len() * 100 / FEATURES.len(),
+                exercised,
+                missing,
+            },
+        })
+    }
+}
+
+/// Count `message` as a request, notification, or response.
+fn classify(coverage: &mut SessionCoverage, message: &Value) {
+    let Some(method) = message.get("method").and_then(Value::as_str) else {
+        if message.get("error").is_some() {
+            coverage.responses.errors += 1;
+        } else if message.get("result").is_some() {
+            coverage.responses.results += 1;
+        }
+        return;
+    };
+    if message.get("id").is_none() {
+        *coverage
+            .notifications
+            .entry(method.to_string())
+            .or_default() += 1;
+        return;
+    }
+    *coverage.requests.entry(method.to_string()).or_default() += 1;
+    if method == "initialize" {
+        let params = &message["params"];
+        let mut capabilities = BTreeSet::new();
+        if let Some(declared) = params.get("capabilities").and_then(Value::as_object) {
+            for (name, value) in declared {
+                capabilities.insert(name.clone());
+                if let Some(options) = value.as_object() {
+                    for (option, value) in options {
+                        if value != &Value::Bool(false) && !value.is_null() {
+                            capabilities.insert(format!("{name}.{option}"));
+                        }
+                    }
+                }
+            }
+        }
+        coverage.initialize = Some(InitializeOptions {
+            protocol_version: params["protocolVersion"].as_str().map(str::to_string),
+            client_info: params.get("clientInfo").cloned(),
+            capabilities: capabilities.into_iter().collect(),
+        });
+    }
+}
+
+/// Middleware recording every `POST /mcp` message into a [`ConformanceLog`].
+///
+/// Messages are recorded once the response is ready, so an `initialize`
+/// counts toward the session ID the server issued for it.
+pub async fn conformance_middleware(
+    State(log): State<Arc<ConformanceLog>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+
+    let (parts, body) = request.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    let message = serde_json::from_slice::<Value>(&bytes).ok();
+    let mut session = parts
+        .headers
+        .get("mcp-session-id")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let response = next
+        .run(Request::from_parts(parts, Body::from(bytes)))
+        .await;
+    if session.is_none() {
+        session = response
+            .headers()
+            .get("mcp-session-id")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+    }
+    if let Some(message) = message {
+        log.record(session.as_deref().unwrap_or(DEFAULT_SESSION), &message);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_classifies_messages() {
+        let log = ConformanceLog::new();
+        log.record(
+            "s1",
+            &json!({
+                "jsonrpc": "2.0", "id": 0, "method": "initialize",
+                "params": {
+                    "protocolVersion": "2025-06-18",
+                    "capabilities": { "roots": { "listChanged": true }, "sampling": {} },
+                    "clientInfo": { "name": "c", "version": "1" }
+                }
+            }),
+        );
+        log.record("s1", &json!({ "method": "notifications/initialized" }));
+        log.record(
+            "s1",
+            &json!([
+                { "id": 1, "method": "tools/call", "params": { "name": "echo" } },
+                { "id": 2, "method": "tools/call", "params": { "name": "add" } },
+                { "id": 3, "method": "vendor/extension" }
+            ]),
+        );
+        log.record("s1", &json!({ "id": "srv-1", "result": {} }));
+        log.record("s1", &json!({ "id": "srv-2", "error": { "code": -1 } }));
+
+        let report = log.report("s1").unwrap();
+        let initialize = report.initialize.unwrap();
+        assert_eq!(initialize.protocol_version.as_deref(), Some("2025-06-18"));
+        assert_eq!(
+            initialize.capabilities,
+            ["roots", "roots.listChanged", "sampling"]
+        );
+        assert_eq!(report.requests["tools/call"], 2);
+        assert_eq!(report.requests["vendor/extension"], 1);
+        assert_eq!(report.notifications["notifications/initialized"], 1);
+        assert_eq!(
+            report.responses,
+            ResponseCounts {
+                results: 1,
+                errors: 1
+            }
+        );
+        assert_eq!(
+            report.coverage.exercised,
+            ["initialize", "tools/call", "notifications/initialized"]
+        );
+        assert_eq!(report.coverage.missing.len(), FEATURES.len() - 3);
+        assert_eq!(report.coverage.percent, 3 * 100 / FEATURES.len());
+        assert!(log.report("s2").is_none());
+    }
+
+    #[test]
+    fn test_oldest_session_dropped() {
+        let log = ConformanceLog::new();
+        for i in 0..=MAX_SESSIONS {
+            log.record(&i.to_string(), &json!({ "id": 1, "method": "ping" }));
+        }
+        assert!(log.report("0").is_none());
+        assert!(log.report(&MAX_SESSIONS.to_string()).is_some());
+    }
+}
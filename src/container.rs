@@ -0,0 +1,208 @@
+//! Testcontainers image for the published Docker image (feature `testcontainers`).
+//!
+//! [`McpTestServerImage`] runs `ghcr.io/nazq/mcp-test-server` at this
+//! crate's version and waits until `GET /readyz` answers `200`:
+//!
+//! ```rust,no_run
+//! use mcp_test_server::container::{McpTestServerImage, mcp_url};
+//! use testcontainers::runners::AsyncRunner;
+//!
+//! # async fn example() -> testcontainers::core::error::Result<()> {
+//! let container = McpTestServerImage::default().with_api_key("secret").start().await?;
+//! let url = mcp_url(&container).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Server settings are passed as the `MCP_*` environment variables listed in
+//! [`Config`](crate::Config); [`McpTestServerImage::validate`] checks them
+//! the way the binary does at startup.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use testcontainers::ContainerAsync;
+use testcontainers::Image;
+use testcontainers::core::error::Result;
+use testcontainers::core::wait::HttpWaitStrategy;
+use testcontainers::core::{ContainerPort, WaitFor};
+
+use crate::config::{Config, ConfigErrors};
+
+/// Name of the published image.
+pub const IMAGE_NAME: &str = "ghcr.io/nazq/mcp-test-server";
+
+/// Port the server listens on inside the container.
+pub const MCP_PORT: ContainerPort = ContainerPort::Tcp(3000);
+
+/// The `mcp-test-server` Docker image.
+#[derive(Debug, Clone)]
+pub struct McpTestServerImage {
+    tag: String,
+    env: BTreeMap<String, String>,
+}
+
+impl Default for McpTestServerImage {
+    fn default() -> Self {
+        Self {
+            tag: env!("CARGO_PKG_VERSION").to_string(),
+            env: BTreeMap::new(),
+        }
+    }
+}
+
+impl McpTestServerImage {
+    /// Use another image tag, e.g. `latest` (default: this crate's version).
+    #[must_use]
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = tag.into();
+        self
+    }
+
+    /// Set an environment variable, e.g. `MCP_STRICT_ARGS=true`.
+    ///
+    /// `MCP_HOST` and `MCP_PORT` are ignored: the server always listens on
+    /// [`MCP_PORT`] on all interfaces, and the mapped host port comes from
+    /// the container.
+    #[must_use]
+    pub fn with_env(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let name = name.into();
+        if name != "MCP_HOST" && name != "MCP_PORT" {
+            self.env.insert(name, value.into());
+        }
+        self
+    }
+
+    /// Require `Authorization: Bearer <key>` (`MCP_API_KEY`).
+    #[must_use]
+    pub fn with_api_key(self, api_key: impl Into<String>) -> Self {
+        self.with_env("MCP_API_KEY", api_key)
+    }
+
+    /// Issue sessions and push notifications (`MCP_STATEFUL`).
+    #[must_use]
+    pub fn with_stateful(self, stateful: bool) -> Self {
+        self.with_env("MCP_STATEFUL", stateful.to_string())
+    }
+
+    /// Set the server log level (`MCP_LOG_LEVEL`).
+    #[must_use]
+    pub fn with_log_level(self, level: impl Into<String>) -> Self {
+        self.with_env("MCP_LOG_LEVEL", level)
+    }
+
+    /// The environment variables passed to the container.
+    #[must_use]
+    pub const fn env(&self) -> &BTreeMap<String, String> {
+        &self.env
+    }
+
+    /// Check the environment variables as the server will at startup.
+    ///
+    /// # Errors
+    ///
+    /// Returns every value that doesn't parse and every unknown `MCP_*` name;
+    /// the container would exit with the same list.
+    pub fn validate(&self) -> std::result::Result<(), ConfigErrors> {
+        Config::validate_vars(self.env.clone())
+    }
+}
+
+impl Image for McpTestServerImage {
+    fn name(&self) -> &str {
+        IMAGE_NAME
+    }
+
+    fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    fn ready_conditions(&self) -> Vec<WaitFor> {
+        vec![WaitFor::http(
+            HttpWaitStrategy::new("/readyz")
+                .with_port(MCP_PORT)
+                .with_expected_status_code(200_u16),
+        )]
+    }
+
+    fn env_vars(
+        &self,
+    ) -> impl IntoIterator<Item = (impl Into<Cow<'_, str>>, impl Into<Cow<'_, str>>)> {
+        &self.env
+    }
+
+    fn expose_ports(&self) -> &[ContainerPort] {
+        &[MCP_PORT]
+    }
+}
+
+/// Base URL of a running container as seen from the host, e.g.
+/// `http://localhost:49153`.
+///
+/// # Errors
+///
+/// Returns an error if Docker can't report the host or the mapped port.
+pub async fn base_url(container: &ContainerAsync<McpTestServerImage>) -> Result<String> {
+    let host = container.get_host().await?;
+    let port = container.get_host_port_ipv4(MCP_PORT).await?;
+    Ok(format!("http://{host}:{port}"))
+}
+
+/// The container's MCP endpoint (`{base_url}/mcp`).
+///
+/// # Errors
+///
+/// Returns an error if Docker can't report the host or the mapped port.
+pub async fn mcp_url(container: &ContainerAsync<McpTestServerImage>) -> Result<String> {
+    base_url(container).await.map(|url| format!("{url}/mcp"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_to_crate_version() {
+        let image = McpTestServerImage::default();
+        assert_eq!(image.name(), IMAGE_NAME);
+        assert_eq!(image.tag(), env!("CARGO_PKG_VERSION"));
+        assert_eq!(image.with_tag("latest").tag(), "latest");
+    }
+
+    #[test]
+    fn test_env_mapping() {
+        let image = McpTestServerImage::default()
+            .with_api_key("secret")
+            .with_stateful(true)
+            .with_env("MCP_PORT", "8080");
+        assert_eq!(
+            image.env().get("MCP_API_KEY").map(String::as_str),
+            Some("secret")
+        );
+        assert_eq!(
+            image.env().get("MCP_STATEFUL").map(String::as_str),
+            Some("true")
+        );
+        assert!(!image.env().contains_key("MCP_PORT"));
+        assert!(image.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_reports_bad_env() {
+        let image = McpTestServerImage::default()
+            .with_stateful(true)
+            .with_env("MCP_STATFUL", "true");
+        let errors = image.validate().unwrap_err();
+        assert!(errors.to_string().contains("MCP_STATFUL"));
+    }
+
+    #[test]
+    fn test_waits_on_readyz() {
+        let image = McpTestServerImage::default();
+        assert_eq!(image.expose_ports(), &[MCP_PORT]);
+        assert!(matches!(
+            image.ready_conditions().as_slice(),
+            [WaitFor::Http(_)]
+        ));
+    }
+}
@@ -0,0 +1,84 @@
+//! Response compression (`MCP_COMPRESSION`).
+//!
+//! With [`ResponseCompression::On`], responses are compressed with gzip,
+//! deflate, or brotli as the client's `Accept-Encoding` asks, including the
+//! `text/event-stream` bodies of `POST /mcp` and the standalone SSE stream.
+//! The encoder flushes whenever the server has nothing more to send yet, so
+//! compressed SSE events still arrive as they happen. Bodies under 32 bytes,
+//! such as the empty `202 Accepted`, stay uncompressed.
+//!
+//! [`ResponseCompression::Forced`] gzips every response whatever the client
+//! accepts, like a misconfigured proxy, so clients can check they notice a
+//! `Content-Encoding` they never asked for.
+
+use axum::Router;
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::{HeaderValue, header};
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use futures_util::StreamExt;
+use tower_http::compression::CompressionLayer;
+use tower_http::compression::predicate::{NotForContentType, Predicate, SizeAbove};
+
+/// Whether and how HTTP responses are compressed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResponseCompression {
+    /// Never compress (default).
+    #[default]
+    Off,
+    /// Compress as `Accept-Encoding` negotiates.
+    On,
+    /// Gzip every response, even when the client does not accept it.
+    Forced,
+}
+
+impl ResponseCompression {
+    /// Parse `off`, `on`, or `forced` (case-insensitive).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "off" => Some(Self::Off),
+            "on" => Some(Self::On),
+            "forced" => Some(Self::Forced),
+            _ => None,
+        }
+    }
+
+    /// Compress the responses of `router` in this mode.
+    pub fn layer(self, router: Router) -> Router {
+        if self == Self::Off {
+            return router;
+        }
+        // Unlike the default predicate, SSE streams are compressed too
+        let predicate = SizeAbove::default().and(NotForContentType::GRPC);
+        let router = router
+            .layer(middleware::from_fn(fuse_body_middleware))
+            .layer(CompressionLayer::new().compress_when(predicate));
+        if self == Self::Forced {
+            // Outside the compression layer, so it negotiates gzip
+            router.layer(middleware::from_fn(force_gzip_middleware))
+        } else {
+            router
+        }
+    }
+}
+
+/// Middleware replacing the request's `Accept-Encoding` with `gzip`
+/// ([`ResponseCompression::Forced`]).
+pub async fn force_gzip_middleware(mut request: Request, next: Next) -> Response {
+    request
+        .headers_mut()
+        .insert(header::ACCEPT_ENCODING, HeaderValue::from_static("gzip"));
+    next.run(request).await
+}
+
+/// Middleware making response bodies safe to poll after they end.
+///
+/// The compression layer polls a body once more for trailers after its data
+/// is exhausted, which the SSE body streams do not allow.
+pub async fn fuse_body_middleware(request: Request, next: Next) -> Response {
+    next.run(request)
+        .await
+        .map(|body| Body::from_stream(body.into_data_stream().fuse()))
+}
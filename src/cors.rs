@@ -0,0 +1,192 @@
+//! CORS for browser-based clients (`MCP_CORS_*`).
+//!
+//! By default every origin may call every endpoint with any method and
+//! header, and every response header is exposed. [`CorsPolicy`] narrows
+//! that down to test how browser clients cope with restrictive setups:
+//! preflights that refuse a method or header, an `Mcp-Session-Id` the page
+//! cannot read because it is not exposed, or no CORS headers at all.
+//!
+//! Preflight (`OPTIONS`) requests are answered before authentication, as
+//! browsers send them without credentials. Origins listed in
+//! `MCP_CORS_ORIGINS` also pass the DNS rebinding check of
+//! [`auth_middleware`](crate::auth::auth_middleware), which otherwise only
+//! admits localhost and VS Code origins.
+
+use axum::http::{HeaderName, HeaderValue, Method};
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, Any, CorsLayer, ExposeHeaders};
+
+/// Origins allowed to read responses.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum CorsOrigins {
+    /// Any origin, answered with `Access-Control-Allow-Origin: *` (default).
+    #[default]
+    Any,
+    /// No CORS headers at all, so browsers block every cross-origin call.
+    None,
+    /// Only these origins, e.g. `https://app.example.com`.
+    List(Vec<String>),
+}
+
+impl CorsOrigins {
+    /// Parse `*`, `none`, or a comma-separated list of origins.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "*" => return Some(Self::Any),
+            "none" => return Some(Self::None),
+            _ => {}
+        }
+        let origins: Vec<String> = value
+            .split(',')
+            .map(|origin| origin.trim().trim_end_matches('/').to_string())
+            .filter(|origin| !origin.is_empty())
+            .collect();
+        let valid =
+            |origin: &String| origin.contains("://") && HeaderValue::from_str(origin).is_ok();
+        (!origins.is_empty() && origins.iter().all(valid)).then_some(Self::List(origins))
+    }
+}
+
+/// CORS answers of the HTTP endpoints.
+///
+/// `None` lists allow anything, as the default does.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorsPolicy {
+    /// Origins allowed to read responses.
+    pub origins: CorsOrigins,
+    /// Methods a preflight allows.
+    pub methods: Option<Vec<Method>>,
+    /// Request headers a preflight allows.
+    pub headers: Option<Vec<HeaderName>>,
+    /// Response headers exposed to the page; empty exposes none.
+    pub expose_headers: Option<Vec<HeaderName>>,
+}
+
+impl CorsPolicy {
+    /// Whether `origin` is one of the listed origins.
+    #[must_use]
+    pub fn lists(&self, origin: &str) -> bool {
+        match &self.origins {
+            CorsOrigins::List(origins) => origins.iter().any(|o| o == origin),
+            CorsOrigins::Any | CorsOrigins::None => false,
+        }
+    }
+
+    /// The layer answering CORS requests; `None` with [`CorsOrigins::None`].
+    #[must_use]
+    pub fn layer(&self) -> Option<CorsLayer> {
+        let origin = match &self.origins {
+            CorsOrigins::Any => AllowOrigin::from(Any),
+            CorsOrigins::None => return None,
+            CorsOrigins::List(origins) => AllowOrigin::list(
+                origins
+                    .iter()
+                    .filter_map(|origin| HeaderValue::from_str(origin).ok()),
+            ),
+        };
+        let methods = self
+            .methods
+            .clone()
+            .map_or_else(|| AllowMethods::from(Any), AllowMethods::list);
+        let headers = self
+            .headers
+            .clone()
+            .map_or_else(|| AllowHeaders::from(Any), AllowHeaders::list);
+        let expose = self
+            .expose_headers
+            .clone()
+            .map_or_else(|| ExposeHeaders::from(Any), ExposeHeaders::list);
+        Some(
+            CorsLayer::new()
+                .allow_origin(origin)
+                .allow_methods(methods)
+                .allow_headers(headers)
+                .expose_headers(expose),
+        )
+    }
+}
+
+/// Parse a comma-separated list of methods, e.g. `GET, POST`; `*` allows
+/// any.
+#[must_use]
+pub fn parse_methods(value: &str) -> Option<Option<Vec<Method>>> {
+    if value.trim() == "*" {
+        return Some(None);
+    }
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|method| !method.is_empty())
+        .map(|method| Method::from_bytes(method.to_ascii_uppercase().as_bytes()).ok())
+        .collect::<Option<Vec<_>>>()
+        .map(Some)
+}
+
+/// Parse a comma-separated list of header names; `*` allows any and an
+/// empty value none.
+#[must_use]
+pub fn parse_headers(value: &str) -> Option<Option<Vec<HeaderName>>> {
+    if value.trim() == "*" {
+        return Some(None);
+    }
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| HeaderName::from_bytes(name.as_bytes()).ok())
+        .collect::<Option<Vec<_>>>()
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_origins() {
+        assert_eq!(CorsOrigins::parse("*"), Some(CorsOrigins::Any));
+        assert_eq!(CorsOrigins::parse(" None "), Some(CorsOrigins::None));
+        assert_eq!(
+            CorsOrigins::parse("https://a.example, http://b.example:8080/"),
+            Some(CorsOrigins::List(vec![
+                "https://a.example".to_string(),
+                "http://b.example:8080".to_string(),
+            ]))
+        );
+        assert_eq!(CorsOrigins::parse("a.example"), None);
+        assert_eq!(CorsOrigins::parse(""), None);
+    }
+
+    #[test]
+    fn test_parse_methods_and_headers() {
+        assert_eq!(parse_methods("*"), Some(None));
+        assert_eq!(
+            parse_methods("get, POST"),
+            Some(Some(vec![Method::GET, Method::POST]))
+        );
+        assert_eq!(parse_methods("GET, P O S T"), None);
+        assert_eq!(parse_headers(""), Some(Some(Vec::new())));
+        assert_eq!(
+            parse_headers("Mcp-Session-Id"),
+            Some(Some(vec![HeaderName::from_static("mcp-session-id")]))
+        );
+        assert_eq!(parse_headers("bad header"), None);
+    }
+
+    #[test]
+    fn test_listed_origins() {
+        let policy = CorsPolicy {
+            origins: CorsOrigins::List(vec!["https://a.example".to_string()]),
+            ..CorsPolicy::default()
+        };
+        assert!(policy.lists("https://a.example"));
+        assert!(!policy.lists("https://b.example"));
+        assert!(!CorsPolicy::default().lists("https://a.example"));
+        assert!(policy.layer().is_some());
+        let none = CorsPolicy {
+            origins: CorsOrigins::None,
+            ..CorsPolicy::default()
+        };
+        assert!(none.layer().is_none());
+    }
+}
@@ -0,0 +1,365 @@
+//! Resources and prompts registered with [`McpTestServerBuilder`].
+//!
+//! Registered entries are listed after the built-ins and take precedence
+//! over a built-in with the same URI or name.
+//!
+//! [`McpTestServerBuilder`]: crate::McpTestServerBuilder
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use futures_util::future::BoxFuture;
+use rmcp::ErrorData as McpError;
+use rmcp::model::{
+    AnnotateAble, Prompt, PromptArgument, PromptMessage, RawResource, RawResourceTemplate,
+    ReadResourceResult, Resource, ResourceContents, ResourceTemplate,
+};
+
+/// Handler of a resource or resource template registered with the builder.
+///
+/// Receives the requested URI and, for templates, the values of the
+/// template's variables (empty for fixed URIs).
+pub type CustomResourceHandler = Arc<
+    dyn Fn(
+            String,
+            HashMap<String, String>,
+        ) -> BoxFuture<'static, Result<ResourceContents, McpError>>
+        + Send
+        + Sync,
+>;
+
+/// Handler of a prompt registered with the builder.
+///
+/// Receives the prompt's arguments; required ones are checked beforehand.
+pub type CustomPromptHandler = Arc<
+    dyn Fn(HashMap<String, String>) -> BoxFuture<'static, Result<Vec<PromptMessage>, McpError>>
+        + Send
+        + Sync,
+>;
+
+enum ResourceSource {
+    Static(ResourceContents),
+    Handler(CustomResourceHandler),
+}
+
+/// Registered resources, resource templates, and prompts.
+#[derive(Default)]
+pub(crate) struct CustomRegistry {
+    resources: Vec<(Resource, ResourceSource)>,
+    templates: Vec<(ResourceTemplate, CustomResourceHandler)>,
+    prompts: Vec<(Prompt, CustomPromptHandler)>,
+}
+
+impl CustomRegistry {
+    /// Register a resource whose contents never change.
+    ///
+    /// UTF-8 `contents` are served as text, anything else as a blob.
+    pub fn add_static_resource(&mut self, uri: &str, mime_type: String, contents: Vec<u8>) {
+        let contents = match String::from_utf8(contents) {
+            Ok(text) => ResourceContents::TextResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some(mime_type.clone()),
+                text,
+                meta: None,
+            },
+            Err(e) => ResourceContents::BlobResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some(mime_type.clone()),
+                blob: BASE64.encode(e.into_bytes()),
+                meta: None,
+            },
+        };
+        let resource = resource(uri, mime_type);
+        self.resources.retain(|(r, _)| r.uri != uri);
+        self.resources
+            .push((resource, ResourceSource::Static(contents)));
+    }
+
+    /// Register a resource read by `handler` on every `resources/read`.
+    pub fn add_resource(&mut self, uri: &str, mime_type: String, handler: CustomResourceHandler) {
+        let resource = resource(uri, mime_type);
+        self.resources.retain(|(r, _)| r.uri != uri);
+        self.resources
+            .push((resource, ResourceSource::Handler(handler)));
+    }
+
+    /// Register a resource template such as `app://users/{id}`.
+    pub fn add_template(
+        &mut self,
+        uri_template: &str,
+        mime_type: String,
+        handler: CustomResourceHandler,
+    ) {
+        let name = last_segment(uri_template);
+        let template = RawResourceTemplate::new(uri_template, name)
+            .with_mime_type(mime_type)
+            .no_annotation();
+        self.templates
+            .retain(|(t, _)| t.uri_template != uri_template);
+        self.templates.push((template, handler));
+    }
+
+    /// Register a prompt with the given declared arguments.
+    pub fn add_prompt(
+        &mut self,
+        name: &str,
+        arguments: Vec<PromptArgument>,
+        handler: CustomPromptHandler,
+    ) {
+        let arguments = (!arguments.is_empty()).then_some(arguments);
+        let prompt = Prompt::new(name, None::<String>, arguments);
+        self.prompts.retain(|(p, _)| p.name != name);
+        self.prompts.push((prompt, handler));
+    }
+
+    /// `builtin` with registered resources replacing or following it.
+    pub fn merge_resources(&self, mut builtin: Vec<Resource>) -> Vec<Resource> {
+        builtin.retain(|r| !self.resources.iter().any(|(c, _)| c.uri == r.uri));
+        builtin.extend(self.resources.iter().map(|(r, _)| r.clone()));
+        builtin
+    }
+
+    /// `builtin` with registered templates replacing or following it.
+    pub fn merge_templates(&self, mut builtin: Vec<ResourceTemplate>) -> Vec<ResourceTemplate> {
+        builtin.retain(|t| {
+            !self
+                .templates
+                .iter()
+                .any(|(c, _)| c.uri_template == t.uri_template)
+        });
+        builtin.extend(self.templates.iter().map(|(t, _)| t.clone()));
+        builtin
+    }
+
+    /// `builtin` with registered prompts replacing or following it.
+    pub fn merge_prompts(&self, mut builtin: Vec<Prompt>) -> Vec<Prompt> {
+        builtin.retain(|p| !self.prompts.iter().any(|(c, _)| c.name == p.name));
+        builtin.extend(self.prompts.iter().map(|(p, _)| p.clone()));
+        builtin
+    }
+
+    /// Read `uri` if a registered resource or template serves it.
+    pub async fn read(&self, uri: &str) -> Option<Result<ReadResourceResult, McpError>> {
+        let (handler, variables) =
+            if let Some((_, source)) = self.resources.iter().find(|(r, _)| r.uri == uri) {
+                match source {
+                    ResourceSource::Static(contents) => {
+                        return Some(Ok(ReadResourceResult::new(vec![contents.clone()])));
+                    }
+                    ResourceSource::Handler(handler) => (handler, HashMap::new()),
+                }
+            } else {
+                self.templates.iter().find_map(|(template, handler)| {
+                    match_template(&template.uri_template, uri).map(|vars| (handler, vars))
+                })?
+            };
+        Some(
+            handler(uri.to_string(), variables)
+                .await
+                .map(|contents| ReadResourceResult::new(vec![contents])),
+        )
+    }
+
+    /// The registered prompt called `name` and its handler.
+    pub fn prompt(&self, name: &str) -> Option<(&Prompt, &CustomPromptHandler)> {
+        self.prompts
+            .iter()
+            .find(|(p, _)| p.name == name)
+            .map(|(p, h)| (p, h))
+    }
+}
+
+impl std::fmt::Debug for CustomRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomRegistry")
+            .field(
+                "resources",
+                &self
+                    .resources
+                    .iter()
+                    .map(|(r, _)| &r.uri)
+                    .collect::<Vec<_>>(),
+            )
+            .field(
+                "templates",
+                &self
+                    .templates
+                    .iter()
+                    .map(|(t, _)| &t.uri_template)
+                    .collect::<Vec<_>>(),
+            )
+            .field(
+                "prompts",
+                &self
+                    .prompts
+                    .iter()
+                    .map(|(p, _)| &p.name)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+fn resource(uri: &str, mime_type: String) -> Resource {
+    RawResource::new(uri, last_segment(uri))
+        .with_mime_type(mime_type)
+        .no_annotation()
+}
+
+/// The part of `uri` after its last `/`, or all of it.
+fn last_segment(uri: &str) -> String {
+    uri.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .filter(|s| !s.is_empty())
+        .unwrap_or(uri)
+        .to_string()
+}
+
+/// Match `uri` against a template with simple `{name}` variables.
+///
+/// A variable matches one or more characters up to the literal text that
+/// follows it (a trailing variable takes the rest of the URI, slashes
+/// included). Returns the variable values, or `None` if `uri` doesn't match.
+#[must_use]
+pub fn match_template(template: &str, uri: &str) -> Option<HashMap<String, String>> {
+    let mut variables = HashMap::new();
+    let mut rest = uri;
+    let mut pattern = template;
+    loop {
+        let Some(open) = pattern.find('{') else {
+            return (rest == pattern).then_some(variables);
+        };
+        rest = rest.strip_prefix(&pattern[..open])?;
+        let close = open + pattern[open..].find('}')?;
+        let name = &pattern[open + 1..close];
+        pattern = &pattern[close + 1..];
+        let literal = pattern.find('{').map_or(pattern, |next| &pattern[..next]);
+        let end = if literal.is_empty() {
+            rest.len()
+        } else {
+            rest.find(literal)?
+        };
+        if end == 0 {
+            return None;
+        }
+        variables.insert(name.to_string(), rest[..end].to_string());
+        rest = &rest[end..];
+    }
+}
+
+/// Spell `uri` the way `template` does, if the two match ignoring ASCII
+/// case.
+///
+/// Variables match as in [`match_template`]; the literal parts take the
+/// template's spelling and variable values are kept as sent. Returns `None`
+/// if `uri` doesn't match.
+#[must_use]
+pub fn respell_template_uri(template: &str, uri: &str) -> Option<String> {
+    let mut respelled = String::with_capacity(uri.len());
+    let mut rest = uri;
+    let mut pattern = template;
+    loop {
+        let Some(open) = pattern.find('{') else {
+            return rest
+                .eq_ignore_ascii_case(pattern)
+                .then(|| respelled + pattern);
+        };
+        let prefix = &pattern[..open];
+        if !rest
+            .get(..prefix.len())
+            .is_some_and(|head| head.eq_ignore_ascii_case(prefix))
+        {
+            return None;
+        }
+        respelled.push_str(prefix);
+        rest = &rest[prefix.len()..];
+        let close = open + pattern[open..].find('}')?;
+        pattern = &pattern[close + 1..];
+        let literal = pattern.find('{').map_or(pattern, |next| &pattern[..next]);
+        let end = if literal.is_empty() {
+            rest.len()
+        } else {
+            // ASCII lowercasing keeps byte offsets, so `end` indexes `rest`
+            rest.to_ascii_lowercase()
+                .find(&literal.to_ascii_lowercase())?
+        };
+        if end == 0 {
+            return None;
+        }
+        respelled.push_str(&rest[..end]);
+        rest = &rest[end..];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_respell_template_uri() {
+        assert_eq!(
+            respell_template_uri("app://users/{id}.json", "APP://Users/Ada.JSON").as_deref(),
+            Some("app://users/Ada.json")
+        );
+        assert_eq!(
+            respell_template_uri("test://files/{path}", "Test://Files/Docs/README.md").as_deref(),
+            Some("test://files/Docs/README.md")
+        );
+        assert_eq!(
+            respell_template_uri("app://users/{id}", "app://groups/1"),
+            None
+        );
+        assert_eq!(
+            respell_template_uri("app://users/{id}", "APP://USERS/"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_match_template() {
+        let vars = match_template("app://users/{id}/posts/{post}", "app://users/7/posts/a/b");
+        assert_eq!(vars.unwrap()["post"], "a/b");
+        let vars = match_template("app://users/{id}.json", "app://users/42.json").unwrap();
+        assert_eq!(vars["id"], "42");
+        assert!(match_template("app://users/{id}", "app://users/").is_none());
+        assert!(match_template("app://users/{id}", "app://groups/1").is_none());
+        assert!(
+            match_template("app://fixed", "app://fixed")
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_static_resources_text_or_blob() {
+        let mut registry = CustomRegistry::default();
+        registry.add_static_resource("app://a", "text/plain".into(), b"hi".to_vec());
+        registry.add_static_resource(
+            "app://b",
+            "application/octet-stream".into(),
+            vec![0xff, 0x00],
+        );
+
+        let text = registry.read("app://a").await.unwrap().unwrap();
+        assert!(matches!(
+            &text.contents[0],
+            ResourceContents::TextResourceContents { text, .. } if text == "hi"
+        ));
+        let blob = registry.read("app://b").await.unwrap().unwrap();
+        assert!(matches!(
+            &blob.contents[0],
+            ResourceContents::BlobResourceContents { blob, .. } if blob == "/wA="
+        ));
+        assert!(registry.read("app://c").await.is_none());
+    }
+
+    #[test]
+    fn test_registered_entries_replace_builtins() {
+        let mut registry = CustomRegistry::default();
+        registry.add_static_resource("test://static/hello.txt", "text/plain".into(), vec![]);
+        let builtin = vec![resource("test://static/hello.txt", "text/plain".into())];
+        assert_eq!(registry.merge_resources(builtin).len(), 1);
+    }
+}
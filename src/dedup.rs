@@ -0,0 +1,325 @@
+//! Duplicate request detection for client retry auditing.
+//!
+//! Every `POST /mcp` body is hashed with SHA-256 and counted per session
+//! (the `mcp-session-id` header, or `default` before a session exists).
+//! Byte-for-byte identical bodies within a session are almost always client
+//! retries — harmless for `tools/list`, but a bug when the retried call is a
+//! non-idempotent `tools/call`.
+//!
+//! `initialize` and `notifications/initialized` are not recorded: they are
+//! sent before a session exists, so every client's copies would share the
+//! `default` key. For the same reason the report is only meaningful per
+//! client in stateful mode; without `MCP_STATEFUL` all clients share
+//! `default`, and identical requests from two clients count as a retry.
+//!
+//! The log keeps the latest [`MAX_SESSIONS`] sessions and the latest
+//! [`MAX_BODIES`] distinct bodies of each; older ones are dropped.
+//!
+//! The report is served at `GET /debug/duplicates` and reset with
+//! `DELETE /debug/duplicates`.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Sessions kept; the oldest is dropped when a new one would exceed this.
+pub const MAX_SESSIONS: usize = 1000;
+
+/// Distinct bodies kept per session; the oldest is dropped when a new one
+/// would exceed this.
+pub const MAX_BODIES: usize = 1000;
+
+/// Lifecycle messages sent before a session ID exists, never recorded.
+const LIFECYCLE_METHODS: &[&str] = &["initialize", "notifications/initialized"];
+
+#[derive(Debug)]
+struct BodyRecord {
+    count: u64,
+    method: Option<String>,
+    id: Option<serde_json::Value>,
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+}
+
+#[derive(Debug, Default)]
+struct SessionBodies {
+    /// Bodies received, including dropped ones.
+    requests: u64,
+    records: HashMap<String, BodyRecord>,
+    /// Body hashes, oldest first.
+    order: VecDeque<String>,
+}
+
+#[derive(Debug, Default)]
+struct Sessions {
+    bodies: HashMap<String, SessionBodies>,
+    /// Session keys, oldest first.
+    order: VecDeque<String>,
+}
+
+/// Per-session record of request body hashes.
+#[derive(Debug, Default)]
+pub struct RequestLog {
+    sessions: Mutex<Sessions>,
+}
+
+/// Snapshot of duplicate requests across all sessions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DuplicateReport {
+    /// Extra copies received across all sessions (a body seen 3 times counts 2).
+    pub total_duplicates: u64,
+    /// Sessions that received at least one duplicate, sorted by session ID.
+    pub sessions: Vec<SessionDuplicates>,
+}
+
+/// Duplicates received within one session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SessionDuplicates {
+    /// The `mcp-session-id`, or `default` for requests without one.
+    pub session: String,
+    /// Total request bodies received in this session.
+    pub requests: u64,
+    /// Bodies received more than once, most repeated first.
+    pub duplicates: Vec<DuplicateEntry>,
+}
+
+/// A request body received more than once.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DuplicateEntry {
+    /// Hex SHA-256 of the raw body.
+    pub sha256: String,
+    /// Times the body was received.
+    pub count: u64,
+    /// JSON-RPC `method`, if the body parsed as a single message.
+    pub method: Option<String>,
+    /// JSON-RPC `id`, if present.
+    pub id: Option<serde_json::Value>,
+    /// RFC 3339 time of the first copy.
+    pub first_seen: String,
+    /// RFC 3339 time of the latest copy.
+    pub last_seen: String,
+}
+
+impl RequestLog {
+    /// Create an empty log.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a request body for `session`, returning how many times this
+    /// exact body has now been seen in that session.
+    ///
+    /// Returns `None` for lifecycle messages, which are not recorded.
+    #[allow(clippy::significant_drop_tightening)]
+    pub fn record(&self, session: &str, body: &[u8]) -> Option<u64> {
+        let message = serde_json::from_slice::<serde_json::Value>(body).ok();
+        let method = message
+            .as_ref()
+            .and_then(|m| m.get("method"))
+            .and_then(|m| m.as_str());
+        if method.is_some_and(|method| LIFECYCLE_METHODS.contains(&method)) {
+            return None;
+        }
+        let sha256 = format!("{:x}", Sha256::digest(body));
+        let now = Utc::now();
+        let mut sessions = self.sessions.lock().unwrap_or_else(PoisonError::into_inner);
+        if !sessions.bodies.contains_key(session) {
+            if sessions.order.len() == MAX_SESSIONS
+                && let Some(oldest) = sessions.order.pop_front()
+            {
+                sessions.bodies.remove(&oldest);
+            }
+            sessions.order.push_back(session.to_string());
+        }
+        let bodies = sessions.bodies.entry(session.to_string()).or_default();
+        bodies.requests += 1;
+        if !bodies.records.contains_key(&sha256) {
+            if bodies.order.len() == MAX_BODIES
+                && let Some(oldest) = bodies.order.pop_front()
+            {
+                bodies.records.remove(&oldest);
+            }
+            bodies.order.push_back(sha256.clone());
+        }
+        let record = bodies.records.entry(sha256).or_insert_with(|| BodyRecord {
+            count: 0,
+            method: method.map(str::to_string),
+            id: message.as_ref().and_then(|m| m.get("id")).cloned(),
+            first_seen: now,
+            last_seen: now,
+        });
+        record.count += 1;
+        record.last_seen = now;
+        Some(record.count)
+    }
+
+    /// Build a report of every body received more than once.
+    #[must_use]
+    pub fn report(&self) -> DuplicateReport {
+        let sessions = self.sessions.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut report = DuplicateReport {
+            total_duplicates: 0,
+            sessions: Vec::new(),
+        };
+        for (session, bodies) in &sessions.bodies {
+            let mut duplicates: Vec<DuplicateEntry> = bodies
+                .records
+                .iter()
+                .filter(|(_, record)| record.count > 1)
+                .map(|(sha256, record)| DuplicateEntry {
+                    sha256: sha256.clone(),
+                    count: record.count,
+                    method: record.method.clone(),
+                    id: record.id.clone(),
+                    first_seen: record.first_seen.to_rfc3339(),
+                    last_seen: record.last_seen.to_rfc3339(),
+                })
+                .collect();
+            if duplicates.is_empty() {
+                continue;
+            }
+            duplicates.sort_by(|a, b| b.count.cmp(&a.count).then(a.sha256.cmp(&b.sha256)));
+            report.total_duplicates += duplicates.iter().map(|d| d.count - 1).sum::<u64>();
+            report.sessions.push(SessionDuplicates {
+                session: session.clone(),
+                requests: bodies.requests,
+                duplicates,
+            });
+        }
+        drop(sessions);
+        report.sessions.sort_by(|a, b| a.session.cmp(&b.session));
+        report
+    }
+
+    /// Forget every recorded body.
+    pub fn clear(&self) {
+        *self.sessions.lock().unwrap_or_else(PoisonError::into_inner) = Sessions::default();
+    }
+}
+
+/// Middleware hashing every `POST` body into a [`RequestLog`].
+pub async fn request_log_middleware(
+    State(log): State<Arc<RequestLog>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+
+    let (mut parts, body) = request.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    let session = crate::sessions::session_key(Some(&parts));
+    if let Some(count) = log.record(session, &bytes)
+        && count > 1
+    {
+        tracing::warn!(session, count, "Duplicate request body received");
+    }
+    parts
+        .extensions
+        .insert(crate::echo::ReceivedBody(bytes.len()));
+
+    next.run(Request::from_parts(parts, Body::from(bytes)))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALL: &[u8] =
+        br#"{"jsonrpc":"2.0","id":7,"method":"tools/call","params":{"name":"noop"}}"#;
+
+    #[test]
+    fn test_unique_bodies_not_reported() {
+        let log = RequestLog::new();
+        assert_eq!(log.record("s1", b"{\"id\":1}"), Some(1));
+        assert_eq!(log.record("s1", b"{\"id\":2}"), Some(1));
+        assert_eq!(log.report().total_duplicates, 0);
+        assert!(log.report().sessions.is_empty());
+    }
+
+    #[test]
+    fn test_duplicates_counted_per_session() {
+        let log = RequestLog::new();
+        log.record("s1", CALL);
+        log.record("s1", CALL);
+        log.record("s1", CALL);
+        // Same body in another session is not a duplicate of the first
+        log.record("s2", CALL);
+
+        let report = log.report();
+        assert_eq!(report.total_duplicates, 2);
+        assert_eq!(report.sessions.len(), 1);
+        let session = &report.sessions[0];
+        assert_eq!(session.session, "s1");
+        assert_eq!(session.requests, 3);
+        let entry = &session.duplicates[0];
+        assert_eq!(entry.count, 3);
+        assert_eq!(entry.method.as_deref(), Some("tools/call"));
+        assert_eq!(entry.id, Some(serde_json::json!(7)));
+        assert_eq!(entry.sha256, format!("{:x}", Sha256::digest(CALL)));
+    }
+
+    #[test]
+    fn test_non_json_body_still_hashed() {
+        let log = RequestLog::new();
+        log.record("s1", b"not json");
+        log.record("s1", b"not json");
+        let entry = &log.report().sessions[0].duplicates[0];
+        assert_eq!(entry.method, None);
+        assert_eq!(entry.id, None);
+    }
+
+    #[test]
+    fn test_lifecycle_messages_not_recorded() {
+        let log = RequestLog::new();
+        let initialize = br#"{"jsonrpc":"2.0","id":0,"method":"initialize","params":{}}"#;
+        let initialized = br#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#;
+        for _ in 0..2 {
+            assert_eq!(log.record("default", initialize), None);
+            assert_eq!(log.record("default", initialized), None);
+        }
+        assert_eq!(log.report().total_duplicates, 0);
+    }
+
+    #[test]
+    fn test_sessions_and_bodies_bounded() {
+        let log = RequestLog::new();
+        for i in 0..=MAX_BODIES {
+            log.record("s1", format!("{{\"id\":{i}}}").as_bytes());
+        }
+        // The first body was dropped, so its copy counts as new
+        assert_eq!(log.record("s1", b"{\"id\":0}"), Some(1));
+        assert_eq!(log.record("s1", b"{\"id\":2}"), Some(2));
+        assert_eq!(log.report().sessions[0].requests, MAX_BODIES as u64 + 3);
+
+        for i in 0..MAX_SESSIONS {
+            log.record(&format!("other-{i}"), CALL);
+        }
+        // s1 was the oldest session and is gone
+        assert!(log.report().sessions.is_empty());
+        assert_eq!(log.sessions.lock().unwrap().bodies.len(), MAX_SESSIONS);
+    }
+
+    #[test]
+    fn test_clear() {
+        let log = RequestLog::new();
+        log.record("s1", CALL);
+        log.record("s1", CALL);
+        log.clear();
+        assert_eq!(log.report().total_duplicates, 0);
+    }
+}
@@ -0,0 +1,86 @@
+//! Dry-run support for `tools/call`.
+//!
+//! A client sets `_meta.dryRun = true` on a `tools/call` request to ask what
+//! the call would do without doing it. The server reads the flag once and
+//! hands it to tools as an [`Extension<DryRun>`](rmcp::handler::server::common::Extension).
+//!
+//! Tools that mutate server state check the flag. In a dry run they return a
+//! [`preview`] of the change and leave state untouched; the result carries
+//! `_meta.dryRun = true`. Tools without side effects ignore the flag and run
+//! normally, so their results carry no `_meta.dryRun` marker. This tells the
+//! client whether the tool really honored the dry run.
+
+use rmcp::model::{CallToolResult, Content, Meta};
+use serde_json::Value;
+
+/// The `_meta` key carrying the dry-run flag, on requests and results.
+pub const META_KEY: &str = "dryRun";
+
+/// Whether the current `tools/call` is a dry run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DryRun(pub bool);
+
+impl DryRun {
+    /// Read the flag from request metadata. Only a JSON `true` enables it.
+    #[must_use]
+    pub fn from_meta(meta: &Meta) -> Self {
+        Self(meta.0.get(META_KEY).and_then(Value::as_bool) == Some(true))
+    }
+
+    /// Whether this is a dry run.
+    #[must_use]
+    pub const fn enabled(self) -> bool {
+        self.0
+    }
+}
+
+/// Build the result of a dry-run call to a stateful tool.
+///
+/// `action` describes the change in prose (e.g. `set key "a" to "1"`).
+/// `changes` describes it as structured data for clients to render.
+#[must_use]
+pub fn preview(action: &str, changes: Value) -> CallToolResult {
+    let mut result =
+        CallToolResult::success(vec![Content::text(format!("Dry run: would {action}"))]);
+    let mut structured = serde_json::Map::new();
+    structured.insert(META_KEY.to_string(), Value::Bool(true));
+    structured.insert("action".to_string(), Value::String(action.to_string()));
+    structured.insert("changes".to_string(), changes);
+    result.structured_content = Some(Value::Object(structured));
+    let mut meta = Meta::new();
+    meta.0.insert(META_KEY.to_string(), Value::Bool(true));
+    result.meta = Some(meta);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(value: Value) -> Meta {
+        let mut meta = Meta::new();
+        meta.0.insert(META_KEY.to_string(), value);
+        meta
+    }
+
+    #[test]
+    fn test_from_meta() {
+        assert!(DryRun::from_meta(&meta(Value::Bool(true))).enabled());
+        assert!(!DryRun::from_meta(&meta(Value::Bool(false))).enabled());
+        // Truthy strings are not enough
+        assert!(!DryRun::from_meta(&meta(Value::String("true".into()))).enabled());
+        assert!(!DryRun::from_meta(&Meta::new()).enabled());
+    }
+
+    #[test]
+    fn test_preview_marks_result() {
+        let result = preview("set key \"a\"", serde_json::json!({ "key": "a" }));
+        assert_eq!(result.is_error, Some(false));
+        assert_eq!(
+            result.content[0].as_text().unwrap().text,
+            "Dry run: would set key \"a\""
+        );
+        assert_eq!(result.meta.unwrap().0[META_KEY], true);
+        assert_eq!(result.structured_content.unwrap()["changes"]["key"], "a");
+    }
+}
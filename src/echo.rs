@@ -0,0 +1,135 @@
+//! Echo of the HTTP request the server received.
+//!
+//! The `debug_echo_request` tool and `/debug/headers` (any method) report
+//! what actually arrived after the client's transport layer and any
+//! proxies: method, path, HTTP version, every header, the
+//! `MCP-Protocol-Version` and `Mcp-Session-Id` headers, and the size of the
+//! body. For the tool, the body is the whole `POST /mcp` that carried the
+//! `tools/call`, as the client sent it before any compat rewriting.
+//!
+//! Credentials are not echoed: `Authorization` keeps only its scheme and
+//! `Cookie` only its length.
+
+use axum::Json;
+use axum::extract::Request;
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, StatusCode};
+use serde_json::{Map, Value, json};
+
+/// Size in bytes of the `POST /mcp` body, as received from the client.
+///
+/// Added to the request extensions by
+/// [`request_log_middleware`](crate::dedup::request_log_middleware), which
+/// buffers the body anyway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReceivedBody(pub usize);
+
+/// Describe the request behind `parts`, whose body was `body_bytes` long.
+///
+/// `parts` is `None` for requests that did not arrive over HTTP.
+#[must_use]
+pub fn request_report(parts: Option<&Parts>, body_bytes: Option<usize>) -> Value {
+    let Some(parts) = parts else {
+        return json!({
+            "transport": "in-process",
+            "headers": {},
+            "protocol_version": null,
+            "session_id": null,
+            "body_bytes": body_bytes,
+        });
+    };
+    let header = |name: &str| {
+        parts
+            .headers
+            .get(name)
+            .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned())
+    };
+    json!({
+        "transport": "http",
+        "method": parts.method.as_str(),
+        "path": parts.uri.path(),
+        "query": parts.uri.query(),
+        "http_version": format!("{:?}", parts.version),
+        "headers": headers(&parts.headers),
+        "protocol_version": header("mcp-protocol-version"),
+        "session_id": header("mcp-session-id"),
+        "content_length": header("content-length").and_then(|v| v.parse::<u64>().ok()),
+        "body_bytes": body_bytes,
+    })
+}
+
+/// Every header by lowercase name; repeated headers are joined with `, `.
+fn headers(headers: &HeaderMap) -> Value {
+    let mut map = Map::new();
+    for name in headers.keys() {
+        let values: Vec<String> = headers
+            .get_all(name)
+            .iter()
+            .map(|value| redact(name.as_str(), &String::from_utf8_lossy(value.as_bytes())))
+            .collect();
+        map.insert(name.to_string(), Value::String(values.join(", ")));
+    }
+    Value::Object(map)
+}
+
+/// `value` of the header `name`, with credentials masked.
+fn redact(name: &str, value: &str) -> String {
+    match name {
+        "authorization" | "proxy-authorization" => {
+            let scheme = value.split_whitespace().next().unwrap_or_default();
+            format!("{scheme} <redacted>")
+        }
+        "cookie" => format!("<redacted, {} bytes>", value.len()),
+        _ => value.to_string(),
+    }
+}
+
+/// `/debug/headers`: the request as received, for any method.
+///
+/// # Errors
+///
+/// Returns `400` if the body cannot be read.
+pub async fn debug_headers(request: Request) -> Result<Json<Value>, (StatusCode, String)> {
+    let (parts, body) = request.into_parts();
+    let bytes = axum::body::to_bytes(body, usize::MAX)
+        .await
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    Ok(Json(request_report(Some(&parts), Some(bytes.len()))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_redacts_credentials() {
+        let (parts, ()) = axum::http::Request::post("/mcp?x=1")
+            .header("Authorization", "Bearer secret-token")
+            .header("Cookie", "session=abc")
+            .header("MCP-Protocol-Version", "2025-06-18")
+            .header("Accept", "application/json")
+            .header("Accept", "text/event-stream")
+            .body(())
+            .unwrap()
+            .into_parts();
+        let report = request_report(Some(&parts), Some(42));
+        assert_eq!(report["method"], "POST");
+        assert_eq!(report["query"], "x=1");
+        assert_eq!(report["protocol_version"], "2025-06-18");
+        assert_eq!(report["session_id"], Value::Null);
+        assert_eq!(report["body_bytes"], 42);
+        assert_eq!(report["headers"]["authorization"], "Bearer <redacted>");
+        assert_eq!(report["headers"]["cookie"], "<redacted, 11 bytes>");
+        assert_eq!(
+            report["headers"]["accept"],
+            "application/json, text/event-stream"
+        );
+    }
+
+    #[test]
+    fn test_report_without_http() {
+        let report = request_report(None, None);
+        assert_eq!(report["transport"], "in-process");
+        assert_eq!(report["body_bytes"], Value::Null);
+    }
+}
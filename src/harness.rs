@@ -0,0 +1,343 @@
+//! Test harness for client test suites (feature `test-util`).
+//!
+//! [`TestServer`] starts the server on a free localhost port and shuts it
+//! down when dropped; [`McpClient`] is a minimal Streamable HTTP client for
+//! poking at it with raw JSON-RPC:
+//!
+//! ```rust,no_run
+//! use mcp_test_server::harness::{McpClient, TestServer};
+//!
+//! # async fn example() {
+//! let server = TestServer::start().await;
+//! let client = McpClient::connect(&server).await;
+//! let tools = client.request("tools/list", serde_json::json!({})).await;
+//! assert!(tools["result"]["tools"].is_array());
+//! # }
+//! ```
+//!
+//! Like assertions, the helpers panic when something goes wrong, so a
+//! failing setup fails the test that called it.
+
+#![allow(clippy::missing_panics_doc)]
+
+use std::net::SocketAddr;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use crate::{Config, McpTestServer, McpTestServerBuilder, ServerHandle};
+
+/// Test server handle that automatically shuts down when dropped.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use mcp_test_server::harness::TestServer;
+/// # async fn example() -> reqwest::Result<()> {
+/// let server = TestServer::start().await;
+/// let client = reqwest::Client::new();
+/// let resp = client.get(server.health_url()).send().await?;
+/// assert_eq!(resp.status(), 200);
+/// // Server shuts down when `server` is dropped (or call `shutdown().await`)
+/// # Ok(())
+/// # }
+/// ```
+pub struct TestServer {
+    /// The socket address the server is bound to.
+    pub addr: SocketAddr,
+    scheme: &'static str,
+    handle: Option<ServerHandle>,
+}
+
+impl TestServer {
+    /// Start a test server on an available port with default configuration.
+    pub async fn start() -> Self {
+        Self::start_with_config(Config::default()).await
+    }
+
+    /// Start a test server with API key authentication enabled.
+    pub async fn start_with_auth(api_key: impl Into<String>) -> Self {
+        let config = Config::builder().api_key(api_key).build();
+        Self::start_with_config(config).await
+    }
+
+    /// Start a test server with custom configuration.
+    ///
+    /// Note: The host and port will be overridden to use localhost
+    /// and a free port chosen by the OS.
+    pub async fn start_with_config(config: Config) -> Self {
+        Self::start_with_builder(McpTestServer::builder().config(config)).await
+    }
+
+    /// Start a test server with custom tools from `builder`.
+    ///
+    /// As with [`TestServer::start_with_config`], the host and port are
+    /// overridden.
+    pub async fn start_with_builder(mut builder: McpTestServerBuilder) -> Self {
+        builder.config.host = std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST);
+        builder.config.port = 0;
+
+        let handle = builder
+            .build()
+            .spawn()
+            .await
+            .expect("Failed to start test server");
+        Self {
+            addr: handle.addr(),
+            scheme: handle.scheme(),
+            handle: Some(handle),
+        }
+    }
+
+    /// Start a test server that shuts down when `signal` completes.
+    pub async fn start_until(
+        config: Config,
+        signal: impl std::future::Future<Output = ()> + Send + 'static,
+    ) -> Self {
+        let mut server = Self::start_with_config(config).await;
+        let handle = server.handle.take();
+        tokio::spawn(async move {
+            signal.await;
+            if let Some(handle) = handle
+                && let Err(e) = handle.shutdown().await
+            {
+                tracing::error!("Test server error: {e}");
+            }
+        });
+        server
+    }
+
+    /// Shut the server down and wait until it has stopped.
+    pub async fn shutdown(mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.shutdown().await.expect("Test server failed");
+        }
+    }
+
+    /// Get the base URL for this test server (`https://` with TLS).
+    #[must_use]
+    pub fn base_url(&self) -> String {
+        format!("{}://{}", self.scheme, self.addr)
+    }
+
+    /// Get the health endpoint URL.
+    #[must_use]
+    pub fn health_url(&self) -> String {
+        format!("{}/health", self.base_url())
+    }
+
+    /// Get the MCP streamable HTTP endpoint URL.
+    #[must_use]
+    pub fn mcp_url(&self) -> String {
+        format!("{}/mcp", self.base_url())
+    }
+
+    /// Get the server's port.
+    #[must_use]
+    pub const fn port(&self) -> u16 {
+        self.addr.port()
+    }
+}
+
+/// Initialize tracing for tests.
+///
+/// This function is safe to call multiple times; subsequent calls are no-ops.
+pub fn init_test_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_test_writer()
+        .with_max_level(tracing::Level::DEBUG)
+        .try_init();
+}
+
+/// Create a pre-configured reqwest client for testing.
+#[must_use]
+pub fn test_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()
+        .expect("Failed to build test client")
+}
+
+/// Create a reqwest client with an authorization header.
+#[must_use]
+pub fn test_client_with_auth(api_key: &str) -> reqwest::Client {
+    use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        AUTHORIZATION,
+        HeaderValue::from_str(&format!("Bearer {api_key}")).expect("Invalid API key"),
+    );
+
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .default_headers(headers)
+        .build()
+        .expect("Failed to build test client")
+}
+
+/// Minimal JSON-RPC client speaking the Streamable HTTP transport.
+///
+/// Performs the `initialize` handshake on connect and tracks the
+/// `Mcp-Session-Id` header when the server issues one.
+pub struct McpClient {
+    client: reqwest::Client,
+    url: String,
+    session_id: Option<String>,
+    next_id: std::sync::atomic::AtomicU64,
+}
+
+impl McpClient {
+    /// Connect to a test server and complete the initialization handshake.
+    pub async fn connect(server: &TestServer) -> Self {
+        Self::connect_with_client(server, test_client()).await
+    }
+
+    /// Connect sending `Authorization: Bearer <token>` on every request.
+    pub async fn connect_with_auth(server: &TestServer, token: &str) -> Self {
+        Self::connect_with_client(server, test_client_with_auth(token)).await
+    }
+
+    async fn connect_with_client(server: &TestServer, client: reqwest::Client) -> Self {
+        let mut this = Self {
+            client,
+            url: server.mcp_url(),
+            session_id: None,
+            next_id: std::sync::atomic::AtomicU64::new(1),
+        };
+
+        let response = this
+            .post(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 0,
+                "method": "initialize",
+                "params": {
+                    "protocolVersion": "2025-06-18",
+                    "capabilities": {},
+                    "clientInfo": { "name": "test-client", "version": "0.0.0" }
+                }
+            }))
+            .await;
+        assert!(response.status().is_success(), "initialize failed");
+        this.session_id = response
+            .headers()
+            .get("mcp-session-id")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let _ = response.text().await;
+
+        this.notify("notifications/initialized", serde_json::json!({}))
+            .await;
+        this
+    }
+
+    /// The session ID issued by the server, if any.
+    #[must_use]
+    pub fn session_id(&self) -> Option<&str> {
+        self.session_id.as_deref()
+    }
+
+    /// POST a raw JSON-RPC message.
+    pub async fn post(&self, message: &serde_json::Value) -> reqwest::Response {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header("Accept", "application/json, text/event-stream")
+            .json(message);
+        if let Some(id) = &self.session_id {
+            request = request.header("mcp-session-id", id);
+        }
+        request.send().await.expect("MCP request failed")
+    }
+
+    /// Terminate the session with `DELETE /mcp`.
+    pub async fn delete(&self) -> reqwest::Response {
+        let mut request = self.client.delete(&self.url);
+        if let Some(id) = &self.session_id {
+            request = request.header("mcp-session-id", id);
+        }
+        request.send().await.expect("MCP request failed")
+    }
+
+    /// Send a request and return the JSON-RPC response message.
+    pub async fn request(&self, method: &str, params: serde_json::Value) -> serde_json::Value {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let response = self
+            .post(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": method,
+                "params": params,
+            }))
+            .await;
+        let body = response.text().await.expect("Failed to read response body");
+        parse_sse_messages(&body)
+            .into_iter()
+            .find(|m| m["id"] == id)
+            .unwrap_or_else(|| panic!("No response to {method} in body: {body}"))
+    }
+
+    /// Send a notification.
+    pub async fn notify(&self, method: &str, params: serde_json::Value) {
+        let response = self
+            .post(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": method,
+                "params": params,
+            }))
+            .await;
+        assert!(response.status().is_success(), "{method} was rejected");
+    }
+
+    /// Open the standalone server-to-client SSE stream (requires stateful mode).
+    pub async fn open_stream(&self) -> SseStream {
+        let mut request = self
+            .client
+            .get(&self.url)
+            .header("Accept", "text/event-stream")
+            .timeout(Duration::from_secs(30));
+        if let Some(id) = &self.session_id {
+            request = request.header("mcp-session-id", id);
+        }
+        let response = request.send().await.expect("Failed to open SSE stream");
+        assert!(response.status().is_success(), "SSE stream rejected");
+        SseStream {
+            response,
+            buffer: String::new(),
+        }
+    }
+}
+
+/// Incrementally parsed server-sent event stream of JSON-RPC messages.
+pub struct SseStream {
+    response: reqwest::Response,
+    buffer: String,
+}
+
+impl SseStream {
+    /// Wait for the next JSON-RPC message, or `None` if the stream ends.
+    pub async fn next_message(&mut self) -> Option<serde_json::Value> {
+        loop {
+            if let Some(end) = self.buffer.find("\n\n") {
+                let event: String = self.buffer.drain(..end + 2).collect();
+                if let Some(message) = parse_sse_messages(&event).into_iter().next() {
+                    return Some(message);
+                }
+                continue;
+            }
+            let chunk = self.response.chunk().await.ok()??;
+            self.buffer.push_str(&String::from_utf8_lossy(&chunk));
+        }
+    }
+}
+
+/// Extract the JSON payloads of `data:` lines from an SSE (or plain JSON) body.
+#[must_use]
+pub fn parse_sse_messages(body: &str) -> Vec<serde_json::Value> {
+    if let Ok(value) = serde_json::from_str(body) {
+        return vec![value];
+    }
+    body.lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .filter_map(|data| serde_json::from_str(data.trim()).ok())
+        .collect()
+}
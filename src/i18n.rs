@@ -0,0 +1,365 @@
+//! Error message and prompt localization.
+//!
+//! Clients should branch on JSON-RPC error codes and structured `data`, not on
+//! message text. With `MCP_ERROR_LANG` set, every error message the server
+//! returns — protocol errors and `isError` tool results alike — is rewritten
+//! in another language, so string-matching clients break loudly and clients
+//! that display messages can check non-Latin and right-to-left rendering.
+//!
+//! Codes and `data` are never changed.
+//!
+//! Prompts are localized separately: `MCP_LOCALE` picks the [`Locale`] of
+//! prompt titles, descriptions, and `greeting_localized` output (see
+//! [`crate::prompts::localized`]).
+
+use rmcp::ErrorData;
+use rmcp::model::{CallToolResult, RawContent};
+
+/// Language used for error messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorLang {
+    /// English, unchanged (default).
+    #[default]
+    English,
+    /// German.
+    German,
+    /// Japanese (non-Latin script).
+    Japanese,
+    /// Arabic (right-to-left script).
+    Arabic,
+    /// Pseudo-localization: accented letters, padded and bracketed, so the
+    /// text stays readable but matches no English string.
+    Pseudo,
+    /// Lorem ipsum with the same word count as the original.
+    Lorem,
+}
+
+/// Known English message prefixes and their German, Japanese, and Arabic
+/// translations. Whatever follows the prefix (names, URIs) is kept as is.
+const TRANSLATIONS: &[(&str, [&str; 3])] = &[
+    (
+        "Unknown resource URI: ",
+        [
+            "Unbekannte Ressourcen-URI: ",
+            "不明なリソースURI: ",
+            "معرّف مورد غير معروف: ",
+        ],
+    ),
+    (
+        "Resource does not support subscriptions: ",
+        [
+            "Ressource unterstützt keine Abonnements: ",
+            "このリソースは購読に対応していません: ",
+            "المورد لا يدعم الاشتراكات: ",
+        ],
+    ),
+    (
+        "Unknown prompt: ",
+        [
+            "Unbekannter Prompt: ",
+            "不明なプロンプト: ",
+            "موجّه غير معروف: ",
+        ],
+    ),
+    (
+        "Missing required argument: ",
+        [
+            "Fehlendes Pflichtargument: ",
+            "必須引数がありません: ",
+            "وسيطة مطلوبة مفقودة: ",
+        ],
+    ),
+    (
+        "Unknown argument(s) for tool ",
+        [
+            "Unbekannte Argumente für Werkzeug ",
+            "ツールの不明な引数 ",
+            "وسائط غير معروفة للأداة ",
+        ],
+    ),
+    (
+        "failed to deserialize parameters: ",
+        [
+            "Parameter konnten nicht deserialisiert werden: ",
+            "パラメータの逆シリアル化に失敗しました: ",
+            "تعذّر إلغاء تسلسل المعاملات: ",
+        ],
+    ),
+    (
+        "tool not found",
+        [
+            "Werkzeug nicht gefunden",
+            "ツールが見つかりません",
+            "الأداة غير موجودة",
+        ],
+    ),
+    (
+        "Division by zero",
+        ["Division durch null", "ゼロ除算", "القسمة على صفر"],
+    ),
+    (
+        "min must be less than or equal to max",
+        [
+            "min muss kleiner oder gleich max sein",
+            "min は max 以下である必要があります",
+            "يجب أن يكون min أصغر من max أو مساويًا له",
+        ],
+    ),
+    (
+        "This tool always fails",
+        [
+            "Dieses Werkzeug schlägt immer fehl",
+            "このツールは常に失敗します",
+            "هذه الأداة تفشل دائمًا",
+        ],
+    ),
+];
+
+/// Prefix for messages with no translation, per language.
+const GENERIC_PREFIX: [&str; 3] = ["Fehler: ", "エラー: ", "خطأ: "];
+
+const LOREM_WORDS: &[&str] = &[
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+];
+
+impl ErrorLang {
+    /// Parse a language code: `en`, `de`, `ja`, `ar`, `pseudo`, or `lorem`
+    /// (case-insensitive).
+    #[must_use]
+    pub fn parse(code: &str) -> Option<Self> {
+        match code.trim().to_ascii_lowercase().as_str() {
+            "en" => Some(Self::English),
+            "de" => Some(Self::German),
+            "ja" => Some(Self::Japanese),
+            "ar" => Some(Self::Arabic),
+            "pseudo" => Some(Self::Pseudo),
+            "lorem" => Some(Self::Lorem),
+            _ => None,
+        }
+    }
+
+    /// Localize one message.
+    #[must_use]
+    pub fn localize(self, message: &str) -> String {
+        let index = match self {
+            Self::English => return message.to_string(),
+            Self::Pseudo => return pseudo_localize(message),
+            Self::Lorem => return lorem(message),
+            Self::German => 0,
+            Self::Japanese => 1,
+            Self::Arabic => 2,
+        };
+        TRANSLATIONS
+            .iter()
+            .find_map(|(english, translated)| {
+                message
+                    .strip_prefix(english)
+                    .map(|rest| format!("{}{rest}", translated[index]))
+            })
+            .unwrap_or_else(|| format!("{}{message}", GENERIC_PREFIX[index]))
+    }
+
+    /// Localize the message of a protocol error, keeping its code and data.
+    #[must_use]
+    pub fn localize_error(self, mut error: ErrorData) -> ErrorData {
+        if self != Self::English {
+            error.message = self.localize(&error.message).into();
+        }
+        error
+    }
+
+    /// Localize the text content of an `isError` tool result.
+    #[must_use]
+    pub fn localize_tool_result(self, mut result: CallToolResult) -> CallToolResult {
+        if self == Self::English || result.is_error != Some(true) {
+            return result;
+        }
+        for content in &mut result.content {
+            if let RawContent::Text(text) = &mut content.raw {
+                text.text = self.localize(&text.text);
+            }
+        }
+        result
+    }
+}
+
+/// Locale of prompt metadata and `greeting_localized` output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    /// English (default).
+    #[default]
+    English,
+    /// German.
+    German,
+    /// Japanese.
+    Japanese,
+    /// Arabic (right-to-left script).
+    Arabic,
+}
+
+impl Locale {
+    /// Parse a locale: `en`, `de`, `ja`, or `ar`, optionally with a region
+    /// such as `de-AT` or `ja_JP` (case-insensitive).
+    #[must_use]
+    pub fn parse(code: &str) -> Option<Self> {
+        let language = code.trim().split(['-', '_']).next()?;
+        match language.to_ascii_lowercase().as_str() {
+            "en" => Some(Self::English),
+            "de" => Some(Self::German),
+            "ja" => Some(Self::Japanese),
+            "ar" => Some(Self::Arabic),
+            _ => None,
+        }
+    }
+
+    /// The language code: `en`, `de`, `ja`, or `ar`.
+    #[must_use]
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::German => "de",
+            Self::Japanese => "ja",
+            Self::Arabic => "ar",
+        }
+    }
+}
+
+/// Accent every ASCII letter, then bracket and pad by about a third to mimic
+/// the expansion of real translations.
+fn pseudo_localize(message: &str) -> String {
+    let accented: String = message
+        .chars()
+        .map(|c| match c {
+            'a' => 'á',
+            'e' => 'é',
+            'i' => 'î',
+            'o' => 'ö',
+            'u' => 'ü',
+            'c' => 'ç',
+            'n' => 'ñ',
+            's' => 'š',
+            'y' => 'ý',
+            'A' => 'Å',
+            'E' => 'É',
+            'I' => 'Î',
+            'O' => 'Ö',
+            'U' => 'Û',
+            'C' => 'Ç',
+            'N' => 'Ñ',
+            'S' => 'Š',
+            other => other,
+        })
+        .collect();
+    let padding = "~".repeat(message.chars().count().div_ceil(3));
+    format!("[{accented} {padding}]")
+}
+
+/// Replace each word with lorem ipsum, keeping the word count.
+fn lorem(message: &str) -> String {
+    let words = message.split_whitespace().count().max(1);
+    LOREM_WORDS
+        .iter()
+        .cycle()
+        .take(words)
+        .copied()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(ErrorLang::parse("DE"), Some(ErrorLang::German));
+        assert_eq!(ErrorLang::parse("pseudo"), Some(ErrorLang::Pseudo));
+        assert_eq!(ErrorLang::parse("klingon"), None);
+    }
+
+    #[test]
+    fn test_parse_locale() {
+        assert_eq!(Locale::parse("ja"), Some(Locale::Japanese));
+        assert_eq!(Locale::parse("de-AT"), Some(Locale::German));
+        assert_eq!(Locale::parse("AR_eg"), Some(Locale::Arabic));
+        assert_eq!(Locale::parse("pseudo"), None);
+        assert_eq!(Locale::parse(""), None);
+    }
+
+    #[test]
+    fn test_english_unchanged() {
+        assert_eq!(
+            ErrorLang::English.localize("Unknown prompt: x"),
+            "Unknown prompt: x"
+        );
+    }
+
+    #[test]
+    fn test_known_prefix_translated_and_suffix_kept() {
+        assert_eq!(
+            ErrorLang::German.localize("Unknown resource URI: test://nope"),
+            "Unbekannte Ressourcen-URI: test://nope"
+        );
+        assert_eq!(ErrorLang::Japanese.localize("Division by zero"), "ゼロ除算");
+    }
+
+    #[test]
+    fn test_unknown_message_gets_generic_prefix() {
+        assert_eq!(
+            ErrorLang::Arabic.localize("custom failure"),
+            "خطأ: custom failure"
+        );
+    }
+
+    #[test]
+    fn test_pseudo_localization() {
+        let localized = ErrorLang::Pseudo.localize("Unknown prompt");
+        assert_eq!(localized, "[Ûñkñöwñ prömpt ~~~~~]");
+    }
+
+    #[test]
+    fn test_lorem_keeps_word_count() {
+        let localized = ErrorLang::Lorem.localize("Missing required argument: text");
+        assert_eq!(localized, "lorem ipsum dolor sit");
+    }
+
+    #[test]
+    fn test_localize_error_keeps_code_and_data() {
+        let error = ErrorData::invalid_params(
+            "Missing required argument: name",
+            Some(serde_json::json!({ "argument": "name" })),
+        );
+        let localized = ErrorLang::German.localize_error(error);
+        assert_eq!(localized.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+        assert_eq!(localized.message, "Fehlendes Pflichtargument: name");
+        assert_eq!(
+            localized.data,
+            Some(serde_json::json!({ "argument": "name" }))
+        );
+    }
+
+    #[test]
+    fn test_localize_tool_result_only_errors() {
+        let ok = CallToolResult::success(vec![rmcp::model::Content::text("Division by zero")]);
+        let ok = ErrorLang::German.localize_tool_result(ok);
+        assert_eq!(ok.content[0].as_text().unwrap().text, "Division by zero");
+
+        let err = CallToolResult::error(vec![rmcp::model::Content::text("Division by zero")]);
+        let err = ErrorLang::German.localize_tool_result(err);
+        assert_eq!(
+            err.content[0].as_text().unwrap().text,
+            "Division durch null"
+        );
+    }
+}
@@ -1,6 +1,10 @@
 //! Server icon assets.
 //!
-//! Contains the base64-encoded SVG icon used in the server's `Implementation` metadata.
+//! Contains the base64-encoded icons used in the server's `Implementation`
+//! metadata and in the definitions of the MCP App tools. `MCP_SERVER_ICONS`
+//! picks which server icons are advertised (see [`ServerIcons`]).
+
+use rmcp::model::Icon;
 
 /// Base64-encoded SVG data URI for the server icon (test tube / beaker).
 ///
@@ -18,3 +22,141 @@ pub const SERVER_ICON_SVG: &str = concat!(
     "N2FiMyIgc3Ryb2tlPSJub25lIi8+PGNpcmNsZSBjeD0iNDIiIGN5PSI0MiIgcj0iMi41IiBmaWxs",
     "PSIjNWI5YmQzIiBzdHJva2U9Im5vbmUiLz48L3N2Zz4=",
 );
+
+/// Base64-encoded 16x16 PNG data URI for the server icon.
+pub const SERVER_ICON_PNG_16: &str = concat!(
+    "data:image/png;base64,",
+    "iVBORw0KGgoAAAANSUhEUgAAABAAAAAQCAYAAAAf8/9hAAAATElEQVR42mNgwAOMqzb/B2EGUgBM",
+    "Ey5MkWa8hhCrGashpGrGMGRgDSBXM9wQig0AgejZl/+Tg+FhMPAGkGMI1tRIkWZiDSEpZxKjCQCw",
+    "qMk2gcwupwAAAABJRU5ErkJggg==",
+);
+
+/// Base64-encoded 32x32 PNG data URI for the server icon.
+pub const SERVER_ICON_PNG_32: &str = concat!(
+    "data:image/png;base64,",
+    "iVBORw0KGgoAAAANSUhEUgAAACAAAAAgCAYAAABzenr0AAAAeklEQVR42u3XSw0AIQxFUTwhZ5xg",
+    "BRlIGUEggNDQ6edBpiRd37MibUq3vVxapwYSNcV8jYsR0rAIoh1nIazi2wgowDpOIrziS8S/Ad7x",
+    "CRGAAMABT307Ys4BIBDTTxgATwS5E0DjRwAsEazNGBrXhKhcR9A4F3PdtT0AMFkcWO+IovMAAAAA",
+    "SUVORK5CYII=",
+);
+
+/// Base64-encoded 64x64 PNG data URI for the server icon.
+pub const SERVER_ICON_PNG_64: &str = concat!(
+    "data:image/png;base64,",
+    "iVBORw0KGgoAAAANSUhEUgAAAEAAAABACAYAAACqaXHeAAAA6ElEQVR42u3bUREDIQxF0fVUOeuk",
+    "ViqjUlYQNdAOSwkhyX2ZQcA9n0COQ6Nxmcfz3UYOLrgMiHV4GojV4WEhvMNDQeyO34YQJXwLRNR4",
+    "F4To8UsRssQvQcgWb4qQNd4MAQ2QPX4KoUr83whogGrxwwhogKrxtxHQANXjuwgCEAAYgBL/E0EA",
+    "AhCAAAQgAAEIAAtwvq5GOgL4Nuh4AQhAAAiE7q0wHqAywu2XITxARYTh12E8QCWEqV8i6HgBJEcw",
+    "/SuIjs+GsPS/MDo+OoL73gA2PBJCmN0hbLg3RJr9QWy4FYiWjTU+8wFC2D3XoxDsYAAAAABJRU5E",
+    "rkJggg==",
+);
+
+/// Base64-encoded SVG data URI for the MCP App tools (an app window).
+///
+/// Listed in the `icons` of every `ui_*` tool so clients rendering tool
+/// icons have a fixture next to the server icon.
+pub const UI_TOOL_ICON_SVG: &str = concat!(
+    "data:image/svg+xml;base64,",
+    "PHN2ZyB4bWxucz0iaHR0cDovL3d3dy53My5vcmcvMjAwMC9zdmciIHZpZXdCb3g9IjAgMCA2NCA2",
+    "NCIgZmlsbD0ibm9uZSIgc3Ryb2tlPSIjMzM3YWIzIiBzdHJva2Utd2lkdGg9IjMiIHN0cm9rZS1s",
+    "aW5lY2FwPSJyb3VuZCIgc3Ryb2tlLWxpbmVqb2luPSJyb3VuZCI+PHJlY3QgeD0iNiIgeT0iMTAi",
+    "IHdpZHRoPSI1MiIgaGVpZ2h0PSI0NCIgcng9IjQiLz48bGluZSB4MT0iNiIgeTE9IjIyIiB4Mj0i",
+    "NTgiIHkyPSIyMiIvPjxjaXJjbGUgY3g9IjEzIiBjeT0iMTYiIHI9IjEuNSIgZmlsbD0iIzMzN2Fi",
+    "MyIgc3Ryb2tlPSJub25lIi8+PGNpcmNsZSBjeD0iMTkiIGN5PSIxNiIgcj0iMS41IiBmaWxsPSIj",
+    "NWI5YmQzIiBzdHJva2U9Im5vbmUiLz48cmVjdCB4PSIxNCIgeT0iMzAiIHdpZHRoPSIzNiIgaGVp",
+    "Z2h0PSIxNCIgcng9IjMiIHN0cm9rZT0iIzViOWJkMyIvPjwvc3ZnPg==",
+);
+
+/// Which server icons `initialize` advertises in `serverInfo.icons`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ServerIcons {
+    /// The SVG followed by the 16, 32, and 64 pixel PNGs (default).
+    #[default]
+    All,
+    /// Only the SVG.
+    Svg,
+    /// Only the PNGs, for clients falling back from SVG.
+    Png,
+    /// No icons; `icons` is omitted.
+    None,
+}
+
+impl ServerIcons {
+    /// Parse `all`, `svg`, `png`, or `none` (case-insensitive).
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "all" => Some(Self::All),
+            "svg" => Some(Self::Svg),
+            "png" => Some(Self::Png),
+            "none" => Some(Self::None),
+            _ => None,
+        }
+    }
+
+    /// The icons to advertise; `None` omits the field.
+    #[must_use]
+    pub fn icons(self) -> Option<Vec<Icon>> {
+        let svg = || {
+            Icon::new(SERVER_ICON_SVG)
+                .with_mime_type("image/svg+xml")
+                .with_sizes(vec!["any".to_string()])
+        };
+        let pngs = || {
+            [
+                (SERVER_ICON_PNG_16, "16x16"),
+                (SERVER_ICON_PNG_32, "32x32"),
+                (SERVER_ICON_PNG_64, "64x64"),
+            ]
+            .map(|(src, size)| {
+                Icon::new(src)
+                    .with_mime_type("image/png")
+                    .with_sizes(vec![size.to_string()])
+            })
+        };
+        match self {
+            Self::All => Some(std::iter::once(svg()).chain(pngs()).collect()),
+            Self::Svg => Some(vec![svg()]),
+            Self::Png => Some(pngs().to_vec()),
+            Self::None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+
+    #[test]
+    fn test_png_icons_decode_at_their_size() {
+        for (src, size) in [
+            (SERVER_ICON_PNG_16, 16),
+            (SERVER_ICON_PNG_32, 32),
+            (SERVER_ICON_PNG_64, 64),
+        ] {
+            let data = src.strip_prefix("data:image/png;base64,").unwrap();
+            let png = base64::engine::general_purpose::STANDARD
+                .decode(data)
+                .unwrap();
+            assert_eq!(&png[1..4], b"PNG");
+            // IHDR width and height
+            assert_eq!(u32::from_be_bytes(png[16..20].try_into().unwrap()), size);
+            assert_eq!(u32::from_be_bytes(png[20..24].try_into().unwrap()), size);
+        }
+    }
+
+    #[test]
+    fn test_server_icons_selection() {
+        assert_eq!(ServerIcons::parse("PNG"), Some(ServerIcons::Png));
+        assert_eq!(ServerIcons::parse("gif"), None);
+        assert_eq!(ServerIcons::All.icons().unwrap().len(), 4);
+        assert_eq!(ServerIcons::Svg.icons().unwrap().len(), 1);
+        let png = ServerIcons::Png.icons().unwrap();
+        assert!(
+            png.iter()
+                .all(|i| i.mime_type.as_deref() == Some("image/png"))
+        );
+        assert_eq!(ServerIcons::None.icons(), None);
+    }
+}
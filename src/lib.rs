@@ -17,11 +17,11 @@
 //! - Streamable HTTP transport (`/mcp` endpoint)
 //! - API key authentication via `Authorization: Bearer` header
 //! - OAuth 2.1 mock endpoints for testing client authentication flows
-//! - 33 tools for testing (math, string, encoding, utility, testing, tasks, UI)
+//! - 97 tools for testing (math, string, array, encoding, compression, conversion, utility, key-value, counter, scratch resources, ephemeral resources, prompt registration, fetch, regex, date/time, CSV, XML, JSON query, templates, Markdown, testing, tasks, UI)
 //! - MCP Tasks support for async long-running operations
-//! - 11 resources (static, dynamic, and MCP App UI) with subscription support
+//! - 29 resources (static, dynamic, case fixtures, meta, and MCP App UI) with subscription support
 //! - 7 MCP App interactive UI tools with CDN fallbacks
-//! - 5 prompts with argument validation
+//! - 9 prompts with argument validation and localized metadata
 //! - Auto-completion for prompt arguments
 //! - Logging level control
 //!
@@ -38,6 +38,37 @@
 //! }
 //! ```
 //!
+//! To embed the server in tests, bind port 0 and wait for it to be ready
+//! instead of sleeping:
+//!
+//! ```rust,no_run
+//! use mcp_test_server::{Config, McpTestServer};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let server = McpTestServer::new(Config::builder().port(0).build());
+//! let listener = server.bind().await?;
+//! let running = server.clone();
+//! tokio::spawn(async move { running.run_with_listener(listener, std::future::pending()).await });
+//! let addr = server.ready().await;
+//! println!("MCP endpoint: http://{addr}/mcp");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Or let [`McpTestServer::spawn`] do that, and stop the server cleanly
+//! with the returned [`ServerHandle`]:
+//!
+//! ```rust,no_run
+//! use mcp_test_server::{Config, McpTestServer};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let handle = McpTestServer::new(Config::builder().port(0).build()).spawn().await?;
+//! println!("MCP endpoint: {}", handle.mcp_url());
+//! handle.shutdown().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! # Configuration
 //!
 //! Configuration is done via environment variables:
@@ -47,29 +78,152 @@
 //! | `MCP_HOST` | `0.0.0.0` | Server bind address |
 //! | `MCP_PORT` | `3000` | Server listen port |
 //! | `MCP_API_KEY` | (none) | API key for authentication |
+//! | `MCP_SEED` | (none) | Default seed for generated fixtures and seeded random tools |
+//! | `MCP_UUID_NAMESPACE` | (none) | Deterministic UUID v5 namespace for `random_uuid` |
+//! | `MCP_AUTH_FAILURE_MODE` | `spec` | `spec` (401 + challenge), `forbidden` (403), or `bare_401` |
 //! | `MCP_LOG_LEVEL` | `info` | Logging level |
+//! | `MCP_STRICT_ARGS` | `false` | Reject unknown tool arguments with `-32602` |
+//! | `MCP_STRICT_PROTOCOL_VERSION` | `false` | Answer `400` to `/mcp` requests after `initialize` without the negotiated `MCP-Protocol-Version` header |
+//! | `MCP_BATCH` | `spec` | JSON-RPC batches on `/mcp`: `spec` (accepted before `2025-06-18`), `accept`, or `reject` |
+//! | `MCP_LENIENT_NAMES` | `false` | Match tool names and resource URIs ignoring case and whitespace |
+//! | `MCP_STATEFUL` | `false` | Enable `Mcp-Session-Id` sessions and server-to-client notifications |
+//! | `MCP_MAX_SESSIONS` | (none) | Maximum concurrent stateful sessions |
+//! | `MCP_SESSION_TTL` | (none) | Idle timeout of stateful sessions, e.g. `30s` |
+//! | `MCP_COMPAT` | (none) | Older protocol revision to speak: `2024-11-05` or `2025-03-26` |
+//! | `MCP_DRAIN_SECS` | (none) | Seconds to keep serving existing sessions after Ctrl+C |
+//! | `MCP_RECORD` | (none) | Append every `POST /mcp` exchange to this file |
+//! | `MCP_REPLAY` | (none) | Answer `POST /mcp` from a recording instead of running tools |
+//! | `MCP_MOCK_ROUTES` | (none) | JSON file of canned routes for the `fetch_url` mock upstream |
+//! | `MCP_REGEX_TIMEOUT_MS` | `1000` | Time a `regex_*` tool call may run before it fails |
+//! | `MCP_MAX_RESPONSE_BYTES` | (none) | Largest tool result; larger ones become a `-32603` error |
+//! | `MCP_PANIC_POLICY` | `error` | Report tool panics as a `-32603` error or by dropping the connection |
+//! | `MCP_REQUEST_TIMEOUT` | (none) | Time a tool call may run before a `-32001` timeout error, e.g. `30s` |
+//! | `MCP_CHAOS_RESPONSES` | (none) | Disturb responses: comma-separated `duplicate`, `reorder` |
+//! | `MCP_CHAOS_RATE` | `0.1` | Probability that each chaos mode picks a response |
+//! | `MCP_DENY_SESSION_DELETE` | `false` | Answer `DELETE /mcp` with `405` instead of closing the session |
+//! | `MCP_TICKER_INTERVAL_MS` | `1000` | Update interval of `test://dynamic/ticker` |
+//! | `MCP_OAUTH_FAILURES` | (none) | Comma-separated OAuth mock failures to inject |
+//! | `MCP_MAX_SSE_EVENT_BYTES` | (none) | Maximum SSE event payload size |
+//! | `MCP_SSE_OVERSIZE_POLICY` | `reject` | `reject` or `split` oversized SSE events |
+//! | `MCP_SSE_DRIP_BYTES` | (none) | Flush SSE responses in chunks of at most this many bytes |
+//! | `MCP_SSE_DRIP_DELAY` | `10ms` | Pause between dripped SSE chunks |
+//! | `MCP_SSE_KEEPALIVE_SECS` | `15` | Interval of SSE keep-alive comments (`0` disables them) |
+//! | `MCP_TOOL_ERROR_RATE` | (none) | Tools that fail intermittently with `isError`, e.g. `add:0.25,echo:0.5` |
+//! | `MCP_TOOL_ERROR_MODE` | `native` | Report tool failures as each tool does, or all as `result` (`isError`) or `protocol` (JSON-RPC errors) |
+//! | `MCP_SCHEMA_ERRORS` | `opaque` | Detail of `-32602` errors for arguments that fail the schema: `opaque`, `summary`, or `detailed` |
+//! | `MCP_UNKNOWN_TOOL` | `error` | Unknown tools: `error` (`-32602`) or `result` (`isError`) |
+//! | `MCP_UNKNOWN_NOTIFICATION` | `ignore` | Unknown notifications: `ignore` (`202`) or `error` (`400` with `-32601`) |
+//! | `MCP_SYNTHETIC_RESOURCES` | `0` | Number of generated `test://synthetic/{n}` resources |
+//! | `MCP_PING_INTERVAL` | (none) | Send each session a `ping` request this often, e.g. `30s` (stateful) |
+//! | `MCP_OAUTH` | `false` | Require OAuth mock access tokens on `/mcp` |
+//! | `MCP_ERROR_LANG` | `en` | Error message language: `en`, `de`, `ja`, `ar`, `pseudo`, `lorem` |
+//! | `MCP_LOCALE` | `en` | Locale of prompt metadata: `en`, `de`, `ja`, `ar` |
+//! | `MCP_COMPLETION_DELAY` | (none) | Delay before answering `completion/complete`, e.g. `300ms` |
+//! | `MCP_COMPLETION_RATE_LIMIT` | (none) | Most `completion/complete` requests per second and session; more get `-32029` |
+//! | `MCP_SERVER_ICONS` | `all` | Server icons in `initialize`: `all` (SVG and 16/32/64 px PNGs), `svg`, `png`, `none` |
+//! | `MCP_INSTRUCTIONS` | (built-in) | `instructions` in `initialize`; empty omits the field |
+//! | `MCP_SERVER_NAME` | `mcp-test-server` | `serverInfo.name` in `initialize` |
+//! | `MCP_SERVER_TITLE` | `MCP Test Server` | `serverInfo.title` in `initialize`; empty omits the field |
+//! | `MCP_SERVER_VERSION` | (crate version) | `serverInfo.version` in `initialize` |
+//! | `MCP_CAPABILITIES` | (all) | Comma-separated capabilities to advertise, e.g. `tools,resources`, or `none` |
+//! | `MCP_EXPERIMENTAL_CAPABILITIES` | (none) | JSON file whose object is advertised as `capabilities.experimental` |
+//! | `MCP_UI_ASSETS` | `cdn` | MCP App libraries: `cdn` (jsDelivr tags with vanilla fallback) or `inline` (no external scripts) |
+//! | `MCP_UI_CSP` | `none` | `_meta.ui.csp` of the `ui://` apps: `none`, `strict`, `cdn`, or `lax` |
+//! | `MCP_HOSTED_APPS` | `false` | Serve the apps at `/apps/{app}` with a `Content-Security-Policy` header |
+//! | `MCP_COMPRESSION` | `off` | Response compression: `off`, `on` (gzip, deflate, br per `Accept-Encoding`), or `forced` (always gzip) |
+//! | `MCP_CORS_ORIGINS` | `*` | Origins allowed by CORS: `*`, `none` (no CORS headers), or a comma-separated list |
+//! | `MCP_CORS_METHODS` | `*` | Methods a CORS preflight allows |
+//! | `MCP_CORS_HEADERS` | `*` | Request headers a CORS preflight allows |
+//! | `MCP_CORS_EXPOSE_HEADERS` | `*` | Response headers exposed to the page, e.g. `Mcp-Session-Id`; empty exposes none |
+//! | `MCP_TLS_CERT` | (none) | PEM certificate chain; with `MCP_TLS_KEY`, serve HTTPS with `h2` and `http/1.1` via ALPN |
+//! | `MCP_TLS_KEY` | (none) | PEM private key of `MCP_TLS_CERT` |
+//! | `MCP_PUBLIC_URL` | (none) | Base URL behind a reverse proxy, for the OAuth issuer and metadata URLs |
+//!
+//! The binary checks these with [`Config::validate_env`] at startup and exits
+//! listing every problem; `--check-config` validates without binding.
 //!
 //! # Modules
 //!
+//! - [`advertise`] - Capabilities, server identity, and instructions advertised in `initialize`
+//! - [`admin`] - Admin endpoints for fixture setup (batch tool calls)
 //! - [`auth`] - Authentication middleware for API key and origin validation
+//! - [`batch`] - JSON-RPC batches on `/mcp`, served or rejected per protocol revision
+//! - [`chaos`] - Duplicated and reordered responses, intermittent tool failures
+//! - `broken` - Spec-violating responses for `mcp-broken-server` (feature `broken-server`)
+//! - [`compat`] - Request shims for clients on older protocol revisions
+//! - [`completion`] - Artificial latency and rate limiting for completions
 //! - [`config`] - Server configuration from environment variables
+//! - [`conformance`] - Per-session protocol coverage reports
+//! - [`content_encoding`] - Response compression negotiated via `Accept-Encoding`
+//! - [`cors`] - CORS answers for browser-based clients
+//! - `container` - Testcontainers image for the Docker image (feature `testcontainers`)
+//! - [`custom`] - Resources and prompts registered by embedders
+//! - [`dedup`] - Duplicate request detection (retry auditing)
+//! - [`dry_run`] - `_meta.dryRun` support for stateful tools
+//! - [`echo`] - Echo of the HTTP request as received (`debug_echo_request`, `/debug/headers`)
+//! - `harness` - `TestServer` and a raw JSON-RPC client for tests (feature `test-util`)
+//! - [`i18n`] - Error message and prompt localization
 //! - [`oauth`] - Mock OAuth 2.1 endpoints (RFC 9728, 8414, 7591)
+//! - [`pings`] - Server-initiated pings and client answers
 //! - [`prompts`] - Prompt templates and argument handling
+//! - [`protocol_header`] - Strict `MCP-Protocol-Version` header checks
+//! - [`proxy`] - Public URLs behind reverse proxies (`MCP_PUBLIC_URL`, `X-Forwarded-*`)
+//! - [`replay`] - Recording of JSON-RPC exchanges and replay of recordings
 //! - [`resources`] - Static and dynamic resource handlers
+//! - [`scenarios`] - Reusable end-to-end scenarios for client test suites
 //! - [`server`] - Main server implementation with all tools
+//! - [`sessions`] - Session tracking, limits, expiry, and termination
+//! - [`sse`] - SSE event rewriting (maximum event size, slow drip)
+//! - [`supervision`] - Tool panic supervision, request timeouts, and deliberate connection aborts
+//! - [`tool_errors`] - Reporting tool failures as `isError` results or JSON-RPC errors
 //! - [`tools`] - Tool parameter structures
+//! - [`transport`] - Connection instrumentation and serving (half-close detection and handling) and TLS with ALPN
+//! - [`unknown`] - Responses to unknown tools and unknown notifications
+//! - [`validation`] - Strict tool argument validation, schema error details, and the response size cap
 
+pub mod admin;
+pub mod advertise;
 pub mod auth;
+pub mod batch;
+#[cfg(feature = "broken-server")]
+pub mod broken;
+pub mod chaos;
+pub mod compat;
+pub mod completion;
 pub mod config;
+pub mod conformance;
+#[cfg(feature = "testcontainers")]
+pub mod container;
+pub mod content_encoding;
+pub mod cors;
+pub mod custom;
+pub mod dedup;
+pub mod dry_run;
+pub mod echo;
 pub mod error;
+#[cfg(feature = "test-util")]
+pub mod harness;
+pub mod i18n;
 pub mod icons;
 pub mod oauth;
+pub mod pings;
 pub mod prompts;
+pub mod protocol_header;
+pub mod proxy;
+pub mod replay;
 pub mod resources;
+pub mod scenarios;
 pub mod server;
+pub mod sessions;
+pub mod sse;
+pub mod supervision;
+pub mod tool_errors;
 pub mod tools;
+pub mod transport;
+pub mod unknown;
+pub mod validation;
 
 pub use config::Config;
 pub use error::{Result, ServerError};
 pub use resources::ResourceHandler;
-pub use server::McpTestServer;
+pub use server::{McpTestServer, McpTestServerBuilder, ServerHandle};
@@ -1,10 +1,35 @@
 //! MCP Test Server entry point.
+//!
+//! `mcp-test-server --check-config` validates the environment and exits
+//! without binding: 0 if the configuration is valid, 1 otherwise.
+
+use std::process::ExitCode;
 
 use mcp_test_server::{Config, McpTestServer};
 use tracing_subscriber::{EnvFilter, fmt, prelude::*};
 
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
+async fn main() -> anyhow::Result<ExitCode> {
+    let check_only = match std::env::args().nth(1).as_deref() {
+        None => false,
+        Some("--check-config") => true,
+        Some(arg) => anyhow::bail!("unknown argument {arg:?} (supported: --check-config)"),
+    };
+
+    // Fail fast, listing every problem, before anything binds
+    if let Err(errors) = Config::validate_env() {
+        eprintln!("{errors}");
+        return Ok(ExitCode::FAILURE);
+    }
+    if check_only {
+        let config = Config::from_env();
+        println!(
+            "Configuration OK: would listen on {}:{}",
+            config.host, config.port
+        );
+        return Ok(ExitCode::SUCCESS);
+    }
+
     // Initialize tracing
     let filter =
         EnvFilter::try_from_env("MCP_LOG_LEVEL").unwrap_or_else(|_| EnvFilter::new("info"));
@@ -27,5 +52,5 @@ async fn main() -> anyhow::Result<()> {
     let server = McpTestServer::new(config);
     server.run().await?;
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
@@ -10,28 +10,112 @@
 //! 5. Client exchanges code at `/oauth/token`
 //! 6. Client uses Bearer token for `/mcp`
 //!
+//! In OAuth mode ([`bearer_auth_middleware`]), step 6 is enforced: `/mcp`
+//! answers requests without a valid token with `401` and a
+//! `WWW-Authenticate` challenge pointing at the protected resource metadata.
+//!
+//! Machine-to-machine clients can skip steps 3–5 by registering with a
+//! `client_secret_post` or `client_secret_basic` auth method and requesting
+//! tokens directly with `grant_type=client_credentials`.
+//!
 //! All tokens are test tokens — no real cryptographic verification.
+//!
+//! The mock can also be told to misbehave (see [`OAuthFailure`]) so that
+//! client error handling can be exercised systematically.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
 use std::sync::Arc;
 
 use axum::Router;
-use axum::extract::{Query, State};
-use axum::response::{Html, IntoResponse, Json, Redirect};
+use axum::extract::{Query, Request, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header::WWW_AUTHENTICATE};
+use axum::middleware::Next;
+use axum::response::{Html, IntoResponse, Json, Redirect, Response};
 use axum::routing::{get, post};
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
 use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
 
+/// A failure mode the OAuth mock can inject on demand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OAuthFailure {
+    /// `/oauth/register` rejects every registration with `invalid_client`.
+    RegisterInvalidClient,
+    /// `/oauth/token` treats every authorization code as expired.
+    ExpiredCode,
+    /// `/oauth/authorize` redirects back with a `state` that differs from the request.
+    StateMismatch,
+    /// `/oauth/token` responds with `500 Internal Server Error`.
+    TokenServerError,
+    /// The `.well-known` metadata documents are served as truncated JSON.
+    MalformedMetadata,
+}
+
+impl OAuthFailure {
+    /// Every failure mode, in a stable order.
+    pub const ALL: [Self; 5] = [
+        Self::RegisterInvalidClient,
+        Self::ExpiredCode,
+        Self::StateMismatch,
+        Self::TokenServerError,
+        Self::MalformedMetadata,
+    ];
+
+    /// The name used for this failure in `MCP_OAUTH_FAILURES`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::RegisterInvalidClient => "register_invalid_client",
+            Self::ExpiredCode => "expired_code",
+            Self::StateMismatch => "state_mismatch",
+            Self::TokenServerError => "token_500",
+            Self::MalformedMetadata => "malformed_metadata",
+        }
+    }
+
+    /// Parse a failure mode from its name (case-insensitive).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim();
+        Self::ALL
+            .into_iter()
+            .find(|f| f.as_str().eq_ignore_ascii_case(name))
+    }
+
+    /// Parse a comma-separated list of failure names, skipping unknown entries.
+    #[must_use]
+    pub fn parse_list(list: &str) -> Vec<Self> {
+        list.split(',').filter_map(Self::parse).collect()
+    }
+}
+
 /// Shared state for the OAuth mock server.
 #[derive(Debug, Clone)]
 pub struct OAuthState {
     /// Base URL of this server (e.g., `http://localhost:3000`).
     pub issuer: String,
+    /// Fixed public base URL overriding the issuer and request headers.
+    public_url: Option<String>,
     /// Registered clients: `client_id` -> client metadata.
     clients: Arc<Mutex<HashMap<String, RegisteredClient>>>,
     /// Pending authorization codes: code -> grant metadata.
     codes: Arc<Mutex<HashMap<String, AuthorizationGrant>>>,
+    /// Issued access tokens: token -> grant metadata.
+    tokens: Arc<Mutex<HashMap<String, IssuedToken>>>,
+    /// Failure modes to inject.
+    failures: Arc<HashSet<OAuthFailure>>,
+}
+
+/// Metadata recorded for every access token the mock issues.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IssuedToken {
+    /// Client the token was issued to, if known.
+    pub client_id: Option<String>,
+    /// Granted scope.
+    pub scope: String,
+    /// Grant type used to obtain the token.
+    pub grant_type: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -39,11 +123,15 @@ struct RegisteredClient {
     client_id: String,
     client_name: Option<String>,
     redirect_uris: Vec<String>,
+    /// Secret issued at registration for confidential clients.
+    #[serde(skip)]
+    client_secret: Option<String>,
+    token_endpoint_auth_method: String,
 }
 
 #[derive(Debug, Clone)]
 struct AuthorizationGrant {
-    _client_id: String,
+    client_id: String,
     redirect_uri: String,
     code_challenge: Option<String>,
     _code_challenge_method: Option<String>,
@@ -55,12 +143,82 @@ impl OAuthState {
     pub fn new(issuer: impl Into<String>) -> Self {
         Self {
             issuer: issuer.into(),
+            public_url: None,
             clients: Arc::new(Mutex::new(HashMap::new())),
             codes: Arc::new(Mutex::new(HashMap::new())),
+            tokens: Arc::new(Mutex::new(HashMap::new())),
+            failures: Arc::new(HashSet::new()),
+        }
+    }
+
+    /// Inject the given failure modes into every flow.
+    #[must_use]
+    pub fn with_failures(mut self, failures: impl IntoIterator<Item = OAuthFailure>) -> Self {
+        self.failures = Arc::new(failures.into_iter().collect());
+        self
+    }
+
+    /// Use `public_url` as the base of every advertised URL, whatever the
+    /// request headers say (`MCP_PUBLIC_URL`).
+    #[must_use]
+    pub fn with_public_url(mut self, public_url: Option<String>) -> Self {
+        self.public_url = public_url;
+        self
+    }
+
+    /// Base URL the client sees this server at: the public URL if set,
+    /// otherwise derived from forwarded headers, `Host`, or the issuer.
+    #[must_use]
+    pub fn base_url(&self, headers: &HeaderMap) -> String {
+        self.public_url
+            .clone()
+            .unwrap_or_else(|| crate::proxy::external_base_url(headers, &self.issuer))
+    }
+
+    /// Look up an access token issued by this mock.
+    pub async fn token_info(&self, token: &str) -> Option<IssuedToken> {
+        self.tokens.lock().await.get(token).cloned()
+    }
+
+    /// Issue and record a new access token.
+    async fn issue_access_token(
+        &self,
+        client_id: Option<String>,
+        scope: String,
+        grant_type: &str,
+    ) -> String {
+        let token = format!("test-access-{}", uuid::Uuid::new_v4());
+        let info = IssuedToken {
+            client_id,
+            scope,
+            grant_type: grant_type.to_string(),
+        };
+        self.tokens.lock().await.insert(token.clone(), info);
+        token
+    }
+
+    /// Whether a failure mode is enabled, logging the injection if so.
+    fn injects(&self, failure: OAuthFailure) -> bool {
+        let enabled = self.failures.contains(&failure);
+        if enabled {
+            tracing::debug!(failure = failure.as_str(), "Injecting OAuth failure");
         }
+        enabled
     }
 }
 
+/// A JSON document cut off mid-object, served in place of metadata when
+/// [`OAuthFailure::MalformedMetadata`] is enabled.
+fn malformed_json(document: &serde_json::Value) -> Response {
+    let text = document.to_string();
+    let truncated: String = text.chars().take(text.len() / 2).collect();
+    (
+        [(axum::http::header::CONTENT_TYPE, "application/json")],
+        truncated,
+    )
+        .into_response()
+}
+
 /// Build the OAuth router with all discovery and flow endpoints.
 ///
 /// All routes are public (no auth) — this is the OAuth provider itself.
@@ -87,13 +245,21 @@ pub fn oauth_router(state: OAuthState) -> Router {
 /// `GET /.well-known/oauth-protected-resource`
 ///
 /// RFC 9728: tells MCP clients where to find the authorization server.
-async fn protected_resource_metadata(State(state): State<OAuthState>) -> Json<serde_json::Value> {
-    Json(serde_json::json!({
-        "resource": state.issuer,
-        "authorization_servers": [state.issuer],
+async fn protected_resource_metadata(
+    State(state): State<OAuthState>,
+    headers: HeaderMap,
+) -> Response {
+    let base = state.base_url(&headers);
+    let document = serde_json::json!({
+        "resource": base,
+        "authorization_servers": [base],
         "bearer_methods_supported": ["header"],
         "scopes_supported": ["mcp"]
-    }))
+    });
+    if state.injects(OAuthFailure::MalformedMetadata) {
+        return malformed_json(&document);
+    }
+    Json(document).into_response()
 }
 
 // =============================================================================
@@ -103,59 +269,113 @@ async fn protected_resource_metadata(State(state): State<OAuthState>) -> Json<se
 /// `GET /.well-known/oauth-authorization-server`
 ///
 /// RFC 8414: tells MCP clients the full OAuth endpoint layout.
-async fn authorization_server_metadata(State(state): State<OAuthState>) -> Json<serde_json::Value> {
-    Json(serde_json::json!({
-        "issuer": state.issuer,
-        "authorization_endpoint": format!("{}/oauth/authorize", state.issuer),
-        "token_endpoint": format!("{}/oauth/token", state.issuer),
-        "registration_endpoint": format!("{}/oauth/register", state.issuer),
+async fn authorization_server_metadata(
+    State(state): State<OAuthState>,
+    headers: HeaderMap,
+) -> Response {
+    let base = state.base_url(&headers);
+    let document = serde_json::json!({
+        "issuer": base,
+        "authorization_endpoint": format!("{base}/oauth/authorize"),
+        "token_endpoint": format!("{base}/oauth/token"),
+        "registration_endpoint": format!("{base}/oauth/register"),
         "response_types_supported": ["code"],
-        "grant_types_supported": ["authorization_code", "refresh_token"],
+        "grant_types_supported": ["authorization_code", "refresh_token", "client_credentials"],
         "code_challenge_methods_supported": ["S256", "plain"],
-        "token_endpoint_auth_methods_supported": ["none"],
+        "token_endpoint_auth_methods_supported": TOKEN_ENDPOINT_AUTH_METHODS,
         "scopes_supported": ["mcp"],
         "service_documentation": "https://github.com/nazq/test_mcp_servers"
-    }))
+    });
+    if state.injects(OAuthFailure::MalformedMetadata) {
+        return malformed_json(&document);
+    }
+    Json(document).into_response()
 }
 
 // =============================================================================
 // RFC 7591 — Dynamic Client Registration
 // =============================================================================
 
+/// Client authentication methods accepted at the token endpoint.
+const TOKEN_ENDPOINT_AUTH_METHODS: [&str; 3] =
+    ["none", "client_secret_post", "client_secret_basic"];
+
 #[derive(Debug, Deserialize)]
 struct RegisterRequest {
     #[serde(default)]
     client_name: Option<String>,
     #[serde(default)]
     redirect_uris: Vec<String>,
+    /// Requested client authentication method (default: `none`).
+    #[serde(default)]
+    token_endpoint_auth_method: Option<String>,
 }
 
 /// `POST /oauth/register`
 ///
 /// RFC 7591: Dynamic client registration. Issues a new `client_id` for any
 /// registrant. No authentication required (public DCR).
+///
+/// Clients registering with `client_secret_post` or `client_secret_basic`
+/// also receive a `client_secret` and may use the `client_credentials` grant.
 async fn register_client(
     State(state): State<OAuthState>,
     Json(request): Json<RegisterRequest>,
-) -> Json<serde_json::Value> {
+) -> Response {
+    if state.injects(OAuthFailure::RegisterInvalidClient) {
+        return token_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_client",
+            "Client registration rejected (injected failure)",
+        );
+    }
+
+    let auth_method = request
+        .token_endpoint_auth_method
+        .clone()
+        .unwrap_or_else(|| "none".to_string());
+    if !TOKEN_ENDPOINT_AUTH_METHODS.contains(&auth_method.as_str()) {
+        return token_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_client_metadata",
+            format!("Unsupported token_endpoint_auth_method: {auth_method}"),
+        );
+    }
+
     let client_id = format!("test-client-{}", uuid::Uuid::new_v4());
+    let client_secret =
+        (auth_method != "none").then(|| format!("test-secret-{}", uuid::Uuid::new_v4()));
+    let grant_types = if client_secret.is_some() {
+        vec!["authorization_code", "refresh_token", "client_credentials"]
+    } else {
+        vec!["authorization_code", "refresh_token"]
+    };
 
     let client = RegisteredClient {
         client_id: client_id.clone(),
         client_name: request.client_name.clone(),
         redirect_uris: request.redirect_uris.clone(),
+        client_secret: client_secret.clone(),
+        token_endpoint_auth_method: auth_method.clone(),
     };
 
     state.clients.lock().await.insert(client_id.clone(), client);
 
-    Json(serde_json::json!({
+    let mut body = serde_json::json!({
         "client_id": client_id,
         "client_name": request.client_name,
         "redirect_uris": request.redirect_uris,
-        "grant_types": ["authorization_code", "refresh_token"],
+        "grant_types": grant_types,
         "response_types": ["code"],
-        "token_endpoint_auth_method": "none"
-    }))
+        "token_endpoint_auth_method": auth_method
+    });
+    if let Some(secret) = client_secret {
+        body["client_secret"] = serde_json::json!(secret);
+        // RFC 7591 §3.2.1: 0 means the secret never expires
+        body["client_secret_expires_at"] = serde_json::json!(0);
+    }
+
+    Json(body).into_response()
 }
 
 // =============================================================================
@@ -204,7 +424,7 @@ async fn authorize(
 
     // Store the grant
     let grant = AuthorizationGrant {
-        _client_id: params.client_id,
+        client_id: params.client_id,
         redirect_uri: params.redirect_uri.clone(),
         code_challenge: params.code_challenge,
         _code_challenge_method: params.code_challenge_method,
@@ -216,7 +436,10 @@ async fn authorize(
     let mut redirect_url = params.redirect_uri;
     redirect_url.push_str(if redirect_url.contains('?') { "&" } else { "?" });
     let _ = write!(redirect_url, "code={code}");
-    if let Some(ref s) = params.state {
+    if state.injects(OAuthFailure::StateMismatch) {
+        let s = params.state.as_deref().unwrap_or("unsolicited");
+        let _ = write!(redirect_url, "&state={s}-mismatch");
+    } else if let Some(ref s) = params.state {
         let _ = write!(redirect_url, "&state={s}");
     }
 
@@ -235,24 +458,116 @@ struct TokenRequest {
     #[serde(default)]
     redirect_uri: Option<String>,
     #[serde(default)]
-    _client_id: Option<String>,
+    client_id: Option<String>,
+    #[serde(default)]
+    client_secret: Option<String>,
     #[serde(default)]
     code_verifier: Option<String>,
     #[serde(default)]
     refresh_token: Option<String>,
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+/// Build an RFC 6749 §5.2 token endpoint error response.
+fn token_error(status: StatusCode, error: &str, description: impl Into<String>) -> Response {
+    (
+        status,
+        Json(serde_json::json!({
+            "error": error,
+            "error_description": description.into()
+        })),
+    )
+        .into_response()
+}
+
+/// Extract client credentials from an HTTP Basic `Authorization` header.
+///
+/// Returns `None` if the header is absent or not a well-formed Basic credential.
+fn basic_credentials(headers: &HeaderMap) -> Option<(String, String)> {
+    let header = headers.get("authorization")?.to_str().ok()?;
+    let encoded = header.strip_prefix("Basic ")?;
+    let decoded = String::from_utf8(BASE64.decode(encoded.trim()).ok()?).ok()?;
+    let (id, secret) = decoded.split_once(':')?;
+    Some((id.to_string(), secret.to_string()))
+}
+
+/// Authenticate a confidential client for the `client_credentials` grant.
+///
+/// Credentials must arrive via the method the client registered with:
+/// form fields for `client_secret_post`, the `Authorization` header for
+/// `client_secret_basic`.
+async fn authenticate_client(
+    state: &OAuthState,
+    headers: &HeaderMap,
+    request: &TokenRequest,
+) -> Result<RegisteredClient, Response> {
+    let (method, client_id, secret) = if let Some((id, secret)) = basic_credentials(headers) {
+        ("client_secret_basic", id, secret)
+    } else if let (Some(id), Some(secret)) = (&request.client_id, &request.client_secret) {
+        ("client_secret_post", id.clone(), secret.clone())
+    } else {
+        return Err(token_error(
+            StatusCode::UNAUTHORIZED,
+            "invalid_client",
+            "Client authentication required",
+        ));
+    };
+
+    let client = state.clients.lock().await.get(&client_id).cloned();
+    let Some(client) = client else {
+        return Err(token_error(
+            StatusCode::UNAUTHORIZED,
+            "invalid_client",
+            "Unknown client_id",
+        ));
+    };
+
+    if client.token_endpoint_auth_method != method {
+        return Err(token_error(
+            StatusCode::UNAUTHORIZED,
+            "invalid_client",
+            format!(
+                "Client registered with {} but authenticated with {method}",
+                client.token_endpoint_auth_method
+            ),
+        ));
+    }
+
+    let valid = client.client_secret.as_deref().is_some_and(|expected| {
+        crate::auth::constant_time_compare(expected.as_bytes(), secret.as_bytes())
+    });
+    if !valid {
+        return Err(token_error(
+            StatusCode::UNAUTHORIZED,
+            "invalid_client",
+            "Invalid client_secret",
+        ));
+    }
+
+    Ok(client)
 }
 
 /// `POST /oauth/token`
 ///
 /// Token endpoint. Exchanges authorization codes for access tokens.
-/// Also handles refresh token grants.
+/// Also handles refresh token and client credentials grants.
 ///
 /// For testing, returns a simple opaque token — no JWT signing.
-#[allow(clippy::significant_drop_tightening)]
+#[allow(clippy::significant_drop_tightening, clippy::too_many_lines)]
 async fn token_exchange(
     State(state): State<OAuthState>,
+    headers: HeaderMap,
     axum::Form(request): axum::Form<TokenRequest>,
 ) -> impl IntoResponse {
+    if state.injects(OAuthFailure::TokenServerError) {
+        return token_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "server_error",
+            "Token endpoint failure (injected)",
+        );
+    }
+
     match request.grant_type.as_str() {
         "authorization_code" => {
             let Some(code) = &request.code else {
@@ -278,6 +593,14 @@ async fn token_exchange(
                     .into_response();
             };
 
+            if state.injects(OAuthFailure::ExpiredCode) {
+                return token_error(
+                    StatusCode::BAD_REQUEST,
+                    "invalid_grant",
+                    "Authorization code has expired",
+                );
+            }
+
             // Validate redirect_uri matches
             if let Some(ref uri) = request.redirect_uri
                 && *uri != grant.redirect_uri
@@ -305,7 +628,10 @@ async fn token_exchange(
             }
 
             // Issue tokens
-            let access_token = format!("test-access-{}", uuid::Uuid::new_v4());
+            let scope = grant.scope.unwrap_or_else(|| "mcp".to_string());
+            let access_token = state
+                .issue_access_token(Some(grant.client_id), scope.clone(), "authorization_code")
+                .await;
             let refresh_token = format!("test-refresh-{}", uuid::Uuid::new_v4());
 
             Json(serde_json::json!({
@@ -313,7 +639,7 @@ async fn token_exchange(
                 "token_type": "Bearer",
                 "expires_in": 3600,
                 "refresh_token": refresh_token,
-                "scope": grant.scope.unwrap_or_else(|| "mcp".to_string())
+                "scope": scope
             }))
             .into_response()
         }
@@ -331,7 +657,13 @@ async fn token_exchange(
             }
 
             // For testing, always issue a new token pair
-            let access_token = format!("test-access-{}", uuid::Uuid::new_v4());
+            let access_token = state
+                .issue_access_token(
+                    request.client_id.clone(),
+                    "mcp".to_string(),
+                    "refresh_token",
+                )
+                .await;
             let refresh_token = format!("test-refresh-{}", uuid::Uuid::new_v4());
 
             Json(serde_json::json!({
@@ -344,6 +676,32 @@ async fn token_exchange(
             .into_response()
         }
 
+        "client_credentials" => {
+            let client = match authenticate_client(&state, &headers, &request).await {
+                Ok(client) => client,
+                Err(response) => return response,
+            };
+
+            // RFC 6749 §4.4.3: no refresh token for client credentials
+            let scope = request.scope.unwrap_or_else(|| "mcp".to_string());
+            let access_token = state
+                .issue_access_token(
+                    Some(client.client_id.clone()),
+                    scope.clone(),
+                    "client_credentials",
+                )
+                .await;
+            tracing::debug!(client_id = %client.client_id, "Issued client_credentials token");
+
+            Json(serde_json::json!({
+                "access_token": access_token,
+                "token_type": "Bearer",
+                "expires_in": 3600,
+                "scope": scope
+            }))
+            .into_response()
+        }
+
         _ => (
             axum::http::StatusCode::BAD_REQUEST,
             Json(serde_json::json!({
@@ -355,6 +713,79 @@ async fn token_exchange(
     }
 }
 
+// =============================================================================
+// OAuth Mode — Bearer Token Enforcement (RFC 6750, RFC 9728 §5.1)
+// =============================================================================
+
+/// URL of the protected resource metadata document, as seen by the client.
+///
+/// Uses [`OAuthState::base_url`] so the challenge points at an address the
+/// client can actually reach, even through a proxy.
+fn resource_metadata_url(state: &OAuthState, headers: &HeaderMap) -> String {
+    format!(
+        "{}/.well-known/oauth-protected-resource",
+        state.base_url(headers)
+    )
+}
+
+/// Build a `401 Unauthorized` response with a Bearer challenge.
+///
+/// `error` is omitted when no credentials were presented (RFC 6750 §3.1).
+fn bearer_challenge(state: &OAuthState, headers: &HeaderMap, error: Option<&str>) -> Response {
+    let metadata = resource_metadata_url(state, headers);
+    let challenge = error.map_or_else(
+        || format!(r#"Bearer resource_metadata="{metadata}", scope="mcp""#),
+        |description| {
+            format!(
+                r#"Bearer error="invalid_token", error_description="{description}", resource_metadata="{metadata}""#
+            )
+        },
+    );
+    let mut response = token_error(
+        StatusCode::UNAUTHORIZED,
+        if error.is_some() {
+            "invalid_token"
+        } else {
+            "unauthorized"
+        },
+        error.unwrap_or("Bearer token required"),
+    );
+    if let Ok(value) = HeaderValue::from_str(&challenge) {
+        response.headers_mut().insert(WWW_AUTHENTICATE, value);
+    }
+    response
+}
+
+/// Middleware requiring an access token issued by this mock.
+///
+/// Requests without a token, or with one the mock never issued, receive
+/// `401` with `WWW-Authenticate: Bearer resource_metadata="..."` so clients
+/// can discover the authorization server from the challenge alone. The
+/// token's [`IssuedToken`] is added to the request extensions on success.
+pub async fn bearer_auth_middleware(
+    State(state): State<OAuthState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let token = request
+        .headers()
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::to_string);
+    let Some(token) = token else {
+        tracing::debug!("Missing bearer token, sending challenge");
+        return bearer_challenge(&state, request.headers(), None);
+    };
+    let Some(info) = state.token_info(&token).await else {
+        tracing::debug!("Unknown bearer token, sending challenge");
+        return bearer_challenge(&state, request.headers(), Some("Unknown access token"));
+    };
+
+    request.extensions_mut().insert(info);
+    next.run(request).await
+}
+
 #[cfg(test)]
 #[allow(clippy::significant_drop_tightening)]
 mod tests {
@@ -364,11 +795,42 @@ mod tests {
         OAuthState::new("http://localhost:3000")
     }
 
+    async fn response_json(response: Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    fn register_request(auth_method: Option<&str>) -> RegisterRequest {
+        RegisterRequest {
+            client_name: Some("Test Client".to_string()),
+            redirect_uris: vec!["http://localhost:8080/callback".to_string()],
+            token_endpoint_auth_method: auth_method.map(String::from),
+        }
+    }
+
+    fn client_credentials_request(
+        client_id: Option<String>,
+        client_secret: Option<String>,
+    ) -> TokenRequest {
+        TokenRequest {
+            grant_type: "client_credentials".to_string(),
+            code: None,
+            redirect_uri: None,
+            client_id,
+            client_secret,
+            code_verifier: None,
+            refresh_token: None,
+            scope: None,
+        }
+    }
+
     #[tokio::test]
     async fn test_protected_resource_metadata() {
         let state = test_state();
-        let result = protected_resource_metadata(State(state)).await;
-        let json = result.0;
+        let result = protected_resource_metadata(State(state), HeaderMap::new()).await;
+        let json = response_json(result).await;
         assert_eq!(json["resource"], "http://localhost:3000");
         assert!(
             json["authorization_servers"]
@@ -381,8 +843,8 @@ mod tests {
     #[tokio::test]
     async fn test_authorization_server_metadata() {
         let state = test_state();
-        let result = authorization_server_metadata(State(state)).await;
-        let json = result.0;
+        let result = authorization_server_metadata(State(state), HeaderMap::new()).await;
+        let json = response_json(result).await;
         assert_eq!(json["issuer"], "http://localhost:3000");
         assert_eq!(
             json["authorization_endpoint"],
@@ -404,12 +866,9 @@ mod tests {
     #[tokio::test]
     async fn test_register_client() {
         let state = test_state();
-        let request = RegisterRequest {
-            client_name: Some("Test Client".to_string()),
-            redirect_uris: vec!["http://localhost:8080/callback".to_string()],
-        };
+        let request = register_request(None);
         let result = register_client(State(state.clone()), Json(request)).await;
-        let json = result.0;
+        let json = response_json(result).await;
 
         assert!(
             json["client_id"]
@@ -419,6 +878,7 @@ mod tests {
         );
         assert_eq!(json["client_name"], "Test Client");
         assert_eq!(json["token_endpoint_auth_method"], "none");
+        assert!(json.get("client_secret").is_none());
 
         // Verify client was stored
         assert_eq!(state.clients.lock().await.len(), 1);
@@ -432,9 +892,13 @@ mod tests {
         let reg_request = RegisterRequest {
             client_name: Some("Flow Test".to_string()),
             redirect_uris: vec!["http://localhost:8080/callback".to_string()],
+            token_endpoint_auth_method: None,
         };
         let reg_result = register_client(State(state.clone()), Json(reg_request)).await;
-        let client_id = reg_result.0["client_id"].as_str().unwrap().to_string();
+        let client_id = response_json(reg_result).await["client_id"]
+            .as_str()
+            .unwrap()
+            .to_string();
 
         // 2. Authorize — creates a code and redirects
         let auth_params = AuthorizeParams {
@@ -476,11 +940,18 @@ mod tests {
             grant_type: "authorization_code".to_string(),
             code: Some(code),
             redirect_uri: Some("http://localhost:8080/callback".to_string()),
-            _client_id: Some(client_id),
+            client_id: Some(client_id),
+            client_secret: None,
             code_verifier: None,
             refresh_token: None,
+            scope: None,
         };
-        let token_result = token_exchange(State(state.clone()), axum::Form(token_request)).await;
+        let token_result = token_exchange(
+            State(state.clone()),
+            HeaderMap::new(),
+            axum::Form(token_request),
+        )
+        .await;
         let token_response = token_result.into_response();
         assert!(token_response.status().is_success());
     }
@@ -492,11 +963,13 @@ mod tests {
             grant_type: "authorization_code".to_string(),
             code: Some("invalid-code".to_string()),
             redirect_uri: None,
-            _client_id: None,
+            client_id: None,
+            client_secret: None,
             code_verifier: None,
             refresh_token: None,
+            scope: None,
         };
-        let result = token_exchange(State(state), axum::Form(request)).await;
+        let result = token_exchange(State(state), HeaderMap::new(), axum::Form(request)).await;
         let response = result.into_response();
         assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
     }
@@ -508,11 +981,13 @@ mod tests {
             grant_type: "refresh_token".to_string(),
             code: None,
             redirect_uri: None,
-            _client_id: None,
+            client_id: None,
+            client_secret: None,
             code_verifier: None,
             refresh_token: Some("test-refresh-token".to_string()),
+            scope: None,
         };
-        let result = token_exchange(State(state), axum::Form(request)).await;
+        let result = token_exchange(State(state), HeaderMap::new(), axum::Form(request)).await;
         let response = result.into_response();
         assert!(response.status().is_success());
     }
@@ -521,15 +996,330 @@ mod tests {
     async fn test_unsupported_grant_type() {
         let state = test_state();
         let request = TokenRequest {
-            grant_type: "client_credentials".to_string(),
+            grant_type: "password".to_string(),
             code: None,
             redirect_uri: None,
-            _client_id: None,
+            client_id: None,
+            client_secret: None,
             code_verifier: None,
             refresh_token: None,
+            scope: None,
         };
-        let result = token_exchange(State(state), axum::Form(request)).await;
+        let result = token_exchange(State(state), HeaderMap::new(), axum::Form(request)).await;
         let response = result.into_response();
         assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
     }
+
+    #[tokio::test]
+    async fn test_register_confidential_client_issues_secret() {
+        let state = test_state();
+        let result = register_client(
+            State(state),
+            Json(register_request(Some("client_secret_post"))),
+        )
+        .await;
+        let json = response_json(result).await;
+
+        assert_eq!(json["token_endpoint_auth_method"], "client_secret_post");
+        assert!(
+            json["client_secret"]
+                .as_str()
+                .unwrap()
+                .starts_with("test-secret-")
+        );
+        assert!(
+            json["grant_types"]
+                .as_array()
+                .unwrap()
+                .contains(&serde_json::json!("client_credentials"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_register_unsupported_auth_method() {
+        let state = test_state();
+        let result = register_client(
+            State(state),
+            Json(register_request(Some("private_key_jwt"))),
+        )
+        .await;
+        assert_eq!(result.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response_json(result).await["error"],
+            "invalid_client_metadata"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_client_credentials_post() {
+        let state = test_state();
+        let reg = register_client(
+            State(state.clone()),
+            Json(register_request(Some("client_secret_post"))),
+        )
+        .await;
+        let reg = response_json(reg).await;
+        let request = client_credentials_request(
+            reg["client_id"].as_str().map(String::from),
+            reg["client_secret"].as_str().map(String::from),
+        );
+
+        let result = token_exchange(State(state), HeaderMap::new(), axum::Form(request)).await;
+        let response = result.into_response();
+        assert!(response.status().is_success());
+        let json = response_json(response).await;
+        assert!(
+            json["access_token"]
+                .as_str()
+                .unwrap()
+                .starts_with("test-access-")
+        );
+        assert!(json.get("refresh_token").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_client_credentials_basic() {
+        let state = test_state();
+        let reg = register_client(
+            State(state.clone()),
+            Json(register_request(Some("client_secret_basic"))),
+        )
+        .await;
+        let reg = response_json(reg).await;
+        let credentials = format!(
+            "{}:{}",
+            reg["client_id"].as_str().unwrap(),
+            reg["client_secret"].as_str().unwrap()
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "authorization",
+            format!("Basic {}", BASE64.encode(credentials))
+                .parse()
+                .unwrap(),
+        );
+
+        let result = token_exchange(
+            State(state),
+            headers,
+            axum::Form(client_credentials_request(None, None)),
+        )
+        .await;
+        assert!(result.into_response().status().is_success());
+    }
+
+    #[tokio::test]
+    async fn test_client_credentials_wrong_secret() {
+        let state = test_state();
+        let reg = register_client(
+            State(state.clone()),
+            Json(register_request(Some("client_secret_post"))),
+        )
+        .await;
+        let reg = response_json(reg).await;
+        let request = client_credentials_request(
+            reg["client_id"].as_str().map(String::from),
+            Some("wrong".to_string()),
+        );
+
+        let result = token_exchange(State(state), HeaderMap::new(), axum::Form(request)).await;
+        let response = result.into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response_json(response).await["error"], "invalid_client");
+    }
+
+    #[tokio::test]
+    async fn test_client_credentials_public_client_rejected() {
+        let state = test_state();
+        let reg = register_client(State(state.clone()), Json(register_request(None))).await;
+        let reg = response_json(reg).await;
+        let request = client_credentials_request(
+            reg["client_id"].as_str().map(String::from),
+            Some("anything".to_string()),
+        );
+
+        let result = token_exchange(State(state), HeaderMap::new(), axum::Form(request)).await;
+        assert_eq!(result.into_response().status(), StatusCode::UNAUTHORIZED);
+    }
+
+    // =============================================================================
+    // FAILURE INJECTION TESTS
+    // =============================================================================
+
+    fn authorize_params(state: Option<&str>) -> AuthorizeParams {
+        AuthorizeParams {
+            client_id: "test-client".to_string(),
+            redirect_uri: "http://localhost:8080/callback".to_string(),
+            response_type: "code".to_string(),
+            state: state.map(String::from),
+            code_challenge: None,
+            code_challenge_method: None,
+            scope: None,
+        }
+    }
+
+    fn location(response: &Response) -> String {
+        response.headers()["location"].to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_failure_names_round_trip() {
+        for failure in OAuthFailure::ALL {
+            assert_eq!(OAuthFailure::parse(failure.as_str()), Some(failure));
+        }
+        assert_eq!(
+            OAuthFailure::parse_list("token_500, EXPIRED_CODE,bogus"),
+            vec![OAuthFailure::TokenServerError, OAuthFailure::ExpiredCode]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inject_register_invalid_client() {
+        let state = test_state().with_failures([OAuthFailure::RegisterInvalidClient]);
+        let result = register_client(State(state.clone()), Json(register_request(None))).await;
+        assert_eq!(result.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(response_json(result).await["error"], "invalid_client");
+        assert!(state.clients.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_inject_expired_code() {
+        let state = test_state().with_failures([OAuthFailure::ExpiredCode]);
+        let response = authorize(State(state.clone()), Query(authorize_params(None)))
+            .await
+            .into_response();
+        let code = location(&response)
+            .split("code=")
+            .nth(1)
+            .unwrap()
+            .to_string();
+
+        let request = TokenRequest {
+            grant_type: "authorization_code".to_string(),
+            code: Some(code),
+            redirect_uri: None,
+            client_id: None,
+            client_secret: None,
+            code_verifier: None,
+            refresh_token: None,
+            scope: None,
+        };
+        let result = token_exchange(State(state), HeaderMap::new(), axum::Form(request))
+            .await
+            .into_response();
+        assert_eq!(result.status(), StatusCode::BAD_REQUEST);
+        let json = response_json(result).await;
+        assert_eq!(json["error"], "invalid_grant");
+        assert!(
+            json["error_description"]
+                .as_str()
+                .unwrap()
+                .contains("expired")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inject_state_mismatch() {
+        let state = test_state().with_failures([OAuthFailure::StateMismatch]);
+        let response = authorize(State(state.clone()), Query(authorize_params(Some("abc"))))
+            .await
+            .into_response();
+        assert!(location(&response).contains("state=abc-mismatch"));
+
+        // A state is sent back even when the client did not send one
+        let response = authorize(State(state), Query(authorize_params(None)))
+            .await
+            .into_response();
+        assert!(location(&response).contains("state="));
+    }
+
+    #[tokio::test]
+    async fn test_inject_token_server_error() {
+        let state = test_state().with_failures([OAuthFailure::TokenServerError]);
+        let request = client_credentials_request(None, None);
+        let result = token_exchange(State(state), HeaderMap::new(), axum::Form(request))
+            .await
+            .into_response();
+        assert_eq!(result.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(response_json(result).await["error"], "server_error");
+    }
+
+    #[tokio::test]
+    async fn test_inject_malformed_metadata() {
+        let state = test_state().with_failures([OAuthFailure::MalformedMetadata]);
+        for response in [
+            protected_resource_metadata(State(state.clone()), HeaderMap::new()).await,
+            authorization_server_metadata(State(state.clone()), HeaderMap::new()).await,
+        ] {
+            assert!(response.status().is_success());
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert!(serde_json::from_slice::<serde_json::Value>(&bytes).is_err());
+        }
+    }
+
+    // =============================================================================
+    // OAUTH MODE TESTS
+    // =============================================================================
+
+    fn protected_app(state: OAuthState) -> Router {
+        Router::new().route("/mcp", get(|| async { "ok" })).layer(
+            axum::middleware::from_fn_with_state(state, bearer_auth_middleware),
+        )
+    }
+
+    async fn call_protected(state: OAuthState, token: Option<&str>) -> Response {
+        use tower::ServiceExt;
+
+        let mut request = axum::http::Request::builder()
+            .uri("/mcp")
+            .header("host", "mcp.test:3000");
+        if let Some(token) = token {
+            request = request.header("authorization", format!("Bearer {token}"));
+        }
+        protected_app(state)
+            .oneshot(request.body(axum::body::Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_missing_token_gets_resource_metadata_challenge() {
+        let response = call_protected(test_state(), None).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let challenge = response.headers()[WWW_AUTHENTICATE].to_str().unwrap();
+        assert!(challenge.starts_with("Bearer "));
+        assert!(challenge.contains(
+            r#"resource_metadata="http://mcp.test:3000/.well-known/oauth-protected-resource""#
+        ));
+        assert!(!challenge.contains("error="));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_token_gets_invalid_token_challenge() {
+        let response = call_protected(test_state(), Some("forged")).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let challenge = response.headers()[WWW_AUTHENTICATE].to_str().unwrap();
+        assert!(challenge.contains(r#"error="invalid_token""#));
+        assert!(challenge.contains("resource_metadata="));
+    }
+
+    #[tokio::test]
+    async fn test_issued_token_is_accepted() {
+        let state = test_state();
+        let token = state
+            .issue_access_token(
+                Some("c1".to_string()),
+                "mcp".to_string(),
+                "client_credentials",
+            )
+            .await;
+        let response = call_protected(state.clone(), Some(&token)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let info = state.token_info(&token).await.unwrap();
+        assert_eq!(info.client_id.as_deref(), Some("c1"));
+        assert_eq!(info.grant_type, "client_credentials");
+    }
 }
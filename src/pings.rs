@@ -0,0 +1,157 @@
+//! Server-initiated `ping` requests and how clients answer them.
+//!
+//! With a ping interval configured (`MCP_PING_INTERVAL`, stateful mode),
+//! every initialized session gets a task that sends the client a `ping`
+//! request at that interval, over the session's standalone `GET /mcp`
+//! stream. A ping counts as answered when the client POSTs a result for it
+//! before the next one is due, and as failed otherwise (error response, no
+//! answer in time, or the stream is gone).
+//!
+//! `GET /admin/pings` reports the counts per session, so tests can check
+//! that a client answers pings and keeps idle connections alive.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use rmcp::model::{PingRequest, ServerRequest};
+use rmcp::service::{PeerRequestOptions, ServiceError};
+use rmcp::{Peer, RoleServer};
+use serde::Serialize;
+
+/// Ping outcomes of one session.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PingStats {
+    /// Pings sent.
+    pub sent: u64,
+    /// Pings the client answered with a result in time.
+    pub answered: u64,
+    /// Pings answered with an error, not answered in time, or not delivered.
+    pub failed: u64,
+    /// Round trip of the latest answered ping, in milliseconds.
+    pub last_rtt_ms: Option<u64>,
+    /// RFC 3339 time of the latest answered ping.
+    pub last_answered_at: Option<String>,
+    /// Why the latest failed ping failed.
+    pub last_error: Option<String>,
+}
+
+/// Response of `GET /admin/pings`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PingReport {
+    /// The `MCP_PING_INTERVAL` in milliseconds, if pings are enabled.
+    pub interval_ms: Option<u128>,
+    /// Outcomes by session ID.
+    pub sessions: BTreeMap<String, PingStats>,
+}
+
+/// Pings of all sessions.
+#[derive(Debug, Default)]
+pub struct PingMonitor {
+    interval: Option<Duration>,
+    stats: Mutex<HashMap<String, PingStats>>,
+}
+
+impl PingMonitor {
+    /// Ping every session at `interval`, or never.
+    #[must_use]
+    pub fn new(interval: Option<Duration>) -> Self {
+        Self {
+            interval,
+            ..Self::default()
+        }
+    }
+
+    /// The ping outcomes of every session pinged so far.
+    #[must_use]
+    pub fn report(&self) -> PingReport {
+        let sessions = self
+            .stats
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(id, stats)| (id.clone(), stats.clone()))
+            .collect();
+        PingReport {
+            interval_ms: self.interval.map(|i| i.as_millis()),
+            sessions,
+        }
+    }
+
+    fn update(&self, session: &str, f: impl FnOnce(&mut PingStats)) {
+        let mut stats = self.stats.lock().unwrap_or_else(PoisonError::into_inner);
+        f(stats.entry(session.to_string()).or_default());
+    }
+
+    /// Ping `peer` at the interval until its transport closes.
+    ///
+    /// Returns at once if pings are disabled.
+    pub async fn run(&self, session: &str, peer: Peer<RoleServer>) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+        self.update(session, |_| {});
+        loop {
+            tokio::time::sleep(interval).await;
+            if peer.is_transport_closed() {
+                break;
+            }
+            self.update(session, |s| s.sent += 1);
+            let started = Instant::now();
+            let options = PeerRequestOptions {
+                timeout: Some(interval),
+                meta: None,
+            };
+            let outcome = match peer
+                .send_request_with_option(
+                    ServerRequest::PingRequest(PingRequest::default()),
+                    options,
+                )
+                .await
+            {
+                Ok(handle) => handle.await_response().await,
+                Err(e) => Err(e),
+            };
+            match outcome {
+                Ok(_) => {
+                    let rtt = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+                    self.update(session, |s| {
+                        s.answered += 1;
+                        s.last_rtt_ms = Some(rtt);
+                        s.last_answered_at = Some(Utc::now().to_rfc3339());
+                    });
+                }
+                Err(e) => {
+                    tracing::debug!(%session, error = %e, "Ping not answered");
+                    let closed = matches!(e, ServiceError::TransportClosed);
+                    self.update(session, |s| {
+                        s.failed += 1;
+                        s.last_error = Some(e.to_string());
+                    });
+                    if closed {
+                        break;
+                    }
+                }
+            }
+        }
+        tracing::debug!(%session, "Stopped pinging session");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_lists_sessions_sorted() {
+        let monitor = PingMonitor::new(Some(Duration::from_millis(250)));
+        monitor.update("b", |s| s.sent += 1);
+        monitor.update("a", |s| s.failed += 1);
+        let report = monitor.report();
+        assert_eq!(report.interval_ms, Some(250));
+        let ids: Vec<&str> = report.sessions.keys().map(String::as_str).collect();
+        assert_eq!(ids, ["a", "b"]);
+        assert_eq!(report.sessions["b"].sent, 1);
+    }
+}
@@ -0,0 +1,274 @@
+//! Prompt localization for `MCP_LOCALE`.
+//!
+//! Every built-in prompt gets a title in the configured [`Locale`], and away
+//! from English its description and argument descriptions are translated
+//! too. `greeting_localized` greets in the locale. Prompt names, argument
+//! names, and accepted values never change, so clients can check that they
+//! display the localized text but send the canonical identifiers.
+
+use rmcp::model::Prompt;
+
+use crate::i18n::Locale;
+
+/// Titles (English, German, Japanese, Arabic) and translated descriptions
+/// (German, Japanese, Arabic) of the built-in prompts.
+const PROMPTS: &[(&str, [&str; 4], [&str; 3])] = &[
+    (
+        "greeting",
+        ["Greeting", "Begrüßung", "挨拶", "تحية"],
+        [
+            "Eine einfache Begrüßung",
+            "シンプルな挨拶のプロンプト",
+            "موجّه تحية بسيط",
+        ],
+    ),
+    (
+        "code_review",
+        [
+            "Code Review",
+            "Code-Review",
+            "コードレビュー",
+            "مراجعة الشيفرة",
+        ],
+        [
+            "Mehrteiliger Prompt für ein Code-Review",
+            "コードレビュー用の複数メッセージのプロンプト",
+            "موجّه متعدد الرسائل لمراجعة الشيفرة",
+        ],
+    ),
+    (
+        "summarize",
+        ["Summarize", "Zusammenfassen", "要約", "تلخيص"],
+        [
+            "Prompt zum Zusammenfassen von Text",
+            "テキストを要約するプロンプト",
+            "موجّه لتلخيص النص",
+        ],
+    ),
+    (
+        "translate",
+        ["Translate", "Übersetzen", "翻訳", "ترجمة"],
+        [
+            "Text in eine andere Sprache übersetzen",
+            "テキストを別の言語に翻訳する",
+            "ترجمة النص إلى لغة أخرى",
+        ],
+    ),
+    (
+        "with_resource",
+        ["With Resource", "Mit Ressource", "リソース付き", "مع مورد"],
+        [
+            "Prompt, der test://static/data.json als Ressourcenblock einbettet",
+            "test://static/data.json をリソースブロックとして埋め込むプロンプト",
+            "موجّه يضمّن test://static/data.json ككتلة مورد",
+        ],
+    ),
+    (
+        "tone_rewrite",
+        [
+            "Tone Rewrite",
+            "Tonfall ändern",
+            "トーンの書き換え",
+            "إعادة الصياغة بنبرة",
+        ],
+        [
+            "Text in einem von mehreren festen Tonfällen umschreiben",
+            "決められたトーンのいずれかでテキストを書き換える",
+            "إعادة كتابة النص بإحدى النبرات المحددة",
+        ],
+    ),
+    (
+        "long_conversation",
+        [
+            "Long Conversation",
+            "Langes Gespräch",
+            "長い会話",
+            "محادثة طويلة",
+        ],
+        [
+            "Abwechselnde Benutzer- und Assistentennachrichten von je etwa 1 KB",
+            "それぞれ約1KBのユーザーとアシスタントの交互メッセージ",
+            "رسائل متناوبة بين المستخدم والمساعد بحجم 1 كيلوبايت تقريبًا لكل منها",
+        ],
+    ),
+    (
+        "flaky_prompt",
+        [
+            "Flaky Prompt",
+            "Unzuverlässiger Prompt",
+            "不安定なプロンプト",
+            "موجّه غير مستقر",
+        ],
+        [
+            "Schlägt bei prompts/get immer fehl, wie mode es verlangt",
+            "prompts/get は mode の指定どおりに必ず失敗する",
+            "يفشل prompts/get دائمًا بالطريقة التي يحددها mode",
+        ],
+    ),
+    (
+        "greeting_localized",
+        [
+            "Localized Greeting",
+            "Lokalisierte Begrüßung",
+            "ローカライズされた挨拶",
+            "تحية مترجمة",
+        ],
+        [
+            "Eine Begrüßung in der MCP_LOCALE des Servers",
+            "サーバーの MCP_LOCALE での挨拶",
+            "تحية بلغة MCP_LOCALE الخاصة بالخادم",
+        ],
+    ),
+];
+
+/// Translated argument descriptions (German, Japanese, Arabic), by prompt
+/// and argument name.
+const ARGUMENTS: &[(&str, &str, [&str; 3])] = &[
+    (
+        "greeting",
+        "name",
+        [
+            "Zu begrüßender Name",
+            "挨拶する相手の名前",
+            "الاسم المراد تحيته",
+        ],
+    ),
+    (
+        "greeting_localized",
+        "name",
+        [
+            "Zu begrüßender Name",
+            "挨拶する相手の名前",
+            "الاسم المراد تحيته",
+        ],
+    ),
+    (
+        "code_review",
+        "code",
+        [
+            "Zu prüfender Code",
+            "レビューするコード",
+            "الشيفرة المراد مراجعتها",
+        ],
+    ),
+    (
+        "code_review",
+        "language",
+        ["Programmiersprache", "プログラミング言語", "لغة البرمجة"],
+    ),
+    (
+        "summarize",
+        "text",
+        [
+            "Zusammenzufassender Text",
+            "要約するテキスト",
+            "النص المراد تلخيصه",
+        ],
+    ),
+    (
+        "translate",
+        "text",
+        [
+            "Zu übersetzender Text",
+            "翻訳するテキスト",
+            "النص المراد ترجمته",
+        ],
+    ),
+    (
+        "translate",
+        "language",
+        ["Zielsprache", "翻訳先の言語", "اللغة الهدف"],
+    ),
+    (
+        "tone_rewrite",
+        "text",
+        [
+            "Umzuschreibender Text",
+            "書き換えるテキスト",
+            "النص المراد إعادة كتابته",
+        ],
+    ),
+    (
+        "tone_rewrite",
+        "tone",
+        [
+            "Zieltonfall: casual, formal, friendly, playful oder professional",
+            "目標のトーン: casual、formal、friendly、playful、professional のいずれか",
+            "النبرة المطلوبة: إحدى casual أو formal أو friendly أو playful أو professional",
+        ],
+    ),
+    (
+        "flaky_prompt",
+        "mode",
+        [
+            "Fehlermodus: internal_error, invalid_params oder slow_failure (Standard internal_error)",
+            "失敗モード: internal_error、invalid_params、slow_failure のいずれか(既定値 internal_error)",
+            "وضع الفشل: internal_error أو invalid_params أو slow_failure (الافتراضي internal_error)",
+        ],
+    ),
+    (
+        "flaky_prompt",
+        "delay_ms",
+        [
+            "Millisekunden, die slow_failure wartet, bis 60000 (Standard 5000)",
+            "slow_failure が待つミリ秒数、60000 まで(既定値 5000)",
+            "عدد المللي ثانية التي ينتظرها slow_failure، حتى 60000 (الافتراضي 5000)",
+        ],
+    ),
+    (
+        "long_conversation",
+        "messages",
+        [
+            "Anzahl der Nachrichten, 1 bis 1000 (Standard 40)",
+            "メッセージ数、1〜1000(既定値 40)",
+            "عدد الرسائل، من 1 إلى 1000 (الافتراضي 40)",
+        ],
+    ),
+];
+
+/// Index of `locale` among the translations, or `None` for English.
+const fn translation_index(locale: Locale) -> Option<usize> {
+    match locale {
+        Locale::English => None,
+        Locale::German => Some(0),
+        Locale::Japanese => Some(1),
+        Locale::Arabic => Some(2),
+    }
+}
+
+/// Title `prompt` in `locale` and, away from English, translate its
+/// descriptions. Prompts without translations, such as builder-registered
+/// ones, are returned unchanged.
+#[must_use]
+pub fn localize_prompt(mut prompt: Prompt, locale: Locale) -> Prompt {
+    let Some((_, titles, descriptions)) = PROMPTS.iter().find(|(name, ..)| *name == prompt.name)
+    else {
+        return prompt;
+    };
+    prompt.title = Some(titles[translation_index(locale).map_or(0, |i| i + 1)].to_string());
+
+    let Some(index) = translation_index(locale) else {
+        return prompt;
+    };
+    prompt.description = Some(descriptions[index].to_string());
+    for argument in prompt.arguments.iter_mut().flatten() {
+        if let Some((.., translated)) = ARGUMENTS
+            .iter()
+            .find(|(p, a, _)| *p == prompt.name && *a == argument.name)
+        {
+            argument.description = Some(translated[index].to_string());
+        }
+    }
+    prompt
+}
+
+/// The `greeting_localized` message for `name` in `locale`.
+#[must_use]
+pub fn greeting(name: &str, locale: Locale) -> String {
+    match locale {
+        Locale::English => format!("Hello, {name}!"),
+        Locale::German => format!("Hallo, {name}!"),
+        Locale::Japanese => format!("こんにちは、{name}さん!"),
+        Locale::Arabic => format!("مرحبًا، {name}!"),
+    }
+}
@@ -6,15 +6,142 @@
 // ServerHandler trait requires Result return type even when implementation cannot fail
 #![allow(clippy::unnecessary_wraps)]
 
+pub mod localized;
+pub mod registered;
 pub mod templates;
 
+use crate::i18n::Locale;
 use crate::server::McpTestServer;
 use rmcp::{
     ErrorData as McpError,
-    model::{GetPromptRequestParams, GetPromptResult, ListPromptsResult, PromptMessage},
+    model::{GetPromptRequestParams, GetPromptResult, ListPromptsResult, Prompt, PromptMessage},
     service::{RequestContext, RoleServer},
 };
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Maximum number of `prompts/get` calls retained by the audit log.
+const MAX_AUDIT_ENTRIES: usize = 100;
+
+/// Record of how the arguments of a single `prompts/get` call were used.
+#[derive(Debug, Clone, Serialize)]
+pub struct PromptAuditEntry {
+    /// Name of the requested prompt.
+    pub prompt: String,
+    /// RFC3339 timestamp of the call.
+    pub timestamp: String,
+    /// Arguments that were declared by the prompt and substituted into it.
+    pub interpolated: Vec<String>,
+    /// Arguments the client sent that the prompt does not declare (silently dropped).
+    pub ignored: Vec<String>,
+    /// Declared arguments the client did not send.
+    pub missing: Vec<String>,
+    /// Whether the prompt was generated successfully.
+    pub success: bool,
+}
+
+/// Bounded, shared log of `prompts/get` argument usage.
+///
+/// Lets clients detect extraneous or misnamed arguments that would otherwise
+/// be dropped without any signal.
+#[derive(Debug, Clone, Default)]
+pub struct PromptAuditLog {
+    entries: Arc<Mutex<VecDeque<PromptAuditEntry>>>,
+}
+
+impl PromptAuditLog {
+    /// Create an empty audit log.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a `prompts/get` call, classifying each argument against the
+    /// arguments the prompt called `name` declares in `prompts`, the list
+    /// `prompts/list` returns.
+    pub fn record<S: std::hash::BuildHasher>(
+        &self,
+        prompts: &[Prompt],
+        name: &str,
+        arguments: &HashMap<String, String, S>,
+        success: bool,
+    ) -> PromptAuditEntry {
+        let declared: Vec<String> = prompts
+            .iter()
+            .find(|p| p.name == name)
+            .and_then(|p| p.arguments.as_ref())
+            .into_iter()
+            .flatten()
+            .map(|a| a.name.clone())
+            .collect();
+
+        let mut provided: Vec<&String> = arguments.keys().collect();
+        provided.sort();
+
+        let (interpolated, ignored): (Vec<String>, Vec<String>) = provided
+            .into_iter()
+            .cloned()
+            .partition(|arg| declared.contains(arg));
+        let missing = declared
+            .into_iter()
+            .filter(|arg| !arguments.contains_key(arg))
+            .collect();
+
+        let entry = PromptAuditEntry {
+            prompt: name.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            interpolated,
+            ignored,
+            missing,
+            success,
+        };
+
+        if !entry.ignored.is_empty() {
+            tracing::debug!(prompt = name, ignored = ?entry.ignored, "Prompt arguments ignored");
+        }
+
+        let mut entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if entries.len() >= MAX_AUDIT_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(entry.clone());
+        entry
+    }
+
+    /// Take all recorded calls, oldest first, leaving the log empty.
+    pub fn drain(&self) -> Vec<PromptAuditEntry> {
+        std::mem::take(
+            &mut *self
+                .entries
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+        )
+        .into()
+    }
+
+    /// Snapshot all recorded calls, oldest first.
+    #[must_use]
+    pub fn entries(&self) -> Vec<PromptAuditEntry> {
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Remove all recorded calls.
+    pub fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clear();
+    }
+}
 
 /// Convert JSON arguments map to a `HashMap<String, String>`.
 ///
@@ -44,32 +171,58 @@ pub fn convert_json_args(
 pub fn get_prompt_by_name<S: std::hash::BuildHasher>(
     name: &str,
     arguments: &HashMap<String, String, S>,
+) -> Result<(Vec<PromptMessage>, Option<String>), McpError> {
+    get_localized_prompt_by_name(name, arguments, Locale::English)
+}
+
+/// Get a prompt by name as [`get_prompt_by_name`] does, with its description
+/// and any localized output in `locale`.
+///
+/// # Errors
+///
+/// Returns an error if the prompt is not found or if required arguments are missing.
+pub fn get_localized_prompt_by_name<S: std::hash::BuildHasher>(
+    name: &str,
+    arguments: &HashMap<String, String, S>,
+    locale: Locale,
 ) -> Result<(Vec<PromptMessage>, Option<String>), McpError> {
     // Generate prompt messages
-    let messages = templates::generate_prompt(name, arguments)?;
+    let messages = templates::generate_localized_prompt(name, arguments, locale)?;
 
     // Find prompt metadata for description
     let prompt = templates::get_all_prompts()
         .into_iter()
-        .find(|p| p.name == name);
+        .find(|p| p.name == name)
+        .map(|p| localized::localize_prompt(p, locale));
 
     Ok((messages, prompt.and_then(|p| p.description)))
 }
 
 impl McpTestServer {
+    /// Every prompt `prompts/list` returns: the built-in prompts localized
+    /// for `MCP_LOCALE` (or the builder prompts replacing them), then the
+    /// prompts registered with `register_prompt`.
+    #[must_use]
+    pub fn listed_prompts(&self) -> Vec<Prompt> {
+        let locale = self.config().locale;
+        let builtin = templates::get_all_prompts()
+            .into_iter()
+            .map(|p| localized::localize_prompt(p, locale))
+            .collect();
+        let mut prompts = self.custom.merge_prompts(builtin);
+        prompts.extend(self.registered_prompts().list());
+        prompts
+    }
+
     /// List all available prompts.
     ///
-    /// Note: `&self` is required by the `ServerHandler` trait interface, even though
-    /// this method doesn't use instance state. Returns `Result` for MCP protocol consistency.
+    /// Returns `Result` for MCP protocol consistency.
     pub(crate) fn list_prompts_impl(
         &self,
         _context: RequestContext<RoleServer>,
     ) -> Result<ListPromptsResult, McpError> {
-        let _ = self; // Required by ServerHandler trait
-        let prompts = templates::get_all_prompts();
-
         Ok(ListPromptsResult {
-            prompts,
+            prompts: self.listed_prompts(),
             next_cursor: None,
             meta: None,
         })
@@ -77,16 +230,21 @@ impl McpTestServer {
 
     /// Get a specific prompt with substituted arguments.
     ///
-    /// Note: `&self` is required by the `ServerHandler` trait interface, even though
-    /// this method doesn't use instance state.
+    /// Every call is recorded in the prompt audit log, including failures.
     pub(crate) fn get_prompt_impl(
         &self,
         request: GetPromptRequestParams,
         _context: RequestContext<RoleServer>,
     ) -> Result<GetPromptResult, McpError> {
-        let _ = self; // Required by ServerHandler trait
         let arguments = convert_json_args(request.arguments);
-        let (messages, description) = get_prompt_by_name(&request.name, &arguments)?;
+        let result = get_localized_prompt_by_name(&request.name, &arguments, self.config().locale);
+        self.prompt_audit_log().record(
+            &self.listed_prompts(),
+            &request.name,
+            &arguments,
+            result.is_ok(),
+        );
+        let (messages, description) = result?;
 
         let mut result = GetPromptResult::new(messages);
         if let Some(desc) = description {
@@ -94,6 +252,67 @@ impl McpTestServer {
         }
         Ok(result)
     }
+
+    /// Get a prompt registered with the `register_prompt` tool, or `None` if
+    /// there is none called `request.name`.
+    ///
+    /// Every call is recorded in the prompt audit log, including failures.
+    pub(crate) fn get_registered_prompt(
+        &self,
+        request: &GetPromptRequestParams,
+    ) -> Option<Result<GetPromptResult, McpError>> {
+        let arguments = convert_json_args(request.arguments.clone());
+        let (prompt, result) = self.registered_prompts().get(&request.name, &arguments)?;
+        self.prompt_audit_log().record(
+            &self.listed_prompts(),
+            &request.name,
+            &arguments,
+            result.is_ok(),
+        );
+        Some(result.map(|messages| {
+            let result = GetPromptResult::new(messages);
+            match prompt.description {
+                Some(desc) => result.with_description(desc),
+                None => result,
+            }
+        }))
+    }
+
+    /// Get a prompt registered with the builder, after checking that its
+    /// required arguments are present.
+    ///
+    /// Every call is recorded in the prompt audit log, including failures.
+    pub(crate) async fn get_custom_prompt(
+        &self,
+        request: GetPromptRequestParams,
+    ) -> Result<GetPromptResult, McpError> {
+        let Some((prompt, handler)) = self.custom.prompt(&request.name) else {
+            return Err(McpError::invalid_params(
+                format!("Unknown prompt: {}", request.name),
+                None,
+            ));
+        };
+        let arguments = convert_json_args(request.arguments);
+        let missing = prompt
+            .arguments
+            .iter()
+            .flatten()
+            .find(|a| a.required == Some(true) && !arguments.contains_key(&a.name));
+        let result = match missing {
+            Some(argument) => Err(McpError::invalid_params(
+                format!("Missing required argument: {}", argument.name),
+                None,
+            )),
+            None => handler(arguments.clone()).await,
+        };
+        self.prompt_audit_log().record(
+            &self.listed_prompts(),
+            &request.name,
+            &arguments,
+            result.is_ok(),
+        );
+        Ok(GetPromptResult::new(result?))
+    }
 }
 
 #[cfg(test)]
@@ -141,6 +360,60 @@ mod tests {
         assert!(description.is_some());
     }
 
+    #[test]
+    fn test_audit_classifies_arguments() {
+        let log = PromptAuditLog::new();
+        let mut args = HashMap::new();
+        args.insert("code".to_string(), "fn main() {}".to_string());
+        args.insert("lang".to_string(), "rust".to_string());
+
+        let entry = log.record(&templates::get_all_prompts(), "code_review", &args, false);
+        assert_eq!(entry.interpolated, vec!["code"]);
+        assert_eq!(entry.ignored, vec!["lang"]);
+        assert_eq!(entry.missing, vec!["language"]);
+        assert!(!entry.success);
+        assert_eq!(log.entries().len(), 1);
+    }
+
+    #[test]
+    fn test_audit_unknown_prompt_ignores_everything() {
+        let log = PromptAuditLog::new();
+        let mut args = HashMap::new();
+        args.insert("name".to_string(), "Alice".to_string());
+
+        let entry = log.record(&templates::get_all_prompts(), "nonexistent", &args, false);
+        assert!(entry.interpolated.is_empty());
+        assert_eq!(entry.ignored, vec!["name"]);
+    }
+
+    #[test]
+    fn test_audit_log_is_bounded_and_clearable() {
+        let log = PromptAuditLog::new();
+        let args = HashMap::new();
+        for _ in 0..(MAX_AUDIT_ENTRIES + 5) {
+            log.record(&templates::get_all_prompts(), "with_resource", &args, true);
+        }
+        assert_eq!(log.entries().len(), MAX_AUDIT_ENTRIES);
+
+        log.clear();
+        assert!(log.entries().is_empty());
+    }
+
+    #[test]
+    fn test_audit_drain_empties_log() {
+        let log = PromptAuditLog::new();
+        log.record(
+            &templates::get_all_prompts(),
+            "simple_prompt",
+            &HashMap::new(),
+            true,
+        );
+        let drained = log.drain();
+        assert_eq!(drained.len(), 1);
+        assert_eq!(drained[0].prompt, "simple_prompt");
+        assert!(log.entries().is_empty());
+    }
+
     #[test]
     fn test_get_prompt_by_name_unknown() {
         let args = HashMap::new();
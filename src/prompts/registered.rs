@@ -0,0 +1,182 @@
+//! Prompts registered at runtime with the `register_prompt` tool.
+//!
+//! Each one is a single user message rendered from a template, with
+//! `{argument}` placeholders replaced by the `prompts/get` arguments. They
+//! are shared by all sessions and listed after the built-in and
+//! builder-registered prompts, in registration order.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use rmcp::{
+    ErrorData as McpError,
+    model::{Prompt, PromptMessage, PromptMessageRole},
+};
+
+/// Most prompts that can be registered at runtime.
+pub const MAX_REGISTERED_PROMPTS: usize = 100;
+
+/// Runtime-registered prompts and their templates.
+#[derive(Debug, Clone, Default)]
+pub struct RegisteredPrompts {
+    prompts: Arc<Mutex<Vec<(Prompt, String)>>>,
+}
+
+impl RegisteredPrompts {
+    /// Create an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `prompt`, rendered from `template`, replacing any registered
+    /// prompt of the same name. Returns whether one was replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if [`MAX_REGISTERED_PROMPTS`] are already registered.
+    pub fn register(&self, prompt: Prompt, template: String) -> Result<bool, String> {
+        let mut prompts = self.prompts.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(entry) = prompts.iter_mut().find(|(p, _)| p.name == prompt.name) {
+            *entry = (prompt, template);
+            return Ok(true);
+        }
+        if prompts.len() >= MAX_REGISTERED_PROMPTS {
+            return Err(format!(
+                "At most {MAX_REGISTERED_PROMPTS} prompts can be registered"
+            ));
+        }
+        prompts.push((prompt, template));
+        drop(prompts);
+        Ok(false)
+    }
+
+    /// Whether a prompt called `name` is registered.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.prompts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .any(|(p, _)| p.name == name)
+    }
+
+    /// The registered prompts, in registration order.
+    #[must_use]
+    pub fn list(&self) -> Vec<Prompt> {
+        self.prompts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(p, _)| p.clone())
+            .collect()
+    }
+
+    /// Render the registered prompt `name` with `arguments`, along with its
+    /// declaration; `None` if no such prompt is registered.
+    ///
+    /// # Errors
+    ///
+    /// The inner result is an invalid params error if a required argument is
+    /// missing.
+    #[must_use]
+    pub fn get(
+        &self,
+        name: &str,
+        arguments: &HashMap<String, String>,
+    ) -> Option<(Prompt, Result<Vec<PromptMessage>, McpError>)> {
+        let (prompt, template) = self
+            .prompts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .find(|(p, _)| p.name == name)
+            .cloned()?;
+        let result = render(&prompt, &template, arguments);
+        Some((prompt, result))
+    }
+}
+
+/// Replace the `{argument}` placeholders of `template` for each declared
+/// argument; optional arguments that were not sent become empty.
+fn render(
+    prompt: &Prompt,
+    template: &str,
+    arguments: &HashMap<String, String>,
+) -> Result<Vec<PromptMessage>, McpError> {
+    let mut text = template.to_string();
+    for argument in prompt.arguments.iter().flatten() {
+        let value = match arguments.get(&argument.name) {
+            Some(value) => value.as_str(),
+            None if argument.required == Some(true) => {
+                return Err(McpError::invalid_params(
+                    format!("Missing required argument: {}", argument.name),
+                    None,
+                ));
+            }
+            None => "",
+        };
+        text = text.replace(&format!("{{{}}}", argument.name), value);
+    }
+    Ok(vec![PromptMessage::new_text(PromptMessageRole::User, text)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::{PromptArgument, PromptMessageContent};
+
+    fn prompt(name: &str) -> Prompt {
+        Prompt::new(
+            name,
+            Some("Registered"),
+            Some(vec![
+                PromptArgument::new("who").with_required(true),
+                PromptArgument::new("mood").with_required(false),
+            ]),
+        )
+    }
+
+    #[test]
+    fn test_register_renders_and_replaces() {
+        let registry = RegisteredPrompts::new();
+        assert_eq!(
+            registry.register(prompt("hi"), "Hi {who}, {mood}!".to_string()),
+            Ok(false)
+        );
+
+        let args = HashMap::from([("who".to_string(), "Ada".to_string())]);
+        let (_, result) = registry.get("hi", &args).unwrap();
+        let PromptMessageContent::Text { text } = &result.unwrap()[0].content else {
+            panic!("Expected text content");
+        };
+        assert_eq!(text, "Hi Ada, !");
+        assert!(registry.get("hi", &HashMap::new()).unwrap().1.is_err());
+
+        assert_eq!(
+            registry.register(prompt("hi"), "Bye {who}".to_string()),
+            Ok(true)
+        );
+        assert_eq!(registry.list().len(), 1);
+        assert!(registry.get("missing", &args).is_none());
+    }
+
+    #[test]
+    fn test_register_is_bounded() {
+        let registry = RegisteredPrompts::new();
+        for n in 0..MAX_REGISTERED_PROMPTS {
+            assert!(
+                registry
+                    .register(prompt(&format!("p{n}")), String::new())
+                    .is_ok()
+            );
+        }
+        assert!(
+            registry
+                .register(prompt("one_more"), String::new())
+                .is_err()
+        );
+        // Replacing an existing prompt is still allowed
+        assert_eq!(registry.register(prompt("p0"), String::new()), Ok(true));
+    }
+}
@@ -1,13 +1,126 @@
-//! Prompt templates: greeting, `code_review`, summarize, translate, `with_resource`.
+//! Prompt templates: greeting, `code_review`, summarize, translate, `with_resource`,
+//! `tone_rewrite`, `long_conversation`, `greeting_localized`, `flaky_prompt`.
 
 use rmcp::{
     ErrorData as McpError,
-    model::{Prompt, PromptArgument, PromptMessage, PromptMessageRole},
+    model::{Prompt, PromptArgument, PromptMessage, PromptMessageRole, ResourceContents},
 };
-use std::{collections::HashMap, hash::BuildHasher};
+use std::{collections::HashMap, hash::BuildHasher, time::Duration};
+
+use super::localized;
+use crate::i18n::Locale;
+use crate::resources::static_resources::get_data_json_content;
+
+/// The values the `tone` argument of `tone_rewrite` accepts, also offered by
+/// `completion/complete`.
+pub const TONES: &[&str] = &["casual", "formal", "friendly", "playful", "professional"];
+
+/// Messages `long_conversation` generates without a `messages` argument.
+pub const DEFAULT_CONVERSATION_MESSAGES: usize = 40;
+
+/// Most messages `long_conversation` generates.
+pub const MAX_CONVERSATION_MESSAGES: usize = 1000;
+
+/// Lines of filler text in each `long_conversation` message (about 1 KB).
+const CONVERSATION_LINES: usize = 12;
+
+/// The values the `mode` argument of `flaky_prompt` accepts.
+pub const FLAKY_PROMPT_MODES: &[&str] = &["internal_error", "invalid_params", "slow_failure"];
+
+/// How long `flaky_prompt` waits before failing in `slow_failure` mode
+/// without a `delay_ms` argument.
+pub const FLAKY_PROMPT_DELAY: Duration = Duration::from_secs(5);
+
+/// Longest `delay_ms` `flaky_prompt` accepts.
+pub const MAX_FLAKY_PROMPT_DELAY: Duration = Duration::from_secs(60);
+
+/// How a `prompts/get` of `flaky_prompt` fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlakyPromptFailure {
+    /// `-32603` internal error, right away (default).
+    InternalError,
+    /// `-32602` invalid params, right away.
+    InvalidParams,
+    /// `-32603` internal error after a delay, [`FLAKY_PROMPT_DELAY`] by default.
+    SlowFailure(Duration),
+}
+
+impl FlakyPromptFailure {
+    /// The failure the `mode` argument asks for.
+    ///
+    /// # Errors
+    ///
+    /// Returns an invalid params error if `mode` is not one of
+    /// [`FLAKY_PROMPT_MODES`], or `delay_ms` is not a number of milliseconds
+    /// up to [`MAX_FLAKY_PROMPT_DELAY`].
+    pub fn from_arguments<S: BuildHasher>(
+        args: &HashMap<String, String, S>,
+    ) -> Result<Self, McpError> {
+        match args.get("mode").map_or("internal_error", String::as_str) {
+            "internal_error" => Ok(Self::InternalError),
+            "invalid_params" => Ok(Self::InvalidParams),
+            "slow_failure" => args
+                .get("delay_ms")
+                .map_or(Ok(FLAKY_PROMPT_DELAY), |value| parse_delay(value))
+                .map(Self::SlowFailure),
+            mode => Err(McpError::invalid_params(
+                format!(
+                    "Invalid mode: {mode} (expected one of {})",
+                    FLAKY_PROMPT_MODES.join(", ")
+                ),
+                Some(
+                    serde_json::json!({ "argument": "mode", "value": mode, "allowed": FLAKY_PROMPT_MODES }),
+                ),
+            )),
+        }
+    }
+
+    /// How long to wait before failing.
+    #[must_use]
+    pub const fn delay(self) -> Duration {
+        match self {
+            Self::SlowFailure(delay) => delay,
+            Self::InternalError | Self::InvalidParams => Duration::ZERO,
+        }
+    }
+
+    /// The error `prompts/get` fails with, with `{"prompt", "mode"}` as data.
+    #[must_use]
+    pub fn error(self) -> McpError {
+        let (mode, message) = match self {
+            Self::InternalError => ("internal_error", "Prompt generation failed"),
+            Self::InvalidParams => ("invalid_params", "Prompt rejected its arguments"),
+            Self::SlowFailure(_) => ("slow_failure", "Prompt generation timed out"),
+        };
+        let data = Some(serde_json::json!({ "prompt": "flaky_prompt", "mode": mode }));
+        match self {
+            Self::InvalidParams => McpError::invalid_params(message, data),
+            Self::InternalError | Self::SlowFailure(_) => McpError::internal_error(message, data),
+        }
+    }
+}
+
+/// Parse the `delay_ms` argument of `flaky_prompt`.
+fn parse_delay(value: &str) -> Result<Duration, McpError> {
+    value
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_millis)
+        .filter(|delay| *delay <= MAX_FLAKY_PROMPT_DELAY)
+        .ok_or_else(|| {
+            McpError::invalid_params(
+                format!(
+                    "Invalid delay_ms: {value} (expected 0 to {})",
+                    MAX_FLAKY_PROMPT_DELAY.as_millis()
+                ),
+                None,
+            )
+        })
+}
 
 /// Get all available prompts with their metadata.
 #[must_use]
+#[allow(clippy::too_many_lines)] // one declaration per prompt
 pub fn get_all_prompts() -> Vec<Prompt> {
     vec![
         Prompt::new(
@@ -54,9 +167,61 @@ pub fn get_all_prompts() -> Vec<Prompt> {
         ),
         Prompt::new(
             "with_resource",
-            Some("Prompt that references an embedded resource"),
+            Some("Prompt that embeds test://static/data.json as a resource block"),
             Some(vec![]),
         ),
+        Prompt::new(
+            "tone_rewrite",
+            Some("Rewrite text in one of a fixed set of tones"),
+            Some(vec![
+                PromptArgument::new("text")
+                    .with_description("Text to rewrite")
+                    .with_required(true),
+                PromptArgument::new("tone")
+                    .with_description(format!("Target tone: one of {}", TONES.join(", ")))
+                    .with_required(true),
+            ]),
+        ),
+        Prompt::new(
+            "long_conversation",
+            Some("Alternating user/assistant messages of about 1 KB each"),
+            Some(vec![
+                PromptArgument::new("messages")
+                    .with_description(format!(
+                        "Number of messages, 1 to {MAX_CONVERSATION_MESSAGES} \
+                         (default {DEFAULT_CONVERSATION_MESSAGES})"
+                    ))
+                    .with_required(false),
+            ]),
+        ),
+        Prompt::new(
+            "flaky_prompt",
+            Some("Always fails prompts/get, in the way mode asks for"),
+            Some(vec![
+                PromptArgument::new("mode")
+                    .with_description(format!(
+                        "Failure mode: {} (default internal_error)",
+                        FLAKY_PROMPT_MODES.join(", ")
+                    ))
+                    .with_required(false),
+                PromptArgument::new("delay_ms")
+                    .with_description(format!(
+                        "Milliseconds slow_failure waits, up to {} (default {})",
+                        MAX_FLAKY_PROMPT_DELAY.as_millis(),
+                        FLAKY_PROMPT_DELAY.as_millis()
+                    ))
+                    .with_required(false),
+            ]),
+        ),
+        Prompt::new(
+            "greeting_localized",
+            Some("A greeting in the server's MCP_LOCALE"),
+            Some(vec![
+                PromptArgument::new("name")
+                    .with_description("Name to greet")
+                    .with_required(true),
+            ]),
+        ),
     ]
 }
 
@@ -67,9 +232,24 @@ pub fn get_all_prompts() -> Vec<Prompt> {
 /// Returns an error if:
 /// - The prompt name is unknown
 /// - Required arguments are missing
+/// - An argument has a value outside its allowed set
 pub fn generate_prompt<S: BuildHasher>(
     name: &str,
     arguments: &HashMap<String, String, S>,
+) -> Result<Vec<PromptMessage>, McpError> {
+    generate_localized_prompt(name, arguments, Locale::English)
+}
+
+/// Generate prompt messages as [`generate_prompt`] does, with
+/// `greeting_localized` greeting in `locale`.
+///
+/// # Errors
+///
+/// Returns the same errors as [`generate_prompt`].
+pub fn generate_localized_prompt<S: BuildHasher>(
+    name: &str,
+    arguments: &HashMap<String, String, S>,
+    locale: Locale,
 ) -> Result<Vec<PromptMessage>, McpError> {
     match name {
         "greeting" => generate_greeting(arguments),
@@ -77,6 +257,10 @@ pub fn generate_prompt<S: BuildHasher>(
         "summarize" => generate_summarize(arguments),
         "translate" => generate_translate(arguments),
         "with_resource" => Ok(generate_with_resource()),
+        "tone_rewrite" => generate_tone_rewrite(arguments),
+        "long_conversation" => generate_long_conversation(arguments),
+        "greeting_localized" => generate_greeting_localized(arguments, locale),
+        "flaky_prompt" => Err(FlakyPromptFailure::from_arguments(arguments)?.error()),
         _ => Err(McpError::invalid_params(
             format!("Unknown prompt: {name}"),
             None,
@@ -97,6 +281,20 @@ fn generate_greeting<S: BuildHasher>(
     )])
 }
 
+fn generate_greeting_localized<S: BuildHasher>(
+    args: &HashMap<String, String, S>,
+    locale: Locale,
+) -> Result<Vec<PromptMessage>, McpError> {
+    let name = args
+        .get("name")
+        .ok_or_else(|| McpError::invalid_params("Missing required argument: name", None))?;
+
+    Ok(vec![PromptMessage::new_text(
+        PromptMessageRole::User,
+        localized::greeting(name, locale),
+    )])
+}
+
 fn generate_code_review<S: BuildHasher>(
     args: &HashMap<String, String, S>,
 ) -> Result<Vec<PromptMessage>, McpError> {
@@ -148,15 +346,99 @@ fn generate_translate<S: BuildHasher>(
     )])
 }
 
+fn generate_tone_rewrite<S: BuildHasher>(
+    args: &HashMap<String, String, S>,
+) -> Result<Vec<PromptMessage>, McpError> {
+    let text = args
+        .get("text")
+        .ok_or_else(|| McpError::invalid_params("Missing required argument: text", None))?;
+    let tone = args
+        .get("tone")
+        .ok_or_else(|| McpError::invalid_params("Missing required argument: tone", None))?;
+    if !TONES.contains(&tone.as_str()) {
+        return Err(McpError::invalid_params(
+            format!(
+                "Invalid tone: {tone} (expected one of {})",
+                TONES.join(", ")
+            ),
+            Some(serde_json::json!({ "argument": "tone", "value": tone, "allowed": TONES })),
+        ));
+    }
+
+    Ok(vec![PromptMessage::new_text(
+        PromptMessageRole::User,
+        format!("Please rewrite the following text in a {tone} tone:\n\n{text}"),
+    )])
+}
+
+fn generate_long_conversation<S: BuildHasher>(
+    args: &HashMap<String, String, S>,
+) -> Result<Vec<PromptMessage>, McpError> {
+    use std::fmt::Write;
+
+    let count = match args.get("messages") {
+        None => DEFAULT_CONVERSATION_MESSAGES,
+        Some(value) => value
+            .parse::<usize>()
+            .ok()
+            .filter(|n| (1..=MAX_CONVERSATION_MESSAGES).contains(n))
+            .ok_or_else(|| {
+                McpError::invalid_params(
+                    format!(
+                        "Invalid messages: {value} (expected 1 to {MAX_CONVERSATION_MESSAGES})"
+                    ),
+                    None,
+                )
+            })?,
+    };
+
+    Ok((1..=count)
+        .map(|n| {
+            // Users speak on odd turns, so the conversation opens with a user message
+            let (role, speaker) = if n % 2 == 1 {
+                (PromptMessageRole::User, "user")
+            } else {
+                (PromptMessageRole::Assistant, "assistant")
+            };
+            let mut text = format!("Message {n} of {count} ({speaker}).\n");
+            for line in 1..=CONVERSATION_LINES {
+                let _ = writeln!(
+                    text,
+                    "Line {line:02} of message {n:04}: filler text for prompt size testing."
+                );
+            }
+            PromptMessage::new_text(role, text)
+        })
+        .collect())
+}
+
 fn generate_with_resource() -> Vec<PromptMessage> {
+    let ResourceContents::TextResourceContents {
+        uri,
+        mime_type,
+        text,
+        ..
+    } = get_data_json_content()
+    else {
+        unreachable!("test://static/data.json is a text resource");
+    };
     vec![
         PromptMessage::new_text(
             PromptMessageRole::User,
-            "Please analyze the resource at test://static/config",
+            "Please analyze the JSON data in the attached resource.",
+        ),
+        PromptMessage::new_resource(
+            PromptMessageRole::User,
+            uri,
+            mime_type,
+            Some(text),
+            None,
+            None,
+            None,
         ),
         PromptMessage::new_text(
             PromptMessageRole::Assistant,
-            "I'll analyze the configuration resource for you.",
+            "I'll analyze the data resource for you.",
         ),
     ]
 }
@@ -0,0 +1,212 @@
+//! Strict `MCP-Protocol-Version` header checks (`MCP_STRICT_PROTOCOL_VERSION`).
+//!
+//! After `initialize`, clients must send the negotiated revision in the
+//! `MCP-Protocol-Version` header of every request to `/mcp`. By default the
+//! server follows the spec's fallback for servers: a missing header means
+//! `2025-03-26`, and only revisions it does not know are refused. In strict
+//! mode every `POST`, `GET`, and `DELETE` other than `initialize` itself
+//! must carry the header, and its value must be the revision negotiated for
+//! the session; anything else gets `400 Bad Request` with a JSON-RPC
+//! `-32600` error whose `data` holds the `expected` and `received` values.
+//! Sessions that negotiated a revision older than `2025-06-18`, which
+//! introduced the header, may still leave it out.
+//!
+//! Without sessions nothing ties a request to its `initialize`, so any
+//! known revision up to the server's own is accepted. Negotiated revisions
+//! are kept in the [`SessionRegistry`] and dropped with the session, however
+//! it ends.
+
+use std::sync::Arc;
+
+use axum::body::{Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Json, Response};
+use rmcp::model::{ErrorCode, ProtocolVersion};
+use serde_json::Value;
+
+use crate::sessions::SessionRegistry;
+
+/// Name of the header carrying the negotiated revision.
+pub const HEADER: &str = "mcp-protocol-version";
+
+/// First revision that requires the header.
+const HEADER_SINCE: &str = "2025-06-18";
+
+/// Revisions negotiated per session.
+#[derive(Debug)]
+pub struct NegotiatedVersions {
+    /// Revision the server offers in `initialize`.
+    server: String,
+    /// Live sessions, holding the revision each negotiated.
+    sessions: Arc<SessionRegistry>,
+}
+
+impl NegotiatedVersions {
+    /// Track `sessions` of a server offering `server` in `initialize`.
+    #[must_use]
+    pub fn new(server: &ProtocolVersion, sessions: Arc<SessionRegistry>) -> Self {
+        Self {
+            server: server.as_str().to_string(),
+            sessions,
+        }
+    }
+
+    /// The revision negotiated when a client requests `requested`: the
+    /// client's if it is older than the server's, the server's otherwise.
+    #[must_use]
+    pub fn negotiate(&self, requested: &str) -> String {
+        if requested < self.server.as_str() {
+            requested.to_string()
+        } else {
+            self.server.clone()
+        }
+    }
+
+    /// Whether `version` may be sent without a known session: a known
+    /// revision no newer than the server's.
+    fn acceptable(&self, version: &str) -> bool {
+        version <= self.server.as_str()
+            && ProtocolVersion::KNOWN_VERSIONS
+                .iter()
+                .any(|known| known.as_str() == version)
+    }
+}
+
+/// Middleware rejecting `/mcp` requests without the negotiated
+/// `MCP-Protocol-Version` header.
+pub async fn strict_protocol_version_middleware(
+    State(versions): State<Arc<NegotiatedVersions>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    let (parts, body) = request.into_parts();
+    let bytes = if method == Method::POST {
+        match axum::body::to_bytes(body, usize::MAX).await {
+            Ok(bytes) => bytes,
+            Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        }
+    } else {
+        Bytes::new()
+    };
+    let session = parts
+        .headers
+        .get("mcp-session-id")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let message = serde_json::from_slice::<Value>(&bytes).ok();
+
+    if let Some(requested) = message.as_ref().and_then(initialize_version) {
+        let response = next
+            .run(Request::from_parts(parts, Body::from(bytes)))
+            .await;
+        if let Some(session) = response
+            .headers()
+            .get("mcp-session-id")
+            .and_then(|v| v.to_str().ok())
+        {
+            versions
+                .sessions
+                .set_protocol_version(session, versions.negotiate(&requested));
+        }
+        return response;
+    }
+
+    let received = parts
+        .headers
+        .get(HEADER)
+        .map(|v| String::from_utf8_lossy(v.as_bytes()).into_owned());
+    let expected = session
+        .as_deref()
+        .and_then(|s| versions.sessions.protocol_version(s));
+    let problem = match (&received, &expected) {
+        (None, Some(expected)) if expected.as_str() < HEADER_SINCE => None,
+        (None, _) => Some("missing MCP-Protocol-Version header".to_string()),
+        (Some(received), Some(expected)) if received != expected => Some(format!(
+            "unexpected MCP-Protocol-Version {received}, negotiated {expected}"
+        )),
+        (Some(received), None) if !versions.acceptable(received) => {
+            Some(format!("unsupported MCP-Protocol-Version {received}"))
+        }
+        _ => None,
+    };
+    if let Some(problem) = problem {
+        tracing::info!(%method, %problem, "Rejecting request");
+        let id = message
+            .as_ref()
+            .and_then(|m| m.get("id"))
+            .cloned()
+            .unwrap_or(Value::Null);
+        let error = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {
+                "code": ErrorCode::INVALID_REQUEST.0,
+                "message": format!("Bad Request: {problem}"),
+                "data": {
+                    "expected": expected.unwrap_or_else(|| versions.server.clone()),
+                    "received": received,
+                },
+            },
+        });
+        return (StatusCode::BAD_REQUEST, Json(error)).into_response();
+    }
+
+    next.run(Request::from_parts(parts, Body::from(bytes)))
+        .await
+}
+
+/// The `protocolVersion` requested by a body that is an `initialize`
+/// request.
+fn initialize_version(message: &Value) -> Option<String> {
+    if message.get("method")?.as_str()? != "initialize" {
+        return None;
+    }
+    Some(
+        message
+            .pointer("/params/protocolVersion")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_negotiate() {
+        let versions = NegotiatedVersions::new(&ProtocolVersion::V_2025_06_18, Arc::default());
+        assert_eq!(versions.negotiate("2025-03-26"), "2025-03-26");
+        assert_eq!(versions.negotiate("2025-06-18"), "2025-06-18");
+        assert_eq!(versions.negotiate("2099-01-01"), "2025-06-18");
+    }
+
+    #[test]
+    fn test_acceptable_without_session() {
+        let versions = NegotiatedVersions::new(&ProtocolVersion::V_2025_03_26, Arc::default());
+        assert!(versions.acceptable("2024-11-05"));
+        assert!(versions.acceptable("2025-03-26"));
+        assert!(!versions.acceptable("2025-06-18"));
+        assert!(!versions.acceptable("2025-01-01"));
+    }
+
+    #[test]
+    fn test_initialize_version() {
+        assert_eq!(
+            initialize_version(&json!({
+                "id": 0, "method": "initialize",
+                "params": { "protocolVersion": "2025-06-18" }
+            })),
+            Some("2025-06-18".to_string())
+        );
+        assert_eq!(
+            initialize_version(&json!({ "id": 1, "method": "tools/list" })),
+            None
+        );
+    }
+}
@@ -0,0 +1,288 @@
+//! Public URLs behind reverse proxies (`MCP_PUBLIC_URL`, `X-Forwarded-*`).
+//!
+//! The OAuth issuer, the metadata endpoints, and the `resource_metadata` of
+//! Bearer challenges are absolute URLs. Derived from the bind address, they
+//! would point at `http://0.0.0.0:3000` inside a container, which clients
+//! behind a proxy cannot reach. [`external_base_url`] derives them per
+//! request instead, first match wins:
+//!
+//! 1. `MCP_PUBLIC_URL`, e.g. `https://mcp.example.com/test`
+//! 2. the `proto` and `host` of the first `Forwarded` element (RFC 7239)
+//! 3. `X-Forwarded-Proto`, `X-Forwarded-Host`, and `X-Forwarded-Port`, with
+//!    `X-Forwarded-Prefix` appended as a path
+//! 4. the `Host` header
+//! 5. the scheme and address the server is bound to
+//!
+//! Forwarded headers are always trusted: this is a test server, and tests
+//! set them by hand as often as proxies do. Their values are checked,
+//! though, since they end up in JSON documents and in the quoted
+//! `resource_metadata` of `WWW-Authenticate`: a scheme other than `http` or
+//! `https`, or a host that is not a `host[:port]` authority, is replaced by
+//! the bound address, and a prefix that is not a plain URL path is dropped.
+
+use std::net::Ipv6Addr;
+
+use axum::http::HeaderMap;
+use axum::http::uri::Authority;
+
+/// Parse an absolute `http://` or `https://` base URL, without a trailing
+/// slash, query, or fragment.
+#[must_use]
+pub fn parse_public_url(value: &str) -> Option<String> {
+    let url = value.trim().trim_end_matches('/');
+    let (scheme, rest) = url.split_once("://")?;
+    let host = rest.split('/').next().unwrap_or_default();
+    let valid = matches!(scheme.to_ascii_lowercase().as_str(), "http" | "https")
+        && !host.is_empty()
+        && !url.contains(['?', '#', ' ']);
+    valid.then(|| url.to_string())
+}
+
+/// Origin (`scheme://host[:port]`) of an absolute URL, as browsers send it
+/// in `Origin`.
+#[must_use]
+pub fn origin(url: &str) -> &str {
+    let authority = url.find("://").map_or(0, |i| i + 3);
+    url[authority..]
+        .find('/')
+        .map_or(url, |path| &url[..authority + path])
+}
+
+/// Base URL the client used to reach this server, as seen through any
+/// proxies in front of it.
+///
+/// `fallback` is the base URL of the bound address; it supplies the scheme
+/// and host when no header does.
+#[must_use]
+pub fn external_base_url(headers: &HeaderMap, fallback: &str) -> String {
+    let forwarded = header(headers, "forwarded").map(forwarded_params);
+    let forwarded_param = |name: &str| {
+        forwarded
+            .as_ref()
+            .and_then(|params| {
+                params
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            })
+            .map(|(_, value)| value.clone())
+    };
+    let (fallback_scheme, fallback_host) = fallback.split_once("://").unwrap_or(("http", fallback));
+
+    let scheme = forwarded_param("proto")
+        .or_else(|| first(headers, "x-forwarded-proto"))
+        .map(|scheme| scheme.to_ascii_lowercase())
+        .filter(|scheme| matches!(scheme.as_str(), "http" | "https"))
+        .unwrap_or_else(|| fallback_scheme.to_string());
+    let forwarded_host = forwarded_param("host").or_else(|| {
+        first(headers, "x-forwarded-host").map(|host| match first(headers, "x-forwarded-port") {
+            Some(port) if !host.contains(':') && !is_default_port(&scheme, &port) => {
+                format!("{host}:{port}")
+            }
+            _ => host,
+        })
+    });
+    let host = forwarded_host
+        .or_else(|| header(headers, "host").map(str::to_string))
+        .filter(|host| is_authority(host))
+        .unwrap_or_else(|| fallback_host.to_string());
+    let prefix = first(headers, "x-forwarded-prefix")
+        .map(|prefix| prefix.trim_matches('/').to_string())
+        .filter(|prefix| !prefix.is_empty() && is_path(prefix))
+        .map(|prefix| format!("/{prefix}"))
+        .unwrap_or_default();
+    format!("{scheme}://{host}{prefix}")
+}
+
+/// Whether `value` is a `host[:port]` authority without userinfo: a DNS
+/// name, an IPv4 address, or a bracketed IPv6 address.
+fn is_authority(value: &str) -> bool {
+    let Ok(authority) = value.parse::<Authority>() else {
+        return false;
+    };
+    let host = authority.host();
+    let is_name = !host.is_empty()
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.'));
+    let is_ipv6 = host
+        .strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .is_some_and(|host| host.parse::<Ipv6Addr>().is_ok());
+    let is_port = value
+        .strip_prefix(host)
+        .is_some_and(|rest| rest.is_empty() || rest[1..].parse::<u16>().is_ok());
+    !value.contains('@') && (is_name || is_ipv6) && is_port
+}
+
+/// Whether `value` is a URL path of unreserved characters, `/`, and
+/// percent escapes.
+fn is_path(value: &str) -> bool {
+    value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~' | '/' | '%'))
+}
+
+/// Value of the header `name`, if it is valid ASCII.
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+/// First comma-separated value of the header `name`, i.e. the one added by
+/// the proxy closest to the client.
+fn first(headers: &HeaderMap, name: &str) -> Option<String> {
+    header(headers, name)
+        .and_then(|value| value.split(',').next())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// `key=value` pairs of the first element of a `Forwarded` header, with
+/// quotes removed.
+fn forwarded_params(value: &str) -> Vec<(String, String)> {
+    value
+        .split(',')
+        .next()
+        .unwrap_or_default()
+        .split(';')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| {
+            (
+                key.trim().to_string(),
+                value.trim().trim_matches('"').to_string(),
+            )
+        })
+        .filter(|(_, value)| !value.is_empty())
+        .collect()
+}
+
+/// Whether `port` is the default port of `scheme`, so it is left out of
+/// URLs.
+fn is_default_port(scheme: &str, port: &str) -> bool {
+    matches!((scheme, port), ("http", "80") | ("https", "443"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    const FALLBACK: &str = "http://0.0.0.0:3000";
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn test_parse_public_url() {
+        assert_eq!(
+            parse_public_url("https://mcp.example.com/"),
+            Some("https://mcp.example.com".to_string())
+        );
+        assert_eq!(
+            parse_public_url(" http://proxy:8080/mcp-test "),
+            Some("http://proxy:8080/mcp-test".to_string())
+        );
+        assert_eq!(parse_public_url("mcp.example.com"), None);
+        assert_eq!(parse_public_url("ftp://mcp.example.com"), None);
+        assert_eq!(parse_public_url("https://"), None);
+        assert_eq!(parse_public_url("https://a.example/?x=1"), None);
+    }
+
+    #[test]
+    fn test_origin() {
+        assert_eq!(
+            origin("https://mcp.example.com/test"),
+            "https://mcp.example.com"
+        );
+        assert_eq!(origin("http://proxy:8080"), "http://proxy:8080");
+    }
+
+    #[test]
+    fn test_falls_back_to_host_then_bind_address() {
+        assert_eq!(external_base_url(&HeaderMap::new(), FALLBACK), FALLBACK);
+        assert_eq!(
+            external_base_url(&headers(&[("host", "localhost:3000")]), FALLBACK),
+            "http://localhost:3000"
+        );
+    }
+
+    #[test]
+    fn test_x_forwarded_headers() {
+        let headers = headers(&[
+            ("host", "mcp:3000"),
+            ("x-forwarded-proto", "https, http"),
+            ("x-forwarded-host", "mcp.example.com, proxy.internal"),
+            ("x-forwarded-port", "8443"),
+            ("x-forwarded-prefix", "/test/"),
+        ]);
+        assert_eq!(
+            external_base_url(&headers, FALLBACK),
+            "https://mcp.example.com:8443/test"
+        );
+    }
+
+    #[test]
+    fn test_default_forwarded_port_is_omitted() {
+        let headers = headers(&[
+            ("x-forwarded-proto", "https"),
+            ("x-forwarded-host", "mcp.example.com"),
+            ("x-forwarded-port", "443"),
+        ]);
+        assert_eq!(
+            external_base_url(&headers, FALLBACK),
+            "https://mcp.example.com"
+        );
+    }
+
+    #[test]
+    fn test_malformed_values_fall_back_to_bind_address() {
+        let hostile = headers(&[
+            ("x-forwarded-proto", "javascript"),
+            ("x-forwarded-host", r#"evil.example.com", error="x"#),
+            ("x-forwarded-prefix", r#"/a"b"#),
+        ]);
+        assert_eq!(external_base_url(&hostile, FALLBACK), FALLBACK);
+        let forwarded = headers(&[("forwarded", "proto=https;host=user@evil.example.com")]);
+        assert_eq!(
+            external_base_url(&forwarded, FALLBACK),
+            "https://0.0.0.0:3000"
+        );
+        assert_eq!(
+            external_base_url(&headers(&[("host", "[::1]:3000")]), FALLBACK),
+            "http://[::1]:3000"
+        );
+    }
+
+    #[test]
+    fn test_is_authority() {
+        assert!(is_authority("mcp.example.com"));
+        assert!(is_authority("mcp:8443"));
+        assert!(is_authority("[2001:db8::1]:443"));
+        assert!(!is_authority(""));
+        assert!(!is_authority("a b"));
+        assert!(!is_authority("a,b"));
+        assert!(!is_authority("a\"b"));
+        assert!(!is_authority("mcp:port"));
+        assert!(!is_authority("[not-ipv6]"));
+    }
+
+    #[test]
+    fn test_forwarded_header_wins() {
+        let headers = headers(&[
+            (
+                "forwarded",
+                r#"for=192.0.2.60;proto=https;host="mcp.example.com", for=10.0.0.1"#,
+            ),
+            ("x-forwarded-host", "other.example.com"),
+        ]);
+        assert_eq!(
+            external_base_url(&headers, FALLBACK),
+            "https://mcp.example.com"
+        );
+    }
+}
@@ -0,0 +1,312 @@
+//! Record-and-replay of JSON-RPC exchanges on `POST /mcp`.
+//!
+//! With `MCP_RECORD` set, every `POST /mcp` appends one line of JSON to the
+//! file: the request message and the messages the server sent back (the
+//! response and any notifications on its stream). With `MCP_REPLAY` set to
+//! such a file, the server answers from the recording instead of running
+//! tools, so a client regression test always sees the same server behavior:
+//!
+//! - Requests match recorded ones by `method` and `params`, ignoring `id`
+//!   and `params._meta`. The response gets the new request's `id`.
+//! - A request recorded several times replays its responses in order, then
+//!   repeats the last one.
+//! - Notifications are accepted with `202`; unrecorded requests get a
+//!   JSON-RPC `-32603` error naming the method.
+//!
+//! Replay is stateless: no sessions are issued and `GET /mcp` is refused.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use axum::body::{Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::header::CONTENT_TYPE;
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Json, Response};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// One `POST /mcp`: what the client sent and what the server answered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Exchange {
+    /// The JSON-RPC message the client sent.
+    pub request: Value,
+    /// The JSON-RPC messages the server sent back, in order.
+    pub responses: Vec<Value>,
+}
+
+/// Appends exchanges to a recording file, one JSON object per line.
+#[derive(Debug)]
+pub struct Recorder {
+    file: Mutex<File>,
+}
+
+impl Recorder {
+    /// Create (or truncate) the recording file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created.
+    pub fn create(path: &Path) -> std::io::Result<Self> {
+        Ok(Self {
+            file: Mutex::new(File::create(path)?),
+        })
+    }
+
+    /// Append one exchange and flush it to disk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn record(&self, exchange: &Exchange) -> std::io::Result<()> {
+        let mut line = serde_json::to_string(exchange)?;
+        line.push('\n');
+        let mut file = self.file.lock().unwrap_or_else(PoisonError::into_inner);
+        file.write_all(line.as_bytes())?;
+        file.flush()
+    }
+}
+
+/// Recorded responses keyed by request method and parameters.
+#[derive(Debug, Default)]
+pub struct Replay {
+    /// Responses per request key, and how many of them were served.
+    recorded: Mutex<HashMap<String, (Vec<Exchange>, usize)>>,
+}
+
+impl Replay {
+    /// Load a recording written by [`Recorder`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or a line is not an
+    /// exchange; the error names the line.
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let mut exchanges = Vec::new();
+        for (index, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let exchange = serde_json::from_str(&line).map_err(|e| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{}:{}: {e}", path.display(), index + 1),
+                )
+            })?;
+            exchanges.push(exchange);
+        }
+        Ok(Self::from_exchanges(exchanges))
+    }
+
+    /// Replay the given exchanges.
+    #[must_use]
+    pub fn from_exchanges(exchanges: impl IntoIterator<Item = Exchange>) -> Self {
+        let mut recorded: HashMap<String, (Vec<Exchange>, usize)> = HashMap::new();
+        for exchange in exchanges {
+            recorded
+                .entry(request_key(&exchange.request))
+                .or_default()
+                .0
+                .push(exchange);
+        }
+        Self {
+            recorded: Mutex::new(recorded),
+        }
+    }
+
+    /// The recorded messages answering `request`, with the response `id`
+    /// rewritten to the request's, or `None` if it was never recorded.
+    pub fn respond(&self, request: &Value) -> Option<Vec<Value>> {
+        let mut recorded = self.recorded.lock().unwrap_or_else(PoisonError::into_inner);
+        let (exchanges, served) = recorded.get_mut(&request_key(request))?;
+        let exchange = exchanges.get(*served).or_else(|| exchanges.last())?.clone();
+        *served += 1;
+        drop(recorded);
+        let recorded_id = &exchange.request["id"];
+        Some(
+            exchange
+                .responses
+                .into_iter()
+                .map(|mut message| {
+                    if message.get("id") == Some(recorded_id) {
+                        message["id"] = request["id"].clone();
+                    }
+                    message
+                })
+                .collect(),
+        )
+    }
+}
+
+/// `method` and `params` without `_meta`, as a string key.
+fn request_key(request: &Value) -> String {
+    let mut params = request.get("params").cloned().unwrap_or(Value::Null);
+    if let Some(params) = params.as_object_mut() {
+        params.remove("_meta");
+    }
+    json!([request["method"], params]).to_string()
+}
+
+/// JSON-RPC messages in a response body, either plain JSON or SSE `data:` lines.
+fn response_messages(body: &[u8]) -> Vec<Value> {
+    if let Ok(message) = serde_json::from_slice(body) {
+        return vec![message];
+    }
+    String::from_utf8_lossy(body)
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .filter_map(|data| serde_json::from_str(data.trim()).ok())
+        .collect()
+}
+
+/// Axum middleware appending every `POST /mcp` to the [`Recorder`].
+pub async fn record_middleware(
+    State(recorder): State<Arc<Recorder>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+
+    let (parts, body) = request.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    let message = serde_json::from_slice::<Value>(&bytes).ok();
+    let response = next
+        .run(Request::from_parts(parts, Body::from(bytes)))
+        .await;
+    let Some(message) = message else {
+        return response;
+    };
+
+    let (parts, body) = response.into_parts();
+    let body = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::warn!(error = %e, "Response body failed; exchange not recorded");
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+    let exchange = Exchange {
+        request: message,
+        responses: response_messages(&body),
+    };
+    if let Err(e) = recorder.record(&exchange) {
+        tracing::warn!(error = %e, "Failed to record exchange");
+    }
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// `POST /mcp` handler answering from a [`Replay`].
+pub async fn replay_handler(State(replay): State<Arc<Replay>>, body: Bytes) -> Response {
+    let Ok(request) = serde_json::from_slice::<Value>(&body) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": { "code": -32700, "message": "Parse error" },
+            })),
+        )
+            .into_response();
+    };
+    let Some(id) = request.get("id").cloned() else {
+        return StatusCode::ACCEPTED.into_response();
+    };
+    let Some(messages) = replay.respond(&request) else {
+        let method = request["method"].as_str().unwrap_or_default();
+        tracing::warn!(method, "No recorded response");
+        return Json(json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {
+                "code": -32603,
+                "message": format!("No recorded response for {method}"),
+                "data": { "method": method, "params": request.get("params") },
+            },
+        }))
+        .into_response();
+    };
+    if let [message] = messages.as_slice() {
+        return Json(message.clone()).into_response();
+    }
+    let events = messages.iter().fold(String::new(), |mut events, message| {
+        let _ = write!(events, "event: message\ndata: {message}\n\n");
+        events
+    });
+    ([(CONTENT_TYPE, "text/event-stream")], events).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exchange(id: u64, method: &str, params: &Value, result: &Value) -> Exchange {
+        Exchange {
+            request: json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }),
+            responses: vec![json!({ "jsonrpc": "2.0", "id": id, "result": result })],
+        }
+    }
+
+    #[test]
+    fn test_matches_method_and_params_and_rewrites_id() {
+        let replay = Replay::from_exchanges([
+            exchange(1, "tools/call", &json!({ "name": "add" }), &json!(3)),
+            exchange(2, "tools/call", &json!({ "name": "echo" }), &json!("hi")),
+        ]);
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": "abc",
+            "method": "tools/call",
+            "params": { "name": "echo", "_meta": { "progressToken": 1 } }
+        });
+        let messages = replay.respond(&request).unwrap();
+        assert_eq!(
+            messages,
+            [json!({ "jsonrpc": "2.0", "id": "abc", "result": "hi" })]
+        );
+        assert!(
+            replay
+                .respond(&json!({ "id": 3, "method": "tools/call", "params": { "name": "x" } }))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_repeats_replay_in_order_then_last() {
+        let replay = Replay::from_exchanges([
+            exchange(1, "resources/read", &json!({ "uri": "c" }), &json!(1)),
+            exchange(2, "resources/read", &json!({ "uri": "c" }), &json!(2)),
+        ]);
+        let request = json!({ "id": 9, "method": "resources/read", "params": { "uri": "c" } });
+        let results: Vec<Value> = (0..3)
+            .map(|_| replay.respond(&request).unwrap()[0]["result"].clone())
+            .collect();
+        assert_eq!(results, [json!(1), json!(2), json!(2)]);
+    }
+
+    #[test]
+    fn test_response_messages_from_sse() {
+        let body = b"id: 0\nretry: 3000\ndata: \n\nevent: message\ndata: {\"id\":1}\n\n";
+        assert_eq!(response_messages(body), [json!({ "id": 1 })]);
+        assert_eq!(response_messages(b"{\"id\":2}"), [json!({ "id": 2 })]);
+    }
+
+    #[test]
+    fn test_load_names_bad_line() {
+        let path = std::env::temp_dir().join(format!("replay-bad-{}.jsonl", std::process::id()));
+        std::fs::write(&path, "{\"request\":{},\"responses\":[]}\nnot json\n").unwrap();
+        let error = Replay::load(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert!(error.to_string().contains(":2:"), "{error}");
+    }
+}
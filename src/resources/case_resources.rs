@@ -0,0 +1,53 @@
+//! Case-sensitivity fixtures: resources whose URIs differ only by case or
+//! trailing whitespace.
+//!
+//! URIs are compared byte-for-byte by default, so each of these is a distinct
+//! resource with distinct content. A client that lowercases or trims URIs
+//! before sending them will read the wrong one.
+
+use rmcp::model::{AnnotateAble, RawResource, Resource, ResourceContents};
+
+/// URIs of the case-sensitivity fixtures, paired with a short label.
+pub const CASE_FIXTURE_URIS: [(&str, &str); 4] = [
+    ("test://case/readme.txt", "lowercase"),
+    ("test://case/README.txt", "uppercase"),
+    ("test://case/ReadMe.txt", "mixed case"),
+    ("test://case/readme.txt ", "trailing space"),
+];
+
+/// Get the case-sensitivity fixture resources.
+#[must_use]
+pub fn list_case_resources() -> Vec<Resource> {
+    CASE_FIXTURE_URIS
+        .iter()
+        .map(|(uri, label)| {
+            RawResource {
+                uri: (*uri).to_string(),
+                name: format!("case-{}", label.replace(' ', "-")),
+                title: Some(format!("Case fixture ({label})")),
+                description: Some(format!(
+                    "Differs from its siblings only by case or whitespace ({label}): {uri:?}"
+                )),
+                mime_type: Some("text/plain".to_string()),
+                size: None,
+                icons: None,
+                meta: None,
+            }
+            .no_annotation()
+        })
+        .collect()
+}
+
+/// Read a case-sensitivity fixture by its exact URI.
+#[must_use]
+pub fn read_case_resource(uri: &str) -> Option<ResourceContents> {
+    CASE_FIXTURE_URIS
+        .iter()
+        .find(|(fixture, _)| *fixture == uri)
+        .map(|(fixture, label)| ResourceContents::TextResourceContents {
+            uri: (*fixture).to_string(),
+            mime_type: Some("text/plain".to_string()),
+            text: format!("Case fixture ({label}), exact URI: {fixture:?}"),
+            meta: None,
+        })
+}
@@ -1,23 +1,61 @@
-//! Dynamic resources: counter, timestamp, random.
+//! Dynamic resources: counter, timestamp, random, ticker, auth context, and
+//! the slow and flaky resources for testing read timeouts and failures.
 
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
 
-use chrono::Utc;
-use rmcp::model::{AnnotateAble, RawResource, Resource, ResourceContents};
+use chrono::{DateTime, Utc};
+use rmcp::{
+    Peer, RoleServer,
+    model::{
+        AnnotateAble, Meta, RawResource, Resource, ResourceContents, ResourceUpdatedNotification,
+        ResourceUpdatedNotificationParam, ServerNotification,
+    },
+};
+use tokio_util::sync::CancellationToken;
+
+/// URI of the counter resource.
+pub const COUNTER_URI: &str = "test://dynamic/counter";
+
+/// URI of the ticker resource.
+pub const TICKER_URI: &str = "test://dynamic/ticker";
+
+/// URI of the auth context resource.
+pub const AUTH_CONTEXT_URI: &str = "test://dynamic/auth-context";
+
+/// URI of the slow resource; `?delay_ms=` sets how long a read takes.
+pub const SLOW_URI: &str = "test://dynamic/slow";
+
+/// URI of the flaky resource; `?fail_rate=` sets how often a read fails.
+pub const FLAKY_URI: &str = "test://dynamic/flaky";
+
+/// Read delay of the slow resource without a `delay_ms`.
+pub const DEFAULT_SLOW_DELAY: Duration = Duration::from_secs(1);
+
+/// Longest `delay_ms` the slow resource accepts.
+pub const MAX_SLOW_DELAY: Duration = Duration::from_secs(60);
+
+/// Failure rate of the flaky resource without a `fail_rate`.
+pub const DEFAULT_FAIL_RATE: f64 = 0.5;
 
 /// Counter state for the counter resource.
 /// This is shared across all reads and increments on each access.
 #[derive(Debug)]
 pub struct CounterState {
     counter: AtomicU64,
+    /// Subscribed peers by subscriber.
+    watchers: Mutex<HashMap<String, Peer<RoleServer>>>,
 }
 
 impl CounterState {
     /// Create a new counter starting at 0.
     #[must_use]
-    pub const fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             counter: AtomicU64::new(0),
+            watchers: Mutex::new(HashMap::new()),
         }
     }
 
@@ -25,6 +63,61 @@ impl CounterState {
     pub fn increment(&self) -> u64 {
         self.counter.fetch_add(1, Ordering::SeqCst) + 1
     }
+
+    /// Send update notifications to `peer` until [`CounterState::unwatch`].
+    pub fn watch(&self, subscriber: &str, peer: Peer<RoleServer>) {
+        self.watchers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(subscriber.to_string(), peer);
+    }
+
+    /// Stop sending update notifications to a subscriber.
+    pub fn unwatch(&self, subscriber: &str) {
+        self.watchers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(subscriber);
+    }
+
+    /// Number of subscribers receiving update notifications.
+    #[must_use]
+    pub fn watchers(&self) -> usize {
+        self.watchers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Tell every subscriber the counter is now `value` (in `_meta.value`),
+    /// forgetting those that can't be reached.
+    pub async fn notify(&self, value: u64) {
+        let watchers: Vec<(String, Peer<RoleServer>)> = self
+            .watchers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(key, peer)| (key.clone(), peer.clone()))
+            .collect();
+        for (subscriber, peer) in watchers {
+            let mut notification = ResourceUpdatedNotification::new(
+                ResourceUpdatedNotificationParam::new(COUNTER_URI),
+            );
+            let mut meta = Meta::new();
+            meta.0
+                .insert("value".to_string(), serde_json::Value::from(value));
+            notification.extensions.insert(meta);
+            if let Err(e) = peer
+                .send_notification(ServerNotification::ResourceUpdatedNotification(
+                    notification,
+                ))
+                .await
+            {
+                tracing::debug!(%subscriber, error = %e, "Dropping unreachable counter subscriber");
+                self.unwatch(&subscriber);
+            }
+        }
+    }
 }
 
 impl Default for CounterState {
@@ -37,10 +130,13 @@ impl Default for CounterState {
 #[must_use]
 pub fn get_counter_resource() -> Resource {
     RawResource {
-        uri: "test://dynamic/counter".to_string(),
+        uri: COUNTER_URI.to_string(),
         name: "counter".to_string(),
         title: Some("Incrementing Counter".to_string()),
-        description: Some("A counter that increments on each read".to_string()),
+        description: Some(
+            "A counter that increments on each read; subscribers are notified of every change"
+                .to_string(),
+        ),
         mime_type: Some("text/plain".to_string()),
         size: None,
         icons: None,
@@ -53,7 +149,7 @@ pub fn get_counter_resource() -> Resource {
 #[must_use]
 pub fn get_counter_content(value: u64) -> ResourceContents {
     ResourceContents::TextResourceContents {
-        uri: "test://dynamic/counter".to_string(),
+        uri: COUNTER_URI.to_string(),
         mime_type: Some("text/plain".to_string()),
         text: format!("Counter value: {value}"),
         meta: None,
@@ -120,6 +216,297 @@ pub fn get_random_content() -> ResourceContents {
     }
 }
 
+/// A single scheduled tick of the ticker resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tick {
+    /// Sequence number, starting at 0 when the clock starts.
+    pub seq: u64,
+    /// Wall-clock time the tick was scheduled for.
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Tick {
+    /// Render the tick as a JSON object.
+    #[must_use]
+    pub fn to_json(&self, interval: Duration) -> serde_json::Map<String, serde_json::Value> {
+        let mut map = serde_json::Map::new();
+        map.insert("seq".to_string(), self.seq.into());
+        map.insert(
+            "timestamp".to_string(),
+            self.timestamp
+                .to_rfc3339_opts(chrono::SecondsFormat::Micros, true)
+                .into(),
+        );
+        map.insert(
+            "interval_ms".to_string(),
+            u64::try_from(interval.as_millis())
+                .unwrap_or(u64::MAX)
+                .into(),
+        );
+        map
+    }
+}
+
+/// Drift-free clock for the ticker resource.
+///
+/// Tick `n` is scheduled at exactly `start + n * interval`, so a late tick
+/// never delays the ones after it. Clients can compare the scheduled
+/// timestamp with their receive time to measure delivery latency, and look
+/// for gaps in `seq` to measure loss.
+#[derive(Debug)]
+pub struct TickerClock {
+    start: tokio::time::Instant,
+    start_wall: DateTime<Utc>,
+    interval: Duration,
+}
+
+impl TickerClock {
+    /// Start a clock ticking at the given interval (clamped to at least 1ms).
+    #[must_use]
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            start: tokio::time::Instant::now(),
+            start_wall: Utc::now(),
+            interval: interval.max(Duration::from_millis(1)),
+        }
+    }
+
+    /// The interval between ticks.
+    #[must_use]
+    pub const fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// The tick with the given sequence number.
+    #[must_use]
+    pub fn tick(&self, seq: u64) -> Tick {
+        Tick {
+            seq,
+            timestamp: self.start_wall + self.offset(seq),
+        }
+    }
+
+    /// The most recent tick that has been scheduled.
+    #[must_use]
+    pub fn current(&self) -> Tick {
+        let elapsed = self.start.elapsed().as_nanos();
+        let seq = u64::try_from(elapsed / self.interval.as_nanos()).unwrap_or(u64::MAX);
+        self.tick(seq)
+    }
+
+    /// The monotonic instant at which the given tick is due.
+    #[must_use]
+    pub fn deadline(&self, seq: u64) -> tokio::time::Instant {
+        self.start + self.offset(seq)
+    }
+
+    fn offset(&self, seq: u64) -> Duration {
+        let nanos = self.interval.as_nanos().saturating_mul(u128::from(seq));
+        Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+    }
+}
+
+/// Get the ticker dynamic resource.
+#[must_use]
+pub fn get_ticker_resource() -> Resource {
+    RawResource {
+        uri: TICKER_URI.to_string(),
+        name: "ticker".to_string(),
+        title: Some("Ticker".to_string()),
+        description: Some(
+            "Subscribable resource updated at a fixed interval with sequence numbers and \
+             timestamps, for measuring notification latency and loss"
+                .to_string(),
+        ),
+        mime_type: Some("application/json".to_string()),
+        size: None,
+        icons: None,
+        meta: None,
+    }
+    .no_annotation()
+}
+
+/// Get the ticker content for the current tick.
+#[must_use]
+pub fn get_ticker_content(clock: &TickerClock) -> ResourceContents {
+    let tick = clock.current().to_json(clock.interval());
+    ResourceContents::TextResourceContents {
+        uri: TICKER_URI.to_string(),
+        mime_type: Some("application/json".to_string()),
+        text: serde_json::Value::Object(tick).to_string(),
+        meta: None,
+    }
+}
+
+/// Send a `notifications/resources/updated` for every tick until cancelled.
+///
+/// Each notification carries the tick's `seq`, `timestamp`, and
+/// `interval_ms` in `_meta`. The loop stops when `ct` is cancelled or the
+/// peer can no longer be reached.
+pub async fn run_ticker(clock: &TickerClock, peer: Peer<RoleServer>, ct: CancellationToken) {
+    let mut seq = clock.current().seq + 1;
+    loop {
+        tokio::select! {
+            () = ct.cancelled() => break,
+            () = tokio::time::sleep_until(clock.deadline(seq)) => {}
+        }
+
+        let mut notification =
+            ResourceUpdatedNotification::new(ResourceUpdatedNotificationParam::new(TICKER_URI));
+        notification
+            .extensions
+            .insert(Meta(clock.tick(seq).to_json(clock.interval())));
+        if let Err(e) = peer
+            .send_notification(ServerNotification::ResourceUpdatedNotification(
+                notification,
+            ))
+            .await
+        {
+            tracing::debug!(seq, error = %e, "Ticker stopped: peer unreachable");
+            break;
+        }
+        seq += 1;
+    }
+}
+
+/// Get the auth context dynamic resource.
+#[must_use]
+pub fn get_auth_context_resource() -> Resource {
+    RawResource {
+        uri: AUTH_CONTEXT_URI.to_string(),
+        name: "auth-context".to_string(),
+        title: Some("Authentication Context".to_string()),
+        description: Some(
+            "The authenticated principal (API key id, OAuth client_id, scopes) behind the reading request"
+                .to_string(),
+        ),
+        mime_type: Some("application/json".to_string()),
+        size: None,
+        icons: None,
+        meta: None,
+    }
+    .no_annotation()
+}
+
+/// Get the auth context content for a description built by
+/// [`auth_context`](crate::auth::auth_context).
+#[must_use]
+pub fn get_auth_context_content(context: &serde_json::Value) -> ResourceContents {
+    ResourceContents::TextResourceContents {
+        uri: AUTH_CONTEXT_URI.to_string(),
+        mime_type: Some("application/json".to_string()),
+        text: serde_json::to_string_pretty(context).unwrap_or_default(),
+        meta: None,
+    }
+}
+
+/// How a read of the slow or flaky resource misbehaves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnreliableRead {
+    /// The read succeeds after this delay.
+    Slow(Duration),
+    /// The read fails with `-32002` at this rate.
+    Flaky(f64),
+}
+
+impl UnreliableRead {
+    /// The behavior a read of `uri` asks for, if it is the slow or flaky
+    /// resource, with or without a query.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if the query has an unknown parameter, or a
+    /// `delay_ms` or `fail_rate` that is not a number or out of range.
+    pub fn parse(uri: &str) -> Option<Result<Self, String>> {
+        let (base, query) = uri.split_once('?').unwrap_or((uri, ""));
+        let (mut read, param) = match base {
+            SLOW_URI => (Self::Slow(DEFAULT_SLOW_DELAY), "delay_ms"),
+            FLAKY_URI => (Self::Flaky(DEFAULT_FAIL_RATE), "fail_rate"),
+            _ => return None,
+        };
+        for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+            if key != param {
+                return Some(Err(format!(
+                    "Unknown parameter '{key}' for {base}; expected {param}"
+                )));
+            }
+            read = match read {
+                Self::Slow(_) => match value.parse::<u64>().map(Duration::from_millis) {
+                    Ok(delay) if delay <= MAX_SLOW_DELAY => Self::Slow(delay),
+                    _ => {
+                        return Some(Err(format!(
+                            "delay_ms must be a whole number of milliseconds up to {}, got '{value}'",
+                            MAX_SLOW_DELAY.as_millis()
+                        )));
+                    }
+                },
+                Self::Flaky(_) => match value.parse::<f64>() {
+                    Ok(rate) if (0.0..=1.0).contains(&rate) => Self::Flaky(rate),
+                    _ => {
+                        return Some(Err(format!(
+                            "fail_rate must be between 0 and 1, got '{value}'"
+                        )));
+                    }
+                },
+            };
+        }
+        Some(Ok(read))
+    }
+}
+
+/// Get the slow dynamic resource.
+#[must_use]
+pub fn get_slow_resource() -> Resource {
+    RawResource {
+        uri: SLOW_URI.to_string(),
+        name: "slow".to_string(),
+        title: Some("Slow Resource".to_string()),
+        description: Some(format!(
+            "Takes {} ms to read; append ?delay_ms=N (at most {}) to change that",
+            DEFAULT_SLOW_DELAY.as_millis(),
+            MAX_SLOW_DELAY.as_millis()
+        )),
+        mime_type: Some("text/plain".to_string()),
+        size: None,
+        icons: None,
+        meta: None,
+    }
+    .no_annotation()
+}
+
+/// Get the flaky dynamic resource.
+#[must_use]
+pub fn get_flaky_resource() -> Resource {
+    RawResource {
+        uri: FLAKY_URI.to_string(),
+        name: "flaky".to_string(),
+        title: Some("Flaky Resource".to_string()),
+        description: Some(format!(
+            "Reads fail with -32002 at rate {DEFAULT_FAIL_RATE}; append ?fail_rate=R (0 to 1) to change that"
+        )),
+        mime_type: Some("text/plain".to_string()),
+        size: None,
+        icons: None,
+        meta: None,
+    }
+    .no_annotation()
+}
+
+/// Get the content of a successful slow or flaky read of `uri`.
+#[must_use]
+pub fn get_unreliable_content(uri: &str, read: UnreliableRead) -> ResourceContents {
+    let text = match read {
+        UnreliableRead::Slow(delay) => format!("Read after {} ms", delay.as_millis()),
+        UnreliableRead::Flaky(rate) => format!("Read succeeded (fail_rate {rate})"),
+    };
+    ResourceContents::TextResourceContents {
+        uri: uri.to_string(),
+        mime_type: Some("text/plain".to_string()),
+        text,
+        meta: None,
+    }
+}
+
 /// Get all dynamic resources.
 #[must_use]
 pub fn list_dynamic_resources() -> Vec<Resource> {
@@ -127,5 +514,9 @@ pub fn list_dynamic_resources() -> Vec<Resource> {
         get_counter_resource(),
         get_timestamp_resource(),
         get_random_resource(),
+        get_ticker_resource(),
+        get_auth_context_resource(),
+        get_slow_resource(),
+        get_flaky_resource(),
     ]
 }
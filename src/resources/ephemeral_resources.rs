@@ -0,0 +1,141 @@
+//! The ephemeral resource: `test://dynamic/ephemeral`.
+//!
+//! It exists only for `ttl_secs` after the `ephemeral_create` tool creates
+//! it. Until then it is listed by `resources/list` and readable; afterwards
+//! it drops out of listings, the creating session receives
+//! `notifications/resources/list_changed`, and it is left as a tombstone:
+//! reads fail with `-32002` (resource not found) and the expiry time as
+//! data. Clients can test how they handle a URI that went stale.
+
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rmcp::{
+    ErrorData, Peer, RoleServer,
+    model::{AnnotateAble, RawResource, Resource, ResourceContents},
+};
+
+/// URI of the ephemeral resource.
+pub const EPHEMERAL_URI: &str = "test://dynamic/ephemeral";
+
+/// Lifetime of the ephemeral resource created without a `ttl_secs`.
+pub const DEFAULT_EPHEMERAL_TTL: Duration = Duration::from_secs(5);
+
+/// Longest lifetime `ephemeral_create` accepts.
+pub const MAX_EPHEMERAL_TTL: Duration = Duration::from_secs(300);
+
+#[derive(Debug)]
+struct Ephemeral {
+    text: String,
+    expires_at: DateTime<Utc>,
+    /// Bumped on every creation, so a replaced resource's expiry is ignored.
+    generation: u64,
+}
+
+/// The ephemeral resource, live or tombstoned.
+#[derive(Debug, Default)]
+pub struct EphemeralStore {
+    current: Mutex<Option<Ephemeral>>,
+}
+
+impl EphemeralStore {
+    /// Create a store in which the resource was never created.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create or replace the resource with `text` for `ttl`, returning its
+    /// generation and expiry time.
+    pub fn create(&self, text: String, ttl: Duration) -> (u64, DateTime<Utc>) {
+        let expires_at = Utc::now() + ttl;
+        let mut current = self.current.lock().unwrap_or_else(PoisonError::into_inner);
+        let generation = current.as_ref().map_or(1, |e| e.generation + 1);
+        *current = Some(Ephemeral {
+            text,
+            expires_at,
+            generation,
+        });
+        drop(current);
+        (generation, expires_at)
+    }
+
+    /// The resource as listed, while it is live.
+    #[must_use]
+    pub fn list(&self) -> Vec<Resource> {
+        let current = self.current.lock().unwrap_or_else(PoisonError::into_inner);
+        current
+            .as_ref()
+            .filter(|e| Utc::now() < e.expires_at)
+            .map(|e| {
+                RawResource {
+                    uri: EPHEMERAL_URI.to_string(),
+                    name: "ephemeral".to_string(),
+                    title: None,
+                    description: Some(format!(
+                        "Created by ephemeral_create; expires at {}",
+                        e.expires_at.to_rfc3339()
+                    )),
+                    mime_type: Some("text/plain".to_string()),
+                    size: u32::try_from(e.text.len()).ok(),
+                    icons: None,
+                    meta: None,
+                }
+                .no_annotation()
+            })
+            .into_iter()
+            .collect()
+    }
+
+    /// Read `uri` if it is the ephemeral resource and was ever created.
+    ///
+    /// # Errors
+    ///
+    /// The inner result is a `-32002` resource not found error with
+    /// `{"uri", "expired_at"}` as data once the resource has expired.
+    #[must_use]
+    pub fn read(&self, uri: &str) -> Option<Result<ResourceContents, ErrorData>> {
+        if uri != EPHEMERAL_URI {
+            return None;
+        }
+        let (text, expires_at) = self
+            .current
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .map(|e| (e.text.clone(), e.expires_at))?;
+        if Utc::now() >= expires_at {
+            return Some(Err(ErrorData::resource_not_found(
+                format!("Resource expired: {uri}"),
+                Some(serde_json::json!({ "uri": uri, "expired_at": expires_at.to_rfc3339() })),
+            )));
+        }
+        Some(Ok(ResourceContents::TextResourceContents {
+            uri: uri.to_string(),
+            mime_type: Some("text/plain".to_string()),
+            text,
+            meta: None,
+        }))
+    }
+
+    /// Wait for generation `generation` to expire after `ttl`, then tell
+    /// `peer` the resource list changed, unless the resource was replaced
+    /// in the meantime.
+    pub async fn expire(&self, generation: u64, ttl: Duration, peer: Peer<RoleServer>) {
+        tokio::time::sleep(ttl).await;
+        let current = self
+            .current
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .map(|e| e.generation);
+        if current != Some(generation) {
+            return;
+        }
+        tracing::debug!(uri = EPHEMERAL_URI, "Ephemeral resource expired");
+        if let Err(e) = peer.notify_resource_list_changed().await {
+            tracing::debug!(error = %e, "Resource list change not sent");
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! Meta resources: runtime flags.
+//!
+//! `test://meta/flags` reports the server's runtime toggles. They are changed
+//! with `PATCH /admin/flags` while the server runs, and every change sends
+//! `notifications/resources/updated` to subscribers (requires
+//! `MCP_STATEFUL=true`). A test can reconfigure the server mid-run and check
+//! that the client noticed.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError, RwLock};
+
+use rmcp::{
+    Peer, RoleServer,
+    model::{
+        AnnotateAble, JsonObject, Meta, RawResource, Resource, ResourceContents,
+        ResourceUpdatedNotification, ResourceUpdatedNotificationParam, ServerNotification,
+    },
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// URI of the runtime flags resource.
+pub const FLAGS_URI: &str = "test://meta/flags";
+
+/// Runtime toggles.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuntimeFlags {
+    /// Fault injection on or off (default: off).
+    pub chaos: bool,
+    /// Requests per second allowed, `null` for unlimited (default: unlimited).
+    pub rate_limit: Option<u32>,
+    /// Name of the active behavior profile (default: `default`).
+    pub profile: String,
+}
+
+impl Default for RuntimeFlags {
+    fn default() -> Self {
+        Self {
+            chaos: false,
+            rate_limit: None,
+            profile: "default".to_string(),
+        }
+    }
+}
+
+/// The flags with a revision number that increases on every change.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FlagsSnapshot {
+    /// Number of changes since startup.
+    pub revision: u64,
+    /// Current flag values.
+    #[serde(flatten)]
+    pub flags: RuntimeFlags,
+}
+
+/// Current flags and the peers subscribed to their changes.
+#[derive(Debug, Default)]
+pub struct FlagStore {
+    current: RwLock<FlagsSnapshot>,
+    /// Subscribed peers, keyed by subscriber.
+    watchers: Mutex<HashMap<String, Peer<RoleServer>>>,
+}
+
+impl FlagStore {
+    /// Create a store with default flags.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current flags.
+    #[must_use]
+    pub fn snapshot(&self) -> FlagsSnapshot {
+        self.current
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Apply a partial update: each key in `patch` replaces that flag.
+    ///
+    /// If the flags changed, the revision is bumped and subscribers are
+    /// notified. Returns the new snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if `patch` names an unknown flag or gives a value
+    /// of the wrong type; the flags are then left unchanged.
+    pub async fn update(&self, patch: JsonObject) -> Result<FlagsSnapshot, String> {
+        let snapshot = {
+            let mut current = self.current.write().unwrap_or_else(PoisonError::into_inner);
+            let Value::Object(mut merged) =
+                serde_json::to_value(&current.flags).map_err(|e| e.to_string())?
+            else {
+                return Err("flags are not an object".to_string());
+            };
+            merged.extend(patch);
+            let flags: RuntimeFlags =
+                serde_json::from_value(Value::Object(merged)).map_err(|e| e.to_string())?;
+            if flags == current.flags {
+                return Ok(current.clone());
+            }
+            current.flags = flags;
+            current.revision += 1;
+            current.clone()
+        };
+        tracing::info!(
+            revision = snapshot.revision,
+            flags = ?snapshot.flags,
+            "Runtime flags changed"
+        );
+        self.notify(snapshot.revision).await;
+        Ok(snapshot)
+    }
+
+    /// Send change notifications to `peer` until [`FlagStore::unwatch`].
+    pub fn watch(&self, subscriber: &str, peer: Peer<RoleServer>) {
+        self.watchers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(subscriber.to_string(), peer);
+    }
+
+    /// Stop sending change notifications to a subscriber.
+    pub fn unwatch(&self, subscriber: &str) {
+        self.watchers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(subscriber);
+    }
+
+    /// Number of subscribers receiving change notifications.
+    #[must_use]
+    pub fn watchers(&self) -> usize {
+        self.watchers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Notify every subscriber, forgetting those that can't be reached.
+    async fn notify(&self, revision: u64) {
+        let watchers: Vec<(String, Peer<RoleServer>)> = self
+            .watchers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(key, peer)| (key.clone(), peer.clone()))
+            .collect();
+        for (subscriber, peer) in watchers {
+            let mut notification =
+                ResourceUpdatedNotification::new(ResourceUpdatedNotificationParam::new(FLAGS_URI));
+            let mut meta = Meta::new();
+            meta.0.insert("revision".to_string(), Value::from(revision));
+            notification.extensions.insert(meta);
+            if let Err(e) = peer
+                .send_notification(ServerNotification::ResourceUpdatedNotification(
+                    notification,
+                ))
+                .await
+            {
+                tracing::debug!(%subscriber, error = %e, "Dropping unreachable flags subscriber");
+                self.unwatch(&subscriber);
+            }
+        }
+    }
+}
+
+/// Get the runtime flags resource.
+#[must_use]
+pub fn get_flags_resource() -> Resource {
+    RawResource {
+        uri: FLAGS_URI.to_string(),
+        name: "flags".to_string(),
+        title: Some("Runtime Flags".to_string()),
+        description: Some(
+            "Current runtime toggles (chaos, rate limit, profile); subscribers are notified \
+             when an admin changes them"
+                .to_string(),
+        ),
+        mime_type: Some("application/json".to_string()),
+        size: None,
+        icons: None,
+        meta: None,
+    }
+    .no_annotation()
+}
+
+/// Get the runtime flags content.
+#[must_use]
+pub fn get_flags_content(store: &FlagStore) -> ResourceContents {
+    ResourceContents::TextResourceContents {
+        uri: FLAGS_URI.to_string(),
+        mime_type: Some("application/json".to_string()),
+        text: serde_json::to_string_pretty(&store.snapshot()).unwrap_or_default(),
+        meta: None,
+    }
+}
+
+/// Get all meta resources.
+#[must_use]
+pub fn list_meta_resources() -> Vec<Resource> {
+    vec![get_flags_resource()]
+}
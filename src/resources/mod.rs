@@ -1,20 +1,81 @@
 //! Resource implementations for the MCP test server.
 
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
 
 use rmcp::{
-    ErrorData,
+    ErrorData, Peer, RoleServer,
     model::{
         AnnotateAble, ListResourceTemplatesResult, ListResourcesResult, PaginatedRequestParams,
-        RawResourceTemplate, ReadResourceRequestParams, ReadResourceResult, SubscribeRequestParams,
-        UnsubscribeRequestParams,
+        RawResourceTemplate, ReadResourceRequestParams, ReadResourceResult, Resource,
+        SubscribeRequestParams, UnsubscribeRequestParams,
     },
+    service::RequestContext,
 };
 
+pub mod case_resources;
 pub mod dynamic_resources;
+pub mod ephemeral_resources;
+pub mod meta_resources;
+pub mod scratch_resources;
+pub mod session_resources;
 pub mod static_resources;
+pub mod subscriptions;
+pub mod synthetic_resources;
+pub mod template_resources;
+pub mod ui_csp;
+pub mod ui_events;
 
-use dynamic_resources::CounterState;
+use dynamic_resources::{AUTH_CONTEXT_URI, COUNTER_URI, CounterState, TICKER_URI, TickerClock};
+use ephemeral_resources::EphemeralStore;
+use meta_resources::{FLAGS_URI, FlagStore};
+use scratch_resources::{SCRATCH_PREFIX, ScratchStore};
+use session_resources::{SESSION_COUNTER_URI, SESSION_INFO_URI, SessionStore};
+use static_resources::UiAssets;
+use subscriptions::SubscriptionTable;
+use tokio_util::sync::CancellationToken;
+use ui_csp::UiCsp;
+use ui_events::{UI_EVENTS_URI, UiEventLog};
+
+/// Resources per `resources/list` page.
+pub const RESOURCE_PAGE_SIZE: usize = 100;
+
+/// The page of `resources` starting at `cursor`, with a `nextCursor` if more
+/// follow.
+///
+/// Cursors are opaque to clients; they hold the offset of the page.
+///
+/// # Errors
+///
+/// Returns an invalid params error if `cursor` was not handed out by an
+/// earlier page.
+pub fn paginate_resources(
+    resources: Vec<Resource>,
+    cursor: Option<&str>,
+) -> Result<ListResourcesResult, ErrorData> {
+    let start = match cursor {
+        None => 0,
+        Some(cursor) => cursor
+            .parse::<usize>()
+            .ok()
+            .filter(|&start| start > 0 && start < resources.len())
+            .ok_or_else(|| {
+                ErrorData::invalid_params(format!("Invalid cursor: {cursor:?}"), None)
+            })?,
+    };
+    let end = (start + RESOURCE_PAGE_SIZE).min(resources.len());
+    let next_cursor = (end < resources.len()).then(|| end.to_string());
+    let mut result = ListResourcesResult::with_all_items(
+        resources
+            .into_iter()
+            .skip(start)
+            .take(end - start)
+            .collect(),
+    );
+    result.next_cursor = next_cursor;
+    Ok(result)
+}
 
 /// Resource handler implementation.
 ///
@@ -22,6 +83,27 @@ use dynamic_resources::CounterState;
 #[derive(Debug, Clone)]
 pub struct ResourceHandler {
     counter_state: Arc<CounterState>,
+    ticker: Arc<TickerClock>,
+    /// Running ticker notification loops, keyed by subscriber.
+    tickers: Arc<Mutex<HashMap<String, CancellationToken>>>,
+    /// Runtime flags behind `test://meta/flags`.
+    flags: Arc<FlagStore>,
+    /// Client-written resources under `test://scratch/`.
+    scratch: Arc<ScratchStore>,
+    /// State behind `test://session/*`, by session.
+    sessions: Arc<SessionStore>,
+    /// The `test://dynamic/ephemeral` resource.
+    ephemeral: Arc<EphemeralStore>,
+    /// Current `resources/subscribe` subscriptions, by session.
+    subscriptions: Arc<SubscriptionTable>,
+    /// Messages reported by MCP Apps, behind `test://dynamic/ui-events`.
+    ui_events: Arc<UiEventLog>,
+    /// Number of `test://synthetic/{n}` resources.
+    synthetic: usize,
+    /// Delivery of the `ui://` apps' libraries.
+    ui_assets: UiAssets,
+    /// CSP declared on the `ui://` apps.
+    ui_csp: UiCsp,
 }
 
 impl ResourceHandler {
@@ -30,9 +112,159 @@ impl ResourceHandler {
     pub fn new() -> Self {
         Self {
             counter_state: Arc::new(CounterState::new()),
+            ticker: Arc::new(TickerClock::new(Duration::from_secs(1))),
+            tickers: Arc::new(Mutex::new(HashMap::new())),
+            flags: Arc::new(FlagStore::new()),
+            scratch: Arc::new(ScratchStore::new()),
+            sessions: Arc::new(SessionStore::new()),
+            ephemeral: Arc::new(EphemeralStore::new()),
+            subscriptions: Arc::new(SubscriptionTable::new()),
+            ui_events: Arc::new(UiEventLog::new()),
+            synthetic: 0,
+            ui_assets: UiAssets::Cdn,
+            ui_csp: UiCsp::None,
         }
     }
 
+    /// Set the interval of the ticker resource, restarting its clock.
+    #[must_use]
+    pub fn with_ticker_interval(mut self, interval: Duration) -> Self {
+        self.ticker = Arc::new(TickerClock::new(interval));
+        self
+    }
+
+    /// Generate `count` synthetic resources, `test://synthetic/1` onwards
+    /// (at most [`MAX_SYNTHETIC_RESOURCES`](synthetic_resources::MAX_SYNTHETIC_RESOURCES)).
+    #[must_use]
+    pub fn with_synthetic_resources(mut self, count: usize) -> Self {
+        self.synthetic = count.min(synthetic_resources::MAX_SYNTHETIC_RESOURCES);
+        self
+    }
+
+    /// Serve the `ui://` apps with their libraries delivered as `assets`.
+    #[must_use]
+    pub const fn with_ui_assets(mut self, assets: UiAssets) -> Self {
+        self.ui_assets = assets;
+        self
+    }
+
+    /// How the `ui://` apps get their libraries.
+    #[must_use]
+    pub const fn ui_assets(&self) -> UiAssets {
+        self.ui_assets
+    }
+
+    /// Declare `csp` as `_meta.ui.csp` of the `ui://` apps.
+    #[must_use]
+    pub const fn with_ui_csp(mut self, csp: UiCsp) -> Self {
+        self.ui_csp = csp;
+        self
+    }
+
+    /// The CSP declared on the `ui://` apps.
+    #[must_use]
+    pub const fn ui_csp(&self) -> UiCsp {
+        self.ui_csp
+    }
+
+    /// The contents of the `ui://` app at `uri`, delivered and declared as
+    /// configured; `None` if there is no such app.
+    #[must_use]
+    pub fn ui_app(&self, uri: &str) -> Option<rmcp::model::ResourceContents> {
+        uri.starts_with("ui://")
+            .then(|| static_resources::read_static_resource(uri))
+            .flatten()
+            .map(|contents| self.ui_csp.apply(self.ui_assets.apply(contents)))
+    }
+
+    /// Start sending ticker update notifications to a subscriber.
+    ///
+    /// `subscriber` is the session key, so subscribers must have sessions of
+    /// their own; the server refuses ticker subscriptions over stateless
+    /// HTTP. Subscribing again with the same key while a loop is running is
+    /// a no-op.
+    pub fn start_ticker(&self, subscriber: &str, peer: Peer<RoleServer>) {
+        let mut tickers = self.tickers.lock().unwrap_or_else(PoisonError::into_inner);
+        tickers.retain(|_, ct| !ct.is_cancelled());
+        if tickers.contains_key(subscriber) {
+            return;
+        }
+        let ct = CancellationToken::new();
+        tickers.insert(subscriber.to_string(), ct.clone());
+        drop(tickers);
+
+        let clock = Arc::clone(&self.ticker);
+        tokio::spawn(async move {
+            dynamic_resources::run_ticker(&clock, peer, ct.clone()).await;
+            ct.cancel();
+        });
+    }
+
+    /// Stop sending ticker update notifications to a subscriber.
+    pub fn stop_ticker(&self, subscriber: &str) {
+        let removed = self
+            .tickers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(subscriber);
+        if let Some(ct) = removed {
+            ct.cancel();
+        }
+    }
+
+    /// Number of subscribers currently receiving ticker notifications.
+    #[must_use]
+    pub fn active_tickers(&self) -> usize {
+        self.tickers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .filter(|ct| !ct.is_cancelled())
+            .count()
+    }
+
+    /// The counter behind `test://dynamic/counter`.
+    #[must_use]
+    pub const fn counter(&self) -> &Arc<CounterState> {
+        &self.counter_state
+    }
+
+    /// The runtime flags behind `test://meta/flags`.
+    #[must_use]
+    pub const fn flags(&self) -> &Arc<FlagStore> {
+        &self.flags
+    }
+
+    /// The client-written resources under `test://scratch/`.
+    #[must_use]
+    pub const fn scratch(&self) -> &Arc<ScratchStore> {
+        &self.scratch
+    }
+
+    /// The per-session state behind `test://session/*`.
+    #[must_use]
+    pub const fn sessions(&self) -> &Arc<SessionStore> {
+        &self.sessions
+    }
+
+    /// The `test://dynamic/ephemeral` resource.
+    #[must_use]
+    pub const fn ephemeral(&self) -> &Arc<EphemeralStore> {
+        &self.ephemeral
+    }
+
+    /// The current resource subscriptions, by session.
+    #[must_use]
+    pub const fn subscriptions(&self) -> &Arc<SubscriptionTable> {
+        &self.subscriptions
+    }
+
+    /// The MCP App message log behind `test://dynamic/ui-events`.
+    #[must_use]
+    pub const fn ui_events(&self) -> &Arc<UiEventLog> {
+        &self.ui_events
+    }
+
     /// List all available resources.
     ///
     /// # Errors
@@ -46,10 +278,35 @@ impl ResourceHandler {
         let mut resources = Vec::new();
 
         // Add static resources
-        resources.extend(static_resources::list_static_resources());
+        resources.extend(
+            static_resources::list_static_resources()
+                .into_iter()
+                .map(|resource| self.ui_csp.annotate(resource)),
+        );
 
         // Add dynamic resources
         resources.extend(dynamic_resources::list_dynamic_resources());
+        resources.push(ui_events::get_ui_events_resource());
+
+        // Add the ephemeral resource while it is live
+        resources.extend(self.ephemeral.list());
+
+        // Add case-sensitivity fixtures
+        resources.extend(case_resources::list_case_resources());
+
+        // Add meta resources
+        resources.extend(meta_resources::list_meta_resources());
+
+        // Add per-session resources
+        resources.extend(session_resources::list_session_resources());
+
+        // Add scratch resources written so far
+        resources.extend(self.scratch.list());
+
+        // Add synthetic resources
+        resources.extend(synthetic_resources::list_synthetic_resources(
+            self.synthetic,
+        ));
 
         // Add template resource
         // Note: The template itself is not listed as a resource, only via list_resource_templates
@@ -73,7 +330,11 @@ impl ResourceHandler {
             .with_mime_type("text/plain")
             .no_annotation();
 
-        Ok(ListResourceTemplatesResult::with_all_items(vec![template]))
+        Ok(ListResourceTemplatesResult::with_all_items(vec![
+            template,
+            template_resources::repo_template(),
+            template_resources::many_template(),
+        ]))
     }
 
     /// Read a resource by URI.
@@ -84,17 +345,54 @@ impl ResourceHandler {
     pub fn read_resource(
         &self,
         request: &ReadResourceRequestParams,
+    ) -> Result<ReadResourceResult, ErrorData> {
+        self.read(request, None)
+    }
+
+    /// Read a resource for the request in `context`, which decides the
+    /// session of `test://session/*` and the principal of
+    /// `test://dynamic/auth-context`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the resource URI is unknown or invalid.
+    pub fn read_resource_with_context(
+        &self,
+        request: &ReadResourceRequestParams,
+        context: &RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, ErrorData> {
+        self.read(request, context.extensions.get())
+    }
+
+    /// Read a resource for the HTTP request `parts`; `None` outside HTTP.
+    fn read(
+        &self,
+        request: &ReadResourceRequestParams,
+        parts: Option<&axum::http::request::Parts>,
     ) -> Result<ReadResourceResult, ErrorData> {
         let uri = &request.uri;
 
         // Try static resources first
+        if uri == static_resources::MULTI_URI {
+            return Ok(ReadResourceResult::new(
+                static_resources::get_multi_contents(),
+            ));
+        }
+        if let Some(content) = self.ui_app(uri) {
+            return Ok(ReadResourceResult::new(vec![content]));
+        }
         if let Some(content) = static_resources::read_static_resource(uri) {
             return Ok(ReadResourceResult::new(vec![content]));
         }
 
+        // Try case-sensitivity fixtures (exact match only)
+        if let Some(content) = case_resources::read_case_resource(uri) {
+            return Ok(ReadResourceResult::new(vec![content]));
+        }
+
         // Try dynamic resources
         match uri.as_str() {
-            "test://dynamic/counter" => {
+            COUNTER_URI => {
                 let value = self.counter_state.increment();
                 let content = dynamic_resources::get_counter_content(value);
                 return Ok(ReadResourceResult::new(vec![content]));
@@ -107,9 +405,51 @@ impl ResourceHandler {
                 let content = dynamic_resources::get_random_content();
                 return Ok(ReadResourceResult::new(vec![content]));
             }
+            TICKER_URI => {
+                let content = dynamic_resources::get_ticker_content(&self.ticker);
+                return Ok(ReadResourceResult::new(vec![content]));
+            }
+            FLAGS_URI => {
+                let content = meta_resources::get_flags_content(&self.flags);
+                return Ok(ReadResourceResult::new(vec![content]));
+            }
+            UI_EVENTS_URI => {
+                return Ok(ReadResourceResult::new(vec![self.ui_events.content()]));
+            }
+            AUTH_CONTEXT_URI => {
+                // Without an HTTP request there is no principal
+                let content =
+                    dynamic_resources::get_auth_context_content(&crate::auth::auth_context(parts));
+                return Ok(ReadResourceResult::new(vec![content]));
+            }
+            SESSION_INFO_URI => {
+                let session = crate::sessions::session_key(parts);
+                let content = self.sessions.info_content(session);
+                return Ok(ReadResourceResult::new(vec![content]));
+            }
+            SESSION_COUNTER_URI => {
+                let session = crate::sessions::session_key(parts);
+                let content = self.sessions.counter_content(session);
+                return Ok(ReadResourceResult::new(vec![content]));
+            }
             _ => {}
         }
 
+        // Try scratch resources
+        if let Some(content) = self.scratch.read(uri) {
+            return Ok(ReadResourceResult::new(vec![content]));
+        }
+
+        // Try synthetic resources
+        if let Some(content) = synthetic_resources::read_synthetic_resource(uri, self.synthetic) {
+            return Ok(ReadResourceResult::new(vec![content]));
+        }
+
+        // Try the ephemeral resource, which may be expired
+        if let Some(read) = self.ephemeral.read(uri) {
+            return read.map(|content| ReadResourceResult::new(vec![content]));
+        }
+
         // Try template resource: test://files/{path}
         if let Some(path) = uri.strip_prefix("test://files/") {
             let content = rmcp::model::ResourceContents::TextResourceContents {
@@ -121,6 +461,16 @@ impl ResourceHandler {
             return Ok(ReadResourceResult::new(vec![content]));
         }
 
+        // Try template resource: test://repos/{owner}/{repo}
+        if let Some(content) = template_resources::read_repo_resource(uri) {
+            return Ok(ReadResourceResult::new(vec![content]));
+        }
+
+        // Try template resource: test://many/{item}
+        if let Some(content) = template_resources::read_many_resource(uri) {
+            return Ok(ReadResourceResult::new(vec![content]));
+        }
+
         // Unknown resource
         Err(ErrorData::invalid_request(
             format!("Unknown resource URI: {uri}"),
@@ -128,37 +478,65 @@ impl ResourceHandler {
         ))
     }
 
-    /// Subscribe to resource updates.
+    /// Subscribe `session` to resource updates.
     ///
     /// # Errors
     ///
-    /// Returns an error if the resource does not support subscriptions.
-    pub fn subscribe(&self, request: &SubscribeRequestParams) -> Result<(), ErrorData> {
-        // For now, we accept subscriptions to the random resource
-        // In a real implementation, we would track subscriptions and send notifications
+    /// Returns an error if the resource does not support subscriptions, or an
+    /// invalid request error with `{"uri"}` as data if `session` is already
+    /// subscribed to it.
+    pub fn subscribe(
+        &self,
+        request: &SubscribeRequestParams,
+        session: &str,
+    ) -> Result<(), ErrorData> {
+        // The random resource accepts subscriptions but never notifies; the
+        // ticker's notification loop is started by the server via `start_ticker`,
+        // and counter, flag, and scratch subscribers are registered with `watch`
         let uri = &request.uri;
 
         match uri.as_str() {
-            "test://dynamic/random" => {
-                // Subscription accepted
-                Ok(())
+            "test://dynamic/random" | COUNTER_URI | TICKER_URI | FLAGS_URI => {}
+            // Scratch resources may be subscribed to before they are written
+            scratch if scratch.starts_with(SCRATCH_PREFIX) => {
+                scratch_resources::validate_name(&scratch[SCRATCH_PREFIX.len()..])
+                    .map_err(|e| ErrorData::invalid_params(e, None))?;
+            }
+            _ => {
+                return Err(ErrorData::invalid_request(
+                    format!("Resource does not support subscriptions: {uri}"),
+                    None,
+                ));
             }
-            _ => Err(ErrorData::invalid_request(
-                format!("Resource does not support subscriptions: {uri}"),
-                None,
-            )),
+        }
+
+        if self.subscriptions.add(session, uri) {
+            Ok(())
+        } else {
+            Err(ErrorData::invalid_request(
+                format!("Already subscribed to {uri}"),
+                Some(serde_json::json!({ "uri": uri })),
+            ))
         }
     }
 
-    /// Unsubscribe from resource updates.
+    /// Unsubscribe `session` from resource updates.
+    ///
+    /// Unsubscribing from a URI the session is not subscribed to is accepted,
+    /// so clients may unsubscribe defensively.
     ///
     /// # Errors
     ///
     /// This function currently does not return errors, but returns `Result`
     /// for API consistency with the MCP protocol.
-    pub const fn unsubscribe(&self, _request: &UnsubscribeRequestParams) -> Result<(), ErrorData> {
-        // For now, we accept unsubscribe for any URI
-        // In a real implementation, we would remove the subscription
+    pub fn unsubscribe(
+        &self,
+        request: &UnsubscribeRequestParams,
+        session: &str,
+    ) -> Result<(), ErrorData> {
+        if !self.subscriptions.remove(session, &request.uri) {
+            tracing::debug!(uri = %request.uri, session, "Unsubscribe without subscription");
+        }
         Ok(())
     }
 }
@@ -0,0 +1,259 @@
+//! Scratch resources: `test://scratch/{name}`.
+//!
+//! Unlike every other resource these are written by clients, through the
+//! `resource_write` and `resource_delete` tools. They are shared by the whole
+//! server, listed by `resources/list` while they exist, and gone when it
+//! stops. Subscribers of a scratch URI receive `notifications/resources/updated`
+//! whenever it is written or deleted (requires `MCP_STATEFUL=true`), so a
+//! client can test the full create, read, update, delete loop.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, PoisonError};
+
+use rmcp::{
+    Peer, RoleServer,
+    model::{
+        AnnotateAble, RawResource, Resource, ResourceContents, ResourceUpdatedNotificationParam,
+    },
+};
+
+/// URI prefix of the scratch namespace.
+pub const SCRATCH_PREFIX: &str = "test://scratch/";
+
+/// Scratch resources the server holds at once.
+pub const MAX_SCRATCH_RESOURCES: usize = 100;
+
+/// Longest accepted resource name, in bytes.
+const MAX_NAME_LEN: usize = 128;
+
+/// MIME type of resources written without one.
+pub const DEFAULT_MIME_TYPE: &str = "text/plain";
+
+/// A written scratch resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScratchEntry {
+    /// Text content.
+    pub text: String,
+    /// MIME type reported on list and read.
+    pub mime_type: String,
+}
+
+/// Scratch resources and the peers subscribed to them.
+#[derive(Debug, Default)]
+pub struct ScratchStore {
+    entries: Mutex<BTreeMap<String, ScratchEntry>>,
+    /// Subscribed peers by URI, then by subscriber.
+    watchers: Mutex<HashMap<String, HashMap<String, Peer<RoleServer>>>>,
+}
+
+/// The URI of the scratch resource `name`.
+#[must_use]
+pub fn scratch_uri(name: &str) -> String {
+    format!("{SCRATCH_PREFIX}{name}")
+}
+
+/// Check that `name` can be used in a scratch URI.
+///
+/// # Errors
+///
+/// Returns a message if `name` is empty, longer than 128 bytes, or contains
+/// characters other than ASCII letters, digits, `-`, `_`, and `.`.
+pub fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Resource name must not be empty".to_string());
+    }
+    if name.len() > MAX_NAME_LEN {
+        return Err(format!("Resource name is longer than {MAX_NAME_LEN} bytes"));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(format!(
+            "Invalid resource name {name:?}: use letters, digits, '-', '_', and '.'"
+        ));
+    }
+    Ok(())
+}
+
+impl ScratchStore {
+    /// Create an empty store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The resource `name`, if it was written.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<ScratchEntry> {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+            .cloned()
+    }
+
+    /// Create or replace the resource `name`, returning the entry it replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if `name` is invalid (see [`validate_name`]), or is
+    /// new and the store already holds [`MAX_SCRATCH_RESOURCES`] resources.
+    #[allow(clippy::significant_drop_tightening)]
+    pub fn write(&self, name: &str, entry: ScratchEntry) -> Result<Option<ScratchEntry>, String> {
+        validate_name(name)?;
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        if entries.len() >= MAX_SCRATCH_RESOURCES && !entries.contains_key(name) {
+            return Err(format!(
+                "Server holds the maximum of {MAX_SCRATCH_RESOURCES} scratch resources"
+            ));
+        }
+        Ok(entries.insert(name.to_string(), entry))
+    }
+
+    /// Remove the resource `name`, returning it if it existed.
+    pub fn delete(&self, name: &str) -> Option<ScratchEntry> {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(name)
+    }
+
+    /// The current scratch resources as listed, sorted by name.
+    #[must_use]
+    pub fn list(&self) -> Vec<Resource> {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(name, entry)| {
+                RawResource {
+                    uri: scratch_uri(name),
+                    name: name.clone(),
+                    title: None,
+                    description: Some("Scratch resource written by a client".to_string()),
+                    mime_type: Some(entry.mime_type.clone()),
+                    size: u32::try_from(entry.text.len()).ok(),
+                    icons: None,
+                    meta: None,
+                }
+                .no_annotation()
+            })
+            .collect()
+    }
+
+    /// Contents of the scratch resource at `uri`, if it exists.
+    #[must_use]
+    pub fn read(&self, uri: &str) -> Option<ResourceContents> {
+        let entry = self.get(uri.strip_prefix(SCRATCH_PREFIX)?)?;
+        Some(ResourceContents::TextResourceContents {
+            uri: uri.to_string(),
+            mime_type: Some(entry.mime_type),
+            text: entry.text,
+            meta: None,
+        })
+    }
+
+    /// Send update notifications for `uri` to `peer` until
+    /// [`ScratchStore::unwatch`].
+    pub fn watch(&self, uri: &str, subscriber: &str, peer: Peer<RoleServer>) {
+        self.watchers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(uri.to_string())
+            .or_default()
+            .insert(subscriber.to_string(), peer);
+    }
+
+    /// Stop sending update notifications for `uri` to a subscriber.
+    pub fn unwatch(&self, uri: &str, subscriber: &str) {
+        let mut watchers = self.watchers.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(subscribers) = watchers.get_mut(uri) {
+            subscribers.remove(subscriber);
+            if subscribers.is_empty() {
+                watchers.remove(uri);
+            }
+        }
+    }
+
+    /// Notify every subscriber of `uri`, forgetting those that can't be
+    /// reached.
+    pub async fn notify_updated(&self, uri: &str) {
+        let subscribers: Vec<(String, Peer<RoleServer>)> = self
+            .watchers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(uri)
+            .map(|subscribers| {
+                subscribers
+                    .iter()
+                    .map(|(key, peer)| (key.clone(), peer.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        for (subscriber, peer) in subscribers {
+            if let Err(e) = peer
+                .notify_resource_updated(ResourceUpdatedNotificationParam::new(uri))
+                .await
+            {
+                tracing::debug!(%subscriber, %uri, error = %e, "Dropping unreachable scratch subscriber");
+                self.unwatch(uri, &subscriber);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(text: &str) -> ScratchEntry {
+        ScratchEntry {
+            text: text.to_string(),
+            mime_type: DEFAULT_MIME_TYPE.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_write_read_delete() {
+        let store = ScratchStore::new();
+        assert_eq!(store.write("notes.txt", entry("v1")), Ok(None));
+        assert_eq!(store.write("notes.txt", entry("v2")), Ok(Some(entry("v1"))));
+
+        let listed = store.list();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].uri, "test://scratch/notes.txt");
+        assert_eq!(listed[0].size, Some(2));
+
+        let Some(ResourceContents::TextResourceContents { text, .. }) =
+            store.read("test://scratch/notes.txt")
+        else {
+            panic!("scratch resource not readable");
+        };
+        assert_eq!(text, "v2");
+
+        assert_eq!(store.delete("notes.txt"), Some(entry("v2")));
+        assert!(store.read("test://scratch/notes.txt").is_none());
+        assert!(store.list().is_empty());
+    }
+
+    #[test]
+    fn test_names_are_validated() {
+        let store = ScratchStore::new();
+        for name in ["", "a/b", "has space", &"x".repeat(MAX_NAME_LEN + 1)] {
+            assert!(store.write(name, entry("")).is_err(), "{name:?}");
+        }
+        assert!(store.write("ok-name_1.json", entry("")).is_ok());
+    }
+
+    #[test]
+    fn test_resource_limit() {
+        let store = ScratchStore::new();
+        for i in 0..MAX_SCRATCH_RESOURCES {
+            store.write(&i.to_string(), entry("")).unwrap();
+        }
+        assert!(store.write("one-more", entry("")).is_err());
+        // Replacing an existing resource is still allowed
+        assert!(store.write("0", entry("x")).is_ok());
+    }
+}
@@ -0,0 +1,127 @@
+//! Per-session resources: `test://session/info` and `test://session/counter`.
+//!
+//! Both URIs are the same for every client, but what a read returns depends
+//! on the session sending it (the `Mcp-Session-Id` header), so two sessions
+//! reading the counter see independent counts. Requests without a session
+//! (stateless mode) all share the session
+//! [`DEFAULT_SESSION`](crate::sessions::DEFAULT_SESSION).
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+use chrono::{DateTime, Utc};
+use rmcp::model::{AnnotateAble, RawResource, Resource, ResourceContents};
+
+/// URI of the resource describing the reading session.
+pub const SESSION_INFO_URI: &str = "test://session/info";
+
+/// URI of the counter scoped to the reading session.
+pub const SESSION_COUNTER_URI: &str = "test://session/counter";
+
+#[derive(Debug)]
+struct SessionState {
+    created_at: DateTime<Utc>,
+    counter: u64,
+}
+
+/// State behind the per-session resources, by session ID.
+#[derive(Debug, Default)]
+pub struct SessionStore {
+    sessions: Mutex<HashMap<String, SessionState>>,
+}
+
+impl SessionStore {
+    /// Create an empty store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `session` was created now, unless it is already known.
+    ///
+    /// The server calls this when a session initializes; a session first
+    /// seen by a read is recorded then.
+    pub fn open(&self, session: &str) {
+        self.with_session(session, |_| {});
+    }
+
+    fn with_session<T>(&self, session: &str, f: impl FnOnce(&mut SessionState) -> T) -> T {
+        let mut sessions = self.sessions.lock().unwrap_or_else(PoisonError::into_inner);
+        f(sessions
+            .entry(session.to_string())
+            .or_insert_with(|| SessionState {
+                created_at: Utc::now(),
+                counter: 0,
+            }))
+    }
+
+    /// Get the info content for `session`: its ID, creation time, and
+    /// current counter value.
+    #[must_use]
+    pub fn info_content(&self, session: &str) -> ResourceContents {
+        let info = self.with_session(session, |state| {
+            serde_json::json!({
+                "session_id": session,
+                "created_at": state.created_at.to_rfc3339(),
+                "counter": state.counter,
+            })
+        });
+        ResourceContents::TextResourceContents {
+            uri: SESSION_INFO_URI.to_string(),
+            mime_type: Some("application/json".to_string()),
+            text: serde_json::to_string_pretty(&info).unwrap_or_default(),
+            meta: None,
+        }
+    }
+
+    /// Increment the counter of `session` and get its new value.
+    #[must_use]
+    pub fn counter_content(&self, session: &str) -> ResourceContents {
+        let value = self.with_session(session, |state| {
+            state.counter += 1;
+            state.counter
+        });
+        ResourceContents::TextResourceContents {
+            uri: SESSION_COUNTER_URI.to_string(),
+            mime_type: Some("text/plain".to_string()),
+            text: value.to_string(),
+            meta: None,
+        }
+    }
+}
+
+/// Get the per-session resources.
+#[must_use]
+pub fn list_session_resources() -> Vec<Resource> {
+    [
+        (
+            SESSION_INFO_URI,
+            "session-info",
+            "Session Info",
+            "The reading session's ID, creation time, and counter value",
+            "application/json",
+        ),
+        (
+            SESSION_COUNTER_URI,
+            "session-counter",
+            "Session Counter",
+            "Increments on each read, separately for each session",
+            "text/plain",
+        ),
+    ]
+    .into_iter()
+    .map(|(uri, name, title, description, mime_type)| {
+        RawResource {
+            uri: uri.to_string(),
+            name: name.to_string(),
+            title: Some(title.to_string()),
+            description: Some(description.to_string()),
+            mime_type: Some(mime_type.to_string()),
+            size: None,
+            icons: None,
+            meta: None,
+        }
+        .no_annotation()
+    })
+    .collect()
+}
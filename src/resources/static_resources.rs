@@ -1,4 +1,5 @@
-//! Static resources: hello.txt, data.json, image.png, large.txt.
+//! Static resources: hello.txt, data.json, image.png, large.txt, audio.wav,
+//! document.pdf, and multi, whose read returns several content items.
 
 use rmcp::model::{AnnotateAble, RawResource, Resource, ResourceContents};
 
@@ -127,9 +128,205 @@ pub fn get_large_txt_content() -> ResourceContents {
     }
 }
 
+/// 0.05 s of a 440 Hz square wave: 8 kHz, 8-bit mono PCM (444 bytes), in base64.
+const AUDIO_WAV_BASE64: &str = concat!(
+    "UklGRrQBAABXQVZFZm10IBAAAAABAAEAQB8AAEAfAAABAAgAZGF0YZABAADAwMDAwMDAwMDAQEBAQEBAQEBAwMDA",
+    "wMDAwMDAQEBAQEBAQEBAwMDAwMDAwMDAQEBAQEBAQEBAwMDAwMDAwMDAQEBAQEBAQEBAwMDAwMDAwMDAQEBAQEBA",
+    "QEBAwMDAwMDAwMDAQEBAQEBAQEBAQMDAwMDAwMDAwEBAQEBAQEBAQMDAwMDAwMDAwEBAQEBAQEBAQMDAwMDAwMDA",
+    "wEBAQEBAQEBAQMDAwMDAwMDAwEBAQEBAQEBAQMDAwMDAwMDAwEBAQEBAQEBAQMDAwMDAwMDAwMBAQEBAQEBAQEDA",
+    "wMDAwMDAwMBAQEBAQEBAQEDAwMDAwMDAwMBAQEBAQEBAQEDAwMDAwMDAwMBAQEBAQEBAQEDAwMDAwMDAwMBAQEBA",
+    "QEBAQEDAwMDAwMDAwMBAQEBAQEBAQEBAwMDAwMDAwMDAQEBAQEBAQEBAwMDAwMDAwMDAQEBAQEBAQEBAwMDAwMDA",
+    "wMDAQEBAQEBAQEBAwMDAwMDAwMDAQEBAQEBAQEBAwMDAwMDAwMDAQEBAQEBAQEBA",
+);
+
+/// Get the audio.wav static resource.
+#[must_use]
+pub fn get_audio_wav_resource() -> Resource {
+    RawResource {
+        uri: "test://static/audio.wav".to_string(),
+        name: "audio.wav".to_string(),
+        title: None,
+        description: Some("A 50 ms WAV audio clip".to_string()),
+        mime_type: Some("audio/wav".to_string()),
+        size: Some(444),
+        icons: None,
+        meta: None,
+    }
+    .no_annotation()
+}
+
+/// Get the audio.wav content.
+#[must_use]
+pub fn get_audio_wav_content() -> ResourceContents {
+    ResourceContents::BlobResourceContents {
+        uri: "test://static/audio.wav".to_string(),
+        mime_type: Some("audio/wav".to_string()),
+        blob: AUDIO_WAV_BASE64.to_string(),
+        meta: None,
+    }
+}
+
+/// A one-page PDF reading "Hello, PDF!" (585 bytes), in base64.
+const DOCUMENT_PDF_BASE64: &str = concat!(
+    "JVBERi0xLjQKMSAwIG9iago8PCAvVHlwZSAvQ2F0YWxvZyAvUGFnZXMgMiAwIFIgPj4KZW5kb2JqCjIgMCBvYmoK",
+    "PDwgL1R5cGUgL1BhZ2VzIC9LaWRzIFszIDAgUl0gL0NvdW50IDEgPj4KZW5kb2JqCjMgMCBvYmoKPDwgL1R5cGUg",
+    "L1BhZ2UgL1BhcmVudCAyIDAgUiAvTWVkaWFCb3ggWzAgMCAyMDAgMTAwXSAvQ29udGVudHMgNCAwIFIgL1Jlc291",
+    "cmNlcyA8PCAvRm9udCA8PCAvRjEgNSAwIFIgPj4gPj4gPj4KZW5kb2JqCjQgMCBvYmoKPDwgL0xlbmd0aCA0MSA+",
+    "PgpzdHJlYW0KQlQgL0YxIDE4IFRmIDIwIDQ1IFRkIChIZWxsbywgUERGISkgVGogRVQKZW5kc3RyZWFtCmVuZG9i",
+    "ago1IDAgb2JqCjw8IC9UeXBlIC9Gb250IC9TdWJ0eXBlIC9UeXBlMSAvQmFzZUZvbnQgL0hlbHZldGljYSA+Pgpl",
+    "bmRvYmoKeHJlZgowIDYKMDAwMDAwMDAwMCA2NTUzNSBmIAowMDAwMDAwMDA5IDAwMDAwIG4gCjAwMDAwMDAwNTgg",
+    "MDAwMDAgbiAKMDAwMDAwMDExNSAwMDAwMCBuIAowMDAwMDAwMjQxIDAwMDAwIG4gCjAwMDAwMDAzMzIgMDAwMDAg",
+    "biAKdHJhaWxlcgo8PCAvU2l6ZSA2IC9Sb290IDEgMCBSID4+CnN0YXJ0eHJlZgo0MDIKJSVFT0YK",
+);
+
+/// Get the document.pdf static resource.
+#[must_use]
+pub fn get_document_pdf_resource() -> Resource {
+    RawResource {
+        uri: "test://static/document.pdf".to_string(),
+        name: "document.pdf".to_string(),
+        title: None,
+        description: Some("A one-page PDF document".to_string()),
+        mime_type: Some("application/pdf".to_string()),
+        size: Some(585),
+        icons: None,
+        meta: None,
+    }
+    .no_annotation()
+}
+
+/// Get the document.pdf content.
+#[must_use]
+pub fn get_document_pdf_content() -> ResourceContents {
+    ResourceContents::BlobResourceContents {
+        uri: "test://static/document.pdf".to_string(),
+        mime_type: Some("application/pdf".to_string()),
+        blob: DOCUMENT_PDF_BASE64.to_string(),
+        meta: None,
+    }
+}
+
+/// URI of the resource whose read returns several content items.
+pub const MULTI_URI: &str = "test://static/multi";
+
+/// Get the multi static resource.
+#[must_use]
+pub fn get_multi_resource() -> Resource {
+    RawResource {
+        uri: MULTI_URI.to_string(),
+        name: "multi".to_string(),
+        title: None,
+        description: Some(
+            "A resource read as three content items: plain text, JSON text, and a PNG blob"
+                .to_string(),
+        ),
+        mime_type: None,
+        size: None,
+        icons: None,
+        meta: None,
+    }
+    .no_annotation()
+}
+
+/// Get the multi contents, in order: text/plain, application/json, image/png.
+#[must_use]
+pub fn get_multi_contents() -> Vec<ResourceContents> {
+    let ResourceContents::BlobResourceContents { blob, .. } = get_image_png_content() else {
+        unreachable!("image.png is a blob");
+    };
+    vec![
+        ResourceContents::TextResourceContents {
+            uri: MULTI_URI.to_string(),
+            mime_type: Some("text/plain".to_string()),
+            text: "Part 1 of 3".to_string(),
+            meta: None,
+        },
+        ResourceContents::TextResourceContents {
+            uri: MULTI_URI.to_string(),
+            mime_type: Some("application/json".to_string()),
+            text: r#"{"part": 2, "of": 3}"#.to_string(),
+            meta: None,
+        },
+        ResourceContents::BlobResourceContents {
+            uri: MULTI_URI.to_string(),
+            mime_type: Some("image/png".to_string()),
+            blob,
+            meta: None,
+        },
+    ]
+}
+
 /// MCP Apps MIME type for interactive UI resources.
 const MCP_APP_MIME_TYPE: &str = "text/html;profile=mcp-app";
 
+/// Marks the start of the CDN script and stylesheet tags in a UI template.
+const CDN_START: &str = "<!-- {{CDN_START}} -->";
+
+/// Marks the end of the CDN tags in a UI template.
+const CDN_END: &str = "<!-- {{CDN_END}} -->";
+
+/// How the rich UI apps get their libraries (`MCP_UI_ASSETS`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UiAssets {
+    /// Load Chart.js and Tabulator from jsDelivr, falling back to vanilla
+    /// HTML/JS when the host's CSP blocks them (default).
+    #[default]
+    Cdn,
+    /// Drop the CDN tags, so every app is self-contained: the shim and the
+    /// vanilla fallbacks are all inline and nothing is fetched.
+    Inline,
+}
+
+impl UiAssets {
+    /// Parse `cdn` or `inline` (case-insensitive).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "cdn" => Some(Self::Cdn),
+            "inline" => Some(Self::Inline),
+            _ => None,
+        }
+    }
+
+    /// Deliver `contents` of a `ui://` app in this mode; other contents are
+    /// returned unchanged.
+    #[must_use]
+    pub fn apply(self, contents: ResourceContents) -> ResourceContents {
+        match (self, contents) {
+            (
+                Self::Inline,
+                ResourceContents::TextResourceContents {
+                    uri,
+                    mime_type,
+                    text,
+                    meta,
+                },
+            ) if uri.starts_with("ui://") => ResourceContents::TextResourceContents {
+                uri,
+                mime_type,
+                text: strip_cdn_tags(&text),
+                meta,
+            },
+            (_, contents) => contents,
+        }
+    }
+}
+
+/// Remove every block between [`CDN_START`] and [`CDN_END`], markers
+/// included.
+fn strip_cdn_tags(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find(CDN_START) {
+        out.push_str(&rest[..start]);
+        rest = rest[start..]
+            .find(CDN_END)
+            .map_or("", |end| &rest[start + end + CDN_END.len()..]);
+    }
+    out.push_str(rest);
+    out
+}
+
 /// Get the button app UI resource.
 #[must_use]
 pub fn get_button_app_resource() -> Resource {
@@ -354,6 +551,9 @@ pub fn list_static_resources() -> Vec<Resource> {
         get_data_json_resource(),
         get_image_png_resource(),
         get_large_txt_resource(),
+        get_audio_wav_resource(),
+        get_document_pdf_resource(),
+        get_multi_resource(),
         get_button_app_resource(),
         get_form_app_resource(),
         get_carousel_app_resource(),
@@ -365,6 +565,9 @@ pub fn list_static_resources() -> Vec<Resource> {
 }
 
 /// Read a static resource by URI.
+///
+/// Returns `None` for [`MULTI_URI`]; its contents come from
+/// [`get_multi_contents`].
 #[must_use]
 pub fn read_static_resource(uri: &str) -> Option<ResourceContents> {
     match uri {
@@ -372,6 +575,8 @@ pub fn read_static_resource(uri: &str) -> Option<ResourceContents> {
         "test://static/data.json" => Some(get_data_json_content()),
         "test://static/image.png" => Some(get_image_png_content()),
         "test://static/large.txt" => Some(get_large_txt_content()),
+        "test://static/audio.wav" => Some(get_audio_wav_content()),
+        "test://static/document.pdf" => Some(get_document_pdf_content()),
         "ui://button/app.html" => Some(get_button_app_content()),
         "ui://form/app.html" => Some(get_form_app_content()),
         "ui://carousel/app.html" => Some(get_carousel_app_content()),
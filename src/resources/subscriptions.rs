@@ -0,0 +1,103 @@
+//! Resource subscriptions by session.
+//!
+//! Every `resources/subscribe` is recorded here until the matching
+//! `resources/unsubscribe`, so a session holds at most one subscription per
+//! URI and subscribing twice is an error. `GET /admin/subscriptions` reports
+//! the table, letting tests check a client's subscription bookkeeping (for
+//! example that it unsubscribes when a view closes).
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, PoisonError};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// One subscription of a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Subscription {
+    /// The subscribed URI.
+    pub uri: String,
+    /// RFC 3339 time of the `resources/subscribe`.
+    pub subscribed_at: String,
+}
+
+/// Response of `GET /admin/subscriptions`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SubscriptionReport {
+    /// Subscriptions by session ID, each sorted by URI.
+    pub sessions: BTreeMap<String, Vec<Subscription>>,
+    /// Number of subscriptions across all sessions.
+    pub total: usize,
+}
+
+/// Current subscriptions: subscription times by URI, by session.
+#[derive(Debug, Default)]
+pub struct SubscriptionTable {
+    sessions: Mutex<BTreeMap<String, BTreeMap<String, DateTime<Utc>>>>,
+}
+
+impl SubscriptionTable {
+    /// Create an empty table.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `session` subscribed to `uri`; `false` if it already was.
+    pub fn add(&self, session: &str, uri: &str) -> bool {
+        let mut sessions = self.sessions.lock().unwrap_or_else(PoisonError::into_inner);
+        let uris = sessions.entry(session.to_string()).or_default();
+        let added = !uris.contains_key(uri);
+        if added {
+            uris.insert(uri.to_string(), Utc::now());
+        }
+        drop(sessions);
+        added
+    }
+
+    /// Forget that `session` subscribed to `uri`; `false` if it was not.
+    pub fn remove(&self, session: &str, uri: &str) -> bool {
+        let mut sessions = self.sessions.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(uris) = sessions.get_mut(session) else {
+            return false;
+        };
+        let removed = uris.remove(uri).is_some();
+        if uris.is_empty() {
+            sessions.remove(session);
+        }
+        removed
+    }
+
+    /// Whether `session` is subscribed to `uri`.
+    #[must_use]
+    pub fn contains(&self, session: &str, uri: &str) -> bool {
+        self.sessions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(session)
+            .is_some_and(|uris| uris.contains_key(uri))
+    }
+
+    /// Every current subscription.
+    #[must_use]
+    pub fn report(&self) -> SubscriptionReport {
+        let sessions: BTreeMap<String, Vec<Subscription>> = self
+            .sessions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(session, uris)| {
+                let subscriptions = uris
+                    .iter()
+                    .map(|(uri, at)| Subscription {
+                        uri: uri.clone(),
+                        subscribed_at: at.to_rfc3339(),
+                    })
+                    .collect();
+                (session.clone(), subscriptions)
+            })
+            .collect();
+        let total = sessions.values().map(Vec::len).sum();
+        SubscriptionReport { sessions, total }
+    }
+}
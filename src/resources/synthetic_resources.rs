@@ -0,0 +1,57 @@
+//! Synthetic resources: `test://synthetic/{n}` for `n` from 1 to
+//! `MCP_SYNTHETIC_RESOURCES`.
+//!
+//! They exist to make `resources/list` long. With thousands of them a
+//! listing spans many pages (see [`RESOURCE_PAGE_SIZE`](super::RESOURCE_PAGE_SIZE)),
+//! so clients can test cursor loops and list virtualization at scale. Each
+//! one is also readable.
+
+use rmcp::model::{AnnotateAble, RawResource, Resource, ResourceContents};
+
+/// URI prefix of the synthetic resources.
+pub const SYNTHETIC_PREFIX: &str = "test://synthetic/";
+
+/// Most synthetic resources the server generates.
+pub const MAX_SYNTHETIC_RESOURCES: usize = 100_000;
+
+/// The URI of synthetic resource `n`.
+#[must_use]
+pub fn synthetic_uri(n: usize) -> String {
+    format!("{SYNTHETIC_PREFIX}{n}")
+}
+
+/// Get the first `count` synthetic resources, in order.
+#[must_use]
+pub fn list_synthetic_resources(count: usize) -> Vec<Resource> {
+    (1..=count)
+        .map(|n| {
+            RawResource {
+                uri: synthetic_uri(n),
+                name: format!("synthetic-{n}"),
+                title: None,
+                description: Some(format!("Synthetic resource {n} of {count}")),
+                mime_type: Some("text/plain".to_string()),
+                size: None,
+                icons: None,
+                meta: None,
+            }
+            .no_annotation()
+        })
+        .collect()
+}
+
+/// Read synthetic resource `uri` if it is one of the first `count`.
+#[must_use]
+pub fn read_synthetic_resource(uri: &str, count: usize) -> Option<ResourceContents> {
+    let digits = uri.strip_prefix(SYNTHETIC_PREFIX)?;
+    // Only the canonical spelling: no sign or leading zeros
+    let n: usize = digits.parse().ok().filter(|n| synthetic_uri(*n) == uri)?;
+    (1..=count)
+        .contains(&n)
+        .then(|| ResourceContents::TextResourceContents {
+            uri: uri.to_string(),
+            mime_type: Some("text/plain".to_string()),
+            text: format!("Synthetic resource {n} of {count}"),
+            meta: None,
+        })
+}
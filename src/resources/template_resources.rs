@@ -0,0 +1,120 @@
+//! Resource templates with completion fixtures.
+//!
+//! - `test://repos/{owner}/{repo}`: completing `repo` depends on the `owner`
+//!   already chosen, which clients pass in `completion/complete` as
+//!   `context.arguments`. Without that context every repository name is
+//!   offered, so a client that drops the context gets visibly different
+//!   (wider) completions.
+//! - `test://many/{item}`: more completion candidates than the 100 one
+//!   response may hold, so responses are truncated with `total` and
+//!   `hasMore: true`.
+
+use rmcp::model::{
+    AnnotateAble, CompletionContext, RawResourceTemplate, ResourceContents, ResourceTemplate,
+};
+
+/// URI template of the repository resources.
+pub const REPO_TEMPLATE: &str = "test://repos/{owner}/{repo}";
+
+/// Repository names by owner, both sorted.
+pub const REPOS: [(&str, &[&str]); 3] = [
+    ("acme", &["anvil", "rocket", "widgets"]),
+    ("alice", &["dotfiles", "notes"]),
+    ("bob", &["blog", "dotfiles", "website"]),
+];
+
+/// Get the repository resource template.
+#[must_use]
+pub fn repo_template() -> ResourceTemplate {
+    RawResourceTemplate::new(REPO_TEMPLATE, "repos")
+        .with_title("Repository Template")
+        .with_description(
+            "A repository by owner and name; completing repo uses the owner from the context",
+        )
+        .with_mime_type("application/json")
+        .no_annotation()
+}
+
+/// Read `test://repos/{owner}/{repo}` for a known owner and repository.
+#[must_use]
+pub fn read_repo_resource(uri: &str) -> Option<ResourceContents> {
+    let (owner, repo) = uri.strip_prefix("test://repos/")?.split_once('/')?;
+    REPOS
+        .iter()
+        .find(|(o, repos)| *o == owner && repos.contains(&repo))
+        .map(|_| ResourceContents::TextResourceContents {
+            uri: uri.to_string(),
+            mime_type: Some("application/json".to_string()),
+            text: serde_json::json!({ "owner": owner, "repo": repo }).to_string(),
+            meta: None,
+        })
+}
+
+/// Completion candidates for the template variable `argument`.
+///
+/// `repo` candidates are those of the `owner` in `context`, or of every
+/// owner (sorted, without duplicates) if the context has none.
+#[must_use]
+pub fn complete_repo_argument(argument: &str, context: Option<&CompletionContext>) -> Vec<String> {
+    match argument {
+        "owner" => REPOS.iter().map(|(o, _)| (*o).to_string()).collect(),
+        "repo" => {
+            let owner = context.and_then(|c| c.get_argument("owner"));
+            let mut repos: Vec<String> = REPOS
+                .iter()
+                .filter(|(o, _)| owner.is_none_or(|owner| owner == o))
+                .flat_map(|(_, repos)| repos.iter().map(|r| (*r).to_string()))
+                .collect();
+            repos.sort();
+            repos.dedup();
+            repos
+        }
+        _ => vec![],
+    }
+}
+
+/// URI template of the resources with many completions.
+pub const MANY_TEMPLATE: &str = "test://many/{item}";
+
+/// Number of `test://many/{item}` items: `item-001` to `item-250`.
+pub const MANY_ITEMS: usize = 250;
+
+/// Get the template with many completions.
+#[must_use]
+pub fn many_template() -> ResourceTemplate {
+    RawResourceTemplate::new(MANY_TEMPLATE, "many")
+        .with_title("Many Items Template")
+        .with_description(format!(
+            "One of {MANY_ITEMS} items; completions are truncated to 100 with total and hasMore"
+        ))
+        .with_mime_type("text/plain")
+        .no_annotation()
+}
+
+fn many_item(n: usize) -> String {
+    format!("item-{n:03}")
+}
+
+/// Read `test://many/{item}` for one of the [`MANY_ITEMS`] items.
+#[must_use]
+pub fn read_many_resource(uri: &str) -> Option<ResourceContents> {
+    let item = uri.strip_prefix("test://many/")?;
+    (1..=MANY_ITEMS)
+        .any(|n| many_item(n) == item)
+        .then(|| ResourceContents::TextResourceContents {
+            uri: uri.to_string(),
+            mime_type: Some("text/plain".to_string()),
+            text: format!("Many item: {item}"),
+            meta: None,
+        })
+}
+
+/// Completion candidates for `item`: every item name, in order.
+#[must_use]
+pub fn complete_many_argument(argument: &str) -> Vec<String> {
+    if argument == "item" {
+        (1..=MANY_ITEMS).map(many_item).collect()
+    } else {
+        vec![]
+    }
+}
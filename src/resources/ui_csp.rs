@@ -0,0 +1,199 @@
+//! Content Security Policy of the MCP Apps (`MCP_UI_CSP`).
+//!
+//! The policy is declared in two places. `_meta.ui.csp` on the `ui://`
+//! resources lists the origins an app may reach (`connectDomains`) and load
+//! scripts, styles, images, and fonts from (`resourceDomains`); hosts build
+//! their iframe sandbox from it. With `MCP_HOSTED_APPS`, the same apps are
+//! also served over plain HTTP at `GET /apps/{app}` with a real
+//! `Content-Security-Policy` header derived from the same lists, so a
+//! browser enforces it without a host in between. `?csp=` overrides the
+//! policy of one request, so strict and lax pages can be compared side by
+//! side.
+
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use rmcp::model::{Meta, Resource, ResourceContents};
+use serde::Deserialize;
+
+use super::static_resources::{self, UiAssets};
+
+/// Origin the rich apps load Chart.js and Tabulator from.
+pub const CDN_ORIGIN: &str = "https://cdn.jsdelivr.net";
+
+/// CSP declared for the `ui://` apps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UiCsp {
+    /// Declare nothing, so hosts apply their default policy (default).
+    #[default]
+    None,
+    /// Declare empty domain lists: no external origin at all.
+    Strict,
+    /// Allow loading resources from the CDN the rich apps use.
+    Cdn,
+    /// Allow connecting to and loading resources from any origin.
+    Lax,
+}
+
+impl UiCsp {
+    /// Parse `none`, `strict`, `cdn`, or `lax` (case-insensitive).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "none" => Some(Self::None),
+            "strict" => Some(Self::Strict),
+            "cdn" => Some(Self::Cdn),
+            "lax" => Some(Self::Lax),
+            _ => None,
+        }
+    }
+
+    /// The `connectDomains` and `resourceDomains` of this policy.
+    ///
+    /// [`UiCsp::None`] has the lists of [`UiCsp::Strict`], which is what the
+    /// MCP Apps spec has hosts assume when an app declares nothing.
+    const fn domains(self) -> (&'static [&'static str], &'static [&'static str]) {
+        match self {
+            Self::None | Self::Strict => (&[], &[]),
+            Self::Cdn => (&[], &[CDN_ORIGIN]),
+            Self::Lax => (&["*"], &["*"]),
+        }
+    }
+
+    /// `_meta.ui.csp` of this policy; `None` declares nothing.
+    #[must_use]
+    pub fn declaration(self) -> Option<serde_json::Value> {
+        let (connect, resource) = self.domains();
+        (self != Self::None).then(|| {
+            serde_json::json!({
+                "connectDomains": connect,
+                "resourceDomains": resource,
+            })
+        })
+    }
+
+    /// The `Content-Security-Policy` header of a hosted app.
+    ///
+    /// Inline scripts and styles are always allowed, since every app inlines
+    /// its shim.
+    #[must_use]
+    pub fn header(self) -> String {
+        let (connect, resource) = self.domains();
+        let resource = if resource.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", resource.join(" "))
+        };
+        let connect = if connect.is_empty() {
+            "'none'".to_string()
+        } else {
+            connect.join(" ")
+        };
+        format!(
+            "default-src 'none'; script-src 'unsafe-inline'{resource}; \
+             style-src 'unsafe-inline'{resource}; img-src data:{resource}; \
+             font-src data:{resource}; media-src data:{resource}; \
+             connect-src {connect}; frame-src 'none'; base-uri 'none'"
+        )
+    }
+
+    /// Declare this policy on the listing of a `ui://` app; other resources
+    /// are returned unchanged.
+    #[must_use]
+    pub fn annotate(self, mut resource: Resource) -> Resource {
+        if resource.raw.uri.starts_with("ui://") {
+            resource.raw.meta = self.declare(resource.raw.meta.take());
+        }
+        resource
+    }
+
+    /// Declare this policy on `contents` of a `ui://` app; other contents are
+    /// returned unchanged.
+    #[must_use]
+    pub fn apply(self, contents: ResourceContents) -> ResourceContents {
+        match contents {
+            ResourceContents::TextResourceContents {
+                uri,
+                mime_type,
+                text,
+                meta,
+            } if uri.starts_with("ui://") => ResourceContents::TextResourceContents {
+                uri,
+                mime_type,
+                text,
+                meta: self.declare(meta),
+            },
+            contents => contents,
+        }
+    }
+
+    /// `meta` with `ui.csp` set to this policy's declaration.
+    fn declare(self, meta: Option<Meta>) -> Option<Meta> {
+        let Some(csp) = self.declaration() else {
+            return meta;
+        };
+        let mut meta = meta.unwrap_or_default();
+        let ui = meta.0.entry("ui").or_insert_with(|| serde_json::json!({}));
+        if let Some(ui) = ui.as_object_mut() {
+            ui.insert("csp".to_string(), csp);
+        }
+        Some(meta)
+    }
+}
+
+/// How `GET /apps/{app}` serves the apps.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HostedApps {
+    /// Policy of requests without `?csp=`.
+    pub csp: UiCsp,
+    /// Delivery of the apps' libraries.
+    pub assets: UiAssets,
+}
+
+/// Query of `GET /apps/{app}`.
+#[derive(Debug, Deserialize)]
+pub struct HostedAppQuery {
+    /// Policy for this request: `none`, `strict`, `cdn`, or `lax`.
+    csp: Option<String>,
+}
+
+/// `GET /apps/{app}`: the HTML of `ui://{app}/app.html` with a
+/// `Content-Security-Policy` header.
+///
+/// # Errors
+///
+/// Returns `404` for an unknown app and `400` for an unknown `?csp=` policy.
+pub async fn hosted_app(
+    State(apps): State<HostedApps>,
+    Path(app): Path<String>,
+    Query(query): Query<HostedAppQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    let csp = match query.csp.as_deref() {
+        None => apps.csp,
+        Some(name) => UiCsp::parse(name).ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("unknown csp {name:?}; expected none, strict, cdn, or lax"),
+            )
+        })?,
+    };
+    let Some(ResourceContents::TextResourceContents { text, .. }) =
+        static_resources::read_static_resource(&format!("ui://{app}/app.html"))
+            .map(|contents| apps.assets.apply(contents))
+    else {
+        return Err((StatusCode::NOT_FOUND, format!("unknown app {app:?}")));
+    };
+    let policy = HeaderValue::from_str(&csp.header())
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("text/html; charset=utf-8"),
+            ),
+            (header::CONTENT_SECURITY_POLICY, policy),
+        ],
+        text,
+    )
+        .into_response())
+}
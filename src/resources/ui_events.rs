@@ -0,0 +1,155 @@
+//! The MCP App message log: `test://dynamic/ui-events`.
+//!
+//! The app shim reports every message its iframe posts to the host
+//! (`ui/initialize`, `tools/call`, `resources/read`,
+//! `ui/notifications/size-changed`, `ui/open-link`, ...) through the
+//! app-only `ui_record_event` tool. Reading the resource returns the log, so
+//! a UI host can assert on what its iframe actually sent rather than on what
+//! it forwarded. The reports themselves are not logged.
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, PoisonError};
+
+use chrono::{DateTime, Utc};
+use rmcp::model::{AnnotateAble, RawResource, Resource, ResourceContents};
+use serde::Serialize;
+
+/// URI of the MCP App message log.
+pub const UI_EVENTS_URI: &str = "test://dynamic/ui-events";
+
+/// Most messages kept; older ones are dropped first.
+pub const MAX_UI_EVENTS: usize = 1000;
+
+/// One message an app posted to its host.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UiEvent {
+    /// Position in the log, from 1; keeps counting when old events drop out.
+    pub seq: u64,
+    /// When the report arrived.
+    pub received_at: DateTime<Utc>,
+    /// Name of the app that posted it, from its `ui/initialize`.
+    pub app: String,
+    /// JSON-RPC method, e.g. `ui/open-link`.
+    pub method: String,
+    /// JSON-RPC id of a request; absent for notifications.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<serde_json::Value>,
+    /// The message params.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Default)]
+struct Log {
+    events: VecDeque<UiEvent>,
+    total: u64,
+}
+
+/// The messages reported by MCP Apps, shared by all sessions.
+#[derive(Debug, Default)]
+pub struct UiEventLog {
+    log: Mutex<Log>,
+}
+
+impl UiEventLog {
+    /// Create an empty log.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a message, returning its `seq`.
+    pub fn record(
+        &self,
+        app: String,
+        method: String,
+        id: Option<serde_json::Value>,
+        params: Option<serde_json::Value>,
+    ) -> u64 {
+        let mut log = self.log.lock().unwrap_or_else(PoisonError::into_inner);
+        log.total += 1;
+        let seq = log.total;
+        if log.events.len() == MAX_UI_EVENTS {
+            log.events.pop_front();
+        }
+        log.events.push_back(UiEvent {
+            seq,
+            received_at: Utc::now(),
+            app,
+            method,
+            id,
+            params,
+        });
+        drop(log);
+        seq
+    }
+
+    /// The logged messages, oldest first.
+    #[must_use]
+    pub fn events(&self) -> Vec<UiEvent> {
+        let log = self.log.lock().unwrap_or_else(PoisonError::into_inner);
+        log.events.iter().cloned().collect()
+    }
+
+    /// The log as read: `{"events": [...], "total"}`, where `total` counts
+    /// dropped events too.
+    #[must_use]
+    pub fn content(&self) -> ResourceContents {
+        let log = self.log.lock().unwrap_or_else(PoisonError::into_inner);
+        let body = serde_json::json!({ "events": log.events, "total": log.total });
+        drop(log);
+        ResourceContents::TextResourceContents {
+            uri: UI_EVENTS_URI.to_string(),
+            mime_type: Some("application/json".to_string()),
+            text: body.to_string(),
+            meta: None,
+        }
+    }
+}
+
+/// Get the MCP App message log resource.
+#[must_use]
+pub fn get_ui_events_resource() -> Resource {
+    RawResource {
+        uri: UI_EVENTS_URI.to_string(),
+        name: "ui-events".to_string(),
+        title: Some("MCP App Messages".to_string()),
+        description: Some(
+            "Every message the MCP App iframes posted to their host, as reported by the shim"
+                .to_string(),
+        ),
+        mime_type: Some("application/json".to_string()),
+        size: None,
+        icons: None,
+        meta: None,
+    }
+    .no_annotation()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_is_bounded_and_keeps_counting() {
+        let log = UiEventLog::new();
+        for n in 0..=MAX_UI_EVENTS {
+            log.record(
+                "Dashboard".to_string(),
+                "tools/call".to_string(),
+                Some(serde_json::json!(n)),
+                None,
+            );
+        }
+        let events = log.events();
+        assert_eq!(events.len(), MAX_UI_EVENTS);
+        assert_eq!(events[0].seq, 2);
+
+        let ResourceContents::TextResourceContents { text, .. } = log.content() else {
+            panic!("Expected text contents");
+        };
+        let body: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(body["total"], MAX_UI_EVENTS + 1);
+        assert!(body["events"][0].get("params").is_none());
+    }
+}
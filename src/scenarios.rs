@@ -0,0 +1,463 @@
+//! Reusable end-to-end scenarios for MCP client test suites.
+//!
+//! A downstream client implements [`ScenarioClient`] as a thin adapter over
+//! its own API. [`run_all`] (or [`Scenario::run`]) then starts a server
+//! configured for each scenario on a free localhost port, drives the client
+//! through it, and returns a [`ScenarioReport`] with one [`Check`] per
+//! expectation, so failures point at the exact step that went wrong.
+//!
+//! ```rust,no_run
+//! # async fn example(client: &mut impl mcp_test_server::scenarios::ScenarioClient) {
+//! let report = mcp_test_server::scenarios::run_all(client).await;
+//! assert!(report.passed(), "{}", serde_json::to_string_pretty(&report).unwrap());
+//! # }
+//! ```
+
+use std::future::Future;
+use std::net::{IpAddr, Ipv4Addr};
+use std::time::Instant;
+
+use rmcp::model::{CallToolResult, JsonObject};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::{Config, McpTestServer, ServerHandle};
+
+/// API key the [`Scenario::AuthFailure`] server requires.
+pub const SCENARIO_API_KEY: &str = "scenario-key";
+
+/// Where a scenario's server is listening.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Target {
+    /// URL of the Streamable HTTP endpoint, e.g. `http://127.0.0.1:4000/mcp`.
+    pub url: String,
+    /// Bearer token to send in `Authorization`, if any.
+    pub bearer_token: Option<String>,
+}
+
+/// Adapter between the scenarios and the client under test.
+///
+/// Errors are the client's own messages; scenarios only check whether a
+/// call failed and report the message as detail.
+pub trait ScenarioClient: Send {
+    /// Connect to `target` and complete the `initialize` handshake.
+    fn connect(&mut self, target: &Target) -> impl Future<Output = Result<(), String>> + Send;
+
+    /// Names of the tools listed by the server.
+    fn list_tools(&mut self) -> impl Future<Output = Result<Vec<String>, String>> + Send;
+
+    /// Call a tool. An `isError` result is `Ok`; a JSON-RPC error is `Err`.
+    fn call_tool(
+        &mut self,
+        name: &str,
+        arguments: JsonObject,
+    ) -> impl Future<Output = Result<CallToolResult, String>> + Send;
+
+    /// Close the connection (and session, if any).
+    fn disconnect(&mut self) -> impl Future<Output = ()> + Send;
+}
+
+/// A built-in end-to-end scenario.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Scenario {
+    /// Initialize, list tools, call a tool, and disconnect.
+    HappyPath,
+    /// Connect without a key and with a wrong key (both must fail), then with
+    /// the right key (must work).
+    AuthFailure,
+    /// Tool errors, protocol errors, and a slow call, then check that the
+    /// client still works.
+    ChaosRun,
+}
+
+impl Scenario {
+    /// Every scenario, in the order [`run_all`] runs them.
+    pub const ALL: [Self; 3] = [Self::HappyPath, Self::AuthFailure, Self::ChaosRun];
+
+    /// Name used in reports.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::HappyPath => "happy_path",
+            Self::AuthFailure => "auth_failure",
+            Self::ChaosRun => "chaos_run",
+        }
+    }
+
+    /// Server configuration for this scenario (host and port are overridden).
+    #[must_use]
+    pub fn config(self) -> Config {
+        match self {
+            Self::HappyPath => Config::default(),
+            Self::AuthFailure => Config::builder().api_key(SCENARIO_API_KEY).build(),
+            Self::ChaosRun => Config::builder().strict_args(true).build(),
+        }
+    }
+
+    /// Start a server for this scenario and drive `client` through it.
+    pub async fn run<C: ScenarioClient>(self, client: &mut C) -> ScenarioOutcome {
+        let started = Instant::now();
+        let mut checks = Checks::default();
+        match ScenarioServer::start(self.config()).await {
+            Ok(server) => {
+                match self {
+                    Self::HappyPath => happy_path(client, &server, &mut checks).await,
+                    Self::AuthFailure => auth_failure(client, &server, &mut checks).await,
+                    Self::ChaosRun => chaos_run(client, &server, &mut checks).await,
+                }
+                client.disconnect().await;
+            }
+            Err(e) => checks.fail("server_started", e),
+        }
+        let checks = checks.0;
+        ScenarioOutcome {
+            scenario: self,
+            passed: checks.iter().all(|c| c.passed),
+            checks,
+            duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        }
+    }
+}
+
+/// Run every scenario in [`Scenario::ALL`] against `client`.
+pub async fn run_all<C: ScenarioClient>(client: &mut C) -> ScenarioReport {
+    let mut scenarios = Vec::new();
+    for scenario in Scenario::ALL {
+        let outcome = scenario.run(client).await;
+        tracing::info!(
+            scenario = scenario.name(),
+            passed = outcome.passed,
+            "Scenario finished"
+        );
+        scenarios.push(outcome);
+    }
+    ScenarioReport { scenarios }
+}
+
+/// Results of a set of scenarios.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScenarioReport {
+    /// One outcome per scenario, in run order.
+    pub scenarios: Vec<ScenarioOutcome>,
+}
+
+impl ScenarioReport {
+    /// Whether every scenario passed.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.scenarios.iter().all(|s| s.passed)
+    }
+
+    /// Checks that failed, with the scenario they belong to.
+    #[must_use]
+    pub fn failures(&self) -> Vec<(Scenario, &Check)> {
+        self.scenarios
+            .iter()
+            .flat_map(|s| {
+                s.checks
+                    .iter()
+                    .filter(|c| !c.passed)
+                    .map(|c| (s.scenario, c))
+            })
+            .collect()
+    }
+}
+
+/// Result of one scenario.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScenarioOutcome {
+    /// The scenario that ran.
+    pub scenario: Scenario,
+    /// Whether every check passed.
+    pub passed: bool,
+    /// Checks in the order they ran.
+    pub checks: Vec<Check>,
+    /// Wall-clock time of the scenario, including server startup.
+    pub duration_ms: u64,
+}
+
+/// One expectation checked during a scenario.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Check {
+    /// What was checked, e.g. `initialize` or `wrong_key_rejected`.
+    pub name: String,
+    /// Whether the expectation held.
+    pub passed: bool,
+    /// What was observed when the check failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Default)]
+struct Checks(Vec<Check>);
+
+impl Checks {
+    fn pass(&mut self, name: &str) {
+        self.0.push(Check {
+            name: name.to_string(),
+            passed: true,
+            detail: None,
+        });
+    }
+
+    fn fail(&mut self, name: &str, detail: impl Into<String>) {
+        self.0.push(Check {
+            name: name.to_string(),
+            passed: false,
+            detail: Some(detail.into()),
+        });
+    }
+
+    /// Record `Ok` as a pass and `Err` as a failure; returns whether it passed.
+    fn check(&mut self, name: &str, result: Result<(), String>) -> bool {
+        match result {
+            Ok(()) => {
+                self.pass(name);
+                true
+            }
+            Err(detail) => {
+                self.fail(name, detail);
+                false
+            }
+        }
+    }
+}
+
+/// A server running on a free localhost port until dropped.
+struct ScenarioServer(ServerHandle);
+
+impl ScenarioServer {
+    async fn start(mut config: Config) -> Result<Self, String> {
+        config.host = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        config.port = 0;
+        McpTestServer::new(config)
+            .spawn()
+            .await
+            .map(Self)
+            .map_err(|e| format!("failed to start: {e}"))
+    }
+
+    fn target(&self, bearer_token: Option<&str>) -> Target {
+        Target {
+            url: self.0.mcp_url(),
+            bearer_token: bearer_token.map(str::to_string),
+        }
+    }
+}
+
+fn arguments(value: serde_json::Value) -> JsonObject {
+    match value {
+        serde_json::Value::Object(map) => map,
+        _ => JsonObject::new(),
+    }
+}
+
+/// Concatenated text content of a tool result.
+fn result_text(result: &CallToolResult) -> String {
+    result
+        .content
+        .iter()
+        .filter_map(|c| c.as_text().map(|t| t.text.as_str()))
+        .collect()
+}
+
+/// Expect a successful result whose text contains `expected`.
+fn expect_text(result: Result<CallToolResult, String>, expected: &str) -> Result<(), String> {
+    let result = result?;
+    if result.is_error == Some(true) {
+        return Err(format!(
+            "unexpected isError result: {}",
+            result_text(&result)
+        ));
+    }
+    let text = result_text(&result);
+    if text.contains(expected) {
+        Ok(())
+    } else {
+        Err(format!(
+            "expected text containing {expected:?}, got {text:?}"
+        ))
+    }
+}
+
+/// Expect an `isError` result whose text contains `expected`.
+fn expect_tool_error(result: Result<CallToolResult, String>, expected: &str) -> Result<(), String> {
+    match result {
+        Ok(result) if result.is_error == Some(true) => {
+            let text = result_text(&result);
+            if text.contains(expected) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "expected error text containing {expected:?}, got {text:?}"
+                ))
+            }
+        }
+        Ok(result) => Err(format!(
+            "expected isError result, got success: {}",
+            result_text(&result)
+        )),
+        Err(e) => Err(format!("expected isError result, got protocol error: {e}")),
+    }
+}
+
+/// Expect the call to fail with a JSON-RPC error.
+fn expect_protocol_error(result: Result<CallToolResult, String>) -> Result<(), String> {
+    result.map_or(Ok(()), |result| {
+        Err(format!(
+            "expected a JSON-RPC error, got a result: {}",
+            result_text(&result)
+        ))
+    })
+}
+
+async fn happy_path<C: ScenarioClient>(
+    client: &mut C,
+    server: &ScenarioServer,
+    checks: &mut Checks,
+) {
+    if !checks.check("initialize", client.connect(&server.target(None)).await) {
+        return;
+    }
+    let tools = client.list_tools().await.and_then(|tools| {
+        if tools.iter().any(|t| t == "echo") {
+            Ok(())
+        } else {
+            Err(format!("echo missing from {} listed tools", tools.len()))
+        }
+    });
+    checks.check("list_tools", tools);
+    let echo = client
+        .call_tool("echo", arguments(json!({ "text": "scenario" })))
+        .await;
+    checks.check("call_echo", expect_text(echo, "scenario"));
+    let add = client
+        .call_tool("add", arguments(json!({ "a": 2, "b": 3 })))
+        .await;
+    checks.check("call_add", expect_text(add, "5"));
+}
+
+async fn auth_failure<C: ScenarioClient>(
+    client: &mut C,
+    server: &ScenarioServer,
+    checks: &mut Checks,
+) {
+    for (name, token) in [
+        ("missing_key_rejected", None),
+        ("wrong_key_rejected", Some("wrong")),
+    ] {
+        let rejected = match client.connect(&server.target(token)).await {
+            Ok(()) => {
+                client.disconnect().await;
+                Err("connect succeeded".to_string())
+            }
+            Err(_) => Ok(()),
+        };
+        checks.check(name, rejected);
+    }
+    if !checks.check(
+        "valid_key_accepted",
+        client.connect(&server.target(Some(SCENARIO_API_KEY))).await,
+    ) {
+        return;
+    }
+    let listed = client.list_tools().await.map(|_| ());
+    checks.check("list_tools", listed);
+}
+
+async fn chaos_run<C: ScenarioClient>(
+    client: &mut C,
+    server: &ScenarioServer,
+    checks: &mut Checks,
+) {
+    if !checks.check("initialize", client.connect(&server.target(None)).await) {
+        return;
+    }
+    let fail = client.call_tool("fail", JsonObject::new()).await;
+    checks.check("tool_error", expect_tool_error(fail, "always fails"));
+    let custom = client
+        .call_tool(
+            "fail_with_message",
+            arguments(json!({ "message": "chaos monkey" })),
+        )
+        .await;
+    checks.check(
+        "tool_error_message",
+        expect_tool_error(custom, "chaos monkey"),
+    );
+    let unknown = client.call_tool("no_such_tool", JsonObject::new()).await;
+    checks.check("unknown_tool_error", expect_protocol_error(unknown));
+    let invalid = client
+        .call_tool(
+            "echo",
+            arguments(json!({ "text": "x", "unexpected": true })),
+        )
+        .await;
+    checks.check("invalid_arguments_error", expect_protocol_error(invalid));
+    let slow = client
+        .call_tool(
+            "slow_echo",
+            arguments(json!({ "text": "eventually", "delay_ms": 200 })),
+        )
+        .await;
+    checks.check("slow_call", expect_text(slow, "eventually"));
+    let echo = client
+        .call_tool("echo", arguments(json!({ "text": "recovered" })))
+        .await;
+    checks.check("recovered", expect_text(echo, "recovered"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scenario_names_are_unique() {
+        let mut names: Vec<_> = Scenario::ALL.iter().map(|s| s.name()).collect();
+        names.dedup();
+        assert_eq!(names.len(), Scenario::ALL.len());
+        assert_eq!(
+            serde_json::to_value(Scenario::ChaosRun).unwrap(),
+            Scenario::ChaosRun.name()
+        );
+    }
+
+    #[test]
+    fn test_report_lists_failures() {
+        let mut checks = Checks::default();
+        checks.pass("initialize");
+        checks.fail("list_tools", "timed out");
+        let report = ScenarioReport {
+            scenarios: vec![ScenarioOutcome {
+                scenario: Scenario::HappyPath,
+                passed: false,
+                checks: checks.0,
+                duration_ms: 1,
+            }],
+        };
+        assert!(!report.passed());
+        let failures = report.failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, Scenario::HappyPath);
+        assert_eq!(failures[0].1.detail.as_deref(), Some("timed out"));
+        let json = serde_json::to_value(&report).unwrap();
+        assert!(json["scenarios"][0]["checks"][0].get("detail").is_none());
+    }
+
+    #[test]
+    fn test_expectations() {
+        let ok = Ok(CallToolResult::success(vec![rmcp::model::Content::text(
+            "hi",
+        )]));
+        let err = Ok(CallToolResult::error(vec![rmcp::model::Content::text(
+            "boom",
+        )]));
+        assert!(expect_text(ok.clone(), "hi").is_ok());
+        assert!(expect_text(err.clone(), "boom").is_err());
+        assert!(expect_tool_error(err, "boom").is_ok());
+        assert!(expect_tool_error(ok.clone(), "hi").is_err());
+        assert!(expect_protocol_error(Err("-32602".to_string())).is_ok());
+        assert!(expect_protocol_error(ok).is_err());
+    }
+}
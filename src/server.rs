@@ -8,48 +8,80 @@ use chrono::Utc;
 use rand::Rng;
 use rmcp::ErrorData as McpError;
 use rmcp::{
-    handler::server::{ServerHandler, router::tool::ToolRouter, wrapper::Parameters},
+    handler::server::{
+        ServerHandler,
+        common::Extension,
+        router::tool::{ToolRoute, ToolRouter},
+        tool::ToolCallContext,
+        wrapper::Parameters,
+    },
     model::{
-        CompleteResult, CompletionInfo, ExtensionCapabilities, Icon, Implementation,
-        ListResourceTemplatesResult, ListResourcesResult, ProtocolVersion, ReadResourceResult,
-        Reference, ServerCapabilities, ServerInfo,
+        CallToolResult, CompleteResult, CompletionInfo, Content, ExtensionCapabilities, Icon,
+        Implementation, ListResourceTemplatesResult, ListResourcesResult, ProtocolVersion,
+        ReadResourceResult, Reference, ServerCapabilities, ServerInfo,
     },
     task_handler,
     task_manager::OperationProcessor,
-    tool, tool_handler, tool_router,
+    tool, tool_router,
     transport::streamable_http_server::{
         StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
     },
 };
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
 use tokio::sync::Mutex;
 use tokio_util::sync::CancellationToken;
-use tower_http::cors::CorsLayer;
 
 use crate::{
     auth::auth_middleware,
     config::Config,
+    dry_run::DryRun,
     tools::{
+        array::{DedupArrayParams, DedupedArray, SortArrayParams, SortedArray},
+        compression::{
+            GzipCompressParams, GzipDecompressParams, ZstdCompressParams, ZstdDecompressParams,
+        },
+        convert::{JsonToYamlParams, TomlToJsonParams, YamlToJsonParams},
+        counter::{self, CounterIncrementParams, CounterResetParams},
+        csv::{CsvFormatParams, CsvParseParams},
+        datetime::{DateAddParams, DateDiffParams, TimezoneConvertParams},
         encoding::{
-            Base64DecodeParams, Base64EncodeParams, HashSha256Params, JsonParseParams,
-            JsonStringifyParams,
+            Base64DecodeParams, Base64EncodeParams, HashAlgorithm, HashCrc32Params, HashMd5Params,
+            HashParams, HashSha1Params, HashSha256Params, HashSha512Params, HmacSha256Params,
+            JsonParseParams, JsonStringifyParams, UrlDecodeParams, UrlEncodeParams, UrlParseParams,
+            digest,
         },
-        math::{AddParams, DivideParams, MultiplyParams, SubtractParams},
+        ephemeral::{self, EphemeralCreateParams},
+        fetch::FetchUrlParams,
+        fixtures::{FixtureGenerateParams, MAX_FIXTURE_COUNT},
+        kv::{self, KvDeleteParams, KvGetParams, KvListParams, KvSetParams},
+        markdown::MarkdownToHtmlParams,
+        math::{AddParams, DivideParams, MultiplyParams, Stats, StatsParams, SubtractParams},
+        prompts::{self, RegisterPromptParams},
+        query::{JsonQueryParams, JsonQueryResult},
+        regex::{RegexExtractParams, RegexMatchParams, RegexReplaceParams},
+        scratch::{self, ResourceDeleteParams, ResourceWriteParams},
         string::{
-            ConcatParams, EchoParams, LengthParams, LowercaseParams, ReverseParams, UppercaseParams,
+            ConcatParams, EchoParams, LengthParams, LowercaseParams, ReverseParams, UnicodeInfo,
+            UnicodeInfoParams, UppercaseParams, WordCount, WordCountParams,
         },
+        template::RenderTemplateParams,
         testing::{
-            BinaryDataParams, FailParams, FailWithMessageParams, LargeResponseParams,
-            NestedDataParams, SleepParams, SlowEchoParams, TaskCancellableParams, TaskFailParams,
-            TaskSlowComputeParams,
+            BinaryDataParams, EdgeCaseTextParams, FailParams, FailWithMessageParams,
+            HugeResponseParams, LargeResponseParams, MAX_HUGE_RESPONSE_BYTES,
+            MAX_STREAM_INTERVAL_MS, MAX_STREAM_LINES, NestedDataParams, PanicMode, PanicParams,
+            PromptAuditParams, SleepParams, SlowEchoParams, StreamLinesParams,
+            TaskCancellableParams, TaskFailParams, TaskSlowComputeParams,
         },
         ui::{
-            UiInternalOnlyParams, UiResourceButtonParams, UiResourceCarouselParams,
-            UiResourceDashboardParams, UiResourceDataTableParams, UiResourceFormParams,
-            UiResourcePipelineParams,
+            UiInternalOnlyParams, UiRecordEventParams, UiResourceButtonParams,
+            UiResourceCarouselParams, UiResourceDashboardParams, UiResourceDataTableParams,
+            UiResourceFormParams, UiResourcePipelineParams,
         },
-        utility::{CurrentTimeParams, RandomNumberParams, RandomUuidParams},
+        utility::{
+            CurrentTimeParams, DiceRoll, RandomChoice, RandomChoiceParams, RandomNumberParams,
+            RandomString, RandomStringParams, RandomUuidParams, RollDiceParams,
+        },
+        xml::{XmlParseParams, XmlQueryParams},
     },
 };
 
@@ -61,7 +93,11 @@ use crate::{
 /// `visibility` controls where the tool appears:
 /// - `"both"` — visible to both the LLM and the UI iframe
 /// - `"app"` — only callable from the iframe, hidden from the LLM
-fn ui_meta(resource_uri: &str, visibility: &str) -> rmcp::model::Meta {
+///
+/// `preferred_size` is the width and height in CSS pixels the app is laid
+/// out for, a hint hosts may use to size the iframe before the app reports
+/// its own size.
+fn ui_meta(resource_uri: &str, visibility: &str, (width, height): (u32, u32)) -> rmcp::model::Meta {
     debug_assert!(
         visibility == "both" || visibility == "app",
         "Invalid UI visibility: {visibility}"
@@ -70,7 +106,11 @@ fn ui_meta(resource_uri: &str, visibility: &str) -> rmcp::model::Meta {
     let mut meta = rmcp::model::Meta::new();
     meta.insert(
         "ui".to_string(),
-        serde_json::json!({ "resourceUri": resource_uri, "visibility": visibility }),
+        serde_json::json!({
+            "resourceUri": resource_uri,
+            "visibility": visibility,
+            "preferredSize": { "width": width, "height": height },
+        }),
     );
     // Legacy flat key — only carries resourceUri. Visibility is new and not
     // supported by hosts that still read the flat key format.
@@ -86,6 +126,35 @@ fn ui_meta(resource_uri: &str, visibility: &str) -> rmcp::model::Meta {
     meta
 }
 
+/// `_meta` of an app-only tool not tied to one app resource.
+fn app_only_meta() -> rmcp::model::Meta {
+    let mut meta = rmcp::model::Meta::new();
+    meta.insert("ui".to_string(), serde_json::json!({ "visibility": "app" }));
+    meta
+}
+
+/// Result of a UI tool: `message`, then the app HTML at `resource_uri`, as
+/// `resources` serves it, as an embedded resource for hosts that render
+/// `ui://` resources inline.
+fn ui_result(
+    message: &str,
+    resource_uri: &str,
+    resources: &crate::resources::ResourceHandler,
+) -> CallToolResult {
+    let mut content = vec![Content::text(message)];
+    content.extend(resources.ui_app(resource_uri).map(Content::resource));
+    CallToolResult::success(content)
+}
+
+/// Icons of the UI tools: the app-window SVG, scalable to any size.
+fn ui_icons() -> Vec<Icon> {
+    vec![
+        Icon::new(crate::icons::UI_TOOL_ICON_SVG)
+            .with_mime_type("image/svg+xml")
+            .with_sizes(vec!["any".to_string()]),
+    ]
+}
+
 /// Helper function to create nested JSON data.
 fn create_nested(depth: usize) -> serde_json::Value {
     if depth == 0 {
@@ -98,6 +167,12 @@ fn create_nested(depth: usize) -> serde_json::Value {
     }
 }
 
+/// Pause in the `panic` tool between the log message and the connection abort.
+const ABORT_FLUSH_DELAY: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// Buffer size of each direction of an in-process connection.
+const IN_PROCESS_BUFFER_BYTES: usize = 64 * 1024;
+
 /// Health check response.
 #[derive(Debug, Serialize, Deserialize)]
 struct HealthResponse {
@@ -111,6 +186,27 @@ async fn health_check() -> Json<HealthResponse> {
     })
 }
 
+/// Readiness handler: `503` once the server has started draining.
+async fn readiness_check(
+    axum::extract::State(sessions): axum::extract::State<Arc<crate::sessions::SessionRegistry>>,
+) -> (axum::http::StatusCode, Json<HealthResponse>) {
+    if sessions.is_draining() {
+        (
+            axum::http::StatusCode::SERVICE_UNAVAILABLE,
+            Json(HealthResponse {
+                status: "draining".to_string(),
+            }),
+        )
+    } else {
+        (
+            axum::http::StatusCode::OK,
+            Json(HealthResponse {
+                status: "ready".to_string(),
+            }),
+        )
+    }
+}
+
 /// The main MCP test server.
 ///
 /// This server provides a comprehensive set of tools, prompts, and resources
@@ -123,6 +219,286 @@ pub struct McpTestServer {
     log_level: Arc<std::sync::atomic::AtomicU8>,
     /// Task processor for async long-running operations (MCP Tasks spec).
     processor: Arc<Mutex<OperationProcessor>>,
+    /// Record of argument usage for every `prompts/get` call.
+    prompt_audit: crate::prompts::PromptAuditLog,
+    /// Prompts added at runtime by `register_prompt`.
+    registered_prompts: crate::prompts::registered::RegisteredPrompts,
+    /// `MCP_COMPLETION_RATE_LIMIT` bookkeeping.
+    completion_limiter: Arc<crate::completion::CompletionLimiter>,
+    /// Transport-level connection counters (half-close detection).
+    connection_stats: Arc<crate::transport::ConnectionStats>,
+    /// Per-session SHA-256 log of request bodies (retry auditing).
+    request_log: Arc<crate::dedup::RequestLog>,
+    /// Per-session record of the protocol features clients used.
+    conformance: Arc<crate::conformance::ConformanceLog>,
+    /// Per-session maps of the `kv_*` tools.
+    kv: Arc<crate::tools::kv::KvStore>,
+    /// Server-wide and per-session counters of the `counter_*` tools.
+    counters: Arc<crate::tools::counter::Counters>,
+    /// Mock upstream answering `fetch_url`.
+    upstream: Arc<crate::tools::fetch::MockUpstream>,
+    /// Sequence number for deterministic `random_uuid` values.
+    uuid_counter: Arc<std::sync::atomic::AtomicU64>,
+    /// Live Streamable HTTP sessions (stateful mode).
+    sessions: Arc<crate::sessions::SessionRegistry>,
+    /// Outcomes of server-initiated pings.
+    pings: Arc<crate::pings::PingMonitor>,
+    /// Intermittent tool failures (`MCP_TOOL_ERROR_RATE`).
+    tool_faults: Arc<crate::chaos::ToolFaults>,
+    /// Bound address, published once the server accepts connections.
+    ready: Arc<tokio::sync::watch::Sender<Option<std::net::SocketAddr>>>,
+    /// Resources and prompts registered with [`McpTestServerBuilder`].
+    pub(crate) custom: Arc<crate::custom::CustomRegistry>,
+}
+
+/// A server started with [`McpTestServer::spawn`].
+///
+/// Dropping the handle also starts a graceful shutdown, but does not wait
+/// for it; call [`ServerHandle::shutdown`] to stop deterministically.
+#[derive(Debug)]
+pub struct ServerHandle {
+    addr: std::net::SocketAddr,
+    scheme: &'static str,
+    shutdown: Option<tokio::sync::oneshot::Sender<()>>,
+    task: tokio::task::JoinHandle<anyhow::Result<()>>,
+}
+
+impl ServerHandle {
+    /// The address the server is listening on.
+    #[must_use]
+    pub const fn addr(&self) -> std::net::SocketAddr {
+        self.addr
+    }
+
+    /// URL scheme of the server: `https` with TLS, `http` without.
+    #[must_use]
+    pub const fn scheme(&self) -> &'static str {
+        self.scheme
+    }
+
+    /// URL of the Streamable HTTP endpoint.
+    #[must_use]
+    pub fn mcp_url(&self) -> String {
+        format!("{}://{}/mcp", self.scheme, self.addr)
+    }
+
+    /// Shut the server down and wait until it has stopped.
+    ///
+    /// Cancels open streams and waits for in-flight requests, after the
+    /// drain period if one is configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns the server's runtime error, or an error if its task panicked.
+    pub async fn shutdown(mut self) -> anyhow::Result<()> {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        (&mut self.task).await?
+    }
+}
+
+impl Drop for ServerHandle {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+/// Handler of a tool registered with [`McpTestServerBuilder::with_tool`].
+///
+/// Receives the call's arguments (an empty object when none were sent).
+pub type CustomToolHandler = Arc<
+    dyn Fn(
+            rmcp::model::JsonObject,
+        ) -> futures_util::future::BoxFuture<
+            'static,
+            Result<rmcp::model::CallToolResult, McpError>,
+        > + Send
+        + Sync,
+>;
+
+/// Builds an [`McpTestServer`] with extra tools on top of the built-ins.
+///
+/// ```rust,no_run
+/// use mcp_test_server::{Config, McpTestServer};
+/// use rmcp::model::{CallToolResult, Content};
+/// use serde_json::json;
+///
+/// let server = McpTestServer::builder()
+///     .config(Config::builder().port(0).build())
+///     .with_tool(
+///         "shout",
+///         json!({ "type": "object", "properties": { "text": { "type": "string" } } }),
+///         |args| async move {
+///             let text = args["text"].as_str().unwrap_or_default().to_uppercase();
+///             Ok(CallToolResult::success(vec![Content::text(text)]))
+///         },
+///     )
+///     .build();
+/// ```
+#[derive(Default)]
+pub struct McpTestServerBuilder {
+    pub(crate) config: Config,
+    tools: Vec<ToolRoute<McpTestServer>>,
+    custom: crate::custom::CustomRegistry,
+}
+
+impl McpTestServerBuilder {
+    /// Use `config` instead of the defaults.
+    #[must_use]
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Register a tool named `name` whose input schema is `schema`.
+    ///
+    /// `handler` is called with the arguments of every `tools/call` for
+    /// the tool. A tool with the name of a built-in replaces it. Strict
+    /// argument checks (`MCP_STRICT_ARGS`), lenient names, dry runs, and
+    /// error localization apply as for built-in tools.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `schema` is not a JSON object.
+    #[must_use]
+    pub fn with_tool<F, Fut>(
+        mut self,
+        name: impl Into<String>,
+        schema: serde_json::Value,
+        handler: F,
+    ) -> Self
+    where
+        F: Fn(rmcp::model::JsonObject) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<rmcp::model::CallToolResult, McpError>>
+            + Send
+            + 'static,
+    {
+        let serde_json::Value::Object(schema) = schema else {
+            panic!("tool input schema must be a JSON object, got {schema}");
+        };
+        let handler: CustomToolHandler = Arc::new(move |args| Box::pin(handler(args)));
+        let tool = rmcp::model::Tool::new_with_raw(name.into(), None, Arc::new(schema));
+        self.tools.push(ToolRoute::new_dyn(
+            tool,
+            move |context: ToolCallContext<'_, McpTestServer>| {
+                handler(context.arguments.unwrap_or_default())
+            },
+        ));
+        self
+    }
+
+    /// Register a resource at `uri` whose contents never change.
+    ///
+    /// UTF-8 `contents` are served as text, anything else as a base64 blob.
+    /// A resource with a built-in's URI replaces it.
+    #[must_use]
+    pub fn with_resource(
+        mut self,
+        uri: impl Into<String>,
+        mime_type: impl Into<String>,
+        contents: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.custom
+            .add_static_resource(&uri.into(), mime_type.into(), contents.into());
+        self
+    }
+
+    /// Register a resource at `uri` read by `handler` on every
+    /// `resources/read`; `handler` gets the URI.
+    #[must_use]
+    pub fn with_resource_handler<F, Fut>(
+        mut self,
+        uri: impl Into<String>,
+        mime_type: impl Into<String>,
+        handler: F,
+    ) -> Self
+    where
+        F: Fn(String) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<rmcp::model::ResourceContents, McpError>>
+            + Send
+            + 'static,
+    {
+        let handler: crate::custom::CustomResourceHandler =
+            Arc::new(move |uri, _| Box::pin(handler(uri)));
+        self.custom
+            .add_resource(&uri.into(), mime_type.into(), handler);
+        self
+    }
+
+    /// Register a resource template such as `app://users/{id}`.
+    ///
+    /// `handler` gets the requested URI and the value of each `{variable}`.
+    /// Listed by `resources/templates/list`.
+    #[must_use]
+    pub fn with_resource_template<F, Fut>(
+        mut self,
+        uri_template: impl Into<String>,
+        mime_type: impl Into<String>,
+        handler: F,
+    ) -> Self
+    where
+        F: Fn(String, std::collections::HashMap<String, String>) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<rmcp::model::ResourceContents, McpError>>
+            + Send
+            + 'static,
+    {
+        let handler: crate::custom::CustomResourceHandler =
+            Arc::new(move |uri, variables| Box::pin(handler(uri, variables)));
+        self.custom
+            .add_template(&uri_template.into(), mime_type.into(), handler);
+        self
+    }
+
+    /// Register a prompt named `name` declaring `arguments`.
+    ///
+    /// `handler` gets the arguments once every required one is present, and
+    /// returns the prompt's messages. Calls are recorded in the prompt audit
+    /// log. A prompt with a built-in's name replaces it.
+    #[must_use]
+    pub fn with_prompt<F, Fut>(
+        mut self,
+        name: impl Into<String>,
+        arguments: Vec<rmcp::model::PromptArgument>,
+        handler: F,
+    ) -> Self
+    where
+        F: Fn(std::collections::HashMap<String, String>) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<Vec<rmcp::model::PromptMessage>, McpError>>
+            + Send
+            + 'static,
+    {
+        let handler: crate::custom::CustomPromptHandler =
+            Arc::new(move |arguments| Box::pin(handler(arguments)));
+        self.custom.add_prompt(&name.into(), arguments, handler);
+        self
+    }
+
+    /// Build the server.
+    #[must_use]
+    pub fn build(self) -> McpTestServer {
+        let mut server = McpTestServer::new(self.config);
+        for route in self.tools {
+            server.tool_router.add_route(route);
+        }
+        server.custom = Arc::new(self.custom);
+        server
+    }
+}
+
+impl std::fmt::Debug for McpTestServerBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("McpTestServerBuilder")
+            .field("config", &self.config)
+            .field(
+                "tools",
+                &self.tools.iter().map(ToolRoute::name).collect::<Vec<_>>(),
+            )
+            .field("custom", &self.custom)
+            .finish()
+    }
 }
 
 impl std::fmt::Debug for McpTestServer {
@@ -134,20 +510,57 @@ impl std::fmt::Debug for McpTestServer {
 }
 
 impl McpTestServer {
+    /// Start building a server with custom tools; see [`McpTestServerBuilder`].
+    #[must_use]
+    pub fn builder() -> McpTestServerBuilder {
+        McpTestServerBuilder::default()
+    }
+
     /// Create a new MCP test server with the given configuration.
     #[must_use]
     pub fn new(config: Config) -> Self {
+        let resource_handler = crate::resources::ResourceHandler::new()
+            .with_ticker_interval(std::time::Duration::from_millis(config.ticker_interval_ms))
+            .with_synthetic_resources(config.synthetic_resources)
+            .with_ui_assets(config.ui_assets)
+            .with_ui_csp(config.ui_csp);
         Self {
-            config,
             tool_router: Self::tool_router(),
-            resource_handler: crate::resources::ResourceHandler::new(),
+            resource_handler,
             // Default to Info level (1)
             log_level: Arc::new(std::sync::atomic::AtomicU8::new(1)),
             processor: Arc::new(Mutex::new(OperationProcessor::new())),
+            prompt_audit: crate::prompts::PromptAuditLog::new(),
+            registered_prompts: crate::prompts::registered::RegisteredPrompts::new(),
+            completion_limiter: Arc::new(crate::completion::CompletionLimiter::new(
+                config.completion_rate_limit,
+            )),
+            connection_stats: Arc::new(crate::transport::ConnectionStats::new()),
+            request_log: Arc::new(crate::dedup::RequestLog::new()),
+            conformance: Arc::new(crate::conformance::ConformanceLog::new()),
+            kv: Arc::new(crate::tools::kv::KvStore::new()),
+            counters: Arc::new(crate::tools::counter::Counters::new()),
+            upstream: Arc::new(crate::tools::fetch::MockUpstream::new(
+                config.mock_routes.clone(),
+            )),
+            uuid_counter: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            sessions: Arc::new(
+                crate::sessions::SessionRegistry::new(config.max_sessions)
+                    .with_ttl(config.session_ttl)
+                    .with_delete_denied(config.deny_session_delete),
+            ),
+            pings: Arc::new(crate::pings::PingMonitor::new(config.ping_interval)),
+            tool_faults: Arc::new(crate::chaos::ToolFaults::new(
+                &config.tool_error_rates,
+                config.seed,
+            )),
+            ready: Arc::new(tokio::sync::watch::Sender::new(None)),
+            custom: Arc::default(),
+            config,
         }
     }
 
-    /// Run the server, listening on the configured host and port.
+    /// Run the server, listening on the configured host and port, until Ctrl+C.
     ///
     /// # Errors
     ///
@@ -156,20 +569,125 @@ impl McpTestServer {
     /// # Panics
     ///
     /// Panics if the Ctrl+C signal handler cannot be installed.
-    #[allow(clippy::cognitive_complexity)]
     pub async fn run(&self) -> anyhow::Result<()> {
-        let addr = std::net::SocketAddr::new(self.config.host, self.config.port);
+        self.run_until(async {
+            tokio::signal::ctrl_c()
+                .await
+                .expect("Failed to listen for Ctrl+C");
+        })
+        .await
+    }
+
+    /// Run the server until `signal` completes, then shut down.
+    ///
+    /// With a drain period configured (`MCP_DRAIN_SECS`), shutdown first
+    /// refuses new sessions, notifies live ones, and keeps serving them for
+    /// the drain period.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server fails to bind or encounters a runtime error.
+    pub async fn run_until(
+        &self,
+        signal: impl std::future::Future<Output = ()> + Send + 'static,
+    ) -> anyhow::Result<()> {
+        let listener = self.bind().await?;
+        self.run_with_listener(listener, signal).await
+    }
+
+    /// Start serving in a background task and return a handle to stop it.
+    ///
+    /// Binds the configured host and port (port 0 picks a free one) before
+    /// returning, so the handle's address accepts connections immediately.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address cannot be bound.
+    pub async fn spawn(&self) -> anyhow::Result<ServerHandle> {
+        let listener = self.bind().await?;
+        let addr = listener.local_addr()?;
+        let (shutdown, stopped) = tokio::sync::oneshot::channel::<()>();
+        let server = self.clone();
+        let task = tokio::spawn(async move {
+            server
+                .run_with_listener(listener, async {
+                    let _ = stopped.await;
+                })
+                .await
+        });
+        Ok(ServerHandle {
+            addr,
+            scheme: self.config.scheme(),
+            shutdown: Some(shutdown),
+            task,
+        })
+    }
+
+    /// Serve one client over an in-memory pipe instead of TCP.
+    ///
+    /// Returns the client's end of the pipe. Messages are newline-delimited
+    /// JSON-RPC, as on stdio, so an rmcp client can `serve` the stream
+    /// directly. HTTP-only behavior (authentication, sessions, compat shims,
+    /// SSE limits) does not apply. The connection ends when the client
+    /// shuts down or drops its end.
+    ///
+    /// Must be called from within a Tokio runtime.
+    #[must_use]
+    pub fn connect_in_process(&self) -> tokio::io::DuplexStream {
+        let (client, transport) = tokio::io::duplex(IN_PROCESS_BUFFER_BYTES);
+        let server = self.clone();
+        tokio::spawn(async move {
+            match rmcp::ServiceExt::serve(server, transport).await {
+                Ok(running) => {
+                    let _ = running.waiting().await;
+                }
+                Err(e) => tracing::debug!(error = %e, "In-process client did not initialize"),
+            }
+        });
+        client
+    }
+
+    /// Bind the configured host and port without serving yet.
+    ///
+    /// Port 0 picks a free port; read it with `local_addr()` on the
+    /// listener, then pass the listener to [`McpTestServer::run_with_listener`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address cannot be bound.
+    pub async fn bind(&self) -> std::io::Result<tokio::net::TcpListener> {
+        tokio::net::TcpListener::bind((self.config.host, self.config.port)).await
+    }
+
+    /// Serve on an already bound `listener` until `signal` completes.
+    ///
+    /// The configured host and port are ignored. [`McpTestServer::ready`]
+    /// resolves once the routes are built and connections are served.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the listener's address cannot be read, the TLS
+    /// certificate or key cannot be loaded, or the server encounters a
+    /// runtime error.
+    #[allow(clippy::cognitive_complexity)]
+    pub async fn run_with_listener(
+        &self,
+        listener: tokio::net::TcpListener,
+        signal: impl std::future::Future<Output = ()> + Send + 'static,
+    ) -> anyhow::Result<()> {
+        let addr = listener.local_addr()?;
         tracing::info!(%addr, "Starting MCP Test Server");
 
         // Create cancellation token for graceful shutdown
         let ct = CancellationToken::new();
 
         // Setup Streamable HTTP transport
-        let session_manager = Arc::new(LocalSessionManager::default());
+        let session_manager = self.sessions.manager();
         let streamable_http_config = StreamableHttpServerConfig {
-            sse_keep_alive: Some(std::time::Duration::from_secs(15)),
+            sse_keep_alive: (self.config.sse_keepalive_secs > 0)
+                .then(|| std::time::Duration::from_secs(self.config.sse_keepalive_secs)),
             sse_retry: Some(std::time::Duration::from_secs(3)),
-            stateful_mode: false,
+            stateful_mode: self.config.stateful,
             json_response: false,
             cancellation_token: ct.clone(),
         };
@@ -182,112 +700,591 @@ impl McpTestServer {
             streamable_http_config,
         );
 
-        // Build protected routes with auth middleware
-        let protected_routes = Router::new()
-            .route(
-                "/mcp",
-                axum::routing::get_service(streamable_http_service.clone()),
-            )
-            .route(
-                "/mcp",
-                axum::routing::post_service(streamable_http_service.clone()),
-            )
-            .route(
-                "/mcp",
-                axum::routing::delete_service(streamable_http_service),
-            )
-            .layer(middleware::from_fn_with_state(
-                self.config.clone(),
-                auth_middleware,
-            ));
-
         // Build OAuth mock endpoints (public — these ARE the auth provider)
-        let oauth_state = crate::oauth::OAuthState::new(format!("http://{addr}"));
+        let oauth_state =
+            crate::oauth::OAuthState::new(format!("{}://{addr}", self.config.scheme()))
+                .with_failures(self.config.oauth_failures.iter().copied())
+                .with_public_url(self.config.public_url.clone());
+        let protected_routes =
+            self.protected_routes(streamable_http_service, oauth_state.clone())?;
         let oauth_routes = crate::oauth::oauth_router(oauth_state);
 
         // Build the main router combining public, OAuth, and protected routes
-        let app = Router::new()
+        let mut app = Router::new()
             .route("/health", get(health_check))
+            .route(
+                "/readyz",
+                get(readiness_check).with_state(Arc::clone(&self.sessions)),
+            )
+            .merge(self.hosted_app_routes())
             .merge(oauth_routes)
-            .merge(protected_routes)
-            .layer(CorsLayer::permissive());
+            .merge(protected_routes);
+        if let Some(cors) = self.config.cors.layer() {
+            app = app.layer(cors);
+        }
+        let app = self.config.compression.layer(app);
 
-        // Bind TCP listener
-        let listener = tokio::net::TcpListener::bind(addr).await?;
-        tracing::info!(%addr, "Server listening on Streamable HTTP (/mcp) transport");
+        let listener =
+            crate::transport::TrackedListener::new(listener, Arc::clone(&self.connection_stats));
+        let acceptor = match (&self.config.tls_cert, &self.config.tls_key) {
+            (Some(cert), Some(key)) => Some(crate::transport::tls_acceptor(cert, key)?),
+            _ => None,
+        };
+        tracing::info!(
+            %addr,
+            scheme = self.config.scheme(),
+            "Server listening on Streamable HTTP (/mcp) transport"
+        );
+        self.ready.send_replace(Some(addr));
 
         // Setup graceful shutdown
         let shutdown_ct = ct.clone();
+        let sessions = Arc::clone(&self.sessions);
+        let drain_period = self.config.drain_period;
         let shutdown = async move {
-            tokio::signal::ctrl_c()
-                .await
-                .expect("Failed to listen for Ctrl+C");
+            signal.await;
+            if let Some(drain) = drain_period {
+                sessions.start_draining();
+                let notified = sessions.notify_shutdown(drain).await;
+                tracing::info!(
+                    drain_secs = drain.as_secs(),
+                    notified,
+                    "Shutdown signal received, refusing new sessions while draining"
+                );
+                tokio::time::sleep(drain).await;
+            }
             tracing::info!("Shutdown signal received, draining connections...");
             shutdown_ct.cancel();
         };
 
         // Run the server with graceful shutdown
-        axum::serve(listener, app)
-            .with_graceful_shutdown(shutdown)
-            .await?;
+        if let Some(acceptor) = acceptor {
+            let listener = crate::transport::TlsListener::new(listener, acceptor);
+            crate::transport::serve(listener, app, shutdown).await;
+        } else {
+            crate::transport::serve(listener, app, shutdown).await;
+        }
 
         tracing::info!("Server shutdown complete");
         Ok(())
     }
 
+    /// Build the routes that sit behind authentication: `/mcp` and diagnostics.
+    fn protected_routes(
+        &self,
+        mcp: StreamableHttpService<Self, LocalSessionManager>,
+        oauth_state: crate::oauth::OAuthState,
+    ) -> anyhow::Result<Router> {
+        let mut auth_config = self.config.clone();
+        if self.config.oauth && auth_config.api_key.take().is_some() {
+            tracing::warn!("MCP_OAUTH is enabled; ignoring MCP_API_KEY");
+        }
+
+        let mut routes = self
+            .mcp_routes(mcp)?
+            .layer(middleware::from_fn(crate::supervision::abort_middleware))
+            .layer(middleware::from_fn_with_state(
+                Arc::clone(&self.request_log),
+                crate::dedup::request_log_middleware,
+            ))
+            .layer(middleware::from_fn_with_state(
+                Arc::clone(&self.conformance),
+                crate::conformance::conformance_middleware,
+            ));
+        if self.config.stateful {
+            routes = routes.layer(middleware::from_fn_with_state(
+                Arc::clone(&self.sessions),
+                crate::sessions::session_middleware,
+            ));
+        }
+        routes = routes.merge(self.debug_routes()).merge(self.admin_routes());
+        if self.config.oauth {
+            routes = routes.layer(middleware::from_fn_with_state(
+                oauth_state,
+                crate::oauth::bearer_auth_middleware,
+            ));
+        }
+        routes = routes.layer(middleware::from_fn_with_state(auth_config, auth_middleware));
+        if let Some(max_bytes) = self.config.max_sse_event_bytes {
+            routes = routes.layer(middleware::from_fn_with_state(
+                crate::sse::SseEventLimit {
+                    max_bytes,
+                    policy: self.config.sse_oversize_policy,
+                },
+                crate::sse::event_size_middleware,
+            ));
+        }
+        if let Some(chunk_bytes) = self.config.sse_drip_bytes {
+            routes = routes.layer(middleware::from_fn_with_state(
+                crate::sse::SseDrip {
+                    chunk_bytes,
+                    delay: self.config.sse_drip_delay,
+                },
+                crate::sse::drip_middleware,
+            ));
+        }
+        Ok(routes)
+    }
+
+    /// Build `/mcp` itself, served by rmcp or a replay, with the middleware
+    /// that applies to it alone.
+    fn mcp_routes(
+        &self,
+        mcp: StreamableHttpService<Self, LocalSessionManager>,
+    ) -> anyhow::Result<Router> {
+        let mut mcp_routes = if let Some(path) = &self.config.replay {
+            let replay = crate::replay::Replay::load(path)
+                .map_err(|e| anyhow::anyhow!("failed to load recording {}: {e}", path.display()))?;
+            tracing::info!(path = %path.display(), "Replaying recorded exchanges on /mcp");
+            Router::new().route(
+                "/mcp",
+                axum::routing::post(crate::replay::replay_handler).with_state(Arc::new(replay)),
+            )
+        } else {
+            Router::new()
+                .route("/mcp", axum::routing::get_service(mcp.clone()))
+                .route("/mcp", axum::routing::post_service(mcp.clone()))
+                .route("/mcp", axum::routing::delete_service(mcp))
+        };
+        if self.config.strict_protocol_version {
+            let server_version = self.config.compat.map_or(
+                ProtocolVersion::LATEST,
+                crate::compat::CompatTarget::protocol_version,
+            );
+            mcp_routes = mcp_routes.layer(middleware::from_fn_with_state(
+                Arc::new(crate::protocol_header::NegotiatedVersions::new(
+                    &server_version,
+                    Arc::clone(&self.sessions),
+                )),
+                crate::protocol_header::strict_protocol_version_middleware,
+            ));
+        }
+        if self.config.unknown_notification == crate::unknown::UnknownNotificationPolicy::Error {
+            // Inside compat, so legacy notification names are upgraded first
+            mcp_routes = mcp_routes.layer(middleware::from_fn(
+                crate::unknown::unknown_notification_middleware,
+            ));
+        }
+        if let Some(target) = self.config.compat {
+            // Inside the duplicate log, so it still sees the client's bytes
+            mcp_routes = mcp_routes.layer(middleware::from_fn_with_state(
+                target,
+                crate::compat::compat_middleware,
+            ));
+        }
+        if let Some(path) = &self.config.record {
+            // Outside compat, so the recording holds what the client saw
+            let recorder = crate::replay::Recorder::create(path).map_err(|e| {
+                anyhow::anyhow!("failed to create recording {}: {e}", path.display())
+            })?;
+            tracing::info!(path = %path.display(), "Recording exchanges on /mcp");
+            mcp_routes = mcp_routes.layer(middleware::from_fn_with_state(
+                Arc::new(recorder),
+                crate::replay::record_middleware,
+            ));
+        }
+        if !self.config.chaos_responses.is_empty() {
+            mcp_routes = mcp_routes.layer(middleware::from_fn_with_state(
+                Arc::new(crate::chaos::ChaosState::new(
+                    self.config.chaos_responses.clone(),
+                    self.config.chaos_rate,
+                    self.config.seed,
+                )),
+                crate::chaos::response_chaos_middleware,
+            ));
+        }
+        // Outermost, so each message of a batch passes everything above
+        let batches = crate::batch::Batches {
+            policy: self.config.batch,
+            inner: mcp_routes.clone(),
+        };
+        Ok(mcp_routes.layer(middleware::from_fn_with_state(
+            batches,
+            crate::batch::batch_middleware,
+        )))
+    }
+
+    /// Build the `/admin/*` fixture setup routes.
+    fn admin_routes(&self) -> Router {
+        Router::new()
+            .route(
+                "/admin/tools/batch",
+                axum::routing::post(crate::admin::batch_tools),
+            )
+            .route(
+                "/admin/flags",
+                get(crate::admin::get_flags).patch(crate::admin::update_flags),
+            )
+            .route("/admin/sessions", get(crate::admin::list_sessions))
+            .route(
+                "/admin/sessions/events",
+                get(crate::admin::session_events).delete(crate::admin::clear_session_events),
+            )
+            .route(
+                "/admin/sessions/{id}",
+                axum::routing::delete(crate::admin::terminate_session),
+            )
+            .route("/admin/pings", get(crate::admin::ping_report))
+            .route(
+                "/admin/subscriptions",
+                get(crate::admin::subscription_report),
+            )
+            .route(
+                "/admin/sessions/{id}/flood",
+                axum::routing::post(crate::admin::flood_session),
+            )
+            .route(
+                "/admin/conformance/{session}",
+                get(crate::admin::conformance_report),
+            )
+            .with_state(self.clone())
+    }
+
+    /// Build the public `/apps/{app}` routes if `MCP_HOSTED_APPS` is set.
+    fn hosted_app_routes(&self) -> Router {
+        if !self.config.hosted_apps {
+            return Router::new();
+        }
+        Router::new().route(
+            "/apps/{app}",
+            get(crate::resources::ui_csp::hosted_app).with_state(
+                crate::resources::ui_csp::HostedApps {
+                    csp: self.config.ui_csp,
+                    assets: self.config.ui_assets,
+                },
+            ),
+        )
+    }
+
+    /// Build the `/debug/*` diagnostics routes.
+    fn debug_routes(&self) -> Router {
+        Router::new()
+            .route(
+                "/debug/connections",
+                get({
+                    let stats = Arc::clone(&self.connection_stats);
+                    move || async move { Json(stats.snapshot()) }
+                }),
+            )
+            .route(
+                "/debug/headers",
+                axum::routing::any(crate::echo::debug_headers),
+            )
+            .route(
+                "/debug/duplicates",
+                get({
+                    let log = Arc::clone(&self.request_log);
+                    move || async move { Json(log.report()) }
+                })
+                .delete({
+                    let log = Arc::clone(&self.request_log);
+                    move || async move {
+                        log.clear();
+                        axum::http::StatusCode::NO_CONTENT
+                    }
+                }),
+            )
+    }
+
     /// Get the server configuration.
     #[must_use]
     pub const fn config(&self) -> &Config {
         &self.config
     }
-}
 
-/// Tool router implementation for aggregating tools.
-#[tool_router]
-impl McpTestServer {
-    // Math tools
+    /// Get the resource handler.
+    #[must_use]
+    pub const fn resource_handler(&self) -> &crate::resources::ResourceHandler {
+        &self.resource_handler
+    }
 
-    /// Add two numbers together.
-    #[tool(description = "Add two numbers together")]
-    async fn add(&self, Parameters(params): Parameters<AddParams>) -> String {
-        let result = params.a + params.b;
-        result.to_string()
+    /// Get the live session registry.
+    #[must_use]
+    pub const fn sessions(&self) -> &Arc<crate::sessions::SessionRegistry> {
+        &self.sessions
     }
 
-    /// Subtract two numbers.
-    #[tool(description = "Subtract second number from first number")]
-    async fn subtract(&self, Parameters(params): Parameters<SubtractParams>) -> String {
-        let result = params.a - params.b;
-        result.to_string()
+    /// Get the outcomes of server-initiated pings.
+    #[must_use]
+    pub const fn pings(&self) -> &Arc<crate::pings::PingMonitor> {
+        &self.pings
     }
 
-    /// Multiply two numbers.
-    #[tool(description = "Multiply two numbers together")]
-    async fn multiply(&self, Parameters(params): Parameters<MultiplyParams>) -> String {
-        let result = params.a * params.b;
-        result.to_string()
+    /// The address the server is serving on, once it is ready.
+    #[must_use]
+    pub fn local_addr(&self) -> Option<std::net::SocketAddr> {
+        *self.ready.borrow()
     }
 
-    /// Divide two numbers with zero check.
-    #[tool(description = "Divide first number by second number")]
-    async fn divide(&self, Parameters(params): Parameters<DivideParams>) -> Result<String, String> {
-        if params.b == 0.0 {
-            return Err("Division by zero".to_string());
+    /// Wait until the server serves connections and return its address.
+    ///
+    /// Works on any clone of the server, so spawn `run()` on one clone and
+    /// await `ready()` on another instead of sleeping.
+    pub async fn ready(&self) -> std::net::SocketAddr {
+        let mut ready = self.ready.subscribe();
+        loop {
+            let addr = *ready.borrow_and_update();
+            if let Some(addr) = addr {
+                return addr;
+            }
+            // `self` holds the sender, so the channel cannot close while waiting
+            let _ = ready.changed().await;
         }
-        let result = params.a / params.b;
-        Ok(result.to_string())
     }
 
-    // String tools
-
-    /// Echo text back to the caller.
-    #[tool(description = "Echo the input text back")]
-    async fn echo(&self, Parameters(params): Parameters<EchoParams>) -> String {
-        params.text
+    /// Resolve a tool name leniently (ignoring case and surrounding whitespace).
+    ///
+    /// Returns `None` if the name already matches exactly or matches nothing.
+    fn resolve_tool_name(&self, requested: &str) -> Option<String> {
+        if self.tool_router.has_route(requested) {
+            return None;
+        }
+        let tools = self.tool_router.list_all();
+        crate::validation::resolve_lenient(requested, tools.iter().map(|t| t.name.as_ref()))
+            .map(str::to_string)
     }
 
-    /// Concatenate multiple strings.
-    #[tool(description = "Concatenate multiple strings together")]
+    /// Resolve a resource URI leniently (ignoring case and surrounding whitespace).
+    ///
+    /// Candidates are the resources `resources/list` returns (built-in,
+    /// scratch, and builder resources), then the templates
+    /// `resources/templates/list` returns, whose variable values are kept as
+    /// sent. Returns `None` if the URI matches none of them.
+    fn resolve_resource_uri(&self, requested: &str) -> Option<String> {
+        let resources = self
+            .custom
+            .merge_resources(self.resource_handler.list_resources(None).ok()?.resources);
+        if let Some(uri) =
+            crate::validation::resolve_lenient(requested, resources.iter().map(|r| r.uri.as_str()))
+        {
+            return Some(uri.to_string());
+        }
+        let templates = self.custom.merge_templates(
+            self.resource_handler
+                .list_resource_templates(None)
+                .ok()?
+                .resource_templates,
+        );
+        templates.iter().find_map(|template| {
+            crate::custom::respell_template_uri(&template.uri_template, requested.trim())
+        })
+    }
+
+    /// Read the slow or flaky resource: wait out the delay (unless the
+    /// request is cancelled) or fail at the rate.
+    /// Wait before `flaky_prompt` fails, as its `slow_failure` mode asks,
+    /// unless the request is cancelled first.
+    async fn delay_prompt_failure(
+        &self,
+        request: &rmcp::model::GetPromptRequestParams,
+        context: &rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) {
+        let _ = self;
+        let arguments = crate::prompts::convert_json_args(request.arguments.clone());
+        let Ok(failure) = crate::prompts::templates::FlakyPromptFailure::from_arguments(&arguments)
+        else {
+            return;
+        };
+        tokio::select! {
+            () = context.ct.cancelled() => {}
+            () = tokio::time::sleep(failure.delay()) => {}
+        }
+    }
+
+    async fn read_unreliable(
+        &self,
+        uri: &str,
+        read: Result<crate::resources::dynamic_resources::UnreliableRead, String>,
+        context: &rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<ReadResourceResult, rmcp::ErrorData> {
+        use crate::resources::dynamic_resources::{UnreliableRead, get_unreliable_content};
+
+        let read = read.map_err(|e| rmcp::ErrorData::invalid_params(e, None))?;
+        match read {
+            UnreliableRead::Slow(delay) => {
+                tokio::select! {
+                    () = context.ct.cancelled() => {
+                        return Err(rmcp::ErrorData::internal_error(
+                            format!("Read of {uri} cancelled"),
+                            None,
+                        ));
+                    }
+                    () = tokio::time::sleep(delay) => {}
+                }
+            }
+            UnreliableRead::Flaky(rate) => {
+                if rand::rng().random_bool(rate) {
+                    return Err(rmcp::ErrorData::resource_not_found(
+                        format!("Resource temporarily unavailable: {uri}"),
+                        Some(serde_json::json!({ "uri": uri })),
+                    ));
+                }
+            }
+        }
+        Ok(ReadResourceResult::new(vec![get_unreliable_content(
+            uri, read,
+        )]))
+    }
+
+    /// The RNG seed for a call: the `requested` one, the server seed, or a
+    /// random seed.
+    fn resolve_seed(&self, requested: Option<u64>) -> u64 {
+        requested
+            .or(self.config.seed)
+            .unwrap_or_else(|| rand::rng().random())
+    }
+
+    /// Dispatch a tool call after lenient name resolution and strict
+    /// argument checks, then apply the response size cap. The `_meta.dryRun`
+    /// flag is passed to tools as an `Extension<DryRun>`.
+    async fn call_tool_impl(
+        &self,
+        mut request: rmcp::model::CallToolRequestParams,
+        mut context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<rmcp::model::CallToolResult, rmcp::ErrorData> {
+        let dry_run = request
+            .meta
+            .as_ref()
+            .map_or_else(|| DryRun::from_meta(&context.meta), DryRun::from_meta);
+        context.extensions.insert(dry_run);
+        if self.config.lenient_names
+            && let Some(name) = self.resolve_tool_name(&request.name)
+        {
+            request.name = name.into();
+        }
+        if self.config.unknown_tool == crate::unknown::UnknownToolPolicy::Result
+            && self.tool_router.get(&request.name).is_none()
+        {
+            return Ok(crate::unknown::unknown_tool_result(&request.name));
+        }
+        if self.config.strict_args
+            && let Some(tool) = self.tool_router.get(&request.name)
+        {
+            crate::validation::reject_unknown_arguments(tool, request.arguments.as_ref())?;
+        }
+        if self.tool_faults.should_fail(&request.name) {
+            tracing::info!(tool = %request.name, "Injecting tool failure");
+            return Ok(rmcp::model::CallToolResult::error(vec![Content::text(
+                format!(
+                    "Injected failure of {} (MCP_TOOL_ERROR_RATE); retry the call",
+                    request.name
+                ),
+            )]));
+        }
+        let schema_check = (self.config.schema_errors
+            != crate::validation::SchemaErrorDetail::Opaque)
+            .then(|| self.tool_router.get(&request.name).cloned())
+            .flatten()
+            .map(|tool| (tool, request.arguments.clone()));
+        let tcc = ToolCallContext::new(self, request, context);
+        let result = self
+            .tool_router
+            .call(tcc)
+            .await
+            .map_err(|e| match &schema_check {
+                Some((tool, arguments)) => crate::validation::explain_invalid_params(
+                    e,
+                    tool,
+                    arguments.as_ref(),
+                    self.config.schema_errors,
+                ),
+                None => e,
+            })?;
+        if let Some(limit) = self.config.max_response_bytes {
+            crate::validation::reject_oversized_result(&result, limit)?;
+        }
+        Ok(result)
+    }
+
+    /// Get the transport-level connection counters.
+    #[must_use]
+    pub const fn connection_stats(&self) -> &Arc<crate::transport::ConnectionStats> {
+        &self.connection_stats
+    }
+
+    /// Get the per-session request body log.
+    #[must_use]
+    pub const fn request_log(&self) -> &Arc<crate::dedup::RequestLog> {
+        &self.request_log
+    }
+
+    /// Get the per-session protocol coverage log.
+    #[must_use]
+    pub const fn conformance(&self) -> &Arc<crate::conformance::ConformanceLog> {
+        &self.conformance
+    }
+
+    /// Get the prompt argument audit log.
+    #[must_use]
+    pub const fn prompt_audit_log(&self) -> &crate::prompts::PromptAuditLog {
+        &self.prompt_audit
+    }
+
+    /// Get the prompts registered at runtime by `register_prompt`.
+    #[must_use]
+    pub const fn registered_prompts(&self) -> &crate::prompts::registered::RegisteredPrompts {
+        &self.registered_prompts
+    }
+}
+
+/// Tool router implementation for aggregating tools.
+#[tool_router]
+impl McpTestServer {
+    // Math tools
+
+    /// Add two numbers together.
+    #[tool(description = "Add two numbers together")]
+    async fn add(&self, Parameters(params): Parameters<AddParams>) -> String {
+        let result = params.a + params.b;
+        result.to_string()
+    }
+
+    /// Subtract two numbers.
+    #[tool(description = "Subtract second number from first number")]
+    async fn subtract(&self, Parameters(params): Parameters<SubtractParams>) -> String {
+        let result = params.a - params.b;
+        result.to_string()
+    }
+
+    /// Multiply two numbers.
+    #[tool(description = "Multiply two numbers together")]
+    async fn multiply(&self, Parameters(params): Parameters<MultiplyParams>) -> String {
+        let result = params.a * params.b;
+        result.to_string()
+    }
+
+    /// Divide two numbers with zero check.
+    #[tool(description = "Divide first number by second number")]
+    async fn divide(&self, Parameters(params): Parameters<DivideParams>) -> Result<String, String> {
+        if params.b == 0.0 {
+            return Err("Division by zero".to_string());
+        }
+        let result = params.a / params.b;
+        Ok(result.to_string())
+    }
+
+    /// Summary statistics over an array of numbers.
+    #[tool(
+        description = "Summary statistics of a number array: count, min, max, sum, mean, median, variance, stddev, and percentiles"
+    )]
+    async fn stats(
+        &self,
+        Parameters(params): Parameters<StatsParams>,
+    ) -> Result<rmcp::handler::server::wrapper::Json<Stats>, String> {
+        crate::tools::math::stats(&params.values, &params.percentiles, params.sample)
+            .map(rmcp::handler::server::wrapper::Json)
+    }
+
+    // String tools
+
+    /// Echo text back to the caller.
+    #[tool(description = "Echo the input text back")]
+    async fn echo(&self, Parameters(params): Parameters<EchoParams>) -> String {
+        params.text
+    }
+
+    /// Concatenate multiple strings.
+    #[tool(description = "Concatenate multiple strings together")]
     async fn concat(&self, Parameters(params): Parameters<ConcatParams>) -> String {
         params.strings.join("")
     }
@@ -316,6 +1313,54 @@ impl McpTestServer {
         params.text.len().to_string()
     }
 
+    /// Codepoints, graphemes, and normalization forms of a string.
+    #[tool(
+        description = "Unicode details of text: byte, codepoint, and grapheme counts, each codepoint as U+XXXX, and the NFC/NFD/NFKC/NFKD forms"
+    )]
+    async fn unicode_info(
+        &self,
+        Parameters(params): Parameters<UnicodeInfoParams>,
+    ) -> rmcp::handler::server::wrapper::Json<UnicodeInfo> {
+        rmcp::handler::server::wrapper::Json(crate::tools::string::unicode_info(&params.text))
+    }
+
+    /// Count words, lines, and graphemes.
+    #[tool(
+        description = "Count the words (Unicode word boundaries), lines, graphemes, and bytes of text"
+    )]
+    async fn word_count(
+        &self,
+        Parameters(params): Parameters<WordCountParams>,
+    ) -> rmcp::handler::server::wrapper::Json<WordCount> {
+        rmcp::handler::server::wrapper::Json(crate::tools::string::word_count(&params.text))
+    }
+
+    // Array tools — array-of-any parameters
+
+    /// Sort an array of numbers or strings.
+    #[tool(
+        description = "Stable sort of a JSON array of numbers or of strings, or of objects by a key field, ascending or descending"
+    )]
+    async fn sort_array(
+        &self,
+        Parameters(params): Parameters<SortArrayParams>,
+    ) -> Result<rmcp::handler::server::wrapper::Json<SortedArray>, String> {
+        crate::tools::array::sort(params.values, params.order, params.key.as_deref())
+            .map(rmcp::handler::server::wrapper::Json)
+    }
+
+    /// Remove duplicate values from an array.
+    #[tool(
+        description = "Remove duplicates from a JSON array of any values, keeping first occurrences in order; optionally compare objects by a key field"
+    )]
+    async fn dedup_array(
+        &self,
+        Parameters(params): Parameters<DedupArrayParams>,
+    ) -> Result<rmcp::handler::server::wrapper::Json<DedupedArray>, String> {
+        crate::tools::array::dedup(params.values, params.key.as_deref())
+            .map(rmcp::handler::server::wrapper::Json)
+    }
+
     // Encoding tools
 
     /// Parse a JSON string into a value.
@@ -359,10 +1404,72 @@ impl McpTestServer {
     /// Generate SHA-256 hash of text.
     #[tool(description = "Generate SHA-256 hash of text")]
     async fn hash_sha256(&self, Parameters(params): Parameters<HashSha256Params>) -> String {
-        let mut hasher = Sha256::new();
-        hasher.update(params.text.as_bytes());
-        let result = hasher.finalize();
-        format!("{result:x}")
+        digest(HashAlgorithm::Sha256, &params.text)
+    }
+
+    /// Generate MD5 hash of text.
+    #[tool(description = "Generate MD5 hash of text")]
+    async fn hash_md5(&self, Parameters(params): Parameters<HashMd5Params>) -> String {
+        digest(HashAlgorithm::Md5, &params.text)
+    }
+
+    /// Generate SHA-1 hash of text.
+    #[tool(description = "Generate SHA-1 hash of text")]
+    async fn hash_sha1(&self, Parameters(params): Parameters<HashSha1Params>) -> String {
+        digest(HashAlgorithm::Sha1, &params.text)
+    }
+
+    /// Generate SHA-512 hash of text.
+    #[tool(description = "Generate SHA-512 hash of text")]
+    async fn hash_sha512(&self, Parameters(params): Parameters<HashSha512Params>) -> String {
+        digest(HashAlgorithm::Sha512, &params.text)
+    }
+
+    /// Generate CRC-32 checksum of text.
+    #[tool(description = "Generate CRC-32 checksum of text, as 8 hex digits")]
+    async fn hash_crc32(&self, Parameters(params): Parameters<HashCrc32Params>) -> String {
+        digest(HashAlgorithm::Crc32, &params.text)
+    }
+
+    /// Hash a string with a chosen algorithm.
+    #[tool(
+        description = "Hash text with md5, sha1, sha256, sha512, or crc32; returns lowercase hex"
+    )]
+    async fn hash(&self, Parameters(params): Parameters<HashParams>) -> String {
+        digest(params.algorithm, &params.text)
+    }
+
+    /// Compute HMAC-SHA256 of a string.
+    #[tool(description = "Compute HMAC-SHA256 of text under a secret key; returns lowercase hex")]
+    async fn hmac_sha256(&self, Parameters(params): Parameters<HmacSha256Params>) -> String {
+        crate::tools::encoding::hmac_sha256(&params.key, &params.text)
+    }
+
+    /// Percent-encode a string.
+    #[tool(description = "Percent-encode text for a URL, or as an HTML form value with form: true")]
+    async fn url_encode(&self, Parameters(params): Parameters<UrlEncodeParams>) -> String {
+        crate::tools::encoding::url_encode(&params.text, params.form)
+    }
+
+    /// Decode a percent-encoded string.
+    #[tool(description = "Decode percent-encoded text, or an HTML form value with form: true")]
+    async fn url_decode(
+        &self,
+        Parameters(params): Parameters<UrlDecodeParams>,
+    ) -> Result<String, String> {
+        crate::tools::encoding::url_decode(&params.encoded, params.form)
+    }
+
+    /// Split a URL into its components.
+    #[tool(
+        description = "Parse a URL into scheme, credentials, host, port, path, query parameters, and fragment"
+    )]
+    async fn url_parse(
+        &self,
+        Parameters(params): Parameters<UrlParseParams>,
+    ) -> Result<rmcp::handler::server::wrapper::Json<crate::tools::encoding::ParsedUrl>, String>
+    {
+        crate::tools::encoding::parse_url(&params.url).map(rmcp::handler::server::wrapper::Json)
     }
 
     // Utility tools
@@ -384,7 +1491,17 @@ impl McpTestServer {
     /// Generate a random UUID v4.
     #[tool(description = "Generate a random UUID v4")]
     async fn random_uuid(&self, Parameters(_params): Parameters<RandomUuidParams>) -> String {
-        let uuid = uuid::Uuid::new_v4();
+        // With a namespace configured, derive v5 UUIDs from a counter so
+        // snapshot tests see the same sequence on every run
+        let uuid = self
+            .config
+            .uuid_namespace
+            .map_or_else(uuid::Uuid::new_v4, |namespace| {
+                let n = self
+                    .uuid_counter
+                    .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                uuid::Uuid::new_v5(&namespace, n.to_string().as_bytes())
+            });
         uuid.to_string()
     }
 
@@ -395,6 +1512,640 @@ impl McpTestServer {
         now.to_rfc3339()
     }
 
+    /// Generate a random string from a character set.
+    #[tool(
+        description = "Generate a random string of a given length from a charset (alphanumeric, alphabetic, numeric, hex, password) or custom chars. The same seed always gives the same string."
+    )]
+    async fn random_string(
+        &self,
+        Parameters(params): Parameters<RandomStringParams>,
+    ) -> Result<rmcp::handler::server::wrapper::Json<RandomString>, String> {
+        crate::tools::utility::random_string(
+            params.length,
+            params.charset,
+            params.chars.as_deref(),
+            self.resolve_seed(params.seed),
+        )
+        .map(rmcp::handler::server::wrapper::Json)
+    }
+
+    /// Roll dice in `NdM` notation.
+    #[tool(
+        description = "Roll dice in NdM notation with an optional modifier (3d6, d20, 2d8+3). The same seed always gives the same rolls."
+    )]
+    async fn roll_dice(
+        &self,
+        Parameters(params): Parameters<RollDiceParams>,
+    ) -> Result<rmcp::handler::server::wrapper::Json<DiceRoll>, String> {
+        crate::tools::utility::roll_dice(&params.notation, self.resolve_seed(params.seed))
+            .map(rmcp::handler::server::wrapper::Json)
+    }
+
+    /// Pick random items from a list.
+    #[tool(
+        description = "Pick one or more random items from a list, optionally without replacement. The same seed always gives the same picks."
+    )]
+    async fn random_choice(
+        &self,
+        Parameters(params): Parameters<RandomChoiceParams>,
+    ) -> Result<rmcp::handler::server::wrapper::Json<RandomChoice>, String> {
+        crate::tools::utility::random_choice(
+            &params.items,
+            params.count,
+            params.unique,
+            self.resolve_seed(params.seed),
+        )
+        .map(rmcp::handler::server::wrapper::Json)
+    }
+
+    // Key-value tools — per-session state with observable side effects
+
+    /// Store a value in this session's key-value map.
+    #[tool(
+        description = "Store a string value under a key in this session's key-value store; returns the previous value"
+    )]
+    async fn kv_set(
+        &self,
+        Parameters(params): Parameters<KvSetParams>,
+        Extension(dry_run): Extension<DryRun>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let session = crate::sessions::session_key(context.extensions.get());
+        if dry_run.enabled() {
+            let previous = self.kv.get(session, &params.key);
+            return Ok(crate::dry_run::preview(
+                &format!("set key {:?} to {:?}", params.key, params.value),
+                kv::set_result(&params.key, &params.value, previous.as_deref()),
+            ));
+        }
+        Ok(
+            match self.kv.set(session, &params.key, params.value.clone()) {
+                Ok(previous) => CallToolResult::success(vec![Content::text(
+                    kv::set_result(&params.key, &params.value, previous.as_deref()).to_string(),
+                )]),
+                Err(e) => CallToolResult::error(vec![Content::text(e)]),
+            },
+        )
+    }
+
+    /// Read a value from this session's key-value map.
+    #[tool(description = "Get the value stored under a key in this session's key-value store")]
+    async fn kv_get(
+        &self,
+        Parameters(params): Parameters<KvGetParams>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<String, String> {
+        let session = crate::sessions::session_key(context.extensions.get());
+        self.kv
+            .get(session, &params.key)
+            .ok_or_else(|| format!("Key not found: {}", params.key))
+    }
+
+    /// Delete a key from this session's key-value map.
+    #[tool(
+        description = "Delete a key from this session's key-value store; returns whether it existed"
+    )]
+    async fn kv_delete(
+        &self,
+        Parameters(params): Parameters<KvDeleteParams>,
+        Extension(dry_run): Extension<DryRun>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let session = crate::sessions::session_key(context.extensions.get());
+        if dry_run.enabled() {
+            let previous = self.kv.get(session, &params.key);
+            return Ok(crate::dry_run::preview(
+                &format!("delete key {:?}", params.key),
+                kv::delete_result(&params.key, previous.as_deref()),
+            ));
+        }
+        let previous = self.kv.delete(session, &params.key);
+        Ok(CallToolResult::success(vec![Content::text(
+            kv::delete_result(&params.key, previous.as_deref()).to_string(),
+        )]))
+    }
+
+    /// List entries of this session's key-value map.
+    #[tool(
+        description = "List the entries of this session's key-value store, sorted by key, optionally filtered by key prefix"
+    )]
+    async fn kv_list(
+        &self,
+        Parameters(params): Parameters<KvListParams>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> String {
+        let session = crate::sessions::session_key(context.extensions.get());
+        let entries = self
+            .kv
+            .list(session, params.prefix.as_deref().unwrap_or_default());
+        serde_json::json!({ "count": entries.len(), "entries": entries }).to_string()
+    }
+
+    // Counter tools — exactly-once, in-order delivery and session isolation
+
+    /// Increment the session or server-wide counter.
+    #[tool(
+        description = "Add one to this session's counter (scope: session, the default) or the counter shared by all sessions (scope: server); returns the new value"
+    )]
+    async fn counter_increment(
+        &self,
+        Parameters(params): Parameters<CounterIncrementParams>,
+        Extension(dry_run): Extension<DryRun>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let session = crate::sessions::session_key(context.extensions.get());
+        if dry_run.enabled() {
+            let value = self.counters.get(params.scope, session);
+            return Ok(crate::dry_run::preview(
+                &format!("increment the {} counter", params.scope.as_str()),
+                counter::counter_result(params.scope, value, value.saturating_add(1)),
+            ));
+        }
+        let value = self.counters.increment(params.scope, session);
+        Ok(CallToolResult::success(vec![Content::text(
+            counter::counter_result(params.scope, value - 1, value).to_string(),
+        )]))
+    }
+
+    /// Reset the session or server-wide counter to zero.
+    #[tool(
+        description = "Reset this session's counter (scope: session, the default) or the server-wide counter (scope: server) to zero; returns the previous value"
+    )]
+    async fn counter_reset(
+        &self,
+        Parameters(params): Parameters<CounterResetParams>,
+        Extension(dry_run): Extension<DryRun>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let session = crate::sessions::session_key(context.extensions.get());
+        if dry_run.enabled() {
+            let value = self.counters.get(params.scope, session);
+            return Ok(crate::dry_run::preview(
+                &format!("reset the {} counter", params.scope.as_str()),
+                counter::counter_result(params.scope, value, 0),
+            ));
+        }
+        let previous = self.counters.reset(params.scope, session);
+        Ok(CallToolResult::success(vec![Content::text(
+            counter::counter_result(params.scope, previous, 0).to_string(),
+        )]))
+    }
+
+    // Scratch resource tools — client-written resources with notifications
+
+    /// Create or replace a `test://scratch/{name}` resource.
+    #[tool(
+        description = "Create or replace the resource test://scratch/{name}; notifies of list changes and updates"
+    )]
+    async fn resource_write(
+        &self,
+        Parameters(params): Parameters<ResourceWriteParams>,
+        Extension(dry_run): Extension<DryRun>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        use crate::resources::scratch_resources::{self, DEFAULT_MIME_TYPE, ScratchEntry};
+
+        if let Err(e) = scratch_resources::validate_name(&params.name) {
+            return Ok(CallToolResult::error(vec![Content::text(e)]));
+        }
+        let store = self.resource_handler.scratch();
+        let uri = scratch_resources::scratch_uri(&params.name);
+        let size = params.text.len();
+        if dry_run.enabled() {
+            let created = store.get(&params.name).is_none();
+            return Ok(crate::dry_run::preview(
+                &format!("write resource {uri}"),
+                scratch::write_result(&uri, created, size),
+            ));
+        }
+        let entry = ScratchEntry {
+            text: params.text,
+            mime_type: params
+                .mime_type
+                .unwrap_or_else(|| DEFAULT_MIME_TYPE.to_string()),
+        };
+        let created = match store.write(&params.name, entry) {
+            Ok(previous) => previous.is_none(),
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        if created && let Err(e) = context.peer.notify_resource_list_changed().await {
+            tracing::debug!(error = %e, "Resource list change not sent");
+        }
+        store.notify_updated(&uri).await;
+        Ok(CallToolResult::success(vec![Content::text(
+            scratch::write_result(&uri, created, size).to_string(),
+        )]))
+    }
+
+    /// Delete a `test://scratch/{name}` resource.
+    #[tool(description = "Delete the resource test://scratch/{name}; returns whether it existed")]
+    async fn resource_delete(
+        &self,
+        Parameters(params): Parameters<ResourceDeleteParams>,
+        Extension(dry_run): Extension<DryRun>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let store = self.resource_handler.scratch();
+        let uri = crate::resources::scratch_resources::scratch_uri(&params.name);
+        if dry_run.enabled() {
+            return Ok(crate::dry_run::preview(
+                &format!("delete resource {uri}"),
+                scratch::delete_result(&uri, store.get(&params.name).is_some()),
+            ));
+        }
+        let deleted = store.delete(&params.name).is_some();
+        if deleted {
+            if let Err(e) = context.peer.notify_resource_list_changed().await {
+                tracing::debug!(error = %e, "Resource list change not sent");
+            }
+            store.notify_updated(&uri).await;
+        }
+        Ok(CallToolResult::success(vec![Content::text(
+            scratch::delete_result(&uri, deleted).to_string(),
+        )]))
+    }
+
+    // Ephemeral resource tool — a resource that expires
+
+    /// Create `test://dynamic/ephemeral`, which expires after `ttl_secs`.
+    #[tool(
+        description = "Create or replace the resource test://dynamic/ephemeral for ttl_secs seconds (1-300, default 5); it then leaves the resource list and reads fail with -32002"
+    )]
+    async fn ephemeral_create(
+        &self,
+        Parameters(params): Parameters<EphemeralCreateParams>,
+        Extension(dry_run): Extension<DryRun>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        use crate::resources::ephemeral_resources::{
+            DEFAULT_EPHEMERAL_TTL, EPHEMERAL_URI, MAX_EPHEMERAL_TTL,
+        };
+
+        let ttl = params
+            .ttl_secs
+            .map_or(DEFAULT_EPHEMERAL_TTL, std::time::Duration::from_secs);
+        if ttl.is_zero() || ttl > MAX_EPHEMERAL_TTL {
+            return Ok(CallToolResult::error(vec![Content::text(format!(
+                "ttl_secs must be between 1 and {}",
+                MAX_EPHEMERAL_TTL.as_secs()
+            ))]));
+        }
+        if dry_run.enabled() {
+            let expires_at = (chrono::Utc::now() + ttl).to_rfc3339();
+            return Ok(crate::dry_run::preview(
+                &format!("create resource {EPHEMERAL_URI}"),
+                ephemeral::create_result(EPHEMERAL_URI, ttl.as_secs(), &expires_at),
+            ));
+        }
+        let store = Arc::clone(self.resource_handler.ephemeral());
+        let text = params.text.unwrap_or_else(|| "ephemeral".to_string());
+        let (generation, expires_at) = store.create(text, ttl);
+        if let Err(e) = context.peer.notify_resource_list_changed().await {
+            tracing::debug!(error = %e, "Resource list change not sent");
+        }
+        let peer = context.peer.clone();
+        tokio::spawn(async move { store.expire(generation, ttl, peer).await });
+        Ok(CallToolResult::success(vec![Content::text(
+            ephemeral::create_result(EPHEMERAL_URI, ttl.as_secs(), &expires_at.to_rfc3339())
+                .to_string(),
+        )]))
+    }
+
+    // Prompt registration tool — prompts added at runtime
+
+    /// Register a prompt rendered from a template.
+    #[tool(
+        description = "Register a prompt with the given arguments whose single user message is template with {argument} placeholders filled in; notifies of prompt list changes"
+    )]
+    async fn register_prompt(
+        &self,
+        Parameters(params): Parameters<RegisterPromptParams>,
+        Extension(dry_run): Extension<DryRun>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let prompt = match params.to_prompt() {
+            Ok(prompt) => prompt,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let builtin = crate::prompts::templates::get_all_prompts()
+            .iter()
+            .any(|p| p.name == prompt.name);
+        if builtin || self.custom.prompt(&prompt.name).is_some() {
+            return Ok(CallToolResult::error(vec![Content::text(format!(
+                "Prompt already defined by the server: {}",
+                prompt.name
+            ))]));
+        }
+        let arguments = params.arguments.len();
+        if dry_run.enabled() {
+            let replaced = self.registered_prompts.contains(&prompt.name);
+            return Ok(crate::dry_run::preview(
+                &format!("register prompt {}", prompt.name),
+                prompts::register_result(&prompt.name, replaced, arguments),
+            ));
+        }
+        let name = prompt.name.clone();
+        let replaced = match self.registered_prompts.register(prompt, params.template) {
+            Ok(replaced) => replaced,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        if let Err(e) = context.peer.notify_prompt_list_changed().await {
+            tracing::debug!(error = %e, "Prompt list change not sent");
+        }
+        Ok(CallToolResult::success(vec![Content::text(
+            prompts::register_result(&name, replaced, arguments).to_string(),
+        )]))
+    }
+
+    // Fetch tool — simulated HTTP against the mock upstream
+
+    /// Fetch a URL from the built-in mock upstream.
+    #[tool(
+        description = "Fetch a URL from the mock upstream http://mock.test (no real network access); returns status, headers, and body"
+    )]
+    async fn fetch_url(
+        &self,
+        Parameters(params): Parameters<FetchUrlParams>,
+    ) -> Result<rmcp::handler::server::wrapper::Json<crate::tools::fetch::FetchResponse>, String>
+    {
+        let (response, delay) = self.upstream.resolve(
+            params.method.as_deref().unwrap_or("GET"),
+            &params.url,
+            &params.headers,
+            params.body.as_deref(),
+        )?;
+        tokio::time::sleep(delay).await;
+        Ok(rmcp::handler::server::wrapper::Json(response))
+    }
+
+    // Regex tools — structured capture groups under a timeout
+
+    /// Find the first match of a regex.
+    #[tool(
+        description = "Test whether a regex matches text; returns the first match with its capture groups"
+    )]
+    async fn regex_match(
+        &self,
+        Parameters(params): Parameters<RegexMatchParams>,
+    ) -> Result<rmcp::handler::server::wrapper::Json<crate::tools::regex::MatchResult>, String>
+    {
+        let timeout = std::time::Duration::from_millis(self.config.regex_timeout_ms);
+        crate::tools::regex::run_with_timeout(timeout, move || {
+            crate::tools::regex::find(&params.pattern, &params.text)
+        })
+        .await
+        .map(rmcp::handler::server::wrapper::Json)
+    }
+
+    /// Replace regex matches.
+    #[tool(
+        description = "Replace the first or every regex match in text; $1 or ${name} insert capture groups"
+    )]
+    async fn regex_replace(
+        &self,
+        Parameters(params): Parameters<RegexReplaceParams>,
+    ) -> Result<rmcp::handler::server::wrapper::Json<crate::tools::regex::ReplaceResult>, String>
+    {
+        let timeout = std::time::Duration::from_millis(self.config.regex_timeout_ms);
+        crate::tools::regex::run_with_timeout(timeout, move || {
+            crate::tools::regex::replace(
+                &params.pattern,
+                &params.text,
+                &params.replacement,
+                params.all,
+            )
+        })
+        .await
+        .map(rmcp::handler::server::wrapper::Json)
+    }
+
+    /// Extract every regex match.
+    #[tool(description = "Extract every regex match in text with its capture groups")]
+    async fn regex_extract(
+        &self,
+        Parameters(params): Parameters<RegexExtractParams>,
+    ) -> Result<rmcp::handler::server::wrapper::Json<crate::tools::regex::ExtractResult>, String>
+    {
+        let timeout = std::time::Duration::from_millis(self.config.regex_timeout_ms);
+        crate::tools::regex::run_with_timeout(timeout, move || {
+            crate::tools::regex::extract(&params.pattern, &params.text, params.limit)
+        })
+        .await
+        .map(rmcp::handler::server::wrapper::Json)
+    }
+
+    // Compression tools — gzip and zstd payloads as base64
+
+    /// Gzip a string.
+    #[tool(
+        description = "Gzip text at a level from 0 to 9; returns the compressed bytes as base64"
+    )]
+    async fn gzip_compress(
+        &self,
+        Parameters(params): Parameters<GzipCompressParams>,
+    ) -> Result<String, String> {
+        crate::tools::compression::gzip_compress(&params.text, params.level)
+    }
+
+    /// Gunzip base64 data.
+    #[tool(description = "Decompress base64-encoded gzip data back to text")]
+    async fn gzip_decompress(
+        &self,
+        Parameters(params): Parameters<GzipDecompressParams>,
+    ) -> Result<String, String> {
+        crate::tools::compression::gzip_decompress(&params.data)
+    }
+
+    /// Zstd-compress a string.
+    #[tool(
+        description = "Zstd-compress text at a level from 1 to 22; returns the compressed bytes as base64"
+    )]
+    async fn zstd_compress(
+        &self,
+        Parameters(params): Parameters<ZstdCompressParams>,
+    ) -> Result<String, String> {
+        crate::tools::compression::zstd_compress(&params.text, params.level)
+    }
+
+    /// Decompress base64 zstd data.
+    #[tool(description = "Decompress base64-encoded zstd data back to text")]
+    async fn zstd_decompress(
+        &self,
+        Parameters(params): Parameters<ZstdDecompressParams>,
+    ) -> Result<String, String> {
+        crate::tools::compression::zstd_decompress(&params.data)
+    }
+
+    // Conversion tools — YAML and TOML through JSON
+
+    /// Convert YAML to JSON.
+    #[tool(description = "Convert a YAML document to pretty-printed JSON")]
+    async fn yaml_to_json(
+        &self,
+        Parameters(params): Parameters<YamlToJsonParams>,
+    ) -> Result<String, String> {
+        crate::tools::convert::yaml_to_json(&params.yaml)
+    }
+
+    /// Convert JSON to YAML.
+    #[tool(description = "Convert a JSON value to a YAML document")]
+    async fn json_to_yaml(
+        &self,
+        Parameters(params): Parameters<JsonToYamlParams>,
+    ) -> Result<String, String> {
+        crate::tools::convert::json_to_yaml(&params.value)
+    }
+
+    /// Convert TOML to JSON.
+    #[tool(
+        description = "Convert a TOML document to pretty-printed JSON; datetimes become RFC 3339 strings"
+    )]
+    async fn toml_to_json(
+        &self,
+        Parameters(params): Parameters<TomlToJsonParams>,
+    ) -> Result<String, String> {
+        crate::tools::convert::toml_to_json(&params.toml)
+    }
+
+    // CSV tools — tabular text to and from JSON rows
+
+    /// Parse CSV into rows.
+    #[tool(
+        description = "Parse CSV or TSV text into JSON rows: objects keyed by the header record, or arrays of cells"
+    )]
+    async fn csv_parse(
+        &self,
+        Parameters(params): Parameters<CsvParseParams>,
+    ) -> Result<rmcp::handler::server::wrapper::Json<crate::tools::csv::ParsedCsv>, String> {
+        crate::tools::csv::parse(
+            &params.text,
+            &params.delimiter,
+            &params.quote,
+            params.has_headers,
+        )
+        .map(rmcp::handler::server::wrapper::Json)
+    }
+
+    /// Format rows as CSV.
+    #[tool(
+        description = "Format JSON rows (all objects or all arrays) as CSV or TSV text, quoting cells as needed"
+    )]
+    async fn csv_format(
+        &self,
+        Parameters(params): Parameters<CsvFormatParams>,
+    ) -> Result<rmcp::handler::server::wrapper::Json<crate::tools::csv::FormattedCsv>, String> {
+        crate::tools::csv::format(
+            &params.rows,
+            params.headers,
+            &params.delimiter,
+            &params.quote,
+        )
+        .map(rmcp::handler::server::wrapper::Json)
+    }
+
+    // XML tools — element trees and XPath queries
+
+    /// Parse XML into an element tree.
+    #[tool(
+        description = "Parse an XML document into its element tree: names, namespaces, attributes, and text"
+    )]
+    async fn xml_parse(
+        &self,
+        Parameters(params): Parameters<XmlParseParams>,
+    ) -> Result<rmcp::handler::server::wrapper::Json<crate::tools::xml::XmlElement>, String> {
+        crate::tools::xml::parse(&params.xml).map(rmcp::handler::server::wrapper::Json)
+    }
+
+    /// Evaluate an `XPath` query.
+    #[tool(
+        description = "Evaluate an XPath 1.0 expression against an XML document; returns the selected nodes or a scalar"
+    )]
+    async fn xml_query(
+        &self,
+        Parameters(params): Parameters<XmlQueryParams>,
+    ) -> Result<rmcp::handler::server::wrapper::Json<crate::tools::xml::XmlQueryResult>, String>
+    {
+        crate::tools::xml::query(&params.xml, &params.xpath, &params.namespaces)
+            .map(rmcp::handler::server::wrapper::Json)
+    }
+
+    // JSON query tool — JSONPath over a provided document
+
+    /// Query a JSON document with `JSONPath`.
+    #[tool(
+        description = "Evaluate an RFC 9535 JSONPath expression (e.g. $.items[?@.price < 10].name) against a JSON document; returns each match with its normalized path"
+    )]
+    async fn json_query(
+        &self,
+        Parameters(params): Parameters<JsonQueryParams>,
+    ) -> Result<rmcp::handler::server::wrapper::Json<JsonQueryResult>, String> {
+        crate::tools::query::query(&params.document, &params.path)
+            .map(rmcp::handler::server::wrapper::Json)
+    }
+
+    // Template tool — free-form nested object parameters
+
+    /// Render a template with a JSON context.
+    #[tool(
+        description = "Render a Jinja-style template with a JSON object context; undefined is lenient, chainable, or strict"
+    )]
+    async fn render_template(
+        &self,
+        Parameters(params): Parameters<RenderTemplateParams>,
+    ) -> Result<String, String> {
+        crate::tools::template::render(&params.template, &params.context, params.undefined)
+    }
+
+    // Markdown tool — HTML output with a sanitization pass
+
+    /// Render Markdown to HTML.
+    #[tool(
+        description = "Render GitHub-flavored Markdown to HTML; sanitize (default true) strips scripts, event handlers, and javascript: URLs"
+    )]
+    async fn markdown_to_html(
+        &self,
+        Parameters(params): Parameters<MarkdownToHtmlParams>,
+    ) -> String {
+        crate::tools::markdown::to_html(&params.markdown, params.sanitize)
+    }
+
+    // Date/time tools — RFC 3339 arithmetic and IANA time zones
+
+    /// Add an offset to a timestamp.
+    #[tool(
+        description = "Add an amount of seconds, minutes, hours, days, weeks, months, or years to an RFC 3339 timestamp"
+    )]
+    async fn date_add(
+        &self,
+        Parameters(params): Parameters<DateAddParams>,
+    ) -> Result<String, String> {
+        crate::tools::datetime::add(&params.timestamp, params.amount, params.unit)
+    }
+
+    /// Difference between two timestamps.
+    #[tool(description = "Whole units between two RFC 3339 timestamps; negative if end is earlier")]
+    async fn date_diff(
+        &self,
+        Parameters(params): Parameters<DateDiffParams>,
+    ) -> Result<rmcp::handler::server::wrapper::Json<crate::tools::datetime::DateDiff>, String>
+    {
+        crate::tools::datetime::diff(&params.start, &params.end, params.unit)
+            .map(rmcp::handler::server::wrapper::Json)
+    }
+
+    /// Convert a timestamp to a time zone.
+    #[tool(
+        description = "Express an RFC 3339 timestamp in an IANA time zone, with its UTC offset and DST status"
+    )]
+    async fn timezone_convert(
+        &self,
+        Parameters(params): Parameters<TimezoneConvertParams>,
+    ) -> Result<rmcp::handler::server::wrapper::Json<crate::tools::datetime::ZonedTime>, String>
+    {
+        crate::tools::datetime::convert(&params.timestamp, &params.timezone)
+            .map(rmcp::handler::server::wrapper::Json)
+    }
+
     // Testing tools
 
     /// Sleep for a specified duration.
@@ -426,38 +2177,279 @@ impl McpTestServer {
         params.text
     }
 
-    /// Generate deeply nested JSON data.
-    #[tool(description = "Generate deeply nested JSON data")]
-    async fn nested_data(
+    /// Generate deeply nested JSON data.
+    #[tool(description = "Generate deeply nested JSON data")]
+    async fn nested_data(
+        &self,
+        Parameters(params): Parameters<NestedDataParams>,
+    ) -> Result<String, String> {
+        let data = create_nested(params.depth);
+        serde_json::to_string_pretty(&data).map_err(|e| e.to_string())
+    }
+
+    /// Generate a large text response.
+    #[tool(description = "Generate a large text response")]
+    async fn large_response(&self, Parameters(params): Parameters<LargeResponseParams>) -> String {
+        let line = "This is a line of text to create a large response.
+";
+        let lines_needed = params.size_bytes.div_ceil(line.len());
+        line.repeat(lines_needed)
+    }
+
+    /// Return a text payload of an exact size, subject to the response cap.
+    #[tool(
+        description = "Return exactly size_bytes of text (up to 512 MiB); fails with a -32603 error when over the server's MCP_MAX_RESPONSE_BYTES limit"
+    )]
+    async fn huge_response(
+        &self,
+        Parameters(params): Parameters<HugeResponseParams>,
+    ) -> Result<CallToolResult, McpError> {
+        if params.size_bytes > MAX_HUGE_RESPONSE_BYTES {
+            return Ok(CallToolResult::error(vec![Content::text(format!(
+                "size_bytes must be at most {MAX_HUGE_RESPONSE_BYTES}"
+            ))]));
+        }
+        // The text alone is over the cap; don't build it just to reject it
+        if let Some(limit) = self.config.max_response_bytes
+            && params.size_bytes > limit
+        {
+            return Err(crate::validation::response_too_large(
+                params.size_bytes,
+                limit,
+            ));
+        }
+        Ok(CallToolResult::success(vec![Content::text(
+            crate::tools::testing::huge_text(params.size_bytes),
+        )]))
+    }
+
+    /// Panic in the handler, or drop the connection, after starting the response.
+    #[tool(
+        description = "Fail as badly as possible after one log message: mode panic (default) panics in the handler, abort_connection drops the connection mid-response"
+    )]
+    async fn panic(
+        &self,
+        Parameters(params): Parameters<PanicParams>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<String, String> {
+        // Start the response, so the failure lands mid-stream
+        let _ = context
+            .peer
+            .notify_logging_message(
+                rmcp::model::LoggingMessageNotificationParam::new(
+                    rmcp::model::LoggingLevel::Warning,
+                    serde_json::json!({ "mode": params.mode, "message": params.message }),
+                )
+                .with_logger("panic"),
+            )
+            .await;
+        match params.mode {
+            PanicMode::Panic => panic!("{}", params.message),
+            PanicMode::AbortConnection => {
+                let abort = crate::supervision::ConnectionAbort::of(&context.extensions)
+                    .ok_or("abort_connection needs the HTTP transport")?;
+                // Let the log message reach the client before cutting it off
+                tokio::time::sleep(ABORT_FLUSH_DELAY).await;
+                abort.trigger();
+                Err("Connection aborted".to_string())
+            }
+        }
+    }
+
+    /// Return text that commonly breaks client text handling.
+    #[tool(
+        description = "Return awkward text: lone_surrogates (as literal \\uXXXX escapes), nul, bom, long_line (length characters, no line break), rtl, or zero_width"
+    )]
+    async fn edge_case_text(
+        &self,
+        Parameters(params): Parameters<EdgeCaseTextParams>,
+    ) -> Result<String, String> {
+        crate::tools::testing::edge_case_text(params.mode, params.length)
+    }
+
+    /// Generate random binary data and return as base64.
+    #[tool(description = "Generate random binary data as base64")]
+    async fn binary_data(&self, Parameters(params): Parameters<BinaryDataParams>) -> String {
+        use rand::Rng;
+        let mut rng = rand::rng();
+        let data: Vec<u8> = (0..params.size_bytes).map(|_| rng.random()).collect();
+        BASE64.encode(&data)
+    }
+
+    /// Stream lines as progress notifications, then return them all.
+    #[tool(
+        description = "Send lines one at a time over the response stream as progress notifications (logging messages without a progressToken), then return them all; count and interval_ms set the pace"
+    )]
+    async fn stream_lines(
+        &self,
+        Parameters(params): Parameters<StreamLinesParams>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<String, String> {
+        if params.count > MAX_STREAM_LINES {
+            return Err(format!("count must be at most {MAX_STREAM_LINES}"));
+        }
+        if params.interval_ms > MAX_STREAM_INTERVAL_MS {
+            return Err(format!(
+                "interval_ms must be at most {MAX_STREAM_INTERVAL_MS}"
+            ));
+        }
+        let token = context.meta.get_progress_token();
+        let mut lines = Vec::new();
+        for n in 1..=params.count {
+            if n > 1 {
+                tokio::select! {
+                    () = context.ct.cancelled() => {
+                        return Err(format!("Cancelled after {} lines", lines.len()));
+                    }
+                    () = tokio::time::sleep(std::time::Duration::from_millis(params.interval_ms)) => {}
+                }
+            }
+            let line = format!("{} {n}/{}", params.text, params.count);
+            let sent =
+                match &token {
+                    Some(token) => {
+                        context
+                            .peer
+                            .notify_progress(
+                                rmcp::model::ProgressNotificationParam::new(
+                                    token.clone(),
+                                    f64::from(n),
+                                )
+                                .with_total(f64::from(params.count))
+                                .with_message(line.clone()),
+                            )
+                            .await
+                    }
+                    None => context
+                        .peer
+                        .notify_logging_message(
+                            rmcp::model::LoggingMessageNotificationParam::new(
+                                rmcp::model::LoggingLevel::Info,
+                                serde_json::json!({ "line": line, "n": n, "count": params.count }),
+                            )
+                            .with_logger("stream_lines"),
+                        )
+                        .await,
+                };
+            // A client that hung up gets no result either
+            sent.map_err(|e| format!("Stream closed after {} lines: {e}", lines.len()))?;
+            lines.push(line);
+        }
+        Ok(lines.join("\n"))
+    }
+
+    /// Never answer; wait for cancellation or the server's request timeout.
+    #[tool(
+        description = "Never respond: hold the request open until it is cancelled, the session closes, or MCP_REQUEST_TIMEOUT expires"
+    )]
+    async fn hang(
+        &self,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<String, String> {
+        context.ct.cancelled().await;
+        Err("Cancelled".to_string())
+    }
+
+    /// No-operation tool for testing tool invocation without side effects.
+    #[tool(description = "No-op tool that returns immediately")]
+    async fn noop(&self) -> String {
+        "ok".to_string()
+    }
+
+    /// Report which `prompts/get` arguments were interpolated vs ignored.
+    #[tool(
+        description = "Report which arguments of recent prompts/get calls were interpolated, ignored, or missing"
+    )]
+    async fn prompt_audit(
+        &self,
+        Parameters(params): Parameters<PromptAuditParams>,
+    ) -> Result<String, String> {
+        let calls = if params.clear {
+            self.prompt_audit.drain()
+        } else {
+            self.prompt_audit.entries()
+        };
+        serde_json::to_string_pretty(&serde_json::json!({ "calls": calls }))
+            .map_err(|e| e.to_string())
+    }
+
+    /// Generate seeded fixture records.
+    #[tool(
+        description = "Generate realistic fixture records (people, addresses, products). The same kind, count, and seed always produce the same records."
+    )]
+    async fn fixture_generate(
+        &self,
+        Parameters(params): Parameters<FixtureGenerateParams>,
+    ) -> Result<String, String> {
+        if params.count > MAX_FIXTURE_COUNT {
+            return Err(format!("count must be at most {MAX_FIXTURE_COUNT}"));
+        }
+        let seed = self.resolve_seed(params.seed);
+        let records = crate::tools::fixtures::generate(params.kind, params.count, seed);
+        serde_json::to_string_pretty(&serde_json::json!({
+            "kind": params.kind,
+            "seed": seed,
+            "count": records.len(),
+            "records": records,
+        }))
+        .map_err(|e| e.to_string())
+    }
+
+    /// Report the authenticated principal behind this request.
+    #[tool(
+        description = "Return the authenticated principal (API key id, OAuth client_id, scopes) and session of this request"
+    )]
+    async fn whoami(
+        &self,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<String, String> {
+        serde_json::to_string_pretty(&crate::auth::auth_context(context.extensions.get()))
+            .map_err(|e| e.to_string())
+    }
+
+    /// Echo the HTTP request that carried this call.
+    #[tool(
+        description = "Return the HTTP headers, MCP-Protocol-Version, Mcp-Session-Id, and body size of the request that carried this call, as the server received them"
+    )]
+    async fn debug_echo_request(
         &self,
-        Parameters(params): Parameters<NestedDataParams>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> Result<String, String> {
-        let data = create_nested(params.depth);
-        serde_json::to_string_pretty(&data).map_err(|e| e.to_string())
+        let parts = context.extensions.get::<axum::http::request::Parts>();
+        let body_bytes = parts
+            .and_then(|parts| parts.extensions.get::<crate::echo::ReceivedBody>())
+            .map(|body| body.0);
+        serde_json::to_string_pretty(&crate::echo::request_report(parts, body_bytes))
+            .map_err(|e| e.to_string())
     }
 
-    /// Generate a large text response.
-    #[tool(description = "Generate a large text response")]
-    async fn large_response(&self, Parameters(params): Parameters<LargeResponseParams>) -> String {
-        let line = "This is a line of text to create a large response.
-";
-        let lines_needed = params.size_bytes.div_ceil(line.len());
-        line.repeat(lines_needed)
+    // Case-sensitivity fixtures — tool names that differ only by case.
+    //
+    // Each returns its own exact name so a client that normalizes names
+    // before calling can see which tool actually ran.
+
+    /// Lowercase variant of the case-sensitivity fixture.
+    #[tool(description = "Case fixture: returns its exact name (lowercase variant)")]
+    async fn case_probe(&self) -> String {
+        "case_probe".to_string()
     }
 
-    /// Generate random binary data and return as base64.
-    #[tool(description = "Generate random binary data as base64")]
-    async fn binary_data(&self, Parameters(params): Parameters<BinaryDataParams>) -> String {
-        use rand::Rng;
-        let mut rng = rand::rng();
-        let data: Vec<u8> = (0..params.size_bytes).map(|_| rng.random()).collect();
-        BASE64.encode(&data)
+    /// Mixed-case variant of the case-sensitivity fixture.
+    #[tool(
+        name = "Case_Probe",
+        description = "Case fixture: returns its exact name (mixed case variant)"
+    )]
+    async fn case_probe_mixed(&self) -> String {
+        "Case_Probe".to_string()
     }
 
-    /// No-operation tool for testing tool invocation without side effects.
-    #[tool(description = "No-op tool that returns immediately")]
-    async fn noop(&self) -> String {
-        "ok".to_string()
+    /// Uppercase variant of the case-sensitivity fixture.
+    #[tool(
+        name = "CASE_PROBE",
+        description = "Case fixture: returns its exact name (uppercase variant)"
+    )]
+    async fn case_probe_upper(&self) -> String {
+        "CASE_PROBE".to_string()
     }
 
     // Task tools — async long-running operations (MCP Tasks spec)
@@ -529,44 +2521,63 @@ impl McpTestServer {
     // These tools declare `_meta.ui.resourceUri` so MCP Apps-capable hosts
     // (VS Code Insiders, Claude Desktop) fetch and render the interactive HTML
     // from the corresponding `ui://` resource via `resources/read`.
-    // The tool itself returns plain text — the host pushes it to the iframe
-    // via `ui/notifications/tool-result`.
+    // The tools return a text message followed by the app HTML as an
+    // embedded resource — MCP Apps hosts push the result to the iframe via
+    // `ui/notifications/tool-result`, hosts without MCP Apps support can
+    // render the embedded `ui://` resource instead.
 
     /// Interactive button app — host renders `ui://button/app.html`.
     #[tool(
         description = "Returns a single interactive UI button (tests single UI resource rendering)",
-        meta = ui_meta("ui://button/app.html", "both")
+        title = "Button App",
+        icons = ui_icons(),
+        meta = ui_meta("ui://button/app.html", "both", (320, 120))
     )]
     async fn ui_resource_button(
         &self,
         Parameters(_params): Parameters<UiResourceButtonParams>,
-    ) -> String {
-        "Button UI ready. Click the button to call the echo tool.".to_string()
+    ) -> Result<CallToolResult, McpError> {
+        Ok(ui_result(
+            "Button UI ready. Click the button to call the echo tool.",
+            "ui://button/app.html",
+            &self.resource_handler,
+        ))
     }
 
     /// Interactive form app — host renders `ui://form/app.html`.
     #[tool(
         description = "Returns a single interactive UI form (tests single UI resource rendering)",
-        meta = ui_meta("ui://form/app.html", "both")
+        title = "Form App",
+        icons = ui_icons(),
+        meta = ui_meta("ui://form/app.html", "both", (400, 320))
     )]
     async fn ui_resource_form(
         &self,
         Parameters(_params): Parameters<UiResourceFormParams>,
-    ) -> String {
-        "Form UI ready. Fill in the form and submit to call the concat tool.".to_string()
+    ) -> Result<CallToolResult, McpError> {
+        Ok(ui_result(
+            "Form UI ready. Fill in the form and submit to call the concat tool.",
+            "ui://form/app.html",
+            &self.resource_handler,
+        ))
     }
 
     /// Interactive carousel app — host renders `ui://carousel/app.html`.
     #[tool(
         description = "Returns 3 interactive UI cards (tests multi-resource carousel rendering)",
-        meta = ui_meta("ui://carousel/app.html", "both")
+        title = "Carousel App",
+        icons = ui_icons(),
+        meta = ui_meta("ui://carousel/app.html", "both", (720, 280))
     )]
     async fn ui_resource_carousel(
         &self,
         Parameters(_params): Parameters<UiResourceCarouselParams>,
-    ) -> String {
-        "Carousel UI ready. 3 interactive cards loaded. Click a card to call the echo tool."
-            .to_string()
+    ) -> Result<CallToolResult, McpError> {
+        Ok(ui_result(
+            "Carousel UI ready. 3 interactive cards loaded. Click a card to call the echo tool.",
+            "ui://carousel/app.html",
+            &self.resource_handler,
+        ))
     }
 
     /// Internal-only UI tool — hidden from the LLM, only callable from the iframe.
@@ -574,7 +2585,9 @@ impl McpTestServer {
     /// Tests client-side tool filtering based on `_meta.ui.visibility: "app"`.
     #[tool(
         description = "Internal tool only callable from a UI iframe (tests app-only visibility filtering)",
-        meta = ui_meta("ui://internal_only/app.html", "app")
+        title = "Internal App Tool",
+        icons = ui_icons(),
+        meta = ui_meta("ui://internal_only/app.html", "app", (320, 160))
     )]
     async fn ui_internal_only(
         &self,
@@ -588,92 +2601,197 @@ impl McpTestServer {
         .to_string()
     }
 
+    /// Log a message an app iframe posted to its host.
+    ///
+    /// App-only, like `ui_internal_only`: the shim calls it, the LLM never
+    /// sees it.
+    #[tool(
+        description = "Record a message an MCP App posted to its host in test://dynamic/ui-events (called by the app shim)",
+        title = "Record App Message",
+        icons = ui_icons(),
+        meta = app_only_meta()
+    )]
+    async fn ui_record_event(&self, Parameters(params): Parameters<UiRecordEventParams>) -> String {
+        let seq = self.resource_handler.ui_events().record(
+            params.app,
+            params.method,
+            params.id,
+            params.params,
+        );
+        serde_json::json!({ "seq": seq }).to_string()
+    }
+
     // Rich MCP App tools — complex interactive UIs with CDN library fallbacks
 
     /// Dashboard with Chart.js charts and tool call metrics.
     #[tool(
         description = "Interactive dashboard with Chart.js charts and tool call metrics (MCP App)",
-        meta = ui_meta("ui://dashboard/app.html", "both")
+        title = "Metrics Dashboard",
+        icons = ui_icons(),
+        meta = ui_meta("ui://dashboard/app.html", "both", (800, 600))
     )]
     async fn ui_resource_dashboard(
         &self,
         Parameters(_params): Parameters<UiResourceDashboardParams>,
-    ) -> String {
-        "Dashboard UI ready. Calls add and current_time tools for live metrics.".to_string()
+    ) -> Result<CallToolResult, McpError> {
+        Ok(ui_result(
+            "Dashboard UI ready. Calls add and current_time tools for live metrics.",
+            "ui://dashboard/app.html",
+            &self.resource_handler,
+        ))
     }
 
     /// Filterable, sortable data table of the server's tool registry.
     #[tool(
         description = "Filterable tool registry table with Tabulator.js and vanilla fallback (MCP App)",
-        meta = ui_meta("ui://data_table/app.html", "both")
+        title = "Tool Registry Table",
+        icons = ui_icons(),
+        meta = ui_meta("ui://data_table/app.html", "both", (800, 500))
     )]
     async fn ui_resource_data_table(
         &self,
         Parameters(_params): Parameters<UiResourceDataTableParams>,
-    ) -> String {
-        "Data table UI ready. Uses Tabulator (CDN) with vanilla table fallback.".to_string()
+    ) -> Result<CallToolResult, McpError> {
+        Ok(ui_result(
+            "Data table UI ready. Uses Tabulator (CDN) with vanilla table fallback.",
+            "ui://data_table/app.html",
+            &self.resource_handler,
+        ))
     }
 
     /// Interactive ETL pipeline visualizer.
     #[tool(
         description = "Interactive ETL pipeline visualizer with stage-by-stage execution (MCP App)",
-        meta = ui_meta("ui://pipeline/app.html", "both")
+        title = "ETL Pipeline Visualizer",
+        icons = ui_icons(),
+        meta = ui_meta("ui://pipeline/app.html", "both", (800, 420))
     )]
     async fn ui_resource_pipeline(
         &self,
         Parameters(_params): Parameters<UiResourcePipelineParams>,
-    ) -> String {
-        "Pipeline UI ready. Run the pipeline to execute all stages sequentially.".to_string()
+    ) -> Result<CallToolResult, McpError> {
+        Ok(ui_result(
+            "Pipeline UI ready. Run the pipeline to execute all stages sequentially.",
+            "ui://pipeline/app.html",
+            &self.resource_handler,
+        ))
     }
 }
 
-#[tool_handler(router = self.tool_router)]
 #[task_handler(processor = self.processor)]
 // TODO(upstream): remove allow(deprecated) once rmcp-macros publishes fix
 // PR: https://github.com/modelcontextprotocol/rust-sdk/pull/727
 #[allow(deprecated, clippy::significant_drop_tightening)]
 impl ServerHandler for McpTestServer {
     fn get_info(&self) -> ServerInfo {
-        ServerInfo::new(
-            ServerCapabilities::builder()
-                .enable_tools()
-                .enable_tool_list_changed()
-                .enable_prompts()
-                .enable_prompts_list_changed()
-                .enable_resources()
-                .enable_resources_list_changed()
-                .enable_resources_subscribe()
-                .enable_logging()
-                .enable_completions()
-                .enable_tasks()
-                .enable_extensions_with({
-                    let mut ext = ExtensionCapabilities::new();
-                    ext.insert(
-                        "io.modelcontextprotocol/ui".to_string(),
-                        serde_json::Map::new(),
-                    );
-                    ext
-                })
-                .build(),
+        let config = &self.config;
+        let mut implementation = Implementation::new(
+            config.server_name.as_deref().unwrap_or("mcp-test-server"),
+            config
+                .server_version
+                .as_deref()
+                .unwrap_or(env!("CARGO_PKG_VERSION")),
         )
-        .with_protocol_version(ProtocolVersion::LATEST)
-        .with_server_info(
-            Implementation::new("mcp-test-server", env!("CARGO_PKG_VERSION"))
-                .with_title("MCP Test Server")
-                .with_description(
-                    "Comprehensive MCP test server for validating client implementations.",
-                )
-                .with_icons(vec![
-                    Icon::new(crate::icons::SERVER_ICON_SVG)
-                        .with_mime_type("image/svg+xml")
-                        .with_sizes(vec!["any".to_string()]),
-                ])
-                .with_website_url("https://github.com/nazq/test_mcp_servers"),
-        )
-        .with_instructions(
+        .with_description("Comprehensive MCP test server for validating client implementations.")
+        .with_website_url("https://github.com/nazq/test_mcp_servers");
+        let title = config.server_title.as_deref().unwrap_or("MCP Test Server");
+        implementation.title = Some(title.to_string()).filter(|t| !t.is_empty());
+        implementation.icons = config.server_icons.icons();
+
+        let mut capabilities = ServerCapabilities::builder()
+            .enable_tools()
+            .enable_tool_list_changed()
+            .enable_prompts()
+            .enable_prompts_list_changed()
+            .enable_resources()
+            .enable_resources_list_changed()
+            .enable_resources_subscribe()
+            .enable_logging()
+            .enable_completions()
+            .enable_tasks()
+            .enable_extensions_with({
+                let mut ext = ExtensionCapabilities::new();
+                ext.insert(
+                    "io.modelcontextprotocol/ui".to_string(),
+                    serde_json::Map::new(),
+                );
+                ext
+            })
+            .build();
+        if let Some(advertised) = &config.capabilities {
+            crate::advertise::restrict(&mut capabilities, advertised);
+        }
+        capabilities
+            .experimental
+            .clone_from(&config.experimental_capabilities);
+
+        let mut info = ServerInfo::new(capabilities)
+            .with_protocol_version(config.compat.map_or(
+                ProtocolVersion::LATEST,
+                crate::compat::CompatTarget::protocol_version,
+            ))
+            .with_server_info(implementation);
+        info.instructions = Some(config.instructions.clone().unwrap_or_else(|| {
             "A comprehensive MCP test server providing tools, prompts, and resources \
-             for testing MCP client implementations.",
-        )
+             for testing MCP client implementations."
+                .to_string()
+        }))
+        .filter(|i| !i.is_empty());
+        info
+    }
+
+    async fn call_tool(
+        &self,
+        request: rmcp::model::CallToolRequestParams,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<rmcp::model::CallToolResult, rmcp::ErrorData> {
+        use futures_util::FutureExt;
+
+        let lang = self.config.error_lang;
+        let tool = request.name.clone();
+        let abort = crate::supervision::ConnectionAbort::of(&context.extensions).cloned();
+        let supervised = std::panic::AssertUnwindSafe(self.call_tool_impl(request, context))
+            .catch_unwind()
+            .map(|called| {
+                called.unwrap_or_else(|payload| {
+                    let message = crate::supervision::panic_message(payload.as_ref());
+                    tracing::error!(%tool, %message, "Tool handler panicked");
+                    if self.config.panic_policy == crate::supervision::PanicPolicy::Abort
+                        && let Some(abort) = abort
+                    {
+                        abort.trigger();
+                    }
+                    Err(McpError::internal_error(
+                        format!("Tool panicked: {message}"),
+                        Some(serde_json::json!({ "tool": &tool })),
+                    ))
+                })
+            });
+        let called = match self.config.request_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, supervised)
+                .await
+                .unwrap_or_else(|_| Err(crate::supervision::request_timed_out(&tool, timeout))),
+            None => supervised.await,
+        };
+        self.config
+            .tool_error_mode
+            .report(&tool, called)
+            .map(|result| lang.localize_tool_result(result))
+            .map_err(|e| lang.localize_error(e))
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<rmcp::model::PaginatedRequestParams>,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<rmcp::model::ListToolsResult, rmcp::ErrorData> {
+        Ok(rmcp::model::ListToolsResult::with_all_items(
+            self.tool_router.list_all(),
+        ))
+    }
+
+    fn get_tool(&self, name: &str) -> Option<rmcp::model::Tool> {
+        self.tool_router.get(name).cloned()
     }
 
     async fn list_prompts(
@@ -689,7 +2807,17 @@ impl ServerHandler for McpTestServer {
         request: rmcp::model::GetPromptRequestParams,
         context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> Result<rmcp::model::GetPromptResult, rmcp::ErrorData> {
-        self.get_prompt_impl(request, context)
+        let result = if self.custom.prompt(&request.name).is_some() {
+            self.get_custom_prompt(request).await
+        } else if let Some(result) = self.get_registered_prompt(&request) {
+            result
+        } else {
+            if request.name == "flaky_prompt" {
+                self.delay_prompt_failure(&request, &context).await;
+            }
+            self.get_prompt_impl(request, context)
+        };
+        result.map_err(|e| self.config.error_lang.localize_error(e))
     }
 
     async fn list_resources(
@@ -697,7 +2825,11 @@ impl ServerHandler for McpTestServer {
         request: Option<rmcp::model::PaginatedRequestParams>,
         _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> Result<ListResourcesResult, rmcp::ErrorData> {
-        self.resource_handler.list_resources(request)
+        let cursor = request.and_then(|r| r.cursor);
+        let resources = self
+            .custom
+            .merge_resources(self.resource_handler.list_resources(None)?.resources);
+        crate::resources::paginate_resources(resources, cursor.as_deref())
     }
 
     async fn list_resource_templates(
@@ -705,44 +2837,145 @@ impl ServerHandler for McpTestServer {
         request: Option<rmcp::model::PaginatedRequestParams>,
         _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> Result<ListResourceTemplatesResult, rmcp::ErrorData> {
-        self.resource_handler.list_resource_templates(request)
+        let mut result = self.resource_handler.list_resource_templates(request)?;
+        result.resource_templates = self.custom.merge_templates(result.resource_templates);
+        Ok(result)
     }
 
     async fn read_resource(
         &self,
         request: rmcp::model::ReadResourceRequestParams,
-        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> Result<ReadResourceResult, rmcp::ErrorData> {
-        self.resource_handler.read_resource(&request)
+        let mut request = request;
+        if self.config.lenient_names
+            && let Some(uri) = self.resolve_resource_uri(&request.uri)
+        {
+            request.uri = uri;
+        }
+        if let Some(result) = self.custom.read(&request.uri).await {
+            return result.map_err(|e| self.config.error_lang.localize_error(e));
+        }
+        if request.uri == crate::resources::dynamic_resources::COUNTER_URI {
+            // Read here rather than in the handler so subscribers hear of
+            // the exact value this read produced
+            let counter = self.resource_handler.counter();
+            let value = counter.increment();
+            counter.notify(value).await;
+            return Ok(ReadResourceResult::new(vec![
+                crate::resources::dynamic_resources::get_counter_content(value),
+            ]));
+        }
+        if let Some(read) = crate::resources::dynamic_resources::UnreliableRead::parse(&request.uri)
+        {
+            return self
+                .read_unreliable(&request.uri, read, &context)
+                .await
+                .map_err(|e| self.config.error_lang.localize_error(e));
+        }
+        self.resource_handler
+            .read_resource_with_context(&request, &context)
+            .map_err(|e| self.config.error_lang.localize_error(e))
     }
 
     async fn subscribe(
         &self,
         request: rmcp::model::SubscribeRequestParams,
-        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> Result<(), rmcp::ErrorData> {
-        self.resource_handler.subscribe(&request)
+        // Over stateless HTTP every client shares one session key, and no
+        // stream outlives the response to carry the ticks
+        if request.uri == crate::resources::dynamic_resources::TICKER_URI
+            && !self.config.stateful
+            && context
+                .extensions
+                .get::<axum::http::request::Parts>()
+                .is_some()
+        {
+            return Err(self
+                .config
+                .error_lang
+                .localize_error(rmcp::ErrorData::invalid_request(
+                    format!(
+                        "Subscriptions to {} require MCP_STATEFUL",
+                        crate::resources::dynamic_resources::TICKER_URI
+                    ),
+                    None,
+                )));
+        }
+        let session = crate::sessions::session_key(context.extensions.get());
+        self.resource_handler
+            .subscribe(&request, session)
+            .map_err(|e| self.config.error_lang.localize_error(e))?;
+        match request.uri.as_str() {
+            crate::resources::dynamic_resources::COUNTER_URI => self
+                .resource_handler
+                .counter()
+                .watch(session, context.peer.clone()),
+            crate::resources::dynamic_resources::TICKER_URI => self
+                .resource_handler
+                .start_ticker(session, context.peer.clone()),
+            crate::resources::meta_resources::FLAGS_URI => self
+                .resource_handler
+                .flags()
+                .watch(session, context.peer.clone()),
+            uri if uri.starts_with(crate::resources::scratch_resources::SCRATCH_PREFIX) => self
+                .resource_handler
+                .scratch()
+                .watch(uri, session, context.peer.clone()),
+            _ => {}
+        }
+        Ok(())
     }
 
     async fn unsubscribe(
         &self,
         request: rmcp::model::UnsubscribeRequestParams,
-        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> Result<(), rmcp::ErrorData> {
-        self.resource_handler.unsubscribe(&request)
+        let session = crate::sessions::session_key(context.extensions.get());
+        match request.uri.as_str() {
+            crate::resources::dynamic_resources::COUNTER_URI => {
+                self.resource_handler.counter().unwatch(session);
+            }
+            crate::resources::dynamic_resources::TICKER_URI => {
+                self.resource_handler.stop_ticker(session);
+            }
+            crate::resources::meta_resources::FLAGS_URI => {
+                self.resource_handler.flags().unwatch(session);
+            }
+            uri if uri.starts_with(crate::resources::scratch_resources::SCRATCH_PREFIX) => {
+                self.resource_handler.scratch().unwatch(uri, session);
+            }
+            _ => {}
+        }
+        self.resource_handler
+            .unsubscribe(&request, session)
+            .map_err(|e| self.config.error_lang.localize_error(e))
     }
 
     async fn complete(
         &self,
         request: rmcp::model::CompleteRequestParams,
-        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> Result<CompleteResult, rmcp::ErrorData> {
+        self.completion_limiter
+            .check(crate::sessions::session_key(context.extensions.get()))?;
+        if let Some(delay) = self.config.completion_delay {
+            tokio::select! {
+                () = context.ct.cancelled() => {
+                    return Err(rmcp::ErrorData::internal_error("Completion cancelled", None));
+                }
+                () = tokio::time::sleep(delay) => {}
+            }
+        }
+
         // Provide completions based on the reference type and argument
         let values = match &request.r#ref {
             Reference::Prompt(prompt_ref) => {
                 // For prompt arguments, provide completions based on the prompt name and argument
                 match (prompt_ref.name.as_str(), request.argument.name.as_str()) {
-                    ("greeting", "name") => {
+                    ("greeting" | "greeting_localized", "name") => {
                         vec![
                             "Alice".into(),
                             "Bob".into(),
@@ -768,6 +3001,14 @@ impl ServerHandler for McpTestServer {
                             "Chinese".into(),
                         ]
                     }
+                    ("flaky_prompt", "mode") => crate::prompts::templates::FLAKY_PROMPT_MODES
+                        .iter()
+                        .map(|&mode| mode.to_string())
+                        .collect(),
+                    ("tone_rewrite", "tone") => crate::prompts::templates::TONES
+                        .iter()
+                        .map(|&tone| tone.to_string())
+                        .collect(),
                     _ => vec![],
                 }
             }
@@ -779,6 +3020,15 @@ impl ServerHandler for McpTestServer {
                         "data.json".into(),
                         "config.yaml".into(),
                     ]
+                } else if resource_ref.uri == crate::resources::template_resources::REPO_TEMPLATE {
+                    crate::resources::template_resources::complete_repo_argument(
+                        &request.argument.name,
+                        request.context.as_ref(),
+                    )
+                } else if resource_ref.uri == crate::resources::template_resources::MANY_TEMPLATE {
+                    crate::resources::template_resources::complete_many_argument(
+                        &request.argument.name,
+                    )
                 } else {
                     vec![]
                 }
@@ -796,9 +3046,18 @@ impl ServerHandler for McpTestServer {
                 .collect()
         };
 
-        Ok(CompleteResult::new(CompletionInfo::new(filtered).map_err(
-            |e| rmcp::ErrorData::new(rmcp::model::ErrorCode::INTERNAL_ERROR, e, None),
-        )?))
+        // A response holds at most 100 values; say how many there are in all
+        let completion = if filtered.len() > CompletionInfo::MAX_VALUES {
+            let total = u32::try_from(filtered.len()).ok();
+            let mut values = filtered;
+            values.truncate(CompletionInfo::MAX_VALUES);
+            CompletionInfo::with_pagination(values, total, true)
+        } else {
+            CompletionInfo::new(filtered)
+        };
+        Ok(CompleteResult::new(completion.map_err(|e| {
+            rmcp::ErrorData::new(rmcp::model::ErrorCode::INTERNAL_ERROR, e, None)
+        })?))
     }
 
     async fn set_level(
@@ -824,11 +3083,34 @@ impl ServerHandler for McpTestServer {
         tracing::info!("Log level set to {:?}", request.level);
         Ok(())
     }
+
+    async fn on_initialized(
+        &self,
+        context: rmcp::service::NotificationContext<rmcp::service::RoleServer>,
+    ) {
+        // Keep the peer so shutdown can reach sessions without subscriptions
+        if let Some(id) = context
+            .extensions
+            .get::<axum::http::request::Parts>()
+            .and_then(|parts| parts.headers.get("mcp-session-id"))
+            .and_then(|v| v.to_str().ok())
+        {
+            self.sessions.attach_peer(id, context.peer.clone());
+            self.resource_handler.sessions().open(id);
+            if self.config.ping_interval.is_some() {
+                let pings = Arc::clone(&self.pings);
+                let (id, peer) = (id.to_string(), context.peer.clone());
+                tokio::spawn(async move { pings.run(&id, peer).await });
+            }
+        }
+        tracing::info!("client initialized");
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tools::fixtures::FixtureKind;
 
     // Helper to create test server
     fn test_server() -> McpTestServer {
@@ -1044,6 +3326,48 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_hash_matches_dedicated_tools() {
+        let server = test_server();
+        let text = || "hello".to_string();
+        let hash = |algorithm| {
+            server.hash(Parameters(HashParams {
+                text: text(),
+                algorithm,
+            }))
+        };
+        assert_eq!(
+            hash(HashAlgorithm::Md5).await,
+            server
+                .hash_md5(Parameters(HashMd5Params { text: text() }))
+                .await
+        );
+        assert_eq!(
+            hash(HashAlgorithm::Sha1).await,
+            server
+                .hash_sha1(Parameters(HashSha1Params { text: text() }))
+                .await
+        );
+        assert_eq!(
+            hash(HashAlgorithm::Sha256).await,
+            server
+                .hash_sha256(Parameters(HashSha256Params { text: text() }))
+                .await
+        );
+        assert_eq!(
+            hash(HashAlgorithm::Sha512).await,
+            server
+                .hash_sha512(Parameters(HashSha512Params { text: text() }))
+                .await
+        );
+        assert_eq!(
+            hash(HashAlgorithm::Crc32).await,
+            server
+                .hash_crc32(Parameters(HashCrc32Params { text: text() }))
+                .await
+        );
+    }
+
     // =============================================================================
     // UTILITY TOOL TESTS
     // =============================================================================
@@ -1077,6 +3401,25 @@ mod tests {
         assert_eq!(result.chars().filter(|c| *c == '-').count(), 4);
     }
 
+    #[tokio::test]
+    async fn test_random_uuid_deterministic_with_namespace() {
+        let config = Config::builder()
+            .uuid_namespace(uuid::Uuid::NAMESPACE_URL)
+            .build();
+        let first = McpTestServer::new(config.clone());
+        let second = McpTestServer::new(config);
+
+        let a1 = first.random_uuid(Parameters(RandomUuidParams {})).await;
+        let a2 = first.random_uuid(Parameters(RandomUuidParams {})).await;
+        let b1 = second.random_uuid(Parameters(RandomUuidParams {})).await;
+        assert_ne!(a1, a2);
+        assert_eq!(a1, b1);
+
+        let parsed = uuid::Uuid::parse_str(&a1).unwrap();
+        assert_eq!(parsed.get_version_num(), 5);
+        assert_eq!(parsed, uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_URL, b"0"));
+    }
+
     #[tokio::test]
     async fn test_current_time() {
         let server = test_server();
@@ -1149,6 +3492,60 @@ mod tests {
         assert_eq!(parsed["nested"]["nested"]["level"], 1);
     }
 
+    #[tokio::test]
+    async fn test_fixture_generate_is_deterministic() {
+        let server = test_server();
+        let generate = |kind, seed| {
+            server.fixture_generate(Parameters(FixtureGenerateParams {
+                kind,
+                count: 3,
+                seed: Some(seed),
+            }))
+        };
+
+        let first = generate(FixtureKind::People, 7).await.unwrap();
+        let again = generate(FixtureKind::People, 7).await.unwrap();
+        let other = generate(FixtureKind::People, 8).await.unwrap();
+        assert_eq!(first, again);
+        assert_ne!(first, other);
+
+        let json: serde_json::Value = serde_json::from_str(&first).unwrap();
+        assert_eq!(json["seed"], 7);
+        assert_eq!(json["records"].as_array().unwrap().len(), 3);
+        assert!(json["records"][0]["email"].as_str().unwrap().contains('@'));
+    }
+
+    #[tokio::test]
+    async fn test_fixture_generate_uses_server_seed() {
+        let server = McpTestServer::new(Config::builder().seed(99).build());
+        let result = server
+            .fixture_generate(Parameters(FixtureGenerateParams {
+                kind: FixtureKind::Products,
+                count: 2,
+                seed: None,
+            }))
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(json["seed"], 99);
+        assert_eq!(json["kind"], "products");
+        let product = &json["records"][1];
+        assert_eq!(product["in_stock"], product["stock"].as_u64().unwrap() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_fixture_generate_rejects_large_count() {
+        let server = test_server();
+        let result = server
+            .fixture_generate(Parameters(FixtureGenerateParams {
+                kind: FixtureKind::Addresses,
+                count: MAX_FIXTURE_COUNT + 1,
+                seed: None,
+            }))
+            .await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_large_response() {
         let server = test_server();
@@ -1158,6 +3555,22 @@ mod tests {
         assert!(result.len() >= 1000);
     }
 
+    #[tokio::test]
+    async fn test_huge_response_respects_cap() {
+        let result = test_server()
+            .huge_response(Parameters(HugeResponseParams { size_bytes: 1000 }))
+            .await
+            .unwrap();
+        assert_eq!(result.content[0].as_text().unwrap().text.len(), 1000);
+
+        let server = McpTestServer::new(Config::builder().max_response_bytes(500).build());
+        let err = server
+            .huge_response(Parameters(HugeResponseParams { size_bytes: 1000 }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, rmcp::model::ErrorCode::INTERNAL_ERROR);
+    }
+
     #[tokio::test]
     async fn test_binary_data() {
         use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
@@ -1171,6 +3584,137 @@ mod tests {
         assert_eq!(decoded.len(), 100);
     }
 
+    #[tokio::test]
+    async fn test_prompt_audit() {
+        let server = test_server();
+        let mut args = std::collections::HashMap::new();
+        args.insert("name".to_string(), "Alice".to_string());
+        args.insert("nmae".to_string(), "typo".to_string());
+        server
+            .prompt_audit_log()
+            .record(&server.listed_prompts(), "greeting", &args, true);
+
+        let result = server
+            .prompt_audit(Parameters(PromptAuditParams { clear: true }))
+            .await
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(parsed["calls"][0]["prompt"], "greeting");
+        assert_eq!(parsed["calls"][0]["interpolated"][0], "name");
+        assert_eq!(parsed["calls"][0]["ignored"][0], "nmae");
+        assert!(server.prompt_audit_log().entries().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_prompt_audit_resolves_registered_arguments() {
+        use rmcp::model::{GetPromptRequestParams, Prompt, PromptArgument};
+
+        let server = test_server();
+        let prompt = Prompt::new(
+            "fixture",
+            None::<String>,
+            Some(vec![PromptArgument::new("who")]),
+        );
+        server
+            .registered_prompts()
+            .register(prompt, "Hi {who}".to_string())
+            .unwrap();
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("who".to_string(), "Ada".into());
+        arguments.insert("extra".to_string(), "x".into());
+        server
+            .get_registered_prompt(
+                &GetPromptRequestParams::new("fixture").with_arguments(arguments),
+            )
+            .unwrap()
+            .unwrap();
+
+        let entry = &server.prompt_audit_log().entries()[0];
+        assert_eq!(entry.interpolated, vec!["who"]);
+        assert_eq!(entry.ignored, vec!["extra"]);
+    }
+
+    #[test]
+    fn test_strict_args_against_generated_schema() {
+        let server = test_server();
+        let tool = server.get_tool("echo").unwrap();
+        let args = serde_json::json!({ "text": "hi", "txet": "typo" });
+        let err = crate::validation::reject_unknown_arguments(&tool, args.as_object()).unwrap_err();
+        assert!(err.message.contains("txet"));
+
+        let args = serde_json::json!({ "text": "hi" });
+        assert!(crate::validation::reject_unknown_arguments(&tool, args.as_object()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_case_probe_variants() {
+        let server = test_server();
+        assert_eq!(server.case_probe().await, "case_probe");
+        assert_eq!(server.case_probe_mixed().await, "Case_Probe");
+        assert_eq!(server.case_probe_upper().await, "CASE_PROBE");
+        assert!(server.get_tool("Case_Probe").is_some());
+        assert!(server.get_tool("case_probe ").is_none());
+    }
+
+    #[test]
+    fn test_resolve_tool_name_lenient() {
+        let server = test_server();
+        assert_eq!(server.resolve_tool_name("echo"), None);
+        assert_eq!(server.resolve_tool_name(" ECHO "), Some("echo".to_string()));
+        assert_eq!(
+            server.resolve_tool_name("case_probe "),
+            Some("case_probe".to_string())
+        );
+        assert_eq!(server.resolve_tool_name("no_such_tool"), None);
+    }
+
+    #[test]
+    fn test_resolve_resource_uri_lenient() {
+        let server = test_server();
+        assert_eq!(
+            server
+                .resolve_resource_uri("test://static/HELLO.txt")
+                .as_deref(),
+            Some("test://static/hello.txt")
+        );
+        assert_eq!(
+            server
+                .resolve_resource_uri("test://case/readme.txt ")
+                .as_deref(),
+            Some("test://case/readme.txt ")
+        );
+        assert_eq!(server.resolve_resource_uri("test://nowhere"), None);
+    }
+
+    #[test]
+    fn test_resolve_resource_uri_lenient_beyond_static_list() {
+        let server = test_server();
+        assert_eq!(
+            server
+                .resolve_resource_uri(" TEST://Repos/Octo/Hello ")
+                .as_deref(),
+            Some("test://repos/Octo/Hello")
+        );
+
+        server
+            .resource_handler()
+            .scratch()
+            .write(
+                "Notes",
+                crate::resources::scratch_resources::ScratchEntry {
+                    text: "hi".to_string(),
+                    mime_type: "text/plain".to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            server
+                .resolve_resource_uri("test://SCRATCH/Notes")
+                .as_deref(),
+            Some("test://scratch/Notes")
+        );
+    }
+
     // =============================================================================
     // SERVER INFO TESTS
     // =============================================================================
@@ -1203,6 +3747,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_server_icons_follow_config() {
+        let icons = test_server().get_info().server_info.icons.unwrap();
+        let types: Vec<_> = icons
+            .iter()
+            .filter_map(|i| i.mime_type.as_deref())
+            .collect();
+        assert_eq!(
+            types,
+            ["image/svg+xml", "image/png", "image/png", "image/png"]
+        );
+        assert_eq!(icons[3].sizes, Some(vec!["64x64".to_string()]));
+
+        let config = Config::builder()
+            .server_icons(crate::icons::ServerIcons::None)
+            .build();
+        assert!(
+            McpTestServer::new(config)
+                .get_info()
+                .server_info
+                .icons
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_default_initialize_shape() {
+        let info = test_server().get_info();
+        assert_eq!(info.server_info.name, "mcp-test-server");
+        assert_eq!(info.server_info.title.as_deref(), Some("MCP Test Server"));
+        assert!(info.instructions.is_some());
+        let resources = info.capabilities.resources.unwrap();
+        assert_eq!(resources.subscribe, Some(true));
+        assert!(info.capabilities.tasks.is_some());
+    }
+
+    #[test]
+    fn test_experimental_capabilities_are_advertised() {
+        assert!(test_server().get_info().capabilities.experimental.is_none());
+
+        let mut settings = serde_json::Map::new();
+        settings.insert("version".to_string(), serde_json::json!(2));
+        let config = Config::builder()
+            .experimental_capabilities([("acme/streaming".to_string(), settings)].into())
+            .capabilities([])
+            .build();
+        let info = McpTestServer::new(config).get_info();
+        let experimental = info.capabilities.experimental.unwrap();
+        assert_eq!(experimental["acme/streaming"]["version"], 2);
+    }
+
+    #[test]
+    fn test_ui_tools_have_title_icons_and_meta() {
+        let server = test_server();
+        let ui_tools: Vec<_> = server
+            .tool_router
+            .list_all()
+            .into_iter()
+            .filter(|tool| tool.name.starts_with("ui_"))
+            .collect();
+        assert_eq!(ui_tools.len(), 8);
+        for tool in ui_tools {
+            assert!(tool.title.is_some(), "{} has no title", tool.name);
+            let icons = tool.icons.as_ref().expect("icons should be Some");
+            assert_eq!(icons[0].mime_type.as_deref(), Some("image/svg+xml"));
+            assert!(icons[0].src.starts_with("data:image/svg+xml;base64,"));
+            let ui = &tool.meta.as_ref().expect("meta should be Some")["ui"];
+            if ui.get("resourceUri").is_none() {
+                assert_eq!(ui["visibility"], "app", "{}", tool.name);
+                continue;
+            }
+            assert!(ui["preferredSize"]["width"].as_u64().unwrap() > 0);
+            assert!(ui["preferredSize"]["height"].as_u64().unwrap() > 0);
+        }
+    }
+
     #[test]
     fn test_config_accessor() {
         let config = Config::default();
@@ -1229,13 +3849,52 @@ mod tests {
     // UI RESOURCE TOOL TESTS
     // =============================================================================
 
+    /// Assert a UI tool result is `message` followed by the embedded app.
+    fn assert_ui_result(result: &CallToolResult, message: &str, uri: &str) {
+        assert!(result.content[0].as_text().unwrap().text.contains(message));
+        let embedded = result.content[1].as_resource().unwrap();
+        let rmcp::model::ResourceContents::TextResourceContents {
+            uri: embedded_uri,
+            mime_type,
+            text,
+            ..
+        } = &embedded.resource
+        else {
+            panic!("Expected text resource contents");
+        };
+        assert_eq!(embedded_uri, uri);
+        assert_eq!(mime_type.as_deref(), Some("text/html;profile=mcp-app"));
+        assert!(text.contains("<html"));
+    }
+
+    #[tokio::test]
+    async fn test_rich_ui_tools_embed_their_apps() {
+        let server = test_server();
+        let dashboard = server
+            .ui_resource_dashboard(Parameters(UiResourceDashboardParams {}))
+            .await
+            .unwrap();
+        assert_ui_result(&dashboard, "Dashboard UI ready", "ui://dashboard/app.html");
+        let table = server
+            .ui_resource_data_table(Parameters(UiResourceDataTableParams {}))
+            .await
+            .unwrap();
+        assert_ui_result(&table, "Data table UI ready", "ui://data_table/app.html");
+        let pipeline = server
+            .ui_resource_pipeline(Parameters(UiResourcePipelineParams {}))
+            .await
+            .unwrap();
+        assert_ui_result(&pipeline, "Pipeline UI ready", "ui://pipeline/app.html");
+    }
+
     #[tokio::test]
     async fn test_ui_resource_button() {
         let server = test_server();
         let result = server
             .ui_resource_button(Parameters(UiResourceButtonParams {}))
-            .await;
-        assert!(result.contains("Button UI ready"));
+            .await
+            .unwrap();
+        assert_ui_result(&result, "Button UI ready", "ui://button/app.html");
     }
 
     #[tokio::test]
@@ -1243,8 +3902,9 @@ mod tests {
         let server = test_server();
         let result = server
             .ui_resource_form(Parameters(UiResourceFormParams {}))
-            .await;
-        assert!(result.contains("Form UI ready"));
+            .await
+            .unwrap();
+        assert_ui_result(&result, "Form UI ready", "ui://form/app.html");
     }
 
     #[tokio::test]
@@ -1252,8 +3912,9 @@ mod tests {
         let server = test_server();
         let result = server
             .ui_resource_carousel(Parameters(UiResourceCarouselParams {}))
-            .await;
-        assert!(result.contains("Carousel UI ready"));
+            .await
+            .unwrap();
+        assert_ui_result(&result, "Carousel UI ready", "ui://carousel/app.html");
     }
 
     #[tokio::test]
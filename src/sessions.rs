@@ -0,0 +1,673 @@
+//! Streamable HTTP session tracking, limits, and forced termination.
+//!
+//! In stateful mode every session created on `/mcp` is recorded with its
+//! creation time and request count. `GET /admin/sessions` lists the live
+//! sessions and `DELETE /admin/sessions/{id}` terminates one, so the client's
+//! next request with that `Mcp-Session-Id` gets `404 Not Found`, as after a
+//! server restart.
+//!
+//! With `MCP_MAX_SESSIONS` set, an `initialize` that would open one session
+//! too many is refused with `503 Service Unavailable` and a JSON-RPC error.
+//!
+//! A client ends its session with `DELETE /mcp`. With
+//! `MCP_DENY_SESSION_DELETE=true` the server refuses instead, with
+//! `405 Method Not Allowed` as the spec permits, and the session lives on.
+//!
+//! Every lifecycle change — created, deleted, delete refused, terminated,
+//! expired, rejected — is appended to an event log served at
+//! `GET /admin/sessions/events`, so tests can check what the server saw.
+//!
+//! While the server drains before shutdown (`MCP_DRAIN_SECS`), requests
+//! that would open a new session get `503` and each live session receives a
+//! final `notifications/message`; existing sessions keep being served.
+//!
+//! With `MCP_SESSION_TTL` set, a session that receives no request for that
+//! long expires: it is closed when the next request for it arrives (which
+//! then gets the spec's `404`), or when sessions are listed or counted.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use axum::body::{Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::{Method, StatusCode, header::ALLOW, request::Parts};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use rmcp::model::{ErrorCode, LoggingLevel, LoggingMessageNotificationParam};
+use rmcp::transport::streamable_http_server::SessionManager;
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+use rmcp::{Peer, RoleServer};
+use serde::Serialize;
+
+/// Header carrying the session ID.
+const SESSION_HEADER: &str = "mcp-session-id";
+
+/// Session key of requests sent without an `Mcp-Session-Id`: every request
+/// in stateless mode, and `initialize` in stateful mode.
+pub const DEFAULT_SESSION: &str = "default";
+
+/// Events kept in the lifecycle log; older ones are dropped.
+const MAX_EVENTS: usize = 1000;
+
+/// JSON-RPC error code sent when a new session is refused (session limit
+/// reached, or draining for shutdown).
+pub const SESSION_LIMIT_CODE: ErrorCode = ErrorCode(-32000);
+
+/// The session a request belongs to, by its HTTP parts: its
+/// `Mcp-Session-Id` header, or [`DEFAULT_SESSION`] without one.
+#[must_use]
+pub fn session_key(parts: Option<&Parts>) -> &str {
+    parts
+        .and_then(|parts| parts.headers.get(SESSION_HEADER))
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or(DEFAULT_SESSION)
+}
+
+#[derive(Debug)]
+struct SessionRecord {
+    created_at: DateTime<Utc>,
+    last_active: DateTime<Utc>,
+    requests: u64,
+}
+
+impl SessionRecord {
+    fn idle_for(&self, now: DateTime<Utc>) -> Duration {
+        (now - self.last_active).to_std().unwrap_or_default()
+    }
+}
+
+/// Live sessions of the Streamable HTTP transport.
+#[derive(Debug, Default)]
+pub struct SessionRegistry {
+    manager: Arc<LocalSessionManager>,
+    records: Mutex<HashMap<String, SessionRecord>>,
+    max_sessions: Option<usize>,
+    ttl: Option<Duration>,
+    deny_delete: bool,
+    events: Mutex<VecDeque<SessionEvent>>,
+    next_seq: Mutex<u64>,
+    /// Peers of initialized sessions, for server-initiated notifications.
+    peers: Mutex<HashMap<String, Peer<RoleServer>>>,
+    /// Revisions negotiated in `initialize`, for `MCP_STRICT_PROTOCOL_VERSION`.
+    protocol_versions: Mutex<HashMap<String, String>>,
+    draining: AtomicBool,
+}
+
+/// What happened to a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionEventKind {
+    /// `initialize` opened the session.
+    Created,
+    /// The client ended the session with `DELETE /mcp`.
+    Deleted,
+    /// The client sent `DELETE /mcp` and got `405` (`MCP_DENY_SESSION_DELETE`).
+    DeleteRefused,
+    /// An admin ended the session with `DELETE /admin/sessions/{id}`.
+    Terminated,
+    /// The session was idle for longer than `MCP_SESSION_TTL`.
+    Expired,
+    /// `initialize` was refused because of `MCP_MAX_SESSIONS` or draining.
+    Rejected,
+}
+
+/// One entry of the session lifecycle log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SessionEvent {
+    /// Position in the log, starting at 1.
+    pub seq: u64,
+    /// What happened.
+    pub kind: SessionEventKind,
+    /// The session, if one was involved (`rejected` has none).
+    pub session: Option<String>,
+    /// RFC 3339 time of the event.
+    pub at: String,
+}
+
+/// One live session, as listed by `GET /admin/sessions`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SessionInfo {
+    /// The `Mcp-Session-Id`.
+    pub id: String,
+    /// RFC 3339 time the session was created.
+    pub created_at: String,
+    /// RFC 3339 time of the latest request for the session.
+    pub last_active_at: String,
+    /// HTTP requests received for the session, including `initialize`.
+    pub requests: u64,
+}
+
+/// Response of `GET /admin/sessions`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SessionList {
+    /// Live sessions, oldest first.
+    pub sessions: Vec<SessionInfo>,
+    /// The `MCP_MAX_SESSIONS` limit, if any.
+    pub max_sessions: Option<usize>,
+    /// The `MCP_SESSION_TTL` idle timeout in milliseconds, if any.
+    pub session_ttl_ms: Option<u128>,
+}
+
+impl SessionRegistry {
+    /// Create an empty registry allowing at most `max_sessions` sessions.
+    #[must_use]
+    pub fn new(max_sessions: Option<usize>) -> Self {
+        Self {
+            max_sessions,
+            ..Self::default()
+        }
+    }
+
+    /// Expire sessions idle for longer than `ttl`.
+    #[must_use]
+    pub const fn with_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Answer `DELETE /mcp` with `405 Method Not Allowed`.
+    #[must_use]
+    pub const fn with_delete_denied(mut self, deny: bool) -> Self {
+        self.deny_delete = deny;
+        self
+    }
+
+    /// The session lifecycle log, oldest first.
+    #[must_use]
+    pub fn events(&self) -> Vec<SessionEvent> {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Clear the session lifecycle log. Sequence numbers keep increasing.
+    pub fn clear_events(&self) {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    fn record_event(&self, kind: SessionEventKind, session: Option<&str>) {
+        let seq = {
+            let mut next = self.next_seq.lock().unwrap_or_else(PoisonError::into_inner);
+            *next += 1;
+            *next
+        };
+        let mut events = self.events.lock().unwrap_or_else(PoisonError::into_inner);
+        if events.len() == MAX_EVENTS {
+            events.pop_front();
+        }
+        events.push_back(SessionEvent {
+            seq,
+            kind,
+            session: session.map(str::to_string),
+            at: Utc::now().to_rfc3339(),
+        });
+    }
+
+    /// Remember the peer of an initialized session.
+    pub fn attach_peer(&self, id: &str, peer: Peer<RoleServer>) {
+        self.peers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(id.to_string(), peer);
+    }
+
+    /// The peer of an initialized session, if it is known.
+    #[must_use]
+    pub fn peer(&self, id: &str) -> Option<Peer<RoleServer>> {
+        self.peers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(id)
+            .cloned()
+    }
+
+    /// Remember the revision `initialize` negotiated for a session.
+    pub fn set_protocol_version(&self, id: &str, version: String) {
+        self.protocol_versions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(id.to_string(), version);
+    }
+
+    /// The revision negotiated for a session, until the session ends.
+    #[must_use]
+    pub fn protocol_version(&self, id: &str) -> Option<String> {
+        self.protocol_versions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(id)
+            .cloned()
+    }
+
+    /// Refuse new sessions from now on; existing sessions are still served.
+    pub fn start_draining(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether the server is draining before shutdown.
+    #[must_use]
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Tell every initialized session that the server shuts down in `drain`.
+    ///
+    /// Sends a `warning` `notifications/message` with
+    /// `{"event": "shutdown", "drain_secs"}` as data. Returns the number of
+    /// sessions reached.
+    pub async fn notify_shutdown(&self, drain: Duration) -> usize {
+        let peers: Vec<(String, Peer<RoleServer>)> = self
+            .peers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(id, peer)| (id.clone(), peer.clone()))
+            .collect();
+        let mut reached = 0;
+        for (id, peer) in peers {
+            let param = LoggingMessageNotificationParam::new(
+                LoggingLevel::Warning,
+                serde_json::json!({ "event": "shutdown", "drain_secs": drain.as_secs() }),
+            )
+            .with_logger("mcp-test-server");
+            match peer.notify_logging_message(param).await {
+                Ok(()) => reached += 1,
+                Err(e) => tracing::debug!(session = %id, error = %e, "Shutdown notice not sent"),
+            }
+        }
+        reached
+    }
+
+    /// The session manager to hand to the Streamable HTTP service.
+    #[must_use]
+    pub fn manager(&self) -> Arc<LocalSessionManager> {
+        Arc::clone(&self.manager)
+    }
+
+    /// Number of live sessions, after expiring idle ones.
+    pub async fn len(&self) -> usize {
+        self.expire_idle().await;
+        self.manager.sessions.read().await.len()
+    }
+
+    /// Whether there are no live sessions.
+    pub async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+
+    /// List live sessions, forgetting records of sessions that have ended.
+    pub async fn list(&self) -> SessionList {
+        self.expire_idle().await;
+        let live: Vec<String> = self
+            .manager
+            .sessions
+            .read()
+            .await
+            .keys()
+            .map(ToString::to_string)
+            .collect();
+        self.protocol_versions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|id, _| live.contains(id));
+        let mut records = self.records.lock().unwrap_or_else(PoisonError::into_inner);
+        records.retain(|id, _| live.contains(id));
+        let mut sessions: Vec<SessionInfo> = records
+            .iter()
+            .map(|(id, record)| SessionInfo {
+                id: id.clone(),
+                created_at: record.created_at.to_rfc3339(),
+                last_active_at: record.last_active.to_rfc3339(),
+                requests: record.requests,
+            })
+            .collect();
+        drop(records);
+        sessions.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        SessionList {
+            sessions,
+            max_sessions: self.max_sessions,
+            session_ttl_ms: self.ttl.map(|ttl| ttl.as_millis()),
+        }
+    }
+
+    /// Close every session idle for longer than the TTL.
+    pub async fn expire_idle(&self) {
+        let Some(ttl) = self.ttl else { return };
+        let now = Utc::now();
+        let expired: Vec<String> = self
+            .records
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter(|(_, record)| record.idle_for(now) > ttl)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in expired {
+            self.expire(&id).await;
+        }
+    }
+
+    async fn expire(&self, id: &str) {
+        tracing::info!(session = %id, "Session expired after inactivity");
+        self.record_event(SessionEventKind::Expired, Some(id));
+        self.close(id).await;
+    }
+
+    /// Terminate a session. Returns `false` if no such session is live.
+    pub async fn terminate(&self, id: &str) -> bool {
+        let live = self.close(id).await;
+        if live {
+            tracing::info!(session = %id, "Session terminated by admin request");
+            self.record_event(SessionEventKind::Terminated, Some(id));
+        }
+        live
+    }
+
+    /// Close a session and drop its record. Returns whether it was live.
+    async fn close(&self, id: &str) -> bool {
+        let session_id: Arc<str> = Arc::from(id);
+        let live = self.manager.has_session(&session_id).await.unwrap_or(false);
+        if live && let Err(e) = self.manager.close_session(&session_id).await {
+            tracing::warn!(session = %id, error = %e, "Failed to close session");
+        }
+        self.forget(id);
+        live
+    }
+
+    fn created(&self, id: &str) {
+        self.record_event(SessionEventKind::Created, Some(id));
+        let now = Utc::now();
+        self.records
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(
+                id.to_string(),
+                SessionRecord {
+                    created_at: now,
+                    last_active: now,
+                    requests: 1,
+                },
+            );
+    }
+
+    /// Count a request for `id`. Returns `false` if the session had been
+    /// idle for longer than the TTL; the request is then not counted.
+    #[allow(clippy::significant_drop_tightening)]
+    fn touched(&self, id: &str) -> bool {
+        let now = Utc::now();
+        let mut records = self.records.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(record) = records.get_mut(id) else {
+            return true;
+        };
+        if self.ttl.is_some_and(|ttl| record.idle_for(now) > ttl) {
+            return false;
+        }
+        record.requests += 1;
+        record.last_active = now;
+        true
+    }
+
+    fn forget(&self, id: &str) {
+        self.records
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(id);
+        self.peers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(id);
+        self.protocol_versions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(id);
+    }
+}
+
+/// Track sessions on `/mcp` and enforce `MCP_MAX_SESSIONS`.
+pub async fn session_middleware(
+    State(registry): State<Arc<SessionRegistry>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    let session = request
+        .headers()
+        .get(SESSION_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    if let Some(id) = session {
+        if !registry.touched(&id) {
+            // Close it so the transport answers 404 session not found
+            registry.expire(&id).await;
+        }
+        if method == Method::DELETE && registry.deny_delete {
+            tracing::info!(session = %id, "Refusing client session termination");
+            registry.record_event(SessionEventKind::DeleteRefused, Some(&id));
+            return (
+                StatusCode::METHOD_NOT_ALLOWED,
+                [(ALLOW, "GET, POST")],
+                "Session termination is not allowed",
+            )
+                .into_response();
+        }
+        let response = next.run(request).await;
+        if method == Method::DELETE && response.status().is_success() {
+            tracing::info!(session = %id, "Session deleted by client");
+            registry.record_event(SessionEventKind::Deleted, Some(&id));
+            registry.forget(&id);
+        }
+        return response;
+    }
+
+    // Only an `initialize` without a session ID opens a session
+    let (parts, body) = request.into_parts();
+    let bytes = if method == Method::POST {
+        match axum::body::to_bytes(body, usize::MAX).await {
+            Ok(bytes) => bytes,
+            Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        }
+    } else {
+        Bytes::new()
+    };
+    let opens_session = serde_json::from_slice::<serde_json::Value>(&bytes)
+        .is_ok_and(|message| message.get("method").and_then(|m| m.as_str()) == Some("initialize"));
+    let request = Request::from_parts(parts, Body::from(bytes));
+
+    if opens_session && registry.is_draining() {
+        tracing::info!("Draining for shutdown, rejecting new session");
+        registry.record_event(SessionEventKind::Rejected, None);
+        return rejection_response(
+            "Server is shutting down",
+            &serde_json::json!({ "draining": true }),
+        );
+    }
+    if opens_session
+        && let Some(max) = registry.max_sessions
+        && registry.len().await >= max
+    {
+        tracing::info!(max, "Session limit reached, rejecting initialize");
+        registry.record_event(SessionEventKind::Rejected, None);
+        return rejection_response(
+            &format!("Session limit reached (max {max})"),
+            &serde_json::json!({ "max_sessions": max }),
+        );
+    }
+
+    let response = next.run(request).await;
+    if let Some(id) = response
+        .headers()
+        .get(SESSION_HEADER)
+        .and_then(|v| v.to_str().ok())
+    {
+        registry.created(id);
+    }
+    response
+}
+
+/// `503` with a JSON-RPC error, for a request that may not open a session.
+fn rejection_response(message: &str, data: &serde_json::Value) -> Response {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": null,
+        "error": {
+            "code": SESSION_LIMIT_CODE.0,
+            "message": message,
+            "data": data,
+        },
+    });
+    (StatusCode::SERVICE_UNAVAILABLE, axum::Json(body)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_records_follow_live_sessions() {
+        let registry = SessionRegistry::new(None);
+        let (id, _transport) = registry.manager.create_session().await.unwrap();
+        registry.created(&id);
+        assert!(registry.touched(&id));
+        // A record without a live session is dropped from the listing
+        registry.created("gone");
+
+        let list = registry.list().await;
+        assert_eq!(list.sessions.len(), 1);
+        assert_eq!(list.sessions[0].id, id.to_string());
+        assert_eq!(list.sessions[0].requests, 2);
+
+        assert!(registry.terminate(&id).await);
+        assert!(registry.is_empty().await);
+        assert!(!registry.terminate(&id).await);
+        assert!(registry.list().await.sessions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_idle_sessions_expire() {
+        let registry = SessionRegistry::new(None).with_ttl(Some(Duration::from_secs(60)));
+        let (idle, _idle_transport) = registry.manager.create_session().await.unwrap();
+        let (busy, _busy_transport) = registry.manager.create_session().await.unwrap();
+        registry.created(&idle);
+        registry.created(&busy);
+        registry
+            .records
+            .lock()
+            .unwrap()
+            .get_mut(&*idle)
+            .unwrap()
+            .last_active -= chrono::TimeDelta::minutes(2);
+
+        assert!(!registry.touched(&idle));
+        assert!(registry.touched(&busy));
+        assert_eq!(registry.len().await, 1);
+        let list = registry.list().await;
+        assert_eq!(list.sessions[0].id, busy.to_string());
+        assert_eq!(list.session_ttl_ms, Some(60_000));
+    }
+
+    #[tokio::test]
+    async fn test_protocol_versions_end_with_their_session() {
+        let registry = SessionRegistry::new(None).with_ttl(Some(Duration::from_secs(60)));
+        let (terminated, _terminated_transport) = registry.manager.create_session().await.unwrap();
+        let (idle, _idle_transport) = registry.manager.create_session().await.unwrap();
+        for id in [&terminated, &idle] {
+            registry.created(id);
+            registry.set_protocol_version(id, "2025-06-18".to_string());
+        }
+        // Recorded for a session that ended without the registry noticing
+        registry.set_protocol_version("gone", "2025-06-18".to_string());
+        assert_eq!(
+            registry.protocol_version(&idle).as_deref(),
+            Some("2025-06-18")
+        );
+
+        assert!(registry.terminate(&terminated).await);
+        assert_eq!(registry.protocol_version(&terminated), None);
+        registry
+            .records
+            .lock()
+            .unwrap()
+            .get_mut(&*idle)
+            .unwrap()
+            .last_active -= chrono::TimeDelta::minutes(2);
+        registry.list().await;
+        assert_eq!(registry.protocol_version(&idle), None);
+        assert_eq!(registry.protocol_version("gone"), None);
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_events_are_logged() {
+        let registry = SessionRegistry::new(None);
+        let (id, _transport) = registry.manager.create_session().await.unwrap();
+        registry.created(&id);
+        registry.record_event(SessionEventKind::Rejected, None);
+        assert!(registry.terminate(&id).await);
+
+        let events = registry.events();
+        let kinds: Vec<_> = events.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                SessionEventKind::Created,
+                SessionEventKind::Rejected,
+                SessionEventKind::Terminated
+            ]
+        );
+        assert_eq!(events[2].seq, 3);
+        assert_eq!(events[1].session, None);
+
+        registry.clear_events();
+        assert!(registry.events().is_empty());
+        registry.record_event(SessionEventKind::Created, Some("next"));
+        assert_eq!(registry.events()[0].seq, 4);
+    }
+
+    #[tokio::test]
+    async fn test_event_log_is_bounded() {
+        let registry = SessionRegistry::new(None);
+        for _ in 0..=MAX_EVENTS {
+            registry.record_event(SessionEventKind::Rejected, None);
+        }
+        let events = registry.events();
+        assert_eq!(events.len(), MAX_EVENTS);
+        assert_eq!(events[0].seq, 2);
+    }
+
+    #[tokio::test]
+    async fn test_draining_keeps_live_sessions() {
+        let registry = SessionRegistry::new(None);
+        let (id, _transport) = registry.manager.create_session().await.unwrap();
+        registry.created(&id);
+        assert!(!registry.is_draining());
+        registry.start_draining();
+        assert!(registry.is_draining());
+        // No initialized peers to reach, and live sessions stay listed
+        assert_eq!(registry.notify_shutdown(Duration::from_secs(1)).await, 0);
+        assert_eq!(registry.list().await.sessions.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_session_limit_response() {
+        let response = rejection_response(
+            "Session limit reached (max 2)",
+            &serde_json::json!({ "max_sessions": 2 }),
+        );
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], SESSION_LIMIT_CODE.0);
+        assert_eq!(body["error"]["data"]["max_sessions"], 2);
+    }
+}
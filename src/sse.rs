@@ -0,0 +1,402 @@
+//! Server-sent event rewriting for transport-level negative testing.
+//!
+//! The MCP SDK writes each JSON-RPC message as a single SSE event. When a
+//! maximum event size is configured, this middleware inspects every event on
+//! `text/event-stream` responses and applies an [`SseOversizePolicy`] to the
+//! ones whose `data` payload exceeds the limit. Body chunks are buffered as
+//! raw bytes until an event's blank line (`\n\n` or `\r\n\r\n`) arrives, so
+//! events and UTF-8 sequences split across chunks are measured whole.
+//!
+//! With slow drip configured ([`SseDrip`]), [`drip_middleware`] re-chunks
+//! event-stream bodies into pieces of a few bytes and pauses between them,
+//! so events (and the JSON inside them, even UTF-8 sequences) arrive split
+//! across several reads.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use axum::body::{Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::header::CONTENT_TYPE;
+use axum::middleware::Next;
+use axum::response::Response;
+use futures_util::StreamExt;
+
+/// What to do with an SSE event whose payload exceeds the maximum size.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SseOversizePolicy {
+    /// Replace the event with a JSON-RPC error for the same request ID.
+    /// Oversized notifications are dropped.
+    #[default]
+    Reject,
+    /// Split the JSON payload across several events (deliberately invalid:
+    /// each event carries a fragment that is not valid JSON on its own).
+    Split,
+}
+
+impl SseOversizePolicy {
+    /// Parse a policy name (`reject` or `split`, case-insensitive).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "reject" => Some(Self::Reject),
+            "split" => Some(Self::Split),
+            _ => None,
+        }
+    }
+}
+
+/// Maximum SSE event size and the policy applied to oversized events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SseEventLimit {
+    /// Maximum `data` payload size of a single event, in bytes.
+    pub max_bytes: usize,
+    /// What to do with events over the limit.
+    pub policy: SseOversizePolicy,
+}
+
+/// Middleware enforcing an [`SseEventLimit`] on event-stream responses.
+pub async fn event_size_middleware(
+    State(limit): State<SseEventLimit>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let response = next.run(request).await;
+    let is_event_stream = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/event-stream"));
+    if !is_event_stream {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let state = (body.into_data_stream(), Vec::new(), VecDeque::new(), false);
+    let events = futures_util::stream::unfold(
+        state,
+        move |(mut body, mut buffer, mut ready, mut done)| async move {
+            loop {
+                if let Some(event) = ready.pop_front() {
+                    return Some((Ok::<_, axum::Error>(event), (body, buffer, ready, done)));
+                }
+                if done {
+                    return None;
+                }
+                match body.next().await {
+                    Some(Ok(chunk)) => {
+                        buffer.extend_from_slice(&chunk);
+                        while let Some(event) = next_event(&mut buffer) {
+                            match std::str::from_utf8(&event) {
+                                Ok(text) => ready.extend(
+                                    enforce_limit(text, limit).into_iter().map(Bytes::from),
+                                ),
+                                // Not text: nothing to measure, pass it on
+                                Err(_) => ready.push_back(event),
+                            }
+                        }
+                    }
+                    Some(Err(e)) => return Some((Err(e), (body, buffer, ready, true))),
+                    None => {
+                        done = true;
+                        if !buffer.is_empty() {
+                            ready.push_back(Bytes::from(std::mem::take(&mut buffer)));
+                        }
+                    }
+                }
+            }
+        },
+    );
+
+    Response::from_parts(parts, Body::from_stream(events))
+}
+
+/// Chunk size and pacing of slowly dripped event streams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SseDrip {
+    /// Largest chunk flushed at once, in bytes.
+    pub chunk_bytes: usize,
+    /// Pause before each chunk after the first.
+    pub delay: Duration,
+}
+
+/// Middleware flushing event-stream responses in [`SseDrip`] chunks.
+pub async fn drip_middleware(
+    State(drip): State<SseDrip>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let response = next.run(request).await;
+    let is_event_stream = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/event-stream"));
+    if !is_event_stream {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let chunk_bytes = drip.chunk_bytes.max(1);
+    let state = (body.into_data_stream(), Bytes::new(), false);
+    let chunks =
+        futures_util::stream::unfold(state, move |(mut body, mut pending, flushed)| async move {
+            while pending.is_empty() {
+                match body.next().await? {
+                    Ok(chunk) => pending = chunk,
+                    Err(e) => return Some((Err(e), (body, pending, flushed))),
+                }
+            }
+            if flushed {
+                tokio::time::sleep(drip.delay).await;
+            }
+            let chunk = pending.split_to(chunk_bytes.min(pending.len()));
+            Some((Ok::<_, axum::Error>(chunk), (body, pending, true)))
+        });
+
+    Response::from_parts(parts, Body::from_stream(chunks))
+}
+
+/// Take the first complete event, with its blank line, off the front of
+/// `buffer`.
+///
+/// Events end at the first `\n\n` or `\r\n\r\n`; `None` until one has
+/// arrived.
+fn next_event(buffer: &mut Vec<u8>) -> Option<Bytes> {
+    let end = buffer
+        .windows(2)
+        .enumerate()
+        .find_map(|(i, pair)| match pair {
+            b"\n\n" => Some(i + 2),
+            b"\r\n" if buffer[i + 2..].starts_with(b"\r\n") => Some(i + 4),
+            _ => None,
+        })?;
+    Some(buffer.drain(..end).collect::<Vec<_>>().into())
+}
+
+/// Apply the limit to one complete event (including its trailing blank line).
+///
+/// Returns the events to send in its place: the event itself if it is within
+/// the limit, several fragments under [`SseOversizePolicy::Split`], an error
+/// event or nothing under [`SseOversizePolicy::Reject`].
+#[must_use]
+pub fn enforce_limit(event: &str, limit: SseEventLimit) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut data_lines = Vec::new();
+    for line in event.lines().filter(|l| !l.is_empty()) {
+        match line.strip_prefix("data:") {
+            Some(data) => data_lines.push(data.strip_prefix(' ').unwrap_or(data)),
+            None => fields.push(line),
+        }
+    }
+    let data = data_lines.join("\n");
+    if data.len() <= limit.max_bytes {
+        return vec![event.to_string()];
+    }
+
+    match limit.policy {
+        SseOversizePolicy::Split => {
+            let chunks = split_utf8(&data, limit.max_bytes.max(1));
+            tracing::debug!(
+                size = data.len(),
+                events = chunks.len(),
+                "Splitting oversized SSE event"
+            );
+            chunks
+                .into_iter()
+                .enumerate()
+                .map(|(i, chunk)| {
+                    let mut out = String::new();
+                    if i == 0 {
+                        for field in &fields {
+                            out.push_str(field);
+                            out.push('\n');
+                        }
+                    }
+                    out.push_str("data: ");
+                    out.push_str(chunk);
+                    out.push_str("\n\n");
+                    out
+                })
+                .collect()
+        }
+        SseOversizePolicy::Reject => {
+            let id = serde_json::from_str::<serde_json::Value>(&data)
+                .ok()
+                .and_then(|v| v.get("id").cloned());
+            let Some(id) = id else {
+                tracing::warn!(size = data.len(), "Dropping oversized SSE notification");
+                return Vec::new();
+            };
+            tracing::warn!(size = data.len(), %id, "Rejecting oversized SSE event");
+            let error = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {
+                    "code": -32603,
+                    "message": format!(
+                        "SSE event of {} bytes exceeds the maximum of {} bytes",
+                        data.len(),
+                        limit.max_bytes
+                    ),
+                    "data": { "size": data.len(), "max": limit.max_bytes }
+                }
+            });
+            let mut out = String::new();
+            for field in &fields {
+                out.push_str(field);
+                out.push('\n');
+            }
+            out.push_str("data: ");
+            out.push_str(&error.to_string());
+            out.push_str("\n\n");
+            vec![out]
+        }
+    }
+}
+
+/// Split a string into chunks of at most `max` bytes on character boundaries.
+fn split_utf8(s: &str, max: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = s;
+    while !rest.is_empty() {
+        let mut end = max.min(rest.len());
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        if end == 0 {
+            // A single character wider than the limit
+            end = rest.chars().next().map_or(rest.len(), char::len_utf8);
+        }
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limit(max_bytes: usize, policy: SseOversizePolicy) -> SseEventLimit {
+        SseEventLimit { max_bytes, policy }
+    }
+
+    #[test]
+    fn test_small_event_unchanged() {
+        let event = "id: 1\ndata: {\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{}}\n\n";
+        assert_eq!(
+            enforce_limit(event, limit(1024, SseOversizePolicy::Reject)),
+            vec![event.to_string()]
+        );
+    }
+
+    #[test]
+    fn test_split_policy_fragments_payload() {
+        let payload = r#"{"jsonrpc":"2.0","id":7,"result":{"text":"aaaaaaaaaaaaaaaa"}}"#;
+        let event = format!("id: 0\ndata: {payload}\n\n");
+        let events = enforce_limit(&event, limit(16, SseOversizePolicy::Split));
+        assert!(events.len() > 1);
+        assert!(events[0].starts_with("id: 0\n"));
+
+        let mut joined = String::new();
+        for e in &events {
+            let data = e.strip_prefix("id: 0\n").unwrap_or(e);
+            let data = data
+                .strip_prefix("data: ")
+                .unwrap()
+                .strip_suffix("\n\n")
+                .unwrap();
+            assert!(data.len() <= 16);
+            joined.push_str(data);
+        }
+        assert_eq!(joined, payload);
+    }
+
+    #[test]
+    fn test_reject_policy_returns_error_for_request() {
+        let event = r#"data: {"jsonrpc":"2.0","id":"abc","result":{"text":"xxxxxxxxxxxxxxxxxxxx"}}
+
+"#;
+        let events = enforce_limit(event, limit(10, SseOversizePolicy::Reject));
+        assert_eq!(events.len(), 1);
+        let data = events[0].strip_prefix("data: ").unwrap().trim();
+        let json: serde_json::Value = serde_json::from_str(data).unwrap();
+        assert_eq!(json["id"], "abc");
+        assert_eq!(json["error"]["code"], -32603);
+        assert_eq!(json["error"]["data"]["max"], 10);
+    }
+
+    #[test]
+    fn test_reject_policy_drops_notifications() {
+        let event =
+            "data: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/message\",\"params\":{}}\n\n";
+        assert!(enforce_limit(event, limit(10, SseOversizePolicy::Reject)).is_empty());
+    }
+
+    #[test]
+    fn test_next_event_cuts_at_either_blank_line() {
+        let mut buffer = b"data: a\n\ndata: b\r\n\r\ndata: c\n".to_vec();
+        assert_eq!(next_event(&mut buffer).unwrap(), "data: a\n\n");
+        assert_eq!(next_event(&mut buffer).unwrap(), "data: b\r\n\r\n");
+        assert_eq!(next_event(&mut buffer), None);
+        assert_eq!(buffer, b"data: c\n");
+    }
+
+    #[tokio::test]
+    async fn test_middleware_measures_events_split_across_frames() {
+        use tower::ServiceExt;
+
+        // 6 two-byte characters, cut inside the third one
+        let event = "data: {\"id\":1,\"text\":\"éééééé\"}\r\n\r\n".as_bytes();
+        let cut = event.iter().position(|&b| b == 0xC3).unwrap() + 5;
+        let frames = [
+            Bytes::copy_from_slice(&event[..cut]),
+            Bytes::copy_from_slice(&event[cut..]),
+        ];
+        let app = axum::Router::new()
+            .route(
+                "/",
+                axum::routing::get(move || async move {
+                    let frames = futures_util::stream::iter(frames.map(Ok::<_, std::io::Error>));
+                    Response::builder()
+                        .header(CONTENT_TYPE, "text/event-stream")
+                        .body(Body::from_stream(frames))
+                        .unwrap()
+                }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                limit(1024, SseOversizePolicy::Reject),
+                event_size_middleware,
+            ));
+        let response = app
+            .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, event);
+    }
+
+    #[test]
+    fn test_split_utf8_respects_char_boundaries() {
+        let chunks = split_utf8("héllo wörld", 2);
+        assert!(chunks.iter().all(|c| !c.is_empty()));
+        assert_eq!(chunks.concat(), "héllo wörld");
+    }
+
+    #[test]
+    fn test_policy_parse() {
+        assert_eq!(
+            SseOversizePolicy::parse("SPLIT"),
+            Some(SseOversizePolicy::Split)
+        );
+        assert_eq!(
+            SseOversizePolicy::parse("reject"),
+            Some(SseOversizePolicy::Reject)
+        );
+        assert_eq!(SseOversizePolicy::parse("truncate"), None);
+    }
+}
@@ -0,0 +1,154 @@
+//! Panic supervision, request timeouts, and deliberate connection aborts.
+//!
+//! Tool calls run under `catch_unwind`, so a panicking handler cannot take
+//! its request down silently. What the client sees is set by the
+//! [`PanicPolicy`]: a JSON-RPC internal error, or an HTTP response cut off
+//! mid-stream. With a request timeout configured, a call still running when
+//! it expires is dropped and answered with [`request_timed_out`].
+//!
+//! Cutting a response works through [`ConnectionAbort`]:
+//! [`abort_middleware`] gives every `/mcp` request one (tools find it in the
+//! request's `http::request::Parts`), and once triggered the response body
+//! fails, so the server drops the connection instead of finishing the
+//! response.
+
+use std::any::Any;
+
+use axum::body::Body;
+use axum::extract::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+use futures_util::StreamExt;
+use tokio_util::sync::CancellationToken;
+
+/// How a panic in a tool handler is reported to the client.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PanicPolicy {
+    /// Answer with a JSON-RPC internal error (`-32603`) naming the tool.
+    #[default]
+    Error,
+    /// Drop the connection mid-response; no answer is sent.
+    Abort,
+}
+
+impl PanicPolicy {
+    /// Parse a policy name (`error` or `abort`, case-insensitive).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "error" => Some(Self::Error),
+            "abort" => Some(Self::Abort),
+            _ => None,
+        }
+    }
+}
+
+/// Handle that cuts off the HTTP response of one request.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionAbort(CancellationToken);
+
+impl ConnectionAbort {
+    /// Fail the response body, dropping the connection.
+    pub fn trigger(&self) {
+        self.0.cancel();
+    }
+
+    /// Whether the response has been cut off.
+    #[must_use]
+    pub fn is_triggered(&self) -> bool {
+        self.0.is_cancelled()
+    }
+
+    /// The handle of the HTTP request a tool call arrived on, if any.
+    #[must_use]
+    pub fn of(extensions: &rmcp::model::Extensions) -> Option<&Self> {
+        extensions
+            .get::<axum::http::request::Parts>()
+            .and_then(|parts| parts.extensions.get::<Self>())
+    }
+}
+
+/// Middleware giving each request a [`ConnectionAbort`] for its response.
+pub async fn abort_middleware(mut request: Request, next: Next) -> Response {
+    let abort = ConnectionAbort::default();
+    request.extensions_mut().insert(abort.clone());
+    let response = next.run(request).await;
+
+    let (parts, body) = response.into_parts();
+    let chunks = futures_util::stream::unfold(Some(body.into_data_stream()), move |body| {
+        let abort = abort.clone();
+        async move {
+            let mut body = body?;
+            tokio::select! {
+                biased;
+                () = abort.0.cancelled() => Some((
+                    Err(axum::Error::new(std::io::Error::new(
+                        std::io::ErrorKind::ConnectionAborted,
+                        "connection aborted on purpose",
+                    ))),
+                    None,
+                )),
+                chunk = body.next() => chunk.map(|chunk| (chunk, Some(body))),
+            }
+        }
+    });
+    Response::from_parts(parts, Body::from_stream(chunks))
+}
+
+/// Error code of a tool call that outlived the request timeout.
+///
+/// JSON-RPC reserves `-32000` to `-32099` for server errors; `-32001` is the
+/// code MCP SDKs use for request timeouts.
+pub const REQUEST_TIMEOUT: rmcp::model::ErrorCode = rmcp::model::ErrorCode(-32001);
+
+/// The error for a call to `tool` that ran longer than `timeout`.
+#[must_use]
+pub fn request_timed_out(tool: &str, timeout: std::time::Duration) -> rmcp::ErrorData {
+    let timeout_ms = u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX);
+    rmcp::ErrorData::new(
+        REQUEST_TIMEOUT,
+        format!("Request timed out after {timeout_ms}ms"),
+        Some(serde_json::json!({ "tool": tool, "timeout_ms": timeout_ms })),
+    )
+}
+
+/// The message of a caught panic payload.
+#[must_use]
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| (*s).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "non-string panic payload".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(PanicPolicy::parse("error"), Some(PanicPolicy::Error));
+        assert_eq!(PanicPolicy::parse(" ABORT "), Some(PanicPolicy::Abort));
+        assert_eq!(PanicPolicy::parse("ignore"), None);
+    }
+
+    #[test]
+    fn test_request_timed_out() {
+        let err = request_timed_out("hang", std::time::Duration::from_millis(1500));
+        assert_eq!(err.code, REQUEST_TIMEOUT);
+        assert_eq!(err.message, "Request timed out after 1500ms");
+        assert_eq!(err.data.unwrap()["timeout_ms"], 1500);
+    }
+
+    #[test]
+    fn test_panic_message() {
+        let payload = std::panic::catch_unwind(|| panic!("static")).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "static");
+        let code = 7;
+        let payload = std::panic::catch_unwind(|| panic!("formatted {code}")).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "formatted 7");
+        let payload = std::panic::catch_unwind(|| std::panic::panic_any(7)).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "non-string panic payload");
+    }
+}
@@ -0,0 +1,139 @@
+//! How tool failures reach the client.
+//!
+//! MCP has two ways to report a failed tool call: a `CallToolResult` with
+//! `isError: true`, which the model gets to see, or a JSON-RPC error. Which
+//! one a built-in tool uses depends on how it is written, so on its own the
+//! server exercises only one path per tool. A [`ToolErrorMode`]
+//! (`MCP_TOOL_ERROR_MODE`) makes every tool use the same path, so clients can
+//! be tested against both.
+//!
+//! Invalid params errors (`-32602`: unknown tool, bad arguments) are about
+//! the request rather than the tool's execution and are never converted.
+
+use rmcp::ErrorData as McpError;
+use rmcp::model::{CallToolResult, Content, ErrorCode, RawContent};
+
+/// How failed tool calls are reported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ToolErrorMode {
+    /// Each tool reports failures its own way.
+    #[default]
+    Native,
+    /// Every failure is an `isError` result.
+    Result,
+    /// Every failure is a JSON-RPC error.
+    Protocol,
+}
+
+impl ToolErrorMode {
+    /// Parse a mode name (`native`, `result`, or `protocol`,
+    /// case-insensitive).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "native" => Some(Self::Native),
+            "result" => Some(Self::Result),
+            "protocol" => Some(Self::Protocol),
+            _ => None,
+        }
+    }
+
+    /// Report the outcome of a call to `tool` the way this mode says.
+    ///
+    /// # Errors
+    ///
+    /// Returns the JSON-RPC error the client should see: the tool's own
+    /// error, or under [`Self::Protocol`] an `isError` result turned into an
+    /// internal error (`-32603`) with `{"tool", "content"}` as data.
+    pub fn report(
+        self,
+        tool: &str,
+        called: Result<CallToolResult, McpError>,
+    ) -> Result<CallToolResult, McpError> {
+        match (self, called) {
+            (Self::Protocol, Ok(result)) if result.is_error == Some(true) => {
+                Err(protocol_error(tool, &result))
+            }
+            (Self::Result, Err(error)) if error.code != ErrorCode::INVALID_PARAMS => {
+                Ok(CallToolResult::error(vec![Content::text(error.message)]))
+            }
+            (_, called) => called,
+        }
+    }
+}
+
+/// The JSON-RPC error equivalent to an `isError` result.
+fn protocol_error(tool: &str, result: &CallToolResult) -> McpError {
+    let text: Vec<&str> = result
+        .content
+        .iter()
+        .filter_map(|c| match &c.raw {
+            RawContent::Text(text) => Some(text.text.as_str()),
+            _ => None,
+        })
+        .collect();
+    let message = if text.is_empty() {
+        format!("Tool {tool} failed")
+    } else {
+        text.join("\n")
+    };
+    McpError::internal_error(
+        message,
+        Some(serde_json::json!({ "tool": tool, "content": result.content })),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(ToolErrorMode::parse("Result"), Some(ToolErrorMode::Result));
+        assert_eq!(
+            ToolErrorMode::parse(" protocol "),
+            Some(ToolErrorMode::Protocol)
+        );
+        assert_eq!(ToolErrorMode::parse("native"), Some(ToolErrorMode::Native));
+        assert_eq!(ToolErrorMode::parse("both"), None);
+    }
+
+    #[test]
+    fn test_protocol_mode_converts_error_results() {
+        let failed = CallToolResult::error(vec![Content::text("Division by zero")]);
+        let err = ToolErrorMode::Protocol
+            .report("divide", Ok(failed))
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::INTERNAL_ERROR);
+        assert_eq!(err.message, "Division by zero");
+        assert_eq!(err.data.unwrap()["tool"], "divide");
+
+        let ok = CallToolResult::success(vec![Content::text("2")]);
+        assert!(ToolErrorMode::Protocol.report("divide", Ok(ok)).is_ok());
+    }
+
+    #[test]
+    fn test_result_mode_converts_errors_except_invalid_params() {
+        let result = ToolErrorMode::Result
+            .report("hang", Err(McpError::internal_error("Timed out", None)))
+            .unwrap();
+        assert_eq!(result.is_error, Some(true));
+
+        let err = ToolErrorMode::Result
+            .report("nope", Err(McpError::invalid_params("Unknown tool", None)))
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::INVALID_PARAMS);
+    }
+
+    #[test]
+    fn test_native_mode_passes_through() {
+        let failed = CallToolResult::error(vec![Content::text("no")]);
+        let result = ToolErrorMode::Native.report("t", Ok(failed)).unwrap();
+        assert_eq!(result.is_error, Some(true));
+        assert!(
+            ToolErrorMode::Native
+                .report("t", Err(McpError::internal_error("x", None)))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//! Array tools: `sort_array`, `dedup_array`.
+//!
+//! Both take arrays of any JSON values, so clients see an array-of-any input
+//! schema. Sorting needs every sort key to be a number or every one a string;
+//! anything else gives an `isError` result naming the first element at fault.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Sort direction of the `sort_array` tool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// Smallest first.
+    #[default]
+    Asc,
+    /// Largest first.
+    Desc,
+}
+
+/// Parameters for the `sort_array` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SortArrayParams {
+    /// Values to sort: all numbers, all strings, or objects sorted by `key`
+    pub values: Vec<Value>,
+    /// Sort direction (default: asc)
+    #[serde(default)]
+    pub order: SortOrder,
+    /// Sort objects by this field instead of by the values themselves
+    #[serde(default)]
+    pub key: Option<String>,
+}
+
+/// Parameters for the `dedup_array` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DedupArrayParams {
+    /// Values to deduplicate; any JSON values
+    pub values: Vec<Value>,
+    /// Compare objects by this field instead of by the whole value
+    #[serde(default)]
+    pub key: Option<String>,
+}
+
+/// Structured result of `sort_array`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct SortedArray {
+    /// The values in order; equal keys keep their input order.
+    pub values: Vec<Value>,
+}
+
+/// Structured result of `dedup_array`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct DedupedArray {
+    /// The first occurrence of each distinct value, in input order.
+    pub values: Vec<Value>,
+    /// Number of duplicates dropped.
+    pub removed: usize,
+}
+
+/// Whether sort keys are numbers or strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyKind {
+    Number,
+    String,
+}
+
+/// Stable sort of `values` by themselves or by their `key` field.
+///
+/// # Errors
+///
+/// Returns an error if a value lacks `key`, or the sort keys aren't all
+/// numbers or all strings.
+pub fn sort(
+    values: Vec<Value>,
+    order: SortOrder,
+    key: Option<&str>,
+) -> Result<SortedArray, String> {
+    let mut kind = None;
+    for (i, value) in values.iter().enumerate() {
+        let sort_key = sort_key(value, key, i)?;
+        let this = match sort_key {
+            Value::Number(_) => KeyKind::Number,
+            Value::String(_) => KeyKind::String,
+            other => {
+                return Err(format!(
+                    "{} is {}; only numbers and strings can be sorted",
+                    describe(i, key),
+                    type_name(other)
+                ));
+            }
+        };
+        match kind {
+            None => kind = Some(this),
+            Some(first) if first != this => {
+                return Err(format!(
+                    "Type mismatch: {} is {} but {} is {}",
+                    describe(0, key),
+                    type_name(sort_key_unchecked(&values[0], key)),
+                    describe(i, key),
+                    type_name(sort_key)
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+
+    let mut values = values;
+    values.sort_by(|a, b| {
+        let ordering = compare(sort_key_unchecked(a, key), sort_key_unchecked(b, key));
+        match order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    });
+    Ok(SortedArray { values })
+}
+
+/// Drop repeated `values`, keeping the first of each, compared by value or
+/// by their `key` field.
+///
+/// # Errors
+///
+/// Returns an error if a value lacks `key`.
+pub fn dedup(values: Vec<Value>, key: Option<&str>) -> Result<DedupedArray, String> {
+    let total = values.len();
+    let mut seen = HashSet::new();
+    let mut kept = Vec::new();
+    for (i, value) in values.into_iter().enumerate() {
+        // serde_json sorts object keys, so equal values serialize the same
+        if seen.insert(sort_key(&value, key, i)?.to_string()) {
+            kept.push(value);
+        }
+    }
+    Ok(DedupedArray {
+        removed: total - kept.len(),
+        values: kept,
+    })
+}
+
+/// The value `value` sorts or deduplicates by.
+fn sort_key<'a>(value: &'a Value, key: Option<&str>, index: usize) -> Result<&'a Value, String> {
+    let Some(key) = key else {
+        return Ok(value);
+    };
+    match value {
+        Value::Object(map) => map
+            .get(key)
+            .ok_or_else(|| format!("values[{index}] has no field \"{key}\"")),
+        other => Err(format!(
+            "values[{index}] is {}; key \"{key}\" needs objects",
+            type_name(other)
+        )),
+    }
+}
+
+/// [`sort_key`] for values already checked to have one.
+fn sort_key_unchecked<'a>(value: &'a Value, key: Option<&str>) -> &'a Value {
+    key.and_then(|key| value.get(key)).unwrap_or(value)
+}
+
+fn compare(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .unwrap_or_default()
+            .total_cmp(&b.as_f64().unwrap_or_default()),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        _ => Ordering::Equal,
+    }
+}
+
+fn describe(index: usize, key: Option<&str>) -> String {
+    key.map_or_else(
+        || format!("values[{index}]"),
+        |key| format!("values[{index}].{key}"),
+    )
+}
+
+const fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn array(value: Value) -> Vec<Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_sort_numbers_and_strings() {
+        let sorted = sort(array(json!([3, -1.5, 10, 2])), SortOrder::Asc, None).unwrap();
+        assert_eq!(sorted.values, array(json!([-1.5, 2, 3, 10])));
+        let sorted = sort(array(json!(["b", "B", "a"])), SortOrder::Desc, None).unwrap();
+        assert_eq!(sorted.values, array(json!(["b", "a", "B"])));
+        assert!(
+            sort(Vec::new(), SortOrder::Asc, None)
+                .unwrap()
+                .values
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_sort_by_key_is_stable() {
+        let people = array(json!([
+            { "name": "c", "age": 30 },
+            { "name": "a", "age": 25 },
+            { "name": "b", "age": 30 },
+        ]));
+        let sorted = sort(people.clone(), SortOrder::Asc, Some("age")).unwrap();
+        let names: Vec<&str> = sorted
+            .values
+            .iter()
+            .map(|p| p["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["a", "c", "b"]);
+        let sorted = sort(people, SortOrder::Desc, Some("age")).unwrap();
+        let names: Vec<&str> = sorted
+            .values
+            .iter()
+            .map(|p| p["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["c", "b", "a"]);
+    }
+
+    #[test]
+    fn test_sort_type_errors() {
+        let err = sort(array(json!([1, "2"])), SortOrder::Asc, None).unwrap_err();
+        assert_eq!(
+            err,
+            "Type mismatch: values[0] is a number but values[1] is a string"
+        );
+        let err = sort(array(json!(["a", null])), SortOrder::Asc, None).unwrap_err();
+        assert!(err.starts_with("values[1] is null"), "{err}");
+        let err = sort(
+            array(json!([{ "a": 1 }, { "b": 2 }])),
+            SortOrder::Asc,
+            Some("a"),
+        )
+        .unwrap_err();
+        assert_eq!(err, "values[1] has no field \"a\"");
+        let err = sort(array(json!([1])), SortOrder::Asc, Some("a")).unwrap_err();
+        assert!(err.contains("needs objects"), "{err}");
+    }
+
+    #[test]
+    fn test_dedup() {
+        let deduped = dedup(
+            array(json!([1, "1", 1, { "a": [1, 2] }, { "a": [1, 2] }, null, null])),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            deduped.values,
+            array(json!([1, "1", { "a": [1, 2] }, null]))
+        );
+        assert_eq!(deduped.removed, 3);
+
+        let deduped = dedup(
+            array(json!([{ "id": 1, "v": "x" }, { "id": 2 }, { "id": 1, "v": "y" }])),
+            Some("id"),
+        )
+        .unwrap();
+        assert_eq!(
+            deduped.values,
+            array(json!([{ "id": 1, "v": "x" }, { "id": 2 }]))
+        );
+        assert!(dedup(array(json!([{ "a": 1 }])), Some("id")).is_err());
+    }
+}
@@ -0,0 +1,200 @@
+//! Compression tools: `gzip_compress`, `gzip_decompress`, `zstd_compress`,
+//! `zstd_decompress`.
+//!
+//! Compressed data travels as base64, so clients get binary payloads that
+//! survive a JSON round trip. Decompression rejects bad base64, corrupted or
+//! truncated streams, non-UTF-8 output, and output larger than
+//! [`MAX_DECOMPRESSED_BYTES`], each with an `isError` result saying which.
+
+use std::io::{Read, Write};
+
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+/// Largest output the decompression tools produce, guarding against
+/// decompression bombs.
+pub const MAX_DECOMPRESSED_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Parameters for the `gzip_compress` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GzipCompressParams {
+    /// Text to compress
+    pub text: String,
+    /// Compression level, 0 (none) to 9 (best) (default: 6)
+    #[serde(default = "default_gzip_level")]
+    pub level: u32,
+}
+
+/// Parameters for the `gzip_decompress` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GzipDecompressParams {
+    /// Base64-encoded gzip data
+    pub data: String,
+}
+
+/// Parameters for the `zstd_compress` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ZstdCompressParams {
+    /// Text to compress
+    pub text: String,
+    /// Compression level, 1 (fastest) to 22 (best) (default: 3)
+    #[serde(default = "default_zstd_level")]
+    pub level: i32,
+}
+
+/// Parameters for the `zstd_decompress` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ZstdDecompressParams {
+    /// Base64-encoded zstd data
+    pub data: String,
+}
+
+const fn default_gzip_level() -> u32 {
+    6
+}
+
+const fn default_zstd_level() -> i32 {
+    3
+}
+
+/// Gzip `text` at `level`, returning base64.
+///
+/// # Errors
+///
+/// Returns an error if `level` is above 9.
+pub fn gzip_compress(text: &str, level: u32) -> Result<String, String> {
+    if level > 9 {
+        return Err(format!("gzip level must be 0 to 9, got {level}"));
+    }
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::new(level));
+    encoder
+        .write_all(text.as_bytes())
+        .and_then(|()| encoder.finish())
+        .map(|bytes| BASE64.encode(bytes))
+        .map_err(|e| format!("gzip compression failed: {e}"))
+}
+
+/// Decompress base64 gzip `data` to text.
+///
+/// # Errors
+///
+/// Returns an error if `data` isn't base64, the stream is corrupted or
+/// truncated, or the output is too large or not UTF-8.
+pub fn gzip_decompress(data: &str) -> Result<String, String> {
+    let bytes = decode_base64(data)?;
+    read_limited(GzDecoder::new(bytes.as_slice()), "gzip")
+}
+
+/// Zstd-compress `text` at `level`, returning base64.
+///
+/// # Errors
+///
+/// Returns an error if `level` is outside 1 to 22.
+pub fn zstd_compress(text: &str, level: i32) -> Result<String, String> {
+    if !(1..=22).contains(&level) {
+        return Err(format!("zstd level must be 1 to 22, got {level}"));
+    }
+    zstd::encode_all(text.as_bytes(), level)
+        .map(|bytes| BASE64.encode(bytes))
+        .map_err(|e| format!("zstd compression failed: {e}"))
+}
+
+/// Decompress base64 zstd `data` to text.
+///
+/// # Errors
+///
+/// Returns an error if `data` isn't base64, the stream is corrupted or
+/// truncated, or the output is too large or not UTF-8.
+pub fn zstd_decompress(data: &str) -> Result<String, String> {
+    let bytes = decode_base64(data)?;
+    let decoder =
+        zstd::Decoder::new(bytes.as_slice()).map_err(|e| format!("Corrupted zstd data: {e}"))?;
+    read_limited(decoder, "zstd")
+}
+
+fn decode_base64(data: &str) -> Result<Vec<u8>, String> {
+    BASE64
+        .decode(data.trim())
+        .map_err(|e| format!("Invalid base64: {e}"))
+}
+
+/// Read a decompressing `reader` to text, stopping past the size limit.
+fn read_limited(reader: impl Read, format: &str) -> Result<String, String> {
+    let mut bytes = Vec::new();
+    reader
+        .take(MAX_DECOMPRESSED_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Corrupted {format} data: {e}"))?;
+    if bytes.len() as u64 > MAX_DECOMPRESSED_BYTES {
+        return Err(format!(
+            "Decompressed data exceeds {MAX_DECOMPRESSED_BYTES} bytes"
+        ));
+    }
+    String::from_utf8(bytes).map_err(|e| format!("Decompressed data is not valid UTF-8: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "the quick brown fox jumps over the lazy dog. ";
+
+    #[test]
+    fn test_round_trips() {
+        let text = TEXT.repeat(50);
+        for level in [0, 1, 9] {
+            let compressed = gzip_compress(&text, level).unwrap();
+            assert_eq!(gzip_decompress(&compressed).unwrap(), text);
+        }
+        for level in [1, 3, 22] {
+            let compressed = zstd_compress(&text, level).unwrap();
+            assert_eq!(zstd_decompress(&compressed).unwrap(), text);
+        }
+        // Repetitive text shrinks
+        assert!(zstd_compress(&text, 3).unwrap().len() < text.len() / 4);
+        assert_eq!(gzip_decompress(&gzip_compress("", 6).unwrap()).unwrap(), "");
+    }
+
+    #[test]
+    fn test_levels_are_validated() {
+        assert!(gzip_compress(TEXT, 10).is_err());
+        assert!(zstd_compress(TEXT, 0).is_err());
+        assert!(zstd_compress(TEXT, 23).is_err());
+    }
+
+    #[test]
+    fn test_corrupted_input() {
+        assert!(
+            gzip_decompress("not base64!")
+                .unwrap_err()
+                .starts_with("Invalid base64:")
+        );
+        // Valid base64 that isn't a gzip stream
+        let err = gzip_decompress(&BASE64.encode(b"plain bytes")).unwrap_err();
+        assert!(err.starts_with("Corrupted gzip data:"), "{err}");
+        let err = zstd_decompress(&BASE64.encode(b"plain bytes")).unwrap_err();
+        assert!(err.starts_with("Corrupted zstd data:"), "{err}");
+
+        // A truncated stream
+        let full = BASE64.decode(gzip_compress(TEXT, 6).unwrap()).unwrap();
+        let err = gzip_decompress(&BASE64.encode(&full[..full.len() / 2])).unwrap_err();
+        assert!(err.starts_with("Corrupted gzip data:"), "{err}");
+        let full = BASE64.decode(zstd_compress(TEXT, 3).unwrap()).unwrap();
+        let err = zstd_decompress(&BASE64.encode(&full[..full.len() / 2])).unwrap_err();
+        assert!(err.starts_with("Corrupted zstd data:"), "{err}");
+    }
+
+    #[test]
+    fn test_output_is_bounded() {
+        let zeros = vec![0_u8; usize::try_from(MAX_DECOMPRESSED_BYTES).unwrap() + 1];
+        let bomb = BASE64.encode(zstd::encode_all(zeros.as_slice(), 3).unwrap());
+        let err = zstd_decompress(&bomb).unwrap_err();
+        assert!(err.starts_with("Decompressed data exceeds"), "{err}");
+
+        let bytes = zstd::encode_all(&[0xff_u8, 0xfe][..], 3).unwrap();
+        let err = zstd_decompress(&BASE64.encode(bytes)).unwrap_err();
+        assert!(err.contains("not valid UTF-8"), "{err}");
+    }
+}
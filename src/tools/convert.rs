@@ -0,0 +1,140 @@
+//! Format conversion tools: `yaml_to_json`, `json_to_yaml`, `toml_to_json`.
+//!
+//! Conversions go through a parsed value, so a document that doesn't parse
+//! gives an `isError` result carrying the parser's message with its line and
+//! column.
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{Map, Number, Value};
+
+/// Parameters for the `yaml_to_json` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct YamlToJsonParams {
+    /// YAML document to convert
+    pub yaml: String,
+}
+
+/// Parameters for the `json_to_yaml` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct JsonToYamlParams {
+    /// Value to convert to YAML
+    pub value: Value,
+}
+
+/// Parameters for the `toml_to_json` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TomlToJsonParams {
+    /// TOML document to convert
+    pub toml: String,
+}
+
+/// Convert a YAML document to pretty-printed JSON.
+///
+/// Non-string mapping keys become strings, and `.nan` or `.inf` become
+/// `null`, since JSON has no equivalent.
+///
+/// # Errors
+///
+/// Returns `Invalid YAML: ...` if `yaml` doesn't parse or holds more than one
+/// document.
+pub fn yaml_to_json(yaml: &str) -> Result<String, String> {
+    let value: serde_yaml_ng::Value =
+        serde_yaml_ng::from_str(yaml).map_err(|e| format!("Invalid YAML: {e}"))?;
+    let value = serde_json::to_value(value).map_err(|e| format!("Invalid YAML: {e}"))?;
+    serde_json::to_string_pretty(&value).map_err(|e| e.to_string())
+}
+
+/// Convert a JSON value to a YAML document.
+///
+/// # Errors
+///
+/// Returns an error if the YAML serializer rejects the value.
+pub fn json_to_yaml(value: &Value) -> Result<String, String> {
+    serde_yaml_ng::to_string(value).map_err(|e| e.to_string())
+}
+
+/// Convert a TOML document to pretty-printed JSON.
+///
+/// Datetimes become RFC 3339 strings, and `nan` or `inf` become `null`.
+///
+/// # Errors
+///
+/// Returns `Invalid TOML: ...` if `toml` doesn't parse.
+pub fn toml_to_json(toml: &str) -> Result<String, String> {
+    let table: toml::Table = toml::from_str(toml).map_err(|e| format!("Invalid TOML: {e}"))?;
+    serde_json::to_string_pretty(&toml_value(toml::Value::Table(table))).map_err(|e| e.to_string())
+}
+
+fn toml_value(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::from(i),
+        toml::Value::Float(f) => Number::from_f64(f).map_or(Value::Null, Value::Number),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(d) => Value::String(d.to_string()),
+        toml::Value::Array(items) => Value::Array(items.into_iter().map(toml_value).collect()),
+        toml::Value::Table(table) => Value::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, toml_value(value)))
+                .collect::<Map<_, _>>(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parsed(text: &str) -> Value {
+        serde_json::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn test_yaml_to_json() {
+        let yaml = "name: demo\ntags: [a, b]\nnested:\n  1: one\n  ok: true\n  ratio: .nan\n";
+        assert_eq!(
+            parsed(&yaml_to_json(yaml).unwrap()),
+            json!({
+                "name": "demo",
+                "tags": ["a", "b"],
+                "nested": { "1": "one", "ok": true, "ratio": null },
+            })
+        );
+        assert_eq!(parsed(&yaml_to_json("- 1\n- x").unwrap()), json!([1, "x"]));
+    }
+
+    #[test]
+    fn test_yaml_errors() {
+        let err = yaml_to_json("a: [1, 2").unwrap_err();
+        assert!(err.starts_with("Invalid YAML:"), "{err}");
+        assert!(err.contains("line"), "{err}");
+        assert!(yaml_to_json("a: 1\n---\nb: 2\n").is_err());
+    }
+
+    #[test]
+    fn test_json_to_yaml_round_trip() {
+        let value = json!({ "list": [1, "two", null], "map": { "k": "multi\nline" } });
+        let yaml = json_to_yaml(&value).unwrap();
+        assert!(yaml.starts_with("list:\n- 1\n- two\n- null\n"), "{yaml}");
+        assert_eq!(parsed(&yaml_to_json(&yaml).unwrap()), value);
+    }
+
+    #[test]
+    fn test_toml_to_json() {
+        let toml = "title = \"demo\"\nwhen = 2024-06-01T12:00:00Z\n\n[[servers]]\nport = 80\nweight = 0.5\n";
+        assert_eq!(
+            parsed(&toml_to_json(toml).unwrap()),
+            json!({
+                "title": "demo",
+                "when": "2024-06-01T12:00:00Z",
+                "servers": [{ "port": 80, "weight": 0.5 }],
+            })
+        );
+        let err = toml_to_json("a = ").unwrap_err();
+        assert!(err.starts_with("Invalid TOML:"), "{err}");
+        assert!(toml_to_json("a = 1\na = 2").is_err());
+    }
+}
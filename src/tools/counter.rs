@@ -0,0 +1,164 @@
+//! Counter tools: `counter_increment`, `counter_reset`.
+//!
+//! Two kinds of counter: one shared by the whole server and one per session
+//! (keyed like the [`kv`](super::kv) maps). Every increment returns the new
+//! value, so a client that fires N calls can check each was applied exactly
+//! once and in the order the server received them, and that concurrent
+//! sessions don't see each other's counts.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// Which counter a call addresses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CounterScope {
+    /// The calling session's own counter.
+    #[default]
+    Session,
+    /// The counter shared by every session.
+    Server,
+}
+
+impl CounterScope {
+    /// The scope's name as used in tool arguments.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Session => "session",
+            Self::Server => "server",
+        }
+    }
+}
+
+/// Parameters for the `counter_increment` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CounterIncrementParams {
+    /// Counter to increment: session (default) or server
+    #[serde(default)]
+    pub scope: CounterScope,
+}
+
+/// Parameters for the `counter_reset` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CounterResetParams {
+    /// Counter to reset to zero: session (default) or server
+    #[serde(default)]
+    pub scope: CounterScope,
+}
+
+/// The server-wide counter and the per-session counters.
+#[derive(Debug, Default)]
+pub struct Counters {
+    server: Mutex<u64>,
+    sessions: Mutex<HashMap<String, u64>>,
+}
+
+impl Counters {
+    /// Create counters starting at zero.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The current value of the counter.
+    #[must_use]
+    pub fn get(&self, scope: CounterScope, session: &str) -> u64 {
+        match scope {
+            CounterScope::Server => *self.server.lock().unwrap_or_else(PoisonError::into_inner),
+            CounterScope::Session => self
+                .sessions
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .get(session)
+                .copied()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Add one to the counter, returning the new value.
+    pub fn increment(&self, scope: CounterScope, session: &str) -> u64 {
+        self.update(scope, session, |value| value.saturating_add(1))
+    }
+
+    /// Set the counter back to zero, returning the value it had.
+    pub fn reset(&self, scope: CounterScope, session: &str) -> u64 {
+        let mut previous = 0;
+        self.update(scope, session, |value| {
+            previous = value;
+            0
+        });
+        previous
+    }
+
+    #[allow(clippy::significant_drop_tightening)]
+    fn update(&self, scope: CounterScope, session: &str, f: impl FnOnce(u64) -> u64) -> u64 {
+        match scope {
+            CounterScope::Server => {
+                let mut value = self.server.lock().unwrap_or_else(PoisonError::into_inner);
+                *value = f(*value);
+                *value
+            }
+            CounterScope::Session => {
+                let mut sessions = self.sessions.lock().unwrap_or_else(PoisonError::into_inner);
+                let value = sessions.entry(session.to_string()).or_default();
+                *value = f(*value);
+                *value
+            }
+        }
+    }
+}
+
+/// Result body of the counter tools: the scope, the value before the call,
+/// and the value after it.
+#[must_use]
+pub fn counter_result(scope: CounterScope, previous: u64, value: u64) -> Value {
+    json!({ "scope": scope, "previous": previous, "value": value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scopes_are_independent() {
+        let counters = Counters::new();
+        assert_eq!(counters.increment(CounterScope::Session, "s1"), 1);
+        assert_eq!(counters.increment(CounterScope::Session, "s1"), 2);
+        assert_eq!(counters.increment(CounterScope::Session, "s2"), 1);
+        assert_eq!(counters.increment(CounterScope::Server, "s1"), 1);
+        assert_eq!(counters.increment(CounterScope::Server, "s2"), 2);
+
+        assert_eq!(counters.reset(CounterScope::Session, "s1"), 2);
+        assert_eq!(counters.get(CounterScope::Session, "s1"), 0);
+        assert_eq!(counters.get(CounterScope::Session, "s2"), 1);
+        assert_eq!(counters.reset(CounterScope::Server, "any"), 2);
+        assert_eq!(counters.get(CounterScope::Server, "s1"), 0);
+    }
+
+    #[test]
+    fn test_concurrent_increments_are_not_lost() {
+        let counters = Counters::new();
+        let mut values: Vec<u64> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| {
+                    scope.spawn(|| {
+                        (0..100)
+                            .map(|_| counters.increment(CounterScope::Server, "s"))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().unwrap())
+                .collect()
+        });
+        values.sort_unstable();
+        assert_eq!(values, (1..=800).collect::<Vec<_>>());
+    }
+}
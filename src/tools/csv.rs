@@ -0,0 +1,291 @@
+//! CSV tools: `csv_parse`, `csv_format`.
+//!
+//! Both take a one-character `delimiter` (`","` by default, `"\t"` for TSV)
+//! and `quote`, and return structured output. Ragged rows, duplicate headers,
+//! and rows that aren't JSON objects or arrays give an `isError` result
+//! naming the row at fault.
+
+use std::collections::BTreeSet;
+
+use csv::{ReaderBuilder, WriterBuilder};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Parameters for the `csv_parse` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CsvParseParams {
+    /// CSV or TSV text
+    pub text: String,
+    /// Field delimiter, one ASCII character (default: ","; use "\t" for TSV)
+    #[serde(default = "default_delimiter")]
+    pub delimiter: String,
+    /// Quote character, one ASCII character (default: ")
+    #[serde(default = "default_quote")]
+    pub quote: String,
+    /// Treat the first record as column names and return rows as objects (default: true)
+    #[serde(default = "default_has_headers")]
+    pub has_headers: bool,
+}
+
+/// Parameters for the `csv_format` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CsvFormatParams {
+    /// Rows: all objects (keyed by column) or all arrays of cells
+    pub rows: Vec<Value>,
+    /// Column order; defaults to every object key, sorted. Written as a header record
+    #[serde(default)]
+    pub headers: Option<Vec<String>>,
+    /// Field delimiter, one ASCII character (default: ","; use "\t" for TSV)
+    #[serde(default = "default_delimiter")]
+    pub delimiter: String,
+    /// Quote character, one ASCII character (default: ")
+    #[serde(default = "default_quote")]
+    pub quote: String,
+}
+
+fn default_delimiter() -> String {
+    ",".to_string()
+}
+
+fn default_quote() -> String {
+    "\"".to_string()
+}
+
+const fn default_has_headers() -> bool {
+    true
+}
+
+/// Structured result of `csv_parse`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct ParsedCsv {
+    /// Column names, or `null` without `has_headers`.
+    pub headers: Option<Vec<String>>,
+    /// Objects keyed by column name, or arrays of cells without `has_headers`.
+    pub rows: Vec<Value>,
+    /// Number of rows, not counting the header record.
+    pub row_count: usize,
+}
+
+/// Structured result of `csv_format`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct FormattedCsv {
+    /// The CSV text.
+    pub csv: String,
+    /// Number of rows, not counting the header record.
+    pub row_count: usize,
+}
+
+/// The byte for a one-character `delimiter` or `quote` argument.
+fn single_byte(field: &str, value: &str) -> Result<u8, String> {
+    match value.as_bytes() {
+        [byte] if byte.is_ascii() => Ok(*byte),
+        _ => Err(format!(
+            "{field} must be a single ASCII character, got {value:?}"
+        )),
+    }
+}
+
+/// Parse `text` into rows.
+///
+/// # Errors
+///
+/// Returns a message if `delimiter` or `quote` isn't one ASCII character,
+/// the text has ragged rows or invalid UTF-8, or a header repeats.
+pub fn parse(
+    text: &str,
+    delimiter: &str,
+    quote: &str,
+    has_headers: bool,
+) -> Result<ParsedCsv, String> {
+    let mut reader = ReaderBuilder::new()
+        .delimiter(single_byte("delimiter", delimiter)?)
+        .quote(single_byte("quote", quote)?)
+        .has_headers(has_headers)
+        .from_reader(text.as_bytes());
+
+    let headers = if has_headers {
+        let headers: Vec<String> = reader
+            .headers()
+            .map_err(|e| format!("Invalid CSV: {e}"))?
+            .iter()
+            .map(str::to_string)
+            .collect();
+        let mut seen = BTreeSet::new();
+        if let Some(duplicate) = headers.iter().find(|h| !seen.insert(h.as_str())) {
+            return Err(format!(
+                "Invalid CSV: duplicate column header {duplicate:?}"
+            ));
+        }
+        Some(headers)
+    } else {
+        None
+    };
+
+    let rows = reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|e| format!("Invalid CSV: {e}"))?;
+            let cells = record.iter().map(|cell| Value::String(cell.to_string()));
+            Ok(match &headers {
+                Some(headers) => Value::Object(headers.iter().cloned().zip(cells).collect()),
+                None => Value::Array(cells.collect()),
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(ParsedCsv {
+        headers,
+        row_count: rows.len(),
+        rows,
+    })
+}
+
+/// The text written for one cell: strings as-is, `null` as empty, and other
+/// values as JSON.
+fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+/// Format `rows` as CSV.
+///
+/// # Errors
+///
+/// Returns a message if `delimiter` or `quote` isn't one ASCII character, a
+/// row isn't an object or array, rows mix both kinds, or array rows differ in
+/// length.
+pub fn format(
+    rows: &[Value],
+    headers: Option<Vec<String>>,
+    delimiter: &str,
+    quote: &str,
+) -> Result<FormattedCsv, String> {
+    let mut writer = WriterBuilder::new()
+        .delimiter(single_byte("delimiter", delimiter)?)
+        .quote(single_byte("quote", quote)?)
+        .from_writer(Vec::new());
+    let objects = matches!(rows.first(), Some(Value::Object(_)));
+
+    let headers = match headers {
+        Some(headers) => Some(headers),
+        None if objects => Some(
+            rows.iter()
+                .filter_map(Value::as_object)
+                .flat_map(Map::keys)
+                .cloned()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+        ),
+        None => None,
+    };
+    let write_error = |e: csv::Error| format!("Invalid rows: {e}");
+    if let Some(headers) = &headers {
+        writer.write_record(headers).map_err(write_error)?;
+    }
+
+    for (index, row) in rows.iter().enumerate() {
+        let record: Vec<String> = match (row, &headers) {
+            (Value::Object(row), Some(headers)) if objects => {
+                headers.iter().map(|h| cell(row.get(h))).collect()
+            }
+            (Value::Array(row), _) if !objects => row.iter().map(|v| cell(Some(v))).collect(),
+            (Value::Object(_) | Value::Array(_), _) => {
+                return Err(format!(
+                    "Invalid rows: row {index} mixes objects and arrays; use one kind for every row"
+                ));
+            }
+            _ => {
+                return Err(format!(
+                    "Invalid rows: row {index} must be an object or an array"
+                ));
+            }
+        };
+        writer.write_record(&record).map_err(write_error)?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| format!("Invalid rows: {e}"))?;
+    let csv = String::from_utf8(bytes).map_err(|e| format!("Invalid rows: {e}"))?;
+    Ok(FormattedCsv {
+        csv,
+        row_count: rows.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_with_headers() {
+        let parsed = parse(
+            "name,age\nada,36\n\"lovelace, a\",\"say \"\"hi\"\"\"\n",
+            ",",
+            "\"",
+            true,
+        )
+        .unwrap();
+        assert_eq!(parsed.headers, Some(vec!["name".into(), "age".into()]));
+        assert_eq!(parsed.row_count, 2);
+        assert_eq!(parsed.rows[0], json!({"name": "ada", "age": "36"}));
+        assert_eq!(
+            parsed.rows[1],
+            json!({"name": "lovelace, a", "age": "say \"hi\""})
+        );
+    }
+
+    #[test]
+    fn test_parse_tsv_without_headers() {
+        let parsed = parse("a\tb\n'x\ty'\tz\n", "\t", "'", false).unwrap();
+        assert_eq!(parsed.headers, None);
+        assert_eq!(parsed.rows, [json!(["a", "b"]), json!(["x\ty", "z"])]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse("a,b\n1,2,3\n", ",", "\"", true).unwrap_err();
+        assert!(err.starts_with("Invalid CSV:"), "{err}");
+        let err = parse("a,a\n1,2\n", ",", "\"", true).unwrap_err();
+        assert!(err.contains("duplicate column header \"a\""), "{err}");
+        assert!(parse("a", "::", "\"", true).is_err());
+        assert!(parse("a", ",", "", true).is_err());
+        assert!(parse("a", "é", "\"", true).is_err());
+    }
+
+    #[test]
+    fn test_format_objects() {
+        let rows = [json!({"b": "x,y", "a": 1}), json!({"a": null, "c": true})];
+        let formatted = format(&rows, None, ",", "\"").unwrap();
+        assert_eq!(formatted.csv, "a,b,c\n1,\"x,y\",\n,,true\n");
+        assert_eq!(formatted.row_count, 2);
+
+        let formatted = format(&rows, Some(vec!["c".into(), "a".into()]), "\t", "\"").unwrap();
+        assert_eq!(formatted.csv, "c\ta\n\t1\ntrue\t\n");
+    }
+
+    #[test]
+    fn test_format_arrays_round_trip() {
+        let rows = [json!(["a", "say \"hi\""]), json!([1, [2, 3]])];
+        let formatted = format(&rows, None, ",", "\"").unwrap();
+        assert_eq!(formatted.csv, "a,\"say \"\"hi\"\"\"\n1,\"[2,3]\"\n");
+        let parsed = parse(&formatted.csv, ",", "\"", false).unwrap();
+        assert_eq!(parsed.rows[0], json!(["a", "say \"hi\""]));
+    }
+
+    #[test]
+    fn test_format_errors() {
+        assert!(format(&[json!(["a"]), json!(["a", "b"])], None, ",", "\"").is_err());
+        let err = format(&[json!({"a": 1}), json!([1])], None, ",", "\"").unwrap_err();
+        assert!(err.contains("row 1 mixes"), "{err}");
+        let err = format(&[json!(1)], None, ",", "\"").unwrap_err();
+        assert!(err.contains("row 0 must be"), "{err}");
+        assert!(format(&[], None, ",,", "\"").is_err());
+        assert_eq!(format(&[], None, ",", "\"").unwrap().csv, "");
+    }
+}
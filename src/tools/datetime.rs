@@ -0,0 +1,353 @@
+//! Date/time tools: `date_add`, `date_diff`, `timezone_convert`.
+//!
+//! Timestamps are RFC 3339 strings. Units are an enum in the input schema,
+//! time zones are IANA names, and every failure (an unparsable timestamp,
+//! an unknown zone, arithmetic past year 262143) is an `isError` result that
+//! names the offending argument, so clients can test rich validation errors.
+
+use chrono::{DateTime, FixedOffset, Months, Offset, SecondsFormat, TimeDelta, Utc};
+use chrono_tz::{OffsetComponents, OffsetName, Tz};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Unit of a date offset or difference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DateUnit {
+    /// Seconds.
+    Seconds,
+    /// Minutes.
+    Minutes,
+    /// Hours.
+    Hours,
+    /// Days of 24 hours.
+    Days,
+    /// Weeks of 7 days.
+    Weeks,
+    /// Calendar months; adding clamps to the end of shorter months.
+    Months,
+    /// Calendar years of 12 months.
+    Years,
+}
+
+impl DateUnit {
+    /// The unit's name as used in tool arguments.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Seconds => "seconds",
+            Self::Minutes => "minutes",
+            Self::Hours => "hours",
+            Self::Days => "days",
+            Self::Weeks => "weeks",
+            Self::Months => "months",
+            Self::Years => "years",
+        }
+    }
+}
+
+/// Parameters for the `date_add` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DateAddParams {
+    /// RFC 3339 timestamp, e.g. 2024-01-31T12:00:00Z
+    pub timestamp: String,
+    /// Amount to add; negative to subtract
+    pub amount: i64,
+    /// Unit of the amount
+    pub unit: DateUnit,
+}
+
+/// Parameters for the `date_diff` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DateDiffParams {
+    /// RFC 3339 start timestamp
+    pub start: String,
+    /// RFC 3339 end timestamp
+    pub end: String,
+    /// Unit of the result (default: seconds)
+    #[serde(default = "default_unit")]
+    pub unit: DateUnit,
+}
+
+const fn default_unit() -> DateUnit {
+    DateUnit::Seconds
+}
+
+/// Parameters for the `timezone_convert` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TimezoneConvertParams {
+    /// RFC 3339 timestamp
+    pub timestamp: String,
+    /// IANA time zone name, e.g. Europe/Paris
+    pub timezone: String,
+}
+
+/// Structured result of `date_diff`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct DateDiff {
+    /// Whole units from start to end, negative if end is earlier.
+    pub value: i64,
+    /// Unit of `value`.
+    pub unit: DateUnit,
+}
+
+/// Structured result of `timezone_convert`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct ZonedTime {
+    /// The instant as an RFC 3339 timestamp in the target zone.
+    pub timestamp: String,
+    /// IANA name of the zone.
+    pub timezone: String,
+    /// UTC offset in effect, e.g. `+02:00`.
+    pub utc_offset: String,
+    /// Zone abbreviation in effect, e.g. `CEST`, if the zone has one.
+    pub abbreviation: Option<String>,
+    /// Whether daylight saving time is in effect.
+    pub dst: bool,
+}
+
+/// Parse `value`, naming `field` if it isn't RFC 3339.
+///
+/// # Errors
+///
+/// Returns a message quoting the value and the parser's explanation.
+pub fn parse_timestamp(field: &str, value: &str) -> Result<DateTime<FixedOffset>, String> {
+    DateTime::parse_from_rfc3339(value.trim()).map_err(|e| {
+        format!("Invalid {field} {value:?}: {e} (expected RFC 3339, e.g. 2024-01-31T12:00:00Z)")
+    })
+}
+
+/// Look up an IANA time zone.
+///
+/// # Errors
+///
+/// Returns a message naming the zone, suggesting the correctly cased name
+/// if only the case is wrong.
+pub fn parse_timezone(name: &str) -> Result<Tz, String> {
+    name.parse::<Tz>().map_err(|_| {
+        chrono_tz::TZ_VARIANTS
+            .iter()
+            .find(|tz| tz.name().eq_ignore_ascii_case(name))
+            .map_or_else(
+                || format!("Unknown timezone {name:?} (expected an IANA name, e.g. Europe/Paris)"),
+                |tz| format!("Unknown timezone {name:?}; did you mean {:?}?", tz.name()),
+            )
+    })
+}
+
+fn overflow(amount: i64, unit: DateUnit) -> String {
+    format!(
+        "Date arithmetic overflowed: adding {amount} {} leaves the supported range",
+        unit.as_str()
+    )
+}
+
+/// Add `amount` `unit`s to `timestamp`, keeping its UTC offset.
+///
+/// # Errors
+///
+/// Returns an error if `timestamp` is invalid or the result is out of range.
+pub fn add(timestamp: &str, amount: i64, unit: DateUnit) -> Result<String, String> {
+    let start = parse_timestamp("timestamp", timestamp)?;
+    let result = match unit {
+        DateUnit::Months | DateUnit::Years => {
+            let months = if unit == DateUnit::Years {
+                amount.checked_mul(12)
+            } else {
+                Some(amount)
+            };
+            months
+                .and_then(|m| u32::try_from(m.unsigned_abs()).ok().map(|abs| (m, abs)))
+                .and_then(|(m, abs)| {
+                    if m < 0 {
+                        start.checked_sub_months(Months::new(abs))
+                    } else {
+                        start.checked_add_months(Months::new(abs))
+                    }
+                })
+        }
+        _ => delta(amount, unit).and_then(|d| start.checked_add_signed(d)),
+    };
+    result
+        .map(|t| t.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+        .ok_or_else(|| overflow(amount, unit))
+}
+
+const fn delta(amount: i64, unit: DateUnit) -> Option<TimeDelta> {
+    match unit {
+        DateUnit::Seconds => TimeDelta::try_seconds(amount),
+        DateUnit::Minutes => TimeDelta::try_minutes(amount),
+        DateUnit::Hours => TimeDelta::try_hours(amount),
+        DateUnit::Days => TimeDelta::try_days(amount),
+        DateUnit::Weeks => TimeDelta::try_weeks(amount),
+        DateUnit::Months | DateUnit::Years => None,
+    }
+}
+
+/// Whole calendar months from `start` to `end`, which must not be earlier.
+fn whole_months(start: DateTime<Utc>, end: DateTime<Utc>) -> i64 {
+    use chrono::Datelike;
+
+    let mut months = i64::from(end.year() - start.year()) * 12 + i64::from(end.month())
+        - i64::from(start.month());
+    // Step back while adding that many months overshoots the end
+    while months > 0
+        && u32::try_from(months)
+            .ok()
+            .and_then(|m| start.checked_add_months(Months::new(m)))
+            .is_none_or(|t| t > end)
+    {
+        months -= 1;
+    }
+    months
+}
+
+/// Whole `unit`s from `start` to `end`.
+///
+/// # Errors
+///
+/// Returns an error if either timestamp is invalid.
+pub fn diff(start: &str, end: &str, unit: DateUnit) -> Result<DateDiff, String> {
+    let start = parse_timestamp("start", start)?.with_timezone(&Utc);
+    let end = parse_timestamp("end", end)?.with_timezone(&Utc);
+    let delta = end - start;
+    let value = match unit {
+        DateUnit::Seconds => delta.num_seconds(),
+        DateUnit::Minutes => delta.num_minutes(),
+        DateUnit::Hours => delta.num_hours(),
+        DateUnit::Days => delta.num_days(),
+        DateUnit::Weeks => delta.num_weeks(),
+        DateUnit::Months | DateUnit::Years => {
+            let months = if end >= start {
+                whole_months(start, end)
+            } else {
+                -whole_months(end, start)
+            };
+            if unit == DateUnit::Years {
+                months / 12
+            } else {
+                months
+            }
+        }
+    };
+    Ok(DateDiff { value, unit })
+}
+
+/// Express `timestamp` in the IANA zone `timezone`.
+///
+/// # Errors
+///
+/// Returns an error if `timestamp` is invalid or `timezone` unknown.
+pub fn convert(timestamp: &str, timezone: &str) -> Result<ZonedTime, String> {
+    let instant = parse_timestamp("timestamp", timestamp)?;
+    let tz = parse_timezone(timezone.trim())?;
+    let zoned = instant.with_timezone(&tz);
+    let offset = zoned.offset();
+    Ok(ZonedTime {
+        timestamp: zoned.to_rfc3339_opts(SecondsFormat::AutoSi, false),
+        timezone: tz.name().to_string(),
+        utc_offset: offset.fix().to_string(),
+        abbreviation: offset.abbreviation().map(str::to_string),
+        dst: !offset.dst_offset().is_zero(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add() {
+        assert_eq!(
+            add("2024-01-31T12:00:00Z", 1, DateUnit::Months).unwrap(),
+            "2024-02-29T12:00:00Z",
+            "clamped to the end of February"
+        );
+        assert_eq!(
+            add("2024-03-10T00:00:00+05:30", -2, DateUnit::Days).unwrap(),
+            "2024-03-08T00:00:00+05:30"
+        );
+        assert_eq!(
+            add("2024-02-29T00:00:00Z", 1, DateUnit::Years).unwrap(),
+            "2025-02-28T00:00:00Z"
+        );
+        assert_eq!(
+            add("2024-01-01T00:00:00Z", 90, DateUnit::Minutes).unwrap(),
+            "2024-01-01T01:30:00Z"
+        );
+    }
+
+    #[test]
+    fn test_add_errors() {
+        let err = add("yesterday", 1, DateUnit::Days).unwrap_err();
+        assert!(err.starts_with("Invalid timestamp \"yesterday\""), "{err}");
+        let err = add("2024-01-01T00:00:00Z", i64::MAX, DateUnit::Seconds).unwrap_err();
+        assert_eq!(
+            err,
+            format!(
+                "Date arithmetic overflowed: adding {} seconds leaves the supported range",
+                i64::MAX
+            )
+        );
+        assert!(add("2024-01-01T00:00:00Z", i64::MAX, DateUnit::Years).is_err());
+        assert!(add("2024-01-01T00:00:00Z", 300_000, DateUnit::Years).is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let diff_in = |unit| diff("2024-01-31T00:00:00Z", "2025-03-01T06:00:00Z", unit).unwrap();
+        assert_eq!(diff_in(DateUnit::Days).value, 395);
+        assert_eq!(diff_in(DateUnit::Weeks).value, 56);
+        assert_eq!(diff_in(DateUnit::Months).value, 13);
+        assert_eq!(diff_in(DateUnit::Years).value, 1);
+        // Reversed order is negative
+        assert_eq!(
+            diff(
+                "2025-03-01T00:00:00Z",
+                "2024-01-31T00:00:00Z",
+                DateUnit::Months
+            )
+            .unwrap()
+            .value,
+            -13
+        );
+        // Offsets are taken into account
+        assert_eq!(
+            diff(
+                "2024-01-01T00:00:00+02:00",
+                "2024-01-01T00:00:00Z",
+                DateUnit::Hours
+            )
+            .unwrap()
+            .value,
+            2
+        );
+        let err = diff("2024-01-01T00:00:00Z", "soon", DateUnit::Days).unwrap_err();
+        assert!(err.starts_with("Invalid end \"soon\""), "{err}");
+    }
+
+    #[test]
+    fn test_convert() {
+        let summer = convert("2024-07-01T12:00:00Z", "Europe/Paris").unwrap();
+        assert_eq!(summer.timestamp, "2024-07-01T14:00:00+02:00");
+        assert_eq!(summer.utc_offset, "+02:00");
+        assert_eq!(summer.abbreviation.as_deref(), Some("CEST"));
+        assert!(summer.dst);
+        let winter = convert("2024-01-01T12:00:00Z", "Europe/Paris").unwrap();
+        assert_eq!(winter.utc_offset, "+01:00");
+        assert!(!winter.dst);
+    }
+
+    #[test]
+    fn test_unknown_timezone() {
+        assert_eq!(
+            convert("2024-01-01T00:00:00Z", "europe/paris").unwrap_err(),
+            "Unknown timezone \"europe/paris\"; did you mean \"Europe/Paris\"?"
+        );
+        assert!(
+            convert("2024-01-01T00:00:00Z", "Mars/Olympus")
+                .unwrap_err()
+                .starts_with("Unknown timezone \"Mars/Olympus\"")
+        );
+    }
+}
@@ -1,7 +1,27 @@
-//! Encoding tools: `json_parse`, `json_stringify`, `base64_encode`, `base64_decode`, `hash_sha256`.
+//! Encoding tools: JSON, Base64, digests, and URLs.
+//!
+//! `json_parse`, `json_stringify`, `base64_encode`, `base64_decode`, `hash_sha256`,
+//! `hash_md5`, `hash_sha1`, `hash_sha512`, `hash_crc32`, `hash`, `hmac_sha256`,
+//! `url_encode`, `url_decode`, `url_parse`.
 
+use std::collections::BTreeMap;
+
+use hmac::{Hmac, Mac};
+use md5::Md5;
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, percent_decode_str, utf8_percent_encode};
 use schemars::JsonSchema;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+use url::{Url, form_urlencoded};
+
+/// Characters `url_encode` escapes: everything but RFC 3986 unreserved
+/// characters.
+const COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
 
 /// Parameters for the `json_parse` tool.
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -37,3 +57,275 @@ pub struct HashSha256Params {
     /// Text to hash
     pub text: String,
 }
+
+/// Parameters for the `hash_md5` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct HashMd5Params {
+    /// Text to hash
+    pub text: String,
+}
+
+/// Parameters for the `hash_sha1` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct HashSha1Params {
+    /// Text to hash
+    pub text: String,
+}
+
+/// Parameters for the `hash_sha512` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct HashSha512Params {
+    /// Text to hash
+    pub text: String,
+}
+
+/// Parameters for the `hash_crc32` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct HashCrc32Params {
+    /// Text to checksum
+    pub text: String,
+}
+
+/// A digest algorithm of the `hash` tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    /// MD5, 128 bits.
+    Md5,
+    /// SHA-1, 160 bits.
+    Sha1,
+    /// SHA-256, 256 bits.
+    Sha256,
+    /// SHA-512, 512 bits.
+    Sha512,
+    /// CRC-32 (IEEE), 32 bits.
+    Crc32,
+}
+
+/// Parameters for the `hash` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct HashParams {
+    /// Text to hash
+    pub text: String,
+    /// Digest algorithm
+    pub algorithm: HashAlgorithm,
+}
+
+/// Parameters for the `hmac_sha256` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct HmacSha256Params {
+    /// Secret key
+    pub key: String,
+    /// Message to authenticate
+    pub text: String,
+}
+
+/// Lowercase hex digest of `text` with `algorithm`.
+#[must_use]
+pub fn digest(algorithm: HashAlgorithm, text: &str) -> String {
+    let bytes = text.as_bytes();
+    match algorithm {
+        HashAlgorithm::Md5 => format!("{:x}", Md5::digest(bytes)),
+        HashAlgorithm::Sha1 => format!("{:x}", Sha1::digest(bytes)),
+        HashAlgorithm::Sha256 => format!("{:x}", Sha256::digest(bytes)),
+        HashAlgorithm::Sha512 => format!("{:x}", Sha512::digest(bytes)),
+        HashAlgorithm::Crc32 => format!("{:08x}", crc32fast::hash(bytes)),
+    }
+}
+
+/// Lowercase hex HMAC-SHA256 of `text` under `key`.
+///
+/// # Panics
+///
+/// Never: HMAC accepts keys of any length.
+#[must_use]
+pub fn hmac_sha256(key: &str, text: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(text.as_bytes());
+    format!("{:x}", mac.finalize().into_bytes())
+}
+
+/// Parameters for the `url_encode` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UrlEncodeParams {
+    /// Text to percent-encode
+    pub text: String,
+    /// Encode as an HTML form value, with spaces as + (default: false)
+    #[serde(default)]
+    pub form: bool,
+}
+
+/// Parameters for the `url_decode` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UrlDecodeParams {
+    /// Percent-encoded text to decode
+    pub encoded: String,
+    /// Decode as an HTML form value, with + as a space (default: false)
+    #[serde(default)]
+    pub form: bool,
+}
+
+/// Parameters for the `url_parse` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UrlParseParams {
+    /// Absolute URL to parse
+    pub url: String,
+}
+
+/// Structured result of `url_parse`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct ParsedUrl {
+    /// Scheme, lowercased.
+    pub scheme: String,
+    /// User name, if the URL has one.
+    pub username: Option<String>,
+    /// Password, if the URL has one.
+    pub password: Option<String>,
+    /// Host name or IP address, if the URL has one.
+    pub host: Option<String>,
+    /// Explicit port, or the scheme's default.
+    pub port: Option<u16>,
+    /// Path, still percent-encoded.
+    pub path: String,
+    /// Decoded query parameters; a repeated key lists every value in order.
+    pub query: BTreeMap<String, Vec<String>>,
+    /// Fragment after `#`, if any.
+    pub fragment: Option<String>,
+}
+
+/// Percent-encode `text`, leaving only unreserved characters, or as an HTML
+/// form value if `form` is set.
+#[must_use]
+pub fn url_encode(text: &str, form: bool) -> String {
+    if form {
+        form_urlencoded::byte_serialize(text.as_bytes()).collect()
+    } else {
+        utf8_percent_encode(text, COMPONENT).to_string()
+    }
+}
+
+/// Decode percent-escapes in `encoded`, and `+` as a space if `form` is set.
+///
+/// Malformed escapes such as `%zz` are kept as they are.
+///
+/// # Errors
+///
+/// Returns an error if the decoded bytes are not UTF-8.
+pub fn url_decode(encoded: &str, form: bool) -> Result<String, String> {
+    let encoded = if form {
+        encoded.replace('+', " ")
+    } else {
+        encoded.to_string()
+    };
+    percent_decode_str(&encoded)
+        .decode_utf8()
+        .map(std::borrow::Cow::into_owned)
+        .map_err(|e| format!("Decoded text is not valid UTF-8: {e}"))
+}
+
+/// Split `url` into its components.
+///
+/// # Errors
+///
+/// Returns `Invalid URL: ...` if `url` is not an absolute URL.
+pub fn parse_url(url: &str) -> Result<ParsedUrl, String> {
+    let url = Url::parse(url).map_err(|e| format!("Invalid URL: {e}"))?;
+    let mut query: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (key, value) in url.query_pairs() {
+        query
+            .entry(key.into_owned())
+            .or_default()
+            .push(value.into_owned());
+    }
+    Ok(ParsedUrl {
+        scheme: url.scheme().to_string(),
+        username: Some(url.username())
+            .filter(|u| !u.is_empty())
+            .map(str::to_string),
+        password: url.password().map(str::to_string),
+        host: url.host_str().map(str::to_string),
+        port: url.port_or_known_default(),
+        path: url.path().to_string(),
+        query,
+        fragment: url.fragment().map(str::to_string),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digest_known_vectors() {
+        let cases = [
+            (HashAlgorithm::Md5, "900150983cd24fb0d6963f7d28e17f72"),
+            (
+                HashAlgorithm::Sha1,
+                "a9993e364706816aba3e25717850c26c9cd0d89d",
+            ),
+            (
+                HashAlgorithm::Sha256,
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            ),
+            (
+                HashAlgorithm::Sha512,
+                "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+                 2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+            ),
+            (HashAlgorithm::Crc32, "352441c2"),
+        ];
+        for (algorithm, expected) in cases {
+            assert_eq!(digest(algorithm, "abc"), expected, "{algorithm:?}");
+        }
+        // CRC-32 keeps leading zeros
+        assert_eq!(digest(HashAlgorithm::Crc32, ""), "00000000");
+    }
+
+    #[test]
+    fn test_hmac_sha256() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            hmac_sha256("Jefe", "what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_ne!(hmac_sha256("a", "text"), hmac_sha256("b", "text"));
+        // Keys longer than the block size are hashed first
+        assert_eq!(hmac_sha256(&"k".repeat(200), "x").len(), 64);
+    }
+
+    #[test]
+    fn test_url_encode_modes() {
+        assert_eq!(url_encode("a b&c=d/é~", false), "a%20b%26c%3Dd%2F%C3%A9~");
+        assert_eq!(url_encode("a b&c=d", true), "a+b%26c%3Dd");
+    }
+
+    #[test]
+    fn test_url_decode_modes() {
+        assert_eq!(url_decode("a%20b+c%C3%A9", false).unwrap(), "a b+cé");
+        assert_eq!(url_decode("a%20b+c", true).unwrap(), "a b c");
+        assert_eq!(url_decode("100%zz", false).unwrap(), "100%zz");
+        assert!(url_decode("%FF", false).is_err());
+    }
+
+    #[test]
+    fn test_parse_url() {
+        let parsed =
+            parse_url("HTTPS://ada:pw@Example.com/a%20b/c?q=1&tag=x&tag=y%20z#top").unwrap();
+        assert_eq!(parsed.scheme, "https");
+        assert_eq!(parsed.username.as_deref(), Some("ada"));
+        assert_eq!(parsed.password.as_deref(), Some("pw"));
+        assert_eq!(parsed.host.as_deref(), Some("example.com"));
+        assert_eq!(parsed.port, Some(443));
+        assert_eq!(parsed.path, "/a%20b/c");
+        assert_eq!(parsed.query["tag"], ["x", "y z"]);
+        assert_eq!(parsed.fragment.as_deref(), Some("top"));
+
+        let parsed = parse_url("mailto:someone@example.com").unwrap();
+        assert_eq!((parsed.host, parsed.port), (None, None));
+        assert!(parsed.query.is_empty());
+
+        let err = parse_url("/relative/path").unwrap_err();
+        assert!(err.starts_with("Invalid URL:"), "{err}");
+    }
+}
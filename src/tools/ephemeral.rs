@@ -0,0 +1,28 @@
+//! Ephemeral resource tool: `ephemeral_create`.
+//!
+//! Creates `test://dynamic/ephemeral` (see
+//! [`ephemeral_resources`](crate::resources::ephemeral_resources)) for a
+//! number of seconds. Creating it and its expiry both send
+//! `notifications/resources/list_changed` to the calling session.
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+/// Parameters for the `ephemeral_create` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct EphemeralCreateParams {
+    /// Seconds until the resource expires (1 to 300, default 5)
+    #[serde(default)]
+    pub ttl_secs: Option<u64>,
+    /// Text content of the resource (default: "ephemeral")
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+/// Result body of `ephemeral_create`: the URI, its lifetime, and when it
+/// expires.
+#[must_use]
+pub fn create_result(uri: &str, ttl_secs: u64, expires_at: &str) -> Value {
+    json!({ "uri": uri, "ttl_secs": ttl_secs, "expires_at": expires_at })
+}
@@ -0,0 +1,342 @@
+//! HTTP fetch simulation: `fetch_url`.
+//!
+//! The tool never touches the network. It only resolves URLs on the mock
+//! upstream `http://mock.test` (or `https://`), answering from canned routes
+//! so agent clients can test tool-mediated HTTP access deterministically.
+//!
+//! Configured routes (`MCP_MOCK_ROUTES`, a JSON array of [`MockRoute`]) are
+//! matched first, on method and path. Built-in routes answer otherwise:
+//!
+//! - `/status/{code}`: an empty response with that status
+//! - `/delay/{ms}`: `200` after `ms` milliseconds (at most 30 seconds)
+//! - `/echo`: `200` with the request's method, path, query, headers, and body
+//! - `/json`: `200` with a fixed JSON document
+//!
+//! Anything else is a `404`.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Host name of the mock upstream.
+pub const MOCK_HOST: &str = "mock.test";
+
+/// Longest delay `/delay/{ms}` waits.
+pub const MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Parameters for the `fetch_url` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct FetchUrlParams {
+    /// URL on the mock upstream (host mock.test)
+    pub url: String,
+    /// HTTP method (default: GET)
+    #[serde(default)]
+    pub method: Option<String>,
+    /// Request headers
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Request body
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+/// A canned response of the mock upstream.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MockRoute {
+    /// Method to match, any method if unset. Compared case-insensitively.
+    #[serde(default)]
+    pub method: Option<String>,
+    /// Path to match exactly, without the query string.
+    pub path: String,
+    /// Response status (default: 200).
+    #[serde(default = "default_status")]
+    pub status: u16,
+    /// Response headers.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Response body.
+    #[serde(default)]
+    pub body: String,
+    /// Time to wait before responding, in milliseconds.
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+const fn default_status() -> u16 {
+    200
+}
+
+impl MockRoute {
+    /// A route answering `path` with `status` and `body`, for any method.
+    #[must_use]
+    pub fn new(path: impl Into<String>, status: u16, body: impl Into<String>) -> Self {
+        Self {
+            method: None,
+            path: path.into(),
+            status,
+            headers: BTreeMap::new(),
+            body: body.into(),
+            delay_ms: 0,
+        }
+    }
+
+    /// Only match requests with `method`.
+    #[must_use]
+    pub fn with_method(mut self, method: impl Into<String>) -> Self {
+        self.method = Some(method.into());
+        self
+    }
+
+    /// Add a response header.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Wait `delay_ms` milliseconds before responding.
+    #[must_use]
+    pub const fn with_delay_ms(mut self, delay_ms: u64) -> Self {
+        self.delay_ms = delay_ms;
+        self
+    }
+
+    /// Read routes from a JSON file holding an array of routes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read, or `InvalidData` naming
+    /// the file if it isn't a valid route array.
+    pub fn load(path: &Path) -> std::io::Result<Vec<Self>> {
+        let text = std::fs::read_to_string(path)?;
+        serde_json::from_str(&text).map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{}: {e}", path.display()),
+            )
+        })
+    }
+
+    fn matches(&self, method: &str, path: &str) -> bool {
+        self.path == path
+            && self
+                .method
+                .as_ref()
+                .is_none_or(|m| m.eq_ignore_ascii_case(method))
+    }
+}
+
+/// Structured result of `fetch_url`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct FetchResponse {
+    /// The URL that was fetched.
+    pub url: String,
+    /// HTTP status code.
+    pub status: u16,
+    /// Response headers.
+    pub headers: BTreeMap<String, String>,
+    /// Response body.
+    pub body: String,
+}
+
+/// The mock upstream behind `fetch_url`.
+#[derive(Debug, Clone, Default)]
+pub struct MockUpstream {
+    routes: Vec<MockRoute>,
+}
+
+impl MockUpstream {
+    /// An upstream answering from `routes` before the built-in routes.
+    #[must_use]
+    pub const fn new(routes: Vec<MockRoute>) -> Self {
+        Self { routes }
+    }
+
+    /// Resolve a request, returning the response and how long to wait
+    /// before delivering it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if `url` is not an `http` or `https` URL on
+    /// [`MOCK_HOST`].
+    pub fn resolve(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &BTreeMap<String, String>,
+        body: Option<&str>,
+    ) -> Result<(FetchResponse, Duration), String> {
+        let rest = url
+            .strip_prefix("http://")
+            .or_else(|| url.strip_prefix("https://"))
+            .ok_or_else(|| format!("Unsupported URL {url:?}: use http://{MOCK_HOST}/..."))?;
+        let (host, target) = rest
+            .find(['/', '?'])
+            .map_or((rest, ""), |i| rest.split_at(i));
+        if !host.eq_ignore_ascii_case(MOCK_HOST) {
+            return Err(format!(
+                "fetch_url only reaches the mock upstream {MOCK_HOST}, not {host:?}"
+            ));
+        }
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let path = if path.is_empty() { "/" } else { path };
+        let method = method.to_ascii_uppercase();
+
+        let route = self
+            .routes
+            .iter()
+            .find(|route| route.matches(&method, path))
+            .cloned()
+            .unwrap_or_else(|| builtin_route(&method, path, query, headers, body));
+        Ok((
+            FetchResponse {
+                url: url.to_string(),
+                status: route.status,
+                headers: route.headers,
+                body: route.body,
+            },
+            Duration::from_millis(route.delay_ms),
+        ))
+    }
+}
+
+/// The built-in answer to a request no configured route matched.
+fn builtin_route(
+    method: &str,
+    path: &str,
+    query: &str,
+    headers: &BTreeMap<String, String>,
+    body: Option<&str>,
+) -> MockRoute {
+    if let Some(code) = path.strip_prefix("/status/") {
+        if let Some(status) = code.parse().ok().filter(|s| (100..=599).contains(s)) {
+            return MockRoute::new(path, status, "");
+        }
+    } else if let Some(ms) = path.strip_prefix("/delay/") {
+        if let Ok(ms) = ms.parse::<u64>() {
+            let ms = ms.min(u64::try_from(MAX_DELAY.as_millis()).unwrap_or(u64::MAX));
+            return json_route(path, &json!({ "delayed_ms": ms })).with_delay_ms(ms);
+        }
+    } else if path == "/echo" {
+        return json_route(
+            path,
+            &json!({
+                "method": method,
+                "path": path,
+                "query": query,
+                "headers": headers,
+                "body": body,
+            }),
+        );
+    } else if path == "/json" {
+        return json_route(
+            path,
+            &json!({ "id": 1, "name": "mock", "tags": ["test", "fixture"] }),
+        );
+    }
+    MockRoute::new(path, 404, "Not Found").with_header("content-type", "text/plain")
+}
+
+fn json_route(path: &str, body: &serde_json::Value) -> MockRoute {
+    MockRoute::new(path, 200, body.to_string()).with_header("content-type", "application/json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get(upstream: &MockUpstream, url: &str) -> Result<(FetchResponse, Duration), String> {
+        upstream.resolve("GET", url, &BTreeMap::new(), None)
+    }
+
+    #[test]
+    fn test_builtin_routes() {
+        let upstream = MockUpstream::default();
+        let (response, delay) = get(&upstream, "http://mock.test/status/503").unwrap();
+        assert_eq!(response.status, 503);
+        assert_eq!(delay, Duration::ZERO);
+
+        let (response, delay) = get(&upstream, "https://mock.test/delay/250").unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(delay, Duration::from_millis(250));
+        let (_, delay) = get(&upstream, "http://mock.test/delay/999999999").unwrap();
+        assert_eq!(delay, MAX_DELAY);
+
+        let (response, _) = upstream
+            .resolve(
+                "post",
+                "http://mock.test/echo?x=1",
+                &BTreeMap::from([("x-test".to_string(), "1".to_string())]),
+                Some("hi"),
+            )
+            .unwrap();
+        let echoed: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        assert_eq!(echoed["method"], "POST");
+        assert_eq!(echoed["query"], "x=1");
+        assert_eq!(echoed["headers"]["x-test"], "1");
+        assert_eq!(echoed["body"], "hi");
+
+        assert_eq!(get(&upstream, "http://mock.test/").unwrap().0.status, 404);
+        assert_eq!(
+            get(&upstream, "http://mock.test/status/abc")
+                .unwrap()
+                .0
+                .status,
+            404
+        );
+    }
+
+    #[test]
+    fn test_configured_routes_take_precedence() {
+        let upstream = MockUpstream::new(vec![
+            MockRoute::new("/json", 500, "boom").with_method("POST"),
+            MockRoute::new("/users", 201, "[]")
+                .with_header("x-total", "0")
+                .with_delay_ms(10),
+        ]);
+        let (response, delay) = get(&upstream, "http://mock.test/users?page=2").unwrap();
+        assert_eq!(response.status, 201);
+        assert_eq!(response.headers["x-total"], "0");
+        assert_eq!(delay, Duration::from_millis(10));
+        // The POST-only override leaves GET on the built-in route
+        assert_eq!(
+            get(&upstream, "http://mock.test/json").unwrap().0.status,
+            200
+        );
+        let (response, _) = upstream
+            .resolve("POST", "http://mock.test/json", &BTreeMap::new(), None)
+            .unwrap();
+        assert_eq!(response.status, 500);
+    }
+
+    #[test]
+    fn test_only_mock_host_resolves() {
+        let upstream = MockUpstream::default();
+        assert!(get(&upstream, "http://example.com/json").is_err());
+        assert!(get(&upstream, "ftp://mock.test/json").is_err());
+        assert!(get(&upstream, "http://mock.test.evil.com/json").is_err());
+        assert_eq!(get(&upstream, "http://MOCK.TEST").unwrap().0.status, 404);
+        assert_eq!(
+            get(&upstream, "http://mock.test?q=1").unwrap().0.status,
+            404
+        );
+    }
+
+    #[test]
+    fn test_route_file_is_validated() {
+        let path = std::env::temp_dir().join(format!("mock-routes-{}.json", std::process::id()));
+        std::fs::write(&path, r#"[{"path": "/a", "body": "x"}]"#).unwrap();
+        let routes = MockRoute::load(&path).unwrap();
+        assert_eq!(routes, [MockRoute::new("/a", 200, "x")]);
+
+        std::fs::write(&path, r#"[{"path": "/a", "colour": "red"}]"#).unwrap();
+        let err = MockRoute::load(&path).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
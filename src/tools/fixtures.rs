@@ -0,0 +1,189 @@
+//! Fixture tools: `fixture_generate`.
+//!
+//! Generates realistic-looking structured records from a seeded RNG. The same
+//! kind, count, and seed always produce the same records, so clients can
+//! generate test data from the server instead of bundling datasets.
+
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// Largest number of records one call may generate.
+pub const MAX_FIXTURE_COUNT: usize = 1000;
+
+/// Kind of record to generate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FixtureKind {
+    /// Name, email, age, and phone number.
+    People,
+    /// Street, city, region, postal code, and country.
+    Addresses,
+    /// SKU, name, category, price, and stock level.
+    Products,
+}
+
+/// Parameters for the `fixture_generate` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct FixtureGenerateParams {
+    /// Kind of record: people, addresses, or products
+    pub kind: FixtureKind,
+    /// Number of records (default: 10, max: 1000)
+    #[serde(default = "default_count")]
+    pub count: usize,
+    /// RNG seed (default: the server seed, `MCP_SEED`, or a random seed)
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+const fn default_count() -> usize {
+    10
+}
+
+const FIRST_NAMES: &[&str] = &[
+    "Ada",
+    "Alan",
+    "Grace",
+    "Linus",
+    "Margaret",
+    "Dennis",
+    "Barbara",
+    "Ken",
+    "Frances",
+    "Edsger",
+    "Radia",
+    "Donald",
+    "Hedy",
+    "Tim",
+    "Katherine",
+    "Guido",
+];
+const LAST_NAMES: &[&str] = &[
+    "Lovelace",
+    "Turing",
+    "Hopper",
+    "Torvalds",
+    "Hamilton",
+    "Ritchie",
+    "Liskov",
+    "Thompson",
+    "Allen",
+    "Dijkstra",
+    "Perlman",
+    "Knuth",
+    "Lamarr",
+    "Berners-Lee",
+    "Johnson",
+    "van Rossum",
+];
+const EMAIL_DOMAINS: &[&str] = &["example.com", "example.org", "example.net"];
+const STREET_NAMES: &[&str] = &[
+    "Maple", "Oak", "Cedar", "Elm", "Willow", "Birch", "Pine", "Chestnut", "Juniper", "Aspen",
+];
+const STREET_SUFFIXES: &[&str] = &["St", "Ave", "Rd", "Ln", "Way", "Blvd"];
+/// City, region, country (ISO 3166-1 alpha-2).
+const CITIES: &[(&str, &str, &str)] = &[
+    ("Springfield", "IL", "US"),
+    ("Portland", "OR", "US"),
+    ("Austin", "TX", "US"),
+    ("Toronto", "ON", "CA"),
+    ("Manchester", "England", "GB"),
+    ("Melbourne", "VIC", "AU"),
+    ("Dublin", "Leinster", "IE"),
+    ("Auckland", "Auckland", "NZ"),
+];
+const PRODUCT_ADJECTIVES: &[&str] = &[
+    "Ergonomic",
+    "Rustic",
+    "Sleek",
+    "Durable",
+    "Compact",
+    "Wireless",
+    "Handcrafted",
+    "Recycled",
+];
+const PRODUCT_NOUNS: &[&str] = &[
+    "Chair",
+    "Lamp",
+    "Keyboard",
+    "Backpack",
+    "Kettle",
+    "Notebook",
+    "Headphones",
+    "Mug",
+];
+const PRODUCT_CATEGORIES: &[&str] = &["home", "office", "electronics", "outdoor", "kitchen"];
+
+/// Generate `count` records of `kind` from `seed`.
+#[must_use]
+pub fn generate(kind: FixtureKind, count: usize, seed: u64) -> Vec<Value> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (1..=count)
+        .map(|id| match kind {
+            FixtureKind::People => person(&mut rng, id),
+            FixtureKind::Addresses => address(&mut rng, id),
+            FixtureKind::Products => product(&mut rng, id),
+        })
+        .collect()
+}
+
+fn pick<'a>(rng: &mut StdRng, items: &[&'a str]) -> &'a str {
+    items.choose(rng).copied().unwrap_or_default()
+}
+
+fn person(rng: &mut StdRng, id: usize) -> Value {
+    let first = pick(rng, FIRST_NAMES);
+    let last = pick(rng, LAST_NAMES);
+    let local: String = format!("{first}.{last}")
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '.')
+        .collect();
+    json!({
+        "id": id,
+        "first_name": first,
+        "last_name": last,
+        "email": format!("{local}{id}@{}", pick(rng, EMAIL_DOMAINS)),
+        "age": rng.random_range(18..=90),
+        // 555-01xx numbers are reserved for fiction
+        "phone": format!("+1-555-01{:02}", rng.random_range(0..100)),
+    })
+}
+
+fn address(rng: &mut StdRng, id: usize) -> Value {
+    let (city, region, country) = *CITIES.choose(rng).unwrap_or(&CITIES[0]);
+    json!({
+        "id": id,
+        "street": format!(
+            "{} {} {}",
+            rng.random_range(1..=9999),
+            pick(rng, STREET_NAMES),
+            pick(rng, STREET_SUFFIXES)
+        ),
+        "city": city,
+        "region": region,
+        "postal_code": format!("{:05}", rng.random_range(0..100_000)),
+        "country": country,
+    })
+}
+
+fn product(rng: &mut StdRng, id: usize) -> Value {
+    let cents: u32 = rng.random_range(199..=99_999);
+    let stock: u32 = if rng.random_bool(0.8) {
+        rng.random_range(1..=500)
+    } else {
+        0
+    };
+    json!({
+        "id": id,
+        "sku": format!("SKU-{:06}", rng.random_range(0..1_000_000)),
+        "name": format!("{} {}", pick(rng, PRODUCT_ADJECTIVES), pick(rng, PRODUCT_NOUNS)),
+        "category": pick(rng, PRODUCT_CATEGORIES),
+        "price": f64::from(cents) / 100.0,
+        "stock": stock,
+        "in_stock": stock > 0,
+    })
+}
@@ -0,0 +1,165 @@
+//! Key-value tools: `kv_set`, `kv_get`, `kv_delete`, `kv_list`.
+//!
+//! Unlike the other tools these have observable side effects. Each session
+//! (the `mcp-session-id` header, or `default` without sessions) gets its own
+//! in-memory map, so clients can test sequencing and session isolation. The
+//! maps live until the server stops.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, PoisonError};
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+/// Keys a single session may hold.
+pub const MAX_KEYS: usize = 1000;
+
+/// Parameters for the `kv_set` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct KvSetParams {
+    /// Key to set
+    pub key: String,
+    /// Value to store
+    pub value: String,
+}
+
+/// Parameters for the `kv_get` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct KvGetParams {
+    /// Key to read
+    pub key: String,
+}
+
+/// Parameters for the `kv_delete` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct KvDeleteParams {
+    /// Key to delete
+    pub key: String,
+}
+
+/// Parameters for the `kv_list` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct KvListParams {
+    /// Only list keys starting with this prefix
+    #[serde(default)]
+    pub prefix: Option<String>,
+}
+
+/// Per-session key-value maps backing the `kv_*` tools.
+#[derive(Debug, Default)]
+pub struct KvStore {
+    sessions: Mutex<HashMap<String, BTreeMap<String, String>>>,
+}
+
+impl KvStore {
+    /// Create an empty store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The current value of `key` in `session`.
+    #[must_use]
+    pub fn get(&self, session: &str, key: &str) -> Option<String> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(session)
+            .and_then(|map| map.get(key))
+            .cloned()
+    }
+
+    /// Set `key` in `session`, returning the previous value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `key` is new and the session already holds
+    /// [`MAX_KEYS`] keys.
+    #[allow(clippy::significant_drop_tightening)]
+    pub fn set(&self, session: &str, key: &str, value: String) -> Result<Option<String>, String> {
+        let mut sessions = self.sessions.lock().unwrap_or_else(PoisonError::into_inner);
+        let map = sessions.entry(session.to_string()).or_default();
+        if map.len() >= MAX_KEYS && !map.contains_key(key) {
+            return Err(format!("Session holds the maximum of {MAX_KEYS} keys"));
+        }
+        Ok(map.insert(key.to_string(), value))
+    }
+
+    /// Remove `key` from `session`, returning its value if it was set.
+    pub fn delete(&self, session: &str, key: &str) -> Option<String> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_mut(session)
+            .and_then(|map| map.remove(key))
+    }
+
+    /// Entries of `session` whose key starts with `prefix`, sorted by key.
+    #[must_use]
+    pub fn list(&self, session: &str, prefix: &str) -> BTreeMap<String, String> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(session)
+            .map(|map| {
+                map.iter()
+                    .filter(|(key, _)| key.starts_with(prefix))
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Result body of `kv_set`: the key, its new value, and the value it replaced.
+#[must_use]
+pub fn set_result(key: &str, value: &str, previous: Option<&str>) -> Value {
+    json!({ "key": key, "value": value, "previous": previous })
+}
+
+/// Result body of `kv_delete`: the key, whether it existed, and its old value.
+#[must_use]
+pub fn delete_result(key: &str, previous: Option<&str>) -> Value {
+    json!({ "key": key, "deleted": previous.is_some(), "previous": previous })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sessions_are_isolated() {
+        let store = KvStore::new();
+        assert_eq!(store.set("s1", "a", "1".into()), Ok(None));
+        assert_eq!(store.set("s1", "a", "2".into()), Ok(Some("1".into())));
+        assert_eq!(store.get("s1", "a").as_deref(), Some("2"));
+        assert_eq!(store.get("s2", "a"), None);
+        assert_eq!(store.delete("s2", "a"), None);
+        assert_eq!(store.delete("s1", "a").as_deref(), Some("2"));
+        assert_eq!(store.get("s1", "a"), None);
+    }
+
+    #[test]
+    fn test_list_filters_by_prefix() {
+        let store = KvStore::new();
+        for key in ["user:2", "user:1", "order:1"] {
+            store.set("s1", key, key.to_uppercase()).unwrap();
+        }
+        let keys: Vec<String> = store.list("s1", "user:").into_keys().collect();
+        assert_eq!(keys, ["user:1", "user:2"]);
+        assert_eq!(store.list("s1", "").len(), 3);
+        assert!(store.list("s2", "").is_empty());
+    }
+
+    #[test]
+    fn test_key_limit() {
+        let store = KvStore::new();
+        for i in 0..MAX_KEYS {
+            store.set("s1", &i.to_string(), String::new()).unwrap();
+        }
+        assert!(store.set("s1", "one-more", String::new()).is_err());
+        // Overwriting an existing key is still allowed
+        assert!(store.set("s1", "0", "x".into()).is_ok());
+    }
+}
@@ -0,0 +1,86 @@
+//! Markdown tool: `markdown_to_html`.
+//!
+//! Renders `CommonMark` with the GitHub extensions (tables, strikethrough,
+//! task lists, footnotes) through `pulldown-cmark`. Raw HTML in the input is
+//! passed through unless `sanitize` is set, when `ammonia` strips scripts,
+//! event handlers, `javascript:` links, and anything else outside its
+//! allowlist.
+
+use pulldown_cmark::{Options, Parser, html};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+/// Parameters for the `markdown_to_html` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct MarkdownToHtmlParams {
+    /// Markdown text
+    pub markdown: String,
+    /// Strip scripts, event handlers, and unsafe URLs from the HTML (default: true)
+    #[serde(default = "default_sanitize")]
+    pub sanitize: bool,
+}
+
+const fn default_sanitize() -> bool {
+    true
+}
+
+/// Render `markdown` to HTML, sanitized when `sanitize` is set.
+#[must_use]
+pub fn to_html(markdown: &str, sanitize: bool) -> String {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES;
+    let mut rendered = String::new();
+    html::push_html(&mut rendered, Parser::new_ext(markdown, options));
+    if sanitize {
+        // Keep task-list checkboxes, which ammonia drops by default
+        ammonia::Builder::default()
+            .add_tags(["input"])
+            .add_tag_attribute_values("input", "type", ["checkbox"])
+            .add_tag_attributes("input", ["checked", "disabled"])
+            .clean(&rendered)
+            .to_string()
+    } else {
+        rendered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renders_gfm() {
+        let html = to_html(
+            "# Title\n\n**bold** and ~~gone~~\n\n| a | b |\n|---|---|\n| 1 | 2 |\n",
+            true,
+        );
+        assert!(html.contains("<h1>Title</h1>"), "{html}");
+        assert!(html.contains("<strong>bold</strong>"), "{html}");
+        assert!(html.contains("<del>gone</del>"), "{html}");
+        assert!(html.contains("<td>2</td>"), "{html}");
+
+        let html = to_html("- [x] done\n", true);
+        assert!(html.contains("type=\"checkbox\""), "{html}");
+        let html = to_html("<input type=\"text\" value=\"x\">", true);
+        assert!(!html.contains("text"), "{html}");
+    }
+
+    #[test]
+    fn test_sanitize_strips_injection() {
+        let markdown = "hi <script>alert(1)</script>\n\n\
+            <img src=x onerror=\"alert(2)\">\n\n\
+            [link](javascript:alert(3))\n\n\
+            <a href=\"https://example.com\" onclick=\"alert(4)\">ok</a>\n";
+        let html = to_html(markdown, true);
+        for needle in ["<script", "alert", "onerror", "onclick", "javascript:"] {
+            assert!(!html.contains(needle), "{needle} in {html}");
+        }
+        assert!(html.contains("href=\"https://example.com\""), "{html}");
+
+        let raw = to_html(markdown, false);
+        assert!(raw.contains("<script>alert(1)</script>"), "{raw}");
+        assert!(raw.contains("onerror"), "{raw}");
+    }
+}
@@ -1,7 +1,7 @@
-//! Math operation tools: add, subtract, multiply, divide.
+//! Math operation tools: add, subtract, multiply, divide, stats.
 
 use schemars::JsonSchema;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Parameters for the add tool.
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -38,3 +38,166 @@ pub struct DivideParams {
     /// Denominator
     pub b: f64,
 }
+
+/// Parameters for the stats tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct StatsParams {
+    /// Numbers to summarize; must not be empty
+    pub values: Vec<f64>,
+    /// Percentiles to report, each 0 to 100 (default: 25, 50, 75, 90, 99)
+    #[serde(default = "default_percentiles")]
+    pub percentiles: Vec<f64>,
+    /// Use the sample (n - 1) variance instead of the population variance (default: false)
+    #[serde(default)]
+    pub sample: bool,
+}
+
+fn default_percentiles() -> Vec<f64> {
+    vec![25.0, 50.0, 75.0, 90.0, 99.0]
+}
+
+/// Structured result of the stats tool.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct Stats {
+    /// Number of values.
+    pub count: usize,
+    /// Smallest value.
+    pub min: f64,
+    /// Largest value.
+    pub max: f64,
+    /// Sum of the values.
+    pub sum: f64,
+    /// Arithmetic mean.
+    pub mean: f64,
+    /// Middle value, or the mean of the two middle values.
+    pub median: f64,
+    /// Population or sample variance, per `sample`.
+    pub variance: f64,
+    /// Square root of `variance`.
+    pub stddev: f64,
+    /// Requested percentiles, linearly interpolated between closest ranks.
+    pub percentiles: Vec<Percentile>,
+}
+
+/// One percentile of a stats result.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct Percentile {
+    /// Percentile rank, 0 to 100.
+    pub p: f64,
+    /// Value at that rank.
+    pub value: f64,
+}
+
+/// Summary statistics of `values`.
+///
+/// # Errors
+///
+/// Returns an error if `values` is empty, contains NaN or an infinity, needs
+/// two values for a sample variance, a percentile is outside 0 to 100, or a
+/// result overflows `f64`.
+pub fn stats(values: &[f64], percentiles: &[f64], sample: bool) -> Result<Stats, String> {
+    if values.is_empty() {
+        return Err("values must not be empty".to_string());
+    }
+    if let Some(i) = values.iter().position(|v| !v.is_finite()) {
+        return Err(format!("values[{i}] is not a finite number"));
+    }
+    if sample && values.len() < 2 {
+        return Err("sample variance needs at least two values".to_string());
+    }
+    if let Some(p) = percentiles.iter().find(|p| !(0.0..=100.0).contains(*p)) {
+        return Err(format!("percentile {p} is outside 0 to 100"));
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    #[allow(clippy::cast_precision_loss)]
+    let n = values.len() as f64;
+    let sum: f64 = values.iter().sum();
+    let mean = values.iter().map(|v| v / n).sum::<f64>();
+    let squares: f64 = values.iter().map(|v| (v - mean).powi(2)).sum();
+    let variance = squares / if sample { n - 1.0 } else { n };
+
+    let result = Stats {
+        count: values.len(),
+        min: sorted[0],
+        max: sorted[sorted.len() - 1],
+        sum,
+        mean,
+        median: percentile(&sorted, 50.0),
+        variance,
+        stddev: variance.sqrt(),
+        percentiles: percentiles
+            .iter()
+            .map(|&p| Percentile {
+                p,
+                value: percentile(&sorted, p),
+            })
+            .collect(),
+    };
+    if [result.sum, result.variance].iter().any(|v| !v.is_finite()) {
+        return Err("result overflows f64".to_string());
+    }
+    Ok(result)
+}
+
+/// The `p`th percentile of non-empty `sorted`, interpolating between the
+/// closest ranks.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let weight = rank - rank.floor();
+    sorted[lower].mul_add(1.0 - weight, sorted[upper] * weight)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_summary() {
+        let result = stats(
+            &[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0],
+            &[0.0, 25.0, 100.0],
+            false,
+        )
+        .unwrap();
+        assert_eq!(result.count, 8);
+        assert_eq!((result.min, result.max, result.sum), (2.0, 9.0, 40.0));
+        assert!((result.mean - 5.0).abs() < 1e-12);
+        assert!((result.median - 4.5).abs() < 1e-12);
+        assert!((result.variance - 4.0).abs() < 1e-12);
+        assert!((result.stddev - 2.0).abs() < 1e-12);
+        let values: Vec<f64> = result.percentiles.iter().map(|p| p.value).collect();
+        assert_eq!(values, [2.0, 4.0, 9.0]);
+
+        let result = stats(&[1.0, 3.0], &[], true).unwrap();
+        assert!((result.variance - 2.0).abs() < 1e-12);
+        let result = stats(&[42.0], &[50.0], false).unwrap();
+        assert_eq!((result.median, result.stddev), (42.0, 0.0));
+    }
+
+    #[test]
+    fn test_stats_errors() {
+        assert!(stats(&[], &[], false).unwrap_err().contains("empty"));
+        assert!(
+            stats(&[1.0, f64::NAN], &[], false)
+                .unwrap_err()
+                .contains("values[1]")
+        );
+        assert!(stats(&[f64::INFINITY], &[], false).is_err());
+        assert!(stats(&[1.0], &[], true).is_err());
+        assert!(stats(&[1.0], &[101.0], false).is_err());
+        assert!(
+            stats(&[f64::MAX, f64::MAX], &[], false)
+                .unwrap_err()
+                .contains("overflows")
+        );
+    }
+}
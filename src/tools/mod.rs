@@ -1,13 +1,30 @@
 //! Tool implementations for the MCP test server.
 //!
-//! This module provides 33 tools for comprehensive testing of MCP clients,
+//! This module provides 97 tools for comprehensive testing of MCP clients,
 //! organized into the following categories:
 //!
-//! - **math**: Basic arithmetic operations (add, subtract, multiply, divide)
-//! - **string**: Text manipulation (echo, concat, uppercase, lowercase, reverse, length)
-//! - **encoding**: Data encoding/decoding (`json_parse`, `json_stringify`, `base64_encode`, `base64_decode`, `hash_sha256`)
-//! - **utility**: Utility functions (`random_number`, `random_uuid`, `current_time`)
-//! - **testing**: Testing helpers (sleep, fail, `fail_with_message`, `slow_echo`, `nested_data`, `large_response`, `binary_data`, noop, `task_slow_compute`, `task_cancellable`, `task_fail`)
+//! - **math**: Basic arithmetic operations (add, subtract, multiply, divide) and array statistics (stats)
+//! - **string**: Text manipulation (echo, concat, uppercase, lowercase, reverse, length, `unicode_info`, `word_count`)
+//! - **array**: Sorting and deduplicating JSON arrays (`sort_array`, `dedup_array`)
+//! - **encoding**: Data encoding/decoding (`json_parse`, `json_stringify`, `base64_encode`, `base64_decode`, `hash_sha256`, `hash_md5`, `hash_sha1`, `hash_sha512`, `hash_crc32`, `hash`, `hmac_sha256`, `url_encode`, `url_decode`, `url_parse`)
+//! - **compression**: Gzip and zstd to and from base64 (`gzip_compress`, `gzip_decompress`, `zstd_compress`, `zstd_decompress`)
+//! - **convert**: YAML and TOML to and from JSON (`yaml_to_json`, `json_to_yaml`, `toml_to_json`)
+//! - **utility**: Utility functions (`random_number`, `random_uuid`, `current_time`, `random_string`, `roll_dice`, `random_choice`)
+//! - **xml**: XML trees and `XPath` 1.0 queries (`xml_parse`, `xml_query`)
+//! - **query**: RFC 9535 `JSONPath` queries over JSON documents (`json_query`)
+//! - **markdown**: Markdown to sanitized HTML (`markdown_to_html`)
+//! - **template**: Jinja-style templates over a JSON context (`render_template`)
+//! - **fixtures**: Seeded fixture records (`fixture_generate`)
+//! - **kv**: Per-session key-value store (`kv_set`, `kv_get`, `kv_delete`, `kv_list`)
+//! - **counter**: Server-wide and per-session counters (`counter_increment`, `counter_reset`)
+//! - **csv**: CSV and TSV to and from JSON rows (`csv_parse`, `csv_format`)
+//! - **datetime**: RFC 3339 date math and time zones (`date_add`, `date_diff`, `timezone_convert`)
+//! - **fetch**: Simulated HTTP against a mock upstream (`fetch_url`)
+//! - **regex**: Regular expressions with capture groups (`regex_match`, `regex_replace`, `regex_extract`)
+//! - **scratch**: Client-written `test://scratch/` resources (`resource_write`, `resource_delete`)
+//! - **ephemeral**: The expiring `test://dynamic/ephemeral` resource (`ephemeral_create`)
+//! - **prompts**: Prompts registered at runtime (`register_prompt`)
+//! - **testing**: Testing helpers (sleep, fail, `fail_with_message`, `slow_echo`, `stream_lines`, `nested_data`, `large_response`, `huge_response`, `edge_case_text`, `panic`, `hang`, `binary_data`, noop, `prompt_audit`, whoami, `debug_echo_request`, `case_probe`, `Case_Probe`, `CASE_PROBE`, `task_slow_compute`, `task_cancellable`, `task_fail`)
 //! - **ui**: MCP App interactive tools (`ui_resource_button`, `ui_resource_form`, `ui_resource_carousel`, `ui_internal_only`, `ui_resource_dashboard`, `ui_resource_data_table`, `ui_resource_pipeline`)
 
 /// Generate a `JsonSchema` impl for an empty params struct that includes
@@ -39,9 +56,26 @@ macro_rules! empty_params_schema {
 }
 pub(crate) use empty_params_schema;
 
+pub mod array;
+pub mod compression;
+pub mod convert;
+pub mod counter;
+pub mod csv;
+pub mod datetime;
 pub mod encoding;
+pub mod ephemeral;
+pub mod fetch;
+pub mod fixtures;
+pub mod kv;
+pub mod markdown;
 pub mod math;
+pub mod prompts;
+pub mod query;
+pub mod regex;
+pub mod scratch;
 pub mod string;
+pub mod template;
 pub mod testing;
 pub mod ui;
 pub mod utility;
+pub mod xml;
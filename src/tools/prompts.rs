@@ -0,0 +1,79 @@
+//! Prompt registration tool: `register_prompt`.
+//!
+//! Adds a prompt at runtime (see
+//! [`registered`](crate::prompts::registered)) and sends
+//! `notifications/prompts/list_changed` to the calling session, so clients
+//! can test refreshing their prompt list.
+
+use rmcp::model::{Prompt, PromptArgument};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+/// An argument declared by a registered prompt.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RegisterPromptArgument {
+    /// Argument name, substituted for `{name}` in the template
+    pub name: String,
+    /// Description shown to users
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Whether `prompts/get` must supply it (default: false)
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Parameters for the `register_prompt` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RegisterPromptParams {
+    /// Prompt name; must not be a built-in or builder-registered prompt
+    pub name: String,
+    /// Description shown in prompts/list
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Declared arguments (default: none)
+    #[serde(default)]
+    pub arguments: Vec<RegisterPromptArgument>,
+    /// Text of the single user message, with `{argument}` placeholders
+    pub template: String,
+}
+
+impl RegisterPromptParams {
+    /// The prompt these parameters declare.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name or an argument name is empty, or if an
+    /// argument is declared twice.
+    pub fn to_prompt(&self) -> Result<Prompt, String> {
+        if self.name.trim().is_empty() {
+            return Err("Prompt name must not be empty".to_string());
+        }
+        let mut arguments: Vec<PromptArgument> = Vec::with_capacity(self.arguments.len());
+        for argument in &self.arguments {
+            if argument.name.trim().is_empty() {
+                return Err("Argument names must not be empty".to_string());
+            }
+            if arguments.iter().any(|a| a.name == argument.name) {
+                return Err(format!("Duplicate argument: {}", argument.name));
+            }
+            let mut declared = PromptArgument::new(&argument.name).with_required(argument.required);
+            if let Some(description) = &argument.description {
+                declared = declared.with_description(description);
+            }
+            arguments.push(declared);
+        }
+        Ok(Prompt::new(
+            &self.name,
+            self.description.as_deref(),
+            Some(arguments),
+        ))
+    }
+}
+
+/// Result body of `register_prompt`: the name, whether it replaced an
+/// earlier registration, and its argument count.
+#[must_use]
+pub fn register_result(name: &str, replaced: bool, arguments: usize) -> Value {
+    json!({ "name": name, "replaced": replaced, "arguments": arguments })
+}
@@ -0,0 +1,115 @@
+//! JSON query tool: `json_query`.
+//!
+//! Evaluates an RFC 9535 `JSONPath` expression against a JSON document and
+//! returns each match with its normalized path as structured content. An
+//! expression that doesn't parse gives an `isError` result starting with
+//! `Invalid JSONPath:` and naming the position at fault.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_json_path::JsonPath;
+
+/// Parameters for the `json_query` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct JsonQueryParams {
+    /// Document to query; any JSON value
+    pub document: Value,
+    /// RFC 9535 `JSONPath` expression, e.g. `$.store.book[?@.price < 10].title`
+    pub path: String,
+}
+
+/// Structured result of `json_query`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct JsonQueryResult {
+    /// Matched nodes in document order.
+    pub matches: Vec<JsonMatch>,
+    /// Number of matches.
+    pub count: usize,
+}
+
+/// One node matched by `json_query`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct JsonMatch {
+    /// Normalized path of the node, e.g. `$['store']['book'][0]`.
+    pub path: String,
+    /// The node's value.
+    pub value: Value,
+}
+
+/// Evaluate `path` against `document`.
+///
+/// # Errors
+///
+/// Returns an error if `path` isn't a valid `JSONPath` expression.
+pub fn query(document: &Value, path: &str) -> Result<JsonQueryResult, String> {
+    let path = JsonPath::parse(path).map_err(|e| format!("Invalid JSONPath: {e}"))?;
+    let matches: Vec<JsonMatch> = path
+        .query_located(document)
+        .into_iter()
+        .map(|node| JsonMatch {
+            path: node.location().to_string(),
+            value: node.node().clone(),
+        })
+        .collect();
+    Ok(JsonQueryResult {
+        count: matches.len(),
+        matches,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn store() -> Value {
+        json!({
+            "store": {
+                "book": [
+                    { "title": "Sayings", "price": 8.95, "tags": ["wisdom"] },
+                    { "title": "Sword", "price": 12.99 },
+                    { "title": "Moby Dick", "price": 8.99, "isbn": "0-553" },
+                ],
+                "bicycle": { "color": "red", "price": 399 },
+            }
+        })
+    }
+
+    fn values(result: &JsonQueryResult) -> Vec<&Value> {
+        result.matches.iter().map(|m| &m.value).collect()
+    }
+
+    #[test]
+    fn test_query_selectors() {
+        let doc = store();
+        let result = query(&doc, "$.store.book[?@.price < 10].title").unwrap();
+        assert_eq!(values(&result), [&json!("Sayings"), &json!("Moby Dick")]);
+        assert_eq!(result.matches[1].path, "$['store']['book'][2]['title']");
+        assert_eq!(result.count, 2);
+
+        let result = query(&doc, "$..price").unwrap();
+        assert_eq!(result.count, 4);
+        let result = query(&doc, "$.store.book[-1].isbn").unwrap();
+        assert_eq!(values(&result), [&json!("0-553")]);
+        let result = query(&doc, "$.store.book[?length(@.tags) > 0].title").unwrap();
+        assert_eq!(values(&result), [&json!("Sayings")]);
+    }
+
+    #[test]
+    fn test_no_matches_and_root() {
+        let doc = store();
+        assert_eq!(query(&doc, "$.missing").unwrap().count, 0);
+        let result = query(&json!(42), "$").unwrap();
+        assert_eq!(result.matches[0].path, "$");
+        assert_eq!(result.matches[0].value, 42);
+    }
+
+    #[test]
+    fn test_invalid_path() {
+        for bad in ["store.book", "$[", "$.store[?@.price <]", ""] {
+            let err = query(&store(), bad).unwrap_err();
+            assert!(err.starts_with("Invalid JSONPath:"), "{bad}: {err}");
+        }
+    }
+}
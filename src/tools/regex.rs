@@ -0,0 +1,305 @@
+//! Regex tools: `regex_match`, `regex_replace`, `regex_extract`.
+//!
+//! Patterns use the syntax of the [`regex`](https://docs.rs/regex) crate.
+//! Results are structured output with capture groups, invalid patterns give
+//! an `isError` result naming the problem, and every call runs under the
+//! server's timeout (`MCP_REGEX_TIMEOUT_MS`), so clients can exercise both
+//! error paths and structured results.
+
+use std::time::Duration;
+
+use regex::{Captures, Regex, RegexBuilder};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Largest compiled pattern accepted, in bytes.
+const SIZE_LIMIT: usize = 1 << 20;
+
+/// Most matches `regex_extract` returns.
+pub const MAX_MATCHES: usize = 1000;
+
+/// Parameters for the `regex_match` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RegexMatchParams {
+    /// Regular expression
+    pub pattern: String,
+    /// Text to search
+    pub text: String,
+}
+
+/// Parameters for the `regex_replace` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RegexReplaceParams {
+    /// Regular expression
+    pub pattern: String,
+    /// Text to search
+    pub text: String,
+    /// Replacement; $1 or ${name} insert capture groups
+    pub replacement: String,
+    /// Replace every match rather than only the first (default: true)
+    #[serde(default = "default_all")]
+    pub all: bool,
+}
+
+const fn default_all() -> bool {
+    true
+}
+
+/// Parameters for the `regex_extract` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RegexExtractParams {
+    /// Regular expression
+    pub pattern: String,
+    /// Text to search
+    pub text: String,
+    /// Most matches to return (default: 100, max: 1000)
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+const fn default_limit() -> usize {
+    100
+}
+
+/// One capture group of a match.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct Group {
+    /// Group index; 0 is the whole match.
+    pub index: usize,
+    /// Group name, for `(?<name>...)` groups.
+    pub name: Option<String>,
+    /// Matched text, or `null` if the group did not participate.
+    pub text: Option<String>,
+    /// Byte offset where the group starts.
+    pub start: Option<usize>,
+    /// Byte offset just past the group's end.
+    pub end: Option<usize>,
+}
+
+/// One match with its capture groups.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct Match {
+    /// Matched text.
+    pub text: String,
+    /// Byte offset where the match starts.
+    pub start: usize,
+    /// Byte offset just past the match's end.
+    pub end: usize,
+    /// Every capture group, including group 0.
+    pub groups: Vec<Group>,
+}
+
+/// Structured result of `regex_match`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct MatchResult {
+    /// Whether the pattern matched anywhere in the text.
+    pub matched: bool,
+    /// The first match, if any.
+    #[serde(rename = "match")]
+    pub first: Option<Match>,
+}
+
+/// Structured result of `regex_replace`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct ReplaceResult {
+    /// The text after replacement.
+    pub result: String,
+    /// Number of matches replaced.
+    pub replacements: usize,
+}
+
+/// Structured result of `regex_extract`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct ExtractResult {
+    /// Number of matches returned.
+    pub count: usize,
+    /// Matches in order of appearance.
+    pub matches: Vec<Match>,
+    /// Whether matches beyond `limit` were left out.
+    pub truncated: bool,
+}
+
+/// Compile `pattern`.
+///
+/// # Errors
+///
+/// Returns `Invalid regex pattern: ...` with the parser's explanation.
+pub fn compile(pattern: &str) -> Result<Regex, String> {
+    RegexBuilder::new(pattern)
+        .size_limit(SIZE_LIMIT)
+        .build()
+        .map_err(|e| format!("Invalid regex pattern: {e}"))
+}
+
+fn to_match(regex: &Regex, captures: &Captures<'_>) -> Match {
+    let whole = captures.get(0).expect("group 0 always participates");
+    Match {
+        text: whole.as_str().to_string(),
+        start: whole.start(),
+        end: whole.end(),
+        groups: regex
+            .capture_names()
+            .enumerate()
+            .map(|(index, name)| {
+                let group = captures.get(index);
+                Group {
+                    index,
+                    name: name.map(str::to_string),
+                    text: group.map(|m| m.as_str().to_string()),
+                    start: group.map(|m| m.start()),
+                    end: group.map(|m| m.end()),
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Find the first match of `pattern` in `text`.
+///
+/// # Errors
+///
+/// Returns an error if `pattern` is invalid.
+pub fn find(pattern: &str, text: &str) -> Result<MatchResult, String> {
+    let regex = compile(pattern)?;
+    let first = regex.captures(text).map(|c| to_match(&regex, &c));
+    Ok(MatchResult {
+        matched: first.is_some(),
+        first,
+    })
+}
+
+/// Replace the first or every match of `pattern` in `text`.
+///
+/// # Errors
+///
+/// Returns an error if `pattern` is invalid.
+pub fn replace(
+    pattern: &str,
+    text: &str,
+    replacement: &str,
+    all: bool,
+) -> Result<ReplaceResult, String> {
+    let regex = compile(pattern)?;
+    // `replacen` treats a limit of 0 as "every match"
+    let (limit, replacements) = if all {
+        (0, regex.find_iter(text).count())
+    } else {
+        (1, usize::from(regex.is_match(text)))
+    };
+    Ok(ReplaceResult {
+        result: regex.replacen(text, limit, replacement).into_owned(),
+        replacements,
+    })
+}
+
+/// Every match of `pattern` in `text`, up to `limit`.
+///
+/// # Errors
+///
+/// Returns an error if `pattern` is invalid or `limit` exceeds
+/// [`MAX_MATCHES`].
+pub fn extract(pattern: &str, text: &str, limit: usize) -> Result<ExtractResult, String> {
+    if limit > MAX_MATCHES {
+        return Err(format!("limit must be at most {MAX_MATCHES}"));
+    }
+    let regex = compile(pattern)?;
+    let mut captures = regex.captures_iter(text);
+    let matches: Vec<Match> = captures
+        .by_ref()
+        .take(limit)
+        .map(|c| to_match(&regex, &c))
+        .collect();
+    Ok(ExtractResult {
+        count: matches.len(),
+        truncated: captures.next().is_some(),
+        matches,
+    })
+}
+
+/// Run `f` on a blocking thread, failing if it takes longer than `timeout`.
+///
+/// A call that times out keeps its thread until it finishes; the client just
+/// stops waiting for it.
+///
+/// # Errors
+///
+/// Returns the error of `f`, or a timeout message.
+pub async fn run_with_timeout<T: Send + 'static>(
+    timeout: Duration,
+    f: impl FnOnce() -> Result<T, String> + Send + 'static,
+) -> Result<T, String> {
+    match tokio::time::timeout(timeout, tokio::task::spawn_blocking(f)).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(e)) => Err(format!("Regex evaluation failed: {e}")),
+        Err(_) => Err(format!(
+            "Regex evaluation exceeded the {}ms timeout",
+            timeout.as_millis()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_reports_groups() {
+        let result = find(r"(?<year>\d{4})-(\d{2})(-x)?", "on 2024-06 ok").unwrap();
+        assert!(result.matched);
+        let first = result.first.unwrap();
+        assert_eq!(
+            (first.text.as_str(), first.start, first.end),
+            ("2024-06", 3, 10)
+        );
+        assert_eq!(first.groups.len(), 4);
+        assert_eq!(first.groups[1].name.as_deref(), Some("year"));
+        assert_eq!(first.groups[1].text.as_deref(), Some("2024"));
+        assert_eq!(first.groups[2].name, None);
+        // The optional group didn't participate
+        assert_eq!(first.groups[3].text, None);
+
+        assert!(!find("z", "abc").unwrap().matched);
+    }
+
+    #[test]
+    fn test_replace_first_or_all() {
+        let all = replace(r"(\w+)@(\w+)", "a@x b@y", "$2:$1", true).unwrap();
+        assert_eq!(all.result, "x:a y:b");
+        assert_eq!(all.replacements, 2);
+        let first = replace(r"\d", "1 2 3", "#", false).unwrap();
+        assert_eq!(first.result, "# 2 3");
+        assert_eq!(first.replacements, 1);
+        assert_eq!(replace("z", "abc", "", true).unwrap().replacements, 0);
+    }
+
+    #[test]
+    fn test_extract_truncates() {
+        let result = extract(r"\d", "1 2 3", 2).unwrap();
+        assert_eq!(result.count, 2);
+        assert!(result.truncated);
+        assert_eq!(result.matches[1].text, "2");
+        assert!(!extract(r"\d", "1 2 3", 3).unwrap().truncated);
+        assert!(extract(r"\d", "1", MAX_MATCHES + 1).is_err());
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let err = find("(unclosed", "x").unwrap_err();
+        assert!(err.starts_with("Invalid regex pattern:"), "{err}");
+        // Backreferences aren't supported by the linear-time engine
+        assert!(compile(r"(a)\1").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_with_timeout() {
+        let ok = run_with_timeout(Duration::from_secs(5), || Ok(1)).await;
+        assert_eq!(ok, Ok(1));
+        let err = run_with_timeout(Duration::from_millis(10), || {
+            std::thread::sleep(Duration::from_millis(200));
+            Ok(())
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(err, "Regex evaluation exceeded the 10ms timeout");
+    }
+}
@@ -0,0 +1,42 @@
+//! Scratch resource tools: `resource_write`, `resource_delete`.
+//!
+//! These mutate the `test://scratch/{name}` resources (see
+//! [`scratch_resources`](crate::resources::scratch_resources)). Creating or
+//! deleting a resource sends `notifications/resources/list_changed` to the
+//! calling session; writing or deleting one sends
+//! `notifications/resources/updated` to its subscribers.
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+/// Parameters for the `resource_write` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ResourceWriteParams {
+    /// Resource name (letters, digits, '-', '_', '.'); the last segment of its URI
+    pub name: String,
+    /// Text content of the resource
+    pub text: String,
+    /// MIME type of the content (default: text/plain)
+    #[serde(default)]
+    pub mime_type: Option<String>,
+}
+
+/// Parameters for the `resource_delete` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ResourceDeleteParams {
+    /// Name of the resource to delete
+    pub name: String,
+}
+
+/// Result body of `resource_write`: the URI, whether it was new, and its size.
+#[must_use]
+pub fn write_result(uri: &str, created: bool, size: usize) -> Value {
+    json!({ "uri": uri, "created": created, "size": size })
+}
+
+/// Result body of `resource_delete`: the URI and whether it existed.
+#[must_use]
+pub fn delete_result(uri: &str, deleted: bool) -> Value {
+    json!({ "uri": uri, "deleted": deleted })
+}
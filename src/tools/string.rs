@@ -1,7 +1,10 @@
-//! String operation tools: echo, concat, uppercase, lowercase, reverse, length.
+//! String operation tools: echo, concat, uppercase, lowercase, reverse, length,
+//! `unicode_info`, `word_count`.
 
 use schemars::JsonSchema;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use unicode_normalization::{UnicodeNormalization, is_nfc, is_nfd, is_nfkc, is_nfkd};
+use unicode_segmentation::UnicodeSegmentation;
 
 /// Parameters for the echo tool.
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -44,3 +47,146 @@ pub struct LengthParams {
     /// Text to get length of
     pub text: String,
 }
+
+/// Parameters for the `unicode_info` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UnicodeInfoParams {
+    /// Text to analyze
+    pub text: String,
+}
+
+/// Parameters for the `word_count` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct WordCountParams {
+    /// Text to count
+    pub text: String,
+}
+
+/// Structured result of `unicode_info`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct UnicodeInfo {
+    /// UTF-8 length in bytes, what `length` reports.
+    pub bytes: usize,
+    /// Number of Unicode scalar values.
+    pub codepoints: usize,
+    /// Number of extended grapheme clusters, what a reader counts as characters.
+    pub graphemes: usize,
+    /// Each scalar value as `U+XXXX`.
+    pub scalars: Vec<String>,
+    /// The four normalization forms of the text.
+    pub normalization: Normalization,
+}
+
+/// The text in each Unicode normalization form.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct Normalization {
+    /// Canonical composition.
+    pub nfc: NormalForm,
+    /// Canonical decomposition.
+    pub nfd: NormalForm,
+    /// Compatibility composition.
+    pub nfkc: NormalForm,
+    /// Compatibility decomposition.
+    pub nfkd: NormalForm,
+}
+
+/// The text in one normalization form.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct NormalForm {
+    /// The normalized text.
+    pub text: String,
+    /// Whether the input was already in this form.
+    pub is_normalized: bool,
+    /// Number of scalar values once normalized.
+    pub codepoints: usize,
+}
+
+/// Structured result of `word_count`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct WordCount {
+    /// Words by Unicode word boundaries (UAX #29), punctuation excluded.
+    pub words: usize,
+    /// Lines, split on `\n` or `\r\n`.
+    pub lines: usize,
+    /// Extended grapheme clusters.
+    pub graphemes: usize,
+    /// UTF-8 length in bytes.
+    pub bytes: usize,
+}
+
+/// Codepoint, grapheme, and normalization details of `text`.
+#[must_use]
+pub fn unicode_info(text: &str) -> UnicodeInfo {
+    let form = |normalized: String, is_normalized: bool| NormalForm {
+        codepoints: normalized.chars().count(),
+        text: normalized,
+        is_normalized,
+    };
+    UnicodeInfo {
+        bytes: text.len(),
+        codepoints: text.chars().count(),
+        graphemes: text.graphemes(true).count(),
+        scalars: text
+            .chars()
+            .map(|c| format!("U+{:04X}", u32::from(c)))
+            .collect(),
+        normalization: Normalization {
+            nfc: form(text.nfc().collect(), is_nfc(text)),
+            nfd: form(text.nfd().collect(), is_nfd(text)),
+            nfkc: form(text.nfkc().collect(), is_nfkc(text)),
+            nfkd: form(text.nfkd().collect(), is_nfkd(text)),
+        },
+    }
+}
+
+/// Word, line, grapheme, and byte counts of `text`.
+#[must_use]
+pub fn word_count(text: &str) -> WordCount {
+    WordCount {
+        words: text.unicode_words().count(),
+        lines: text.lines().count(),
+        graphemes: text.graphemes(true).count(),
+        bytes: text.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unicode_info_counts() {
+        // e + combining acute, a family emoji (ZWJ sequence), and a flag
+        let text = "e\u{301}👨‍👩‍👧🇯🇵";
+        let info = unicode_info(text);
+        assert_eq!(info.graphemes, 3);
+        assert_eq!(info.codepoints, 2 + 5 + 2);
+        assert_eq!(info.bytes, text.len());
+        assert_eq!(&info.scalars[..2], ["U+0065", "U+0301"]);
+        assert_eq!(info.scalars[2], "U+1F468");
+    }
+
+    #[test]
+    fn test_normalization_forms() {
+        let info = unicode_info("e\u{301}");
+        assert_eq!(info.normalization.nfc.text, "\u{e9}");
+        assert_eq!(info.normalization.nfc.codepoints, 1);
+        assert!(!info.normalization.nfc.is_normalized);
+        assert!(info.normalization.nfd.is_normalized);
+
+        // The fi ligature only decomposes under compatibility forms
+        let info = unicode_info("\u{fb01}");
+        assert_eq!(info.normalization.nfc.text, "\u{fb01}");
+        assert_eq!(info.normalization.nfkc.text, "fi");
+        assert!(!info.normalization.nfkd.is_normalized);
+    }
+
+    #[test]
+    fn test_word_count() {
+        let count = word_count("Hello, wörld! 你好\r\nsecond line — 🎉 done\n");
+        assert_eq!(count.lines, 2);
+        assert_eq!(count.words, 7);
+        assert_eq!(word_count("").words, 0);
+        assert_eq!(word_count("  ...  ").words, 0);
+    }
+}
@@ -0,0 +1,124 @@
+//! Template tool: `render_template`.
+//!
+//! Renders a Jinja-style template string with `minijinja` against a free-form
+//! JSON context, so clients can exercise tools whose parameters are arbitrary
+//! nested objects. The `undefined` mode picks how missing variables behave.
+
+use minijinja::{Environment, UndefinedBehavior};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// How `render_template` treats variables missing from the context.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum UndefinedMode {
+    /// Missing variables print as empty; attribute access on them fails.
+    #[default]
+    Lenient,
+    /// Like lenient, but attribute access on a missing variable is also empty.
+    Chainable,
+    /// Any use of a missing variable fails the render.
+    Strict,
+}
+
+impl From<UndefinedMode> for UndefinedBehavior {
+    fn from(mode: UndefinedMode) -> Self {
+        match mode {
+            UndefinedMode::Lenient => Self::Lenient,
+            UndefinedMode::Chainable => Self::Chainable,
+            UndefinedMode::Strict => Self::Strict,
+        }
+    }
+}
+
+/// Parameters for the `render_template` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RenderTemplateParams {
+    /// Jinja-style template, e.g. `Hello {{ user.name }}!`
+    pub template: String,
+    /// Variables available to the template; any nested JSON object
+    #[serde(default)]
+    pub context: Map<String, Value>,
+    /// How missing variables behave (default: lenient)
+    #[serde(default)]
+    pub undefined: UndefinedMode,
+}
+
+/// Render `template` with `context`.
+///
+/// # Errors
+///
+/// Returns an error if the template doesn't compile, or rendering fails,
+/// for example on a missing variable in strict mode.
+pub fn render(
+    template: &str,
+    context: &Map<String, Value>,
+    undefined: UndefinedMode,
+) -> Result<String, String> {
+    let mut env = Environment::new();
+    env.set_undefined_behavior(undefined.into());
+    let template = env
+        .template_from_str(template)
+        .map_err(|e| format!("Invalid template: {e}"))?;
+    template
+        .render(context)
+        .map_err(|e| format!("Render failed: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn context(value: &Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_render_nested_context() {
+        let ctx = context(&json!({
+            "user": { "name": "Ada", "roles": ["admin", "dev"] },
+            "count": 3,
+        }));
+        let out = render(
+            "{{ user.name }} has {{ count }} items: {{ user.roles | join(', ') }}",
+            &ctx,
+            UndefinedMode::Lenient,
+        )
+        .unwrap();
+        assert_eq!(out, "Ada has 3 items: admin, dev");
+
+        let out = render(
+            "{% for r in user.roles %}[{{ loop.index }}:{{ r }}]{% endfor %}",
+            &ctx,
+            UndefinedMode::Strict,
+        )
+        .unwrap();
+        assert_eq!(out, "[1:admin][2:dev]");
+    }
+
+    #[test]
+    fn test_undefined_modes() {
+        let ctx = Map::new();
+        assert_eq!(
+            render("<{{ missing }}>", &ctx, UndefinedMode::Lenient).unwrap(),
+            "<>"
+        );
+        assert!(render("{{ missing.field }}", &ctx, UndefinedMode::Lenient).is_err());
+        assert_eq!(
+            render("<{{ missing.field }}>", &ctx, UndefinedMode::Chainable).unwrap(),
+            "<>"
+        );
+        let err = render("{{ missing }}", &ctx, UndefinedMode::Strict).unwrap_err();
+        assert!(err.starts_with("Render failed:"), "{err}");
+    }
+
+    #[test]
+    fn test_invalid_template() {
+        let err = render("{% if %}", &Map::new(), UndefinedMode::Lenient).unwrap_err();
+        assert!(err.starts_with("Invalid template:"), "{err}");
+        let err = render("{{ unclosed", &Map::new(), UndefinedMode::Lenient).unwrap_err();
+        assert!(err.starts_with("Invalid template:"), "{err}");
+    }
+}
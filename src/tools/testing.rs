@@ -1,7 +1,7 @@
-//! Testing tools: sleep, fail, `fail_with_message`, `slow_echo`, `nested_data`, `large_response`, `binary_data`.
+//! Testing tools: sleep, fail, `fail_with_message`, `slow_echo`, `nested_data`, `large_response`, `huge_response`, `binary_data`, `prompt_audit`, `stream_lines`, `edge_case_text`, `panic`.
 
 use schemars::JsonSchema;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Parameters for the sleep tool.
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -34,6 +34,38 @@ pub struct SlowEchoParams {
     pub delay_ms: u64,
 }
 
+/// Most lines `stream_lines` sends.
+pub const MAX_STREAM_LINES: u32 = 1000;
+
+/// Longest pause `stream_lines` takes between lines, in milliseconds.
+pub const MAX_STREAM_INTERVAL_MS: u64 = 10_000;
+
+/// Parameters for the `stream_lines` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct StreamLinesParams {
+    /// Number of lines to send (default: 10, max: 1000)
+    #[serde(default = "default_stream_count")]
+    pub count: u32,
+    /// Milliseconds between lines (default: 100, max: 10000)
+    #[serde(default = "default_stream_interval")]
+    pub interval_ms: u64,
+    /// Text of each line, numbered as `{text} {n}/{count}` (default: "line")
+    #[serde(default = "default_stream_text")]
+    pub text: String,
+}
+
+const fn default_stream_count() -> u32 {
+    10
+}
+
+const fn default_stream_interval() -> u64 {
+    100
+}
+
+fn default_stream_text() -> String {
+    "line".to_string()
+}
+
 /// Parameters for the `nested_data` tool.
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct NestedDataParams {
@@ -48,6 +80,115 @@ pub struct LargeResponseParams {
     pub size_bytes: usize,
 }
 
+/// Largest payload `huge_response` builds, in bytes (512 MiB).
+pub const MAX_HUGE_RESPONSE_BYTES: usize = 512 * 1024 * 1024;
+
+/// Parameters for the `huge_response` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct HugeResponseParams {
+    /// Exact size of the text payload in bytes (max: 536870912)
+    pub size_bytes: usize,
+}
+
+/// Text of exactly `size_bytes` bytes, made of repeated ASCII lines.
+#[must_use]
+pub fn huge_text(size_bytes: usize) -> String {
+    const LINE: &str = "This line pads a deliberately huge response.\n";
+    let mut text = LINE.repeat(size_bytes.div_ceil(LINE.len()));
+    text.truncate(size_bytes);
+    text
+}
+
+/// Longest line `edge_case_text` builds in `long_line` mode, in characters.
+pub const MAX_EDGE_CASE_LINE: usize = 10_000_000;
+
+/// Kind of awkward text returned by `edge_case_text`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeCaseMode {
+    /// Unpaired UTF-16 surrogates. UTF-8 cannot carry them, so they are
+    /// written as literal `\uD800`-style escapes for the client to (mis)decode.
+    LoneSurrogates,
+    /// NUL (`U+0000`) at the start, middle, and end.
+    Nul,
+    /// A leading byte order mark, and another one mid-text.
+    Bom,
+    /// One line of `length` characters with no line break.
+    LongLine,
+    /// Hebrew and Arabic mixed with Latin text, digits, and bidi overrides.
+    Rtl,
+    /// Zero-width spaces, joiners, and word joiners between visible letters.
+    ZeroWidth,
+}
+
+/// Parameters for the `edge_case_text` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct EdgeCaseTextParams {
+    /// Which edge case to return
+    pub mode: EdgeCaseMode,
+    /// Line length in characters for `long_line` (default: 100000, max: 10000000)
+    #[serde(default = "default_edge_case_length")]
+    pub length: usize,
+}
+
+const fn default_edge_case_length() -> usize {
+    100_000
+}
+
+/// The text of an `edge_case_text` mode.
+///
+/// # Errors
+///
+/// Returns an error if `length` is over [`MAX_EDGE_CASE_LINE`] in `long_line` mode.
+pub fn edge_case_text(mode: EdgeCaseMode, length: usize) -> Result<String, String> {
+    let text = match mode {
+        EdgeCaseMode::LoneSurrogates => {
+            r"high \uD800 alone, low \uDFFF alone, reversed pair \uDE00\uD83D".to_string()
+        }
+        EdgeCaseMode::Nul => "\0leading, inner\0nul, trailing\0".to_string(),
+        EdgeCaseMode::Bom => "\u{FEFF}starts with a BOM, and one\u{FEFF}inside".to_string(),
+        EdgeCaseMode::LongLine => {
+            if length > MAX_EDGE_CASE_LINE {
+                return Err(format!("length must be at most {MAX_EDGE_CASE_LINE}"));
+            }
+            "abcdefghij".chars().cycle().take(length).collect()
+        }
+        EdgeCaseMode::Rtl => "English, עברית 123, العربية ٤٥٦, and \u{202E}cod.exe\u{202C} \
+                              with an override, \u{2067}isolated שלום\u{2069} end"
+            .to_string(),
+        EdgeCaseMode::ZeroWidth => {
+            "zero\u{200B}width\u{200C}non\u{200D}joiner\u{2060}word\u{FEFF}".to_string()
+        }
+    };
+    Ok(text)
+}
+
+/// How the `panic` tool fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PanicMode {
+    /// Panic in the tool handler.
+    #[default]
+    Panic,
+    /// Drop the connection mid-response.
+    AbortConnection,
+}
+
+/// Parameters for the `panic` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PanicParams {
+    /// How to fail (default: panic)
+    #[serde(default)]
+    pub mode: PanicMode,
+    /// Panic message (default: "Simulated panic")
+    #[serde(default = "default_panic_message")]
+    pub message: String,
+}
+
+fn default_panic_message() -> String {
+    "Simulated panic".to_string()
+}
+
 /// Parameters for the `binary_data` tool.
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct BinaryDataParams {
@@ -55,6 +196,14 @@ pub struct BinaryDataParams {
     pub size_bytes: usize,
 }
 
+/// Parameters for the `prompt_audit` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PromptAuditParams {
+    /// Clear the audit log after returning it (default: false)
+    #[serde(default)]
+    pub clear: bool,
+}
+
 // =============================================================================
 // TASK TOOLS — async long-running operations (MCP Tasks spec)
 // =============================================================================
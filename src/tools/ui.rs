@@ -3,6 +3,7 @@
 //! These tools return embedded HTML resources with `ui://` URIs to test
 //! how MCP clients render and handle interactive UI content.
 
+use schemars::JsonSchema;
 use serde::Deserialize;
 
 /// Parameters for the `ui_resource_button` tool (no input needed).
@@ -63,3 +64,21 @@ super::empty_params_schema!(
     UiResourcePipelineParams,
     "Parameters for the `ui_resource_pipeline` tool (no input needed)."
 );
+
+/// Parameters for the `ui_record_event` tool.
+///
+/// The app shim calls it for every message its iframe posts to the host,
+/// filling `test://dynamic/ui-events`.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UiRecordEventParams {
+    /// Name of the reporting app, from its `ui/initialize`
+    pub app: String,
+    /// JSON-RPC method of the posted message
+    pub method: String,
+    /// JSON-RPC id, for requests
+    #[serde(default)]
+    pub id: Option<serde_json::Value>,
+    /// Params of the posted message
+    #[serde(default)]
+    pub params: Option<serde_json::Value>,
+}
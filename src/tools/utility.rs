@@ -1,7 +1,28 @@
-//! Utility tools: `random_number`, `random_uuid`, `current_time`.
+//! Utility tools: `random_number`, `random_uuid`, `current_time`,
+//! `random_string`, `roll_dice`, `random_choice`.
+//!
+//! The seeded tools draw from a `StdRng` seeded by the `seed` argument, the
+//! server seed (`MCP_SEED`), or a random seed, and echo the seed they used,
+//! so any result can be reproduced.
 
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, index};
+use rand::{Rng, SeedableRng};
 use schemars::JsonSchema;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Longest string `random_string` generates.
+pub const MAX_STRING_LENGTH: usize = 4096;
+
+/// Most dice `roll_dice` rolls at once.
+pub const MAX_DICE: u32 = 100;
+
+/// Most sides a die may have.
+pub const MAX_SIDES: u32 = 1000;
+
+/// Most items `random_choice` picks at once.
+pub const MAX_CHOICES: usize = 4096;
 
 /// Parameters for the `random_number` tool.
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -27,3 +48,280 @@ super::empty_params_schema!(
     CurrentTimeParams,
     "Parameters for the `current_time` tool (no parameters needed)."
 );
+
+/// Character set of the `random_string` tool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Charset {
+    /// `A-Z`, `a-z`, and `0-9`.
+    #[default]
+    Alphanumeric,
+    /// `A-Z` and `a-z`.
+    Alphabetic,
+    /// `0-9`.
+    Numeric,
+    /// `0-9` and `a-f`.
+    Hex,
+    /// Alphanumerics plus ASCII punctuation, for passwords.
+    Password,
+}
+
+impl Charset {
+    /// The characters of this set.
+    #[must_use]
+    pub const fn chars(self) -> &'static str {
+        match self {
+            Self::Alphanumeric => "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+            Self::Alphabetic => "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+            Self::Numeric => "0123456789",
+            Self::Hex => "0123456789abcdef",
+            Self::Password => {
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789!#$%&*+-=?@^_~"
+            }
+        }
+    }
+}
+
+/// Parameters for the `random_string` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RandomStringParams {
+    /// Number of characters (default: 16, max: 4096)
+    #[serde(default = "default_length")]
+    pub length: usize,
+    /// Built-in character set (default: alphanumeric)
+    #[serde(default)]
+    pub charset: Charset,
+    /// Characters to draw from instead of `charset`
+    #[serde(default)]
+    pub chars: Option<String>,
+    /// RNG seed (default: the server seed, `MCP_SEED`, or a random seed)
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+/// Parameters for the `roll_dice` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RollDiceParams {
+    /// Dice in `NdM` notation with an optional modifier, e.g. `3d6`, `d20`, `2d8+3`
+    pub notation: String,
+    /// RNG seed (default: the server seed, `MCP_SEED`, or a random seed)
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+/// Parameters for the `random_choice` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RandomChoiceParams {
+    /// Items to choose from; any JSON values
+    pub items: Vec<Value>,
+    /// Number of items to pick (default: 1, max: 4096)
+    #[serde(default = "default_count")]
+    pub count: usize,
+    /// Pick without replacement, so no item is chosen twice (default: false)
+    #[serde(default)]
+    pub unique: bool,
+    /// RNG seed (default: the server seed, `MCP_SEED`, or a random seed)
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+const fn default_length() -> usize {
+    16
+}
+
+const fn default_count() -> usize {
+    1
+}
+
+/// Structured result of `random_string`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct RandomString {
+    /// The generated string.
+    pub value: String,
+    /// Seed that reproduces it.
+    pub seed: u64,
+}
+
+/// Structured result of `roll_dice`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct DiceRoll {
+    /// Each die's face, in roll order.
+    pub rolls: Vec<u32>,
+    /// Constant added to the sum of the rolls.
+    pub modifier: i64,
+    /// Sum of the rolls plus the modifier.
+    pub total: i64,
+    /// Seed that reproduces the rolls.
+    pub seed: u64,
+}
+
+/// Structured result of `random_choice`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct RandomChoice {
+    /// The chosen items, in pick order.
+    pub choices: Vec<Value>,
+    /// Seed that reproduces the picks.
+    pub seed: u64,
+}
+
+/// A `length`-character string drawn from `chars`, or from `charset`.
+///
+/// # Errors
+///
+/// Returns an error if `length` is over [`MAX_STRING_LENGTH`] or `chars` is
+/// empty.
+pub fn random_string(
+    length: usize,
+    charset: Charset,
+    chars: Option<&str>,
+    seed: u64,
+) -> Result<RandomString, String> {
+    if length > MAX_STRING_LENGTH {
+        return Err(format!("length must be at most {MAX_STRING_LENGTH}"));
+    }
+    let pool: Vec<char> = chars.unwrap_or_else(|| charset.chars()).chars().collect();
+    if pool.is_empty() {
+        return Err("chars must not be empty".to_string());
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let value = (0..length)
+        .map(|_| pool[rng.random_range(0..pool.len())])
+        .collect();
+    Ok(RandomString { value, seed })
+}
+
+/// Roll the dice of `notation`, e.g. `3d6+2`.
+///
+/// # Errors
+///
+/// Returns an error if `notation` isn't `NdM` with an optional `+K` or `-K`,
+/// or asks for more than [`MAX_DICE`] dice or [`MAX_SIDES`] sides.
+pub fn roll_dice(notation: &str, seed: u64) -> Result<DiceRoll, String> {
+    let invalid = || format!("Invalid dice notation {notation:?}: expected NdM, e.g. 3d6 or 2d8+3");
+    let lowered = notation.trim().to_ascii_lowercase();
+    let (dice, rest) = lowered.split_once('d').ok_or_else(invalid)?;
+    let (sides, modifier) = match rest.find(['+', '-']) {
+        Some(at) => (
+            &rest[..at],
+            rest[at..].parse::<i64>().map_err(|_| invalid())?,
+        ),
+        None => (rest, 0),
+    };
+    let dice: u32 = if dice.is_empty() {
+        1
+    } else {
+        dice.parse().map_err(|_| invalid())?
+    };
+    let sides: u32 = sides.parse().map_err(|_| invalid())?;
+    if !(1..=MAX_DICE).contains(&dice) {
+        return Err(format!("dice count must be 1 to {MAX_DICE}, got {dice}"));
+    }
+    if !(2..=MAX_SIDES).contains(&sides) {
+        return Err(format!("sides must be 2 to {MAX_SIDES}, got {sides}"));
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let rolls: Vec<u32> = (0..dice).map(|_| rng.random_range(1..=sides)).collect();
+    let total = rolls.iter().map(|&r| i64::from(r)).sum::<i64>() + modifier;
+    Ok(DiceRoll {
+        rolls,
+        modifier,
+        total,
+        seed,
+    })
+}
+
+/// Pick `count` of `items`, without replacement when `unique`.
+///
+/// # Errors
+///
+/// Returns an error if `items` is empty, `count` is over [`MAX_CHOICES`], or
+/// `unique` asks for more items than there are.
+pub fn random_choice(
+    items: &[Value],
+    count: usize,
+    unique: bool,
+    seed: u64,
+) -> Result<RandomChoice, String> {
+    if items.is_empty() {
+        return Err("items must not be empty".to_string());
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let choices = if unique {
+        if count > items.len() {
+            return Err(format!(
+                "cannot pick {count} unique items from {}",
+                items.len()
+            ));
+        }
+        index::sample(&mut rng, items.len(), count)
+            .into_iter()
+            .map(|i| items[i].clone())
+            .collect()
+    } else {
+        if count > MAX_CHOICES {
+            return Err(format!("count must be at most {MAX_CHOICES}"));
+        }
+        (0..count)
+            .filter_map(|_| items.choose(&mut rng).cloned())
+            .collect()
+    };
+    Ok(RandomChoice { choices, seed })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_random_string_charsets() {
+        let hex = random_string(64, Charset::Hex, None, 1).unwrap();
+        assert_eq!(hex.value.len(), 64);
+        assert!(hex.value.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(hex, random_string(64, Charset::Hex, None, 1).unwrap());
+        assert_ne!(hex, random_string(64, Charset::Hex, None, 2).unwrap());
+
+        let custom = random_string(20, Charset::Numeric, Some("äb"), 1).unwrap();
+        assert_eq!(custom.value.chars().count(), 20);
+        assert!(custom.value.chars().all(|c| c == 'ä' || c == 'b'));
+
+        assert!(random_string(1, Charset::Hex, Some(""), 1).is_err());
+        assert!(random_string(MAX_STRING_LENGTH + 1, Charset::Hex, None, 1).is_err());
+    }
+
+    #[test]
+    fn test_roll_dice_notation() {
+        let roll = roll_dice("3d6+2", 7).unwrap();
+        assert_eq!(roll.rolls.len(), 3);
+        assert!(roll.rolls.iter().all(|r| (1..=6).contains(r)));
+        assert_eq!(
+            roll.total,
+            roll.rolls.iter().map(|&r| i64::from(r)).sum::<i64>() + 2
+        );
+        assert_eq!(roll, roll_dice("3D6+2", 7).unwrap());
+
+        assert_eq!(roll_dice("d20", 1).unwrap().rolls.len(), 1);
+        assert_eq!(roll_dice("2d8-10", 1).unwrap().modifier, -10);
+        for bad in ["", "6", "3d", "xd6", "3d6+", "3d6*2", "0d6", "1d1", "101d6"] {
+            assert!(roll_dice(bad, 1).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_random_choice() {
+        let items: Vec<Value> = (0..10).map(|i| json!(i)).collect();
+        let picked = random_choice(&items, 10, true, 3).unwrap();
+        let mut sorted = picked.choices.clone();
+        sorted.sort_by_key(Value::as_i64);
+        assert_eq!(sorted, items);
+        assert_eq!(picked, random_choice(&items, 10, true, 3).unwrap());
+
+        assert_eq!(
+            random_choice(&items, 25, false, 3).unwrap().choices.len(),
+            25
+        );
+        assert!(random_choice(&items, 11, true, 3).is_err());
+        assert!(random_choice(&[], 1, false, 3).is_err());
+    }
+}
@@ -0,0 +1,435 @@
+//! XML tools: `xml_parse`, `xml_query`.
+//!
+//! `xml_parse` returns the document's element tree as structured output and
+//! `xml_query` evaluates an `XPath` 1.0 expression against it. Malformed XML
+//! gives an `isError` result starting with `Invalid XML:` and naming the line
+//! and column, so clients can test both content handling and parser errors.
+
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sxd_xpath::nodeset::Node;
+
+/// Deepest element nesting accepted.
+pub const MAX_DEPTH: usize = 64;
+
+/// Parameters for the `xml_parse` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct XmlParseParams {
+    /// XML document to parse
+    pub xml: String,
+}
+
+/// Parameters for the `xml_query` tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct XmlQueryParams {
+    /// XML document to query
+    pub xml: String,
+    /// `XPath` 1.0 expression, e.g. //item[@id='2']/name
+    pub xpath: String,
+    /// Namespace URIs by the prefixes used in the expression
+    #[serde(default)]
+    pub namespaces: BTreeMap<String, String>,
+}
+
+/// An element of a parsed document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct XmlElement {
+    /// Local name.
+    pub name: String,
+    /// Namespace URI, if the element has one.
+    pub namespace: Option<String>,
+    /// Attributes by local name.
+    pub attributes: BTreeMap<String, String>,
+    /// Child elements and non-blank text, in document order.
+    pub children: Vec<XmlContent>,
+}
+
+/// A child of an element.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum XmlContent {
+    /// A child element.
+    Element(XmlElement),
+    /// A run of text, including CDATA.
+    Text {
+        /// The text, with entities resolved.
+        text: String,
+    },
+}
+
+/// One node selected by `xml_query`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct XmlNode {
+    /// `element`, `attribute`, `text`, `comment`, `root`, `namespace`, or
+    /// `processing-instruction`.
+    pub kind: String,
+    /// Name with its prefix, for named nodes.
+    pub name: Option<String>,
+    /// String value: the concatenated text of an element, or the
+    /// value of an attribute.
+    pub value: String,
+}
+
+/// Structured result of `xml_query`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct XmlQueryResult {
+    /// `nodeset`, `boolean`, `number`, or `string`.
+    pub result_type: String,
+    /// Selected nodes in document order; empty unless a node-set.
+    pub nodes: Vec<XmlNode>,
+    /// The boolean, number, or string result; `null` for a node-set or `NaN`.
+    pub value: serde_json::Value,
+}
+
+/// Deepest element nesting in `xml`, counted from its tags alone so it can
+/// run before the recursive parsers see the document.
+fn nesting_depth(xml: &str) -> usize {
+    let (mut depth, mut deepest) = (0_usize, 0);
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        let skip_to = if rest.starts_with("<!--") {
+            Some("-->")
+        } else if rest.starts_with("<![CDATA[") {
+            Some("]]>")
+        } else if rest.starts_with("<?") {
+            Some("?>")
+        } else {
+            None
+        };
+        if let Some(end) = skip_to {
+            let Some(i) = rest.find(end) else { break };
+            rest = &rest[i + end.len()..];
+            continue;
+        }
+        // The closing '>' of the tag, skipping quoted attribute values
+        let mut quote = None;
+        let Some(close) = rest.char_indices().find_map(|(i, c)| match (quote, c) {
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                None
+            }
+            (Some(q), c) if c == q => {
+                quote = None;
+                None
+            }
+            (None, '>') => Some(i),
+            _ => None,
+        }) else {
+            break;
+        };
+        let tag = &rest[..close];
+        if tag.starts_with("</") {
+            depth = depth.saturating_sub(1);
+        } else if !tag.starts_with("<!") && !tag.ends_with('/') {
+            depth += 1;
+            deepest = deepest.max(depth);
+        }
+        rest = &rest[close..];
+    }
+    deepest
+}
+
+fn parse_document(xml: &str) -> Result<roxmltree::Document<'_>, String> {
+    if nesting_depth(xml) > MAX_DEPTH {
+        return Err(format!(
+            "Invalid XML: elements nest deeper than {MAX_DEPTH} levels"
+        ));
+    }
+    roxmltree::Document::parse(xml).map_err(|e| format!("Invalid XML: {e}"))
+}
+
+/// Namespace prefixes of the qualified names in `xpath`, outside string
+/// literals.
+fn prefixes(xpath: &str) -> Vec<&str> {
+    let mut found = Vec::new();
+    let mut quote = None;
+    let mut name_start = None;
+    for (i, c) in xpath.char_indices() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => {
+                quote = Some(c);
+                name_start = None;
+            }
+            ':' => {
+                // `axis::name` doesn't qualify a name
+                let qualifies = xpath[i + 1..]
+                    .chars()
+                    .next()
+                    .is_some_and(|next| next == '*' || next == '_' || next.is_alphabetic());
+                if let Some(start) = name_start.take().filter(|_| qualifies) {
+                    found.push(&xpath[start..i]);
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                name_start.get_or_insert(i);
+            }
+            c if c.is_alphanumeric() || matches!(c, '-' | '.') => {}
+            _ => name_start = None,
+        }
+    }
+    found
+}
+
+fn element(node: roxmltree::Node<'_, '_>) -> XmlElement {
+    XmlElement {
+        name: node.tag_name().name().to_string(),
+        namespace: node.tag_name().namespace().map(str::to_string),
+        attributes: node
+            .attributes()
+            .map(|a| (a.name().to_string(), a.value().to_string()))
+            .collect(),
+        children: node
+            .children()
+            .filter_map(|child| {
+                if child.is_element() {
+                    Some(XmlContent::Element(element(child)))
+                } else {
+                    child
+                        .text()
+                        .filter(|text| child.is_text() && !text.trim().is_empty())
+                        .map(|text| XmlContent::Text {
+                            text: text.to_string(),
+                        })
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Parse `xml` into its root element's tree.
+///
+/// Comments, processing instructions, and whitespace-only text are left out.
+///
+/// # Errors
+///
+/// Returns `Invalid XML: ...` with the position of the problem if `xml` is
+/// not well-formed or nests deeper than [`MAX_DEPTH`].
+pub fn parse(xml: &str) -> Result<XmlElement, String> {
+    Ok(element(parse_document(xml)?.root_element()))
+}
+
+fn node(node: Node<'_>) -> XmlNode {
+    let kind = match node {
+        Node::Root(_) => "root",
+        Node::Element(_) => "element",
+        Node::Attribute(_) => "attribute",
+        Node::Text(_) => "text",
+        Node::Comment(_) => "comment",
+        Node::Namespace(_) => "namespace",
+        Node::ProcessingInstruction(_) => "processing-instruction",
+    };
+    XmlNode {
+        kind: kind.to_string(),
+        name: node.prefixed_name(),
+        value: node.string_value(),
+    }
+}
+
+/// Evaluate the `XPath` 1.0 expression `xpath` against `xml`.
+///
+/// # Errors
+///
+/// Returns `Invalid XML: ...` if `xml` is malformed (see [`parse`]),
+/// `Invalid XPath: ...` if `xpath` doesn't compile or uses a prefix missing
+/// from `namespaces`, or `XPath evaluation failed: ...` for errors such as an
+/// unknown function.
+pub fn query(
+    xml: &str,
+    xpath: &str,
+    namespaces: &BTreeMap<String, String>,
+) -> Result<XmlQueryResult, String> {
+    // Checked by the same parser as `xml_parse`, so both tools report
+    // malformed input identically
+    parse_document(xml)?;
+    let package = sxd_document::parser::parse(xml).map_err(|e| format!("Invalid XML: {e}"))?;
+    let document = package.as_document();
+
+    let compiled = sxd_xpath::Factory::new()
+        .build(xpath)
+        .map_err(|e| format!("Invalid XPath: {e}"))?
+        .ok_or_else(|| "Invalid XPath: expression is empty".to_string())?;
+    // The evaluator panics on an unbound prefix rather than returning an error
+    if let Some(prefix) = prefixes(xpath)
+        .into_iter()
+        .find(|prefix| !namespaces.contains_key(*prefix))
+    {
+        return Err(format!(
+            "Invalid XPath: prefix {prefix:?} is not bound; pass its URI in namespaces"
+        ));
+    }
+    let mut context = sxd_xpath::Context::new();
+    for (prefix, uri) in namespaces {
+        context.set_namespace(prefix, uri);
+    }
+    let value = compiled
+        .evaluate(&context, document.root())
+        .map_err(|e| format!("XPath evaluation failed: {e}"))?;
+
+    let (result_type, nodes, value) = match value {
+        sxd_xpath::Value::Nodeset(nodes) => (
+            "nodeset",
+            nodes.document_order().into_iter().map(node).collect(),
+            serde_json::Value::Null,
+        ),
+        sxd_xpath::Value::Boolean(b) => ("boolean", Vec::new(), serde_json::Value::Bool(b)),
+        sxd_xpath::Value::Number(n) => (
+            "number",
+            Vec::new(),
+            serde_json::Number::from_f64(n).map_or(serde_json::Value::Null, Into::into),
+        ),
+        sxd_xpath::Value::String(s) => ("string", Vec::new(), serde_json::Value::String(s)),
+    };
+    Ok(XmlQueryResult {
+        result_type: result_type.to_string(),
+        nodes,
+        value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CATALOG: &str = r#"<?xml version="1.0"?>
+<catalog xmlns:p="urn:price">
+  <!-- two books -->
+  <book id="1"><title>Dune</title><p:price>9.5</p:price></book>
+  <book id="2"><title>Emma &amp; Co</title><![CDATA[<raw>]]></book>
+</catalog>"#;
+
+    #[test]
+    fn test_parse_tree() {
+        let root = parse(CATALOG).unwrap();
+        assert_eq!(root.name, "catalog");
+        // The comment and blank text are dropped
+        assert_eq!(root.children.len(), 2);
+        let XmlContent::Element(first) = &root.children[0] else {
+            panic!("expected an element");
+        };
+        assert_eq!(first.attributes["id"], "1");
+        let XmlContent::Element(price) = &first.children[1] else {
+            panic!("expected an element");
+        };
+        assert_eq!(
+            (price.name.as_str(), price.namespace.as_deref()),
+            ("price", Some("urn:price"))
+        );
+        let XmlContent::Element(second) = &root.children[1] else {
+            panic!("expected an element");
+        };
+        assert_eq!(
+            second.children[1],
+            XmlContent::Text {
+                text: "<raw>".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_malformed_xml() {
+        for xml in ["<a><b></a>", "<a", "", "<a/><b/>", "<a x='1' x='2'/>"] {
+            let err = parse(xml).unwrap_err();
+            assert!(err.starts_with("Invalid XML:"), "{xml:?}: {err}");
+        }
+        let err = parse("<a><b></a>").unwrap_err();
+        assert!(err.contains("1:"), "{err}");
+
+        let deep = format!(
+            "{}{}",
+            "<a>".repeat(MAX_DEPTH + 1),
+            "</a>".repeat(MAX_DEPTH + 1)
+        );
+        assert!(parse(&deep).unwrap_err().contains("deeper than"));
+        let ok = format!("{}{}", "<a>".repeat(MAX_DEPTH), "</a>".repeat(MAX_DEPTH));
+        assert!(parse(&ok).is_ok());
+    }
+
+    #[test]
+    fn test_nesting_depth_skips_markup() {
+        assert_eq!(nesting_depth("<a><b/><c x='>' y=\"/>\"><d></d></c></a>"), 3);
+        assert_eq!(
+            nesting_depth("<?xml version='1.0'?><!-- <x><y> --><a><![CDATA[<z>]]></a>"),
+            1
+        );
+    }
+
+    #[test]
+    fn test_query_nodes() {
+        let none = BTreeMap::new();
+        let result = query(CATALOG, "//book[@id='2']/title", &none).unwrap();
+        assert_eq!(result.result_type, "nodeset");
+        assert_eq!(
+            result.nodes,
+            [XmlNode {
+                kind: "element".to_string(),
+                name: Some("title".to_string()),
+                value: "Emma & Co".to_string(),
+            }]
+        );
+
+        let result = query(CATALOG, "//book/@id", &none).unwrap();
+        let ids: Vec<_> = result.nodes.iter().map(|n| n.value.as_str()).collect();
+        assert_eq!(ids, ["1", "2"]);
+        assert_eq!(result.nodes[0].kind, "attribute");
+    }
+
+    #[test]
+    fn test_query_scalars_and_namespaces() {
+        let none = BTreeMap::new();
+        let count = query(CATALOG, "count(//book)", &none).unwrap();
+        assert_eq!(
+            (count.result_type.as_str(), count.value.clone()),
+            ("number", 2.0.into())
+        );
+        assert_eq!(
+            query(CATALOG, "boolean(//book)", &none).unwrap().value,
+            true
+        );
+        assert_eq!(
+            query(CATALOG, "string(//title)", &none).unwrap().value,
+            "Dune"
+        );
+        assert_eq!(
+            query(CATALOG, "number('x')", &none).unwrap().value,
+            serde_json::Value::Null
+        );
+
+        let namespaces = BTreeMap::from([("q".to_string(), "urn:price".to_string())]);
+        let result = query(CATALOG, "sum(//q:price)", &namespaces).unwrap();
+        assert_eq!(result.value, 9.5);
+        let err = query(CATALOG, "//q:price", &none).unwrap_err();
+        assert!(err.starts_with("Invalid XPath: prefix \"q\""), "{err}");
+        // Neither axes nor string literals are prefixes
+        let result = query(CATALOG, "child::catalog/book[title='a:b']", &none).unwrap();
+        assert!(result.nodes.is_empty());
+    }
+
+    #[test]
+    fn test_query_errors() {
+        let none = BTreeMap::new();
+        assert!(
+            query("<a>", "/a", &none)
+                .unwrap_err()
+                .starts_with("Invalid XML:")
+        );
+        assert!(
+            query(CATALOG, "//[", &none)
+                .unwrap_err()
+                .starts_with("Invalid XPath:")
+        );
+        assert!(
+            query(CATALOG, "", &none)
+                .unwrap_err()
+                .starts_with("Invalid XPath:")
+        );
+    }
+}
@@ -0,0 +1,453 @@
+//! Connection-level transport instrumentation.
+//!
+//! Wraps accepted TCP connections so the server notices how clients shut
+//! them down. Two conditions are detected and counted:
+//!
+//! - **Client write side closed**: the client sent `FIN` (e.g. `shutdown(SHUT_WR)`)
+//!   after sending request bytes but before the server answered. A complete
+//!   request is still answered ([`serve`] allows HTTP/1 half-close); the
+//!   server closes the connection once the response is written.
+//! - **Write failed after the client closed**: a server write failed with a
+//!   broken pipe or reset before the client had sent `FIN`, i.e. the client
+//!   closed or reset the whole connection while the server was answering.
+//!   TCP does not tell the server when a client shuts down only its read
+//!   side, so such a close cannot be told apart from a full one.
+//!
+//! Counters are exposed via [`ConnectionStats`] and `GET /debug/connections`.
+//!
+//! Every connection may speak HTTP/1.1 or HTTP/2; HTTP/2 is detected from
+//! the client's connection preface, so plain-text `h2c` with prior
+//! knowledge works without an upgrade. With `MCP_TLS_CERT` and
+//! `MCP_TLS_KEY`, [`TlsListener`] terminates TLS and offers `h2` and
+//! `http/1.1` via ALPN.
+
+use std::io;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use futures_util::future::BoxFuture;
+use futures_util::stream::FuturesUnordered;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto::Builder;
+use hyper_util::server::graceful::GracefulShutdown;
+use hyper_util::service::TowerToHyperService;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use serde::Serialize;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::server::TlsStream;
+
+/// Time a client has to finish the TLS handshake.
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Counters for connection lifecycle events.
+#[derive(Debug, Default)]
+pub struct ConnectionStats {
+    accepted: AtomicU64,
+    client_write_half_closed: AtomicU64,
+    write_failed_after_peer_closed: AtomicU64,
+}
+
+/// Point-in-time copy of [`ConnectionStats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ConnectionStatsSnapshot {
+    /// Connections accepted since startup.
+    pub accepted: u64,
+    /// Clients that closed their write side while a request was unanswered.
+    pub client_write_half_closed: u64,
+    /// Connections whose server write failed (broken pipe or reset) because
+    /// the client closed the connection without sending `FIN` first.
+    pub write_failed_after_peer_closed: u64,
+}
+
+impl ConnectionStats {
+    /// Create a new set of zeroed counters.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read the current counter values.
+    #[must_use]
+    pub fn snapshot(&self) -> ConnectionStatsSnapshot {
+        ConnectionStatsSnapshot {
+            accepted: self.accepted.load(Ordering::Relaxed),
+            client_write_half_closed: self.client_write_half_closed.load(Ordering::Relaxed),
+            write_failed_after_peer_closed: self
+                .write_failed_after_peer_closed
+                .load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// TCP listener that wraps every accepted connection in a [`TrackedStream`].
+#[derive(Debug)]
+pub struct TrackedListener {
+    inner: TcpListener,
+    stats: Arc<ConnectionStats>,
+}
+
+impl TrackedListener {
+    /// Wrap a bound listener, recording events into `stats`.
+    #[must_use]
+    pub const fn new(inner: TcpListener, stats: Arc<ConnectionStats>) -> Self {
+        Self { inner, stats }
+    }
+}
+
+impl axum::serve::Listener for TrackedListener {
+    type Io = TrackedStream<TcpStream>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        let (stream, addr) = axum::serve::Listener::accept(&mut self.inner).await;
+        self.stats.accepted.fetch_add(1, Ordering::Relaxed);
+        (
+            TrackedStream::new(stream, addr, Arc::clone(&self.stats)),
+            addr,
+        )
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        self.inner.local_addr()
+    }
+}
+
+/// Load the PEM certificate chain at `cert` and private key at `key` into a
+/// TLS acceptor offering `h2` and `http/1.1`.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be read, holds no certificate or key,
+/// or the key does not match the certificate.
+pub fn tls_acceptor(cert: &Path, key: &Path) -> anyhow::Result<TlsAcceptor> {
+    let certs = load_certs(cert)?;
+    let key = load_key(key)?;
+    let mut config = rustls::ServerConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()?
+    .with_no_client_auth()
+    .with_single_cert(certs, key)?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+/// Read the PEM certificate chain at `path`.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or holds no certificate.
+pub fn load_certs(path: &Path) -> anyhow::Result<Vec<CertificateDer<'static>>> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .map_err(|e| anyhow::anyhow!("{}: {e}", path.display()))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!("{}: {e}", path.display()))?;
+    anyhow::ensure!(
+        !certs.is_empty(),
+        "{}: no PEM certificate found",
+        path.display()
+    );
+    Ok(certs)
+}
+
+/// Read the PEM private key at `path`.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or holds no private key.
+pub fn load_key(path: &Path) -> anyhow::Result<PrivateKeyDer<'static>> {
+    PrivateKeyDer::from_pem_file(path).map_err(|e| anyhow::anyhow!("{}: {e}", path.display()))
+}
+
+/// A TLS handshake in progress, with the client's address.
+type Handshake = BoxFuture<'static, (io::Result<TlsStream<TrackedStream<TcpStream>>>, SocketAddr)>;
+
+/// Listener terminating TLS on the connections of a [`TrackedListener`].
+///
+/// Handshakes run concurrently, so a client that stalls in its handshake
+/// does not hold up others. Failed handshakes are logged and dropped.
+pub struct TlsListener {
+    inner: TrackedListener,
+    acceptor: TlsAcceptor,
+    handshakes: FuturesUnordered<Handshake>,
+}
+
+impl TlsListener {
+    /// Terminate TLS with `acceptor` on the connections of `inner`.
+    #[must_use]
+    pub fn new(inner: TrackedListener, acceptor: TlsAcceptor) -> Self {
+        Self {
+            inner,
+            acceptor,
+            handshakes: FuturesUnordered::new(),
+        }
+    }
+}
+
+impl std::fmt::Debug for TlsListener {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsListener")
+            .field("inner", &self.inner)
+            .field("handshakes", &self.handshakes.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl axum::serve::Listener for TlsListener {
+    type Io = TlsStream<TrackedStream<TcpStream>>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        loop {
+            tokio::select! {
+                (stream, addr) = axum::serve::Listener::accept(&mut self.inner) => {
+                    let handshake = tokio::time::timeout(
+                        TLS_HANDSHAKE_TIMEOUT,
+                        self.acceptor.accept(stream),
+                    );
+                    self.handshakes.push(Box::pin(async move {
+                        let result = handshake.await.unwrap_or_else(|_| {
+                            Err(io::Error::new(io::ErrorKind::TimedOut, "TLS handshake timed out"))
+                        });
+                        (result, addr)
+                    }));
+                }
+                Some((result, addr)) = self.handshakes.next(), if !self.handshakes.is_empty() => {
+                    match result {
+                        Ok(stream) => return (stream, addr),
+                        Err(e) => tracing::debug!(peer = %addr, error = %e, "TLS handshake failed"),
+                    }
+                }
+            }
+        }
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        axum::serve::Listener::local_addr(&self.inner)
+    }
+}
+
+/// Serve `app` on `listener` until `signal` completes, then stop accepting
+/// and wait for open connections to finish.
+///
+/// Like `axum::serve`, but HTTP/1 connections allow half-close: a client
+/// that shuts down its write side after a complete request still gets the
+/// response before the server closes the connection.
+pub async fn serve<L>(mut listener: L, app: axum::Router, signal: impl Future<Output = ()> + Send)
+where
+    L: axum::serve::Listener,
+{
+    let graceful = GracefulShutdown::new();
+    let mut signal = std::pin::pin!(signal);
+    loop {
+        let io = tokio::select! {
+            (io, _) = listener.accept() => io,
+            () = &mut signal => break,
+        };
+        let service = TowerToHyperService::new(app.clone());
+        let watcher = graceful.watcher();
+        tokio::spawn(async move {
+            let mut builder = Builder::new(TokioExecutor::new());
+            builder.http1().half_close(true);
+            // CONNECT protocol needed for HTTP/2 websockets, as in axum::serve
+            builder.http2().enable_connect_protocol();
+            let conn = builder.serve_connection_with_upgrades(TokioIo::new(io), service);
+            if let Err(e) = watcher.watch(conn).await {
+                tracing::trace!(error = %e, "Failed to serve connection");
+            }
+        });
+    }
+    drop(listener);
+    graceful.shutdown().await;
+}
+
+/// A connection that reports half-close conditions to [`ConnectionStats`].
+#[derive(Debug)]
+pub struct TrackedStream<S> {
+    inner: S,
+    peer: SocketAddr,
+    stats: Arc<ConnectionStats>,
+    /// Bytes were read since the server last wrote anything.
+    unanswered: bool,
+    read_eof: bool,
+    write_failed: bool,
+}
+
+impl<S> TrackedStream<S> {
+    /// Wrap a stream from `peer`, recording events into `stats`.
+    pub const fn new(inner: S, peer: SocketAddr, stats: Arc<ConnectionStats>) -> Self {
+        Self {
+            inner,
+            peer,
+            stats,
+            unanswered: false,
+            read_eof: false,
+            write_failed: false,
+        }
+    }
+
+    fn on_read(&mut self, bytes: usize) {
+        if bytes > 0 {
+            self.unanswered = true;
+            return;
+        }
+        if self.read_eof {
+            return;
+        }
+        self.read_eof = true;
+        if self.unanswered {
+            self.stats
+                .client_write_half_closed
+                .fetch_add(1, Ordering::Relaxed);
+            tracing::warn!(peer = %self.peer, "Client closed its write side before the request was answered");
+        } else {
+            tracing::debug!(peer = %self.peer, "Client closed its write side");
+        }
+    }
+
+    fn on_write_error(&mut self, error: &io::Error) {
+        let closed = matches!(
+            error.kind(),
+            io::ErrorKind::BrokenPipe | io::ErrorKind::ConnectionReset
+        );
+        if !closed || self.write_failed || self.read_eof {
+            return;
+        }
+        self.write_failed = true;
+        self.stats
+            .write_failed_after_peer_closed
+            .fetch_add(1, Ordering::Relaxed);
+        tracing::warn!(peer = %self.peer, error = %error, "Write failed after the client closed the connection");
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for TrackedStream<S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let before = buf.filled().len();
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if matches!(result, Poll::Ready(Ok(()))) {
+            let read = buf.filled().len() - before;
+            self.on_read(read);
+        }
+        result
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for TrackedStream<S> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let result = Pin::new(&mut self.inner).poll_write(cx, buf);
+        match &result {
+            Poll::Ready(Ok(n)) if *n > 0 => self.unanswered = false,
+            Poll::Ready(Err(e)) => self.on_write_error(e),
+            _ => {}
+        }
+        result
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let result = Pin::new(&mut self.inner).poll_flush(cx);
+        if let Poll::Ready(Err(e)) = &result {
+            self.on_write_error(e);
+        }
+        result
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        let result = Pin::new(&mut self.inner).poll_write_vectored(cx, bufs);
+        match &result {
+            Poll::Ready(Ok(n)) if *n > 0 => self.unanswered = false,
+            Poll::Ready(Err(e)) => self.on_write_error(e),
+            _ => {}
+        }
+        result
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn tracked(
+        inner: tokio::io::DuplexStream,
+    ) -> (TrackedStream<tokio::io::DuplexStream>, Arc<ConnectionStats>) {
+        let stats = Arc::new(ConnectionStats::new());
+        let peer = "127.0.0.1:1234".parse().unwrap();
+        (TrackedStream::new(inner, peer, Arc::clone(&stats)), stats)
+    }
+
+    #[tokio::test]
+    async fn test_write_half_close_with_unanswered_request() {
+        let (server_side, mut client) = tokio::io::duplex(1024);
+        let (mut stream, stats) = tracked(server_side);
+
+        client.write_all(b"GET / HTTP/1.1\r\n").await.unwrap();
+        client.shutdown().await.unwrap();
+
+        let mut buf = Vec::new();
+        stream.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(stats.snapshot().client_write_half_closed, 1);
+
+        // The read side is still open: the server can answer
+        stream.write_all(b"HTTP/1.1 200 OK\r\n\r\n").await.unwrap();
+        let mut response = [0u8; 8];
+        client.read_exact(&mut response).await.unwrap();
+        assert_eq!(&response, b"HTTP/1.1");
+    }
+
+    #[tokio::test]
+    async fn test_clean_close_after_response_not_counted() {
+        let (server_side, mut client) = tokio::io::duplex(1024);
+        let (mut stream, stats) = tracked(server_side);
+
+        client.write_all(b"ping").await.unwrap();
+        let mut buf = [0u8; 4];
+        stream.read_exact(&mut buf).await.unwrap();
+        stream.write_all(b"pong").await.unwrap();
+        client.shutdown().await.unwrap();
+
+        let mut rest = Vec::new();
+        stream.read_to_end(&mut rest).await.unwrap();
+        assert_eq!(stats.snapshot().client_write_half_closed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_write_failure_after_client_close_counted() {
+        let (server_side, client) = tokio::io::duplex(16);
+        let (mut stream, stats) = tracked(server_side);
+
+        drop(client);
+        assert!(stream.write_all(b"data").await.is_err());
+        assert!(stream.write_all(b"more").await.is_err());
+        assert_eq!(stats.snapshot().write_failed_after_peer_closed, 1);
+    }
+}
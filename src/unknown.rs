@@ -0,0 +1,155 @@
+//! Responses to unknown tools and unknown notifications.
+//!
+//! Servers disagree on both. A `tools/call` for a tool that does not exist
+//! is a `-32602` invalid params error to some and a failed tool call
+//! (`isError` result) to others; see [`UnknownToolPolicy`]
+//! (`MCP_UNKNOWN_TOOL`). A notification with an unknown method is ignored by
+//! most servers, as JSON-RPC forbids answering notifications, while others
+//! reject the HTTP request; see [`UnknownNotificationPolicy`]
+//! (`MCP_UNKNOWN_NOTIFICATION`). Unknown request methods always get `-32601`.
+
+use axum::body::{Body, Bytes};
+use axum::extract::Request;
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Json, Response};
+use rmcp::model::{CallToolResult, Content, ErrorCode};
+use serde_json::Value;
+
+/// How a `tools/call` for an unknown tool is answered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnknownToolPolicy {
+    /// A `-32602` invalid params error.
+    #[default]
+    Error,
+    /// An `isError` result naming the tool.
+    Result,
+}
+
+impl UnknownToolPolicy {
+    /// Parse a policy name (`error` or `result`, case-insensitive).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "error" => Some(Self::Error),
+            "result" => Some(Self::Result),
+            _ => None,
+        }
+    }
+}
+
+/// The `isError` result for a call to the unknown tool `name`.
+#[must_use]
+pub fn unknown_tool_result(name: &str) -> CallToolResult {
+    CallToolResult::error(vec![Content::text(format!("Unknown tool: {name}"))])
+}
+
+/// How a notification with an unknown method is answered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnknownNotificationPolicy {
+    /// Accept it with `202 Accepted` and do nothing.
+    #[default]
+    Ignore,
+    /// Reject it with `400 Bad Request` and a `-32601` error without an id.
+    Error,
+}
+
+impl UnknownNotificationPolicy {
+    /// Parse a policy name (`ignore` or `error`, case-insensitive).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "ignore" => Some(Self::Ignore),
+            "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the server handles the client notification `method`.
+#[must_use]
+pub fn is_known_notification(method: &str) -> bool {
+    method.starts_with("notifications/") && crate::conformance::FEATURES.contains(&method)
+}
+
+/// Middleware rejecting notifications with unknown methods
+/// ([`UnknownNotificationPolicy::Error`]).
+pub async fn unknown_notification_middleware(request: Request, next: Next) -> Response {
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+    let (parts, body) = request.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    if let Some(method) = unknown_notification(&bytes) {
+        tracing::info!(%method, "Rejecting unknown notification");
+        let error = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": null,
+            "error": {
+                "code": ErrorCode::METHOD_NOT_FOUND.0,
+                "message": format!("Method not found: {method}"),
+                "data": { "method": method },
+            },
+        });
+        return (StatusCode::BAD_REQUEST, Json(error)).into_response();
+    }
+    next.run(Request::from_parts(parts, Body::from(bytes)))
+        .await
+}
+
+/// The method of a body that is a single notification the server does not
+/// handle.
+fn unknown_notification(body: &Bytes) -> Option<String> {
+    let message: Value = serde_json::from_slice(body).ok()?;
+    if message.get("id").is_some() {
+        return None;
+    }
+    let method = message.get("method")?.as_str()?;
+    (!is_known_notification(method)).then(|| method.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            UnknownToolPolicy::parse("RESULT"),
+            Some(UnknownToolPolicy::Result)
+        );
+        assert_eq!(UnknownToolPolicy::parse("ignore"), None);
+        assert_eq!(
+            UnknownNotificationPolicy::parse(" error "),
+            Some(UnknownNotificationPolicy::Error)
+        );
+        assert_eq!(UnknownNotificationPolicy::parse("result"), None);
+    }
+
+    #[test]
+    fn test_unknown_notification() {
+        let body = |v: Value| Bytes::from(v.to_string());
+        assert_eq!(
+            unknown_notification(&body(serde_json::json!({
+                "jsonrpc": "2.0", "method": "notifications/bogus"
+            }))),
+            Some("notifications/bogus".to_string())
+        );
+        assert_eq!(
+            unknown_notification(&body(serde_json::json!({
+                "jsonrpc": "2.0", "method": "notifications/initialized"
+            }))),
+            None
+        );
+        // Requests are answered by the server with -32601
+        assert_eq!(
+            unknown_notification(&body(serde_json::json!({
+                "jsonrpc": "2.0", "id": 1, "method": "bogus/method"
+            }))),
+            None
+        );
+    }
+}
@@ -0,0 +1,591 @@
+//! Tool argument validation beyond what serde enforces, and name matching.
+//!
+//! serde silently ignores fields it does not know about. These checks let the
+//! server adopt stricter validation postures so clients can be tested against
+//! them. Name matching is exact by default; [`resolve_lenient`] implements the
+//! opt-in lenient mode that tolerates case and surrounding whitespace.
+//! [`reject_oversized_result`] enforces the optional response size cap.
+//!
+//! When serde rejects a tool's arguments, its message names neither the
+//! field nor the type it wanted. With a [`SchemaErrorDetail`] other than
+//! `opaque` (`MCP_SCHEMA_ERRORS`), [`explain_invalid_params`] checks the
+//! arguments against the tool's input schema and rewrites the `-32602` error
+//! to point at the failing value with a JSON pointer.
+
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, ErrorCode, JsonObject, Tool},
+};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Find the argument names that are not declared in a tool's input schema.
+///
+/// Returns an empty list if the schema allows additional properties or does
+/// not declare a `properties` object. Names are returned sorted.
+#[must_use]
+pub fn unknown_arguments(schema: &JsonObject, arguments: &JsonObject) -> Vec<String> {
+    let allows_additional = schema
+        .get("additionalProperties")
+        .is_some_and(|v| !matches!(v, serde_json::Value::Bool(false)));
+    let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) else {
+        return Vec::new();
+    };
+    if allows_additional {
+        return Vec::new();
+    }
+
+    let mut unknown: Vec<String> = arguments
+        .keys()
+        .filter(|key| !properties.contains_key(*key))
+        .cloned()
+        .collect();
+    unknown.sort();
+    unknown
+}
+
+/// Reject a tool call whose arguments include fields not declared in the
+/// tool's input schema.
+///
+/// # Errors
+///
+/// Returns an `invalid_params` (-32602) error listing the unknown fields and
+/// the fields the tool accepts.
+pub fn reject_unknown_arguments(
+    tool: &Tool,
+    arguments: Option<&JsonObject>,
+) -> Result<(), McpError> {
+    let Some(arguments) = arguments else {
+        return Ok(());
+    };
+    let unknown = unknown_arguments(&tool.input_schema, arguments);
+    if unknown.is_empty() {
+        return Ok(());
+    }
+
+    let mut allowed: Vec<&String> = tool
+        .input_schema
+        .get("properties")
+        .and_then(|p| p.as_object())
+        .map(|p| p.keys().collect())
+        .unwrap_or_default();
+    allowed.sort();
+
+    Err(McpError::invalid_params(
+        format!(
+            "Unknown argument(s) for tool '{}': {}",
+            tool.name,
+            unknown.join(", ")
+        ),
+        Some(serde_json::json!({
+            "tool": tool.name,
+            "unknown": unknown,
+            "allowed": allowed,
+        })),
+    ))
+}
+
+/// Resolve a tool name or resource URI leniently against the known names.
+///
+/// An exact match always wins. Otherwise the request is trimmed and compared
+/// exactly, then case-insensitively. When several known names match
+/// case-insensitively, the lexicographically smallest is chosen so the result
+/// is deterministic. Returns `None` if nothing matches.
+pub fn resolve_lenient<'a>(
+    requested: &str,
+    known: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let known: Vec<&str> = known.into_iter().collect();
+    if let Some(exact) = known.iter().find(|k| **k == requested) {
+        return Some(exact);
+    }
+    let trimmed = requested.trim();
+    if let Some(exact) = known.iter().find(|k| **k == trimmed) {
+        return Some(exact);
+    }
+    known
+        .into_iter()
+        .filter(|k| k.trim().eq_ignore_ascii_case(trimmed))
+        .min()
+}
+
+/// How much an invalid params error says about arguments that fail the
+/// tool's input schema.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SchemaErrorDetail {
+    /// The deserializer's message, unchanged.
+    #[default]
+    Opaque,
+    /// The first failing value: its `pointer`, `expected`, and `actual` type.
+    Summary,
+    /// Every failing value, under `errors`, plus the deserializer's message.
+    Detailed,
+}
+
+impl SchemaErrorDetail {
+    /// Parse a verbosity name (`opaque`, `summary`, or `detailed`,
+    /// case-insensitive).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "opaque" => Some(Self::Opaque),
+            "summary" => Some(Self::Summary),
+            "detailed" => Some(Self::Detailed),
+            _ => None,
+        }
+    }
+}
+
+/// A value in a tool's arguments that fails its input schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchemaViolation {
+    /// JSON pointer (RFC 6901) to the value within the arguments.
+    pub pointer: String,
+    /// What the schema expects, e.g. `number`, `string or null`, `>= 0`.
+    pub expected: String,
+    /// The JSON type found, or `missing`.
+    pub actual: String,
+}
+
+/// Check `arguments` against a tool's input schema.
+///
+/// Understands the subset of JSON Schema that schemars generates: `type`,
+/// `properties`, `required`, `items`, `enum`, `const`, `minimum`,
+/// `maximum`, `anyOf`/`oneOf`, and local `$ref`s. For each object, missing
+/// required properties are listed before problems with the values present.
+#[must_use]
+pub fn schema_violations(schema: &JsonObject, arguments: &Value) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+    check_value(
+        &Value::Object(schema.clone()),
+        schema,
+        arguments,
+        "",
+        &mut violations,
+    );
+    violations
+}
+
+fn check_value(
+    schema: &Value,
+    root: &JsonObject,
+    value: &Value,
+    pointer: &str,
+    out: &mut Vec<SchemaViolation>,
+) {
+    let schema = resolve_ref(schema, root);
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+    let violation = |expected: String| SchemaViolation {
+        pointer: pointer.to_string(),
+        expected,
+        actual: json_type(value).to_string(),
+    };
+
+    let alternatives = schema
+        .get("anyOf")
+        .or_else(|| schema.get("oneOf"))
+        .and_then(Value::as_array);
+    if let Some(alternatives) = alternatives {
+        let matches = alternatives.iter().any(|alt| {
+            let mut nested = Vec::new();
+            check_value(alt, root, value, pointer, &mut nested);
+            nested.is_empty()
+        });
+        if !matches {
+            let expected: Vec<String> = alternatives
+                .iter()
+                .map(|alt| describe(resolve_ref(alt, root)))
+                .collect();
+            out.push(violation(expected.join(" or ")));
+        }
+        return;
+    }
+
+    if let Some(types) = schema.get("type") {
+        let types: Vec<&str> = match types {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|t| has_type(value, t)) {
+            out.push(violation(types.join(" or ")));
+            return;
+        }
+    }
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+        && !allowed.contains(value)
+    {
+        out.push(violation(describe(&Value::Object(schema.clone()))));
+        return;
+    }
+    if let Some(constant) = schema.get("const")
+        && constant != value
+    {
+        out.push(violation(constant.to_string()));
+        return;
+    }
+    if let Some(n) = value.as_f64() {
+        if let Some(min) = schema.get("minimum").and_then(Value::as_f64)
+            && n < min
+        {
+            out.push(violation(format!(">= {min}")));
+        }
+        if let Some(max) = schema.get("maximum").and_then(Value::as_f64)
+            && n > max
+        {
+            out.push(violation(format!("<= {max}")));
+        }
+    }
+
+    match value {
+        Value::Object(map) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for name in schema
+                .get("required")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+            {
+                if !map.contains_key(name) {
+                    let expected = properties
+                        .and_then(|p| p.get(name))
+                        .map_or_else(|| "a value".to_string(), |s| describe(resolve_ref(s, root)));
+                    out.push(SchemaViolation {
+                        pointer: format!("{pointer}/{}", escape_pointer(name)),
+                        expected,
+                        actual: "missing".to_string(),
+                    });
+                }
+            }
+            for (name, property) in properties.into_iter().flatten() {
+                if let Some(value) = map.get(name) {
+                    let pointer = format!("{pointer}/{}", escape_pointer(name));
+                    check_value(property, root, value, &pointer, out);
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    check_value(item_schema, root, item, &format!("{pointer}/{i}"), out);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Follow a local `$ref` (`#/$defs/...` or `#/definitions/...`).
+fn resolve_ref<'a>(schema: &'a Value, root: &'a JsonObject) -> &'a Value {
+    let Some(reference) = schema.get("$ref").and_then(Value::as_str) else {
+        return schema;
+    };
+    let Some(path) = reference.strip_prefix("#/") else {
+        return schema;
+    };
+    let mut parts = path.split('/');
+    let Some(mut target) = parts.next().and_then(|first| root.get(first)) else {
+        return schema;
+    };
+    for part in parts {
+        match target.get(part) {
+            Some(next) => target = next,
+            None => return schema,
+        }
+    }
+    target
+}
+
+/// A short description of what a schema accepts.
+fn describe(schema: &Value) -> String {
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+        return format!("one of {}", allowed.join(", "));
+    }
+    if let Some(constant) = schema.get("const") {
+        return constant.to_string();
+    }
+    match schema.get("type") {
+        Some(Value::String(t)) => t.clone(),
+        Some(Value::Array(ts)) => ts
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" or "),
+        _ => "a value".to_string(),
+    }
+}
+
+/// Whether `value` is of JSON Schema type `name`.
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        "number" => value.is_number(),
+        other => json_type(value) == other,
+    }
+}
+
+/// The JSON Schema type name of `value`.
+const fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Escape a property name for use in a JSON pointer.
+fn escape_pointer(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+/// Rewrite the invalid params error of a call to `tool` to say which
+/// arguments fail its input schema, at the given level of `detail`.
+///
+/// Other errors, and invalid params errors whose arguments pass the schema
+/// (the tool rejected them itself), are returned unchanged.
+#[must_use]
+pub fn explain_invalid_params(
+    error: McpError,
+    tool: &Tool,
+    arguments: Option<&JsonObject>,
+    detail: SchemaErrorDetail,
+) -> McpError {
+    if detail == SchemaErrorDetail::Opaque || error.code != ErrorCode::INVALID_PARAMS {
+        return error;
+    }
+    let arguments = Value::Object(arguments.cloned().unwrap_or_default());
+    let violations = schema_violations(&tool.input_schema, &arguments);
+    let Some(first) = violations.first() else {
+        return error;
+    };
+
+    let message = format!(
+        "Invalid arguments for tool '{}': {} expected {}, got {}",
+        tool.name,
+        if first.pointer.is_empty() {
+            "arguments"
+        } else {
+            &first.pointer
+        },
+        first.expected,
+        first.actual
+    );
+    let mut data = serde_json::json!({
+        "tool": tool.name,
+        "pointer": first.pointer,
+        "expected": first.expected,
+        "actual": first.actual,
+    });
+    if detail == SchemaErrorDetail::Detailed {
+        data["errors"] = serde_json::json!(violations);
+        data["cause"] = Value::String(error.message.into_owned());
+    }
+    McpError::invalid_params(message, Some(data))
+}
+
+/// The error for a tool result of `size` bytes over the `limit`.
+#[must_use]
+pub fn response_too_large(size: usize, limit: usize) -> McpError {
+    McpError::internal_error(
+        format!("Response too large: {size} bytes exceeds the limit of {limit} bytes"),
+        Some(serde_json::json!({ "size": size, "limit": limit })),
+    )
+}
+
+/// Reject a tool result whose JSON serialization is larger than `limit` bytes.
+///
+/// # Errors
+///
+/// Returns an `internal_error` (-32603) with the result's `size` and the
+/// `limit` as data.
+pub fn reject_oversized_result(result: &CallToolResult, limit: usize) -> Result<(), McpError> {
+    let mut counter = ByteCounter(0);
+    // Writing to a counter cannot fail, and a result always serializes
+    let _ = serde_json::to_writer(&mut counter, result);
+    if counter.0 > limit {
+        return Err(response_too_large(counter.0, limit));
+    }
+    Ok(())
+}
+
+/// Writer that only counts the bytes written to it.
+struct ByteCounter(usize);
+
+impl std::io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(value: serde_json::Value) -> JsonObject {
+        match value {
+            serde_json::Value::Object(map) => map,
+            _ => panic!("expected a JSON object"),
+        }
+    }
+
+    fn echo_schema() -> JsonObject {
+        object(serde_json::json!({
+            "type": "object",
+            "properties": { "text": { "type": "string" } },
+            "required": ["text"]
+        }))
+    }
+
+    #[test]
+    fn test_unknown_arguments_detected() {
+        let args = object(serde_json::json!({ "text": "hi", "txet": "typo", "extra": 1 }));
+        assert_eq!(
+            unknown_arguments(&echo_schema(), &args),
+            vec!["extra", "txet"]
+        );
+    }
+
+    #[test]
+    fn test_known_arguments_pass() {
+        let args = object(serde_json::json!({ "text": "hi" }));
+        assert!(unknown_arguments(&echo_schema(), &args).is_empty());
+    }
+
+    #[test]
+    fn test_additional_properties_allowed() {
+        let mut schema = echo_schema();
+        schema.insert("additionalProperties".to_string(), serde_json::json!(true));
+        let args = object(serde_json::json!({ "text": "hi", "extra": 1 }));
+        assert!(unknown_arguments(&schema, &args).is_empty());
+    }
+
+    #[test]
+    fn test_reject_unknown_arguments_error() {
+        let tool = Tool::new("echo", "Echo", std::sync::Arc::new(echo_schema()));
+        let args = object(serde_json::json!({ "text": "hi", "extra": 1 }));
+        let err = reject_unknown_arguments(&tool, Some(&args)).unwrap_err();
+        assert_eq!(err.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+        assert!(err.message.contains("extra"));
+        assert_eq!(err.data.unwrap()["allowed"][0], "text");
+    }
+
+    #[test]
+    fn test_reject_unknown_arguments_no_arguments() {
+        let tool = Tool::new("echo", "Echo", std::sync::Arc::new(echo_schema()));
+        assert!(reject_unknown_arguments(&tool, None).is_ok());
+    }
+
+    #[test]
+    fn test_resolve_lenient_prefers_exact() {
+        let known = ["readme", "README", "readme "];
+        assert_eq!(resolve_lenient("README", known), Some("README"));
+        assert_eq!(resolve_lenient("readme ", known), Some("readme "));
+    }
+
+    #[test]
+    fn test_resolve_lenient_trims_and_folds_case() {
+        let known = ["echo", "add"];
+        assert_eq!(resolve_lenient(" echo\t", known), Some("echo"));
+        assert_eq!(resolve_lenient("ECHO", known), Some("echo"));
+        assert_eq!(resolve_lenient("Add ", known), Some("add"));
+        assert_eq!(resolve_lenient("missing", known), None);
+    }
+
+    #[test]
+    fn test_reject_oversized_result() {
+        let result = CallToolResult::success(vec![rmcp::model::Content::text("x".repeat(100))]);
+        let size = serde_json::to_vec(&result).unwrap().len();
+        assert!(reject_oversized_result(&result, size).is_ok());
+        let err = reject_oversized_result(&result, size - 1).unwrap_err();
+        assert_eq!(err.code, rmcp::model::ErrorCode::INTERNAL_ERROR);
+        assert_eq!(
+            err.data.unwrap(),
+            serde_json::json!({ "size": size, "limit": size - 1 })
+        );
+    }
+
+    #[test]
+    fn test_schema_violations() {
+        let schema = object(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "a": { "type": "number" },
+                "n": { "type": "integer", "minimum": 0 },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "mode": { "$ref": "#/$defs/Mode" },
+                "opt": { "type": ["string", "null"] }
+            },
+            "required": ["a", "b"],
+            "$defs": { "Mode": { "type": "string", "enum": ["fast", "slow"] } }
+        }));
+        let args = serde_json::json!({
+            "a": "one",
+            "n": -1,
+            "tags": ["ok", 2],
+            "mode": "medium",
+            "opt": null
+        });
+        let found: Vec<(String, String, String)> = schema_violations(&schema, &args)
+            .into_iter()
+            .map(|v| (v.pointer, v.expected, v.actual))
+            .collect();
+        let expected = [
+            ("/b", "a value", "missing"),
+            ("/a", "number", "string"),
+            ("/mode", "one of \"fast\", \"slow\"", "string"),
+            ("/n", ">= 0", "number"),
+            ("/tags/1", "string", "number"),
+        ];
+        for (pointer, wanted, actual) in expected {
+            assert!(
+                found.contains(&(pointer.into(), wanted.into(), actual.into())),
+                "{pointer}: {found:?}"
+            );
+        }
+        assert_eq!(found.len(), 5);
+    }
+
+    #[test]
+    fn test_explain_invalid_params() {
+        let tool = Tool::new("echo", "Echo", std::sync::Arc::new(echo_schema()));
+        let args = object(serde_json::json!({ "text": 5 }));
+        let opaque = || McpError::invalid_params("failed to deserialize parameters", None);
+
+        let err = explain_invalid_params(opaque(), &tool, Some(&args), SchemaErrorDetail::Opaque);
+        assert_eq!(err.message, "failed to deserialize parameters");
+
+        let err = explain_invalid_params(opaque(), &tool, Some(&args), SchemaErrorDetail::Summary);
+        assert_eq!(
+            err.message,
+            "Invalid arguments for tool 'echo': /text expected string, got number"
+        );
+        let data = err.data.unwrap();
+        assert_eq!(data["pointer"], "/text");
+        assert!(data.get("errors").is_none());
+
+        let err = explain_invalid_params(opaque(), &tool, None, SchemaErrorDetail::Detailed);
+        let data = err.data.unwrap();
+        assert_eq!(data["errors"][0]["actual"], "missing");
+        assert_eq!(data["cause"], "failed to deserialize parameters");
+    }
+
+    #[test]
+    fn test_resolve_lenient_ambiguous_is_deterministic() {
+        let known = ["ReadMe", "readme", "README"];
+        assert_eq!(resolve_lenient("rEaDmE", known), Some("README"));
+    }
+}
@@ -0,0 +1,108 @@
+//! Array tool tests: heterogeneous JSON arrays over MCP, stable sorting, and
+//! type-mismatch error paths.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::{Value, json};
+
+async fn call(client: &McpClient, name: &str, arguments: Value) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": name, "arguments": arguments }),
+        )
+        .await["result"]
+        .clone()
+}
+
+#[tokio::test]
+async fn test_sort_and_dedup_arrays() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let result = call(
+        &client,
+        "sort_array",
+        json!({ "values": [10, 2.5, -3, 7], "order": "desc" }),
+    )
+    .await;
+    assert_eq!(
+        result["structuredContent"]["values"],
+        json!([10, 7, 2.5, -3])
+    );
+
+    let result = call(
+        &client,
+        "sort_array",
+        json!({
+            "values": [
+                { "id": "x", "rank": 2 },
+                { "id": "y", "rank": 1 },
+                { "id": "z", "rank": 2 },
+            ],
+            "key": "rank",
+        }),
+    )
+    .await;
+    let ids: Vec<&Value> = result["structuredContent"]["values"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| &v["id"])
+        .collect();
+    assert_eq!(ids, [&json!("y"), &json!("x"), &json!("z")]);
+
+    let result = call(
+        &client,
+        "dedup_array",
+        json!({ "values": ["a", 1, "a", [1], { "k": null }, [1], { "k": null }, "1"] }),
+    )
+    .await;
+    assert_eq!(
+        result["structuredContent"],
+        json!({ "values": ["a", 1, [1], { "k": null }, "1"], "removed": 3 })
+    );
+}
+
+#[tokio::test]
+async fn test_type_mismatch_is_a_tool_error() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let cases = [
+        (
+            json!({ "values": [1, 2, "3"] }),
+            "Type mismatch: values[0] is a number but values[2] is a string",
+        ),
+        (
+            json!({ "values": [true, false] }),
+            "values[0] is a boolean; only numbers and strings can be sorted",
+        ),
+        (
+            json!({ "values": [{ "n": "b" }, { "n": 1 }], "key": "n" }),
+            "Type mismatch: values[0].n is a string but values[1].n is a number",
+        ),
+    ];
+    for (arguments, expected) in cases {
+        let result = call(&client, "sort_array", arguments).await;
+        assert_eq!(result["isError"], true);
+        assert_eq!(result["content"][0]["text"], expected);
+    }
+
+    let tools = client.request("tools/list", json!({})).await;
+    let tool = tools["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["name"] == "sort_array")
+        .unwrap()
+        .clone();
+    let values = &tool["inputSchema"]["properties"]["values"];
+    assert_eq!(values["type"], "array");
+    assert!(values["items"].is_null() || values["items"] == json!(true));
+}
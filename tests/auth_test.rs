@@ -1,6 +1,9 @@
 //! Integration tests for authentication middleware.
 //!
-//! These tests verify API key validation and origin checking.
+//! These tests verify API key validation, origin checking, the OAuth mode
+//! bearer challenge, and the principal reported by `whoami`.
+
+mod common;
 
 use axum::{
     Router,
@@ -9,7 +12,11 @@ use axum::{
     middleware,
     routing::get,
 };
-use mcp_test_server::{Config, auth::auth_middleware};
+use common::{McpClient, TestServer};
+use mcp_test_server::{
+    Config,
+    auth::{ApiKeyId, AuthFailureMode, auth_middleware},
+};
 use tower::ServiceExt; // for `oneshot`
 
 async fn protected_handler() -> &'static str {
@@ -74,13 +81,17 @@ async fn test_protected_endpoint_missing_auth_header() {
         .unwrap();
 
     let response = app.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        response.headers()["www-authenticate"],
+        r#"Bearer realm="mcp-test-server""#
+    );
 
     let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
         .await
         .unwrap();
     let body = String::from_utf8(body_bytes.to_vec()).unwrap();
-    assert!(body.contains("forbidden"));
+    assert!(body.contains("unauthorized"));
     assert!(body.contains("Missing Authorization header"));
 }
 
@@ -99,7 +110,8 @@ async fn test_protected_endpoint_invalid_auth_format() {
         .unwrap();
 
     let response = app.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert!(response.headers().contains_key("www-authenticate"));
 
     let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
         .await
@@ -123,7 +135,9 @@ async fn test_protected_endpoint_wrong_api_key() {
         .unwrap();
 
     let response = app.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let challenge = response.headers()["www-authenticate"].to_str().unwrap();
+    assert!(challenge.contains(r#"error="invalid_token""#));
 
     let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
         .await
@@ -132,6 +146,45 @@ async fn test_protected_endpoint_wrong_api_key() {
     assert!(body.contains("Invalid API key"));
 }
 
+#[tokio::test]
+async fn test_forbidden_failure_mode() {
+    let config = Config {
+        api_key: Some("test-secret-key".to_string()),
+        auth_failure_mode: AuthFailureMode::Forbidden,
+        ..Default::default()
+    };
+    let app = create_app(config);
+
+    let request = Request::builder()
+        .uri("/protected")
+        .header("Authorization", "Bearer wrong-key")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert!(!response.headers().contains_key("www-authenticate"));
+}
+
+#[tokio::test]
+async fn test_bare_401_failure_mode() {
+    let config = Config {
+        api_key: Some("test-secret-key".to_string()),
+        auth_failure_mode: AuthFailureMode::BareUnauthorized,
+        ..Default::default()
+    };
+    let app = create_app(config);
+
+    let request = Request::builder()
+        .uri("/protected")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert!(!response.headers().contains_key("www-authenticate"));
+}
+
 #[tokio::test]
 async fn test_protected_endpoint_correct_api_key() {
     let config = Config {
@@ -274,3 +327,136 @@ async fn test_combined_auth_correct_but_bad_origin() {
     let body = String::from_utf8(body_bytes.to_vec()).unwrap();
     assert!(body.contains("Origin not allowed"));
 }
+
+/// Register a client with the mock OAuth server and return an access token
+/// obtained with the client credentials grant.
+async fn client_credentials_token(server: &TestServer) -> String {
+    let client = common::test_client();
+    let registration: serde_json::Value = client
+        .post(format!("{}/oauth/register", server.base_url()))
+        .json(&serde_json::json!({ "token_endpoint_auth_method": "client_secret_post" }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let token: serde_json::Value = client
+        .post(format!("{}/oauth/token", server.base_url()))
+        .form(&[
+            ("grant_type", "client_credentials"),
+            ("client_id", registration["client_id"].as_str().unwrap()),
+            (
+                "client_secret",
+                registration["client_secret"].as_str().unwrap(),
+            ),
+        ])
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    token["access_token"].as_str().unwrap().to_string()
+}
+
+/// Parse the JSON text of a `whoami` result.
+fn whoami_report(response: &serde_json::Value) -> serde_json::Value {
+    let text = response["result"]["content"][0]["text"].as_str().unwrap();
+    serde_json::from_str(text).unwrap()
+}
+
+#[tokio::test]
+async fn test_oauth_mode_challenge_then_client_credentials() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_config(Config::builder().oauth(true).build()).await;
+    let client = common::test_client();
+    let initialize = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-06-18",
+            "capabilities": {},
+            "clientInfo": { "name": "oauth-test", "version": "1.0" }
+        }
+    });
+
+    let response = client
+        .post(server.mcp_url())
+        .json(&initialize)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+    let challenge = response.headers()["www-authenticate"].to_str().unwrap();
+    let expected = format!(
+        "resource_metadata=\"{}/.well-known/oauth-protected-resource\"",
+        server.base_url()
+    );
+    assert!(challenge.starts_with("Bearer "), "{challenge}");
+    assert!(challenge.contains(&expected), "{challenge}");
+
+    let token = client_credentials_token(&server).await;
+
+    let response = client
+        .post(server.mcp_url())
+        .bearer_auth(&token)
+        .header("Accept", "application/json, text/event-stream")
+        .json(&initialize)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_whoami_reports_api_key_id() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_auth("whoami-secret").await;
+    let client = McpClient::connect_with_auth(&server, "whoami-secret").await;
+
+    let response = client
+        .request("tools/call", serde_json::json!({ "name": "whoami" }))
+        .await;
+    let report = whoami_report(&response);
+    assert_eq!(report["method"], "api_key");
+    assert_eq!(report["api_key_id"], ApiKeyId::for_key("whoami-secret").0);
+    assert!(report["client_id"].is_null());
+    assert!(!response.to_string().contains("whoami-secret"));
+}
+
+#[tokio::test]
+async fn test_whoami_and_auth_context_report_oauth_client() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_config(Config::builder().oauth(true).build()).await;
+    let token = client_credentials_token(&server).await;
+    let client = McpClient::connect_with_auth(&server, &token).await;
+
+    let response = client
+        .request("tools/call", serde_json::json!({ "name": "whoami" }))
+        .await;
+    let report = whoami_report(&response);
+    assert_eq!(report["method"], "oauth");
+    assert!(
+        report["client_id"]
+            .as_str()
+            .unwrap()
+            .starts_with("test-client-")
+    );
+    assert_eq!(report["grant_type"], "client_credentials");
+    assert!(report["scopes"].is_array());
+
+    let response = client
+        .request(
+            "resources/read",
+            serde_json::json!({ "uri": "test://dynamic/auth-context" }),
+        )
+        .await;
+    let text = response["result"]["contents"][0]["text"].as_str().unwrap();
+    let context: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(context, report);
+}
@@ -0,0 +1,174 @@
+//! JSON-RPC batches on `/mcp`: served, rejected, or decided by the
+//! `MCP-Protocol-Version` header per `MCP_BATCH`.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use mcp_test_server::batch::BatchPolicy;
+use serde_json::{Value, json};
+
+/// `POST /mcp` `batch` in `session` with the given protocol version header.
+async fn post_batch(
+    server: &TestServer,
+    session: Option<&str>,
+    version: Option<&str>,
+    batch: &Value,
+) -> reqwest::Response {
+    let mut request = common::test_client()
+        .post(server.mcp_url())
+        .header("Accept", "application/json, text/event-stream")
+        .json(batch);
+    if let Some(session) = session {
+        request = request.header("Mcp-Session-Id", session);
+    }
+    if let Some(version) = version {
+        request = request.header("MCP-Protocol-Version", version);
+    }
+    request.send().await.unwrap()
+}
+
+fn two_requests() -> Value {
+    json!([
+        {"jsonrpc": "2.0", "id": "a", "method": "tools/list"},
+        {
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {"name": "add", "arguments": {"a": 2, "b": 3}}
+        }
+    ])
+}
+
+async fn start(policy: BatchPolicy) -> (TestServer, String) {
+    let server =
+        TestServer::start_with_config(Config::builder().stateful(true).batch(policy).build()).await;
+    let session = McpClient::connect(&server)
+        .await
+        .session_id()
+        .unwrap()
+        .to_string();
+    (server, session)
+}
+
+#[tokio::test]
+async fn test_accept_serves_batch_in_order() {
+    common::init_test_tracing();
+    let (server, session) = start(BatchPolicy::Accept).await;
+
+    let response = post_batch(&server, Some(&session), Some("2025-06-18"), &two_requests()).await;
+    assert_eq!(response.status(), 200);
+    assert!(
+        response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("application/json")
+    );
+    let responses: Vec<Value> = response.json().await.unwrap();
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0]["id"], "a");
+    assert!(responses[0]["result"]["tools"].is_array());
+    assert_eq!(responses[1]["id"], 2);
+    assert_eq!(responses[1]["result"]["content"][0]["text"], "5");
+
+    let mixed = json!([
+        {"jsonrpc": "2.0", "id": 3, "method": "initialize", "params": {}},
+        {"jsonrpc": "2.0", "method": "notifications/roots/list_changed"},
+        {"jsonrpc": "2.0", "id": 4, "method": "ping"}
+    ]);
+    let responses: Vec<Value> = post_batch(&server, Some(&session), None, &mixed)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0]["id"], 3);
+    assert_eq!(responses[0]["error"]["code"], -32600);
+    assert_eq!(responses[1]["id"], 4);
+    assert_eq!(responses[1]["result"], json!({}));
+}
+
+#[tokio::test]
+async fn test_notification_only_batch_is_accepted() {
+    common::init_test_tracing();
+    let (server, session) = start(BatchPolicy::Accept).await;
+
+    let batch = json!([
+        {"jsonrpc": "2.0", "method": "notifications/roots/list_changed"},
+        {"jsonrpc": "2.0", "method": "notifications/roots/list_changed"}
+    ]);
+    let response = post_batch(&server, Some(&session), None, &batch).await;
+    assert_eq!(response.status(), 202);
+
+    let empty = post_batch(&server, Some(&session), None, &json!([])).await;
+    assert_eq!(empty.status(), 400);
+    let error: Value = empty.json().await.unwrap();
+    assert_eq!(error["error"]["code"], -32600);
+}
+
+#[tokio::test]
+async fn test_reject_refuses_every_revision() {
+    common::init_test_tracing();
+    let (server, session) = start(BatchPolicy::Reject).await;
+
+    let response = post_batch(&server, Some(&session), Some("2025-03-26"), &two_requests()).await;
+    assert_eq!(response.status(), 400);
+    let error: Value = response.json().await.unwrap();
+    assert_eq!(error["id"], Value::Null);
+    assert_eq!(error["error"]["code"], -32600);
+    assert!(
+        error["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("Batch requests are not supported")
+    );
+}
+
+#[tokio::test]
+async fn test_spec_follows_protocol_version_header() {
+    common::init_test_tracing();
+    let (server, session) = start(BatchPolicy::Spec).await;
+
+    let current = post_batch(&server, Some(&session), Some("2025-06-18"), &two_requests()).await;
+    assert_eq!(current.status(), 400);
+
+    let older = post_batch(&server, Some(&session), Some("2025-03-26"), &two_requests()).await;
+    assert_eq!(older.status(), 200);
+    let responses: Vec<Value> = older.json().await.unwrap();
+    assert_eq!(responses.len(), 2);
+
+    // A missing header means 2025-03-26
+    let missing = post_batch(&server, Some(&session), None, &two_requests()).await;
+    assert_eq!(missing.status(), 200);
+}
+
+#[tokio::test]
+async fn test_spec_serves_batch_without_protocol_version_header() {
+    common::init_test_tracing();
+    let (server, session) = start(BatchPolicy::Spec).await;
+
+    let response = post_batch(&server, Some(&session), None, &two_requests()).await;
+    assert_eq!(response.status(), 200);
+    let responses: Vec<Value> = response.json().await.unwrap();
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0]["id"], "a");
+    assert_eq!(responses[1]["result"]["content"][0]["text"], "5");
+}
+
+#[tokio::test]
+async fn test_spec_rejects_unknown_protocol_version() {
+    common::init_test_tracing();
+    let (server, session) = start(BatchPolicy::Spec).await;
+
+    for version in ["2025-99", "1.0"] {
+        let response = post_batch(&server, Some(&session), Some(version), &two_requests()).await;
+        assert_eq!(response.status(), 400, "{version}");
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["id"], Value::Null);
+        assert_eq!(body["error"]["code"], -32600);
+        assert!(
+            body["error"]["message"].as_str().unwrap().contains(version),
+            "{body}"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+//! Broken server tests: `mcp-broken-server` routes over real HTTP.
+
+#![cfg(feature = "broken-server")]
+
+mod common;
+
+use mcp_test_server::Config;
+use mcp_test_server::broken::{self, BrokenMode};
+
+async fn start(config: Config, mode: BrokenMode) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, broken::router(config, mode))
+            .await
+            .unwrap();
+    });
+    format!("http://{addr}")
+}
+
+fn initialize() -> serde_json::Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-06-18",
+            "capabilities": {},
+            "clientInfo": { "name": "robustness-suite", "version": "0.0.0" }
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_default_and_per_path_modes() {
+    common::init_test_tracing();
+
+    let base = start(Config::default(), BrokenMode::BadCapabilities).await;
+    let client = reqwest::Client::new();
+
+    let init: serde_json::Value = client
+        .post(format!("{base}/mcp"))
+        .json(&initialize())
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(init["result"]["protocolVersion"], "not-a-version");
+    assert_eq!(init["result"]["serverInfo"]["name"], 42);
+
+    let html = client
+        .post(format!("{base}/wrong-content-type/mcp"))
+        .json(&initialize())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(html.headers()["content-type"], "text/html; charset=utf-8");
+    let body: serde_json::Value = serde_json::from_str(&html.text().await.unwrap()).unwrap();
+    assert_eq!(body["result"]["serverInfo"]["name"], "mcp-broken-server");
+
+    let unknown = client
+        .post(format!("{base}/polite/mcp"))
+        .json(&initialize())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(unknown.status(), reqwest::StatusCode::NOT_FOUND);
+
+    let health = client.get(format!("{base}/health")).send().await.unwrap();
+    assert!(health.status().is_success());
+}
+
+#[tokio::test]
+async fn test_api_key_is_shared_with_main_server() {
+    common::init_test_tracing();
+
+    let config = Config::builder().api_key("broken-key").build();
+    let base = start(config, BrokenMode::EmptyBody).await;
+
+    let rejected = reqwest::Client::new()
+        .post(format!("{base}/mcp"))
+        .json(&initialize())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(rejected.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let accepted = common::test_client_with_auth("broken-key")
+        .post(format!("{base}/mcp"))
+        .json(&initialize())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(accepted.status(), reqwest::StatusCode::OK);
+    assert!(accepted.text().await.unwrap().is_empty());
+}
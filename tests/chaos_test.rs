@@ -0,0 +1,135 @@
+//! `MCP_CHAOS_RESPONSES` and `MCP_TOOL_ERROR_RATE` tests: duplicated and
+//! reordered responses, intermittent tool failures.
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{McpClient, TestServer, parse_sse_messages};
+use mcp_test_server::Config;
+use mcp_test_server::chaos::{REORDER_MAX_DELAY, ResponseChaos};
+use serde_json::json;
+
+fn echo(id: u64, text: &str) -> serde_json::Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "tools/call",
+        "params": { "name": "echo", "arguments": { "text": text } },
+    })
+}
+
+#[tokio::test]
+async fn test_duplicate_sends_response_twice() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .chaos_responses([ResponseChaos::Duplicate], 1.0)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    let body = client.post(&echo(5, "twice")).await.text().await.unwrap();
+    let messages = parse_sse_messages(&body);
+    let responses: Vec<_> = messages.iter().filter(|m| m["id"] == 5).collect();
+    assert_eq!(responses.len(), 2, "{body}");
+    assert_eq!(responses[0], responses[1]);
+    assert_eq!(responses[1]["result"]["content"][0]["text"], "twice");
+}
+
+#[tokio::test]
+async fn test_reorder_answers_later_request_first() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .chaos_responses([ResponseChaos::Reorder], 1.0)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    let started = Instant::now();
+    let first = tokio::spawn(client.post(&echo(1, "first")).await.text());
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert!(!first.is_finished(), "first response was not held back");
+
+    // The second response overtakes the held one and releases it
+    let second = client.post(&echo(2, "second")).await.text().await.unwrap();
+    assert_eq!(parse_sse_messages(&second)[0]["id"], 2);
+    let first = first.await.unwrap().unwrap();
+    assert_eq!(parse_sse_messages(&first)[0]["id"], 1);
+    assert!(started.elapsed() < REORDER_MAX_DELAY + Duration::from_millis(100));
+}
+
+#[tokio::test]
+async fn test_reorder_releases_lone_response_after_max_delay() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .chaos_responses([ResponseChaos::Reorder], 1.0)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    let started = Instant::now();
+    let body = client.post(&echo(3, "alone")).await.text().await.unwrap();
+    assert!(started.elapsed() >= REORDER_MAX_DELAY);
+    assert_eq!(parse_sse_messages(&body)[0]["id"], 3);
+}
+
+#[tokio::test]
+async fn test_tool_error_rate_fails_only_listed_tools() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .tool_error_rate("add", 1.0)
+        .seed(7)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": "add", "arguments": { "a": 1, "b": 2 } }),
+        )
+        .await;
+    assert_eq!(response["result"]["isError"], true);
+    let text = response["result"]["content"][0]["text"].as_str().unwrap();
+    assert!(text.contains("MCP_TOOL_ERROR_RATE"), "{text}");
+
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": "subtract", "arguments": { "a": 3, "b": 2 } }),
+        )
+        .await;
+    assert_eq!(response["result"]["content"][0]["text"], "1");
+}
+
+#[tokio::test]
+async fn test_tool_error_rate_fails_intermittently() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .tool_error_rate("echo", 0.5)
+        .seed(42)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    let mut failures = 0;
+    for _ in 0..40 {
+        let response = client
+            .request(
+                "tools/call",
+                json!({ "name": "echo", "arguments": { "text": "hi" } }),
+            )
+            .await;
+        if response["result"]["isError"] == true {
+            failures += 1;
+        } else {
+            assert_eq!(response["result"]["content"][0]["text"], "hi");
+        }
+    }
+    assert!((5..=35).contains(&failures), "{failures} of 40 failed");
+}
@@ -0,0 +1,116 @@
+//! Compatibility tests: `MCP_COMPAT` shims for older client layouts.
+
+mod common;
+
+use common::TestServer;
+use mcp_test_server::compat::CompatTarget;
+
+fn post(
+    server: &TestServer,
+    session: Option<&str>,
+    body: &serde_json::Value,
+) -> reqwest::RequestBuilder {
+    let mut request = common::test_client()
+        .post(server.mcp_url())
+        .header("Accept", "application/json, text/event-stream")
+        .json(body);
+    if let Some(id) = session {
+        request = request.header("mcp-session-id", id);
+    }
+    request
+}
+
+async fn messages(request: reqwest::RequestBuilder) -> Vec<serde_json::Value> {
+    let response = request.send().await.unwrap();
+    assert!(response.status().is_success(), "{}", response.status());
+    common::parse_sse_messages(&response.text().await.unwrap())
+}
+
+#[tokio::test]
+async fn test_legacy_client_layouts_are_accepted() {
+    common::init_test_tracing();
+
+    let config = mcp_test_server::Config::builder()
+        .stateful(true)
+        .compat(CompatTarget::V2024_11_05)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+
+    // No capabilities, no client version, and snake_case names
+    let response = post(
+        &server,
+        None,
+        &serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "method": "initialize",
+            "params": {
+                "protocol_version": "2024-11-05",
+                "client_info": { "name": "legacy-client" }
+            }
+        }),
+    )
+    .send()
+    .await
+    .unwrap();
+    assert!(response.status().is_success(), "{}", response.status());
+    let session = response.headers()["mcp-session-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let init = common::parse_sse_messages(&response.text().await.unwrap());
+    assert_eq!(init[0]["result"]["protocolVersion"], "2024-11-05");
+
+    let initialized = post(
+        &server,
+        Some(&session),
+        &serde_json::json!({ "jsonrpc": "2.0", "method": "initialized" }),
+    )
+    .send()
+    .await
+    .unwrap();
+    assert!(initialized.status().is_success());
+
+    let call = messages(post(
+        &server,
+        Some(&session),
+        &serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "echo", "arguments": "{\"text\":\"hi\"}" }
+        }),
+    ))
+    .await;
+    let result = &call.last().unwrap()["result"];
+    assert_eq!(result["isError"], false, "{result}");
+    assert!(
+        result["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("hi")
+    );
+}
+
+#[tokio::test]
+async fn test_latest_protocol_without_compat() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let init = messages(post(
+        &server,
+        None,
+        &serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "clientInfo": { "name": "current-client", "version": "1.0.0" }
+            }
+        }),
+    ))
+    .await;
+    assert_eq!(init[0]["result"]["protocolVersion"], "2025-06-18");
+}
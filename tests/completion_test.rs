@@ -0,0 +1,149 @@
+//! `completion/complete` over MCP: context-aware template completion,
+//! truncated candidate lists, and completion of constrained prompt arguments.
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use serde_json::{Value, json};
+
+async fn complete(client: &McpClient, params: Value) -> Value {
+    client.request("completion/complete", params).await["result"]["completion"].clone()
+}
+
+#[tokio::test]
+async fn test_repo_completion_uses_context_arguments() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+    let reference = json!({ "type": "ref/resource", "uri": "test://repos/{owner}/{repo}" });
+
+    let with_owner = complete(
+        &client,
+        json!({
+            "ref": reference,
+            "argument": { "name": "repo", "value": "" },
+            "context": { "arguments": { "owner": "bob" } },
+        }),
+    )
+    .await;
+    assert_eq!(with_owner["values"], json!(["blog", "dotfiles", "website"]));
+
+    let with_prefix = complete(
+        &client,
+        json!({
+            "ref": reference,
+            "argument": { "name": "repo", "value": "w" },
+            "context": { "arguments": { "owner": "acme" } },
+        }),
+    )
+    .await;
+    assert_eq!(with_prefix["values"], json!(["widgets"]));
+
+    let without_context = complete(
+        &client,
+        json!({ "ref": reference, "argument": { "name": "repo", "value": "" } }),
+    )
+    .await;
+    assert_eq!(without_context["values"].as_array().unwrap().len(), 7);
+}
+
+#[tokio::test]
+async fn test_many_completion_is_truncated_with_total() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+    let reference = json!({ "type": "ref/resource", "uri": "test://many/{item}" });
+
+    let all = complete(
+        &client,
+        json!({ "ref": reference, "argument": { "name": "item", "value": "" } }),
+    )
+    .await;
+    let values = all["values"].as_array().unwrap();
+    assert_eq!(values.len(), 100);
+    assert_eq!(values[0], "item-001");
+    assert_eq!(all["total"], 250);
+    assert_eq!(all["hasMore"], true);
+
+    // "item-2" narrows the list to item-200 to item-250, which fits
+    let narrowed = complete(
+        &client,
+        json!({ "ref": reference, "argument": { "name": "item", "value": "item-2" } }),
+    )
+    .await;
+    assert_eq!(narrowed["values"].as_array().unwrap().len(), 51);
+    assert!(narrowed.get("total").is_none());
+    assert!(narrowed.get("hasMore").is_none());
+}
+
+#[tokio::test]
+async fn test_tone_completion_offers_only_valid_values() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+    let reference = json!({ "type": "ref/prompt", "name": "tone_rewrite" });
+
+    let tones = complete(
+        &client,
+        json!({ "ref": reference, "argument": { "name": "tone", "value": "f" } }),
+    )
+    .await;
+    assert_eq!(tones["values"], json!(["formal", "friendly"]));
+
+    // Every completed value is accepted, and nothing else is
+    for tone in tones["values"].as_array().unwrap() {
+        let prompt = client
+            .request(
+                "prompts/get",
+                json!({ "name": "tone_rewrite", "arguments": { "text": "hi", "tone": tone } }),
+            )
+            .await;
+        assert!(prompt.get("error").is_none(), "{tone} rejected: {prompt}");
+    }
+    let rejected = client
+        .request(
+            "prompts/get",
+            json!({ "name": "tone_rewrite", "arguments": { "text": "hi", "tone": "grumpy" } }),
+        )
+        .await;
+    assert_eq!(rejected["error"]["code"], -32602);
+    assert_eq!(rejected["error"]["data"]["value"], "grumpy");
+}
+
+#[tokio::test]
+async fn test_completion_delay_and_rate_limit() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .completion_delay(Duration::from_millis(200))
+        .completion_rate_limit(2)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+    let params = json!({
+        "ref": { "type": "ref/prompt", "name": "greeting" },
+        "argument": { "name": "name", "value": "A" },
+    });
+
+    let started = Instant::now();
+    let first = client.request("completion/complete", params.clone()).await;
+    assert!(started.elapsed() >= Duration::from_millis(200));
+    assert_eq!(first["result"]["completion"]["values"], json!(["Alice"]));
+    client.request("completion/complete", params.clone()).await;
+
+    let limited = client.request("completion/complete", params.clone()).await;
+    assert_eq!(limited["error"]["code"], -32029);
+    assert_eq!(limited["error"]["data"]["limit"], 2);
+    assert!(limited["error"]["data"]["retry_after_ms"].as_u64().unwrap() <= 1000);
+
+    // The window slides, so the session may complete again a second later
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    let again = client.request("completion/complete", params).await;
+    assert!(again["error"].is_null(), "still limited: {again}");
+}
@@ -0,0 +1,96 @@
+//! Compression tool tests: gzip and zstd base64 round trips and
+//! corrupted-input error paths.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::{Value, json};
+
+async fn call(client: &McpClient, name: &str, arguments: Value) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": name, "arguments": arguments }),
+        )
+        .await["result"]
+        .clone()
+}
+
+fn text(result: &Value) -> &str {
+    result["content"][0]["text"].as_str().unwrap()
+}
+
+#[tokio::test]
+async fn test_compression_round_trips() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let original = "κόσμε 🌍 ".repeat(200);
+    for format in ["gzip", "zstd"] {
+        let compressed = call(
+            &client,
+            &format!("{format}_compress"),
+            json!({ "text": original }),
+        )
+        .await;
+        assert_ne!(compressed["isError"], true, "{format}");
+        let data = text(&compressed).to_string();
+        assert!(data.len() < original.len(), "{format}");
+
+        let restored = call(
+            &client,
+            &format!("{format}_decompress"),
+            json!({ "data": data }),
+        )
+        .await;
+        assert_eq!(text(&restored), original, "{format}");
+    }
+}
+
+#[tokio::test]
+async fn test_corrupted_input_is_a_tool_error() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let gzip = call(&client, "gzip_compress", json!({ "text": "payload" })).await;
+    // Flip a byte in the middle of the base64 payload
+    let mut corrupted = text(&gzip).to_string().into_bytes();
+    let middle = corrupted.len() / 2;
+    corrupted[middle] = if corrupted[middle] == b'A' {
+        b'B'
+    } else {
+        b'A'
+    };
+    let corrupted = String::from_utf8(corrupted).unwrap();
+
+    let cases = [
+        ("gzip_decompress", "%%%", "Invalid base64:"),
+        ("zstd_decompress", "aGVsbG8=", "Corrupted zstd data:"),
+        (
+            "gzip_decompress",
+            corrupted.as_str(),
+            "Corrupted gzip data:",
+        ),
+    ];
+    for (name, data, expected) in cases {
+        let result = call(&client, name, json!({ "data": data })).await;
+        assert_eq!(result["isError"], true, "{name}");
+        assert!(
+            text(&result).starts_with(expected),
+            "{name}: {}",
+            text(&result)
+        );
+    }
+
+    let result = call(
+        &client,
+        "zstd_compress",
+        json!({ "text": "x", "level": 99 }),
+    )
+    .await;
+    assert_eq!(result["isError"], true);
+}
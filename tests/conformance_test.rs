@@ -0,0 +1,80 @@
+//! Conformance report tests: `GET /admin/conformance/{session}` reflects the
+//! protocol features a client exercised.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_conformance_report_per_session() {
+    common::init_test_tracing();
+
+    let config = mcp_test_server::Config::builder().stateful(true).build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+    let session = client.session_id().unwrap().to_string();
+    client.request("tools/list", json!({})).await;
+    client
+        .request(
+            "tools/call",
+            json!({ "name": "echo", "arguments": { "text": "hi" } }),
+        )
+        .await;
+    client
+        .request(
+            "tools/call",
+            json!({ "name": "add", "arguments": { "a": 1, "b": 2 } }),
+        )
+        .await;
+    // Another session's traffic stays out of this report
+    let other = McpClient::connect(&server).await;
+    other.request("prompts/list", json!({})).await;
+
+    let url = format!("{}/admin/conformance/{session}", server.base_url());
+    let report: serde_json::Value = reqwest::get(&url).await.unwrap().json().await.unwrap();
+    assert_eq!(report["session"], session);
+    assert_eq!(report["initialize"]["protocol_version"], "2025-06-18");
+    assert_eq!(report["initialize"]["client_info"]["name"], "test-client");
+    assert_eq!(
+        report["requests"],
+        json!({ "initialize": 1, "tools/call": 2, "tools/list": 1 })
+    );
+    assert_eq!(
+        report["notifications"],
+        json!({ "notifications/initialized": 1 })
+    );
+    assert_eq!(
+        report["coverage"]["exercised"],
+        json!([
+            "initialize",
+            "tools/list",
+            "tools/call",
+            "notifications/initialized"
+        ])
+    );
+    assert!(
+        report["coverage"]["missing"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("prompts/list"))
+    );
+
+    let missing = reqwest::get(format!("{}/admin/conformance/nope", server.base_url()))
+        .await
+        .unwrap();
+    assert_eq!(missing.status(), reqwest::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_conformance_report_without_sessions() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+    client.request("ping", json!({})).await;
+
+    let url = format!("{}/admin/conformance/default", server.base_url());
+    let report: serde_json::Value = reqwest::get(&url).await.unwrap().json().await.unwrap();
+    assert_eq!(report["requests"]["ping"], 1);
+}
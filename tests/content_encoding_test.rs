@@ -0,0 +1,154 @@
+//! Response compression: negotiated gzip, deflate, and brotli on `/mcp`
+//! (SSE bodies included), and forced gzip for clients that never asked.
+
+mod common;
+
+use std::io::{Read, Write};
+use std::time::Duration;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use mcp_test_server::content_encoding::ResponseCompression;
+use serde_json::{Value, json};
+
+/// POST a `tools/list` request to `server` with `accept_encoding`.
+async fn list_tools(server: &TestServer, accept_encoding: Option<&str>) -> reqwest::Response {
+    let mut request = common::test_client()
+        .post(server.mcp_url())
+        .header("Accept", "application/json, text/event-stream")
+        .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list", "params": {} }));
+    if let Some(encoding) = accept_encoding {
+        request = request.header("Accept-Encoding", encoding);
+    }
+    request.send().await.unwrap()
+}
+
+/// The `Content-Encoding` of `response`, if any.
+fn content_encoding(response: &reqwest::Response) -> Option<String> {
+    response
+        .headers()
+        .get("content-encoding")
+        .map(|v| v.to_str().unwrap().to_string())
+}
+
+/// The JSON-RPC messages of a decoded SSE body.
+fn messages(body: &[u8]) -> Vec<Value> {
+    common::parse_sse_messages(std::str::from_utf8(body).unwrap())
+}
+
+#[tokio::test]
+async fn test_compression_is_negotiated() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .compression(ResponseCompression::On)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+
+    let response = list_tools(&server, Some("gzip")).await;
+    assert_eq!(content_encoding(&response).as_deref(), Some("gzip"));
+    assert!(
+        response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/event-stream")
+    );
+    let mut body = Vec::new();
+    flate2::read::GzDecoder::new(&response.bytes().await.unwrap()[..])
+        .read_to_end(&mut body)
+        .unwrap();
+    assert!(messages(&body)[0]["result"]["tools"].is_array());
+
+    let response = list_tools(&server, Some("deflate")).await;
+    assert_eq!(content_encoding(&response).as_deref(), Some("deflate"));
+    let mut body = Vec::new();
+    flate2::read::ZlibDecoder::new(&response.bytes().await.unwrap()[..])
+        .read_to_end(&mut body)
+        .unwrap();
+    assert!(messages(&body)[0]["result"]["tools"].is_array());
+
+    let response = list_tools(&server, Some("br")).await;
+    assert_eq!(content_encoding(&response).as_deref(), Some("br"));
+
+    let response = list_tools(&server, None).await;
+    assert_eq!(content_encoding(&response), None);
+    let body = response.bytes().await.unwrap();
+    assert!(messages(&body)[0]["result"]["tools"].is_array());
+}
+
+#[tokio::test]
+async fn test_compressed_sse_stream_delivers_events_promptly() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .stateful(true)
+        .compression(ResponseCompression::On)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+    let mut stream = common::test_client()
+        .get(server.mcp_url())
+        .header("Accept", "text/event-stream")
+        .header("Accept-Encoding", "gzip")
+        .header("mcp-session-id", client.session_id().unwrap())
+        .timeout(Duration::from_secs(30))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(content_encoding(&stream).as_deref(), Some("gzip"));
+
+    let result = client
+        .request(
+            "tools/call",
+            json!({
+                "name": "register_prompt",
+                "arguments": { "name": "standup", "template": "Standup" },
+            }),
+        )
+        .await;
+    assert_ne!(result["result"]["isError"], true, "{result}");
+
+    // The stream stays open, so the event must arrive without the gzip trailer
+    let mut decoder = flate2::write::GzDecoder::new(Vec::new());
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !String::from_utf8_lossy(decoder.get_ref()).contains("list_changed") {
+            let chunk = stream.chunk().await.unwrap().expect("SSE stream ended");
+            decoder.write_all(&chunk).unwrap();
+            decoder.flush().unwrap();
+        }
+    })
+    .await
+    .expect("Timed out waiting for a compressed list_changed");
+}
+
+#[tokio::test]
+async fn test_forced_compression_ignores_accept_encoding() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .compression(ResponseCompression::Forced)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+
+    for accept_encoding in [None, Some("identity"), Some("br")] {
+        let response = list_tools(&server, accept_encoding).await;
+        assert_eq!(
+            content_encoding(&response).as_deref(),
+            Some("gzip"),
+            "{accept_encoding:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_compression_is_off_by_default() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let response = list_tools(&server, Some("gzip, deflate, br")).await;
+    assert_eq!(content_encoding(&response), None);
+
+    let client = McpClient::connect(&server).await;
+    let tools = client.request("tools/list", json!({})).await;
+    assert!(tools["result"]["tools"].is_array());
+}
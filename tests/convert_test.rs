@@ -0,0 +1,93 @@
+//! Conversion tool tests: nested YAML and TOML documents through JSON, and
+//! parser error paths.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::{Value, json};
+
+async fn call(client: &McpClient, name: &str, arguments: Value) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": name, "arguments": arguments }),
+        )
+        .await["result"]
+        .clone()
+}
+
+fn text(result: &Value) -> &str {
+    result["content"][0]["text"].as_str().unwrap()
+}
+
+#[tokio::test]
+async fn test_nested_documents_convert_through_json() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let value = json!({
+        "service": { "name": "api", "replicas": 3, "ports": [80, 443] },
+        "env": [{ "key": "MODE", "value": "test" }, { "key": "DEBUG", "value": null }],
+    });
+    let result = call(&client, "json_to_yaml", json!({ "value": value })).await;
+    assert_ne!(result["isError"], true);
+    let yaml = text(&result).to_string();
+    assert!(yaml.contains("replicas: 3"), "{yaml}");
+
+    let result = call(&client, "yaml_to_json", json!({ "yaml": yaml })).await;
+    let round_trip: Value = serde_json::from_str(text(&result)).unwrap();
+    assert_eq!(round_trip, value);
+
+    let toml = "[package]\nname = \"demo\"\nauthors = [\"a\", \"b\"]\n\n[[bin]]\nname = \"cli\"\n";
+    let result = call(&client, "toml_to_json", json!({ "toml": toml })).await;
+    let converted: Value = serde_json::from_str(text(&result)).unwrap();
+    assert_eq!(
+        converted,
+        json!({
+            "package": { "name": "demo", "authors": ["a", "b"] },
+            "bin": [{ "name": "cli" }],
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_malformed_documents_are_tool_errors() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let cases = [
+        (
+            "yaml_to_json",
+            json!({ "yaml": "key: [unclosed" }),
+            "Invalid YAML:",
+        ),
+        (
+            "yaml_to_json",
+            json!({ "yaml": "a: 1\n  b: 2\n" }),
+            "Invalid YAML:",
+        ),
+        (
+            "toml_to_json",
+            json!({ "toml": "[table\nkey = 1" }),
+            "Invalid TOML:",
+        ),
+        (
+            "toml_to_json",
+            json!({ "toml": "a = 1\na = 2" }),
+            "Invalid TOML:",
+        ),
+    ];
+    for (name, arguments, expected) in cases {
+        let result = call(&client, name, arguments).await;
+        assert_eq!(result["isError"], true, "{name}");
+        assert!(
+            text(&result).starts_with(expected),
+            "{name}: {}",
+            text(&result)
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! CORS: the permissive default, restricted origins, methods, and exposed
+//! headers, and CORS switched off.
+
+mod common;
+
+use common::TestServer;
+use mcp_test_server::Config;
+use mcp_test_server::cors::{CorsOrigins, CorsPolicy};
+use reqwest::Method;
+use reqwest::header::HeaderName;
+use serde_json::json;
+
+const ORIGIN: &str = "https://app.example.com";
+
+/// Send a preflight for `POST /mcp` from `origin`.
+async fn preflight(server: &TestServer, origin: &str, method: &str) -> reqwest::Response {
+    common::test_client()
+        .request(Method::OPTIONS, server.mcp_url())
+        .header("Origin", origin)
+        .header("Access-Control-Request-Method", method)
+        .header(
+            "Access-Control-Request-Headers",
+            "content-type, mcp-session-id",
+        )
+        .send()
+        .await
+        .unwrap()
+}
+
+/// `POST /mcp` an `initialize` request from `origin`.
+async fn initialize(server: &TestServer, origin: &str) -> reqwest::Response {
+    common::test_client()
+        .post(server.mcp_url())
+        .header("Origin", origin)
+        .header("Accept", "application/json, text/event-stream")
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "clientInfo": { "name": "browser", "version": "0.0.0" }
+            }
+        }))
+        .send()
+        .await
+        .unwrap()
+}
+
+fn header(response: &reqwest::Response, name: &str) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .map(|v| v.to_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn test_default_cors_is_permissive() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let response = preflight(&server, "http://localhost:5173", "DELETE").await;
+    assert!(response.status().is_success());
+    assert_eq!(
+        header(&response, "access-control-allow-origin").as_deref(),
+        Some("*")
+    );
+
+    let response = initialize(&server, "http://localhost:5173").await;
+    assert_eq!(
+        header(&response, "access-control-expose-headers").as_deref(),
+        Some("*")
+    );
+}
+
+#[tokio::test]
+async fn test_restricted_cors() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .stateful(true)
+        .cors(CorsPolicy {
+            origins: CorsOrigins::List(vec![ORIGIN.to_string()]),
+            methods: Some(vec![Method::GET, Method::POST]),
+            headers: Some(vec![
+                HeaderName::from_static("content-type"),
+                HeaderName::from_static("mcp-session-id"),
+            ]),
+            expose_headers: Some(vec![HeaderName::from_static("mcp-session-id")]),
+        })
+        .build();
+    let server = TestServer::start_with_config(config).await;
+
+    let response = preflight(&server, ORIGIN, "POST").await;
+    assert!(response.status().is_success());
+    assert_eq!(
+        header(&response, "access-control-allow-origin").as_deref(),
+        Some(ORIGIN)
+    );
+    assert_eq!(
+        header(&response, "access-control-allow-methods").as_deref(),
+        Some("GET,POST")
+    );
+    assert_eq!(
+        header(&response, "access-control-allow-headers").as_deref(),
+        Some("content-type,mcp-session-id")
+    );
+
+    let response = preflight(&server, "https://evil.example.com", "POST").await;
+    assert_eq!(header(&response, "access-control-allow-origin"), None);
+
+    // A listed origin also passes the DNS rebinding check
+    let response = initialize(&server, ORIGIN).await;
+    assert!(response.status().is_success());
+    assert!(header(&response, "mcp-session-id").is_some());
+    assert_eq!(
+        header(&response, "access-control-expose-headers").as_deref(),
+        Some("mcp-session-id")
+    );
+    let response = initialize(&server, "https://evil.example.com").await;
+    assert_eq!(response.status(), 403);
+}
+
+#[tokio::test]
+async fn test_cors_can_be_switched_off() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .cors(CorsPolicy {
+            origins: CorsOrigins::None,
+            ..CorsPolicy::default()
+        })
+        .build();
+    let server = TestServer::start_with_config(config).await;
+
+    let response = preflight(&server, "http://localhost:5173", "POST").await;
+    assert!(!response.status().is_success());
+    assert_eq!(header(&response, "access-control-allow-origin"), None);
+
+    let response = initialize(&server, "http://localhost:5173").await;
+    assert!(response.status().is_success());
+    assert_eq!(header(&response, "access-control-allow-origin"), None);
+}
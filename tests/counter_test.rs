@@ -0,0 +1,91 @@
+//! Counter tool tests: exactly-once delivery under concurrency and session
+//! isolation.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::{Value, json};
+
+async fn call(client: &McpClient, name: &str, scope: &str) -> Value {
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": name, "arguments": { "scope": scope } }),
+        )
+        .await;
+    serde_json::from_str(response["result"]["content"][0]["text"].as_str().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_concurrent_increments_are_applied_exactly_once() {
+    common::init_test_tracing();
+
+    let config = mcp_test_server::Config::builder().stateful(true).build();
+    let server = TestServer::start_with_config(config).await;
+    let alice = McpClient::connect(&server).await;
+    let bob = McpClient::connect(&server).await;
+
+    let calls = (0..10).flat_map(|_| {
+        [
+            call(&alice, "counter_increment", "session"),
+            call(&alice, "counter_increment", "server"),
+            call(&bob, "counter_increment", "server"),
+        ]
+    });
+    let results = futures_util::future::join_all(calls).await;
+
+    let mut server_values: Vec<u64> = results
+        .iter()
+        .filter(|r| r["scope"] == "server")
+        .map(|r| r["value"].as_u64().unwrap())
+        .collect();
+    server_values.sort_unstable();
+    assert_eq!(server_values, (1..=20).collect::<Vec<_>>());
+
+    let mut session_values: Vec<u64> = results
+        .iter()
+        .filter(|r| r["scope"] == "session")
+        .map(|r| r["value"].as_u64().unwrap())
+        .collect();
+    session_values.sort_unstable();
+    assert_eq!(session_values, (1..=10).collect::<Vec<_>>());
+
+    // Bob's session counter is untouched by Alice's increments
+    let reset = call(&bob, "counter_reset", "session").await;
+    assert_eq!(
+        reset,
+        json!({ "scope": "session", "previous": 0, "value": 0 })
+    );
+    let reset = call(&bob, "counter_reset", "server").await;
+    assert_eq!(reset["previous"], 20);
+    assert_eq!(
+        call(&alice, "counter_increment", "server").await["value"],
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_counter_dry_run_leaves_value_untouched() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+    call(&client, "counter_increment", "session").await;
+
+    for name in ["counter_increment", "counter_reset"] {
+        let result = client
+            .request(
+                "tools/call",
+                json!({ "name": name, "arguments": {}, "_meta": { "dryRun": true } }),
+            )
+            .await["result"]
+            .clone();
+        assert_eq!(result["_meta"]["dryRun"], true, "{name}");
+        assert_eq!(result["structuredContent"]["changes"]["previous"], 1);
+    }
+
+    assert_eq!(
+        call(&client, "counter_increment", "session").await["value"],
+        2
+    );
+}
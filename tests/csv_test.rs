@@ -0,0 +1,90 @@
+//! CSV tool tests: structured rows, delimiter and quote options, and error
+//! paths.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::{Value, json};
+
+async fn call(client: &McpClient, name: &str, arguments: Value) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": name, "arguments": arguments }),
+        )
+        .await["result"]
+        .clone()
+}
+
+#[tokio::test]
+async fn test_csv_round_trip_with_structured_content() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let result = call(
+        &client,
+        "csv_parse",
+        json!({ "text": "id\tnote\n1\t|tab\there|\n2\tplain\n", "delimiter": "\t", "quote": "|" }),
+    )
+    .await;
+    let parsed = &result["structuredContent"];
+    assert_eq!(parsed["headers"], json!(["id", "note"]));
+    assert_eq!(parsed["row_count"], 2);
+    assert_eq!(parsed["rows"][0], json!({ "id": "1", "note": "tab\there" }));
+    // The text content carries the same rows
+    let text: Value = serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(&text, parsed);
+
+    let result = call(
+        &client,
+        "csv_format",
+        json!({ "rows": parsed["rows"], "headers": ["note", "id"] }),
+    )
+    .await;
+    assert_eq!(
+        result["structuredContent"],
+        json!({ "csv": "note,id\ntab\there,1\nplain,2\n", "row_count": 2 })
+    );
+
+    let tools = client.request("tools/list", json!({})).await;
+    let tools = tools["result"]["tools"].as_array().unwrap();
+    for name in ["csv_parse", "csv_format"] {
+        let tool = tools.iter().find(|t| t["name"] == name).unwrap();
+        assert_eq!(tool["outputSchema"]["type"], "object", "{name}");
+    }
+}
+
+#[tokio::test]
+async fn test_csv_error_paths() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let cases = [
+        ("csv_parse", json!({ "text": "a,b\n1\n" }), "Invalid CSV:"),
+        (
+            "csv_parse",
+            json!({ "text": "a", "delimiter": "||" }),
+            "delimiter must be a single ASCII character",
+        ),
+        (
+            "csv_format",
+            json!({ "rows": [{ "a": 1 }, "b"] }),
+            "Invalid rows: row 1",
+        ),
+        (
+            "csv_format",
+            json!({ "rows": [["a"]], "quote": "" }),
+            "quote must be a single ASCII character",
+        ),
+    ];
+    for (name, arguments, expected) in cases {
+        let result = call(&client, name, arguments).await;
+        assert_eq!(result["isError"], true, "{name}");
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.starts_with(expected), "{name}: {text}");
+    }
+}
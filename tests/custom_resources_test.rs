@@ -0,0 +1,121 @@
+//! Custom resource and prompt registration tests: fixtures added with
+//! `McpTestServerBuilder::with_resource*` and `with_prompt`.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::McpTestServer;
+use rmcp::model::{PromptArgument, PromptMessage, PromptMessageRole, ResourceContents};
+use serde_json::json;
+
+fn fixture_server() -> mcp_test_server::McpTestServerBuilder {
+    McpTestServer::builder()
+        .with_resource("app://config", "application/json", r#"{"debug":true}"#)
+        .with_resource(
+            "app://logo",
+            "image/png",
+            vec![0x89, b'P', b'N', b'G', 0xff],
+        )
+        .with_resource_handler("app://clock", "text/plain", |uri| async move {
+            Ok(ResourceContents::text("tick", uri))
+        })
+        .with_resource_template(
+            "app://users/{id}",
+            "application/json",
+            |uri, vars| async move {
+                Ok(ResourceContents::text(
+                    json!({ "id": vars["id"] }).to_string(),
+                    uri,
+                ))
+            },
+        )
+        .with_prompt(
+            "triage",
+            vec![PromptArgument::new("ticket").with_required(true)],
+            |args| async move {
+                Ok(vec![PromptMessage::new_text(
+                    PromptMessageRole::User,
+                    format!("Triage ticket {}", args["ticket"]),
+                )])
+            },
+        )
+}
+
+#[tokio::test]
+async fn test_custom_resources_listed_and_read() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_builder(fixture_server()).await;
+    let client = McpClient::connect(&server).await;
+
+    let list = client.request("resources/list", json!({})).await;
+    let uris: Vec<&str> = list["result"]["resources"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|r| r["uri"].as_str())
+        .collect();
+    assert!(uris.contains(&"app://config") && uris.contains(&"app://clock"));
+    assert!(uris.contains(&"test://static/hello.txt"), "built-ins kept");
+
+    let templates = client.request("resources/templates/list", json!({})).await;
+    assert!(
+        templates["result"]["resourceTemplates"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|t| t["uriTemplate"] == "app://users/{id}")
+    );
+
+    let config = client
+        .request("resources/read", json!({ "uri": "app://config" }))
+        .await;
+    assert_eq!(config["result"]["contents"][0]["text"], r#"{"debug":true}"#);
+    let logo = client
+        .request("resources/read", json!({ "uri": "app://logo" }))
+        .await;
+    assert_eq!(logo["result"]["contents"][0]["blob"], "iVBOR/8=");
+    let clock = client
+        .request("resources/read", json!({ "uri": "app://clock" }))
+        .await;
+    assert_eq!(clock["result"]["contents"][0]["text"], "tick");
+    let user = client
+        .request("resources/read", json!({ "uri": "app://users/42" }))
+        .await;
+    assert_eq!(user["result"]["contents"][0]["text"], r#"{"id":"42"}"#);
+    assert_eq!(user["result"]["contents"][0]["uri"], "app://users/42");
+}
+
+#[tokio::test]
+async fn test_custom_prompt_checks_required_arguments() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_builder(fixture_server()).await;
+    let client = McpClient::connect(&server).await;
+
+    let list = client.request("prompts/list", json!({})).await;
+    let prompts = list["result"]["prompts"].as_array().unwrap();
+    let triage = prompts.iter().find(|p| p["name"] == "triage").unwrap();
+    assert_eq!(triage["arguments"][0]["required"], true);
+    assert!(prompts.iter().any(|p| p["name"] == "greeting"));
+
+    let prompt = client
+        .request(
+            "prompts/get",
+            json!({ "name": "triage", "arguments": { "ticket": "T-1" } }),
+        )
+        .await;
+    assert_eq!(
+        prompt["result"]["messages"][0]["content"]["text"],
+        "Triage ticket T-1"
+    );
+
+    let missing = client
+        .request("prompts/get", json!({ "name": "triage", "arguments": {} }))
+        .await;
+    assert_eq!(missing["error"]["code"], -32602);
+    assert_eq!(
+        missing["error"]["message"],
+        "Missing required argument: ticket"
+    );
+}
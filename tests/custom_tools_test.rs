@@ -0,0 +1,73 @@
+//! Custom tool registration tests: closure-backed tools added with
+//! `McpTestServerBuilder::with_tool`.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::{Config, McpTestServer};
+use rmcp::model::{CallToolResult, Content};
+use serde_json::json;
+
+fn shout_server() -> mcp_test_server::McpTestServerBuilder {
+    McpTestServer::builder().with_tool(
+        "shout",
+        json!({
+            "type": "object",
+            "properties": { "text": { "type": "string" } },
+            "required": ["text"]
+        }),
+        |args| async move {
+            let text = args["text"].as_str().unwrap_or_default().to_uppercase();
+            Ok(CallToolResult::success(vec![Content::text(text)]))
+        },
+    )
+}
+
+#[tokio::test]
+async fn test_custom_tool_listed_and_called() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_builder(shout_server()).await;
+    let client = McpClient::connect(&server).await;
+
+    let list = client.request("tools/list", json!({})).await;
+    let tools = list["result"]["tools"].as_array().unwrap();
+    let shout = tools.iter().find(|t| t["name"] == "shout").unwrap();
+    assert_eq!(shout["inputSchema"]["required"], json!(["text"]));
+    assert!(tools.iter().any(|t| t["name"] == "echo"), "built-ins kept");
+
+    let result = client
+        .request(
+            "tools/call",
+            json!({ "name": "shout", "arguments": { "text": "hi" } }),
+        )
+        .await;
+    assert_eq!(result["result"]["content"][0]["text"], "HI");
+}
+
+#[tokio::test]
+async fn test_custom_tool_errors_and_overrides() {
+    common::init_test_tracing();
+
+    let builder = shout_server()
+        .config(Config::builder().strict_args(true).build())
+        .with_tool("echo", json!({ "type": "object" }), |_| async {
+            Err(rmcp::ErrorData::internal_error("echo is down", None))
+        });
+    let server = TestServer::start_with_builder(builder).await;
+    let client = McpClient::connect(&server).await;
+
+    let echo = client
+        .request("tools/call", json!({ "name": "echo", "arguments": {} }))
+        .await;
+    assert_eq!(echo["error"]["message"], "echo is down");
+
+    // Strict argument checks use the registered schema
+    let strict = client
+        .request(
+            "tools/call",
+            json!({ "name": "shout", "arguments": { "text": "hi", "volume": 11 } }),
+        )
+        .await;
+    assert_eq!(strict["error"]["code"], -32602);
+}
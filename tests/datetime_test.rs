@@ -0,0 +1,106 @@
+//! Date/time tool tests: enum-constrained units, structured results, and
+//! validation errors.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::{Value, json};
+
+async fn call(client: &McpClient, name: &str, arguments: Value) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": name, "arguments": arguments }),
+        )
+        .await["result"]
+        .clone()
+}
+
+#[tokio::test]
+async fn test_datetime_tools() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let tools = client.request("tools/list", json!({})).await;
+    let date_add = tools["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["name"] == "date_add")
+        .unwrap()
+        .clone();
+    let schema = date_add["inputSchema"].to_string();
+    for unit in ["seconds", "days", "months", "years"] {
+        assert!(schema.contains(&format!("\"{unit}\"")), "{unit}: {schema}");
+    }
+
+    let result = call(
+        &client,
+        "date_add",
+        json!({ "timestamp": "2024-01-31T09:30:00Z", "amount": 1, "unit": "months" }),
+    )
+    .await;
+    assert_eq!(result["content"][0]["text"], "2024-02-29T09:30:00Z");
+
+    let result = call(
+        &client,
+        "date_diff",
+        json!({ "start": "2024-01-01T00:00:00Z", "end": "2024-01-15T12:00:00Z", "unit": "days" }),
+    )
+    .await;
+    assert_eq!(
+        result["structuredContent"],
+        json!({ "value": 14, "unit": "days" })
+    );
+
+    let result = call(
+        &client,
+        "timezone_convert",
+        json!({ "timestamp": "2024-01-01T00:00:00Z", "timezone": "Asia/Tokyo" }),
+    )
+    .await;
+    assert_eq!(
+        result["structuredContent"],
+        json!({
+            "timestamp": "2024-01-01T09:00:00+09:00",
+            "timezone": "Asia/Tokyo",
+            "utc_offset": "+09:00",
+            "abbreviation": "JST",
+            "dst": false
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_datetime_validation_errors() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let cases = [
+        (
+            "timezone_convert",
+            json!({ "timestamp": "2024-01-01T00:00:00Z", "timezone": "asia/tokyo" }),
+            "Unknown timezone \"asia/tokyo\"; did you mean \"Asia/Tokyo\"?",
+        ),
+        (
+            "date_add",
+            json!({ "timestamp": "2024-01-01T00:00:00Z", "amount": i64::MAX, "unit": "weeks" }),
+            "Date arithmetic overflowed",
+        ),
+        (
+            "date_diff",
+            json!({ "start": "01/02/2024", "end": "2024-01-01T00:00:00Z" }),
+            "Invalid start \"01/02/2024\"",
+        ),
+    ];
+    for (tool, arguments, expected) in cases {
+        let result = call(&client, tool, arguments).await;
+        assert_eq!(result["isError"], true, "{tool}");
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.starts_with(expected), "{tool}: {text}");
+    }
+}
@@ -0,0 +1,81 @@
+//! Request echo: `debug_echo_request` and `/debug/headers` report the
+//! headers, protocol version, session, and body size the server received.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use serde_json::{Value, json};
+
+#[tokio::test]
+async fn test_debug_echo_request_reports_what_was_sent() {
+    common::init_test_tracing();
+    let server = TestServer::start_with_config(Config::builder().stateful(true).build()).await;
+    let client = McpClient::connect(&server).await;
+    let session = client.session_id().unwrap().to_string();
+
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 7,
+        "method": "tools/call",
+        "params": {"name": "debug_echo_request", "arguments": {}}
+    })
+    .to_string();
+    let response = common::test_client()
+        .post(server.mcp_url())
+        .header("Content-Type", "application/json")
+        .header("Accept", "application/json, text/event-stream")
+        .header("Mcp-Session-Id", &session)
+        .header("MCP-Protocol-Version", "2025-06-18")
+        .header("X-Client-Trace", "abc123")
+        .body(body.clone())
+        .send()
+        .await
+        .unwrap();
+    let messages = common::parse_sse_messages(&response.text().await.unwrap());
+    let text = messages[0]["result"]["content"][0]["text"]
+        .as_str()
+        .unwrap();
+    let report: Value = serde_json::from_str(text).unwrap();
+
+    assert_eq!(report["transport"], "http");
+    assert_eq!(report["method"], "POST");
+    assert_eq!(report["path"], "/mcp");
+    assert_eq!(report["http_version"], "HTTP/1.1");
+    assert_eq!(report["protocol_version"], "2025-06-18");
+    assert_eq!(report["session_id"], session);
+    assert_eq!(report["body_bytes"], body.len());
+    assert_eq!(report["content_length"], body.len());
+    assert_eq!(report["headers"]["x-client-trace"], "abc123");
+    assert_eq!(
+        report["headers"]["accept"],
+        "application/json, text/event-stream"
+    );
+}
+
+#[tokio::test]
+async fn test_debug_headers_route_echoes_any_method() {
+    common::init_test_tracing();
+    let server = TestServer::start_with_auth("echo-secret").await;
+    let url = format!("{}/debug/headers", server.base_url());
+
+    let report: Value = common::test_client_with_auth("echo-secret")
+        .put(&url)
+        .header("MCP-Protocol-Version", "2025-03-26")
+        .body("0123456789")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(report["method"], "PUT");
+    assert_eq!(report["protocol_version"], "2025-03-26");
+    assert_eq!(report["session_id"], Value::Null);
+    assert_eq!(report["body_bytes"], 10);
+    assert_eq!(report["headers"]["authorization"], "Bearer <redacted>");
+
+    // Behind authentication like the other diagnostics
+    let response = common::test_client().get(&url).send().await.unwrap();
+    assert_eq!(response.status(), 401);
+}
@@ -0,0 +1,55 @@
+//! `edge_case_text` tests: each mode's awkward characters survive the trip
+//! through JSON-RPC intact.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::{Value, json};
+
+async fn text(client: &McpClient, arguments: Value) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": "edge_case_text", "arguments": arguments }),
+        )
+        .await["result"]
+        .clone()
+}
+
+#[tokio::test]
+async fn test_edge_case_modes() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let get = |result: &Value| result["content"][0]["text"].as_str().unwrap().to_string();
+
+    let surrogates = get(&text(&client, json!({ "mode": "lone_surrogates" })).await);
+    assert!(surrogates.contains(r"\uD800"), "{surrogates}");
+
+    let nul = get(&text(&client, json!({ "mode": "nul" })).await);
+    assert!(nul.starts_with('\0') && nul.ends_with('\0'));
+    assert_eq!(nul.matches('\0').count(), 3);
+
+    let bom = get(&text(&client, json!({ "mode": "bom" })).await);
+    assert!(bom.starts_with('\u{FEFF}'));
+
+    let rtl = get(&text(&client, json!({ "mode": "rtl" })).await);
+    assert!(rtl.contains('\u{202E}') && rtl.contains("עברית"));
+
+    let zero_width = get(&text(&client, json!({ "mode": "zero_width" })).await);
+    assert!(zero_width.contains('\u{200B}'));
+    assert!(zero_width.starts_with("zero") && !zero_width.contains(' '));
+
+    let line = get(&text(&client, json!({ "mode": "long_line", "length": 250_000 })).await);
+    assert_eq!(line.len(), 250_000);
+    assert!(!line.contains('\n'));
+
+    let result = text(
+        &client,
+        json!({ "mode": "long_line", "length": 10_000_001 }),
+    )
+    .await;
+    assert_eq!(result["isError"], true);
+}
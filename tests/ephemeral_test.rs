@@ -0,0 +1,85 @@
+//! Ephemeral resource tests: `ephemeral_create` makes `test://dynamic/ephemeral`
+//! for a while, then it leaves the listing and reads hit its tombstone.
+
+mod common;
+
+use std::time::Duration;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use serde_json::{Value, json};
+
+const URI: &str = "test://dynamic/ephemeral";
+
+async fn call(client: &McpClient, arguments: Value) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": "ephemeral_create", "arguments": arguments }),
+        )
+        .await["result"]
+        .clone()
+}
+
+async fn is_listed(client: &McpClient) -> bool {
+    client.request("resources/list", json!({})).await["result"]["resources"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|r| r["uri"] == URI)
+}
+
+#[tokio::test]
+async fn test_ephemeral_resource_expires() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_config(Config::builder().stateful(true).build()).await;
+    let client = McpClient::connect(&server).await;
+    let mut stream = client.open_stream().await;
+
+    let never_created = client
+        .request("resources/read", json!({ "uri": URI }))
+        .await;
+    assert!(never_created.get("error").is_some());
+    assert!(!is_listed(&client).await);
+
+    let result = call(&client, json!({ "ttl_secs": 1, "text": "brief" })).await;
+    let body: Value = serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(body["uri"], URI);
+    assert_eq!(body["ttl_secs"], 1);
+    assert!(is_listed(&client).await);
+    let read = client
+        .request("resources/read", json!({ "uri": URI }))
+        .await;
+    assert_eq!(read["result"]["contents"][0]["text"], "brief");
+
+    // One list change on creation, one on expiry
+    for _ in 0..2 {
+        let message = tokio::time::timeout(Duration::from_secs(5), stream.next_message())
+            .await
+            .expect("Timed out waiting for list_changed")
+            .expect("SSE stream ended");
+        assert_eq!(message["method"], "notifications/resources/list_changed");
+    }
+
+    assert!(!is_listed(&client).await);
+    let read = client
+        .request("resources/read", json!({ "uri": URI }))
+        .await;
+    assert_eq!(read["error"]["code"], -32002);
+    assert_eq!(read["error"]["data"]["uri"], URI);
+    assert_eq!(read["error"]["data"]["expired_at"], body["expires_at"]);
+}
+
+#[tokio::test]
+async fn test_ephemeral_create_rejects_bad_ttl() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    for ttl in [0, 301] {
+        let result = call(&client, json!({ "ttl_secs": ttl })).await;
+        assert_eq!(result["isError"], true, "ttl_secs {ttl}");
+    }
+}
@@ -0,0 +1,283 @@
+//! Integration tests for error reporting over the wire.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use mcp_test_server::i18n::ErrorLang;
+use mcp_test_server::tool_errors::ToolErrorMode;
+use mcp_test_server::unknown::{UnknownNotificationPolicy, UnknownToolPolicy};
+use mcp_test_server::validation::SchemaErrorDetail;
+
+#[tokio::test]
+async fn test_error_lang_localizes_protocol_errors() {
+    common::init_test_tracing();
+
+    let config = Config::builder().error_lang(ErrorLang::German).build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    let response = client
+        .request(
+            "resources/read",
+            serde_json::json!({ "uri": "test://nope" }),
+        )
+        .await;
+    assert_eq!(response["error"]["code"], -32600);
+    assert_eq!(
+        response["error"]["message"],
+        "Unbekannte Ressourcen-URI: test://nope"
+    );
+}
+
+#[tokio::test]
+async fn test_error_lang_localizes_tool_errors() {
+    common::init_test_tracing();
+
+    let config = Config::builder().error_lang(ErrorLang::Pseudo).build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    let response = client
+        .request(
+            "tools/call",
+            serde_json::json!({ "name": "divide", "arguments": { "a": 1, "b": 0 } }),
+        )
+        .await;
+    assert_eq!(response["result"]["isError"], true);
+    assert_eq!(
+        response["result"]["content"][0]["text"],
+        "[Dîvîšîöñ bý zérö ~~~~~~]"
+    );
+
+    // Successful results are never touched
+    let response = client
+        .request(
+            "tools/call",
+            serde_json::json!({ "name": "echo", "arguments": { "text": "Division by zero" } }),
+        )
+        .await;
+    assert_eq!(response["result"]["content"][0]["text"], "Division by zero");
+}
+
+#[tokio::test]
+async fn test_tool_error_mode_protocol_turns_error_results_into_errors() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .tool_error_mode(ToolErrorMode::Protocol)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    let response = client
+        .request(
+            "tools/call",
+            serde_json::json!({ "name": "divide", "arguments": { "a": 1, "b": 0 } }),
+        )
+        .await;
+    assert!(response.get("result").is_none(), "{response}");
+    assert_eq!(response["error"]["code"], -32603);
+    assert_eq!(response["error"]["message"], "Division by zero");
+    assert_eq!(response["error"]["data"]["tool"], "divide");
+    assert_eq!(
+        response["error"]["data"]["content"][0]["text"],
+        "Division by zero"
+    );
+}
+
+#[tokio::test]
+async fn test_tool_error_mode_result_turns_errors_into_error_results() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .tool_error_mode(ToolErrorMode::Result)
+        .max_response_bytes(100)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    let response = client
+        .request(
+            "tools/call",
+            serde_json::json!({ "name": "huge_response", "arguments": { "size_bytes": 1000 } }),
+        )
+        .await;
+    assert_eq!(response["result"]["isError"], true, "{response}");
+    assert!(
+        response["result"]["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("100")
+    );
+
+    // Invalid params stay protocol errors
+    let response = client
+        .request(
+            "tools/call",
+            serde_json::json!({ "name": "no_such_tool", "arguments": {} }),
+        )
+        .await;
+    assert_eq!(response["error"]["code"], -32602);
+}
+
+#[tokio::test]
+async fn test_schema_errors_point_at_failing_argument() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .schema_errors(SchemaErrorDetail::Summary)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    let response = client
+        .request(
+            "tools/call",
+            serde_json::json!({ "name": "add", "arguments": { "a": 1, "b": "two" } }),
+        )
+        .await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(
+        response["error"]["message"],
+        "Invalid arguments for tool 'add': /b expected number, got string"
+    );
+    assert_eq!(
+        response["error"]["data"],
+        serde_json::json!({
+            "tool": "add",
+            "pointer": "/b",
+            "expected": "number",
+            "actual": "string"
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_schema_errors_detailed_lists_every_failure() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .schema_errors(SchemaErrorDetail::Detailed)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    let response = client
+        .request(
+            "tools/call",
+            serde_json::json!({ "name": "add", "arguments": {} }),
+        )
+        .await;
+    let data = &response["error"]["data"];
+    let pointers: Vec<&str> = data["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["pointer"].as_str().unwrap())
+        .collect();
+    assert_eq!(pointers, ["/a", "/b"]);
+    assert_eq!(data["errors"][0]["actual"], "missing");
+    assert!(data["cause"].is_string());
+
+    // Arguments that match the schema are not affected
+    let response = client
+        .request(
+            "tools/call",
+            serde_json::json!({ "name": "add", "arguments": { "a": 1, "b": 2 } }),
+        )
+        .await;
+    assert_eq!(response["result"]["content"][0]["text"], "3");
+}
+
+#[tokio::test]
+async fn test_unknown_tool_policies() {
+    common::init_test_tracing();
+
+    let call = serde_json::json!({ "name": "no_such_tool", "arguments": {} });
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+    let response = client.request("tools/call", call.clone()).await;
+    assert_eq!(response["error"]["code"], -32602);
+
+    let config = Config::builder()
+        .unknown_tool(UnknownToolPolicy::Result)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+    let response = client.request("tools/call", call).await;
+    assert_eq!(response["result"]["isError"], true);
+    assert_eq!(
+        response["result"]["content"][0]["text"],
+        "Unknown tool: no_such_tool"
+    );
+}
+
+#[tokio::test]
+async fn test_trailing_whitespace_tool_name() {
+    common::init_test_tracing();
+
+    let call = serde_json::json!({ "name": "echo ", "arguments": { "text": "hi" } });
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+    let response = client.request("tools/call", call.clone()).await;
+    assert_eq!(response["error"]["code"], -32602);
+
+    let config = Config::builder().lenient_names(true).build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+    let response = client.request("tools/call", call).await;
+    assert_eq!(response["result"]["content"][0]["text"], "hi");
+}
+
+#[tokio::test]
+async fn test_lenient_templated_resource_uri() {
+    common::init_test_tracing();
+
+    let read = serde_json::json!({ "uri": " TEST://Files/Docs/Notes.md " });
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+    let response = client.request("resources/read", read.clone()).await;
+    assert!(response["error"].is_object(), "{response}");
+
+    let config = Config::builder().lenient_names(true).build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+    let response = client.request("resources/read", read).await;
+    let contents = &response["result"]["contents"][0];
+    assert_eq!(contents["uri"], "test://files/Docs/Notes.md");
+    assert_eq!(contents["text"], "File content for path: Docs/Notes.md");
+}
+
+#[tokio::test]
+async fn test_unknown_notification_policies() {
+    common::init_test_tracing();
+
+    let bogus = serde_json::json!({ "jsonrpc": "2.0", "method": "notifications/bogus" });
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+    let response = client.post(&bogus).await;
+    assert_eq!(response.status(), reqwest::StatusCode::ACCEPTED);
+
+    let config = Config::builder()
+        .unknown_notification(UnknownNotificationPolicy::Error)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+    let response = client.post(&bogus).await;
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["id"], serde_json::Value::Null);
+    assert_eq!(body["error"]["code"], -32601);
+    assert_eq!(body["error"]["data"]["method"], "notifications/bogus");
+
+    // Known notifications and unknown requests are unaffected
+    client
+        .notify(
+            "notifications/cancelled",
+            serde_json::json!({ "requestId": 99 }),
+        )
+        .await;
+    let response = client.request("bogus/method", serde_json::json!({})).await;
+    assert_eq!(response["error"]["code"], -32601);
+}
@@ -0,0 +1,114 @@
+//! Fetch tool tests: `fetch_url` answers from the mock upstream only.
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use mcp_test_server::tools::fetch::MockRoute;
+use serde_json::{Value, json};
+
+async fn fetch(client: &McpClient, arguments: Value) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": "fetch_url", "arguments": arguments }),
+        )
+        .await["result"]
+        .clone()
+}
+
+#[tokio::test]
+async fn test_fetch_url_returns_structured_response() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .mock_routes([
+            MockRoute::new("/users", 200, r#"[{"id":1}]"#)
+                .with_method("GET")
+                .with_header("content-type", "application/json"),
+            MockRoute::new("/slow", 504, "").with_delay_ms(100),
+        ])
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    let tools = client.request("tools/list", json!({})).await;
+    let tool = tools["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["name"] == "fetch_url")
+        .unwrap()
+        .clone();
+    assert_eq!(tool["outputSchema"]["type"], "object");
+
+    let result = fetch(&client, json!({ "url": "http://mock.test/users?page=1" })).await;
+    assert_eq!(
+        result["structuredContent"],
+        json!({
+            "url": "http://mock.test/users?page=1",
+            "status": 200,
+            "headers": { "content-type": "application/json" },
+            "body": "[{\"id\":1}]"
+        })
+    );
+
+    // The GET-only route doesn't answer POST; the built-in 404 does
+    let result = fetch(
+        &client,
+        json!({ "url": "http://mock.test/users", "method": "POST" }),
+    )
+    .await;
+    assert_eq!(result["structuredContent"]["status"], 404);
+
+    let started = Instant::now();
+    let result = fetch(&client, json!({ "url": "http://mock.test/slow" })).await;
+    assert_eq!(result["structuredContent"]["status"], 504);
+    assert!(started.elapsed() >= Duration::from_millis(100));
+
+    let result = fetch(
+        &client,
+        json!({
+            "url": "http://mock.test/echo",
+            "method": "PUT",
+            "headers": { "authorization": "Bearer t" },
+            "body": "payload"
+        }),
+    )
+    .await;
+    let echoed: Value =
+        serde_json::from_str(result["structuredContent"]["body"].as_str().unwrap()).unwrap();
+    assert_eq!(echoed["method"], "PUT");
+    assert_eq!(echoed["headers"]["authorization"], "Bearer t");
+    assert_eq!(echoed["body"], "payload");
+}
+
+#[tokio::test]
+async fn test_fetch_url_refuses_other_hosts() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+    let result = fetch(&client, json!({ "url": "https://example.com/" })).await;
+    assert_eq!(result["isError"], true);
+    assert!(
+        result["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("mock.test")
+    );
+
+    let result = fetch(&client, json!({ "url": "http://mock.test/status/418" })).await;
+    assert_eq!(result["structuredContent"]["status"], 418);
+}
+
+#[test]
+fn test_mock_routes_file_is_validated() {
+    let path = std::env::temp_dir().join(format!("mock-routes-bad-{}.json", std::process::id()));
+    std::fs::write(&path, r#"{"path": "/not-an-array"}"#).unwrap();
+    let errors = Config::validate_vars([("MCP_MOCK_ROUTES", path.to_str().unwrap())]).unwrap_err();
+    assert_eq!(errors.0[0].name, "MCP_MOCK_ROUTES");
+    std::fs::remove_file(&path).unwrap();
+}
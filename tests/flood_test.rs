@@ -0,0 +1,116 @@
+//! `POST /admin/sessions/{id}/flood` tests: a burst of notifications reaches
+//! the session's stream.
+
+mod common;
+
+use std::time::Duration;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use serde_json::json;
+
+fn flood_url(server: &TestServer, session: &str) -> String {
+    format!("{}/admin/sessions/{session}/flood", server.base_url())
+}
+
+#[tokio::test]
+async fn test_flood_sends_notifications_at_rate() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_config(Config::builder().stateful(true).build()).await;
+    let client = McpClient::connect(&server).await;
+    let mut stream = client.open_stream().await;
+    let session = client.session_id().unwrap().to_string();
+
+    let flood = tokio::spawn(
+        common::test_client()
+            .post(flood_url(&server, &session))
+            .json(&json!({ "rate": 200, "duration_ms": 250 }))
+            .send(),
+    );
+    let mut methods = Vec::new();
+    while methods.len() < 50 {
+        let message = tokio::time::timeout(Duration::from_secs(3), stream.next_message())
+            .await
+            .expect("flood stalled")
+            .unwrap();
+        methods.push(message["method"].as_str().unwrap().to_string());
+    }
+    assert_eq!(
+        &methods[..3],
+        [
+            "notifications/message",
+            "notifications/resources/updated",
+            "notifications/progress"
+        ]
+    );
+
+    let report: serde_json::Value = flood.await.unwrap().unwrap().json().await.unwrap();
+    assert_eq!(report["session"], session.as_str());
+    assert_eq!(report["sent"], 50);
+    assert_eq!(report["failed"], 0);
+    assert!(report["elapsed_ms"].as_u64().unwrap() >= 200);
+}
+
+#[tokio::test]
+async fn test_flood_sends_only_requested_kinds() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_config(Config::builder().stateful(true).build()).await;
+    let client = McpClient::connect(&server).await;
+    let mut stream = client.open_stream().await;
+    let session = client.session_id().unwrap().to_string();
+
+    let report: serde_json::Value = common::test_client()
+        .post(flood_url(&server, &session))
+        .json(&json!({ "rate": 1000, "duration_ms": 5, "kinds": ["progress"] }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(report["sent"], 5);
+    for n in 1..=5 {
+        let message = stream.next_message().await.unwrap();
+        assert_eq!(message["method"], "notifications/progress");
+        assert_eq!(message["params"]["progressToken"], "flood");
+        assert_eq!(message["params"]["progress"], f64::from(n));
+        assert_eq!(message["params"]["total"], 5.0);
+    }
+}
+
+#[tokio::test]
+async fn test_flood_rejects_bad_requests() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_config(Config::builder().stateful(true).build()).await;
+    let client = McpClient::connect(&server).await;
+    let session = client.session_id().unwrap().to_string();
+
+    for body in [
+        json!({ "rate": 0 }),
+        json!({ "duration_ms": 600_000 }),
+        json!({ "kinds": [] }),
+    ] {
+        let response = common::test_client()
+            .post(flood_url(&server, &session))
+            .json(&body)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            response.status(),
+            reqwest::StatusCode::BAD_REQUEST,
+            "{body}"
+        );
+    }
+
+    let response = common::test_client()
+        .post(flood_url(&server, "no-such-session"))
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,76 @@
+//! `hang` and `MCP_REQUEST_TIMEOUT` tests: a call that never answers is
+//! ended by the server's timeout or by client cancellation.
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_request_timeout_answers_hanging_call() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .request_timeout(Duration::from_millis(300))
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    let started = Instant::now();
+    let response = client
+        .request("tools/call", json!({ "name": "hang", "arguments": {} }))
+        .await;
+    assert!(started.elapsed() >= Duration::from_millis(300));
+    assert_eq!(response["error"]["code"], -32001);
+    assert_eq!(
+        response["error"]["data"],
+        json!({ "tool": "hang", "timeout_ms": 300 })
+    );
+
+    // Quick calls are unaffected
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": "echo", "arguments": { "text": "fast" } }),
+        )
+        .await;
+    assert_eq!(response["result"]["content"][0]["text"], "fast");
+}
+
+#[tokio::test]
+async fn test_hang_ends_on_cancellation() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_config(Config::builder().stateful(true).build()).await;
+    let client = McpClient::connect(&server).await;
+
+    let response = client
+        .post(&json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "tools/call",
+            "params": { "name": "hang", "arguments": {} },
+        }))
+        .await;
+    let body = tokio::spawn(response.text());
+
+    // Still hanging well after a normal call would have finished
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(!body.is_finished());
+
+    client
+        .notify(
+            "notifications/cancelled",
+            json!({ "requestId": 7, "reason": "test" }),
+        )
+        .await;
+    let body = tokio::time::timeout(Duration::from_secs(3), body)
+        .await
+        .expect("hang did not end after cancellation")
+        .unwrap()
+        .unwrap();
+    assert!(!body.contains("\"result\""), "{body}");
+}
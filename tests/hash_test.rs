@@ -0,0 +1,93 @@
+//! Hash tool tests: the per-algorithm tools agree with `hash`, whose
+//! `algorithm` is an enum in the advertised input schema.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::{Value, json};
+
+async fn call(client: &McpClient, name: &str, arguments: Value) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": name, "arguments": arguments }),
+        )
+        .await["result"]
+        .clone()
+}
+
+fn text(result: &Value) -> String {
+    result["content"][0]["text"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_hash_family_is_consistent() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    for (algorithm, digits) in [
+        ("md5", 32),
+        ("sha1", 40),
+        ("sha256", 64),
+        ("sha512", 128),
+        ("crc32", 8),
+    ] {
+        let generic = text(
+            &call(
+                &client,
+                "hash",
+                json!({ "text": "payload", "algorithm": algorithm }),
+            )
+            .await,
+        );
+        let dedicated = text(
+            &call(
+                &client,
+                &format!("hash_{algorithm}"),
+                json!({ "text": "payload" }),
+            )
+            .await,
+        );
+        assert_eq!(generic, dedicated, "{algorithm}");
+        assert_eq!(generic.len(), digits, "{algorithm}");
+    }
+
+    // An algorithm outside the enum fails argument validation
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": "hash", "arguments": { "text": "payload", "algorithm": "sha3" } }),
+        )
+        .await;
+    assert_eq!(response["error"]["code"], -32602, "{response}");
+
+    let first = call(&client, "hmac_sha256", json!({ "key": "k1", "text": "m" })).await;
+    let second = call(&client, "hmac_sha256", json!({ "key": "k2", "text": "m" })).await;
+    assert_eq!(text(&first).len(), 64);
+    assert_ne!(text(&first), text(&second));
+}
+
+#[tokio::test]
+async fn test_hash_algorithm_is_advertised_as_enum() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let tools = client.request("tools/list", json!({})).await;
+    let tool = tools["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["name"] == "hash")
+        .unwrap()
+        .clone();
+    let schema = &tool["inputSchema"];
+    assert_eq!(schema["required"], json!(["text", "algorithm"]));
+    let variants = schema["$defs"]["HashAlgorithm"]["oneOf"]
+        .as_array()
+        .unwrap();
+    assert_eq!(variants.len(), 5);
+}
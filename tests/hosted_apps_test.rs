@@ -0,0 +1,86 @@
+//! Hosted MCP Apps: `GET /apps/{app}` serves the `ui://` app HTML with a
+//! `Content-Security-Policy` header, and `_meta.ui.csp` declares the same
+//! policy over MCP.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use mcp_test_server::resources::ui_csp::{CDN_ORIGIN, UiCsp};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_hosted_apps_send_csp_header() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .ui_csp(UiCsp::Cdn)
+        .hosted_apps(true)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let http = common::test_client();
+
+    let response = http
+        .get(format!("{}/apps/dashboard", server.base_url()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(
+        response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/html")
+    );
+    let csp = response.headers()["content-security-policy"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert_eq!(csp, UiCsp::Cdn.header());
+    assert!(csp.contains(CDN_ORIGIN));
+    assert!(response.text().await.unwrap().contains("McpApp"));
+
+    let strict = http
+        .get(format!("{}/apps/dashboard?csp=strict", server.base_url()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        strict.headers()["content-security-policy"],
+        UiCsp::Strict.header().as_str()
+    );
+
+    for (path, status) in [("/apps/nope", 404), ("/apps/dashboard?csp=open", 400)] {
+        let response = http
+            .get(format!("{}{path}", server.base_url()))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), status, "{path}");
+    }
+
+    let client = McpClient::connect(&server).await;
+    let read = client
+        .request(
+            "resources/read",
+            json!({ "uri": "ui://dashboard/app.html" }),
+        )
+        .await;
+    assert_eq!(
+        read["result"]["contents"][0]["_meta"]["ui"]["csp"]["resourceDomains"],
+        json!([CDN_ORIGIN])
+    );
+}
+
+#[tokio::test]
+async fn test_hosted_apps_are_off_by_default() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let response = common::test_client()
+        .get(format!("{}/apps/dashboard", server.base_url()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
@@ -0,0 +1,135 @@
+//! HTTP/2: h2c with prior knowledge over plain TCP, and h2 or HTTP/1.1
+//! negotiated via ALPN with `MCP_TLS_CERT` and `MCP_TLS_KEY`.
+
+mod common;
+
+use std::path::PathBuf;
+
+use common::TestServer;
+use mcp_test_server::Config;
+use reqwest::Version;
+use serde_json::json;
+
+/// Write a self-signed certificate for `localhost` and its key to a
+/// temporary directory.
+fn self_signed(name: &str) -> (PathBuf, PathBuf) {
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let dir = std::env::temp_dir().join(format!("mcp-tls-{name}-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let cert_path = dir.join("cert.pem");
+    let key_path = dir.join("key.pem");
+    std::fs::write(&cert_path, cert.cert.pem()).unwrap();
+    std::fs::write(&key_path, cert.signing_key.serialize_pem()).unwrap();
+    (cert_path, key_path)
+}
+
+/// `POST /mcp` an `initialize` request with `client`.
+async fn initialize(client: &reqwest::Client, url: &str) -> reqwest::Response {
+    client
+        .post(url)
+        .header("Accept", "application/json, text/event-stream")
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "clientInfo": {"name": "http2-test", "version": "1.0"}
+            }
+        }))
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_h2c_prior_knowledge() {
+    common::init_test_tracing();
+    let server = TestServer::start_with_config(Config::builder().stateful(true).build()).await;
+    let client = reqwest::Client::builder()
+        .http2_prior_knowledge()
+        .build()
+        .unwrap();
+
+    let health = client.get(server.health_url()).send().await.unwrap();
+    assert_eq!(health.version(), Version::HTTP_2);
+    assert_eq!(health.status(), 200);
+
+    let response = initialize(&client, &server.mcp_url()).await;
+    assert_eq!(response.version(), Version::HTTP_2);
+    assert_eq!(response.status(), 200);
+    assert!(response.headers().contains_key("mcp-session-id"));
+    let body = response.text().await.unwrap();
+    assert!(body.contains("protocolVersion"), "{body}");
+}
+
+#[tokio::test]
+async fn test_http1_still_served_without_tls() {
+    common::init_test_tracing();
+    let server = TestServer::start().await;
+    let response = initialize(&common::test_client(), &server.mcp_url()).await;
+    assert_eq!(response.version(), Version::HTTP_11);
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn test_tls_negotiates_h2_via_alpn() {
+    common::init_test_tracing();
+    let (cert, key) = self_signed("h2");
+    let server = TestServer::start_with_config(Config::builder().tls(cert, key).build()).await;
+    assert!(server.mcp_url().starts_with("https://"));
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap();
+
+    let response = initialize(&client, &server.mcp_url()).await;
+    assert_eq!(response.version(), Version::HTTP_2);
+    assert_eq!(response.status(), 200);
+    let body = response.text().await.unwrap();
+    assert!(body.contains("protocolVersion"), "{body}");
+}
+
+#[tokio::test]
+async fn test_tls_serves_http1_clients() {
+    common::init_test_tracing();
+    let (cert, key) = self_signed("http1");
+    let server = TestServer::start_with_config(Config::builder().tls(cert, key).build()).await;
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .http1_only()
+        .build()
+        .unwrap();
+
+    let response = initialize(&client, &server.mcp_url()).await;
+    assert_eq!(response.version(), Version::HTTP_11);
+    assert_eq!(response.status(), 200);
+
+    // Plain HTTP is not answered on a TLS port
+    let plain = server.health_url().replacen("https://", "http://", 1);
+    assert!(common::test_client().get(plain).send().await.is_err());
+}
+
+#[tokio::test]
+async fn test_tls_issuer_uses_https() {
+    common::init_test_tracing();
+    let (cert, key) = self_signed("issuer");
+    let server = TestServer::start_with_config(Config::builder().tls(cert, key).build()).await;
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap();
+    let metadata: serde_json::Value = client
+        .get(format!(
+            "{}/.well-known/oauth-authorization-server",
+            server.base_url()
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(metadata["issuer"], server.base_url());
+}
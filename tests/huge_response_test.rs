@@ -0,0 +1,72 @@
+//! `huge_response` and `MCP_MAX_RESPONSE_BYTES` tests: exact-size payloads,
+//! and oversized results turned into a protocol error.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_huge_response_returns_exact_size() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": "huge_response", "arguments": { "size_bytes": 4_000_000 } }),
+        )
+        .await;
+    let text = response["result"]["content"][0]["text"].as_str().unwrap();
+    assert_eq!(text.len(), 4_000_000);
+
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": "huge_response", "arguments": { "size_bytes": 1_usize << 40 } }),
+        )
+        .await;
+    assert_eq!(response["result"]["isError"], true);
+}
+
+#[tokio::test]
+async fn test_max_response_bytes_turns_oversized_results_into_errors() {
+    common::init_test_tracing();
+
+    let config = Config::builder().max_response_bytes(1000).build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": "huge_response", "arguments": { "size_bytes": 5000 } }),
+        )
+        .await;
+    assert_eq!(response["error"]["code"], -32603);
+    assert_eq!(
+        response["error"]["data"],
+        json!({ "size": 5000, "limit": 1000 })
+    );
+
+    // Any tool's result is capped, measured as serialized JSON
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": "large_response", "arguments": { "size_bytes": 990 } }),
+        )
+        .await;
+    assert_eq!(response["error"]["code"], -32603);
+    assert!(response["error"]["data"]["size"].as_u64().unwrap() > 1000);
+
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": "echo", "arguments": { "text": "small" } }),
+        )
+        .await;
+    assert_eq!(response["result"]["content"][0]["text"], "small");
+}
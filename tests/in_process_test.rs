@@ -0,0 +1,108 @@
+//! In-process transport tests: JSON-RPC over an in-memory pipe, no TCP.
+
+use mcp_test_server::{Config, McpTestServer};
+use serde_json::{Value, json};
+use tokio::io::{
+    AsyncBufReadExt, AsyncWriteExt, BufReader, DuplexStream, Lines, ReadHalf, WriteHalf,
+};
+
+/// Newline-delimited JSON-RPC over the client end of the pipe.
+struct PipeClient {
+    lines: Lines<BufReader<ReadHalf<DuplexStream>>>,
+    writer: WriteHalf<DuplexStream>,
+}
+
+impl PipeClient {
+    async fn connect(server: &McpTestServer) -> Self {
+        let (reader, writer) = tokio::io::split(server.connect_in_process());
+        let mut client = Self {
+            lines: BufReader::new(reader).lines(),
+            writer,
+        };
+        let init = client
+            .request(
+                0,
+                "initialize",
+                json!({
+                    "protocolVersion": "2025-06-18",
+                    "capabilities": {},
+                    "clientInfo": { "name": "pipe-client", "version": "0.0.0" }
+                }),
+            )
+            .await;
+        assert_eq!(init["result"]["serverInfo"]["name"], "mcp-test-server");
+        client
+            .send(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+            .await;
+        client
+    }
+
+    async fn send(&mut self, message: &Value) {
+        let mut line = message.to_string();
+        line.push('\n');
+        self.writer.write_all(line.as_bytes()).await.unwrap();
+    }
+
+    async fn request(&mut self, id: u64, method: &str, params: Value) -> Value {
+        self.send(&json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+            .await;
+        loop {
+            let line = self.lines.next_line().await.unwrap().expect("pipe closed");
+            let message: Value = serde_json::from_str(&line).unwrap();
+            if message["id"] == id {
+                return message;
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_in_process_tool_call() {
+    let server = McpTestServer::new(Config::default());
+    let mut client = PipeClient::connect(&server).await;
+
+    let tools = client.request(1, "tools/list", json!({})).await;
+    assert!(tools["result"]["tools"].as_array().unwrap().len() > 1);
+
+    let echo = client
+        .request(
+            2,
+            "tools/call",
+            json!({ "name": "echo", "arguments": { "text": "no tcp" } }),
+        )
+        .await;
+    assert_eq!(echo["result"]["content"][0]["text"], "no tcp");
+}
+
+#[tokio::test]
+async fn test_in_process_many_interactions() {
+    let server = McpTestServer::new(Config::default());
+    let mut client = PipeClient::connect(&server).await;
+
+    for id in 1..=2000 {
+        let response = client
+            .request(
+                id,
+                "tools/call",
+                json!({ "name": "add", "arguments": { "a": id, "b": 1 } }),
+            )
+            .await;
+        assert_eq!(
+            response["result"]["isError"], false,
+            "call {id}: {response}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_in_process_connections_are_independent() {
+    let server = McpTestServer::new(Config::builder().api_key("ignored-in-process").build());
+    let mut first = PipeClient::connect(&server).await;
+    let mut second = PipeClient::connect(&server).await;
+    first.writer.shutdown().await.unwrap();
+
+    let pong = second.request(1, "ping", json!({})).await;
+    assert_eq!(pong["result"], json!({}));
+    // The server hangs up once the first client closes its side
+    assert!(first.lines.next_line().await.unwrap().is_none());
+}
@@ -0,0 +1,73 @@
+//! `json_query` tests: `JSONPath` matches as structured content, and parse
+//! errors as tool errors.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::{Value, json};
+
+async fn query(client: &McpClient, document: &Value, path: &str) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({
+                "name": "json_query",
+                "arguments": { "document": document, "path": path },
+            }),
+        )
+        .await["result"]
+        .clone()
+}
+
+#[tokio::test]
+async fn test_json_query_structured_matches() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let inventory = json!({
+        "warehouses": [
+            { "name": "north", "items": [{ "sku": "a", "qty": 0 }, { "sku": "b", "qty": 12 }] },
+            { "name": "south", "items": [{ "sku": "c", "qty": 3 }] },
+        ]
+    });
+    let result = query(&client, &inventory, "$.warehouses[*].items[?@.qty > 0].sku").await;
+    assert_eq!(
+        result["structuredContent"],
+        json!({
+            "matches": [
+                { "path": "$['warehouses'][0]['items'][1]['sku']", "value": "b" },
+                { "path": "$['warehouses'][1]['items'][0]['sku']", "value": "c" },
+            ],
+            "count": 2,
+        })
+    );
+
+    let result = query(&client, &inventory, "$..nothing").await;
+    assert_eq!(result["structuredContent"]["count"], 0);
+
+    let tools = client.request("tools/list", json!({})).await;
+    let tool = tools["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["name"] == "json_query")
+        .unwrap()
+        .clone();
+    assert_eq!(tool["outputSchema"]["type"], "object");
+}
+
+#[tokio::test]
+async fn test_invalid_path_is_a_tool_error() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let result = query(&client, &json!({ "a": 1 }), "$.a[?").await;
+    assert_eq!(result["isError"], true);
+    let text = result["content"][0]["text"].as_str().unwrap();
+    assert!(text.starts_with("Invalid JSONPath:"), "{text}");
+    assert!(text.contains("position"), "{text}");
+}
@@ -0,0 +1,132 @@
+//! Key-value tool tests: per-session state, isolation, and dry runs.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::{Value, json};
+
+async fn call(client: &McpClient, name: &str, arguments: Value) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": name, "arguments": arguments }),
+        )
+        .await["result"]
+        .clone()
+}
+
+fn text(result: &Value) -> &str {
+    result["content"][0]["text"].as_str().unwrap()
+}
+
+#[tokio::test]
+async fn test_kv_sequencing_and_session_isolation() {
+    common::init_test_tracing();
+
+    let config = mcp_test_server::Config::builder().stateful(true).build();
+    let server = TestServer::start_with_config(config).await;
+    let alice = McpClient::connect(&server).await;
+    let bob = McpClient::connect(&server).await;
+
+    let set = call(&alice, "kv_set", json!({ "key": "user:1", "value": "a" })).await;
+    assert_eq!(
+        serde_json::from_str::<Value>(text(&set)).unwrap(),
+        json!({ "key": "user:1", "value": "a", "previous": null })
+    );
+    let set = call(&alice, "kv_set", json!({ "key": "user:1", "value": "b" })).await;
+    assert_eq!(
+        serde_json::from_str::<Value>(text(&set)).unwrap()["previous"],
+        "a"
+    );
+    call(&alice, "kv_set", json!({ "key": "order:1", "value": "c" })).await;
+
+    assert_eq!(
+        text(&call(&alice, "kv_get", json!({ "key": "user:1" })).await),
+        "b"
+    );
+    let list = call(&alice, "kv_list", json!({ "prefix": "user:" })).await;
+    assert_eq!(
+        serde_json::from_str::<Value>(text(&list)).unwrap(),
+        json!({ "count": 1, "entries": { "user:1": "b" } })
+    );
+
+    // Bob's session sees none of Alice's keys
+    let missing = call(&bob, "kv_get", json!({ "key": "user:1" })).await;
+    assert_eq!(missing["isError"], true);
+    assert_eq!(text(&missing), "Key not found: user:1");
+
+    let deleted = call(&alice, "kv_delete", json!({ "key": "user:1" })).await;
+    assert_eq!(
+        serde_json::from_str::<Value>(text(&deleted)).unwrap(),
+        json!({ "key": "user:1", "deleted": true, "previous": "b" })
+    );
+    let list = call(&alice, "kv_list", json!({})).await;
+    assert_eq!(
+        serde_json::from_str::<Value>(text(&list)).unwrap()["count"],
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_kv_dry_run_leaves_state_untouched() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+    call(&client, "kv_set", json!({ "key": "k", "value": "old" })).await;
+
+    for (name, arguments) in [
+        ("kv_set", json!({ "key": "k", "value": "new" })),
+        ("kv_delete", json!({ "key": "k" })),
+    ] {
+        let result = client
+            .request(
+                "tools/call",
+                json!({ "name": name, "arguments": arguments, "_meta": { "dryRun": true } }),
+            )
+            .await["result"]
+            .clone();
+        assert_eq!(result["_meta"]["dryRun"], true, "{name}");
+        assert_eq!(result["structuredContent"]["changes"]["previous"], "old");
+    }
+
+    assert_eq!(
+        text(&call(&client, "kv_get", json!({ "key": "k" })).await),
+        "old"
+    );
+}
+
+#[tokio::test]
+async fn test_admin_batch_preloads_session_state() {
+    common::init_test_tracing();
+
+    let config = mcp_test_server::Config::builder().stateful(true).build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+    let other = McpClient::connect(&server).await;
+
+    let body = json!({
+        "session": client.session_id().unwrap(),
+        "calls": [
+            { "name": "kv_set", "arguments": { "key": "fixture", "value": "preloaded" } },
+            { "name": "counter_increment", "arguments": {} }
+        ]
+    });
+    let response: Value = reqwest::Client::new()
+        .post(format!("{}/admin/tools/batch", server.base_url()))
+        .json(&body)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(response["succeeded"], 2);
+
+    assert_eq!(
+        text(&call(&client, "kv_get", json!({ "key": "fixture" })).await),
+        "preloaded"
+    );
+    let missing = call(&other, "kv_get", json!({ "key": "fixture" })).await;
+    assert_eq!(missing["isError"], true);
+}
@@ -19,6 +19,21 @@ async fn test_server_starts_and_responds_to_health_check() {
     assert_eq!(body["status"], "ok");
 }
 
+#[tokio::test]
+async fn test_readyz_reports_ready() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_auth("secret").await;
+    let response = reqwest::get(format!("{}/readyz", server.base_url()))
+        .await
+        .unwrap();
+
+    // Public like /health, so container wait strategies need no key
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "ready");
+}
+
 #[tokio::test]
 async fn test_server_handles_multiple_concurrent_health_checks() {
     common::init_test_tracing();
@@ -72,11 +87,531 @@ async fn test_server_shutdown_is_clean() {
     let response = client.get(server.health_url()).send().await.unwrap();
     assert_eq!(response.status(), reqwest::StatusCode::OK);
 
-    // Drop server (triggers shutdown)
-    drop(server);
+    let health_url = server.health_url();
+    server.shutdown().await;
+
+    // Stopped, not just signalled
+    assert!(client.get(health_url).send().await.is_err());
+}
+
+#[tokio::test]
+async fn test_client_write_half_close_is_counted() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+
+    // Send an incomplete request, then close our write side but keep reading
+    let mut stream = tokio::net::TcpStream::connect(server.addr).await.unwrap();
+    stream
+        .write_all(b"GET /health HTTP/1.1\r\nHost: localhost\r\n")
+        .await
+        .unwrap();
+    stream.shutdown().await.unwrap();
+    let mut rest = Vec::new();
+    let _ = tokio::time::timeout(
+        std::time::Duration::from_secs(2),
+        stream.read_to_end(&mut rest),
+    )
+    .await;
+
+    let stats: serde_json::Value = reqwest::get(format!("{}/debug/connections", server.base_url()))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(stats["client_write_half_closed"], 1);
+    assert!(stats["accepted"].as_u64().unwrap() >= 2);
+}
+
+#[tokio::test]
+async fn test_client_write_half_close_after_complete_request_is_answered() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+
+    // Send a whole request, then close our write side before the answer
+    let mut stream = tokio::net::TcpStream::connect(server.addr).await.unwrap();
+    stream
+        .write_all(b"GET /health HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+    stream.shutdown().await.unwrap();
+    let mut response = Vec::new();
+    tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        stream.read_to_end(&mut response),
+    )
+    .await
+    .expect("server closes the connection after answering")
+    .unwrap();
+
+    let response = String::from_utf8(response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+    assert!(response.ends_with(r#"{"status":"ok"}"#), "{response}");
+}
+
+#[tokio::test]
+async fn test_duplicate_requests_reported() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = common::McpClient::connect(&server).await;
+
+    let call = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 42,
+        "method": "tools/call",
+        "params": { "name": "noop", "arguments": {} }
+    });
+    // Simulate a retry: the identical body sent twice
+    client.post(&call).await;
+    client.post(&call).await;
+    client.request("tools/list", serde_json::json!({})).await;
+
+    let url = format!("{}/debug/duplicates", server.base_url());
+    let report: serde_json::Value = reqwest::get(&url).await.unwrap().json().await.unwrap();
+    assert_eq!(report["total_duplicates"], 1);
+    let duplicates = &report["sessions"][0]["duplicates"];
+    assert_eq!(duplicates.as_array().unwrap().len(), 1);
+    assert_eq!(duplicates[0]["count"], 2);
+    assert_eq!(duplicates[0]["method"], "tools/call");
+    assert_eq!(duplicates[0]["id"], 42);
+
+    let cleared = reqwest::Client::new().delete(&url).send().await.unwrap();
+    assert_eq!(cleared.status(), reqwest::StatusCode::NO_CONTENT);
+    let report: serde_json::Value = reqwest::get(&url).await.unwrap().json().await.unwrap();
+    assert_eq!(report["total_duplicates"], 0);
+}
+
+#[tokio::test]
+async fn test_duplicates_ignore_lifecycle_of_stateless_clients() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    // Both clients send identical initialize bodies under the `default` key
+    let _first = common::McpClient::connect(&server).await;
+    let _second = common::McpClient::connect(&server).await;
+
+    let url = format!("{}/debug/duplicates", server.base_url());
+    let report: serde_json::Value = reqwest::get(&url).await.unwrap().json().await.unwrap();
+    assert_eq!(report["total_duplicates"], 0, "{report}");
+    assert_eq!(report["sessions"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn test_admin_batch_runs_tools_without_client_traffic() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = reqwest::Client::new();
+    let call = serde_json::json!({ "name": "noop", "arguments": {} });
+    let body = serde_json::json!({ "calls": [call, call] });
+
+    let response: serde_json::Value = client
+        .post(format!("{}/admin/tools/batch", server.base_url()))
+        .json(&body)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(response["succeeded"], 2);
+    assert_eq!(response["results"][0]["ok"], true);
+
+    // Admin calls bypass the /mcp endpoint, so nothing is logged as a retry
+    let report: serde_json::Value = reqwest::get(format!("{}/debug/duplicates", server.base_url()))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(report["total_duplicates"], 0);
+}
+
+#[tokio::test]
+async fn test_admin_sessions_list_limit_and_terminate() {
+    common::init_test_tracing();
+
+    let config = mcp_test_server::Config::builder()
+        .stateful(true)
+        .max_sessions(1)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = common::McpClient::connect(&server).await;
+    let session = client.session_id().unwrap().to_string();
+    client.request("tools/list", serde_json::json!({})).await;
+
+    let sessions_url = format!("{}/admin/sessions", server.base_url());
+    let list: serde_json::Value = reqwest::get(&sessions_url)
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(list["max_sessions"], 1);
+    assert_eq!(list["sessions"][0]["id"], session.as_str());
+    // initialize, notifications/initialized, tools/list
+    assert_eq!(list["sessions"][0]["requests"], 3);
+
+    // A second session is over the limit
+    let rejected = common::test_client()
+        .post(server.mcp_url())
+        .header("Accept", "application/json, text/event-stream")
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "clientInfo": { "name": "second", "version": "0.0.0" }
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(rejected.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+    let body: serde_json::Value = rejected.json().await.unwrap();
+    assert_eq!(body["error"]["code"], -32000);
+
+    let admin = reqwest::Client::new();
+    let terminated = admin
+        .delete(format!("{sessions_url}/{session}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(terminated.status(), reqwest::StatusCode::NO_CONTENT);
+
+    // The terminated session is gone for its client
+    let response = client
+        .post(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 99,
+            "method": "tools/list",
+        }))
+        .await;
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    let again = admin
+        .delete(format!("{sessions_url}/{session}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(again.status(), reqwest::StatusCode::NOT_FOUND);
+
+    // The slot is free again
+    let replacement = common::McpClient::connect(&server).await;
+    assert_ne!(replacement.session_id(), Some(session.as_str()));
+}
+
+#[tokio::test]
+async fn test_idle_session_expires_with_404() {
+    common::init_test_tracing();
+
+    let config = mcp_test_server::Config::builder()
+        .stateful(true)
+        .session_ttl(std::time::Duration::from_millis(300))
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = common::McpClient::connect(&server).await;
+    let list = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list" });
+
+    // Activity keeps the session alive
+    tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+    assert!(client.post(&list).await.status().is_success());
+    tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+    assert!(client.post(&list).await.status().is_success());
+
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    let response = client.post(&list).await;
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    let sessions: serde_json::Value = reqwest::get(format!("{}/admin/sessions", server.base_url()))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(sessions["sessions"], serde_json::json!([]));
+    assert_eq!(sessions["session_ttl_ms"], 300);
+}
+
+async fn session_events(server: &TestServer) -> Vec<serde_json::Value> {
+    let log: serde_json::Value =
+        reqwest::get(format!("{}/admin/sessions/events", server.base_url()))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+    log["events"].as_array().unwrap().clone()
+}
+
+#[tokio::test]
+async fn test_client_delete_terminates_session() {
+    common::init_test_tracing();
+
+    let config = mcp_test_server::Config::builder().stateful(true).build();
+    let server = TestServer::start_with_config(config).await;
+    let client = common::McpClient::connect(&server).await;
+    let session = client.session_id().unwrap().to_string();
+
+    assert!(client.delete().await.status().is_success());
+    let response = client
+        .post(&serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list" }))
+        .await;
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    let events = session_events(&server).await;
+    let kinds: Vec<&str> = events.iter().map(|e| e["kind"].as_str().unwrap()).collect();
+    assert_eq!(kinds, ["created", "deleted"]);
+    assert!(events.iter().all(|e| e["session"] == session.as_str()));
+    assert_eq!(events[1]["seq"], 2);
+}
+
+#[tokio::test]
+async fn test_denied_delete_returns_405_and_keeps_session() {
+    common::init_test_tracing();
+
+    let config = mcp_test_server::Config::builder()
+        .stateful(true)
+        .deny_session_delete(true)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = common::McpClient::connect(&server).await;
+
+    let refused = client.delete().await;
+    assert_eq!(refused.status(), reqwest::StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(refused.headers()["allow"], "GET, POST");
+
+    // The session survives the refused termination
+    let list = client.request("tools/list", serde_json::json!({})).await;
+    assert!(list["result"]["tools"].is_array());
+
+    let events = session_events(&server).await;
+    assert_eq!(events.last().unwrap()["kind"], "delete_refused");
+
+    let cleared = reqwest::Client::new()
+        .delete(format!("{}/admin/sessions/events", server.base_url()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(cleared.status(), reqwest::StatusCode::NO_CONTENT);
+    assert!(session_events(&server).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_drain_refuses_new_sessions_and_serves_live_ones() {
+    common::init_test_tracing();
+
+    let config = mcp_test_server::Config::builder()
+        .stateful(true)
+        .drain_period(std::time::Duration::from_secs(1))
+        .build();
+    let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+    let server = TestServer::start_until(config, async {
+        let _ = stopped.await;
+    })
+    .await;
+    let client = common::McpClient::connect(&server).await;
+    let mut stream = client.open_stream().await;
+
+    stop.send(()).unwrap();
+    let notice = tokio::time::timeout(std::time::Duration::from_secs(2), stream.next_message())
+        .await
+        .expect("no shutdown notice")
+        .unwrap();
+    assert_eq!(notice["method"], "notifications/message");
+    assert_eq!(notice["params"]["level"], "warning");
+    assert_eq!(
+        notice["params"]["data"],
+        serde_json::json!({ "event": "shutdown", "drain_secs": 1 })
+    );
+
+    // New sessions are refused, the live one is still served
+    let refused = common::test_client()
+        .post(server.mcp_url())
+        .header("Accept", "application/json, text/event-stream")
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "clientInfo": { "name": "late", "version": "0.0.0" }
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(refused.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+    let body: serde_json::Value = refused.json().await.unwrap();
+    assert_eq!(body["error"]["data"]["draining"], true);
+    let list = client.request("tools/list", serde_json::json!({})).await;
+    assert!(list["result"]["tools"].is_array());
+    let ready = reqwest::get(format!("{}/readyz", server.base_url()))
+        .await
+        .unwrap();
+    assert_eq!(ready.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+
+    // After the drain period the server stops
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+    assert!(reqwest::get(server.health_url()).await.is_err());
+}
+
+#[tokio::test]
+async fn test_drain_without_sessions_keeps_serving() {
+    common::init_test_tracing();
 
-    // Give it a moment to shut down
+    let config = mcp_test_server::Config::builder()
+        .drain_period(std::time::Duration::from_secs(1))
+        .build();
+    let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+    let server = TestServer::start_until(config, async {
+        let _ = stopped.await;
+    })
+    .await;
+    stop.send(()).unwrap();
     tokio::time::sleep(std::time::Duration::from_millis(100)).await;
 
-    // Test passes if we get here without panics
+    // Stateless requests open no session, so draining refuses none of them
+    let client = common::McpClient::connect(&server).await;
+    let list = client.request("tools/list", serde_json::json!({})).await;
+    assert!(list["result"]["tools"].is_array());
+    let ready = reqwest::get(format!("{}/readyz", server.base_url()))
+        .await
+        .unwrap();
+    assert_eq!(ready.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn test_port_zero_reports_bound_address_when_ready() {
+    common::init_test_tracing();
+
+    let config = mcp_test_server::Config::builder()
+        .host("127.0.0.1".parse().unwrap())
+        .port(0)
+        .build();
+    let server = mcp_test_server::McpTestServer::new(config);
+    let listener = server.bind().await.unwrap();
+    let bound = listener.local_addr().unwrap();
+    assert_ne!(bound.port(), 0);
+    assert_eq!(server.local_addr(), None);
+
+    let running = server.clone();
+    let handle = tokio::spawn(async move {
+        running
+            .run_with_listener(listener, std::future::pending())
+            .await
+    });
+    assert_eq!(server.ready().await, bound);
+    assert_eq!(server.local_addr(), Some(bound));
+
+    // Ready means serving: no retry or sleep needed
+    let health = reqwest::get(format!("http://{bound}/health"))
+        .await
+        .unwrap();
+    assert!(health.status().is_success());
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_spawned_server_shuts_down_cleanly() {
+    common::init_test_tracing();
+
+    let config = mcp_test_server::Config::builder()
+        .host("127.0.0.1".parse().unwrap())
+        .port(0)
+        .stateful(true)
+        .build();
+    let handle = mcp_test_server::McpTestServer::new(config)
+        .spawn()
+        .await
+        .unwrap();
+    let addr = handle.addr();
+    assert_eq!(handle.mcp_url(), format!("http://{addr}/mcp"));
+    let health = reqwest::get(format!("http://{addr}/health")).await.unwrap();
+    assert!(health.status().is_success());
+
+    // An open SSE stream must not hold up shutdown
+    let open = common::test_client()
+        .post(handle.mcp_url())
+        .header("Accept", "application/json, text/event-stream")
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "clientInfo": { "name": "stream", "version": "0.0.0" }
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    let session = open.headers()["mcp-session-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let _stream = common::test_client()
+        .get(handle.mcp_url())
+        .header("Accept", "text/event-stream")
+        .header("mcp-session-id", session)
+        .send()
+        .await
+        .unwrap();
+
+    tokio::time::timeout(std::time::Duration::from_secs(5), handle.shutdown())
+        .await
+        .expect("shutdown timed out")
+        .unwrap();
+    assert!(reqwest::get(format!("http://{addr}/health")).await.is_err());
+}
+
+#[tokio::test]
+async fn test_initialize_result_follows_overrides() {
+    common::init_test_tracing();
+
+    let config = mcp_test_server::Config::builder()
+        .instructions("")
+        .server_info("shaped-server", "", "9.9.9")
+        .capabilities([mcp_test_server::advertise::Capability::Resources])
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let response = common::test_client()
+        .post(server.mcp_url())
+        .header("Accept", "application/json, text/event-stream")
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "clientInfo": { "name": "shape-check", "version": "1.0.0" }
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    let init = common::parse_sse_messages(&response.text().await.unwrap());
+    let result = &init[0]["result"];
+
+    assert_eq!(result["serverInfo"]["name"], "shaped-server");
+    assert_eq!(result["serverInfo"]["version"], "9.9.9");
+    assert!(result["serverInfo"].get("title").is_none());
+    assert!(result.get("instructions").is_none());
+    // Resources without subscribe or listChanged, and nothing else
+    assert_eq!(
+        result["capabilities"],
+        serde_json::json!({ "resources": {} })
+    );
 }
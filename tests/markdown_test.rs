@@ -0,0 +1,64 @@
+//! `markdown_to_html` tests: HTML output over MCP and script-injection
+//! attempts stripped by the sanitizer.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::{Value, json};
+
+async fn render(client: &McpClient, arguments: Value) -> String {
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": "markdown_to_html", "arguments": arguments }),
+        )
+        .await;
+    assert_ne!(response["result"]["isError"], true, "{response}");
+    response["result"]["content"][0]["text"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn test_markdown_renders_to_html() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let html = render(
+        &client,
+        json!({ "markdown": "## Report\n\n- [x] done\n- [ ] todo\n\n`code` and [docs](https://example.com)\n" }),
+    )
+    .await;
+    assert!(html.contains("<h2>Report</h2>"), "{html}");
+    assert!(html.contains("<code>code</code>"), "{html}");
+    assert!(html.contains("href=\"https://example.com\""), "{html}");
+    assert!(html.contains("type=\"checkbox\""), "{html}");
+}
+
+#[tokio::test]
+async fn test_injection_attempts_are_stripped() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let attacks = [
+        "<script>fetch('/steal')</script>",
+        "<img src=\"x.png\" onerror=\"fetch('/steal')\">",
+        "[click](javascript:fetch('/steal'))",
+        "<iframe src=\"https://evil.test\"></iframe>",
+        "<div style=\"background:url(javascript:fetch('/steal'))\">x</div>",
+    ];
+    for attack in attacks {
+        let html = render(&client, json!({ "markdown": attack })).await;
+        for needle in ["<script", "onerror", "javascript:", "<iframe", "/steal"] {
+            assert!(!html.contains(needle), "{attack} rendered {html}");
+        }
+
+        let raw = render(&client, json!({ "markdown": attack, "sanitize": false })).await;
+        assert!(raw.contains("/steal") || raw.contains("evil.test"), "{raw}");
+    }
+}
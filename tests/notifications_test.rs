@@ -0,0 +1,280 @@
+//! Server-to-client notification tests over the Streamable HTTP transport.
+
+mod common;
+
+use std::time::Duration;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+
+#[tokio::test]
+async fn test_ticker_notifications_are_sequential() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .stateful(true)
+        .ticker_interval_ms(50)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+    assert!(client.session_id().is_some());
+
+    let mut stream = client.open_stream().await;
+    let response = client
+        .request(
+            "resources/subscribe",
+            serde_json::json!({ "uri": "test://dynamic/ticker" }),
+        )
+        .await;
+    assert!(
+        response.get("error").is_none(),
+        "subscribe failed: {response}"
+    );
+
+    let mut seqs = Vec::new();
+    while seqs.len() < 3 {
+        let message = tokio::time::timeout(Duration::from_secs(5), stream.next_message())
+            .await
+            .expect("Timed out waiting for ticker notification")
+            .expect("SSE stream ended");
+        if message["method"] != "notifications/resources/updated" {
+            continue;
+        }
+        assert_eq!(message["params"]["uri"], "test://dynamic/ticker");
+        let meta = &message["params"]["_meta"];
+        assert_eq!(meta["interval_ms"], 50);
+        assert!(meta["timestamp"].is_string());
+        seqs.push(meta["seq"].as_u64().unwrap());
+    }
+    assert_eq!(seqs[1], seqs[0] + 1);
+    assert_eq!(seqs[2], seqs[1] + 1);
+
+    let response = client
+        .request(
+            "resources/unsubscribe",
+            serde_json::json!({ "uri": "test://dynamic/ticker" }),
+        )
+        .await;
+    assert!(response.get("error").is_none());
+}
+
+#[tokio::test]
+async fn test_ticker_subscriptions_are_per_session() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .stateful(true)
+        .ticker_interval_ms(50)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let first = McpClient::connect(&server).await;
+    let second = McpClient::connect(&server).await;
+    let mut second_stream = second.open_stream().await;
+    let subscribe = serde_json::json!({ "uri": "test://dynamic/ticker" });
+    for client in [&first, &second] {
+        let response = client
+            .request("resources/subscribe", subscribe.clone())
+            .await;
+        assert!(response.get("error").is_none(), "{response}");
+    }
+
+    // One client unsubscribing leaves the other's ticks running
+    let response = first.request("resources/unsubscribe", subscribe).await;
+    assert!(response.get("error").is_none(), "{response}");
+    let mut ticks = 0;
+    while ticks < 3 {
+        let message = tokio::time::timeout(Duration::from_secs(5), second_stream.next_message())
+            .await
+            .expect("Timed out waiting for ticker notification")
+            .expect("SSE stream ended");
+        if message["method"] == "notifications/resources/updated" {
+            ticks += 1;
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_stateless_ticker_subscribe_rejected() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let subscribe = serde_json::json!({ "uri": "test://dynamic/ticker" });
+    for client in [
+        McpClient::connect(&server).await,
+        McpClient::connect(&server).await,
+    ] {
+        let response = client
+            .request("resources/subscribe", subscribe.clone())
+            .await;
+        assert_eq!(response["error"]["code"], -32600, "{response}");
+        assert!(
+            response["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("MCP_STATEFUL")
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_ticker_read_reports_current_tick() {
+    common::init_test_tracing();
+
+    let server =
+        TestServer::start_with_config(Config::builder().ticker_interval_ms(20).build()).await;
+    let client = McpClient::connect(&server).await;
+
+    let response = client
+        .request(
+            "resources/read",
+            serde_json::json!({ "uri": "test://dynamic/ticker" }),
+        )
+        .await;
+    let text = response["result"]["contents"][0]["text"].as_str().unwrap();
+    let tick: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(tick["interval_ms"], 20);
+    assert!(tick["seq"].is_u64());
+}
+
+#[tokio::test]
+async fn test_flag_changes_notify_subscribers() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_config(Config::builder().stateful(true).build()).await;
+    let client = McpClient::connect(&server).await;
+    let mut stream = client.open_stream().await;
+    let response = client
+        .request(
+            "resources/subscribe",
+            serde_json::json!({ "uri": "test://meta/flags" }),
+        )
+        .await;
+    assert!(
+        response.get("error").is_none(),
+        "subscribe failed: {response}"
+    );
+
+    let flags_url = format!("{}/admin/flags", server.base_url());
+    let admin = reqwest::Client::new();
+    let updated: serde_json::Value = admin
+        .patch(&flags_url)
+        .json(&serde_json::json!({ "chaos": true, "profile": "slow" }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(updated["revision"], 1);
+    assert_eq!(updated["chaos"], true);
+
+    let message = loop {
+        let message = tokio::time::timeout(Duration::from_secs(5), stream.next_message())
+            .await
+            .expect("Timed out waiting for flags notification")
+            .expect("SSE stream ended");
+        if message["method"] == "notifications/resources/updated" {
+            break message;
+        }
+    };
+    assert_eq!(message["params"]["uri"], "test://meta/flags");
+    assert_eq!(message["params"]["_meta"]["revision"], 1);
+
+    let response = client
+        .request(
+            "resources/read",
+            serde_json::json!({ "uri": "test://meta/flags" }),
+        )
+        .await;
+    let text = response["result"]["contents"][0]["text"].as_str().unwrap();
+    let flags: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(flags, updated);
+
+    let rejected = admin
+        .patch(&flags_url)
+        .json(&serde_json::json!({ "no_such_flag": 1 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(rejected.status(), reqwest::StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_counter_reads_notify_subscribers_across_sessions() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_config(Config::builder().stateful(true).build()).await;
+    let watcher = McpClient::connect(&server).await;
+    let reader = McpClient::connect(&server).await;
+    let mut stream = watcher.open_stream().await;
+    let uri = serde_json::json!({ "uri": "test://dynamic/counter" });
+    let response = watcher.request("resources/subscribe", uri.clone()).await;
+    assert!(
+        response.get("error").is_none(),
+        "subscribe failed: {response}"
+    );
+
+    let read = reader.request("resources/read", uri.clone()).await;
+    assert_eq!(read["result"]["contents"][0]["text"], "Counter value: 1");
+
+    let message = loop {
+        let message = tokio::time::timeout(Duration::from_secs(5), stream.next_message())
+            .await
+            .expect("Timed out waiting for counter notification")
+            .expect("SSE stream ended");
+        if message["method"] == "notifications/resources/updated" {
+            break message;
+        }
+    };
+    assert_eq!(message["params"]["uri"], "test://dynamic/counter");
+    assert_eq!(message["params"]["_meta"]["value"], 1);
+
+    let response = watcher.request("resources/unsubscribe", uri.clone()).await;
+    assert!(response.get("error").is_none());
+    reader.request("resources/read", uri).await;
+    let quiet = tokio::time::timeout(Duration::from_millis(300), stream.next_message()).await;
+    assert!(quiet.is_err(), "notified after unsubscribing: {quiet:?}");
+}
+
+#[tokio::test]
+async fn test_subscriptions_are_tracked_per_session() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_config(Config::builder().stateful(true).build()).await;
+    let client = McpClient::connect(&server).await;
+    let session = client.session_id().expect("stateful session").to_string();
+    let uri = serde_json::json!({ "uri": "test://meta/flags" });
+    let response = client.request("resources/subscribe", uri.clone()).await;
+    assert!(
+        response.get("error").is_none(),
+        "subscribe failed: {response}"
+    );
+
+    let duplicate = client.request("resources/subscribe", uri.clone()).await;
+    assert_eq!(duplicate["error"]["code"], -32600);
+    assert_eq!(duplicate["error"]["data"]["uri"], "test://meta/flags");
+
+    let report_url = format!("{}/admin/subscriptions", server.base_url());
+    let report: serde_json::Value = reqwest::get(&report_url)
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(report["total"], 1);
+    let subscriptions = report["sessions"][&session].as_array().unwrap();
+    assert_eq!(subscriptions.len(), 1);
+    assert_eq!(subscriptions[0]["uri"], "test://meta/flags");
+    assert!(subscriptions[0]["subscribed_at"].is_string());
+
+    let response = client.request("resources/unsubscribe", uri).await;
+    assert!(response.get("error").is_none());
+    let report: serde_json::Value = reqwest::get(&report_url)
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(report["total"], 0);
+    assert_eq!(report["sessions"], serde_json::json!({}));
+}
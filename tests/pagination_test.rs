@@ -0,0 +1,66 @@
+//! `resources/list` pagination over MCP, stressed with synthetic resources.
+
+mod common;
+
+use std::collections::HashSet;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_synthetic_resources_span_pages() {
+    common::init_test_tracing();
+
+    let config = Config::builder().synthetic_resources(1000).build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    let mut uris = HashSet::new();
+    let mut cursor: Option<String> = None;
+    let mut pages = 0;
+    loop {
+        let params = cursor
+            .as_ref()
+            .map_or_else(|| json!({}), |c| json!({ "cursor": c }));
+        let page = client.request("resources/list", params).await;
+        let resources = page["result"]["resources"].as_array().unwrap();
+        assert!(resources.len() <= 100);
+        for resource in resources {
+            assert!(uris.insert(resource["uri"].as_str().unwrap().to_string()));
+        }
+        pages += 1;
+        match page["result"]["nextCursor"].as_str() {
+            Some(next) => cursor = Some(next.to_string()),
+            None => break,
+        }
+    }
+    assert!(pages > 10, "only {pages} pages");
+    assert!(uris.contains("test://synthetic/1"));
+    assert!(uris.contains("test://synthetic/1000"));
+    assert!(!uris.contains("test://synthetic/1001"));
+
+    let read = client
+        .request("resources/read", json!({ "uri": "test://synthetic/1000" }))
+        .await;
+    assert_eq!(
+        read["result"]["contents"][0]["text"],
+        "Synthetic resource 1000 of 1000"
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_cursor_is_rejected() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let first = client.request("resources/list", json!({})).await;
+    assert!(first["result"]["nextCursor"].is_null());
+
+    let response = client
+        .request("resources/list", json!({ "cursor": "not-a-cursor" }))
+        .await;
+    assert_eq!(response["error"]["code"], -32602);
+}
@@ -0,0 +1,98 @@
+//! `panic` tool tests: caught panics become internal errors or cut the
+//! connection, per `MCP_PANIC_POLICY`, and the server keeps serving.
+
+mod common;
+
+use common::{McpClient, TestServer, parse_sse_messages};
+use mcp_test_server::Config;
+use mcp_test_server::supervision::PanicPolicy;
+use serde_json::{Value, json};
+
+fn call(id: u64, arguments: &Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "tools/call",
+        "params": { "name": "panic", "arguments": arguments },
+    })
+}
+
+/// Read a response body until it ends or the connection drops.
+async fn read_body(mut response: reqwest::Response) -> (String, bool) {
+    let mut body = String::new();
+    loop {
+        match response.chunk().await {
+            Ok(Some(chunk)) => body.push_str(&String::from_utf8_lossy(&chunk)),
+            Ok(None) => return (body, false),
+            Err(_) => return (body, true),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_panic_becomes_internal_error() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": "panic", "arguments": { "message": "boom" } }),
+        )
+        .await;
+    assert_eq!(response["error"]["code"], -32603);
+    assert_eq!(response["error"]["message"], "Tool panicked: boom");
+    assert_eq!(response["error"]["data"]["tool"], "panic");
+
+    // The server survives
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": "echo", "arguments": { "text": "still here" } }),
+        )
+        .await;
+    assert_eq!(response["result"]["content"][0]["text"], "still here");
+}
+
+#[tokio::test]
+async fn test_panic_with_abort_policy_cuts_the_stream() {
+    common::init_test_tracing();
+
+    let config = Config::builder().panic_policy(PanicPolicy::Abort).build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    let (body, aborted) = read_body(client.post(&call(1, &json!({}))).await).await;
+    assert!(aborted, "stream ended cleanly: {body}");
+    let messages = parse_sse_messages(&body);
+    assert!(messages.iter().all(|m| m.get("id").is_none()), "{body}");
+
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": "echo", "arguments": { "text": "still here" } }),
+        )
+        .await;
+    assert_eq!(response["result"]["content"][0]["text"], "still here");
+}
+
+#[tokio::test]
+async fn test_abort_connection_mode_drops_mid_response() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let response = client
+        .post(&call(2, &json!({ "mode": "abort_connection" })))
+        .await;
+    let (body, aborted) = read_body(response).await;
+    assert!(aborted, "stream ended cleanly: {body}");
+    // The log message made it out before the connection dropped
+    let messages = parse_sse_messages(&body);
+    assert_eq!(messages.len(), 1, "{body}");
+    assert_eq!(messages[0]["method"], "notifications/message");
+    assert_eq!(messages[0]["params"]["data"]["mode"], "abort_connection");
+}
@@ -0,0 +1,135 @@
+//! `MCP_SSE_KEEPALIVE_SECS` and `MCP_PING_INTERVAL` tests: keep-alive
+//! comments on idle streams and server-initiated pings.
+
+mod common;
+
+use std::time::Duration;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use serde_json::json;
+
+/// Open the standalone stream without the harness's JSON parsing.
+async fn open_raw_stream(server: &TestServer, client: &McpClient) -> reqwest::Response {
+    common::test_client()
+        .get(server.mcp_url())
+        .header("Accept", "text/event-stream")
+        .header("mcp-session-id", client.session_id().unwrap())
+        .timeout(Duration::from_secs(30))
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn ping_report(server: &TestServer) -> serde_json::Value {
+    reqwest::get(format!("{}/admin/pings", server.base_url()))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_keepalive_comments_on_idle_stream() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .stateful(true)
+        .sse_keepalive_secs(1)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+    let mut stream = open_raw_stream(&server, &client).await;
+
+    let mut received = String::new();
+    while !received.lines().any(|line| line.starts_with(':')) {
+        let chunk = tokio::time::timeout(Duration::from_secs(3), stream.chunk())
+            .await
+            .expect("no keep-alive on idle stream")
+            .unwrap()
+            .unwrap();
+        received.push_str(&String::from_utf8_lossy(&chunk));
+    }
+}
+
+#[tokio::test]
+async fn test_keepalive_disabled() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .stateful(true)
+        .sse_keepalive_secs(0)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+    let mut stream = open_raw_stream(&server, &client).await;
+
+    let mut received = String::new();
+    let _ = tokio::time::timeout(Duration::from_millis(1500), async {
+        while let Ok(Some(chunk)) = stream.chunk().await {
+            received.push_str(&String::from_utf8_lossy(&chunk));
+        }
+    })
+    .await;
+    assert!(
+        !received.lines().any(|line| line.starts_with(':')),
+        "{received:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_answered_pings_are_reported() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .stateful(true)
+        .ping_interval(Duration::from_millis(200))
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+    let mut stream = client.open_stream().await;
+
+    for _ in 0..2 {
+        let ping = tokio::time::timeout(Duration::from_secs(2), stream.next_message())
+            .await
+            .expect("no ping")
+            .unwrap();
+        assert_eq!(ping["method"], "ping");
+        let answer = client
+            .post(&json!({ "jsonrpc": "2.0", "id": ping["id"], "result": {} }))
+            .await;
+        assert!(answer.status().is_success());
+    }
+
+    let report = ping_report(&server).await;
+    assert_eq!(report["interval_ms"], 200);
+    let stats = &report["sessions"][client.session_id().unwrap()];
+    assert!(stats["answered"].as_u64().unwrap() >= 2, "{report}");
+    assert!(stats["last_rtt_ms"].is_u64());
+    assert!(stats["last_answered_at"].is_string());
+}
+
+#[tokio::test]
+async fn test_unanswered_pings_fail() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .stateful(true)
+        .ping_interval(Duration::from_millis(150))
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+    let mut stream = client.open_stream().await;
+
+    // Read the pings but never answer them
+    let ping = stream.next_message().await.unwrap();
+    assert_eq!(ping["method"], "ping");
+    tokio::time::sleep(Duration::from_millis(400)).await;
+
+    let report = ping_report(&server).await;
+    let stats = &report["sessions"][client.session_id().unwrap()];
+    assert_eq!(stats["answered"], 0, "{report}");
+    assert!(stats["failed"].as_u64().unwrap() >= 1, "{report}");
+    assert!(stats["last_error"].as_str().unwrap().contains("timeout"));
+}
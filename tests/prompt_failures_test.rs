@@ -0,0 +1,62 @@
+//! `prompts/get` failures over MCP: `flaky_prompt` fails on purpose, after a
+//! delay if asked to.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_flaky_prompt_slow_failure_waits() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let modes = client
+        .request(
+            "completion/complete",
+            json!({
+                "ref": { "type": "ref/prompt", "name": "flaky_prompt" },
+                "argument": { "name": "mode", "value": "" },
+            }),
+        )
+        .await;
+    assert_eq!(
+        modes["result"]["completion"]["values"],
+        json!(["internal_error", "invalid_params", "slow_failure"])
+    );
+
+    let started = std::time::Instant::now();
+    let failed = client
+        .request(
+            "prompts/get",
+            json!({
+                "name": "flaky_prompt",
+                "arguments": { "mode": "slow_failure", "delay_ms": 500 },
+            }),
+        )
+        .await;
+    assert!(started.elapsed() >= std::time::Duration::from_millis(500));
+    assert_eq!(failed["error"]["code"], -32603);
+    assert_eq!(failed["error"]["data"]["mode"], "slow_failure");
+}
+
+#[tokio::test]
+async fn test_flaky_prompt_modes_fail_immediately() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    for (mode, code) in [("internal_error", -32603), ("invalid_params", -32602)] {
+        let failed = client
+            .request(
+                "prompts/get",
+                json!({ "name": "flaky_prompt", "arguments": { "mode": mode } }),
+            )
+            .await;
+        assert_eq!(failed["error"]["code"], code, "{mode}: {failed}");
+        assert_eq!(failed["error"]["data"]["prompt"], "flaky_prompt");
+    }
+}
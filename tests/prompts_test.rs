@@ -2,12 +2,17 @@
 
 use std::collections::HashMap;
 
-use mcp_test_server::prompts::templates::{generate_prompt, get_all_prompts};
+use mcp_test_server::i18n::Locale;
+use mcp_test_server::prompts::templates::{
+    DEFAULT_CONVERSATION_MESSAGES, FLAKY_PROMPT_DELAY, FlakyPromptFailure,
+    MAX_CONVERSATION_MESSAGES, TONES, generate_prompt, get_all_prompts,
+};
+use mcp_test_server::prompts::{get_localized_prompt_by_name, localized::localize_prompt};
 
 #[test]
-fn test_get_all_prompts_returns_five_prompts() {
+fn test_get_all_prompts_returns_nine_prompts() {
     let prompts = get_all_prompts();
-    assert_eq!(prompts.len(), 5);
+    assert_eq!(prompts.len(), 9);
 
     let names: Vec<&str> = prompts.iter().map(|p| p.name.as_str()).collect();
     assert!(names.contains(&"greeting"));
@@ -15,6 +20,10 @@ fn test_get_all_prompts_returns_five_prompts() {
     assert!(names.contains(&"summarize"));
     assert!(names.contains(&"translate"));
     assert!(names.contains(&"with_resource"));
+    assert!(names.contains(&"tone_rewrite"));
+    assert!(names.contains(&"long_conversation"));
+    assert!(names.contains(&"greeting_localized"));
+    assert!(names.contains(&"flaky_prompt"));
 }
 
 #[test]
@@ -121,16 +130,207 @@ fn test_with_resource_prompt() {
     let args = HashMap::new();
 
     let result = generate_prompt("with_resource", &args).unwrap();
-    assert_eq!(result.len(), 2); // User message + Assistant response
+    assert_eq!(result.len(), 3); // Instruction, embedded resource, assistant response
 
+    match &result[1].content {
+        rmcp::model::PromptMessageContent::Resource { resource } => {
+            let rmcp::model::ResourceContents::TextResourceContents {
+                uri,
+                mime_type,
+                text,
+                ..
+            } = &resource.resource
+            else {
+                panic!("Expected text resource contents");
+            };
+            assert_eq!(uri, "test://static/data.json");
+            assert_eq!(mime_type.as_deref(), Some("application/json"));
+            let data: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(data["name"], "test");
+        }
+        _ => panic!("Expected embedded resource content"),
+    }
+}
+
+#[test]
+fn test_tone_rewrite_prompt() {
+    let mut args = HashMap::new();
+    args.insert("text".to_string(), "hey, ship it".to_string());
+    args.insert("tone".to_string(), "formal".to_string());
+
+    let result = generate_prompt("tone_rewrite", &args).unwrap();
     match &result[0].content {
         rmcp::model::PromptMessageContent::Text { text } => {
-            assert!(text.contains("test://static/config"));
+            assert!(text.contains("formal tone"));
+            assert!(text.contains("hey, ship it"));
         }
         _ => panic!("Expected text content"),
     }
 }
 
+#[test]
+fn test_tone_rewrite_rejects_unknown_tone() {
+    let mut args = HashMap::new();
+    args.insert("text".to_string(), "hello".to_string());
+    args.insert("tone".to_string(), "Formal".to_string());
+
+    let err = generate_prompt("tone_rewrite", &args).unwrap_err();
+    assert_eq!(err.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+    let data = err.data.unwrap();
+    assert_eq!(data["argument"], "tone");
+    assert_eq!(data["value"], "Formal");
+    assert_eq!(data["allowed"], serde_json::json!(TONES));
+}
+
+#[test]
+fn test_long_conversation_prompt() {
+    use rmcp::model::{PromptMessageContent, PromptMessageRole};
+
+    let text_len = |content: &PromptMessageContent| match content {
+        PromptMessageContent::Text { text } => text.len(),
+        _ => panic!("Expected text content"),
+    };
+
+    let result = generate_prompt("long_conversation", &HashMap::new()).unwrap();
+    assert_eq!(result.len(), DEFAULT_CONVERSATION_MESSAGES);
+    assert_eq!(result[0].role, PromptMessageRole::User);
+    assert_eq!(result[1].role, PromptMessageRole::Assistant);
+    let total: usize = result.iter().map(|m| text_len(&m.content)).sum();
+    assert!(total > 30_000, "Default conversation should be tens of KB");
+
+    let mut args = HashMap::new();
+    args.insert("messages".to_string(), "3".to_string());
+    let result = generate_prompt("long_conversation", &args).unwrap();
+    assert_eq!(result.len(), 3);
+    assert_eq!(result[2].role, PromptMessageRole::User);
+}
+
+#[test]
+fn test_long_conversation_rejects_bad_count() {
+    for value in [
+        "0",
+        "-1",
+        "many",
+        &(MAX_CONVERSATION_MESSAGES + 1).to_string(),
+    ] {
+        let mut args = HashMap::new();
+        args.insert("messages".to_string(), value.to_string());
+        assert!(
+            generate_prompt("long_conversation", &args).is_err(),
+            "messages={value} accepted"
+        );
+    }
+}
+
+#[test]
+fn test_every_prompt_is_localized() {
+    for locale in [
+        Locale::English,
+        Locale::German,
+        Locale::Japanese,
+        Locale::Arabic,
+    ] {
+        for prompt in get_all_prompts() {
+            let english = prompt.clone();
+            let localized = localize_prompt(prompt, locale);
+            assert_eq!(localized.name, english.name);
+            assert!(localized.title.is_some(), "{} has no title", english.name);
+            if locale == Locale::English {
+                assert_eq!(localized.description, english.description);
+                continue;
+            }
+            assert_ne!(localized.description, english.description);
+            let english_args = english.arguments.unwrap_or_default();
+            for (arg, original) in localized
+                .arguments
+                .unwrap_or_default()
+                .iter()
+                .zip(&english_args)
+            {
+                assert_eq!(arg.name, original.name);
+                assert_ne!(
+                    arg.description, original.description,
+                    "{}.{} not translated",
+                    english.name, arg.name
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_localized_tone_description_lists_every_tone() {
+    let prompt = get_all_prompts()
+        .into_iter()
+        .find(|p| p.name == "tone_rewrite")
+        .unwrap();
+    let prompt = localize_prompt(prompt, Locale::German);
+    let tone = &prompt.arguments.unwrap()[1];
+    for value in TONES {
+        assert!(tone.description.as_ref().unwrap().contains(value));
+    }
+}
+
+#[test]
+fn test_greeting_localized_prompt() {
+    let mut args = HashMap::new();
+    args.insert("name".to_string(), "Ada".to_string());
+
+    let greeting = |locale| {
+        let (messages, description) =
+            get_localized_prompt_by_name("greeting_localized", &args, locale).unwrap();
+        let rmcp::model::PromptMessageContent::Text { text } = &messages[0].content else {
+            panic!("Expected text content");
+        };
+        (text.clone(), description.unwrap())
+    };
+    assert_eq!(greeting(Locale::English).0, "Hello, Ada!");
+    let (text, description) = greeting(Locale::German);
+    assert_eq!(text, "Hallo, Ada!");
+    assert!(description.contains("Begrüßung"));
+    assert_eq!(greeting(Locale::Japanese).0, "こんにちは、Adaさん!");
+    assert_eq!(
+        generate_prompt("greeting_localized", &args).unwrap().len(),
+        1
+    );
+}
+
+#[test]
+fn test_flaky_prompt_fails_by_mode() {
+    use rmcp::model::ErrorCode;
+
+    let fail = |mode: Option<&str>| {
+        let mut args = HashMap::new();
+        if let Some(mode) = mode {
+            args.insert("mode".to_string(), mode.to_string());
+        }
+        generate_prompt("flaky_prompt", &args).unwrap_err()
+    };
+    let err = fail(None);
+    assert_eq!(err.code, ErrorCode::INTERNAL_ERROR);
+    assert_eq!(err.data.unwrap()["mode"], "internal_error");
+    assert_eq!(fail(Some("invalid_params")).code, ErrorCode::INVALID_PARAMS);
+    assert_eq!(fail(Some("slow_failure")).code, ErrorCode::INTERNAL_ERROR);
+    let mut args = HashMap::new();
+    args.insert("mode".to_string(), "slow_failure".to_string());
+    assert_eq!(
+        FlakyPromptFailure::from_arguments(&args).unwrap().delay(),
+        FLAKY_PROMPT_DELAY
+    );
+    let mut args = HashMap::new();
+    args.insert("mode".to_string(), "slow_failure".to_string());
+    args.insert("delay_ms".to_string(), "soon".to_string());
+    assert!(
+        generate_prompt("flaky_prompt", &args)
+            .unwrap_err()
+            .data
+            .is_none()
+    );
+    let err = fail(Some("explode"));
+    assert_eq!(err.code, ErrorCode::INVALID_PARAMS);
+    assert_eq!(err.data.unwrap()["argument"], "mode");
+}
+
 #[test]
 fn test_unknown_prompt() {
     let args = HashMap::new();
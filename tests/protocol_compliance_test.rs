@@ -236,7 +236,7 @@ mod resources_compliance {
         let handler = ResourceHandler::new();
         let request = SubscribeRequestParams::new("test://dynamic/random".to_string());
 
-        let result = handler.subscribe(&request);
+        let result = handler.subscribe(&request, "default");
         assert!(
             result.is_ok(),
             "Subscription to random resource should succeed"
@@ -249,7 +249,7 @@ mod resources_compliance {
         let handler = ResourceHandler::new();
         let request = SubscribeRequestParams::new("test://static/hello.txt".to_string());
 
-        let result = handler.subscribe(&request);
+        let result = handler.subscribe(&request, "default");
         assert!(
             result.is_err(),
             "Subscription to static resource should fail"
@@ -308,6 +308,7 @@ mod tools_compliance {
         let _ = schema_for!(utility::RandomNumberParams);
         let _ = schema_for!(utility::RandomUuidParams);
         let _ = schema_for!(utility::CurrentTimeParams);
+        let _ = schema_for!(fixtures::FixtureGenerateParams);
         let _ = schema_for!(testing::SleepParams);
         let _ = schema_for!(testing::FailParams);
         let _ = schema_for!(testing::FailWithMessageParams);
@@ -315,6 +316,7 @@ mod tools_compliance {
         let _ = schema_for!(testing::NestedDataParams);
         let _ = schema_for!(testing::LargeResponseParams);
         let _ = schema_for!(testing::BinaryDataParams);
+        let _ = schema_for!(testing::PromptAuditParams);
         let _ = schema_for!(ui::UiResourceButtonParams);
         let _ = schema_for!(ui::UiResourceFormParams);
         let _ = schema_for!(ui::UiResourceCarouselParams);
@@ -365,6 +367,12 @@ mod tools_compliance {
             "large_response",
             "binary_data",
             "noop",
+            "prompt_audit",
+            "whoami",
+            "fixture_generate",
+            "case_probe",
+            "Case_Probe",
+            "CASE_PROBE",
             "ui_resource_button",
             "ui_resource_form",
             "ui_resource_carousel",
@@ -0,0 +1,153 @@
+//! Strict `MCP-Protocol-Version` enforcement: requests after `initialize`
+//! without the negotiated revision get `400 Bad Request`.
+
+mod common;
+
+use common::TestServer;
+use mcp_test_server::Config;
+use serde_json::{Value, json};
+
+/// Initialize requesting `version`, confirm with
+/// `notifications/initialized`, and return the session ID.
+async fn initialize(server: &TestServer, version: &str) -> Option<String> {
+    let response = common::test_client()
+        .post(server.mcp_url())
+        .header("Accept", "application/json, text/event-stream")
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": version,
+                "capabilities": {},
+                "clientInfo": {"name": "header-test", "version": "1.0"}
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let session = response
+        .headers()
+        .get("mcp-session-id")
+        .map(|v| v.to_str().unwrap().to_string());
+    let _ = response.text().await;
+
+    let mut initialized = common::test_client()
+        .post(server.mcp_url())
+        .header("Accept", "application/json, text/event-stream")
+        .header("MCP-Protocol-Version", version)
+        .json(&json!({"jsonrpc": "2.0", "method": "notifications/initialized"}));
+    if let Some(session) = &session {
+        initialized = initialized.header("Mcp-Session-Id", session);
+    }
+    assert_eq!(initialized.send().await.unwrap().status(), 202);
+    session
+}
+
+/// `POST /mcp` a `tools/list` request with the given session and header.
+async fn list_tools(
+    server: &TestServer,
+    session: Option<&str>,
+    version: Option<&str>,
+) -> reqwest::Response {
+    let mut request = common::test_client()
+        .post(server.mcp_url())
+        .header("Accept", "application/json, text/event-stream")
+        .json(&json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"}));
+    if let Some(session) = session {
+        request = request.header("Mcp-Session-Id", session);
+    }
+    if let Some(version) = version {
+        request = request.header("MCP-Protocol-Version", version);
+    }
+    request.send().await.unwrap()
+}
+
+fn strict(stateful: bool) -> Config {
+    Config::builder()
+        .stateful(stateful)
+        .strict_protocol_version(true)
+        .build()
+}
+
+#[tokio::test]
+async fn test_strict_rejects_missing_and_unexpected_header() {
+    common::init_test_tracing();
+    let server = TestServer::start_with_config(strict(true)).await;
+    let session = initialize(&server, "2025-06-18").await.unwrap();
+
+    let missing = list_tools(&server, Some(&session), None).await;
+    assert_eq!(missing.status(), 400);
+    let error: Value = missing.json().await.unwrap();
+    assert_eq!(error["id"], 1);
+    assert_eq!(error["error"]["code"], -32600);
+    assert_eq!(error["error"]["data"]["expected"], "2025-06-18");
+    assert_eq!(error["error"]["data"]["received"], Value::Null);
+
+    let unexpected = list_tools(&server, Some(&session), Some("2025-03-26")).await;
+    assert_eq!(unexpected.status(), 400);
+    let error: Value = unexpected.json().await.unwrap();
+    assert_eq!(error["error"]["data"]["received"], "2025-03-26");
+    assert!(
+        error["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("negotiated 2025-06-18")
+    );
+
+    let stream = common::test_client()
+        .get(server.mcp_url())
+        .header("Accept", "text/event-stream")
+        .header("Mcp-Session-Id", &session)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(stream.status(), 400);
+
+    let ok = list_tools(&server, Some(&session), Some("2025-06-18")).await;
+    assert_eq!(ok.status(), 200);
+}
+
+#[tokio::test]
+async fn test_strict_follows_older_negotiated_revision() {
+    common::init_test_tracing();
+    let server = TestServer::start_with_config(strict(true)).await;
+    let session = initialize(&server, "2025-03-26").await.unwrap();
+
+    // 2025-03-26 predates the header, so leaving it out is allowed
+    let missing = list_tools(&server, Some(&session), None).await;
+    assert_eq!(missing.status(), 200);
+    let ok = list_tools(&server, Some(&session), Some("2025-03-26")).await;
+    assert_eq!(ok.status(), 200);
+    let newer = list_tools(&server, Some(&session), Some("2025-06-18")).await;
+    assert_eq!(newer.status(), 400);
+}
+
+#[tokio::test]
+async fn test_strict_without_sessions() {
+    common::init_test_tracing();
+    let server = TestServer::start_with_config(strict(false)).await;
+    assert_eq!(initialize(&server, "2025-06-18").await, None);
+
+    assert_eq!(list_tools(&server, None, None).await.status(), 400);
+    assert_eq!(
+        list_tools(&server, None, Some("2025-06-18")).await.status(),
+        200
+    );
+    assert_eq!(
+        list_tools(&server, None, Some("2030-01-01")).await.status(),
+        400
+    );
+}
+
+#[tokio::test]
+async fn test_header_optional_by_default() {
+    common::init_test_tracing();
+    let server = TestServer::start_with_config(Config::builder().stateful(true).build()).await;
+    let session = initialize(&server, "2025-06-18").await.unwrap();
+    assert_eq!(
+        list_tools(&server, Some(&session), None).await.status(),
+        200
+    );
+}
@@ -0,0 +1,155 @@
+//! Reverse proxies: OAuth issuer and metadata URLs derived from
+//! `MCP_PUBLIC_URL` or forwarded headers instead of the bind address.
+
+mod common;
+
+use common::TestServer;
+use mcp_test_server::Config;
+use serde_json::Value;
+
+/// `GET` the OAuth authorization server metadata with extra `headers`.
+async fn metadata(server: &TestServer, headers: &[(&str, &str)]) -> Value {
+    let mut request = common::test_client().get(format!(
+        "{}/.well-known/oauth-authorization-server",
+        server.base_url()
+    ));
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    request.send().await.unwrap().json().await.unwrap()
+}
+
+/// The `resource_metadata` URL of the Bearer challenge for `POST /mcp`.
+async fn challenge(server: &TestServer, headers: &[(&str, &str)]) -> String {
+    let mut request = common::test_client().post(server.mcp_url()).body("{}");
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let response = request.send().await.unwrap();
+    assert_eq!(response.status(), 401);
+    response.headers()["www-authenticate"]
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn test_issuer_follows_host_by_default() {
+    common::init_test_tracing();
+    let server = TestServer::start_with_config(Config::builder().oauth(true).build()).await;
+    let json = metadata(&server, &[]).await;
+    assert_eq!(json["issuer"], server.base_url());
+    assert_eq!(
+        json["token_endpoint"],
+        format!("{}/oauth/token", server.base_url())
+    );
+}
+
+#[tokio::test]
+async fn test_forwarded_headers_set_public_urls() {
+    common::init_test_tracing();
+    let server = TestServer::start_with_config(Config::builder().oauth(true).build()).await;
+    let forwarded = [
+        ("X-Forwarded-Proto", "https"),
+        ("X-Forwarded-Host", "mcp.example.com"),
+        ("X-Forwarded-Prefix", "/test"),
+    ];
+
+    let json = metadata(&server, &forwarded).await;
+    assert_eq!(json["issuer"], "https://mcp.example.com/test");
+    assert_eq!(
+        json["authorization_endpoint"],
+        "https://mcp.example.com/test/oauth/authorize"
+    );
+
+    let challenge = challenge(&server, &forwarded).await;
+    assert!(
+        challenge.contains(
+            r#"resource_metadata="https://mcp.example.com/test/.well-known/oauth-protected-resource""#
+        ),
+        "{challenge}"
+    );
+}
+
+#[tokio::test]
+async fn test_public_url_overrides_headers() {
+    common::init_test_tracing();
+    let config = Config::builder()
+        .oauth(true)
+        .public_url("https://public.example.com")
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let forwarded = [("X-Forwarded-Host", "other.example.com")];
+
+    let json = metadata(&server, &forwarded).await;
+    assert_eq!(json["issuer"], "https://public.example.com");
+
+    let resource: Value = common::test_client()
+        .get(format!(
+            "{}/.well-known/oauth-protected-resource",
+            server.base_url()
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(resource["resource"], "https://public.example.com");
+    assert_eq!(
+        resource["authorization_servers"],
+        serde_json::json!(["https://public.example.com"])
+    );
+
+    let challenge = challenge(&server, &forwarded).await;
+    assert!(
+        challenge.contains("https://public.example.com/.well-known/oauth-protected-resource"),
+        "{challenge}"
+    );
+}
+
+#[tokio::test]
+async fn test_public_url_origin_is_allowed() {
+    common::init_test_tracing();
+    let config = Config::builder()
+        .public_url("https://public.example.com/mcp-test")
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let status = |origin: &'static str| {
+        let url = server.mcp_url();
+        async move {
+            common::test_client()
+                .post(url)
+                .header("Origin", origin)
+                .header("Content-Type", "application/json")
+                .header("Accept", "application/json, text/event-stream")
+                .body(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#)
+                .send()
+                .await
+                .unwrap()
+                .status()
+        }
+    };
+    assert_ne!(status("https://public.example.com").await, 403);
+    assert_eq!(status("https://evil.example.com").await, 403);
+}
+
+#[tokio::test]
+async fn test_hostile_forwarded_host_is_ignored() {
+    common::init_test_tracing();
+    let server = TestServer::start_with_config(Config::builder().oauth(true).build()).await;
+    let hostile = [("X-Forwarded-Host", r#"evil.example.com", realm="x"#)];
+
+    let json = metadata(&server, &hostile).await;
+    assert_eq!(json["issuer"], server.base_url());
+
+    let challenge = challenge(&server, &hostile).await;
+    assert!(!challenge.contains("evil"), "{challenge}");
+    assert!(
+        challenge.contains(&format!(
+            r#"resource_metadata="{}/.well-known/oauth-protected-resource""#,
+            server.base_url()
+        )),
+        "{challenge}"
+    );
+}
@@ -0,0 +1,80 @@
+//! Seeded random tool tests: the same seed reproduces `random_string`,
+//! `roll_dice`, and `random_choice` results, and the server seed is the
+//! fallback.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use serde_json::{Value, json};
+
+async fn call(client: &McpClient, name: &str, arguments: Value) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": name, "arguments": arguments }),
+        )
+        .await["result"]
+        .clone()
+}
+
+#[tokio::test]
+async fn test_same_seed_same_result() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let cases = [
+        (
+            "random_string",
+            json!({ "length": 32, "charset": "hex", "seed": 42 }),
+        ),
+        ("roll_dice", json!({ "notation": "4d6+1", "seed": 42 })),
+        (
+            "random_choice",
+            json!({ "items": ["a", 1, { "b": true }, null], "count": 3, "unique": true, "seed": 42 }),
+        ),
+    ];
+    for (name, arguments) in cases {
+        let first = call(&client, name, arguments.clone()).await;
+        let second = call(&client, name, arguments).await;
+        assert_ne!(first["isError"], true, "{name}: {first}");
+        assert_eq!(
+            first["structuredContent"], second["structuredContent"],
+            "{name}"
+        );
+        assert_eq!(first["structuredContent"]["seed"], 42, "{name}");
+    }
+
+    let result = call(
+        &client,
+        "roll_dice",
+        json!({ "notation": "3d6", "seed": 42 }),
+    )
+    .await;
+    let roll = &result["structuredContent"];
+    assert_eq!(roll["rolls"].as_array().unwrap().len(), 3);
+
+    let result = call(&client, "roll_dice", json!({ "notation": "three dice" })).await;
+    assert_eq!(result["isError"], true);
+    assert!(
+        result["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .starts_with("Invalid dice notation")
+    );
+}
+
+#[tokio::test]
+async fn test_server_seed_is_the_default() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_config(Config::builder().seed(7).build()).await;
+    let client = McpClient::connect(&server).await;
+
+    let unseeded = call(&client, "random_string", json!({ "length": 12 })).await;
+    let seeded = call(&client, "random_string", json!({ "length": 12, "seed": 7 })).await;
+    assert_eq!(unseeded["structuredContent"]["seed"], 7);
+    assert_eq!(unseeded["structuredContent"], seeded["structuredContent"]);
+}
@@ -0,0 +1,97 @@
+//! Regex tool tests: structured capture groups, invalid patterns, and the
+//! execution timeout.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use serde_json::{Value, json};
+
+async fn call(client: &McpClient, name: &str, arguments: Value) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": name, "arguments": arguments }),
+        )
+        .await["result"]
+        .clone()
+}
+
+#[tokio::test]
+async fn test_regex_tools_return_structured_matches() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let result = call(
+        &client,
+        "regex_match",
+        json!({ "pattern": r"(?<user>\w+)@(\w+)\.com", "text": "mail ada@example.com now" }),
+    )
+    .await;
+    let found = &result["structuredContent"];
+    assert_eq!(found["matched"], true);
+    assert_eq!(found["match"]["text"], "ada@example.com");
+    assert_eq!(found["match"]["start"], 5);
+    assert_eq!(found["match"]["groups"][1]["name"], "user");
+    assert_eq!(found["match"]["groups"][1]["text"], "ada");
+    assert_eq!(found["match"]["groups"][2]["text"], "example");
+
+    let result = call(
+        &client,
+        "regex_replace",
+        json!({ "pattern": r"(\d+)", "text": "1 and 22", "replacement": "<$1>" }),
+    )
+    .await;
+    assert_eq!(
+        result["structuredContent"],
+        json!({ "result": "<1> and <22>", "replacements": 2 })
+    );
+
+    let result = call(
+        &client,
+        "regex_extract",
+        json!({ "pattern": r"[a-z]", "text": "a1b2c3", "limit": 2 }),
+    )
+    .await;
+    let extracted = &result["structuredContent"];
+    assert_eq!(extracted["count"], 2);
+    assert_eq!(extracted["truncated"], true);
+    assert_eq!(extracted["matches"][1]["text"], "b");
+}
+
+#[tokio::test]
+async fn test_regex_error_paths() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_config(Config::builder().regex_timeout_ms(1).build()).await;
+    let client = McpClient::connect(&server).await;
+
+    let result = call(
+        &client,
+        "regex_match",
+        json!({ "pattern": "(unclosed", "text": "x" }),
+    )
+    .await;
+    assert_eq!(result["isError"], true);
+    assert!(
+        result["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .starts_with("Invalid regex pattern:")
+    );
+
+    // Rewriting a megabyte of text takes far longer than 1ms
+    let result = call(
+        &client,
+        "regex_replace",
+        json!({ "pattern": r"(\w)(\w)", "text": "ab ".repeat(500_000), "replacement": "$2$1" }),
+    )
+    .await;
+    assert_eq!(result["isError"], true);
+    assert_eq!(
+        result["content"][0]["text"],
+        "Regex evaluation exceeded the 1ms timeout"
+    );
+}
@@ -0,0 +1,108 @@
+//! Runtime prompt registration: `register_prompt` adds a prompt that
+//! `prompts/list` and `prompts/get` serve, and announces the change.
+
+mod common;
+
+use std::time::Duration;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use serde_json::{Value, json};
+
+async fn register(client: &McpClient, arguments: Value) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": "register_prompt", "arguments": arguments }),
+        )
+        .await["result"]
+        .clone()
+}
+
+#[tokio::test]
+async fn test_registered_prompt_is_listed_and_rendered() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_config(Config::builder().stateful(true).build()).await;
+    let client = McpClient::connect(&server).await;
+    let mut stream = client.open_stream().await;
+
+    let result = register(
+        &client,
+        json!({
+            "name": "standup",
+            "description": "Daily standup",
+            "arguments": [
+                { "name": "team", "required": true },
+                { "name": "blocker", "description": "Anything blocking" },
+            ],
+            "template": "Standup for {team}. Blockers: {blocker}",
+        }),
+    )
+    .await;
+    assert_ne!(result["isError"], true, "register failed: {result}");
+    let body: Value = serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(
+        body,
+        json!({ "name": "standup", "replaced": false, "arguments": 2 })
+    );
+
+    let message = tokio::time::timeout(Duration::from_secs(5), stream.next_message())
+        .await
+        .expect("Timed out waiting for list_changed")
+        .expect("SSE stream ended");
+    assert_eq!(message["method"], "notifications/prompts/list_changed");
+
+    let list = client.request("prompts/list", json!({})).await;
+    let prompts = list["result"]["prompts"].as_array().unwrap();
+    let standup = prompts.iter().find(|p| p["name"] == "standup").unwrap();
+    assert_eq!(standup["description"], "Daily standup");
+    assert_eq!(standup["arguments"][0]["required"], true);
+    assert_eq!(standup["arguments"][1]["description"], "Anything blocking");
+
+    let prompt = client
+        .request(
+            "prompts/get",
+            json!({ "name": "standup", "arguments": { "team": "core", "blocker": "none" } }),
+        )
+        .await;
+    assert_eq!(
+        prompt["result"]["messages"][0]["content"]["text"],
+        "Standup for core. Blockers: none"
+    );
+    let missing = client
+        .request("prompts/get", json!({ "name": "standup", "arguments": {} }))
+        .await;
+    assert_eq!(missing["error"]["code"], -32602);
+
+    let replaced = register(&client, json!({ "name": "standup", "template": "Again" })).await;
+    let body: Value =
+        serde_json::from_str(replaced["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(body["replaced"], true);
+}
+
+#[tokio::test]
+async fn test_register_prompt_rejects_builtin_and_bad_arguments() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let builtin = register(&client, json!({ "name": "greeting", "template": "x" })).await;
+    assert_eq!(builtin["isError"], true);
+
+    let duplicate = register(
+        &client,
+        json!({
+            "name": "twice",
+            "arguments": [{ "name": "a" }, { "name": "a" }],
+            "template": "{a}",
+        }),
+    )
+    .await;
+    assert_eq!(duplicate["isError"], true);
+
+    let list = client.request("prompts/list", json!({})).await;
+    let prompts = list["result"]["prompts"].as_array().unwrap();
+    assert!(!prompts.iter().any(|p| p["name"] == "twice"));
+}
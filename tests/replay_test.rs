@@ -0,0 +1,73 @@
+//! Record-and-replay tests: `MCP_RECORD` captures exchanges that
+//! `MCP_REPLAY` serves back verbatim.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use serde_json::json;
+
+fn recording_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("mcp-{name}-{}.jsonl", std::process::id()))
+}
+
+#[tokio::test]
+async fn test_replay_serves_recorded_responses() {
+    common::init_test_tracing();
+    let path = recording_path("replay");
+
+    let server = TestServer::start_with_config(Config::builder().record(&path).build()).await;
+    let client = McpClient::connect(&server).await;
+    let recorded_echo = client
+        .request(
+            "tools/call",
+            json!({ "name": "echo", "arguments": { "text": "frozen" } }),
+        )
+        .await;
+    let recorded_uuid = client
+        .request("tools/call", json!({ "name": "random_uuid" }))
+        .await;
+    server.shutdown().await;
+
+    let recording = std::fs::read_to_string(&path).unwrap();
+    // initialize, notifications/initialized, and the two calls
+    assert_eq!(recording.lines().count(), 4, "{recording}");
+
+    let server = TestServer::start_with_config(Config::builder().replay(&path).build()).await;
+    let client = McpClient::connect(&server).await;
+    let uuid = client
+        .request("tools/call", json!({ "name": "random_uuid" }))
+        .await;
+    assert_eq!(uuid["result"], recorded_uuid["result"], "frozen, not rerun");
+    let echo = client
+        .request(
+            "tools/call",
+            json!({ "name": "echo", "arguments": { "text": "frozen" } }),
+        )
+        .await;
+    assert_eq!(echo["result"], recorded_echo["result"]);
+
+    let missing = client
+        .request(
+            "tools/call",
+            json!({ "name": "echo", "arguments": { "text": "new" } }),
+        )
+        .await;
+    assert_eq!(missing["error"]["code"], -32603);
+    assert_eq!(
+        missing["error"]["message"],
+        "No recorded response for tools/call"
+    );
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_replay_requires_existing_recording() {
+    let errors = Config::validate_vars([
+        ("MCP_REPLAY", "/nonexistent/recording.jsonl"),
+        ("MCP_RECORD", "out.jsonl"),
+    ])
+    .unwrap_err();
+    let names: Vec<&str> = errors.0.iter().map(|i| i.name.as_str()).collect();
+    assert_eq!(names, ["MCP_REPLAY", "MCP_RECORD"]);
+}
@@ -1,26 +1,44 @@
 //! Integration tests for resources implementation.
 
 use mcp_test_server::resources::{
-    ResourceHandler,
+    RESOURCE_PAGE_SIZE, ResourceHandler,
+    case_resources::{CASE_FIXTURE_URIS, list_case_resources},
     dynamic_resources::{
-        CounterState, get_counter_content, get_counter_resource, get_random_content,
-        get_random_resource, get_timestamp_content, get_timestamp_resource, list_dynamic_resources,
+        CounterState, TICKER_URI, TickerClock, UnreliableRead, get_counter_content,
+        get_counter_resource, get_random_content, get_random_resource, get_timestamp_content,
+        get_timestamp_resource, list_dynamic_resources,
     },
+    meta_resources::{FLAGS_URI, FlagStore, RuntimeFlags},
+    paginate_resources,
+    session_resources::{SESSION_INFO_URI, SessionStore},
     static_resources::{
-        get_data_json_content, get_data_json_resource, get_hello_content, get_hello_resource,
-        get_image_png_content, get_image_png_resource, get_large_txt_content,
-        get_large_txt_resource, list_static_resources, read_static_resource,
+        UiAssets, get_audio_wav_content, get_audio_wav_resource, get_data_json_content,
+        get_data_json_resource, get_document_pdf_content, get_document_pdf_resource,
+        get_hello_content, get_hello_resource, get_image_png_content, get_image_png_resource,
+        get_large_txt_content, get_large_txt_resource, list_static_resources, read_static_resource,
     },
+    synthetic_resources::read_synthetic_resource,
+    template_resources::{
+        MANY_ITEMS, MANY_TEMPLATE, REPO_TEMPLATE, complete_many_argument, complete_repo_argument,
+    },
+    ui_csp::{CDN_ORIGIN, UiCsp},
+};
+use mcp_test_server::sessions::DEFAULT_SESSION;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use rmcp::model::{
+    CompletionContext, ReadResourceRequestParams, ResourceContents, SubscribeRequestParams,
+    UnsubscribeRequestParams,
 };
-use rmcp::model::{ReadResourceRequestParams, ResourceContents, SubscribeRequestParams};
 
 // Static resource tests
 
 #[test]
 fn test_list_static_resources() {
     let resources = list_static_resources();
-    // 4 original static + 7 UI app resources = 11
-    assert_eq!(resources.len(), 11);
+    // 7 original static + 7 UI app resources = 14
+    assert_eq!(resources.len(), 14);
 }
 
 #[test]
@@ -104,6 +122,46 @@ fn test_large_txt_content_is_over_10kb() {
     }
 }
 
+#[test]
+fn test_audio_wav_content_is_riff_wave() {
+    use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+
+    let resource = get_audio_wav_resource();
+    assert_eq!(resource.mime_type, Some("audio/wav".to_string()));
+    match get_audio_wav_content() {
+        ResourceContents::BlobResourceContents {
+            blob, mime_type, ..
+        } => {
+            assert_eq!(mime_type, Some("audio/wav".to_string()));
+            let bytes = BASE64.decode(&blob).unwrap();
+            assert_eq!(&bytes[0..4], b"RIFF");
+            assert_eq!(&bytes[8..12], b"WAVE");
+            assert_eq!(resource.size, u32::try_from(bytes.len()).ok());
+        }
+        ResourceContents::TextResourceContents { .. } => panic!("Expected blob content"),
+    }
+}
+
+#[test]
+fn test_document_pdf_content_is_pdf() {
+    use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+
+    let resource = get_document_pdf_resource();
+    assert_eq!(resource.mime_type, Some("application/pdf".to_string()));
+    match get_document_pdf_content() {
+        ResourceContents::BlobResourceContents {
+            blob, mime_type, ..
+        } => {
+            assert_eq!(mime_type, Some("application/pdf".to_string()));
+            let bytes = BASE64.decode(&blob).unwrap();
+            assert!(bytes.starts_with(b"%PDF-"));
+            assert!(bytes.ends_with(b"%%EOF\n"));
+            assert_eq!(resource.size, u32::try_from(bytes.len()).ok());
+        }
+        ResourceContents::TextResourceContents { .. } => panic!("Expected blob content"),
+    }
+}
+
 #[test]
 fn test_read_static_resource() {
     let content = read_static_resource("test://static/hello.txt");
@@ -179,12 +237,108 @@ fn test_read_internal_only_app_resource() {
     }
 }
 
+#[test]
+fn test_rich_app_resources_read_resources_and_show_errors() {
+    for uri in ["ui://dashboard/app.html", "ui://data_table/app.html"] {
+        let Some(ResourceContents::TextResourceContents { text, .. }) = read_static_resource(uri)
+        else {
+            panic!("Expected text content for {uri}");
+        };
+        assert!(text.contains("resources/read"), "{uri} has no shim");
+        assert!(
+            text.contains("readServerResource("),
+            "{uri} reads no resource"
+        );
+        assert!(
+            text.contains("McpApp.describeError"),
+            "{uri} shows no errors"
+        );
+    }
+}
+
+#[test]
+fn test_ui_assets_inline_drops_cdn_tags() {
+    let cdn = ResourceHandler::new();
+    let inline = ResourceHandler::new().with_ui_assets(UiAssets::Inline);
+    for uri in ["ui://dashboard/app.html", "ui://data_table/app.html"] {
+        let request = ReadResourceRequestParams::new(uri);
+        let text =
+            |handler: &ResourceHandler| match &handler.read_resource(&request).unwrap().contents[0]
+            {
+                ResourceContents::TextResourceContents { text, .. } => text.clone(),
+                ResourceContents::BlobResourceContents { .. } => panic!("Expected text content"),
+            };
+        assert!(
+            text(&cdn).contains("cdn.jsdelivr.net"),
+            "{uri} has no CDN tags"
+        );
+        let html = text(&inline);
+        assert!(
+            !html.contains("cdn.jsdelivr.net"),
+            "{uri} still loads from the CDN"
+        );
+        assert!(!html.contains("{{CDN_"), "{uri} keeps a marker");
+        assert!(html.contains("McpApp"), "{uri} lost its shim");
+    }
+
+    let hello = ReadResourceRequestParams::new("test://static/hello.txt");
+    assert_eq!(
+        inline.read_resource(&hello).unwrap().contents,
+        cdn.read_resource(&hello).unwrap().contents
+    );
+    assert_eq!(UiAssets::parse(" Inline "), Some(UiAssets::Inline));
+    assert_eq!(UiAssets::parse("bundled"), None);
+}
+
+#[test]
+fn test_ui_csp_is_declared_on_ui_apps() {
+    let request = ReadResourceRequestParams::new("ui://dashboard/app.html");
+    let meta =
+        |handler: &ResourceHandler| match &handler.read_resource(&request).unwrap().contents[0] {
+            ResourceContents::TextResourceContents { meta, .. } => meta.clone(),
+            ResourceContents::BlobResourceContents { .. } => panic!("Expected text content"),
+        };
+    assert_eq!(meta(&ResourceHandler::new()), None);
+
+    let handler = ResourceHandler::new().with_ui_csp(UiCsp::Cdn);
+    let csp = meta(&handler).unwrap().0["ui"]["csp"].clone();
+    assert_eq!(
+        csp,
+        serde_json::json!({ "connectDomains": [], "resourceDomains": [CDN_ORIGIN] })
+    );
+
+    let resources = handler.list_resources(None).unwrap().resources;
+    for resource in &resources {
+        let declared = resource
+            .raw
+            .meta
+            .as_ref()
+            .is_some_and(|meta| meta.0["ui"]["csp"] == csp);
+        assert_eq!(
+            declared,
+            resource.raw.uri.starts_with("ui://"),
+            "{}",
+            resource.raw.uri
+        );
+    }
+
+    assert!(UiCsp::Strict.header().contains("connect-src 'none'"));
+    assert!(!UiCsp::Strict.header().contains(CDN_ORIGIN));
+    assert!(
+        UiCsp::Cdn
+            .header()
+            .contains(&format!("script-src 'unsafe-inline' {CDN_ORIGIN}"))
+    );
+    assert_eq!(UiCsp::parse(" LAX "), Some(UiCsp::Lax));
+    assert_eq!(UiCsp::parse("open"), None);
+}
+
 // Dynamic resource tests
 
 #[test]
 fn test_list_dynamic_resources() {
     let resources = list_dynamic_resources();
-    assert_eq!(resources.len(), 3);
+    assert_eq!(resources.len(), 7);
 }
 
 #[test]
@@ -257,6 +411,59 @@ fn test_random_content_changes() {
     assert_ne!(text1, text2);
 }
 
+#[test]
+fn test_ticker_clock_is_drift_free() {
+    let clock = TickerClock::new(Duration::from_millis(250));
+    let first = clock.tick(0);
+    let later = clock.tick(40);
+    assert_eq!(later.seq, 40);
+    assert_eq!(
+        (later.timestamp - first.timestamp).num_milliseconds(),
+        10_000
+    );
+    assert_eq!(
+        clock.deadline(4) - clock.deadline(0),
+        Duration::from_secs(1)
+    );
+}
+
+#[test]
+fn test_ticker_clock_clamps_zero_interval() {
+    let clock = TickerClock::new(Duration::ZERO);
+    assert_eq!(clock.interval(), Duration::from_millis(1));
+}
+
+#[test]
+fn test_ticker_tick_json() {
+    let clock = TickerClock::new(Duration::from_millis(100));
+    let json = clock.tick(3).to_json(clock.interval());
+    assert_eq!(json["seq"], 3);
+    assert_eq!(json["interval_ms"], 100);
+    assert!(json["timestamp"].as_str().unwrap().ends_with('Z'));
+}
+
+#[test]
+fn test_resource_handler_read_ticker() {
+    let handler = ResourceHandler::new();
+    let request = ReadResourceRequestParams::new(TICKER_URI.to_string());
+    let result = handler.read_resource(&request).unwrap();
+    match &result.contents[0] {
+        ResourceContents::TextResourceContents { text, .. } => {
+            let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(parsed["interval_ms"], 1000);
+        }
+        ResourceContents::BlobResourceContents { .. } => panic!("Expected text content"),
+    }
+}
+
+#[test]
+fn test_resource_handler_subscribe_ticker() {
+    let handler = ResourceHandler::new();
+    let request = SubscribeRequestParams::new(TICKER_URI.to_string());
+    assert!(handler.subscribe(&request, "default").is_ok());
+    assert_eq!(handler.active_tickers(), 0);
+}
+
 // ResourceHandler tests
 
 #[test]
@@ -264,8 +471,142 @@ fn test_resource_handler_list_resources() {
     let handler = ResourceHandler::new();
     let result = handler.list_resources(None).unwrap();
 
-    // 11 static (4 original + 7 UI apps) + 3 dynamic = 14 resources
-    assert_eq!(result.resources.len(), 14);
+    // 14 static (7 original + 7 UI apps) + 7 dynamic + 1 UI message log + 4 case fixtures
+    // + 1 meta + 2 session = 29 resources
+    assert_eq!(result.resources.len(), 29);
+}
+
+#[test]
+fn test_resource_handler_read_multi_returns_several_contents() {
+    let handler = ResourceHandler::new();
+    let request = ReadResourceRequestParams::new("test://static/multi");
+    let result = handler.read_resource(&request).unwrap();
+
+    let mime_types: Vec<_> = result
+        .contents
+        .iter()
+        .map(|c| match c {
+            ResourceContents::TextResourceContents { uri, mime_type, .. }
+            | ResourceContents::BlobResourceContents { uri, mime_type, .. } => {
+                assert_eq!(uri, "test://static/multi");
+                mime_type.as_deref().unwrap()
+            }
+        })
+        .collect();
+    assert_eq!(mime_types, ["text/plain", "application/json", "image/png"]);
+    assert!(matches!(
+        result.contents[2],
+        ResourceContents::BlobResourceContents { .. }
+    ));
+}
+
+#[test]
+fn test_unreliable_read_parse() {
+    assert_eq!(
+        UnreliableRead::parse("test://dynamic/slow"),
+        Some(Ok(UnreliableRead::Slow(Duration::from_secs(1))))
+    );
+    assert_eq!(
+        UnreliableRead::parse("test://dynamic/slow?delay_ms=250"),
+        Some(Ok(UnreliableRead::Slow(Duration::from_millis(250))))
+    );
+    assert_eq!(
+        UnreliableRead::parse("test://dynamic/flaky?fail_rate=0.25"),
+        Some(Ok(UnreliableRead::Flaky(0.25)))
+    );
+    assert!(matches!(
+        UnreliableRead::parse("test://dynamic/flaky?fail_rate=2"),
+        Some(Err(_))
+    ));
+    assert!(matches!(
+        UnreliableRead::parse("test://dynamic/slow?fail_rate=0.5"),
+        Some(Err(_))
+    ));
+    assert_eq!(UnreliableRead::parse("test://dynamic/counter"), None);
+}
+
+#[test]
+fn test_session_store_counts_per_session() {
+    let store = SessionStore::new();
+    let value = |content: ResourceContents| match content {
+        ResourceContents::TextResourceContents { text, .. } => text,
+        ResourceContents::BlobResourceContents { .. } => panic!("Expected text content"),
+    };
+    assert_eq!(value(store.counter_content("a")), "1");
+    assert_eq!(value(store.counter_content("a")), "2");
+    assert_eq!(value(store.counter_content("b")), "1");
+
+    let info: serde_json::Value = serde_json::from_str(&value(store.info_content("a"))).unwrap();
+    assert_eq!(info["session_id"], "a");
+    assert_eq!(info["counter"], 2);
+}
+
+#[test]
+fn test_session_resources_without_context_use_default_session() {
+    let handler = ResourceHandler::new();
+    let request = ReadResourceRequestParams::new(SESSION_INFO_URI);
+    let result = handler.read_resource(&request).unwrap();
+    match &result.contents[0] {
+        ResourceContents::TextResourceContents { text, .. } => {
+            let info: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(info["session_id"], DEFAULT_SESSION);
+        }
+        ResourceContents::BlobResourceContents { .. } => panic!("Expected text content"),
+    }
+}
+
+#[test]
+fn test_paginate_resources() {
+    let handler = ResourceHandler::new().with_synthetic_resources(250);
+    let all = handler.list_resources(None).unwrap().resources;
+    assert_eq!(all.len(), 29 + 250);
+
+    let first = paginate_resources(all.clone(), None).unwrap();
+    assert_eq!(first.resources.len(), RESOURCE_PAGE_SIZE);
+    let next = first.next_cursor.unwrap();
+    let second = paginate_resources(all.clone(), Some(&next)).unwrap();
+    assert_eq!(second.resources[0].uri, all[RESOURCE_PAGE_SIZE].uri);
+    let last = paginate_resources(all.clone(), second.next_cursor.as_deref()).unwrap();
+    assert_eq!(last.resources.len(), all.len() - 2 * RESOURCE_PAGE_SIZE);
+    assert!(last.next_cursor.is_none());
+
+    assert!(paginate_resources(all.clone(), Some("0")).is_err());
+    assert!(paginate_resources(all, Some("9999")).is_err());
+}
+
+#[test]
+fn test_read_synthetic_resource() {
+    assert!(read_synthetic_resource("test://synthetic/3", 3).is_some());
+    assert!(read_synthetic_resource("test://synthetic/4", 3).is_none());
+    assert!(read_synthetic_resource("test://synthetic/0", 3).is_none());
+    assert!(read_synthetic_resource("test://synthetic/03", 3).is_none());
+}
+
+#[test]
+fn test_case_fixtures_are_distinct() {
+    let handler = ResourceHandler::new();
+    let mut texts = Vec::new();
+    for (uri, _) in CASE_FIXTURE_URIS {
+        let request = ReadResourceRequestParams::new(uri.to_string());
+        let result = handler.read_resource(&request).unwrap();
+        match &result.contents[0] {
+            ResourceContents::TextResourceContents { text, uri: got, .. } => {
+                assert_eq!(got, uri);
+                texts.push(text.clone());
+            }
+            ResourceContents::BlobResourceContents { .. } => panic!("Expected text content"),
+        }
+    }
+    texts.dedup();
+    assert_eq!(texts.len(), CASE_FIXTURE_URIS.len());
+    assert_eq!(list_case_resources().len(), CASE_FIXTURE_URIS.len());
+}
+
+#[test]
+fn test_case_fixtures_require_exact_uri() {
+    let handler = ResourceHandler::new();
+    let request = ReadResourceRequestParams::new("test://case/readme.TXT".to_string());
+    assert!(handler.read_resource(&request).is_err());
 }
 
 #[test]
@@ -273,11 +614,69 @@ fn test_resource_handler_list_templates() {
     let handler = ResourceHandler::new();
     let result = handler.list_resource_templates(None).unwrap();
 
-    assert_eq!(result.resource_templates.len(), 1);
+    assert_eq!(result.resource_templates.len(), 3);
     assert_eq!(
         result.resource_templates[0].uri_template,
         "test://files/{path}"
     );
+    assert_eq!(result.resource_templates[1].uri_template, REPO_TEMPLATE);
+    assert_eq!(result.resource_templates[2].uri_template, MANY_TEMPLATE);
+}
+
+#[test]
+fn test_many_template_items() {
+    let items = complete_many_argument("item");
+    assert_eq!(items.len(), MANY_ITEMS);
+    assert_eq!(items[0], "item-001");
+    assert_eq!(items[MANY_ITEMS - 1], "item-250");
+
+    let handler = ResourceHandler::new();
+    let request = ReadResourceRequestParams::new("test://many/item-250");
+    assert!(handler.read_resource(&request).is_ok());
+    let request = ReadResourceRequestParams::new("test://many/item-251");
+    assert!(handler.read_resource(&request).is_err());
+}
+
+#[test]
+fn test_resource_handler_read_repo() {
+    let handler = ResourceHandler::new();
+    let request = ReadResourceRequestParams::new("test://repos/bob/blog");
+    let result = handler.read_resource(&request).unwrap();
+    match &result.contents[0] {
+        ResourceContents::TextResourceContents { text, .. } => {
+            let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+            assert_eq!(parsed["owner"], "bob");
+            assert_eq!(parsed["repo"], "blog");
+        }
+        ResourceContents::BlobResourceContents { .. } => panic!("Expected text content"),
+    }
+
+    // alice has no blog
+    let request = ReadResourceRequestParams::new("test://repos/alice/blog");
+    assert!(handler.read_resource(&request).is_err());
+}
+
+#[test]
+fn test_complete_repo_uses_owner_from_context() {
+    let context = CompletionContext::with_arguments(HashMap::from([(
+        "owner".to_string(),
+        "alice".to_string(),
+    )]));
+    assert_eq!(
+        complete_repo_argument("repo", Some(&context)),
+        ["dotfiles", "notes"]
+    );
+
+    // Without an owner every repository is offered once
+    let all = complete_repo_argument("repo", None);
+    assert_eq!(all.len(), 7);
+    assert_eq!(all.iter().filter(|r| *r == "dotfiles").count(), 1);
+
+    assert_eq!(
+        complete_repo_argument("owner", None),
+        ["acme", "alice", "bob"]
+    );
+    assert!(complete_repo_argument("branch", Some(&context)).is_empty());
 }
 
 #[test]
@@ -289,6 +688,20 @@ fn test_resource_handler_read_static() {
     assert_eq!(result.contents.len(), 1);
 }
 
+#[test]
+fn test_resource_handler_read_auth_context_without_request() {
+    let handler = ResourceHandler::new();
+    let request = ReadResourceRequestParams::new("test://dynamic/auth-context".to_string());
+
+    let result = handler.read_resource(&request).unwrap();
+    let ResourceContents::TextResourceContents { text, .. } = &result.contents[0] else {
+        panic!("Expected text");
+    };
+    let context: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(context["method"], "none");
+    assert!(context["session"].is_null());
+}
+
 #[test]
 fn test_resource_handler_read_dynamic_counter() {
     let handler = ResourceHandler::new();
@@ -338,7 +751,7 @@ fn test_resource_handler_read_unknown() {
 fn test_resource_handler_subscribe_random() {
     let handler = ResourceHandler::new();
     let request = SubscribeRequestParams::new("test://dynamic/random".to_string());
-    let result = handler.subscribe(&request);
+    let result = handler.subscribe(&request, "default");
 
     assert!(result.is_ok());
 }
@@ -347,7 +760,105 @@ fn test_resource_handler_subscribe_random() {
 fn test_resource_handler_subscribe_non_subscribable() {
     let handler = ResourceHandler::new();
     let request = SubscribeRequestParams::new("test://static/hello.txt".to_string());
-    let result = handler.subscribe(&request);
+    let result = handler.subscribe(&request, "default");
 
     assert!(result.is_err());
 }
+
+#[test]
+fn test_resource_handler_subscribe_twice_rejected() {
+    let handler = ResourceHandler::new();
+    let request = SubscribeRequestParams::new(FLAGS_URI.to_string());
+    assert!(handler.subscribe(&request, "a").is_ok());
+
+    let err = handler.subscribe(&request, "a").unwrap_err();
+    assert_eq!(err.code, rmcp::model::ErrorCode::INVALID_REQUEST);
+    assert_eq!(err.data.unwrap()["uri"], FLAGS_URI);
+
+    // Other sessions subscribe independently
+    assert!(handler.subscribe(&request, "b").is_ok());
+    assert_eq!(handler.subscriptions().report().total, 2);
+}
+
+#[test]
+fn test_resource_handler_unsubscribe_allows_resubscribe() {
+    let handler = ResourceHandler::new();
+    let request = SubscribeRequestParams::new(FLAGS_URI.to_string());
+    // `UnsubscribeRequestParams` has no constructor
+    let unsubscribe: UnsubscribeRequestParams =
+        serde_json::from_value(serde_json::json!({ "uri": FLAGS_URI })).unwrap();
+
+    // Unsubscribing without a subscription is accepted
+    assert!(handler.unsubscribe(&unsubscribe, "a").is_ok());
+
+    assert!(handler.subscribe(&request, "a").is_ok());
+    assert!(handler.subscriptions().contains("a", FLAGS_URI));
+    assert!(handler.unsubscribe(&unsubscribe, "a").is_ok());
+    assert!(!handler.subscriptions().contains("a", FLAGS_URI));
+    assert!(handler.subscriptions().report().sessions.is_empty());
+
+    assert!(handler.subscribe(&request, "a").is_ok());
+}
+
+// Meta resource tests
+
+#[tokio::test]
+async fn test_flag_store_update_merges_and_bumps_revision() {
+    let store = FlagStore::new();
+    assert_eq!(store.snapshot().revision, 0);
+    assert_eq!(store.snapshot().flags, RuntimeFlags::default());
+
+    let patch = serde_json::json!({ "rate_limit": 5 });
+    let snapshot = store
+        .update(patch.as_object().unwrap().clone())
+        .await
+        .unwrap();
+    assert_eq!(snapshot.revision, 1);
+    assert_eq!(snapshot.flags.rate_limit, Some(5));
+    assert_eq!(snapshot.flags.profile, "default");
+
+    // Setting the same values again is not a change
+    let snapshot = store
+        .update(patch.as_object().unwrap().clone())
+        .await
+        .unwrap();
+    assert_eq!(snapshot.revision, 1);
+}
+
+#[tokio::test]
+async fn test_flag_store_rejects_bad_patch() {
+    let store = FlagStore::new();
+    for patch in [
+        serde_json::json!({ "chaos": "yes" }),
+        serde_json::json!({ "unknown": true }),
+    ] {
+        assert!(
+            store
+                .update(patch.as_object().unwrap().clone())
+                .await
+                .is_err()
+        );
+    }
+    assert_eq!(store.snapshot().revision, 0);
+}
+
+#[test]
+fn test_resource_handler_read_flags() {
+    let handler = ResourceHandler::new();
+    let request = ReadResourceRequestParams::new(FLAGS_URI.to_string());
+    let result = handler.read_resource(&request).unwrap();
+    let ResourceContents::TextResourceContents { text, .. } = &result.contents[0] else {
+        panic!("Expected text");
+    };
+    let flags: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(flags["revision"], 0);
+    assert_eq!(flags["chaos"], false);
+    assert!(
+        handler
+            .subscribe(
+                &SubscribeRequestParams::new(FLAGS_URI.to_string()),
+                "default"
+            )
+            .is_ok()
+    );
+}
@@ -0,0 +1,150 @@
+//! Scenario tests: the built-in end-to-end scenarios against a reference client.
+
+mod common;
+
+use mcp_test_server::scenarios::{self, Scenario, ScenarioClient, Target};
+use rmcp::model::{CallToolResult, JsonObject};
+
+/// Reference client over `reqwest`, with a switch to ignore HTTP errors on
+/// connect, as a sloppy client might.
+#[derive(Default)]
+struct ReqwestClient {
+    http: reqwest::Client,
+    target: Option<Target>,
+    session_id: Option<String>,
+    ignore_connect_errors: bool,
+}
+
+impl ReqwestClient {
+    async fn post(&self, message: serde_json::Value) -> Result<reqwest::Response, String> {
+        let target = self.target.as_ref().ok_or("not connected")?;
+        let mut request = self
+            .http
+            .post(&target.url)
+            .header("Accept", "application/json, text/event-stream")
+            .json(&message);
+        if let Some(token) = &target.bearer_token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(id) = &self.session_id {
+            request = request.header("mcp-session-id", id);
+        }
+        request.send().await.map_err(|e| e.to_string())
+    }
+
+    async fn request(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        let response = self
+            .post(serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }))
+            .await?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status()));
+        }
+        let body = response.text().await.map_err(|e| e.to_string())?;
+        let message = common::parse_sse_messages(&body)
+            .into_iter()
+            .find(|m| m["id"] == 1)
+            .ok_or_else(|| format!("no response in {body}"))?;
+        if let Some(error) = message.get("error") {
+            return Err(error.to_string());
+        }
+        Ok(message["result"].clone())
+    }
+}
+
+impl ScenarioClient for ReqwestClient {
+    async fn connect(&mut self, target: &Target) -> Result<(), String> {
+        self.target = Some(target.clone());
+        self.session_id = None;
+        let initialize = self
+            .post(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 0,
+                "method": "initialize",
+                "params": {
+                    "protocolVersion": "2025-06-18",
+                    "capabilities": {},
+                    "clientInfo": { "name": "scenario-client", "version": "0.0.0" }
+                }
+            }))
+            .await?;
+        if !initialize.status().is_success() && !self.ignore_connect_errors {
+            return Err(format!("HTTP {}", initialize.status()));
+        }
+        self.session_id = initialize
+            .headers()
+            .get("mcp-session-id")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let _ = initialize.text().await;
+        self.post(serde_json::json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+            .await
+            .map(|_| ())
+    }
+
+    async fn list_tools(&mut self) -> Result<Vec<String>, String> {
+        let result = self.request("tools/list", serde_json::json!({})).await?;
+        Ok(result["tools"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|t| t["name"].as_str().map(str::to_string))
+            .collect())
+    }
+
+    async fn call_tool(
+        &mut self,
+        name: &str,
+        arguments: JsonObject,
+    ) -> Result<CallToolResult, String> {
+        let result = self
+            .request(
+                "tools/call",
+                serde_json::json!({ "name": name, "arguments": arguments }),
+            )
+            .await?;
+        serde_json::from_value(result).map_err(|e| e.to_string())
+    }
+
+    async fn disconnect(&mut self) {
+        self.target = None;
+        self.session_id = None;
+    }
+}
+
+#[tokio::test]
+async fn test_reference_client_passes_all_scenarios() {
+    common::init_test_tracing();
+
+    let mut client = ReqwestClient::default();
+    let report = scenarios::run_all(&mut client).await;
+    assert!(
+        report.passed(),
+        "{}",
+        serde_json::to_string_pretty(&report).unwrap()
+    );
+    assert_eq!(report.scenarios.len(), Scenario::ALL.len());
+    assert!(report.scenarios[2].checks.len() > 3);
+}
+
+#[tokio::test]
+async fn test_client_ignoring_auth_errors_fails_auth_scenario() {
+    common::init_test_tracing();
+
+    let mut client = ReqwestClient {
+        ignore_connect_errors: true,
+        ..ReqwestClient::default()
+    };
+    let outcome = Scenario::AuthFailure.run(&mut client).await;
+    assert!(!outcome.passed);
+    let failed: Vec<&str> = outcome
+        .checks
+        .iter()
+        .filter(|c| !c.passed)
+        .map(|c| c.name.as_str())
+        .collect();
+    assert_eq!(failed, ["missing_key_rejected", "wrong_key_rejected"]);
+}
@@ -0,0 +1,159 @@
+//! Scratch resource tests: `resource_write` and `resource_delete` mutate
+//! `test://scratch/{name}` and notify clients of the change.
+
+mod common;
+
+use std::time::Duration;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use serde_json::{Value, json};
+
+async fn call(client: &McpClient, name: &str, arguments: Value) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": name, "arguments": arguments }),
+        )
+        .await["result"]
+        .clone()
+}
+
+async fn listed_uris(client: &McpClient) -> Vec<String> {
+    client.request("resources/list", json!({})).await["result"]["resources"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|r| r["uri"].as_str())
+        .filter(|uri| uri.starts_with("test://scratch/"))
+        .map(str::to_string)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_scratch_resource_lifecycle() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_config(Config::builder().stateful(true).build()).await;
+    let client = McpClient::connect(&server).await;
+    let mut stream = client.open_stream().await;
+    let uri = "test://scratch/notes.json";
+    let response = client
+        .request("resources/subscribe", json!({ "uri": uri }))
+        .await;
+    assert!(
+        response.get("error").is_none(),
+        "subscribe failed: {response}"
+    );
+
+    let result = call(
+        &client,
+        "resource_write",
+        json!({ "name": "notes.json", "text": "{\"v\":1}", "mime_type": "application/json" }),
+    )
+    .await;
+    let body: Value = serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(body, json!({ "uri": uri, "created": true, "size": 7 }));
+    assert_eq!(listed_uris(&client).await, [uri]);
+
+    let mut methods = Vec::new();
+    while methods.len() < 2 {
+        let message = tokio::time::timeout(Duration::from_secs(5), stream.next_message())
+            .await
+            .expect("Timed out waiting for scratch notifications")
+            .expect("SSE stream ended");
+        if message["method"] == "notifications/resources/updated" {
+            assert_eq!(message["params"]["uri"], uri);
+        }
+        methods.push(message["method"].as_str().unwrap().to_string());
+    }
+    methods.sort();
+    assert_eq!(
+        methods,
+        [
+            "notifications/resources/list_changed",
+            "notifications/resources/updated"
+        ]
+    );
+
+    let read = client
+        .request("resources/read", json!({ "uri": uri }))
+        .await;
+    let contents = &read["result"]["contents"][0];
+    assert_eq!(contents["text"], "{\"v\":1}");
+    assert_eq!(contents["mimeType"], "application/json");
+
+    // Overwriting updates the resource without changing the list
+    let result = call(
+        &client,
+        "resource_write",
+        json!({ "name": "notes.json", "text": "{}" }),
+    )
+    .await;
+    assert!(
+        result["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("\"created\":false")
+    );
+    let message = tokio::time::timeout(Duration::from_secs(5), stream.next_message())
+        .await
+        .expect("Timed out waiting for update notification")
+        .expect("SSE stream ended");
+    assert_eq!(message["method"], "notifications/resources/updated");
+
+    let result = call(&client, "resource_delete", json!({ "name": "notes.json" })).await;
+    assert!(
+        result["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("\"deleted\":true")
+    );
+    assert!(listed_uris(&client).await.is_empty());
+    let read = client
+        .request("resources/read", json!({ "uri": uri }))
+        .await;
+    assert!(
+        read.get("error").is_some(),
+        "deleted resource still readable: {read}"
+    );
+}
+
+#[tokio::test]
+async fn test_scratch_dry_run_and_invalid_names() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let result = client
+        .request(
+            "tools/call",
+            json!({
+                "name": "resource_write",
+                "arguments": { "name": "draft", "text": "hi" },
+                "_meta": { "dryRun": true }
+            }),
+        )
+        .await["result"]
+        .clone();
+    assert_eq!(result["_meta"]["dryRun"], true);
+    assert_eq!(result["structuredContent"]["changes"]["created"], true);
+    assert!(listed_uris(&client).await.is_empty());
+
+    let result = call(
+        &client,
+        "resource_write",
+        json!({ "name": "../etc/passwd", "text": "" }),
+    )
+    .await;
+    assert_eq!(result["isError"], true);
+
+    let result = call(&client, "resource_delete", json!({ "name": "missing" })).await;
+    assert!(
+        result["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("\"deleted\":false")
+    );
+}
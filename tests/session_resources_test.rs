@@ -0,0 +1,41 @@
+//! Per-session resources over MCP: each session sees its own state.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::Config;
+use serde_json::{Value, json};
+
+async fn read_text(client: &McpClient, uri: &str) -> String {
+    let response = client
+        .request("resources/read", json!({ "uri": uri }))
+        .await;
+    response["result"]["contents"][0]["text"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn test_session_resources_are_isolated() {
+    common::init_test_tracing();
+
+    let server = TestServer::start_with_config(Config::builder().stateful(true).build()).await;
+    let alice = McpClient::connect(&server).await;
+    let bob = McpClient::connect(&server).await;
+
+    for expected in ["1", "2", "3"] {
+        assert_eq!(read_text(&alice, "test://session/counter").await, expected);
+    }
+    assert_eq!(read_text(&bob, "test://session/counter").await, "1");
+
+    let info: Value =
+        serde_json::from_str(&read_text(&alice, "test://session/info").await).unwrap();
+    assert_eq!(info["session_id"], alice.session_id().unwrap());
+    assert_eq!(info["counter"], 3);
+    assert!(info["created_at"].is_string());
+
+    let info: Value = serde_json::from_str(&read_text(&bob, "test://session/info").await).unwrap();
+    assert_eq!(info["session_id"], bob.session_id().unwrap());
+    assert_eq!(info["counter"], 1);
+}
@@ -0,0 +1,106 @@
+//! SSE transport behavior tests (event size limits, slow drip).
+
+mod common;
+
+use common::{McpClient, TestServer};
+use mcp_test_server::{Config, sse::SseOversizePolicy};
+
+fn large_response_call(id: u64) -> serde_json::Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "tools/call",
+        "params": { "name": "large_response", "arguments": { "size_bytes": 2000 } }
+    })
+}
+
+#[tokio::test]
+async fn test_oversized_event_rejected_with_error() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .max_sse_event_bytes(512, SseOversizePolicy::Reject)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    // Small responses pass through untouched
+    let response = client
+        .request(
+            "tools/call",
+            serde_json::json!({ "name": "echo", "arguments": { "text": "hi" } }),
+        )
+        .await;
+    assert_eq!(response["result"]["content"][0]["text"], "hi");
+
+    let response = client.post(&large_response_call(42)).await;
+    let body = response.text().await.unwrap();
+    let messages = common::parse_sse_messages(&body);
+    assert_eq!(messages.len(), 1, "body: {body}");
+    assert_eq!(messages[0]["id"], 42);
+    assert_eq!(messages[0]["error"]["code"], -32603);
+    assert_eq!(messages[0]["error"]["data"]["max"], 512);
+}
+
+#[tokio::test]
+async fn test_oversized_event_split_across_events() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .max_sse_event_bytes(512, SseOversizePolicy::Split)
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    let response = client.post(&large_response_call(7)).await;
+    let body = response.text().await.unwrap();
+    let fragments: Vec<&str> = body
+        .split("\n\n")
+        .filter_map(|event| event.lines().find_map(|line| line.strip_prefix("data: ")))
+        .filter(|data| !data.is_empty())
+        .collect();
+
+    assert!(fragments.len() > 1, "expected multiple events: {body}");
+    assert!(fragments.iter().all(|f| f.len() <= 512));
+    // No single fragment is a valid message, but the concatenation is
+    assert!(common::parse_sse_messages(&body).is_empty());
+    let joined: serde_json::Value = serde_json::from_str(&fragments.concat()).unwrap();
+    assert_eq!(joined["id"], 7);
+}
+
+#[tokio::test]
+async fn test_slow_drip_splits_frames_across_chunks() {
+    common::init_test_tracing();
+
+    let config = Config::builder()
+        .sse_drip(16, std::time::Duration::from_millis(20))
+        .build();
+    let server = TestServer::start_with_config(config).await;
+    let client = McpClient::connect(&server).await;
+
+    let started = std::time::Instant::now();
+    let mut response = client
+        .post(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 9,
+            "method": "tools/call",
+            "params": { "name": "echo", "arguments": { "text": "drip drip drip" } }
+        }))
+        .await;
+    let mut chunks = Vec::new();
+    while let Some(chunk) = response.chunk().await.unwrap() {
+        assert!(chunk.len() <= 16, "chunk of {} bytes", chunk.len());
+        chunks.push(chunk);
+    }
+    // The response event alone is well over 100 bytes
+    assert!(chunks.len() >= 6, "only {} chunks", chunks.len());
+    assert!(started.elapsed() >= std::time::Duration::from_millis(100));
+
+    let body = String::from_utf8(chunks.concat()).unwrap();
+    let messages = common::parse_sse_messages(&body);
+    assert_eq!(messages[0]["id"], 9);
+    assert_eq!(
+        messages[0]["result"]["content"][0]["text"],
+        "drip drip drip"
+    );
+}
@@ -0,0 +1,77 @@
+//! Stats tool tests: structured output over number arrays and the
+//! empty-array and non-number error paths.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::{Value, json};
+
+async fn stats(client: &McpClient, arguments: Value) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": "stats", "arguments": arguments }),
+        )
+        .await
+}
+
+#[tokio::test]
+async fn test_stats_structured_output() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let response = stats(
+        &client,
+        json!({ "values": [9, 2, 4, 4, 5, 4, 7, 5], "percentiles": [50, 100] }),
+    )
+    .await;
+    assert_eq!(
+        response["result"]["structuredContent"],
+        json!({
+            "count": 8,
+            "min": 2.0,
+            "max": 9.0,
+            "sum": 40.0,
+            "mean": 5.0,
+            "median": 4.5,
+            "variance": 4.0,
+            "stddev": 2.0,
+            "percentiles": [{ "p": 50.0, "value": 4.5 }, { "p": 100.0, "value": 9.0 }],
+        })
+    );
+
+    let tools = client.request("tools/list", json!({})).await;
+    let tool = tools["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["name"] == "stats")
+        .unwrap()
+        .clone();
+    assert_eq!(tool["inputSchema"]["properties"]["values"]["type"], "array");
+    assert_eq!(tool["outputSchema"]["type"], "object");
+}
+
+#[tokio::test]
+async fn test_stats_error_paths() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    for arguments in [
+        json!({ "values": [] }),
+        json!({ "values": [1.7e308, 1.7e308] }),
+        json!({ "values": [1], "sample": true }),
+        json!({ "values": [1], "percentiles": [150] }),
+    ] {
+        let response = stats(&client, arguments.clone()).await;
+        assert_eq!(response["result"]["isError"], true, "{arguments}");
+    }
+
+    // JSON has no NaN: a non-number element is rejected as invalid params
+    let response = stats(&client, json!({ "values": [1, "NaN"] })).await;
+    assert_eq!(response["error"]["code"], -32602);
+}
@@ -0,0 +1,104 @@
+//! `stream_lines` tests: lines arrive incrementally over the POST response
+//! stream as progress notifications, before the final result.
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{McpClient, TestServer, parse_sse_messages};
+use serde_json::{Value, json};
+
+fn call(id: u64, arguments: &Value, meta: Option<Value>) -> Value {
+    let mut params = json!({ "name": "stream_lines", "arguments": arguments });
+    if let Some(meta) = meta {
+        params["_meta"] = meta;
+    }
+    json!({ "jsonrpc": "2.0", "id": id, "method": "tools/call", "params": params })
+}
+
+#[tokio::test]
+async fn test_lines_stream_as_progress_before_result() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let started = Instant::now();
+    let mut response = client
+        .post(&call(
+            1,
+            &json!({ "count": 5, "interval_ms": 200 }),
+            Some(json!({ "progressToken": "lines" })),
+        ))
+        .await;
+
+    // The first line arrives long before the last would
+    let mut body = String::new();
+    let mut first_at = None;
+    while let Some(chunk) = response.chunk().await.unwrap() {
+        body.push_str(&String::from_utf8_lossy(&chunk));
+        if first_at.is_none() && body.contains("notifications/progress") {
+            first_at = Some(started.elapsed());
+        }
+    }
+    let total = started.elapsed();
+    assert!(total >= Duration::from_millis(800), "{total:?}");
+    assert!(
+        first_at.unwrap() < Duration::from_millis(500),
+        "{first_at:?}"
+    );
+
+    let messages = parse_sse_messages(&body);
+    let (progress, rest): (Vec<_>, Vec<_>) = messages
+        .iter()
+        .partition(|m| m["method"] == "notifications/progress");
+    assert_eq!(progress.len(), 5);
+    for (n, notification) in (1_u32..).zip(&progress) {
+        let params = &notification["params"];
+        assert_eq!(params["progressToken"], "lines");
+        assert_eq!(params["progress"].as_f64(), Some(f64::from(n)));
+        assert_eq!(params["total"].as_f64(), Some(5.0));
+        assert_eq!(params["message"], format!("line {n}/5"));
+    }
+    let result = rest.iter().find(|m| m["id"] == 1).unwrap();
+    assert_eq!(
+        result["result"]["content"][0]["text"],
+        "line 1/5\nline 2/5\nline 3/5\nline 4/5\nline 5/5"
+    );
+    // The result is the last message on the stream
+    assert_eq!(messages.last().unwrap()["id"], 1);
+}
+
+#[tokio::test]
+async fn test_lines_stream_as_log_messages_without_token() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let response = client
+        .post(&call(
+            2,
+            &json!({ "count": 3, "interval_ms": 0, "text": "row" }),
+            None,
+        ))
+        .await;
+    let messages = parse_sse_messages(&response.text().await.unwrap());
+    let lines: Vec<&Value> = messages
+        .iter()
+        .filter(|m| m["method"] == "notifications/message")
+        .map(|m| &m["params"]["data"]["line"])
+        .collect();
+    assert_eq!(
+        lines,
+        [&json!("row 1/3"), &json!("row 2/3"), &json!("row 3/3")]
+    );
+
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": "stream_lines", "arguments": { "count": 1001 } }),
+        )
+        .await;
+    assert_eq!(response["result"]["isError"], true);
+}
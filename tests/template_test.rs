@@ -0,0 +1,99 @@
+//! Template tool tests: nested object contexts over MCP and the
+//! undefined-variable modes.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::{Value, json};
+
+async fn render(client: &McpClient, arguments: Value) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": "render_template", "arguments": arguments }),
+        )
+        .await["result"]
+        .clone()
+}
+
+fn text(result: &Value) -> &str {
+    result["content"][0]["text"].as_str().unwrap()
+}
+
+#[tokio::test]
+async fn test_render_template_with_nested_context() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let result = render(
+        &client,
+        json!({
+            "template": "{% for o in orders %}{{ o.id }}={{ o.lines | map(attribute='qty') | sum }};{% endfor %}{{ meta.region | upper }}",
+            "context": {
+                "orders": [
+                    { "id": "A1", "lines": [{ "qty": 2 }, { "qty": 1 }] },
+                    { "id": "B2", "lines": [{ "qty": 5 }] },
+                ],
+                "meta": { "region": "eu" },
+            },
+        }),
+    )
+    .await;
+    assert_ne!(result["isError"], true, "{}", text(&result));
+    assert_eq!(text(&result), "A1=3;B2=5;EU");
+
+    let tools = client.request("tools/list", json!({})).await;
+    let tool = tools["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["name"] == "render_template")
+        .unwrap()
+        .clone();
+    assert_eq!(
+        tool["inputSchema"]["properties"]["context"]["type"],
+        "object"
+    );
+}
+
+#[tokio::test]
+async fn test_undefined_modes_and_errors() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let cases = [
+        ("lenient", "<{{ missing }}>", Ok("<>")),
+        ("lenient", "{{ missing.field }}", Err("Render failed:")),
+        ("chainable", "<{{ missing.field }}>", Ok("<>")),
+        ("strict", "{{ missing }}", Err("Render failed:")),
+        ("strict", "{% if %}", Err("Invalid template:")),
+    ];
+    for (mode, template, expected) in cases {
+        let result = render(&client, json!({ "template": template, "undefined": mode })).await;
+        match expected {
+            Ok(rendered) => {
+                assert_ne!(result["isError"], true, "{mode} {template}");
+                assert_eq!(text(&result), rendered);
+            }
+            Err(prefix) => {
+                assert_eq!(result["isError"], true, "{mode} {template}");
+                assert!(text(&result).starts_with(prefix), "{}", text(&result));
+            }
+        }
+    }
+
+    let response = client
+        .request(
+            "tools/call",
+            json!({
+                "name": "render_template",
+                "arguments": { "template": "x", "undefined": "sloppy" },
+            }),
+        )
+        .await;
+    assert_eq!(response["error"]["code"], -32602);
+}
@@ -5,19 +5,41 @@
 //! (as they would be from MCP tool calls) and have valid JSON schemas.
 
 use mcp_test_server::tools::{
+    array::{DedupArrayParams, SortArrayParams, SortOrder},
+    compression::{
+        GzipCompressParams, GzipDecompressParams, ZstdCompressParams, ZstdDecompressParams,
+    },
+    convert::{JsonToYamlParams, TomlToJsonParams, YamlToJsonParams},
+    counter::{CounterIncrementParams, CounterResetParams, CounterScope},
+    csv::{CsvFormatParams, CsvParseParams},
+    datetime::{DateAddParams, DateDiffParams, DateUnit, TimezoneConvertParams},
     encoding::{
-        Base64DecodeParams, Base64EncodeParams, HashSha256Params, JsonParseParams,
-        JsonStringifyParams,
+        Base64DecodeParams, Base64EncodeParams, HashAlgorithm, HashCrc32Params, HashMd5Params,
+        HashParams, HashSha1Params, HashSha256Params, HashSha512Params, HmacSha256Params,
+        JsonParseParams, JsonStringifyParams, UrlDecodeParams, UrlEncodeParams, UrlParseParams,
     },
-    math::{AddParams, DivideParams, MultiplyParams, SubtractParams},
+    fetch::FetchUrlParams,
+    kv::{KvDeleteParams, KvGetParams, KvListParams, KvSetParams},
+    markdown::MarkdownToHtmlParams,
+    math::{AddParams, DivideParams, MultiplyParams, StatsParams, SubtractParams},
+    query::JsonQueryParams,
+    regex::{RegexExtractParams, RegexMatchParams, RegexReplaceParams},
+    scratch::{ResourceDeleteParams, ResourceWriteParams},
     string::{
-        ConcatParams, EchoParams, LengthParams, LowercaseParams, ReverseParams, UppercaseParams,
+        ConcatParams, EchoParams, LengthParams, LowercaseParams, ReverseParams, UnicodeInfoParams,
+        UppercaseParams, WordCountParams,
     },
+    template::{RenderTemplateParams, UndefinedMode},
     testing::{
-        BinaryDataParams, FailParams, FailWithMessageParams, LargeResponseParams, NestedDataParams,
-        SleepParams, SlowEchoParams,
+        BinaryDataParams, EdgeCaseMode, EdgeCaseTextParams, FailParams, FailWithMessageParams,
+        HugeResponseParams, LargeResponseParams, NestedDataParams, PanicMode, PanicParams,
+        SleepParams, SlowEchoParams, StreamLinesParams, huge_text,
+    },
+    utility::{
+        Charset, CurrentTimeParams, RandomChoiceParams, RandomNumberParams, RandomStringParams,
+        RandomUuidParams, RollDiceParams,
     },
-    utility::{CurrentTimeParams, RandomNumberParams, RandomUuidParams},
+    xml::{XmlParseParams, XmlQueryParams},
 };
 
 // Test that all param structs can be deserialized from JSON (as MCP would send them)
@@ -47,6 +69,13 @@ fn test_math_params_deserialization() {
     let params: DivideParams = serde_json::from_str(json).unwrap();
     assert!((params.a - 20.0).abs() < f64::EPSILON);
     assert!((params.b - 4.0).abs() < f64::EPSILON);
+
+    // StatsParams
+    let params: StatsParams = serde_json::from_str(r#"{"values": [1, 2.5]}"#).unwrap();
+    assert_eq!(params.values, [1.0, 2.5]);
+    assert_eq!(params.percentiles, [25.0, 50.0, 75.0, 90.0, 99.0]);
+    assert!(!params.sample);
+    assert!(serde_json::from_str::<StatsParams>(r#"{"values": [1, "NaN"]}"#).is_err());
 }
 
 #[test]
@@ -80,6 +109,13 @@ fn test_string_params_deserialization() {
     let json = r#"{"text": "hello world"}"#;
     let params: LengthParams = serde_json::from_str(json).unwrap();
     assert_eq!(params.text, "hello world");
+
+    // UnicodeInfoParams and WordCountParams
+    let json = r#"{"text": "e\u0301 🎉"}"#;
+    let params: UnicodeInfoParams = serde_json::from_str(json).unwrap();
+    assert_eq!(params.text, "e\u{301} 🎉");
+    let params: WordCountParams = serde_json::from_str(json).unwrap();
+    assert_eq!(params.text.chars().count(), 4);
 }
 
 #[test]
@@ -108,6 +144,36 @@ fn test_encoding_params_deserialization() {
     let json = r#"{"text": "test"}"#;
     let params: HashSha256Params = serde_json::from_str(json).unwrap();
     assert_eq!(params.text, "test");
+
+    // The per-algorithm hash params share the same shape
+    serde_json::from_str::<HashMd5Params>(json).unwrap();
+    serde_json::from_str::<HashSha1Params>(json).unwrap();
+    serde_json::from_str::<HashSha512Params>(json).unwrap();
+    serde_json::from_str::<HashCrc32Params>(json).unwrap();
+
+    // HashParams: algorithm is a lowercase enum
+    let params: HashParams =
+        serde_json::from_str(r#"{"text": "test", "algorithm": "sha512"}"#).unwrap();
+    assert_eq!(params.algorithm, HashAlgorithm::Sha512);
+    assert!(
+        serde_json::from_str::<HashParams>(r#"{"text": "test", "algorithm": "sha3"}"#).is_err()
+    );
+
+    // HmacSha256Params
+    let params: HmacSha256Params =
+        serde_json::from_str(r#"{"key": "secret", "text": "test"}"#).unwrap();
+    assert_eq!(params.key, "secret");
+
+    // UrlEncodeParams / UrlDecodeParams: form defaults to false
+    let params: UrlEncodeParams = serde_json::from_str(r#"{"text": "a b"}"#).unwrap();
+    assert!(!params.form);
+    let params: UrlDecodeParams =
+        serde_json::from_str(r#"{"encoded": "a+b", "form": true}"#).unwrap();
+    assert!(params.form);
+
+    // UrlParseParams
+    let params: UrlParseParams = serde_json::from_str(r#"{"url": "http://example.com"}"#).unwrap();
+    assert_eq!(params.url, "http://example.com");
 }
 
 #[test]
@@ -125,6 +191,268 @@ fn test_utility_params_deserialization() {
     // CurrentTimeParams - empty object
     let json = r"{}";
     let _params: CurrentTimeParams = serde_json::from_str(json).unwrap();
+
+    // Seeded random tools
+    let params: RandomStringParams = serde_json::from_str(r"{}").unwrap();
+    assert_eq!((params.length, params.charset), (16, Charset::Alphanumeric));
+    assert!(params.seed.is_none());
+    let params: RandomStringParams =
+        serde_json::from_str(r#"{"length": 8, "charset": "password", "seed": 5}"#).unwrap();
+    assert_eq!(params.charset, Charset::Password);
+    assert_eq!(params.seed, Some(5));
+    assert!(serde_json::from_str::<RandomStringParams>(r#"{"charset": "emoji"}"#).is_err());
+
+    let params: RollDiceParams = serde_json::from_str(r#"{"notation": "2d8+3"}"#).unwrap();
+    assert_eq!(params.notation, "2d8+3");
+
+    let params: RandomChoiceParams =
+        serde_json::from_str(r#"{"items": ["a", {"b": 1}, 2]}"#).unwrap();
+    assert_eq!(params.items.len(), 3);
+    assert_eq!(params.count, 1);
+    assert!(!params.unique);
+}
+
+#[test]
+fn test_kv_params_deserialization() {
+    let params: KvSetParams = serde_json::from_str(r#"{"key": "a", "value": "1"}"#).unwrap();
+    assert_eq!((params.key.as_str(), params.value.as_str()), ("a", "1"));
+
+    let params: KvGetParams = serde_json::from_str(r#"{"key": "a"}"#).unwrap();
+    assert_eq!(params.key, "a");
+
+    let params: KvDeleteParams = serde_json::from_str(r#"{"key": "a"}"#).unwrap();
+    assert_eq!(params.key, "a");
+
+    // KvListParams - prefix is optional
+    let params: KvListParams = serde_json::from_str(r"{}").unwrap();
+    assert_eq!(params.prefix, None);
+}
+
+#[test]
+fn test_counter_params_deserialization() {
+    // scope defaults to the session counter
+    let params: CounterIncrementParams = serde_json::from_str(r"{}").unwrap();
+    assert_eq!(params.scope, CounterScope::Session);
+
+    let params: CounterResetParams = serde_json::from_str(r#"{"scope": "server"}"#).unwrap();
+    assert_eq!(params.scope, CounterScope::Server);
+
+    assert!(serde_json::from_str::<CounterResetParams>(r#"{"scope": "global"}"#).is_err());
+}
+
+#[test]
+fn test_fetch_params_deserialization() {
+    let params: FetchUrlParams =
+        serde_json::from_str(r#"{"url": "http://mock.test/json"}"#).unwrap();
+    assert_eq!(params.url, "http://mock.test/json");
+    assert_eq!(params.method, None);
+    assert!(params.headers.is_empty());
+
+    let params: FetchUrlParams = serde_json::from_str(
+        r#"{"url": "http://mock.test/echo", "method": "POST", "headers": {"x-a": "1"}, "body": "hi"}"#,
+    )
+    .unwrap();
+    assert_eq!(params.method.as_deref(), Some("POST"));
+    assert_eq!(params.headers["x-a"], "1");
+    assert_eq!(params.body.as_deref(), Some("hi"));
+}
+
+#[test]
+fn test_regex_params_deserialization() {
+    let params: RegexMatchParams =
+        serde_json::from_str(r#"{"pattern": "\\d+", "text": "a1"}"#).unwrap();
+    assert_eq!(params.pattern, r"\d+");
+
+    // all defaults to true, limit to 100
+    let params: RegexReplaceParams =
+        serde_json::from_str(r#"{"pattern": "a", "text": "aa", "replacement": "b"}"#).unwrap();
+    assert!(params.all);
+    let params: RegexExtractParams =
+        serde_json::from_str(r#"{"pattern": "a", "text": "aa"}"#).unwrap();
+    assert_eq!(params.limit, 100);
+
+    assert!(
+        serde_json::from_str::<RegexReplaceParams>(r#"{"pattern": "a", "text": "a"}"#).is_err()
+    );
+}
+
+#[test]
+fn test_array_params_deserialization() {
+    let params: SortArrayParams = serde_json::from_str(r#"{"values": [3, "a", null]}"#).unwrap();
+    assert_eq!(params.values.len(), 3);
+    assert_eq!(params.order, SortOrder::Asc);
+    assert!(params.key.is_none());
+    let params: SortArrayParams =
+        serde_json::from_str(r#"{"values": [], "order": "desc", "key": "age"}"#).unwrap();
+    assert_eq!(params.order, SortOrder::Desc);
+    assert_eq!(params.key.as_deref(), Some("age"));
+    assert!(serde_json::from_str::<SortArrayParams>(r#"{"values": {}}"#).is_err());
+
+    let params: DedupArrayParams =
+        serde_json::from_str(r#"{"values": [{"a": 1}, [1], true]}"#).unwrap();
+    assert_eq!(params.values.len(), 3);
+}
+
+#[test]
+fn test_compression_params_deserialization() {
+    let params: GzipCompressParams = serde_json::from_str(r#"{"text": "hi"}"#).unwrap();
+    assert_eq!(params.level, 6);
+    let params: ZstdCompressParams =
+        serde_json::from_str(r#"{"text": "hi", "level": 19}"#).unwrap();
+    assert_eq!(params.level, 19);
+
+    let params: GzipDecompressParams = serde_json::from_str(r#"{"data": "H4sI"}"#).unwrap();
+    assert_eq!(params.data, "H4sI");
+    let params: ZstdDecompressParams = serde_json::from_str(r#"{"data": "KLUv"}"#).unwrap();
+    assert_eq!(params.data, "KLUv");
+
+    // gzip levels are unsigned
+    assert!(serde_json::from_str::<GzipCompressParams>(r#"{"text": "hi", "level": -1}"#).is_err());
+}
+
+#[test]
+fn test_convert_params_deserialization() {
+    let params: YamlToJsonParams = serde_json::from_str(r#"{"yaml": "a: 1"}"#).unwrap();
+    assert_eq!(params.yaml, "a: 1");
+
+    let params: JsonToYamlParams = serde_json::from_str(r#"{"value": {"a": [1]}}"#).unwrap();
+    assert_eq!(params.value["a"][0], 1);
+
+    let params: TomlToJsonParams = serde_json::from_str(r#"{"toml": "a = 1"}"#).unwrap();
+    assert_eq!(params.toml, "a = 1");
+
+    assert!(serde_json::from_str::<TomlToJsonParams>(r#"{"yaml": "a: 1"}"#).is_err());
+}
+
+#[test]
+fn test_hash_algorithm_schema_is_an_enum() {
+    let schema = serde_json::to_value(schemars::schema_for!(HashParams)).unwrap();
+    // Documented variants become one `const` each
+    let names: Vec<&str> = schema["$defs"]["HashAlgorithm"]["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|v| v["const"].as_str())
+        .collect();
+    assert_eq!(names, ["md5", "sha1", "sha256", "sha512", "crc32"]);
+}
+
+#[test]
+fn test_csv_params_deserialization() {
+    let params: CsvParseParams = serde_json::from_str(r#"{"text": "a,b"}"#).unwrap();
+    assert_eq!(params.delimiter, ",");
+    assert_eq!(params.quote, "\"");
+    assert!(params.has_headers);
+
+    let params: CsvFormatParams =
+        serde_json::from_str(r#"{"rows": [{"a": 1}], "delimiter": "\t"}"#).unwrap();
+    assert_eq!(params.rows.len(), 1);
+    assert_eq!(params.delimiter, "\t");
+    assert!(params.headers.is_none());
+
+    assert!(serde_json::from_str::<CsvFormatParams>(r#"{"rows": {"a": 1}}"#).is_err());
+}
+
+#[test]
+fn test_xml_params_deserialization() {
+    let params: XmlParseParams = serde_json::from_str(r#"{"xml": "<a/>"}"#).unwrap();
+    assert_eq!(params.xml, "<a/>");
+
+    let params: XmlQueryParams = serde_json::from_str(r#"{"xml": "<a/>", "xpath": "/a"}"#).unwrap();
+    assert!(params.namespaces.is_empty());
+    let params: XmlQueryParams =
+        serde_json::from_str(r#"{"xml": "<a/>", "xpath": "//p:b", "namespaces": {"p": "urn:p"}}"#)
+            .unwrap();
+    assert_eq!(params.namespaces["p"], "urn:p");
+
+    assert!(serde_json::from_str::<XmlQueryParams>(r#"{"xml": "<a/>"}"#).is_err());
+}
+
+#[test]
+fn test_query_params_deserialization() {
+    let params: JsonQueryParams =
+        serde_json::from_str(r#"{"document": {"a": [1, 2]}, "path": "$.a[0]"}"#).unwrap();
+    assert_eq!(params.document["a"][1], 2);
+    assert_eq!(params.path, "$.a[0]");
+
+    // Any JSON value is a document
+    let params: JsonQueryParams =
+        serde_json::from_str(r#"{"document": [null, "x"], "path": "$"}"#).unwrap();
+    assert!(params.document.is_array());
+    assert!(serde_json::from_str::<JsonQueryParams>(r#"{"path": "$"}"#).is_err());
+}
+
+#[test]
+fn test_markdown_params_deserialization() {
+    let params: MarkdownToHtmlParams = serde_json::from_str(r##"{"markdown": "# hi"}"##).unwrap();
+    assert_eq!(params.markdown, "# hi");
+    assert!(params.sanitize);
+    let params: MarkdownToHtmlParams =
+        serde_json::from_str(r#"{"markdown": "", "sanitize": false}"#).unwrap();
+    assert!(!params.sanitize);
+}
+
+#[test]
+fn test_template_params_deserialization() {
+    let params: RenderTemplateParams = serde_json::from_str(r#"{"template": "hi"}"#).unwrap();
+    assert!(params.context.is_empty());
+    assert_eq!(params.undefined, UndefinedMode::Lenient);
+
+    let params: RenderTemplateParams = serde_json::from_str(
+        r#"{"template": "{{ a.b }}", "context": {"a": {"b": [1, 2]}}, "undefined": "strict"}"#,
+    )
+    .unwrap();
+    assert_eq!(params.context["a"]["b"][1], 2);
+    assert_eq!(params.undefined, UndefinedMode::Strict);
+
+    // The context must be an object
+    assert!(
+        serde_json::from_str::<RenderTemplateParams>(r#"{"template": "x", "context": [1]}"#)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_datetime_params_deserialization() {
+    let params: DateAddParams = serde_json::from_str(
+        r#"{"timestamp": "2024-01-31T00:00:00Z", "amount": -3, "unit": "months"}"#,
+    )
+    .unwrap();
+    assert_eq!(params.amount, -3);
+    assert_eq!(params.unit, DateUnit::Months);
+
+    // unit defaults to seconds
+    let params: DateDiffParams = serde_json::from_str(r#"{"start": "a", "end": "b"}"#).unwrap();
+    assert_eq!(params.unit, DateUnit::Seconds);
+
+    let params: TimezoneConvertParams =
+        serde_json::from_str(r#"{"timestamp": "a", "timezone": "Asia/Tokyo"}"#).unwrap();
+    assert_eq!(params.timezone, "Asia/Tokyo");
+
+    // Units outside the enum are rejected
+    assert!(
+        serde_json::from_str::<DateAddParams>(
+            r#"{"timestamp": "a", "amount": 1, "unit": "fortnights"}"#
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_scratch_params_deserialization() {
+    let params: ResourceWriteParams =
+        serde_json::from_str(r#"{"name": "notes.txt", "text": "hi"}"#).unwrap();
+    assert_eq!(params.name, "notes.txt");
+    assert_eq!(params.mime_type, None);
+
+    let params: ResourceWriteParams = serde_json::from_str(
+        r#"{"name": "data.json", "text": "{}", "mime_type": "application/json"}"#,
+    )
+    .unwrap();
+    assert_eq!(params.mime_type.as_deref(), Some("application/json"));
+
+    let params: ResourceDeleteParams = serde_json::from_str(r#"{"name": "notes.txt"}"#).unwrap();
+    assert_eq!(params.name, "notes.txt");
 }
 
 #[test]
@@ -149,6 +477,14 @@ fn test_testing_params_deserialization() {
     assert_eq!(params.text, "hello");
     assert_eq!(params.delay_ms, 50);
 
+    // StreamLinesParams
+    let params: StreamLinesParams = serde_json::from_str(r"{}").unwrap();
+    assert_eq!((params.count, params.interval_ms), (10, 100));
+    assert_eq!(params.text, "line");
+    let params: StreamLinesParams =
+        serde_json::from_str(r#"{"count": 3, "interval_ms": 0, "text": "row"}"#).unwrap();
+    assert_eq!((params.count, params.interval_ms), (3, 0));
+
     // NestedDataParams
     let json = r#"{"depth": 5}"#;
     let params: NestedDataParams = serde_json::from_str(json).unwrap();
@@ -159,6 +495,27 @@ fn test_testing_params_deserialization() {
     let params: LargeResponseParams = serde_json::from_str(json).unwrap();
     assert_eq!(params.size_bytes, 1024);
 
+    // HugeResponseParams
+    let params: HugeResponseParams = serde_json::from_str(r#"{"size_bytes": 10}"#).unwrap();
+    assert_eq!(params.size_bytes, 10);
+    assert_eq!(huge_text(10).len(), 10);
+    assert_eq!(huge_text(0), "");
+
+    // EdgeCaseTextParams
+    let params: EdgeCaseTextParams = serde_json::from_str(r#"{"mode": "long_line"}"#).unwrap();
+    assert_eq!(
+        (params.mode, params.length),
+        (EdgeCaseMode::LongLine, 100_000)
+    );
+    assert!(serde_json::from_str::<EdgeCaseTextParams>(r#"{"mode": "emoji"}"#).is_err());
+
+    // PanicParams
+    let params: PanicParams = serde_json::from_str(r"{}").unwrap();
+    assert_eq!(params.mode, PanicMode::Panic);
+    assert_eq!(params.message, "Simulated panic");
+    let params: PanicParams = serde_json::from_str(r#"{"mode": "abort_connection"}"#).unwrap();
+    assert_eq!(params.mode, PanicMode::AbortConnection);
+
     // BinaryDataParams
     let json = r#"{"size_bytes": 256}"#;
     let params: BinaryDataParams = serde_json::from_str(json).unwrap();
@@ -174,26 +531,75 @@ fn test_params_have_json_schema() {
     let _ = schema_for!(SubtractParams);
     let _ = schema_for!(MultiplyParams);
     let _ = schema_for!(DivideParams);
+    let _ = schema_for!(StatsParams);
     let _ = schema_for!(EchoParams);
     let _ = schema_for!(ConcatParams);
     let _ = schema_for!(UppercaseParams);
     let _ = schema_for!(LowercaseParams);
     let _ = schema_for!(ReverseParams);
     let _ = schema_for!(LengthParams);
+    let _ = schema_for!(UnicodeInfoParams);
+    let _ = schema_for!(WordCountParams);
     let _ = schema_for!(JsonParseParams);
     let _ = schema_for!(JsonStringifyParams);
     let _ = schema_for!(Base64EncodeParams);
     let _ = schema_for!(Base64DecodeParams);
     let _ = schema_for!(HashSha256Params);
+    let _ = schema_for!(HashMd5Params);
+    let _ = schema_for!(HashSha1Params);
+    let _ = schema_for!(HashSha512Params);
+    let _ = schema_for!(HashCrc32Params);
+    let _ = schema_for!(HmacSha256Params);
+    let _ = schema_for!(UrlEncodeParams);
+    let _ = schema_for!(UrlDecodeParams);
+    let _ = schema_for!(UrlParseParams);
     let _ = schema_for!(RandomNumberParams);
+    let _ = schema_for!(RandomStringParams);
+    let _ = schema_for!(RollDiceParams);
+    let _ = schema_for!(RandomChoiceParams);
     let _ = schema_for!(RandomUuidParams);
     let _ = schema_for!(CurrentTimeParams);
+    let _ = schema_for!(KvSetParams);
+    let _ = schema_for!(KvGetParams);
+    let _ = schema_for!(KvDeleteParams);
+    let _ = schema_for!(KvListParams);
+    let _ = schema_for!(CounterIncrementParams);
+    let _ = schema_for!(CounterResetParams);
+    let _ = schema_for!(ResourceWriteParams);
+    let _ = schema_for!(ResourceDeleteParams);
+    let _ = schema_for!(FetchUrlParams);
+    let _ = schema_for!(RegexMatchParams);
+    let _ = schema_for!(RegexReplaceParams);
+    let _ = schema_for!(RegexExtractParams);
+    let _ = schema_for!(SortArrayParams);
+    let _ = schema_for!(DedupArrayParams);
+    let _ = schema_for!(GzipCompressParams);
+    let _ = schema_for!(GzipDecompressParams);
+    let _ = schema_for!(ZstdCompressParams);
+    let _ = schema_for!(ZstdDecompressParams);
+    let _ = schema_for!(YamlToJsonParams);
+    let _ = schema_for!(JsonToYamlParams);
+    let _ = schema_for!(TomlToJsonParams);
+    let _ = schema_for!(CsvParseParams);
+    let _ = schema_for!(CsvFormatParams);
+    let _ = schema_for!(XmlParseParams);
+    let _ = schema_for!(XmlQueryParams);
+    let _ = schema_for!(JsonQueryParams);
+    let _ = schema_for!(MarkdownToHtmlParams);
+    let _ = schema_for!(RenderTemplateParams);
+    let _ = schema_for!(DateAddParams);
+    let _ = schema_for!(DateDiffParams);
+    let _ = schema_for!(TimezoneConvertParams);
     let _ = schema_for!(SleepParams);
     let _ = schema_for!(FailParams);
     let _ = schema_for!(FailWithMessageParams);
     let _ = schema_for!(SlowEchoParams);
+    let _ = schema_for!(StreamLinesParams);
     let _ = schema_for!(NestedDataParams);
     let _ = schema_for!(LargeResponseParams);
+    let _ = schema_for!(HugeResponseParams);
+    let _ = schema_for!(EdgeCaseTextParams);
+    let _ = schema_for!(PanicParams);
     let _ = schema_for!(BinaryDataParams);
 }
 
@@ -222,3 +628,20 @@ fn test_parameterless_structs_accept_extra_fields() {
     let _: CurrentTimeParams = serde_json::from_str(json).unwrap();
     let _: FailParams = serde_json::from_str(json).unwrap();
 }
+
+#[test]
+fn test_fixture_params_deserialization() {
+    use mcp_test_server::tools::fixtures::{FixtureGenerateParams, FixtureKind};
+
+    let params: FixtureGenerateParams = serde_json::from_str(r#"{"kind": "people"}"#).unwrap();
+    assert_eq!(params.kind, FixtureKind::People);
+    assert_eq!(params.count, 10);
+    assert_eq!(params.seed, None);
+
+    let params: FixtureGenerateParams =
+        serde_json::from_str(r#"{"kind": "products", "count": 3, "seed": 42}"#).unwrap();
+    assert_eq!(params.kind, FixtureKind::Products);
+    assert_eq!(params.seed, Some(42));
+
+    assert!(serde_json::from_str::<FixtureGenerateParams>(r#"{"kind": "planets"}"#).is_err());
+}
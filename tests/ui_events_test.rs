@@ -0,0 +1,83 @@
+//! The MCP App message log: messages reported with `ui_record_event` are
+//! served by `test://dynamic/ui-events`.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::{Value, json};
+
+#[tokio::test]
+async fn test_recorded_messages_are_readable() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    for (method, id, params) in [
+        (
+            "ui/initialize",
+            json!(1),
+            json!({ "protocolVersion": "2026-01-26" }),
+        ),
+        (
+            "ui/open-link",
+            json!(2),
+            json!({ "url": "https://example.com" }),
+        ),
+    ] {
+        let result = client
+            .request(
+                "tools/call",
+                json!({
+                    "name": "ui_record_event",
+                    "arguments": { "app": "Dashboard", "method": method, "id": id, "params": params },
+                }),
+            )
+            .await;
+        assert_ne!(result["result"]["isError"], true, "{result}");
+    }
+    client
+        .request(
+            "tools/call",
+            json!({
+                "name": "ui_record_event",
+                "arguments": { "app": "Dashboard", "method": "ui/notifications/size-changed" },
+            }),
+        )
+        .await;
+
+    let read = client
+        .request(
+            "resources/read",
+            json!({ "uri": "test://dynamic/ui-events" }),
+        )
+        .await;
+    let contents = &read["result"]["contents"][0];
+    assert_eq!(contents["mimeType"], "application/json");
+    let log: Value = serde_json::from_str(contents["text"].as_str().unwrap()).unwrap();
+    assert_eq!(log["total"], 3);
+    let events = log["events"].as_array().unwrap();
+    let methods: Vec<_> = events
+        .iter()
+        .map(|e| e["method"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        methods,
+        [
+            "ui/initialize",
+            "ui/open-link",
+            "ui/notifications/size-changed"
+        ]
+    );
+    assert_eq!(events[1]["params"]["url"], "https://example.com");
+    assert_eq!(events[1]["seq"], 2);
+    assert!(events[2].get("id").is_none());
+
+    let list = client.request("resources/list", json!({})).await;
+    let resources = list["result"]["resources"].as_array().unwrap();
+    assert!(
+        resources
+            .iter()
+            .any(|r| r["uri"] == "test://dynamic/ui-events")
+    );
+}
@@ -0,0 +1,60 @@
+//! Unicode tool tests: multi-byte and emoji text survives transport, and
+//! `unicode_info`/`word_count` report what a reader would count.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::{Value, json};
+
+async fn call(client: &McpClient, name: &str, text: &str) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": name, "arguments": { "text": text } }),
+        )
+        .await["result"]
+        .clone()
+}
+
+#[tokio::test]
+async fn test_unicode_info_over_transport() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    // Decomposed é, a ZWJ family emoji, and a flag
+    let text = "e\u{301}👨‍👩‍👧🇯🇵";
+    let result = call(&client, "unicode_info", text).await;
+    let info = &result["structuredContent"];
+    assert_eq!(info["bytes"], text.len());
+    assert_eq!(info["codepoints"], 9);
+    assert_eq!(info["graphemes"], 3);
+    assert_eq!(info["scalars"][6], "U+1F467");
+    assert_eq!(info["normalization"]["nfc"]["codepoints"], 8);
+    assert_eq!(info["normalization"]["nfd"]["is_normalized"], true);
+    assert!(
+        info["normalization"]["nfc"]["text"]
+            .as_str()
+            .unwrap()
+            .starts_with('\u{e9}')
+    );
+
+    // length reports bytes, for contrast
+    let result = call(&client, "length", text).await;
+    assert_eq!(result["content"][0]["text"], text.len().to_string());
+}
+
+#[tokio::test]
+async fn test_word_count_over_transport() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let result = call(&client, "word_count", "Grüße, señor!\n🎉 naïve café\n").await;
+    assert_eq!(
+        result["structuredContent"],
+        json!({ "words": 4, "lines": 2, "graphemes": 27, "bytes": 35 })
+    );
+}
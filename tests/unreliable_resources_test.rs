@@ -0,0 +1,56 @@
+//! Slow and flaky resource reads over MCP.
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{McpClient, TestServer};
+use serde_json::{Value, json};
+
+async fn read(client: &McpClient, uri: &str) -> Value {
+    client
+        .request("resources/read", json!({ "uri": uri }))
+        .await
+}
+
+#[tokio::test]
+async fn test_slow_read_takes_delay_ms() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let started = Instant::now();
+    let response = read(&client, "test://dynamic/slow?delay_ms=200").await;
+    assert!(started.elapsed() >= Duration::from_millis(200));
+    assert_eq!(
+        response["result"]["contents"][0]["text"],
+        "Read after 200 ms"
+    );
+
+    let response = read(&client, "test://dynamic/slow?delay_ms=600000").await;
+    assert_eq!(response["error"]["code"], -32602);
+    let response = read(&client, "test://dynamic/slow?delay=5").await;
+    assert_eq!(response["error"]["code"], -32602);
+}
+
+#[tokio::test]
+async fn test_flaky_read_fails_with_resource_not_found() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let response = read(&client, "test://dynamic/flaky?fail_rate=1").await;
+    assert_eq!(response["error"]["code"], -32002);
+    assert_eq!(
+        response["error"]["data"]["uri"],
+        "test://dynamic/flaky?fail_rate=1"
+    );
+
+    let response = read(&client, "test://dynamic/flaky?fail_rate=0").await;
+    assert_eq!(
+        response["result"]["contents"][0]["text"],
+        "Read succeeded (fail_rate 0)"
+    );
+}
@@ -0,0 +1,95 @@
+//! URL tool tests: percent-encoding round trips and structured `url_parse`
+//! output with a nested query map.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::{Value, json};
+
+async fn call(client: &McpClient, name: &str, arguments: Value) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": name, "arguments": arguments }),
+        )
+        .await["result"]
+        .clone()
+}
+
+fn text(result: &Value) -> &str {
+    result["content"][0]["text"].as_str().unwrap()
+}
+
+#[tokio::test]
+async fn test_url_encode_decode_round_trip() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    for form in [false, true] {
+        let original = "name=Zoë & co/?#";
+        let encoded = call(
+            &client,
+            "url_encode",
+            json!({ "text": original, "form": form }),
+        )
+        .await;
+        let encoded = text(&encoded).to_string();
+        assert!(encoded.is_ascii(), "{encoded}");
+        assert!(!encoded.contains(['&', '=', '/', '?', '#']), "{encoded}");
+
+        let decoded = call(
+            &client,
+            "url_decode",
+            json!({ "encoded": encoded, "form": form }),
+        )
+        .await;
+        assert_eq!(text(&decoded), original);
+    }
+
+    let result = call(&client, "url_decode", json!({ "encoded": "%C3" })).await;
+    assert_eq!(result["isError"], true);
+}
+
+#[tokio::test]
+async fn test_url_parse_structured_components() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let result = call(
+        &client,
+        "url_parse",
+        json!({ "url": "http://mock.test:8080/api/v1/items?sort=name&id=1&id=2#page-2" }),
+    )
+    .await;
+    assert_eq!(
+        result["structuredContent"],
+        json!({
+            "scheme": "http",
+            "username": null,
+            "password": null,
+            "host": "mock.test",
+            "port": 8080,
+            "path": "/api/v1/items",
+            "query": { "id": ["1", "2"], "sort": ["name"] },
+            "fragment": "page-2",
+        })
+    );
+
+    let tools = client.request("tools/list", json!({})).await;
+    let tool = tools["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["name"] == "url_parse")
+        .unwrap()
+        .clone();
+    assert_eq!(tool["outputSchema"]["type"], "object");
+
+    let result = call(&client, "url_parse", json!({ "url": "not a url" })).await;
+    assert_eq!(result["isError"], true);
+    assert!(text(&result).starts_with("Invalid URL:"));
+}
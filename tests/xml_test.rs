@@ -0,0 +1,101 @@
+//! XML tool tests: element trees, `XPath` node-sets and scalars, and
+//! malformed-XML error responses.
+
+mod common;
+
+use common::{McpClient, TestServer};
+use serde_json::{Value, json};
+
+const ORDERS: &str = r#"<orders xmlns="urn:orders">
+  <order id="A1" status="shipped"><line sku="x">2</line><line sku="y">1</line></order>
+  <order id="B2" status="open"><line sku="x">5</line></order>
+</orders>"#;
+
+async fn call(client: &McpClient, name: &str, arguments: Value) -> Value {
+    client
+        .request(
+            "tools/call",
+            json!({ "name": name, "arguments": arguments }),
+        )
+        .await["result"]
+        .clone()
+}
+
+#[tokio::test]
+async fn test_xml_parse_and_query() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let result = call(&client, "xml_parse", json!({ "xml": ORDERS })).await;
+    let root = &result["structuredContent"];
+    assert_eq!(root["name"], "orders");
+    assert_eq!(root["namespace"], "urn:orders");
+    assert_eq!(root["children"][0]["attributes"]["status"], "shipped");
+    assert_eq!(
+        root["children"][0]["children"][1]["children"][0]["text"],
+        "1"
+    );
+
+    let result = call(
+        &client,
+        "xml_query",
+        json!({
+            "xml": ORDERS,
+            "xpath": "//o:order[@status='open']/@id",
+            "namespaces": { "o": "urn:orders" },
+        }),
+    )
+    .await;
+    assert_eq!(
+        result["structuredContent"],
+        json!({
+            "result_type": "nodeset",
+            "nodes": [{ "kind": "attribute", "name": "id", "value": "B2" }],
+            "value": null,
+        })
+    );
+
+    let result = call(
+        &client,
+        "xml_query",
+        json!({
+            "xml": ORDERS,
+            "xpath": "sum(//o:line[@sku='x'])",
+            "namespaces": { "o": "urn:orders" },
+        }),
+    )
+    .await;
+    assert_eq!(result["structuredContent"]["result_type"], "number");
+    assert_eq!(result["structuredContent"]["value"], 7.0);
+}
+
+#[tokio::test]
+async fn test_malformed_xml_is_a_tool_error() {
+    common::init_test_tracing();
+
+    let server = TestServer::start().await;
+    let client = McpClient::connect(&server).await;
+
+    let cases = [
+        ("xml_parse", json!({ "xml": "<a><b></a>" }), "Invalid XML:"),
+        ("xml_parse", json!({ "xml": "not xml" }), "Invalid XML:"),
+        (
+            "xml_query",
+            json!({ "xml": "<a>&undefined;</a>", "xpath": "/a" }),
+            "Invalid XML:",
+        ),
+        (
+            "xml_query",
+            json!({ "xml": "<a/>", "xpath": "/a[" }),
+            "Invalid XPath:",
+        ),
+    ];
+    for (name, arguments, expected) in cases {
+        let result = call(&client, name, arguments).await;
+        assert_eq!(result["isError"], true, "{name}");
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.starts_with(expected), "{name}: {text}");
+    }
+}
@@ -6,12 +6,19 @@
  *
  * Protocol spec: https://modelcontextprotocol.io/docs/extensions/apps
  * Protocol version: 2026-01-26
+ *
+ * Every message the app posts is also reported to the server through the
+ * app-only `ui_record_event` tool, so hosts can read what the iframe sent
+ * from `test://dynamic/ui-events`. Reports wait for `ui/initialize` to
+ * finish and are not reported themselves.
  */
 class McpApp {
   #nextId = 1;
   #pending = new Map();
   #name;
   #version;
+  #initialized = false;
+  #reports = [];
 
   constructor(name, version) {
     this.#name = name;
@@ -31,6 +38,10 @@ class McpApp {
       protocolVersion: '2026-01-26',
     });
     this.#notify('ui/notifications/initialized', {});
+    this.#initialized = true;
+    var queued = this.#reports;
+    this.#reports = [];
+    queued.forEach((report) => this.#sendReport(report));
     return result;
   }
 
@@ -38,14 +49,56 @@ class McpApp {
     return this.#request('tools/call', { name: name, arguments: args || {} });
   }
 
+  async readServerResource(uri) {
+    return this.#request('resources/read', { uri: uri });
+  }
+
+  /**
+   * Describe a failed tool call: the text of an `isError` result, or the
+   * code and message of a JSON-RPC error. Returns null for a success.
+   */
+  static describeError(resultOrError) {
+    if (!resultOrError) return null;
+    if (resultOrError.isError) {
+      var first = resultOrError.content && resultOrError.content[0];
+      return 'Tool error: ' + ((first && first.text) || 'isError result');
+    }
+    if (typeof resultOrError.code === 'number') {
+      return 'JSON-RPC error ' + resultOrError.code + ': ' + resultOrError.message;
+    }
+    return null;
+  }
+
   sendSizeChanged(width, height) {
     this.#notify('ui/notifications/size-changed', { width: width, height: height });
   }
 
+  /** Report the document size now and whenever it changes. */
+  autoResize() {
+    var send = () => this.sendSizeChanged(
+      document.documentElement.scrollWidth, document.documentElement.scrollHeight);
+    send();
+    if (typeof ResizeObserver !== 'undefined') {
+      new ResizeObserver(send).observe(document.documentElement);
+    }
+  }
+
   async sendMessage(content) {
     return this.#request('ui/message', { role: 'user', content: content });
   }
 
+  async openLink(url) {
+    return this.#request('ui/open-link', { url: url });
+  }
+
+  /** Persist `state` with the host, which may show it to the model. */
+  async saveState(state) {
+    return this.#request('ui/update-model-context', {
+      content: [{ type: 'text', text: JSON.stringify(state) }],
+      structuredContent: state,
+    });
+  }
+
   #handleMessage(event) {
     var msg = event.data;
     if (!msg || msg.jsonrpc !== '2.0') return;
@@ -78,11 +131,26 @@ class McpApp {
     return new Promise(function(resolve, reject) {
       var id = self.#nextId++;
       self.#pending.set(id, { resolve: resolve, reject: reject });
-      window.parent.postMessage({ jsonrpc: '2.0', id: id, method: method, params: params }, '*');
+      self.#post({ jsonrpc: '2.0', id: id, method: method, params: params });
     });
   }
 
   #notify(method, params) {
-    window.parent.postMessage({ jsonrpc: '2.0', method: method, params: params }, '*');
+    this.#post({ jsonrpc: '2.0', method: method, params: params });
+  }
+
+  #post(msg) {
+    window.parent.postMessage(msg, '*');
+    var isReport = msg.method === 'tools/call' && msg.params && msg.params.name === 'ui_record_event';
+    if (isReport) return;
+    var report = { app: this.#name, method: msg.method, params: msg.params };
+    if (msg.id != null) report.id = msg.id;
+    if (this.#initialized) this.#sendReport(report);
+    else this.#reports.push(report);
+  }
+
+  #sendReport(report) {
+    // The log is best effort: a host refusing the call must not break the app
+    this.#request('tools/call', { name: 'ui_record_event', arguments: report }).catch(function() {});
   }
 }